@@ -0,0 +1,21 @@
+//! Records the git commit the binary is built from as `JUST_MCP_GIT_HASH`
+//!
+//! Builds outside a git checkout, such as from a crates.io package, leave it
+//! unset.
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    if let Some(hash) = hash.filter(|hash| !hash.is_empty()) {
+        println!("cargo:rustc-env=JUST_MCP_GIT_HASH={hash}");
+    }
+}
@@ -0,0 +1,29 @@
+//! Feed arbitrary justfile content to every in-process parser
+//!
+//! Parsers may reject the input, but must not panic or hang.
+
+#![no_main]
+
+use just_mcp::parser::{ASTJustParser, EnhancedJustfileParser, JustfileParser, ParserPreference};
+use libfuzzer_sys::fuzz_target;
+use std::cell::RefCell;
+
+thread_local! {
+    static AST_PARSER: RefCell<ASTJustParser> = RefCell::new(ASTJustParser::new().unwrap());
+    static ENHANCED_PARSER: EnhancedJustfileParser =
+        EnhancedJustfileParser::new_with_preference(ParserPreference::Ast).unwrap();
+}
+
+fuzz_target!(|content: &str| {
+    let _ = JustfileParser::new().unwrap().parse_content(content);
+
+    AST_PARSER.with_borrow_mut(|parser| {
+        if let Ok(tree) = parser.parse_content(content) {
+            let _ = parser.extract_recipes(&tree);
+        }
+    });
+
+    ENHANCED_PARSER.with(|parser| {
+        let _ = parser.parse_content(content);
+    });
+});
@@ -0,0 +1,45 @@
+//! Look for parameter values that pass validation but reach the shell unsafely
+//!
+//! The input is split at the first NUL into a parameter name and value.
+//! Validation may reject anything; what it accepts must be free of the
+//! injection patterns it claims to block, and sanitized names and values
+//! must keep their guarantees.
+
+#![no_main]
+
+use just_mcp::security::SecurityValidator;
+use libfuzzer_sys::fuzz_target;
+use std::collections::HashMap;
+
+fuzz_target!(|input: &str| {
+    let validator = SecurityValidator::with_default();
+    let (name, value) = input.split_once('\0').unwrap_or(("value", input));
+
+    if validator.validate_parameter(name, value).is_ok() {
+        for pattern in [";", "&", "|", "$(", "`", "../", "..\\", "\0"] {
+            assert!(!value.contains(pattern), "accepted {value:?}");
+        }
+    }
+    if validator.validate_task_name(value).is_ok() {
+        assert!(value
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-'));
+    }
+
+    let escaped = validator.sanitize_parameter(value);
+    assert!(
+        (escaped.starts_with('\'') && escaped.ends_with('\''))
+            || escaped
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_=/,.+".contains(c)),
+        "unquoted metacharacters in {escaped:?}"
+    );
+
+    let sanitized = validator.sanitize_parameter_name(name);
+    let pattern = regex::Regex::new(r"^[a-zA-Z0-9_][a-zA-Z0-9_.-]{0,63}$").unwrap();
+    assert!(pattern.is_match(&sanitized), "{name:?} -> {sanitized:?}");
+
+    if let Ok(parameters) = serde_json::from_str::<HashMap<String, serde_json::Value>>(input) {
+        let _ = validator.validate_parameters(&parameters);
+    }
+});
@@ -0,0 +1,464 @@
+//! Access log of MCP sessions
+//!
+//! When several clients share a server over `--transport`, operators need
+//! to know who connected and what they did. The access log records, for
+//! each MCP session, the client that introduced itself in `initialize`, the
+//! tools it called and the bytes exchanged. It describes traffic, not
+//! recipe output, and is kept apart from the execution logs.
+//!
+//! Statistics for live and recently closed sessions are kept in memory and
+//! returned by the `_admin_access_log` tool. With `--access-log PATH` every
+//! event is also appended to `PATH` as a JSON line; once the file exceeds
+//! its size limit it is rotated to `PATH.1`, `PATH.2`, ... and the oldest
+//! file beyond the configured count is removed.
+
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Size at which the access log file is rotated
+pub const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Rotated access log files kept next to the current one
+pub const DEFAULT_MAX_FILES: usize = 5;
+
+/// Closed sessions whose statistics stay queryable
+const MAX_CLOSED_SESSIONS: usize = 100;
+
+/// Most recent events kept in memory for queries
+const MAX_RECENT_EVENTS: usize = 1000;
+
+/// Where and how much access log to keep on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessLogConfig {
+    /// File events are appended to; `None` keeps them in memory only
+    pub path: Option<PathBuf>,
+    /// Size in bytes after which the file is rotated
+    pub max_bytes: u64,
+    /// Number of rotated files to keep
+    pub max_files: usize,
+}
+
+impl Default for AccessLogConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            max_bytes: DEFAULT_MAX_BYTES,
+            max_files: DEFAULT_MAX_FILES,
+        }
+    }
+}
+
+/// Something a session did
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AccessEvent {
+    /// A client connected
+    Connect,
+    /// The client introduced itself
+    Initialize {
+        client_name: String,
+        client_version: String,
+        protocol_version: String,
+    },
+    /// A `tools/call` request and its response
+    ToolCall {
+        tool: String,
+        bytes_in: usize,
+        bytes_out: usize,
+        duration_ms: u64,
+        is_error: bool,
+    },
+    /// The session ended
+    Disconnect,
+}
+
+/// One line of the access log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessEntry {
+    pub timestamp: DateTime<Utc>,
+    pub session: String,
+    pub transport: String,
+    #[serde(flatten)]
+    pub event: AccessEvent,
+}
+
+/// Traffic of one session
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionStats {
+    pub session: String,
+    pub transport: String,
+    pub client_name: Option<String>,
+    pub client_version: Option<String>,
+    pub connected_at: DateTime<Utc>,
+    pub last_activity: DateTime<Utc>,
+    /// Set once the session has ended
+    pub disconnected_at: Option<DateTime<Utc>>,
+    pub messages_in: u64,
+    pub messages_out: u64,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    /// Number of calls per tool
+    pub tool_calls: BTreeMap<String, u64>,
+    /// Tool calls that failed
+    pub tool_errors: u64,
+}
+
+/// Answer to an access log query
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessLogReport {
+    /// Matching sessions, live ones first
+    pub sessions: Vec<SessionStats>,
+    /// Most recent matching events, oldest first
+    pub recent_events: Vec<AccessEntry>,
+    /// File events are written to, if any
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Live sessions, then closed ones in the order they ended
+    sessions: Vec<SessionStats>,
+    recent: VecDeque<AccessEntry>,
+    file: Option<File>,
+    file_len: u64,
+}
+
+/// Per-session access log shared by every transport
+#[derive(Debug, Default)]
+pub struct AccessLog {
+    config: AccessLogConfig,
+    state: Mutex<State>,
+}
+
+impl AccessLog {
+    /// Create an access log, opening its file for appending if configured
+    pub fn open(config: AccessLogConfig) -> Result<Self> {
+        let mut state = State::default();
+        if let Some(path) = &config.path {
+            let file = open_append(path)?;
+            state.file_len = file.metadata()?.len();
+            state.file = Some(file);
+        }
+        Ok(Self {
+            config,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Start tracking a session
+    pub fn open_session(&self, session: &str, transport: &str) {
+        let now = Utc::now();
+        {
+            let mut state = self.state.lock().unwrap();
+            let live = state
+                .sessions
+                .iter()
+                .filter(|s| s.disconnected_at.is_none());
+            let position = live.count();
+            state.sessions.insert(
+                position,
+                SessionStats {
+                    session: session.to_string(),
+                    transport: transport.to_string(),
+                    client_name: None,
+                    client_version: None,
+                    connected_at: now,
+                    last_activity: now,
+                    disconnected_at: None,
+                    messages_in: 0,
+                    messages_out: 0,
+                    bytes_in: 0,
+                    bytes_out: 0,
+                    tool_calls: BTreeMap::new(),
+                    tool_errors: 0,
+                },
+            );
+        }
+        self.record(session, AccessEvent::Connect);
+    }
+
+    /// Count a message received from (`inbound`) or sent to a session
+    pub fn record_message(&self, session: &str, inbound: bool, bytes: usize) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(stats) = live_session(&mut state, session) {
+            stats.last_activity = Utc::now();
+            if inbound {
+                stats.messages_in += 1;
+                stats.bytes_in += bytes as u64;
+            } else {
+                stats.messages_out += 1;
+                stats.bytes_out += bytes as u64;
+            }
+        }
+    }
+
+    /// Record an event of a live session
+    pub fn record(&self, session: &str, event: AccessEvent) {
+        let mut state = self.state.lock().unwrap();
+        let Some(stats) = live_session(&mut state, session) else {
+            return;
+        };
+        let timestamp = Utc::now();
+        stats.last_activity = timestamp;
+        match &event {
+            AccessEvent::Initialize {
+                client_name,
+                client_version,
+                ..
+            } => {
+                stats.client_name = Some(client_name.clone());
+                stats.client_version = Some(client_version.clone());
+            }
+            AccessEvent::ToolCall { tool, is_error, .. } => {
+                *stats.tool_calls.entry(tool.clone()).or_default() += 1;
+                if *is_error {
+                    stats.tool_errors += 1;
+                }
+            }
+            AccessEvent::Connect | AccessEvent::Disconnect => {}
+        }
+
+        let entry = AccessEntry {
+            timestamp,
+            session: session.to_string(),
+            transport: stats.transport.clone(),
+            event,
+        };
+        if let Err(e) = self.write(&mut state, &entry) {
+            tracing::warn!("Failed to write access log: {}", e);
+        }
+        if state.recent.len() == MAX_RECENT_EVENTS {
+            state.recent.pop_front();
+        }
+        state.recent.push_back(entry);
+    }
+
+    /// Stop tracking a session, keeping its statistics for later queries
+    pub fn close_session(&self, session: &str) {
+        self.record(session, AccessEvent::Disconnect);
+
+        let mut state = self.state.lock().unwrap();
+        let Some(position) = state
+            .sessions
+            .iter()
+            .position(|s| s.session == session && s.disconnected_at.is_none())
+        else {
+            return;
+        };
+        let mut stats = state.sessions.remove(position);
+        stats.disconnected_at = Some(Utc::now());
+        state.sessions.push(stats);
+
+        let closed = state
+            .sessions
+            .iter()
+            .filter(|s| s.disconnected_at.is_some())
+            .count();
+        if closed > MAX_CLOSED_SESSIONS {
+            let oldest = state
+                .sessions
+                .iter()
+                .position(|s| s.disconnected_at.is_some())
+                .unwrap();
+            state.sessions.remove(oldest);
+        }
+    }
+
+    /// Sessions and up to `limit` recent events, optionally for one session
+    pub fn query(&self, session: Option<&str>, limit: usize) -> AccessLogReport {
+        let state = self.state.lock().unwrap();
+        let matches = |id: &str| session.is_none_or(|wanted| wanted == id);
+        let sessions = state
+            .sessions
+            .iter()
+            .filter(|s| matches(&s.session))
+            .cloned()
+            .collect();
+        let mut recent_events: Vec<AccessEntry> = state
+            .recent
+            .iter()
+            .rev()
+            .filter(|e| matches(&e.session))
+            .take(limit)
+            .cloned()
+            .collect();
+        recent_events.reverse();
+        AccessLogReport {
+            sessions,
+            recent_events,
+            file: self.config.path.clone(),
+        }
+    }
+
+    /// Append an entry to the log file, rotating it first if it is full
+    fn write(&self, state: &mut State, entry: &AccessEntry) -> Result<()> {
+        let Some(path) = &self.config.path else {
+            return Ok(());
+        };
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        if state.file_len > 0 && state.file_len + line.len() as u64 > self.config.max_bytes {
+            state.file = None;
+            rotate(path, self.config.max_files)?;
+            state.file = Some(open_append(path)?);
+            state.file_len = 0;
+        }
+        if let Some(file) = &mut state.file {
+            file.write_all(&line)?;
+            state.file_len += line.len() as u64;
+        }
+        Ok(())
+    }
+}
+
+fn live_session<'a>(state: &'a mut State, session: &str) -> Option<&'a mut SessionStats> {
+    state
+        .sessions
+        .iter_mut()
+        .find(|s| s.session == session && s.disconnected_at.is_none())
+}
+
+fn open_append(path: &Path) -> std::io::Result<File> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Path of the `n`th rotated file
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{n}"));
+    PathBuf::from(name)
+}
+
+/// Shift `PATH` to `PATH.1`, `PATH.1` to `PATH.2` and so on, dropping the
+/// file that would exceed `max_files`
+fn rotate(path: &Path, max_files: usize) -> std::io::Result<()> {
+    if max_files == 0 {
+        return std::fs::remove_file(path);
+    }
+    let oldest = rotated_path(path, max_files);
+    if oldest.exists() {
+        std::fs::remove_file(&oldest)?;
+    }
+    for n in (1..max_files).rev() {
+        let from = rotated_path(path, n);
+        if from.exists() {
+            std::fs::rename(&from, rotated_path(path, n + 1))?;
+        }
+    }
+    std::fs::rename(path, rotated_path(path, 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tool_call(tool: &str, is_error: bool) -> AccessEvent {
+        AccessEvent::ToolCall {
+            tool: tool.to_string(),
+            bytes_in: 40,
+            bytes_out: 120,
+            duration_ms: 5,
+            is_error,
+        }
+    }
+
+    #[test]
+    fn test_session_statistics() {
+        let log = AccessLog::default();
+        log.open_session("a", "http");
+        log.open_session("b", "stdio");
+        log.record(
+            "a",
+            AccessEvent::Initialize {
+                client_name: "claude-desktop".to_string(),
+                client_version: "1.0".to_string(),
+                protocol_version: "2025-06-18".to_string(),
+            },
+        );
+        log.record_message("a", true, 40);
+        log.record_message("a", false, 120);
+        log.record("a", tool_call("build", false));
+        log.record("a", tool_call("build", true));
+        log.record("b", tool_call("test", false));
+        log.close_session("b");
+        // Events of closed or unknown sessions are ignored
+        log.record("b", tool_call("test", false));
+        log.record_message("c", true, 10);
+
+        let report = log.query(None, 100);
+        let ids: Vec<&str> = report.sessions.iter().map(|s| s.session.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+
+        let a = &report.sessions[0];
+        assert_eq!(a.client_name.as_deref(), Some("claude-desktop"));
+        assert_eq!((a.bytes_in, a.bytes_out), (40, 120));
+        assert_eq!(a.tool_calls.get("build"), Some(&2));
+        assert_eq!(a.tool_errors, 1);
+        assert!(a.disconnected_at.is_none());
+        assert!(report.sessions[1].disconnected_at.is_some());
+
+        let report = log.query(Some("b"), 2);
+        assert_eq!(report.sessions.len(), 1);
+        let events: Vec<&AccessEvent> = report.recent_events.iter().map(|e| &e.event).collect();
+        assert_eq!(
+            events,
+            vec![&tool_call("test", false), &AccessEvent::Disconnect]
+        );
+    }
+
+    #[test]
+    fn test_closed_sessions_are_bounded() {
+        let log = AccessLog::default();
+        for i in 0..MAX_CLOSED_SESSIONS + 5 {
+            log.open_session(&i.to_string(), "http");
+            log.close_session(&i.to_string());
+        }
+        log.open_session("live", "http");
+
+        let sessions = log.query(None, 0).sessions;
+        assert_eq!(sessions.len(), MAX_CLOSED_SESSIONS + 1);
+        assert_eq!(sessions[0].session, "live");
+        assert_eq!(sessions[1].session, "5");
+    }
+
+    #[test]
+    fn test_file_is_written_and_rotated() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("logs/access.log");
+        let log = AccessLog::open(AccessLogConfig {
+            path: Some(path.clone()),
+            max_bytes: 400,
+            max_files: 2,
+        })
+        .unwrap();
+
+        log.open_session("a", "http");
+        for _ in 0..20 {
+            log.record("a", tool_call("build", false));
+        }
+
+        let current = std::fs::read_to_string(&path).unwrap();
+        let entry: AccessEntry = serde_json::from_str(current.lines().last().unwrap()).unwrap();
+        assert_eq!(entry.session, "a");
+        assert_eq!(entry.event, tool_call("build", false));
+
+        assert!(rotated_path(&path, 1).exists());
+        assert!(rotated_path(&path, 2).exists());
+        assert!(!rotated_path(&path, 3).exists());
+        for file in [path.clone(), rotated_path(&path, 1)] {
+            assert!(std::fs::metadata(file).unwrap().len() <= 400);
+        }
+        assert_eq!(log.query(None, 5).file, Some(path));
+    }
+}
@@ -0,0 +1,313 @@
+//! Recipe coverage: which recipes `just` knows and which of them are tools
+//!
+//! `just --dump` lists every recipe of a justfile, private ones included.
+//! Each is matched to the tool registered for it by its internal name
+//! (`recipe_/path/to/justfile`); recipes without a tool get the reason they
+//! were left out, so "why can't my agent see recipe X?" has an answer.
+
+use crate::error::{Error, Result};
+use crate::types::ToolDefinition;
+use serde::Serialize;
+use std::path::Path;
+
+/// Coverage of every justfile in the watch directories
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageReport {
+    pub justfiles: Vec<JustfileCoverage>,
+    /// Recipes `just` lists across all justfiles
+    pub total_recipes: usize,
+    /// Of those, recipes exposed as tools
+    pub exposed_recipes: usize,
+}
+
+/// Recipes of one justfile, split into exposed and omitted ones
+#[derive(Debug, Clone, Serialize)]
+pub struct JustfileCoverage {
+    pub justfile: String,
+    /// Parser the justfile's tools are generated with
+    pub parser: String,
+    pub exposed: Vec<ExposedRecipe>,
+    pub omitted: Vec<OmittedRecipe>,
+    /// Tools generated from the justfile for recipes `just` doesn't list
+    pub unknown_to_just: Vec<String>,
+    /// Why `just` could not list the recipes, if it failed
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExposedRecipe {
+    pub recipe: String,
+    pub tool: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OmittedRecipe {
+    pub recipe: String,
+    pub reason: OmissionReason,
+    pub detail: String,
+}
+
+/// Why a recipe has no tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OmissionReason {
+    /// `[private]` or a name starting with `_`
+    Private,
+    /// The justfile's parser failed
+    ParseError,
+    /// The parser succeeded but did not find the recipe
+    NotParsed,
+    /// Its tool name is taken by a recipe of another justfile
+    Shadowed,
+    /// Parsed, but no tool is registered, e.g. after a missed change
+    NotRegistered,
+}
+
+/// A recipe as listed by `just --dump`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListedRecipe {
+    pub name: String,
+    pub private: bool,
+}
+
+/// List the recipes of a justfile with `just --dump --dump-format json`
+pub fn list_recipes(justfile: &Path) -> Result<Vec<ListedRecipe>> {
+    let output = std::process::Command::new("just")
+        .arg("--justfile")
+        .arg(justfile)
+        .args(["--dump", "--dump-format", "json"])
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => crate::just_binary::not_installed("for 'just --dump'"),
+            _ => Error::Io(e),
+        })?;
+    if !output.status.success() {
+        return Err(Error::JustCommand(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    parse_dump(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Recipes in `just --dump --dump-format json` output, sorted by name
+fn parse_dump(dump: &str) -> Result<Vec<ListedRecipe>> {
+    let dump: serde_json::Value = serde_json::from_str(dump)?;
+    let recipes = dump
+        .get("recipes")
+        .and_then(|recipes| recipes.as_object())
+        .ok_or_else(|| Error::JustCommand("just --dump listed no recipes".to_string()))?;
+    let mut listed: Vec<ListedRecipe> = recipes
+        .iter()
+        .map(|(name, recipe)| ListedRecipe {
+            name: name.clone(),
+            private: recipe
+                .get("private")
+                .and_then(|private| private.as_bool())
+                .unwrap_or(false),
+        })
+        .collect();
+    listed.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(listed)
+}
+
+/// Match a justfile's recipes to the registered tools
+///
+/// `parsed` holds the recipes the justfile's parser found, or its error.
+pub fn justfile_coverage(
+    justfile: &Path,
+    parser: &str,
+    listed: &[ListedRecipe],
+    parsed: &std::result::Result<Vec<String>, String>,
+    tools: &[ToolDefinition],
+) -> JustfileCoverage {
+    let suffix = format!("_{}", justfile.display());
+    let recipe_of = |tool: &ToolDefinition| {
+        tool.internal_name
+            .as_deref()
+            .and_then(|internal| internal.strip_suffix(&suffix))
+            .map(str::to_string)
+    };
+
+    let mut exposed = Vec::new();
+    let mut omitted = Vec::new();
+    for recipe in listed {
+        if let Some(tool) = tools
+            .iter()
+            .find(|tool| recipe_of(tool).as_deref() == Some(&recipe.name))
+        {
+            exposed.push(ExposedRecipe {
+                recipe: recipe.name.clone(),
+                tool: tool.name.clone(),
+            });
+            continue;
+        }
+        let (reason, detail) = omission(recipe, parser, parsed, tools);
+        omitted.push(OmittedRecipe {
+            recipe: recipe.name.clone(),
+            reason,
+            detail,
+        });
+    }
+
+    let mut unknown_to_just: Vec<String> = tools
+        .iter()
+        .filter(|tool| {
+            recipe_of(tool).is_some_and(|recipe| !listed.iter().any(|r| r.name == recipe))
+        })
+        .map(|tool| tool.name.clone())
+        .collect();
+    unknown_to_just.sort();
+
+    JustfileCoverage {
+        justfile: justfile.display().to_string(),
+        parser: parser.to_string(),
+        exposed,
+        omitted,
+        unknown_to_just,
+        error: None,
+    }
+}
+
+fn omission(
+    recipe: &ListedRecipe,
+    parser: &str,
+    parsed: &std::result::Result<Vec<String>, String>,
+    tools: &[ToolDefinition],
+) -> (OmissionReason, String) {
+    if recipe.private {
+        let detail = if recipe.name.starts_with('_') {
+            "Recipes whose name starts with '_' are private"
+        } else {
+            "Marked [private]"
+        };
+        return (OmissionReason::Private, detail.to_string());
+    }
+    let found = match parsed {
+        Err(e) => {
+            return (
+                OmissionReason::ParseError,
+                format!("The {parser} parser failed on the justfile: {e}"),
+            )
+        }
+        Ok(found) => found,
+    };
+    if !found.contains(&recipe.name) {
+        return (
+            OmissionReason::NotParsed,
+            format!(
+                "The {parser} parser did not find the recipe; _admin_parser_doctor compares the parsers"
+            ),
+        );
+    }
+
+    // Tools are named after the recipe, with `@name` for named watch directories
+    let prefix = format!("{}_", recipe.name);
+    let shadowing = tools.iter().find(|tool| {
+        (tool.name == recipe.name || tool.name.starts_with(&format!("{}@", recipe.name)))
+            && tool
+                .internal_name
+                .as_deref()
+                .is_some_and(|internal| internal.starts_with(&prefix))
+    });
+    if let Some(tool) = shadowing {
+        let source = tool
+            .internal_name
+            .as_deref()
+            .and_then(|internal| internal.strip_prefix(&prefix))
+            .unwrap_or_default();
+        return (
+            OmissionReason::Shadowed,
+            format!(
+                "Tool name '{}' is taken by the recipe in {source}; name the watch directories to tell them apart",
+                tool.name
+            ),
+        );
+    }
+
+    (
+        OmissionReason::NotRegistered,
+        "Parsed, but no tool is registered; _admin_sync re-scans the justfiles".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(name: &str, internal_name: &str) -> ToolDefinition {
+        ToolDefinition {
+            name: name.to_string(),
+            description: String::new(),
+            input_schema: serde_json::json!({}),
+            dependencies: Vec::new(),
+            source_hash: String::new(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: Some(internal_name.to_string()),
+            metadata: Default::default(),
+        }
+    }
+
+    fn recipe(name: &str, private: bool) -> ListedRecipe {
+        ListedRecipe {
+            name: name.to_string(),
+            private,
+        }
+    }
+
+    #[test]
+    fn test_parse_dump() {
+        let dump = r#"{"recipes": {"test": {"private": false}, "_setup": {"private": true}}}"#;
+        assert_eq!(
+            parse_dump(dump).unwrap(),
+            vec![recipe("_setup", true), recipe("test", false)]
+        );
+        assert!(parse_dump("{}").is_err());
+    }
+
+    #[test]
+    fn test_omitted_recipes_are_explained() {
+        let justfile = Path::new("/app/justfile");
+        let listed = [
+            recipe("build", false),
+            recipe("helper", true),
+            recipe("lint", false),
+            recipe("deploy", false),
+            recipe("fmt", false),
+        ];
+        let parsed = Ok(vec![
+            "build".to_string(),
+            "deploy".to_string(),
+            "fmt".to_string(),
+        ]);
+        let tools = [
+            tool("build", "build_/app/justfile"),
+            tool("deploy", "deploy_/app/sub/justfile"),
+            tool("ghost", "ghost_/app/justfile"),
+        ];
+
+        let coverage = justfile_coverage(justfile, "auto", &listed, &parsed, &tools);
+        assert_eq!(coverage.exposed.len(), 1);
+        assert_eq!(coverage.exposed[0].tool, "build");
+        let reasons: Vec<(&str, OmissionReason)> = coverage
+            .omitted
+            .iter()
+            .map(|o| (o.recipe.as_str(), o.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("helper", OmissionReason::Private),
+                ("lint", OmissionReason::NotParsed),
+                ("deploy", OmissionReason::Shadowed),
+                ("fmt", OmissionReason::NotRegistered),
+            ]
+        );
+        assert!(coverage.omitted[2].detail.contains("/app/sub/justfile"));
+        assert_eq!(coverage.unknown_to_just, vec!["ghost"]);
+
+        let failed = Err("unexpected token".to_string());
+        let coverage = justfile_coverage(justfile, "ast", &listed[2..3], &failed, &[]);
+        assert_eq!(coverage.omitted[0].reason, OmissionReason::ParseError);
+        assert!(coverage.omitted[0].detail.contains("unexpected token"));
+    }
+}
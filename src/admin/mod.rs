@@ -1,19 +1,49 @@
+use crate::access_log::{AccessLog, AccessLogReport};
+use crate::admin::coverage::CoverageReport;
 use crate::error::Result;
+use crate::executor::environment::{recipe_environment, EnvironmentDiff, ShellEnvironment};
+use crate::executor::watchdog::{ExecutionWatchdog, RunningExecution};
+use crate::just_binary::JustBinary;
+use crate::parser::discovery::{find_justfile, find_justfiles, is_justfile};
+use crate::project_lock::{LockConfig, ProjectLock};
 use crate::registry::ToolRegistry;
+use crate::resource_limits::{LimitBounds, LimitChanges, ResourceManager};
 use crate::types::ToolDefinition;
+use crate::watcher::delta::RegistryDelta;
+use crate::watcher::gc::GcReport;
+use crate::watcher::stats::WatcherStats;
 use crate::watcher::JustfileWatcher;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OwnedMutexGuard};
 use tracing::{info, warn};
 
+pub mod coverage;
+pub mod refactor;
+pub mod schemas;
+
 pub struct AdminTools {
     registry: Arc<Mutex<ToolRegistry>>,
     watcher: Arc<JustfileWatcher>,
     watch_paths: Vec<PathBuf>,
     watch_configs: Vec<(PathBuf, Option<String>)>,
+    lock_config: LockConfig,
+    resource_manager: Option<Arc<ResourceManager>>,
+    access_log: Option<Arc<AccessLog>>,
+    watchdog: Option<Arc<ExecutionWatchdog>>,
+    /// Whether `_admin_run_inline_justfile` is offered
+    inline_justfiles: bool,
+    /// In-process write locks, one per justfile being edited
+    write_locks: std::sync::Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>,
+}
+
+/// Locks held while a justfile and the files it imports are edited
+struct WriteLock {
+    _project: ProjectLock,
+    _file: OwnedMutexGuard<()>,
 }
 
 impl AdminTools {
@@ -28,9 +58,78 @@ impl AdminTools {
             watcher,
             watch_paths,
             watch_configs,
+            lock_config: LockConfig::default(),
+            resource_manager: None,
+            access_log: None,
+            watchdog: None,
+            inline_justfiles: false,
+            write_locks: Default::default(),
         }
     }
 
+    /// Resource manager whose limits `_admin_set_limits` adjusts
+    pub fn with_resource_manager(mut self, resource_manager: Arc<ResourceManager>) -> Self {
+        self.resource_manager = Some(resource_manager);
+        self
+    }
+
+    /// Access log that `_admin_access_log` queries
+    pub fn with_access_log(mut self, access_log: Arc<AccessLog>) -> Self {
+        self.access_log = Some(access_log);
+        self
+    }
+
+    /// Watchdog whose executions `_admin_running_executions` lists and
+    /// `_admin_cancel_execution` cancels
+    pub fn with_watchdog(mut self, watchdog: Arc<ExecutionWatchdog>) -> Self {
+        self.watchdog = Some(watchdog);
+        self
+    }
+
+    /// Offer `_admin_run_inline_justfile`, which runs recipes from justfile
+    /// content the client sends
+    pub fn with_inline_justfiles(mut self, enabled: bool) -> Self {
+        self.inline_justfiles = enabled;
+        self
+    }
+
+    pub fn inline_justfiles_enabled(&self) -> bool {
+        self.inline_justfiles
+    }
+
+    /// Configure the advisory project lock taken around justfile edits
+    pub fn with_lock_config(mut self, config: LockConfig) -> Self {
+        self.lock_config = config;
+        self
+    }
+
+    /// Lock a justfile, and the files it imports, for editing
+    ///
+    /// Admin calls editing the same justfile wait for each other in order,
+    /// so calls an agent fires quickly can't interleave their reads and
+    /// writes. The project lock for the justfile's directory is then taken
+    /// to keep other just-mcp instances, and the admin CLI, out. Checks an
+    /// edit relies on must be made while the lock is held.
+    async fn lock_project(&self, justfile_path: &Path, purpose: &str) -> Result<WriteLock> {
+        let key = justfile_path
+            .canonicalize()
+            .unwrap_or_else(|_| justfile_path.to_path_buf());
+        let file_lock = self
+            .write_locks
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_default()
+            .clone();
+        let file = file_lock.lock_owned().await;
+
+        let dir = justfile_path.parent().unwrap_or_else(|| Path::new("."));
+        Ok(WriteLock {
+            _project: ProjectLock::acquire(dir, purpose, &self.lock_config).await?,
+            _file: file,
+        })
+    }
+
     pub async fn register_admin_tools(&self) -> Result<()> {
         let mut registry = self.registry.lock().await;
 
@@ -49,6 +148,7 @@ impl AdminTools {
             source_hash: "admin_tool_sync_v1".to_string(),
             last_modified: std::time::SystemTime::now(),
             internal_name: None,
+            metadata: Default::default(),
         };
 
         registry.add_tool(sync_tool)?;
@@ -93,6 +193,10 @@ impl AdminTools {
                         "type": "array",
                         "description": "Recipe dependencies",
                         "items": {"type": "string"}
+                    },
+                    "target_file": {
+                        "type": "string",
+                        "description": "File to add the recipe to, relative to the justfile (e.g. 'ci.just'). Must be the justfile or a file it imports; defaults to the justfile"
                     }
                 },
                 "required": ["recipe_name", "recipe"],
@@ -102,6 +206,7 @@ impl AdminTools {
             source_hash: "admin_tool_create_recipe_v1".to_string(),
             last_modified: std::time::SystemTime::now(),
             internal_name: None,
+            metadata: Default::default(),
         };
 
         registry.add_tool(create_recipe_tool)?;
@@ -126,6 +231,7 @@ impl AdminTools {
             source_hash: "admin_tool_set_watch_directory_v1".to_string(),
             last_modified: std::time::SystemTime::now(),
             internal_name: None,
+            metadata: Default::default(),
         };
 
         registry.add_tool(set_watch_directory_tool)?;
@@ -151,11 +257,442 @@ impl AdminTools {
             source_hash: "admin_tool_parser_doctor_v1".to_string(),
             last_modified: std::time::SystemTime::now(),
             internal_name: None,
+            metadata: Default::default(),
         };
 
         registry.add_tool(parser_doctor_tool)?;
 
-        // TODO: Add modify_recipe, remove_recipe tools in future subtasks
+        // Register extract_group() tool
+        let extract_group_tool = ToolDefinition {
+            name: "_admin_extract_group".to_string(),
+            description: "Move all recipes of a group into a new file and import it from the justfile. The justfile is backed up first.".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "watch_name": {
+                        "type": "string",
+                        "description": "Name of the watch directory whose justfile to restructure. If omitted, uses the main/default justfile"
+                    },
+                    "group": {
+                        "type": "string",
+                        "description": "Group whose recipes to move, as set by [group('name')]"
+                    },
+                    "target_file": {
+                        "type": "string",
+                        "description": "File to create, relative to the justfile. Defaults to '<group>.just'"
+                    }
+                },
+                "required": ["group"],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_extract_group_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(extract_group_tool)?;
+
+        // Register inline_import() tool
+        let inline_import_tool = ToolDefinition {
+            name: "_admin_inline_import".to_string(),
+            description: "Replace an import statement with the contents of the imported file. Both files are backed up first.".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "watch_name": {
+                        "type": "string",
+                        "description": "Name of the watch directory whose justfile to restructure. If omitted, uses the main/default justfile"
+                    },
+                    "import_path": {
+                        "type": "string",
+                        "description": "Path of the imported file, relative to the justfile"
+                    },
+                    "keep_file": {
+                        "type": "boolean",
+                        "description": "Keep the imported file instead of deleting it",
+                        "default": false
+                    }
+                },
+                "required": ["import_path"],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_inline_import_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(inline_import_tool)?;
+
+        // Register status() tool
+        let status_tool = ToolDefinition {
+            name: "_admin_status".to_string(),
+            description: "Report watched directories, tool count and watcher health: event queue depth, dropped events, update latency and the last event per justfile".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {},
+                "required": [],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_status_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(status_tool)?;
+
+        // Register set_limits() tool
+        let set_limits_tool = ToolDefinition {
+            name: "_admin_set_limits".to_string(),
+            description: "Change execution limits at runtime, within the bounds configured by the operator. Applies to new executions; omitted limits are unchanged, so calling with no arguments reports the current limits".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "max_concurrent_executions": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Maximum number of recipes running at once"
+                    },
+                    "timeout_seconds": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Default execution timeout in seconds"
+                    },
+                    "max_output_size": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Maximum combined stdout and stderr size in bytes"
+                    }
+                },
+                "required": [],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_set_limits_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(set_limits_tool)?;
+
+        // Register env_doctor() tool
+        let env_doctor_tool = ToolDefinition {
+            name: "_admin_env_doctor".to_string(),
+            description: "Compare the environment recipes run with against your login shell and list commonly needed variables (HOME, SSH_AUTH_SOCK, ...) and PATH entries that recipes are missing".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {},
+                "required": [],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_env_doctor_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(env_doctor_tool)?;
+
+        // Register apply_changes() tool
+        let recipe_fields = json!({
+            "recipe_name": {
+                "type": "string",
+                "description": "Name of the recipe"
+            },
+            "description": {
+                "type": "string",
+                "description": "Description/comment for the recipe. For modify, omit to keep the existing comments"
+            },
+            "recipe": {
+                "type": "string",
+                "description": "The command(s) to execute"
+            },
+            "parameters": {
+                "type": "array",
+                "description": "Recipe parameters",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "default": {"type": "string"}
+                    },
+                    "required": ["name"]
+                }
+            },
+            "dependencies": {
+                "type": "array",
+                "description": "Recipe dependencies",
+                "items": {"type": "string"}
+            },
+            "target_file": {
+                "type": "string",
+                "description": "For create: file to add the recipe to, relative to the justfile. Must be imported by it. Defaults to the justfile"
+            }
+        });
+        let mut change_properties = recipe_fields;
+        change_properties["action"] = json!({
+            "type": "string",
+            "enum": ["create", "modify", "remove"],
+            "description": "create appends a new recipe, modify replaces a recipe's header and body where it is defined (keeping its attributes), remove deletes it"
+        });
+        let apply_changes_tool = ToolDefinition {
+            name: "_admin_apply_changes".to_string(),
+            description: "Create, modify and remove several recipes atomically. All changes are validated together with `just --summary` on a temporary copy; only if every change applies are the files backed up and replaced, otherwise nothing is written".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "watch_name": {
+                        "type": "string",
+                        "description": "Name of the watch directory whose justfile to edit. If omitted, uses the main/default justfile"
+                    },
+                    "changes": {
+                        "type": "array",
+                        "description": "Changes to apply in order; later changes see earlier ones",
+                        "minItems": 1,
+                        "items": {
+                            "type": "object",
+                            "properties": change_properties,
+                            "required": ["action", "recipe_name"],
+                            "additionalProperties": false
+                        }
+                    }
+                },
+                "required": ["changes"],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_apply_changes_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(apply_changes_tool)?;
+
+        // Register gc() tool
+        let gc_tool = ToolDefinition {
+            name: "_admin_gc".to_string(),
+            description: "Remove orphaned registry entries: tools whose justfile was deleted, bookkeeping for tools that are gone, names of deleted watch directories, and tools of justfiles edited without a change event. Reports what was found".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "dry_run": {
+                        "type": "boolean",
+                        "description": "Only report orphans without removing them",
+                        "default": false
+                    }
+                },
+                "required": [],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_gc_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(gc_tool)?;
+
+        // Register access_log() tool
+        let access_log_tool = ToolDefinition {
+            name: "_admin_access_log".to_string(),
+            description: "Report client sessions: who connected (from initialize), which tools they called and the bytes exchanged, plus the most recent access log events".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "session": {
+                        "type": "string",
+                        "description": "Only report this session ID"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "Number of recent events to include",
+                        "default": 20
+                    }
+                },
+                "required": [],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_access_log_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(access_log_tool)?;
+
+        // Register running_executions() tool
+        let running_executions_tool = ToolDefinition {
+            name: "_admin_running_executions".to_string(),
+            description: "List running recipe executions with their process id, runtime, CPU time and memory of the process tree, and time since they last used CPU or printed output. Executions idle on both for the hang timeout are flagged as likely hung".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {},
+                "required": [],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_running_executions_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(running_executions_tool)?;
+
+        // Register cancel_execution() tool
+        let cancel_execution_tool = ToolDefinition {
+            name: "_admin_cancel_execution".to_string(),
+            description: "Cancel a running recipe execution listed by _admin_running_executions. Its process is killed and the tool call that started it fails as cancelled".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "id": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Execution id from _admin_running_executions"
+                    }
+                },
+                "required": ["id"],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_cancel_execution_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(cancel_execution_tool)?;
+
+        // Register use_profile() tool
+        let use_profile_tool = ToolDefinition {
+            name: "_admin_use_profile".to_string(),
+            description: "Select the named profile this session's recipe calls run under unless they pass their own 'profile' argument. Profiles bundle environment variables, parameter values and allowed recipes. Omit the profile to clear it".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "profile": {
+                        "type": "string",
+                        "description": "Profile name from the profiles file"
+                    }
+                },
+                "required": [],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_use_profile_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(use_profile_tool)?;
+
+        // Register schema_mode() tool
+        let schema_mode_tool = ToolDefinition {
+            name: "_admin_schema_mode".to_string(),
+            description: "Choose how this session's tools/list sends input schemas: 'compact' drops $schema, additionalProperties: false and empty required lists to save tokens, 'full' sends them as generated. Reports the size of both; list the tools again to get the new schemas".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {
+                    "mode": {
+                        "type": "string",
+                        "enum": ["full", "compact"],
+                        "description": "Schema mode for the rest of the session"
+                    }
+                },
+                "required": ["mode"],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_schema_mode_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(schema_mode_tool)?;
+
+        // Register coverage() tool
+        let coverage_tool = ToolDefinition {
+            name: "_admin_coverage".to_string(),
+            description: "Compare the recipes just lists with the tools exposed for them, giving the reason each omitted recipe has no tool (private, parse error, not parsed, shadowed by another justfile, not registered)".to_string(),
+            input_schema: json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {},
+                "required": [],
+                "additionalProperties": false
+            }),
+            dependencies: vec![],
+            source_hash: "admin_tool_coverage_v1".to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        };
+
+        registry.add_tool(coverage_tool)?;
+
+        if self.inline_justfiles {
+            let inline_tool = ToolDefinition {
+                name: "_admin_run_inline_justfile".to_string(),
+                description: format!(
+                    "Run a recipe from justfile content supplied with the call, in a temporary directory removed afterwards. Parameter validation, resource limits and timeouts apply as for any recipe; the recipe runs with the server's permissions. Content is limited to {} KiB",
+                    crate::executor::inline::MAX_INLINE_JUSTFILE_BYTES / 1024
+                ),
+                input_schema: json!({
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "type": "object",
+                    "properties": {
+                        "content": {
+                            "type": "string",
+                            "description": "Justfile source"
+                        },
+                        "recipe": {
+                            "type": "string",
+                            "description": "Recipe to run"
+                        },
+                        "arguments": {
+                            "type": "object",
+                            "description": "Recipe parameters by name",
+                            "additionalProperties": true
+                        }
+                    },
+                    "required": ["content", "recipe"],
+                    "additionalProperties": false
+                }),
+                dependencies: vec![],
+                source_hash: "admin_tool_run_inline_justfile_v1".to_string(),
+                last_modified: std::time::SystemTime::now(),
+                internal_name: None,
+                metadata: Default::default(),
+            };
+
+            registry.add_tool(inline_tool)?;
+        }
 
         Ok(())
     }
@@ -188,9 +725,8 @@ impl AdminTools {
         for path in &self.watch_paths {
             if path.exists() {
                 if path.is_dir() {
-                    // Scan for justfiles in directory
-                    let justfile_path = path.join("justfile");
-                    if justfile_path.exists() {
+                    // Scan for justfiles in directory, in any casing
+                    for justfile_path in find_justfiles(path) {
                         info!("Found justfile: {}", justfile_path.display());
                         match self.scan_justfile(&justfile_path).await {
                             Ok(task_count) => {
@@ -203,25 +739,7 @@ impl AdminTools {
                             }
                         }
                     }
-
-                    // Also check for capitalized Justfile
-                    let justfile_cap = path.join("Justfile");
-                    if justfile_cap.exists() {
-                        info!("Found Justfile: {}", justfile_cap.display());
-                        match self.scan_justfile(&justfile_cap).await {
-                            Ok(task_count) => {
-                                scanned_files += 1;
-                                found_recipes += task_count;
-                            }
-                            Err(e) => {
-                                warn!("Error scanning {}: {}", justfile_cap.display(), e);
-                                errors.push(format!("{}: {}", justfile_cap.display(), e));
-                            }
-                        }
-                    }
-                } else if path.file_name() == Some(std::ffi::OsStr::new("justfile"))
-                    || path.file_name() == Some(std::ffi::OsStr::new("Justfile"))
-                {
+                } else if is_justfile(path) {
                     // Direct justfile path
                     match self.scan_justfile(path).await {
                         Ok(task_count) => {
@@ -273,33 +791,21 @@ impl AdminTools {
         Ok(task_count)
     }
 
-    pub async fn create_recipe(&self, params: CreateRecipeParams) -> Result<CreateRecipeResult> {
-        info!(
-            "Creating new recipe: {} in {}",
-            params.recipe_name,
-            params.watch_name.as_deref().unwrap_or("default justfile")
-        );
-
-        // Determine which justfile to use
-        let justfile_path = if let Some(watch_name) = params.watch_name {
+    /// Find the justfile for a named watch directory, or the main justfile
+    fn resolve_justfile(&self, watch_name: Option<&str>) -> Result<PathBuf> {
+        if let Some(watch_name) = watch_name {
             // Find the watch directory by name
             let mut found_path = None;
 
             for (path, name) in &self.watch_configs {
                 if let Some(n) = name {
-                    if n == &watch_name {
+                    if n == watch_name {
                         // Found by name
                         if path.is_dir() {
-                            let justfile = path.join("justfile");
-                            if justfile.exists() {
+                            if let Some(justfile) = find_justfile(path) {
                                 found_path = Some(justfile);
                                 break;
                             }
-                            let justfile_cap = path.join("Justfile");
-                            if justfile_cap.exists() {
-                                found_path = Some(justfile_cap);
-                                break;
-                            }
                         } else {
                             found_path = Some(path.clone());
                             break;
@@ -319,7 +825,7 @@ impl AdminTools {
                         .collect::<Vec<_>>()
                         .join(", ")
                 ))
-            })?
+            })
         } else {
             // No name specified - use the main/first justfile
             let (path, _) = &self.watch_configs.first().ok_or_else(|| {
@@ -327,133 +833,395 @@ impl AdminTools {
             })?;
 
             if path.is_dir() {
-                let justfile = path.join("justfile");
-                if justfile.exists() {
-                    justfile
-                } else {
-                    let justfile_cap = path.join("Justfile");
-                    if justfile_cap.exists() {
-                        justfile_cap
-                    } else {
-                        return Err(crate::error::Error::Other(
-                            "No justfile found in main watch directory".to_string(),
-                        ));
-                    }
-                }
+                find_justfile(path).ok_or_else(|| {
+                    crate::error::Error::Other(
+                        "No justfile found in main watch directory".to_string(),
+                    )
+                })
             } else {
-                path.clone()
+                Ok(path.clone())
             }
+        }
+    }
+
+    pub async fn create_recipe(&self, params: CreateRecipeParams) -> Result<CreateRecipeResult> {
+        info!(
+            "Creating new recipe: {} in {}",
+            params.recipe_name,
+            params.watch_name.as_deref().unwrap_or("default justfile")
+        );
+
+        // Determine which justfile to use, and which file in its import
+        // graph receives the recipe
+        let justfile_path = self.resolve_justfile(params.watch_name.as_deref())?;
+        let target_path = match params.target_file.as_deref() {
+            Some(target) => import_target(&justfile_path, target)?,
+            None => justfile_path.clone(),
         };
 
+        let _lock = self.lock_project(&justfile_path, "create_recipe").await?;
+
         // Validate recipe name doesn't conflict with existing recipes
         {
             let registry = self.registry.lock().await;
 
-            // Check for any tool that matches the recipe name exactly or with @name suffix
-            // This handles both single directory (recipename) and multi-directory (recipename@name) cases
-            let existing_recipe = registry.list_tools().iter().any(|tool| {
-                tool.name == params.recipe_name
-                    || tool.name.starts_with(&format!("{}@", params.recipe_name))
-            });
+            // Check for any tool that matches the recipe name exactly or with @name suffix
+            // This handles both single directory (recipename) and multi-directory (recipename@name) cases
+            let existing_recipe = registry.list_tools().iter().any(|tool| {
+                tool.name == params.recipe_name
+                    || tool.name.starts_with(&format!("{}@", params.recipe_name))
+            });
+
+            if existing_recipe {
+                return Err(crate::error::Error::Other(format!(
+                    "Recipe '{}' already exists in {}",
+                    params.recipe_name,
+                    justfile_path.display()
+                )));
+            }
+
+            // Check for admin tool conflicts
+            if params.recipe_name.starts_with("_admin_") {
+                return Err(crate::error::Error::Other(
+                    "Recipe names starting with '_admin_' are reserved".to_string(),
+                ));
+            }
+        }
+
+        // Create backup with dotfile naming
+        let backup_path = backup_file(&target_path)?;
+
+        // Append the recipe after a blank line
+        let existing_content = std::fs::read_to_string(&target_path)?;
+        let recipe_content = refactor::recipe_source(
+            &params.recipe_name,
+            params.description.as_deref(),
+            params.parameters.as_deref().unwrap_or_default(),
+            params.dependencies.as_deref().unwrap_or_default(),
+            &params.recipe,
+        );
+        let new_content = refactor::append_recipe(&existing_content, &recipe_content);
+        std::fs::write(&target_path, &new_content)?;
+
+        // Apply only the tools that changed; the other tools of the justfile
+        // keep their registry entries
+        let registry_delta = self.watcher.parse_and_diff_justfile(&justfile_path).await?;
+        if !registry_delta.is_empty() {
+            self.watcher.send_tools_changed_notification();
+        }
+
+        info!(
+            "Successfully created recipe '{}' in {} ({} added, {} modified, {} removed)",
+            params.recipe_name,
+            target_path.display(),
+            registry_delta.added.len(),
+            registry_delta.modified.len(),
+            registry_delta.removed.len()
+        );
+
+        Ok(CreateRecipeResult {
+            recipe_name: params.recipe_name,
+            justfile_path: target_path.to_string_lossy().to_string(),
+            backup_path: backup_path.to_string_lossy().to_string(),
+            registry_delta,
+        })
+    }
+
+    /// Move all recipes of a group into a new file imported by the justfile
+    ///
+    /// The justfile is backed up first. If `just` rejects the result, the
+    /// justfile is restored and the new file removed.
+    pub async fn extract_group(&self, params: ExtractGroupParams) -> Result<ExtractGroupResult> {
+        info!("Extracting recipe group '{}'", params.group);
+
+        let justfile_path = self.resolve_justfile(params.watch_name.as_deref())?;
+        let base_dir = justfile_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+        let _lock = self.lock_project(&justfile_path, "extract_group").await?;
+
+        let target_file = params
+            .target_file
+            .unwrap_or_else(|| format!("{}.just", params.group));
+        if !is_inside_directory(&target_file) {
+            return Err(crate::error::Error::InvalidParameter(format!(
+                "Target file must be a relative path inside the justfile directory: {target_file}"
+            )));
+        }
+        let import_path = base_dir.join(&target_file);
+        if import_path.exists() {
+            return Err(crate::error::Error::Other(format!(
+                "Target file already exists: {}",
+                import_path.display()
+            )));
+        }
+
+        let content = std::fs::read_to_string(&justfile_path)?;
+        let extraction = refactor::extract_group(&content, &params.group, &target_file)?;
+
+        let backup_path = backup_file(&justfile_path)?;
+        if let Some(parent) = import_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&import_path, &extraction.extracted)?;
+        std::fs::write(&justfile_path, &extraction.remaining)?;
+
+        if let Err(e) = validate_justfile(&justfile_path) {
+            warn!(
+                "Restoring {} after failed extraction",
+                justfile_path.display()
+            );
+            std::fs::copy(&backup_path, &justfile_path)?;
+            std::fs::remove_file(&import_path)?;
+            return Err(e);
+        }
+
+        self.scan_justfile(&justfile_path).await?;
+        self.watcher.send_tools_changed_notification();
+
+        info!(
+            "Moved {} recipe(s) from {} to {}",
+            extraction.recipes.len(),
+            justfile_path.display(),
+            import_path.display()
+        );
+
+        Ok(ExtractGroupResult {
+            group: params.group,
+            recipes: extraction.recipes,
+            justfile_path: justfile_path.to_string_lossy().to_string(),
+            import_path: import_path.to_string_lossy().to_string(),
+            backup_path: backup_path.to_string_lossy().to_string(),
+        })
+    }
 
-            if existing_recipe {
-                return Err(crate::error::Error::Other(format!(
-                    "Recipe '{}' already exists in {}",
-                    params.recipe_name,
+    /// Replace an `import` statement with the contents of the imported file
+    ///
+    /// Both files are backed up first. The imported file is deleted unless
+    /// `keep_file` is set. If `just` rejects the result, both are restored.
+    pub async fn inline_import(&self, params: InlineImportParams) -> Result<InlineImportResult> {
+        info!("Inlining import '{}'", params.import_path);
+
+        let justfile_path = self.resolve_justfile(params.watch_name.as_deref())?;
+        let base_dir = justfile_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+        let import_path = base_dir.join(&params.import_path);
+
+        let _lock = self.lock_project(&justfile_path, "inline_import").await?;
+        let content = std::fs::read_to_string(&justfile_path)?;
+        let line =
+            refactor::find_import_line(&content, &base_dir, &import_path).ok_or_else(|| {
+                crate::error::Error::Other(format!(
+                    "No import of '{}' found in {}",
+                    params.import_path,
                     justfile_path.display()
-                )));
-            }
+                ))
+            })?;
 
-            // Check for admin tool conflicts
-            if params.recipe_name.starts_with("_admin_") {
-                return Err(crate::error::Error::Other(
-                    "Recipe names starting with '_admin_' are reserved".to_string(),
-                ));
-            }
+        let imported = std::fs::read_to_string(&import_path)?;
+        let import_dir = import_path.parent().unwrap_or_else(|| Path::new("."));
+        let nested = crate::parser::imports::extract_imports(&imported);
+        if !nested.is_empty() && import_dir.canonicalize()? != base_dir.canonicalize()? {
+            return Err(crate::error::Error::Other(format!(
+                "{} has its own imports relative to another directory; inline those first",
+                import_path.display()
+            )));
         }
 
-        // Create backup with dotfile naming
-        let backup_path = justfile_path
-            .parent()
-            .unwrap_or_else(|| std::path::Path::new("."))
-            .join(format!(
-                ".{}.bak",
-                justfile_path.file_name().unwrap().to_string_lossy()
-            ));
-        std::fs::copy(&justfile_path, &backup_path)?;
-
-        // Read existing content
-        let existing_content = std::fs::read_to_string(&justfile_path)?;
+        let recipes = refactor::recipe_blocks(&imported)
+            .into_iter()
+            .map(|block| block.name)
+            .collect();
+        let new_content = refactor::inline_import(&content, line, &imported);
 
-        // Build the new recipe content
-        let mut recipe_content = String::new();
+        let mut backup_paths = vec![backup_file(&justfile_path)?];
+        if !params.keep_file {
+            backup_paths.push(backup_file(&import_path)?);
+        }
+        std::fs::write(&justfile_path, &new_content)?;
+        if !params.keep_file {
+            std::fs::remove_file(&import_path)?;
+        }
 
-        // Ensure proper spacing: always add a blank line before the new recipe
-        if !existing_content.is_empty() {
-            // If file doesn't end with newline, add one
-            if !existing_content.ends_with('\n') {
-                recipe_content.push('\n');
+        if let Err(e) = validate_justfile(&justfile_path) {
+            warn!("Restoring {} after failed inline", justfile_path.display());
+            std::fs::copy(&backup_paths[0], &justfile_path)?;
+            if !params.keep_file {
+                std::fs::copy(&backup_paths[1], &import_path)?;
             }
-            // Always add a blank line for visual separation
-            recipe_content.push('\n');
+            return Err(e);
         }
 
-        // Add description as comment
-        if let Some(desc) = &params.description {
-            recipe_content.push_str(&format!("# {desc}\n"));
+        self.scan_justfile(&justfile_path).await?;
+        self.watcher.send_tools_changed_notification();
+
+        Ok(InlineImportResult {
+            recipes,
+            justfile_path: justfile_path.to_string_lossy().to_string(),
+            import_path: import_path.to_string_lossy().to_string(),
+            file_removed: !params.keep_file,
+            backup_paths: backup_paths
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect(),
+        })
+    }
+
+    /// Apply a batch of recipe creations, modifications and removals at once
+    ///
+    /// Every change is applied in memory to the justfile and the files it
+    /// imports, and the result is checked with `just --summary` in a
+    /// temporary copy of the project. Only if all changes apply and `just`
+    /// accepts the result are the changed files backed up and replaced, and
+    /// the registry re-synced once. On any failure nothing is written.
+    pub async fn apply_changes(&self, params: ApplyChangesParams) -> Result<ApplyChangesResult> {
+        info!("Applying {} recipe change(s)", params.changes.len());
+        if params.changes.is_empty() {
+            return Err(crate::error::Error::InvalidParameter(
+                "No changes given".to_string(),
+            ));
         }
 
-        // Add recipe signature
-        recipe_content.push_str(&params.recipe_name);
+        let justfile_path = self.resolve_justfile(params.watch_name.as_deref())?;
+        let _lock = self.lock_project(&justfile_path, "apply_changes").await?;
 
-        // Add parameters
-        if let Some(parameters) = &params.parameters {
-            for param in parameters {
-                recipe_content.push(' ');
-                recipe_content.push_str(&param.name);
-                if let Some(default) = &param.default {
-                    recipe_content.push_str(&format!("=\"{default}\""));
-                }
+        let mut files = Vec::new();
+        for file in crate::parser::imports::resolve_import_graph(&justfile_path)? {
+            if file.exists {
+                let content = std::fs::read_to_string(&file.path)?;
+                files.push((file.path, content));
             }
         }
+        let mut changed = vec![false; files.len()];
+        let mut applied = Vec::new();
 
-        // Add dependencies
-        if let Some(deps) = &params.dependencies {
-            if !deps.is_empty() {
-                recipe_content.push_str(": ");
-                recipe_content.push_str(&deps.join(" "));
-                recipe_content.push('\n');
-            } else {
-                recipe_content.push_str(":\n");
+        for change in &params.changes {
+            let name = change.recipe_name();
+            let defined_in = files
+                .iter()
+                .position(|(_, content)| refactor::has_recipe(content, name));
+            match change {
+                RecipeChange::Create {
+                    recipe_name,
+                    description,
+                    recipe,
+                    parameters,
+                    dependencies,
+                    target_file,
+                } => {
+                    if recipe_name.starts_with("_admin_") {
+                        return Err(crate::error::Error::InvalidParameter(
+                            "Recipe names starting with '_admin_' are reserved".to_string(),
+                        ));
+                    }
+                    if let Some(index) = defined_in {
+                        return Err(crate::error::Error::InvalidParameter(format!(
+                            "Recipe '{recipe_name}' already exists in {}",
+                            files[index].0.display()
+                        )));
+                    }
+                    let index = match target_file {
+                        Some(target) => {
+                            let target = import_target(&justfile_path, target)?.canonicalize()?;
+                            files
+                                .iter()
+                                .position(|(path, _)| {
+                                    path.canonicalize().ok().as_ref() == Some(&target)
+                                })
+                                .ok_or_else(|| {
+                                    crate::error::Error::InvalidParameter(format!(
+                                        "Target file is not imported by {}: {}",
+                                        justfile_path.display(),
+                                        target.display()
+                                    ))
+                                })?
+                        }
+                        None => 0,
+                    };
+                    let source = refactor::recipe_source(
+                        recipe_name,
+                        description.as_deref(),
+                        parameters.as_deref().unwrap_or_default(),
+                        dependencies.as_deref().unwrap_or_default(),
+                        recipe,
+                    );
+                    files[index].1 = refactor::append_recipe(&files[index].1, &source);
+                    changed[index] = true;
+                    applied.push(format!("created {recipe_name}"));
+                }
+                RecipeChange::Modify {
+                    recipe_name,
+                    description,
+                    recipe,
+                    parameters,
+                    dependencies,
+                } => {
+                    let index = defined_in.ok_or_else(|| not_found(recipe_name, &justfile_path))?;
+                    let source = refactor::recipe_source(
+                        recipe_name,
+                        description.as_deref(),
+                        parameters.as_deref().unwrap_or_default(),
+                        dependencies.as_deref().unwrap_or_default(),
+                        recipe,
+                    );
+                    files[index].1 =
+                        refactor::replace_recipe(&files[index].1, recipe_name, &source)?;
+                    changed[index] = true;
+                    applied.push(format!("modified {recipe_name}"));
+                }
+                RecipeChange::Remove { recipe_name } => {
+                    let index = defined_in.ok_or_else(|| not_found(recipe_name, &justfile_path))?;
+                    files[index].1 = refactor::remove_recipe(&files[index].1, recipe_name)?;
+                    changed[index] = true;
+                    applied.push(format!("removed {recipe_name}"));
+                }
             }
-        } else {
-            recipe_content.push_str(":\n");
         }
 
-        // Add recipe body with proper indentation
-        for line in params.recipe.lines() {
-            recipe_content.push_str("    ");
-            recipe_content.push_str(line);
-            recipe_content.push('\n');
-        }
+        validate_staged(&files)?;
 
-        // Write updated content
-        let new_content = existing_content + &recipe_content;
-        std::fs::write(&justfile_path, &new_content)?;
+        // Swap the changed files in, restoring those already replaced if a
+        // later write fails
+        let mut replaced: Vec<(PathBuf, PathBuf)> = Vec::new();
+        for ((path, content), _) in files.iter().zip(&changed).filter(|(_, changed)| **changed) {
+            let result = backup_file(path).and_then(|backup| {
+                replace_file(path, content)?;
+                Ok(backup)
+            });
+            match result {
+                Ok(backup) => replaced.push((path.clone(), backup)),
+                Err(e) => {
+                    for (path, backup) in &replaced {
+                        warn!("Restoring {} after failed apply", path.display());
+                        std::fs::copy(backup, path)?;
+                    }
+                    return Err(e);
+                }
+            }
+        }
 
-        // Re-scan the justfile to update registry
         self.scan_justfile(&justfile_path).await?;
+        self.watcher.send_tools_changed_notification();
 
         info!(
-            "Successfully created recipe '{}' in {}",
-            params.recipe_name,
-            justfile_path.display()
+            "Applied {} change(s) to {} file(s)",
+            applied.len(),
+            replaced.len()
         );
 
-        Ok(CreateRecipeResult {
-            recipe_name: params.recipe_name,
-            justfile_path: justfile_path.to_string_lossy().to_string(),
-            backup_path: backup_path.to_string_lossy().to_string(),
+        Ok(ApplyChangesResult {
+            applied,
+            changed_files: replaced
+                .iter()
+                .map(|(path, _)| path.to_string_lossy().to_string())
+                .collect(),
+            backup_paths: replaced
+                .iter()
+                .map(|(_, backup)| backup.to_string_lossy().to_string())
+                .collect(),
         })
     }
 
@@ -486,16 +1254,9 @@ impl AdminTools {
         }
 
         // Check for justfile presence
-        let justfile_path = absolute_path.join("justfile");
-        let justfile_cap_path = absolute_path.join("Justfile");
-
-        let (justfile_detected, detected_justfile_path) = if justfile_path.exists() {
-            (true, Some(justfile_path.to_string_lossy().to_string()))
-        } else if justfile_cap_path.exists() {
-            (true, Some(justfile_cap_path.to_string_lossy().to_string()))
-        } else {
-            (false, None)
-        };
+        let detected_justfile_path =
+            find_justfile(&absolute_path).map(|path| path.to_string_lossy().to_string());
+        let justfile_detected = detected_justfile_path.is_some();
 
         // Clear the registry cache (remove all non-admin tools)
         {
@@ -542,7 +1303,168 @@ impl AdminTools {
         })
     }
 
+    /// Current watch directories, tool count and watcher health
+    pub async fn status(&self) -> Result<StatusResult> {
+        let tool_count = self
+            .registry
+            .lock()
+            .await
+            .list_tools()
+            .iter()
+            .filter(|tool| !tool.name.starts_with("_admin_"))
+            .count();
+
+        Ok(StatusResult {
+            watch_directories: self
+                .watch_paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect(),
+            tool_count,
+            watcher: self.watcher.stats(),
+            parsers: self.effective_parsers(),
+            just: JustBinary::detect(),
+        })
+    }
+
+    /// Recipes `just` lists in each watched justfile, and why those
+    /// without a tool were left out
+    pub async fn coverage(&self) -> Result<CoverageReport> {
+        let mut justfiles = Vec::new();
+        for path in &self.watch_paths {
+            if path.is_dir() {
+                justfiles.extend(find_justfiles(path));
+            } else if is_justfile(path) {
+                justfiles.push(path.clone());
+            }
+        }
+
+        let tools = self.registry.lock().await.get_all_tools();
+        let parser = crate::parser::EnhancedJustfileParser::new()?;
+        let mut report = Vec::new();
+        for justfile in justfiles {
+            let preference = self.watcher.effective_parser(&justfile).clone();
+            let parsed = parser
+                .parse_file_for_tools_using(&justfile, &preference)
+                .map(|tasks| tasks.into_iter().map(|task| task.name).collect())
+                .map_err(|e| e.to_string());
+            let coverage = match coverage::list_recipes(&justfile) {
+                Ok(listed) => coverage::justfile_coverage(
+                    &justfile,
+                    &preference.to_string(),
+                    &listed,
+                    &parsed,
+                    &tools,
+                ),
+                Err(e @ crate::error::Error::JustNotInstalled { .. }) => return Err(e),
+                Err(e) => {
+                    let mut coverage = coverage::justfile_coverage(
+                        &justfile,
+                        &preference.to_string(),
+                        &[],
+                        &parsed,
+                        &tools,
+                    );
+                    coverage.error = Some(e.to_string());
+                    coverage
+                }
+            };
+            report.push(coverage);
+        }
+
+        Ok(CoverageReport {
+            total_recipes: report
+                .iter()
+                .map(|c| c.exposed.len() + c.omitted.len())
+                .sum(),
+            exposed_recipes: report.iter().map(|c| c.exposed.len()).sum(),
+            justfiles: report,
+        })
+    }
+
+    /// Client sessions and recent access log events
+    pub fn access_log(&self, session: Option<&str>, limit: usize) -> Result<AccessLogReport> {
+        let access_log = self.access_log.as_ref().ok_or_else(|| {
+            crate::error::Error::Other("The access log is not available here".to_string())
+        })?;
+        Ok(access_log.query(session, limit))
+    }
+
+    /// Running recipe executions and the hang timeout they are judged by
+    pub fn running_executions(&self) -> Result<RunningExecutionsResult> {
+        let watchdog = self.watchdog()?;
+        Ok(RunningExecutionsResult {
+            executions: watchdog.running(),
+            hang_timeout_seconds: watchdog.config().hang_timeout.as_secs(),
+        })
+    }
+
+    /// Cancel a running recipe execution
+    pub fn cancel_execution(&self, id: u64) -> Result<RunningExecution> {
+        self.watchdog()?.cancel(id)
+    }
+
+    fn watchdog(&self) -> Result<&Arc<ExecutionWatchdog>> {
+        self.watchdog.as_ref().ok_or_else(|| {
+            crate::error::Error::Other("Execution tracking is not available here".to_string())
+        })
+    }
+
+    /// Sweep the registry for orphaned tools and bookkeeping
+    ///
+    /// Clients are notified if any tools were removed or refreshed.
+    pub async fn gc(&self, dry_run: bool) -> Result<GcReport> {
+        let report = self.watcher.collect_garbage(dry_run).await?;
+        if !dry_run && (!report.orphan_tools.is_empty() || !report.outdated_justfiles.is_empty()) {
+            self.watcher.send_tools_changed_notification();
+        }
+        Ok(report)
+    }
+
+    /// Parser used for each watch directory, after per-project overrides
+    pub fn effective_parsers(&self) -> Vec<ProjectParser> {
+        self.watch_configs
+            .iter()
+            .map(|(path, name)| ProjectParser {
+                watch_directory: path.display().to_string(),
+                name: name.clone(),
+                parser: self.watcher.effective_parser(path).to_string(),
+                overridden: self.watcher.parser_override(path).is_some(),
+            })
+            .collect()
+    }
+
+    /// Adjust execution limits for new executions
+    pub fn set_limits(&self, changes: LimitChanges) -> Result<SetLimitsResult> {
+        let resource_manager = self.resource_manager.as_ref().ok_or_else(|| {
+            crate::error::Error::Other("Resource limits are not adjustable here".to_string())
+        })?;
+
+        let limits = resource_manager.update_limits(&changes)?;
+        Ok(SetLimitsResult {
+            max_concurrent_executions: limits.max_concurrent_executions,
+            timeout_seconds: limits.max_execution_time.as_secs(),
+            max_output_size: limits.max_output_size,
+            bounds: limits.runtime_bounds,
+        })
+    }
+
+    /// Differences between the login shell and the recipe environment
+    pub async fn env_doctor(&self) -> Result<EnvironmentDiff> {
+        info!("Running environment diagnostic");
+        let shell = ShellEnvironment::capture().await?;
+        Ok(EnvironmentDiff::compare(
+            &shell,
+            &recipe_environment(&Default::default()),
+        ))
+    }
+
     pub async fn parser_doctor(&self, verbose: bool) -> Result<String> {
+        Ok(self.parser_doctor_report().await?.to_markdown(verbose))
+    }
+
+    /// Compare the recipes each parser finds with those `just` lists
+    pub async fn parser_doctor_report(&self) -> Result<ParserDoctorReport> {
         info!("Running parser diagnostic");
 
         // Find the main justfile to analyze
@@ -552,19 +1474,11 @@ impl AdminTools {
             })?;
 
             if path.is_dir() {
-                let justfile = path.join("justfile");
-                if justfile.exists() {
-                    justfile
-                } else {
-                    let justfile_cap = path.join("Justfile");
-                    if justfile_cap.exists() {
-                        justfile_cap
-                    } else {
-                        return Err(crate::error::Error::Other(
-                            "No justfile found in main watch directory".to_string(),
-                        ));
-                    }
-                }
+                find_justfile(path).ok_or_else(|| {
+                    crate::error::Error::Other(
+                        "No justfile found in main watch directory".to_string(),
+                    )
+                })?
             } else {
                 path.clone()
             }
@@ -573,19 +1487,27 @@ impl AdminTools {
         // Get expected recipes using `just --summary`
         let expected_recipes = self.get_expected_recipes(&justfile_path).await?;
 
-        // Test AST parser
-        let ast_result = self
-            .test_parser(&justfile_path, crate::parser::ParserPreference::Ast)
-            .await;
-
-        // Test CLI parser
-        let cli_result = self
-            .test_parser(&justfile_path, crate::parser::ParserPreference::Cli)
-            .await;
+        // Test the AST and CLI parsers
+        let mut parsers = Vec::new();
+        for preference in [
+            crate::parser::ParserPreference::Ast,
+            crate::parser::ParserPreference::Cli,
+        ] {
+            let mut result = self.test_parser(&justfile_path, preference).await;
+            result.missing_recipes = expected_recipes
+                .iter()
+                .filter(|recipe| !result.found_recipes.contains(recipe))
+                .cloned()
+                .collect();
+            parsers.push(result);
+        }
 
-        // Format and return results
-        self.format_diagnostic_report(&expected_recipes, &ast_result, &cli_result, verbose)
-            .await
+        Ok(ParserDoctorReport {
+            justfile: justfile_path.display().to_string(),
+            expected_recipes,
+            parsers,
+            effective_parsers: self.effective_parsers(),
+        })
     }
 
     async fn get_expected_recipes(&self, justfile_path: &std::path::Path) -> Result<Vec<String>> {
@@ -599,8 +1521,11 @@ impl AdminTools {
                     .unwrap_or_else(|| std::path::Path::new(".")),
             )
             .output()
-            .map_err(|e| {
-                crate::error::Error::Other(format!("Failed to execute 'just --summary': {e}"))
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => {
+                    crate::just_binary::not_installed("for 'just --summary'")
+                }
+                _ => crate::error::Error::Other(format!("Failed to execute 'just --summary': {e}")),
             })?;
 
         if !output.status.success() {
@@ -662,114 +1587,244 @@ impl AdminTools {
             },
         }
     }
+}
 
-    async fn format_diagnostic_report(
-        &self,
-        expected: &[String],
-        ast_result: &ParserDiagnosticResult,
-        cli_result: &ParserDiagnosticResult,
-        verbose: bool,
-    ) -> Result<String> {
-        let mut report = String::new();
+/// Copy a file to a dotfile backup next to it (`justfile` -> `.justfile.bak`)
+fn backup_file(path: &Path) -> Result<PathBuf> {
+    let backup_path = path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(format!(
+            ".{}.bak",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        ));
+    std::fs::copy(path, &backup_path)?;
+    Ok(backup_path)
+}
 
-        report.push_str("# Parser Diagnostic Report\n\n");
+/// Whether a relative path stays inside the directory it is relative to
+///
+/// Only `..` components leave it; names such as `ci..just` are fine.
+fn is_inside_directory(path: &str) -> bool {
+    let path = Path::new(path);
+    !path.is_absolute()
+        && path
+            .components()
+            .all(|component| !matches!(component, Component::ParentDir))
+}
 
-        // Calculate missing recipes for each parser
-        let ast_missing: Vec<&String> = expected
-            .iter()
-            .filter(|recipe| !ast_result.found_recipes.contains(recipe))
-            .collect();
+/// Resolve a file to add recipes to, which must be in the justfile's import graph
+///
+/// The target is given relative to the justfile and must stay inside its
+/// directory, so admin edits never reach files outside the project even if
+/// an import points there.
+fn import_target(justfile_path: &Path, target: &str) -> Result<PathBuf> {
+    if !is_inside_directory(target) {
+        return Err(crate::error::Error::InvalidParameter(format!(
+            "Target file must be a relative path inside the justfile directory: {target}"
+        )));
+    }
+    let base_dir = justfile_path.parent().unwrap_or_else(|| Path::new("."));
+    let target_path = base_dir.join(target);
+    let canonical = target_path.canonicalize().ok();
+
+    let imported = crate::parser::imports::resolve_import_graph(justfile_path)?
+        .into_iter()
+        .filter(|file| file.exists)
+        .any(|file| file.path.canonicalize().ok() == canonical);
+    if canonical.is_none() || !imported {
+        return Err(crate::error::Error::InvalidParameter(format!(
+            "Target file is not imported by {}: {target}",
+            justfile_path.display()
+        )));
+    }
+    Ok(target_path)
+}
 
-        let cli_missing: Vec<&String> = expected
-            .iter()
-            .filter(|recipe| !cli_result.found_recipes.contains(recipe))
-            .collect();
+/// Check that `just` accepts a justfile, skipping the check if `just` is missing
+fn validate_justfile(path: &Path) -> Result<()> {
+    if !crate::parser::EnhancedJustfileParser::is_just_available() {
+        warn!(
+            "just not available; skipping validation of {}",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let output = std::process::Command::new("just")
+        .arg("--justfile")
+        .arg(path)
+        .arg("--summary")
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(crate::error::Error::Other(format!(
+            "just rejected the edited justfile: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Check edited files with `just` in a temporary copy of the project
+///
+/// `files` is a justfile's import graph, root first. The copy mirrors the
+/// files' layout below their common ancestor so relative imports resolve.
+fn validate_staged(files: &[(PathBuf, String)]) -> Result<()> {
+    let paths: Vec<PathBuf> = files
+        .iter()
+        .map(|(path, _)| path.canonicalize())
+        .collect::<std::io::Result<_>>()?;
+    let mut root = paths[0]
+        .parent()
+        .unwrap_or_else(|| Path::new("/"))
+        .to_path_buf();
+    while !paths.iter().all(|path| path.starts_with(&root)) {
+        if !root.pop() {
+            break;
+        }
+    }
+
+    let staging = tempfile::TempDir::new()?;
+    for (path, (_, content)) in paths.iter().zip(files) {
+        let staged = staging
+            .path()
+            .join(path.strip_prefix(&root).unwrap_or(path));
+        if let Some(parent) = staged.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(staged, content)?;
+    }
+    validate_justfile(
+        &staging
+            .path()
+            .join(paths[0].strip_prefix(&root).unwrap_or(&paths[0])),
+    )
+}
+
+/// Replace a file's content by writing a sibling and renaming it over the file
+fn replace_file(path: &Path, content: &str) -> Result<()> {
+    let staged = path.with_file_name(format!(
+        ".{}.apply",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    std::fs::write(&staged, content)?;
+    if let Err(e) = std::fs::rename(&staged, path) {
+        let _ = std::fs::remove_file(&staged);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+fn not_found(recipe_name: &str, justfile_path: &Path) -> crate::error::Error {
+    crate::error::Error::InvalidParameter(format!(
+        "No recipe named '{recipe_name}' in {} or its imports",
+        justfile_path.display()
+    ))
+}
+
+#[derive(Debug, Serialize)]
+pub struct ParserDiagnosticResult {
+    pub parser_name: String,
+    pub found_recipes: Vec<String>,
+    /// Recipes `just` lists that the parser did not find
+    pub missing_recipes: Vec<String>,
+    pub parsing_errors: Vec<String>,
+}
+
+/// Result of `_admin_parser_doctor`
+#[derive(Debug, Serialize)]
+pub struct ParserDoctorReport {
+    /// Justfile of the first watch directory, which was analyzed
+    pub justfile: String,
+    /// Recipes listed by `just --summary`
+    pub expected_recipes: Vec<String>,
+    /// Recipes found by the AST and CLI parsers
+    pub parsers: Vec<ParserDiagnosticResult>,
+    /// Parser each watch directory is parsed with
+    pub effective_parsers: Vec<ProjectParser>,
+}
+
+impl ParserDoctorReport {
+    /// Render the report as Markdown, with per-parser issues if `verbose`
+    pub fn to_markdown(&self, verbose: bool) -> String {
+        let mut report = String::new();
+        let expected = self.expected_recipes.len();
+
+        report.push_str("# Parser Diagnostic Report\n\n");
 
         // Summary section
         report.push_str("## Summary\n");
-        report.push_str(&format!("- Expected: {}\n", expected.len()));
-        report.push_str(&format!(
-            "- AST parser: {} ({:.0}%) | Missing: {}\n",
-            ast_result.found_recipes.len(),
-            if expected.is_empty() {
-                0.0
-            } else {
-                (ast_result.found_recipes.len() as f64 / expected.len() as f64) * 100.0
-            },
-            ast_missing.len()
-        ));
-        report.push_str(&format!(
-            "- CLI parser: {} ({:.0}%) | Missing: {}\n",
-            cli_result.found_recipes.len(),
-            if expected.is_empty() {
-                0.0
-            } else {
-                (cli_result.found_recipes.len() as f64 / expected.len() as f64) * 100.0
-            },
-            cli_missing.len()
-        ));
+        report.push_str(&format!("- Expected: {expected}\n"));
+        for parser in &self.parsers {
+            report.push_str(&format!(
+                "- {} parser: {} ({:.0}%) | Missing: {}\n",
+                parser.parser_name,
+                parser.found_recipes.len(),
+                if expected == 0 {
+                    0.0
+                } else {
+                    (parser.found_recipes.len() as f64 / expected as f64) * 100.0
+                },
+                parser.missing_recipes.len()
+            ));
+        }
+
+        // Parser each watch directory is actually parsed with
+        report.push_str("\n## Effective Parsers\n");
+        for project in &self.effective_parsers {
+            report.push_str(&format!(
+                "- {}{}: {}{}\n",
+                project.watch_directory,
+                project
+                    .name
+                    .as_ref()
+                    .map(|name| format!(" ({name})"))
+                    .unwrap_or_default(),
+                project.parser,
+                if project.overridden {
+                    " (project override)"
+                } else {
+                    ""
+                }
+            ));
+        }
 
         // Verbose details
         if verbose {
-            // AST Parser Issues
-            report.push_str("\n## AST Parser Issues\n");
-            if ast_missing.is_empty() && ast_result.parsing_errors.is_empty() {
-                report.push_str("### No issues found\n");
-            } else {
-                if !ast_missing.is_empty() {
-                    report.push_str(&format!("### Missing Recipes ({}):\n", ast_missing.len()));
-                    for recipe in &ast_missing {
-                        report.push_str(&format!("- `{recipe}`\n"));
-                    }
+            for parser in &self.parsers {
+                report.push_str(&format!("\n## {} Parser Issues\n", parser.parser_name));
+                if parser.missing_recipes.is_empty() && parser.parsing_errors.is_empty() {
+                    report.push_str("### No issues found\n");
+                    continue;
                 }
 
-                if !ast_result.parsing_errors.is_empty() {
+                if !parser.missing_recipes.is_empty() {
                     report.push_str(&format!(
-                        "### Parsing Errors ({}):\n",
-                        ast_result.parsing_errors.len()
+                        "### Missing Recipes ({}):\n",
+                        parser.missing_recipes.len()
                     ));
-                    for error in &ast_result.parsing_errors {
-                        report.push_str(&format!("- {error}\n"));
-                    }
-                }
-            }
-
-            // CLI Parser Issues
-            report.push_str("\n## CLI Parser Issues\n");
-            if cli_missing.is_empty() && cli_result.parsing_errors.is_empty() {
-                report.push_str("### No issues found\n");
-            } else {
-                if !cli_missing.is_empty() {
-                    report.push_str(&format!("### Missing Recipes ({}):\n", cli_missing.len()));
-                    for recipe in &cli_missing {
+                    for recipe in &parser.missing_recipes {
                         report.push_str(&format!("- `{recipe}`\n"));
                     }
                 }
 
-                if !cli_result.parsing_errors.is_empty() {
+                if !parser.parsing_errors.is_empty() {
                     report.push_str(&format!(
                         "### Parsing Errors ({}):\n",
-                        cli_result.parsing_errors.len()
+                        parser.parsing_errors.len()
                     ));
-                    for error in &cli_result.parsing_errors {
+                    for error in &parser.parsing_errors {
                         report.push_str(&format!("- {error}\n"));
                     }
                 }
             }
         }
 
-        Ok(report)
+        report
     }
 }
 
-#[derive(Debug)]
-pub struct ParserDiagnosticResult {
-    pub found_recipes: Vec<String>,
-    pub missing_recipes: Vec<String>,
-    pub parsing_errors: Vec<String>,
-    pub parser_name: String,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SyncResult {
     pub scanned_files: usize,
@@ -786,19 +1841,153 @@ pub struct CreateRecipeParams {
     pub recipe: String,
     pub parameters: Option<Vec<RecipeParameter>>,
     pub dependencies: Option<Vec<String>>,
+    /// File to add the recipe to, relative to the justfile; must be in its
+    /// import graph [default: the justfile]
+    #[serde(default)]
+    pub target_file: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecipeParameter {
+    pub name: String,
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateRecipeResult {
+    pub recipe_name: String,
+    pub justfile_path: String,
+    pub backup_path: String,
+    /// Tools that changed, so clients can update their list without
+    /// fetching it again
+    pub registry_delta: RegistryDelta,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExtractGroupParams {
+    pub watch_name: Option<String>,
+    pub group: String,
+    /// File to create, relative to the justfile [default: `<group>.just`]
+    pub target_file: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExtractGroupResult {
+    pub group: String,
+    pub recipes: Vec<String>,
+    pub justfile_path: String,
+    pub import_path: String,
+    pub backup_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InlineImportParams {
+    pub watch_name: Option<String>,
+    /// Imported file, as written in the import or relative to the justfile
+    pub import_path: String,
+    /// Keep the imported file instead of deleting it
+    #[serde(default)]
+    pub keep_file: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InlineImportResult {
+    pub recipes: Vec<String>,
+    pub justfile_path: String,
+    pub import_path: String,
+    pub file_removed: bool,
+    pub backup_paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApplyChangesParams {
+    pub watch_name: Option<String>,
+    /// Applied in order, so later changes see earlier ones
+    pub changes: Vec<RecipeChange>,
 }
 
+/// One edit in an `_admin_apply_changes` batch
 #[derive(Debug, Serialize, Deserialize)]
-pub struct RecipeParameter {
-    pub name: String,
-    pub default: Option<String>,
+#[serde(tag = "action", rename_all = "snake_case", deny_unknown_fields)]
+pub enum RecipeChange {
+    /// Append a new recipe to the justfile, or to `target_file`
+    Create {
+        recipe_name: String,
+        description: Option<String>,
+        recipe: String,
+        parameters: Option<Vec<RecipeParameter>>,
+        dependencies: Option<Vec<String>>,
+        /// File to add the recipe to, relative to the justfile; must be in
+        /// its import graph [default: the justfile]
+        target_file: Option<String>,
+    },
+    /// Replace a recipe's header and body where it is defined, keeping its
+    /// attributes, and its comments unless a description is given
+    Modify {
+        recipe_name: String,
+        description: Option<String>,
+        recipe: String,
+        parameters: Option<Vec<RecipeParameter>>,
+        dependencies: Option<Vec<String>>,
+    },
+    /// Delete a recipe and its doc comments
+    Remove { recipe_name: String },
+}
+
+impl RecipeChange {
+    pub fn recipe_name(&self) -> &str {
+        match self {
+            Self::Create { recipe_name, .. }
+            | Self::Modify { recipe_name, .. }
+            | Self::Remove { recipe_name } => recipe_name,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub struct CreateRecipeResult {
-    pub recipe_name: String,
-    pub justfile_path: String,
-    pub backup_path: String,
+pub struct ApplyChangesResult {
+    /// One entry per change, e.g. "created deploy"
+    pub applied: Vec<String>,
+    pub changed_files: Vec<String>,
+    pub backup_paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SetLimitsResult {
+    pub max_concurrent_executions: usize,
+    pub timeout_seconds: u64,
+    pub max_output_size: usize,
+    /// Ceilings configured by the operator
+    pub bounds: LimitBounds,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RunningExecutionsResult {
+    /// Oldest first
+    pub executions: Vec<RunningExecution>,
+    /// Idle time after which an execution is flagged; 0 if flagging is off
+    pub hang_timeout_seconds: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusResult {
+    pub watch_directories: Vec<String>,
+    /// Registered tools, excluding admin tools
+    pub tool_count: usize,
+    pub watcher: WatcherStats,
+    pub parsers: Vec<ProjectParser>,
+    /// Whether `just` is installed, and how to install it if not
+    pub just: JustBinary,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectParser {
+    pub watch_directory: String,
+    pub name: Option<String>,
+    /// Parser preference justfiles in this directory are parsed with
+    pub parser: String,
+    /// Whether `parser` comes from a `--project-parser` override
+    pub overridden: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -834,6 +2023,152 @@ mod tests {
         assert!(tools.iter().any(|t| t.name == "_admin_sync"));
     }
 
+    #[tokio::test]
+    async fn test_inline_justfile_tool_is_opt_in() {
+        for enabled in [false, true] {
+            let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+            let watcher = Arc::new(JustfileWatcher::new(registry.clone()));
+            AdminTools::new(registry.clone(), watcher, vec![], vec![])
+                .with_inline_justfiles(enabled)
+                .register_admin_tools()
+                .await
+                .unwrap();
+            let registered = registry
+                .lock()
+                .await
+                .get_tool("_admin_run_inline_justfile")
+                .is_some();
+            assert_eq!(registered, enabled);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_watcher_stats() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("justfile"),
+            "build:\n    echo build\n\ntest:\n    echo test\n",
+        )
+        .unwrap();
+
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = Arc::new(JustfileWatcher::new(registry.clone()));
+        let admin_tools = AdminTools::new(
+            registry.clone(),
+            watcher,
+            vec![temp_dir.path().to_path_buf()],
+            vec![(temp_dir.path().to_path_buf(), None)],
+        );
+        admin_tools.register_admin_tools().await.unwrap();
+        admin_tools.sync().await.unwrap();
+
+        let status = admin_tools.status().await.unwrap();
+        assert_eq!(status.tool_count, 2);
+        assert_eq!(status.watch_directories.len(), 1);
+        assert_eq!(status.watcher.queue_capacity, 100);
+        assert_eq!(status.watcher.events_dropped, 0);
+
+        assert_eq!(status.parsers.len(), 1);
+        assert!(!status.parsers[0].overridden);
+
+        let json = serde_json::to_value(&status).unwrap();
+        assert!(json["watcher"]["last_events"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_access_log_tool() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = Arc::new(JustfileWatcher::new(registry.clone()));
+        let admin_tools = AdminTools::new(registry.clone(), watcher, vec![], vec![]);
+        admin_tools.register_admin_tools().await.unwrap();
+        assert!(registry
+            .lock()
+            .await
+            .get_tool("_admin_access_log")
+            .is_some());
+        assert!(admin_tools.access_log(None, 20).is_err());
+
+        let access_log = Arc::new(AccessLog::default());
+        access_log.open_session("s1", "http");
+        access_log.open_session("s2", "http");
+        let admin_tools = admin_tools.with_access_log(access_log);
+
+        let report = admin_tools.access_log(Some("s2"), 20).unwrap();
+        assert_eq!(report.sessions.len(), 1);
+        assert_eq!(report.sessions[0].session, "s2");
+        assert_eq!(report.recent_events.len(), 1);
+        assert_eq!(admin_tools.access_log(None, 20).unwrap().sessions.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_running_executions_tools() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = Arc::new(JustfileWatcher::new(registry.clone()));
+        let admin_tools = AdminTools::new(registry.clone(), watcher, vec![], vec![]);
+        admin_tools.register_admin_tools().await.unwrap();
+        for name in ["_admin_running_executions", "_admin_cancel_execution"] {
+            assert!(registry.lock().await.get_tool(name).is_some(), "{name}");
+        }
+        assert!(admin_tools.running_executions().is_err());
+
+        let watchdog = Arc::new(ExecutionWatchdog::default());
+        let admin_tools = admin_tools.with_watchdog(watchdog.clone());
+        let execution = watchdog.start("deploy");
+
+        let report = admin_tools.running_executions().unwrap();
+        assert_eq!(report.hang_timeout_seconds, 120);
+        assert_eq!(report.executions.len(), 1);
+        assert_eq!(report.executions[0].recipe, "deploy");
+
+        let cancelled = admin_tools.cancel_execution(execution.id()).unwrap();
+        assert_eq!(cancelled.id, execution.id());
+        tokio::time::timeout(std::time::Duration::from_secs(1), execution.cancelled())
+            .await
+            .expect("execution should be told to stop");
+        assert!(admin_tools.cancel_execution(execution.id() + 1).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_limits_within_bounds() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = Arc::new(JustfileWatcher::new(registry.clone()));
+        let admin_tools = AdminTools::new(registry.clone(), watcher.clone(), vec![], vec![]);
+        assert!(admin_tools.set_limits(LimitChanges::default()).is_err());
+
+        let resource_manager = Arc::new(ResourceManager::with_default());
+        let admin_tools = AdminTools::new(registry, watcher, vec![], vec![])
+            .with_resource_manager(resource_manager.clone());
+
+        let result = admin_tools
+            .set_limits(LimitChanges {
+                timeout_seconds: Some(1800),
+                max_concurrent_executions: Some(2),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(result.timeout_seconds, 1800);
+        assert_eq!(result.max_concurrent_executions, 2);
+        assert_eq!(result.max_output_size, 10 * 1024 * 1024);
+        assert_eq!(
+            resource_manager.get_timeout(),
+            std::time::Duration::from_secs(1800)
+        );
+
+        // Out-of-bounds changes are rejected as a whole
+        let err = admin_tools
+            .set_limits(LimitChanges {
+                timeout_seconds: Some(60),
+                max_concurrent_executions: Some(1000),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("max_concurrent_executions"));
+        assert_eq!(
+            resource_manager.get_timeout(),
+            std::time::Duration::from_secs(1800)
+        );
+    }
+
     #[tokio::test]
     async fn test_sync_functionality() {
         let temp_dir = TempDir::new().unwrap();
@@ -888,6 +2223,73 @@ build:
         );
     }
 
+    /// Whether a notification is waiting
+    fn pending(receiver: &mut crate::notification::NotificationReceiver) -> bool {
+        futures::FutureExt::now_or_never(receiver.recv()).is_some()
+    }
+
+    #[tokio::test]
+    async fn test_create_recipe_reports_registry_delta() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "build:\n    echo build\n\ntest:\n    echo test\n",
+        )
+        .unwrap();
+
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let (sender, mut receiver) = crate::notification::channel();
+        let watcher =
+            Arc::new(JustfileWatcher::new(registry.clone()).with_notification_sender(sender));
+        watcher
+            .parse_and_update_justfile_without_notification(&justfile_path)
+            .await
+            .unwrap();
+        let admin_tools = AdminTools::new(
+            registry.clone(),
+            watcher.clone(),
+            vec![temp_dir.path().to_path_buf()],
+            vec![(temp_dir.path().to_path_buf(), None)],
+        );
+
+        let mut changes = registry.lock().await.subscribe_changes();
+        let result = admin_tools
+            .create_recipe(CreateRecipeParams {
+                watch_name: None,
+                recipe_name: "lint".to_string(),
+                description: None,
+                recipe: "echo lint".to_string(),
+                parameters: None,
+                dependencies: None,
+                target_file: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.registry_delta.added, ["lint"]);
+        assert!(result.registry_delta.modified.is_empty());
+        assert!(result.registry_delta.removed.is_empty());
+        assert_eq!(result.registry_delta.unchanged, 2);
+        assert_eq!(changes.try_recv().unwrap().tool_name, "lint");
+        assert!(changes.try_recv().is_err());
+        assert!(pending(&mut receiver));
+        assert!(!pending(&mut receiver));
+
+        // Parsing the unchanged file again, as the watcher does on the
+        // write event, reports nothing and sends no notification
+        let delta = watcher
+            .parse_and_diff_justfile(&justfile_path)
+            .await
+            .unwrap();
+        assert!(delta.is_empty());
+        watcher
+            .parse_and_update_justfile(&justfile_path)
+            .await
+            .unwrap();
+        assert!(!pending(&mut receiver));
+    }
+
     #[tokio::test]
     async fn test_create_recipe() {
         let temp_dir = TempDir::new().unwrap();
@@ -921,6 +2323,7 @@ existing:
                 default: Some("world".to_string()),
             }]),
             dependencies: Some(vec!["existing".to_string()]),
+            target_file: None,
         };
 
         let result = admin_tools.create_recipe(params).await.unwrap();
@@ -958,6 +2361,70 @@ existing:
         assert!(!new_recipe_tool.description.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_create_recipe_in_imported_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "import 'ci.just'\nimport 'ci..just'\n\nbuild:\n    echo build\n",
+        )
+        .unwrap();
+        let ci_path = temp_dir.path().join("ci.just");
+        fs::write(&ci_path, "lint:\n    echo lint\n").unwrap();
+        fs::write(temp_dir.path().join("ci..just"), "").unwrap();
+        fs::write(temp_dir.path().join("other.just"), "").unwrap();
+
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = Arc::new(JustfileWatcher::new(registry.clone()));
+        let admin_tools = AdminTools::new(
+            registry.clone(),
+            watcher,
+            vec![temp_dir.path().to_path_buf()],
+            vec![(temp_dir.path().to_path_buf(), None)],
+        );
+        let params = |name: &str, target: &str| CreateRecipeParams {
+            watch_name: None,
+            recipe_name: name.to_string(),
+            description: None,
+            recipe: "echo test".to_string(),
+            parameters: None,
+            dependencies: None,
+            target_file: Some(target.to_string()),
+        };
+
+        let result = admin_tools
+            .create_recipe(params("test", "ci.just"))
+            .await
+            .unwrap();
+        assert_eq!(PathBuf::from(&result.justfile_path), ci_path);
+        assert!(result.backup_path.ends_with(".ci.just.bak"));
+        assert!(fs::read_to_string(&ci_path)
+            .unwrap()
+            .contains("test:\n    echo test\n"));
+        assert!(!fs::read_to_string(&justfile_path)
+            .unwrap()
+            .contains("test:"));
+
+        // Dots inside a file name don't leave the directory
+        admin_tools
+            .create_recipe(params("dotted", "ci..just"))
+            .await
+            .unwrap();
+
+        // Files outside the import graph or the justfile directory are rejected
+        for target in ["other.just", "missing.just", "../ci.just", "/etc/hosts"] {
+            let err = admin_tools
+                .create_recipe(params("other", target))
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, crate::error::Error::InvalidParameter(_)),
+                "{target}: {err}"
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_create_recipe_validation() {
         let temp_dir = TempDir::new().unwrap();
@@ -994,6 +2461,7 @@ existing:
             recipe: "echo \"duplicate\"".to_string(),
             parameters: None,
             dependencies: None,
+            target_file: None,
         };
 
         let result = admin_tools.create_recipe(params).await;
@@ -1008,6 +2476,7 @@ existing:
             recipe: "echo \"admin\"".to_string(),
             parameters: None,
             dependencies: None,
+            target_file: None,
         };
 
         let result = admin_tools.create_recipe(params).await;
@@ -1015,6 +2484,173 @@ existing:
         assert!(result.unwrap_err().to_string().contains("reserved"));
     }
 
+    #[tokio::test]
+    async fn test_create_recipe_waits_for_project_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(&justfile_path, "build:\n    echo build\n").unwrap();
+
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = Arc::new(JustfileWatcher::new(registry.clone()));
+        let lock_config = LockConfig {
+            timeout: std::time::Duration::from_millis(100),
+            ..Default::default()
+        };
+        let admin_tools = AdminTools::new(
+            registry.clone(),
+            watcher,
+            vec![temp_dir.path().to_path_buf()],
+            vec![(temp_dir.path().to_path_buf(), None)],
+        )
+        .with_lock_config(lock_config.clone());
+
+        let params = || CreateRecipeParams {
+            watch_name: None,
+            recipe_name: "deploy".to_string(),
+            description: None,
+            recipe: "echo deploy".to_string(),
+            parameters: None,
+            dependencies: None,
+            target_file: None,
+        };
+
+        // Another instance is editing the project
+        let lock = ProjectLock::acquire(temp_dir.path(), "other instance", &lock_config)
+            .await
+            .unwrap();
+        let err = admin_tools.create_recipe(params()).await.unwrap_err();
+        assert!(err.to_string().contains("other instance"));
+        assert!(!fs::read_to_string(&justfile_path)
+            .unwrap()
+            .contains("deploy"));
+
+        drop(lock);
+        admin_tools.create_recipe(params()).await.unwrap();
+        assert!(fs::read_to_string(&justfile_path)
+            .unwrap()
+            .contains("deploy:"));
+        assert!(!temp_dir
+            .path()
+            .join(crate::project_lock::LOCK_FILE_NAME)
+            .exists());
+    }
+
+    fn stress_admin_tools(temp_dir: &TempDir) -> Arc<AdminTools> {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = Arc::new(JustfileWatcher::new(registry.clone()));
+        Arc::new(AdminTools::new(
+            registry,
+            watcher,
+            vec![temp_dir.path().to_path_buf()],
+            vec![(temp_dir.path().to_path_buf(), None)],
+        ))
+    }
+
+    fn create_params(recipe_name: &str) -> CreateRecipeParams {
+        CreateRecipeParams {
+            watch_name: None,
+            recipe_name: recipe_name.to_string(),
+            description: None,
+            recipe: format!("echo {recipe_name}"),
+            parameters: None,
+            dependencies: None,
+            target_file: None,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_admin_edits_are_serialized() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(&justfile_path, "build:\n    echo build\n").unwrap();
+        let admin_tools = stress_admin_tools(&temp_dir);
+        admin_tools.sync().await.unwrap();
+
+        let mut calls = Vec::new();
+        for i in 0..12 {
+            let admin_tools = admin_tools.clone();
+            calls.push(tokio::spawn(async move {
+                if i % 3 == 0 {
+                    let changes = vec![RecipeChange::Create {
+                        recipe_name: format!("batch{i}"),
+                        description: None,
+                        recipe: "echo batch".to_string(),
+                        parameters: None,
+                        dependencies: None,
+                        target_file: None,
+                    }];
+                    admin_tools
+                        .apply_changes(ApplyChangesParams {
+                            watch_name: None,
+                            changes,
+                        })
+                        .await
+                        .map(|_| ())
+                } else {
+                    admin_tools
+                        .create_recipe(create_params(&format!("recipe{i}")))
+                        .await
+                        .map(|_| ())
+                }
+            }));
+        }
+        for call in calls {
+            call.await.unwrap().unwrap();
+        }
+
+        // Every edit landed exactly once, in a justfile `just` still accepts
+        let content = fs::read_to_string(&justfile_path).unwrap();
+        let mut recipes: Vec<String> = refactor::recipe_blocks(&content)
+            .into_iter()
+            .map(|block| block.name)
+            .collect();
+        recipes.sort();
+        let mut expected: Vec<String> = (0..12)
+            .map(|i| match i % 3 {
+                0 => format!("batch{i}"),
+                _ => format!("recipe{i}"),
+            })
+            .chain(std::iter::once("build".to_string()))
+            .collect();
+        expected.sort();
+        assert_eq!(recipes, expected);
+        validate_justfile(&justfile_path).unwrap();
+        assert!(!temp_dir
+            .path()
+            .join(crate::project_lock::LOCK_FILE_NAME)
+            .exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_creates_of_one_recipe() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(&justfile_path, "build:\n    echo build\n").unwrap();
+        let admin_tools = stress_admin_tools(&temp_dir);
+        admin_tools.sync().await.unwrap();
+
+        let calls: Vec<_> = (0..6)
+            .map(|_| {
+                let admin_tools = admin_tools.clone();
+                tokio::spawn(
+                    async move { admin_tools.create_recipe(create_params("deploy")).await },
+                )
+            })
+            .collect();
+        let mut created = 0;
+        for call in calls {
+            match call.await.unwrap() {
+                Ok(_) => created += 1,
+                Err(e) => assert!(e.to_string().contains("already exists"), "{e}"),
+            }
+        }
+
+        // The name check runs under the lock, so only the first call wins
+        assert_eq!(created, 1);
+        let content = fs::read_to_string(&justfile_path).unwrap();
+        assert_eq!(content.matches("deploy:").count(), 1);
+    }
+
     #[tokio::test]
     async fn test_create_recipe_with_named_dirs() {
         let temp_dir1 = TempDir::new().unwrap();
@@ -1049,6 +2685,7 @@ existing:
             recipe: "npm run build".to_string(),
             parameters: None,
             dependencies: None,
+            target_file: None,
         };
 
         let result = admin_tools.create_recipe(params).await.unwrap();
@@ -1062,6 +2699,204 @@ existing:
         assert!(content.contains("npm run build"));
     }
 
+    #[tokio::test]
+    async fn test_extract_group_and_inline_import() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        let content = r#"# Build the project
+build:
+    echo build
+
+# Run the linter
+[group('ci')]
+lint:
+    echo lint
+
+[group('ci')]
+audit: lint
+    echo audit
+"#;
+        fs::write(&justfile_path, content).unwrap();
+
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = Arc::new(JustfileWatcher::new(registry.clone()));
+        let admin_tools = AdminTools::new(
+            registry.clone(),
+            watcher,
+            vec![temp_dir.path().to_path_buf()],
+            vec![(temp_dir.path().to_path_buf(), None)],
+        );
+
+        let result = admin_tools
+            .extract_group(ExtractGroupParams {
+                watch_name: None,
+                group: "ci".to_string(),
+                target_file: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.recipes, vec!["lint", "audit"]);
+
+        let remaining = fs::read_to_string(&justfile_path).unwrap();
+        assert!(remaining.starts_with("import 'ci.just'\n\n# Build the project"));
+        assert!(!remaining.contains("lint"));
+        let extracted = fs::read_to_string(temp_dir.path().join("ci.just")).unwrap();
+        assert!(extracted.starts_with("# Run the linter\n[group('ci')]\nlint:"));
+        assert!(temp_dir.path().join(".justfile.bak").exists());
+
+        // The target file must not be overwritten
+        let err = admin_tools
+            .extract_group(ExtractGroupParams {
+                watch_name: None,
+                group: "ci".to_string(),
+                target_file: None,
+            })
+            .await;
+        assert!(err.is_err());
+
+        let result = admin_tools
+            .inline_import(InlineImportParams {
+                watch_name: None,
+                import_path: "ci.just".to_string(),
+                keep_file: false,
+            })
+            .await
+            .unwrap();
+        assert!(result.file_removed);
+        assert_eq!(result.backup_paths.len(), 2);
+        assert!(!temp_dir.path().join("ci.just").exists());
+        assert!(temp_dir.path().join(".ci.just.bak").exists());
+
+        let inlined = fs::read_to_string(&justfile_path).unwrap();
+        assert!(!inlined.contains("import"));
+        assert!(inlined.contains("[group('ci')]\naudit: lint"));
+
+        let err = admin_tools
+            .inline_import(InlineImportParams {
+                watch_name: None,
+                import_path: "ci.just".to_string(),
+                keep_file: false,
+            })
+            .await;
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_changes_is_atomic() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        let ci_path = temp_dir.path().join("ci.just");
+        let content = "import 'ci.just'\n\n# Build the project\nbuild:\n    echo build\n\nclean:\n    echo clean\n";
+        fs::write(&justfile_path, content).unwrap();
+        fs::write(&ci_path, "[group('ci')]\nlint:\n    echo lint\n").unwrap();
+
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = Arc::new(JustfileWatcher::new(registry.clone()));
+        let admin_tools = AdminTools::new(
+            registry.clone(),
+            watcher,
+            vec![temp_dir.path().to_path_buf()],
+            vec![(temp_dir.path().to_path_buf(), None)],
+        );
+        let changes = |changes: serde_json::Value| ApplyChangesParams {
+            watch_name: None,
+            changes: serde_json::from_value(changes).unwrap(),
+        };
+
+        // A failing change leaves every file untouched
+        let err = admin_tools
+            .apply_changes(changes(json!([
+                {"action": "create", "recipe_name": "test", "recipe": "echo test"},
+                {"action": "remove", "recipe_name": "missing"}
+            ])))
+            .await;
+        assert!(err.is_err());
+        assert_eq!(fs::read_to_string(&justfile_path).unwrap(), content);
+        assert!(!temp_dir.path().join(".justfile.bak").exists());
+
+        let result = admin_tools
+            .apply_changes(changes(json!([
+                {"action": "create", "recipe_name": "test", "recipe": "echo test", "dependencies": ["build"]},
+                {"action": "modify", "recipe_name": "lint", "recipe": "echo lint --strict", "dependencies": ["build"]},
+                {"action": "remove", "recipe_name": "clean"},
+                {"action": "create", "recipe_name": "audit", "recipe": "echo audit", "target_file": "ci.just"}
+            ])))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.applied,
+            vec![
+                "created test",
+                "modified lint",
+                "removed clean",
+                "created audit"
+            ]
+        );
+        assert_eq!(result.changed_files.len(), 2);
+        assert_eq!(result.backup_paths.len(), 2);
+
+        let justfile = fs::read_to_string(&justfile_path).unwrap();
+        assert!(justfile.ends_with("    echo build\n\ntest: build\n    echo test\n"));
+        assert!(!justfile.contains("clean"));
+        assert_eq!(
+            fs::read_to_string(&ci_path).unwrap(),
+            "[group('ci')]\nlint: build\n    echo lint --strict\n\naudit:\n    echo audit\n"
+        );
+        // build, test, lint and audit, from one re-sync
+        assert_eq!(registry.lock().await.list_tools().len(), 4);
+
+        // A batch `just` rejects is not written either
+        if crate::parser::EnhancedJustfileParser::is_just_available() {
+            let before = fs::read_to_string(&justfile_path).unwrap();
+            let err = admin_tools
+                .apply_changes(changes(json!([
+                    {"action": "modify", "recipe_name": "test", "recipe": "echo test", "dependencies": ["nonexistent"]}
+                ])))
+                .await;
+            assert!(err.unwrap_err().to_string().contains("just rejected"));
+            assert_eq!(fs::read_to_string(&justfile_path).unwrap(), before);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gc_after_watch_directory_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let old_dir = temp_dir.path().join("old");
+        let new_dir = temp_dir.path().join("new");
+        fs::create_dir_all(&old_dir).unwrap();
+        fs::create_dir_all(&new_dir).unwrap();
+        fs::write(old_dir.join("justfile"), "build:\n    echo old\n").unwrap();
+        fs::write(new_dir.join("justfile"), "deploy:\n    echo new\n").unwrap();
+
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = Arc::new(JustfileWatcher::new(registry.clone()));
+        let admin_tools = AdminTools::new(
+            registry.clone(),
+            watcher,
+            vec![old_dir.clone()],
+            vec![(old_dir.clone(), None)],
+        );
+        admin_tools.register_admin_tools().await.unwrap();
+        admin_tools.sync().await.unwrap();
+        admin_tools
+            .set_watch_directory(SetWatchDirectoryParams {
+                path: new_dir.to_string_lossy().to_string(),
+            })
+            .await
+            .unwrap();
+
+        let report = admin_tools.gc(true).await.unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.stale_sources, vec!["build"]);
+        assert!(report.orphan_tools.is_empty());
+
+        admin_tools.gc(false).await.unwrap();
+        assert!(admin_tools.gc(false).await.unwrap().is_clean());
+        let registry = registry.lock().await;
+        assert!(registry.get_tool("deploy").is_some());
+        assert!(registry.get_tool("_admin_gc").is_some());
+    }
+
     #[tokio::test]
     async fn test_set_watch_directory_with_justfile() {
         let temp_dir = TempDir::new().unwrap();
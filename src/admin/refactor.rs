@@ -0,0 +1,492 @@
+//! Source-level justfile restructuring
+//!
+//! Moves recipes between a justfile and the files it imports without
+//! reformatting anything else: recipe blocks (the comment and attribute lines
+//! directly above a header, the header and its body) are cut and pasted as
+//! written. File IO, backups and validation are handled by
+//! [`AdminTools`](super::AdminTools).
+
+use crate::error::{Error, Result};
+use crate::parser::annotations::{extract_annotations, recipe_header_name};
+use crate::parser::imports::extract_imports;
+use std::path::Path;
+
+use super::RecipeParameter;
+
+/// Line range of a recipe in justfile source, including its doc block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecipeBlock {
+    pub name: String,
+    /// First line of the comment and attribute block above the header
+    pub start: usize,
+    /// Line after the last body line
+    pub end: usize,
+}
+
+/// Result of splitting a group's recipes out of a justfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupExtraction {
+    /// The justfile without the group's recipes, with the import added
+    pub remaining: String,
+    /// Content for the new imported file
+    pub extracted: String,
+    /// Names of the moved recipes, in source order
+    pub recipes: Vec<String>,
+}
+
+/// Locate every recipe block in justfile content
+pub fn recipe_blocks(content: &str) -> Vec<RecipeBlock> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut blocks = Vec::new();
+    let mut doc_start = None;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || line.starts_with(char::is_whitespace) {
+            doc_start = None;
+        } else if trimmed.starts_with('#') || trimmed.starts_with('[') {
+            doc_start.get_or_insert(i);
+        } else if let Some(name) = recipe_header_name(trimmed) {
+            let start = doc_start.take().unwrap_or(i);
+            let mut end = i + 1;
+            let mut last_body = i;
+            while end < lines.len() {
+                let body_line = lines[end];
+                if body_line.trim().is_empty() {
+                    end += 1;
+                } else if body_line.starts_with(char::is_whitespace) {
+                    last_body = end;
+                    end += 1;
+                } else {
+                    break;
+                }
+            }
+            blocks.push(RecipeBlock {
+                name,
+                start,
+                end: last_body + 1,
+            });
+            i = last_body + 1;
+            continue;
+        } else {
+            doc_start = None;
+        }
+        i += 1;
+    }
+
+    blocks
+}
+
+/// Move every recipe in `group` into a new file imported as `import_path`
+///
+/// Group membership comes from `[group('name')]` attributes or
+/// `# [group: name]` comment annotations. The import statement is placed
+/// after the last existing import, or before the first remaining recipe.
+pub fn extract_group(content: &str, group: &str, import_path: &str) -> Result<GroupExtraction> {
+    let annotations = extract_annotations(content);
+    let in_group = |name: &str| {
+        annotations.get(name).is_some_and(|found| {
+            found
+                .iter()
+                .any(|a| a.name == "group" && a.values().iter().any(|v| v == group))
+        })
+    };
+
+    let blocks: Vec<RecipeBlock> = recipe_blocks(content)
+        .into_iter()
+        .filter(|block| in_group(&block.name))
+        .collect();
+    if blocks.is_empty() {
+        return Err(Error::InvalidParameter(format!(
+            "No recipes found in group '{group}'"
+        )));
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let extracted = blocks
+        .iter()
+        .map(|block| lines[block.start..block.end].join("\n"))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n";
+
+    // Drop the moved lines, plus the blank lines that separated them
+    let mut removed = vec![false; lines.len()];
+    for block in &blocks {
+        let mut end = block.end;
+        let preceded_by_blank = block.start == 0 || lines[block.start - 1].trim().is_empty();
+        while preceded_by_blank && end < lines.len() && lines[end].trim().is_empty() {
+            end += 1;
+        }
+        removed[block.start..end].fill(true);
+    }
+    let mut remaining: Vec<&str> = lines
+        .iter()
+        .zip(&removed)
+        .filter(|(_, removed)| !**removed)
+        .map(|(line, _)| *line)
+        .collect();
+    while remaining.last().is_some_and(|l| l.trim().is_empty()) {
+        remaining.pop();
+    }
+
+    let import_line = format!("import '{import_path}'");
+    let remaining_content = remaining.join("\n");
+    let last_import = remaining
+        .iter()
+        .rposition(|line| !line.starts_with(char::is_whitespace) && is_import_line(line));
+    let first_recipe = recipe_blocks(&remaining_content).first().map(|b| b.start);
+
+    match (last_import, first_recipe) {
+        (Some(index), _) => remaining.insert(index + 1, &import_line),
+        (None, Some(index)) => {
+            remaining.insert(index, "");
+            remaining.insert(index, &import_line);
+        }
+        (None, None) => {
+            if !remaining.is_empty() {
+                remaining.push("");
+            }
+            remaining.push(&import_line);
+        }
+    }
+
+    Ok(GroupExtraction {
+        remaining: remaining.join("\n") + "\n",
+        extracted,
+        recipes: blocks.into_iter().map(|b| b.name).collect(),
+    })
+}
+
+/// Find the line of the `import` statement that resolves to `target`
+///
+/// Relative import paths are resolved against `base_dir`, the directory of
+/// the justfile containing them. `mod` statements are not matched, since
+/// inlining a module would change its recipes' namespace.
+pub fn find_import_line(content: &str, base_dir: &Path, target: &Path) -> Option<usize> {
+    let target = target.canonicalize().ok()?;
+    content.lines().position(|line| {
+        if line.starts_with(char::is_whitespace) || !is_import_line(line) {
+            return false;
+        }
+        extract_imports(line)
+            .into_iter()
+            .filter_map(|statement| statement.path)
+            .any(|path| {
+                base_dir
+                    .join(path)
+                    .canonicalize()
+                    .is_ok_and(|path| path == target)
+            })
+    })
+}
+
+/// Replace the import statement on `line` with the imported file's content
+pub fn inline_import(content: &str, line: usize, imported: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut result: Vec<&str> = lines[..line].to_vec();
+
+    let imported = imported.trim_matches('\n');
+    if result.last().is_some_and(|l| !l.trim().is_empty()) {
+        result.push("");
+    }
+    result.extend(imported.lines());
+    if lines.get(line + 1).is_some_and(|l| !l.trim().is_empty()) {
+        result.push("");
+    }
+    result.extend(&lines[line + 1..]);
+
+    result.join("\n") + "\n"
+}
+
+/// Source for a recipe: its description comment, header and indented body
+pub fn recipe_source(
+    name: &str,
+    description: Option<&str>,
+    parameters: &[RecipeParameter],
+    dependencies: &[String],
+    body: &str,
+) -> String {
+    let mut source = String::new();
+    if let Some(description) = description {
+        source.push_str(&format!("# {description}\n"));
+    }
+    source.push_str(name);
+    for parameter in parameters {
+        source.push(' ');
+        source.push_str(&parameter.name);
+        if let Some(default) = &parameter.default {
+            source.push_str(&format!("=\"{default}\""));
+        }
+    }
+    source.push(':');
+    if !dependencies.is_empty() {
+        source.push(' ');
+        source.push_str(&dependencies.join(" "));
+    }
+    source.push('\n');
+    for line in body.lines() {
+        source.push_str("    ");
+        source.push_str(line);
+        source.push('\n');
+    }
+    source
+}
+
+/// Append a recipe to the end of justfile content, after a blank line
+pub fn append_recipe(content: &str, recipe: &str) -> String {
+    let mut result = content.to_string();
+    if !result.is_empty() {
+        if !result.ends_with('\n') {
+            result.push('\n');
+        }
+        result.push('\n');
+    }
+    result.push_str(recipe);
+    result
+}
+
+/// Replace a recipe's header and body with `recipe`
+///
+/// Attribute lines above the recipe are kept. So are its comments, unless
+/// `recipe` starts with a new description comment.
+pub fn replace_recipe(content: &str, name: &str, recipe: &str) -> Result<String> {
+    let block = find_recipe(content, name)?;
+    let lines: Vec<&str> = content.lines().collect();
+    let doc = &lines[block.start..block.end];
+    let header = doc
+        .iter()
+        .position(|line| !line.starts_with('#') && !line.starts_with('['))
+        .unwrap_or(0);
+
+    let new_lines: Vec<&str> = recipe.lines().collect();
+    let new_comments = new_lines
+        .iter()
+        .take_while(|line| line.starts_with('#'))
+        .count();
+    let comments: Vec<&str> = if new_comments > 0 {
+        new_lines[..new_comments].to_vec()
+    } else {
+        doc[..header]
+            .iter()
+            .filter(|line| line.starts_with('#'))
+            .copied()
+            .collect()
+    };
+    let attributes = doc[..header].iter().filter(|line| line.starts_with('['));
+
+    let mut result: Vec<&str> = lines[..block.start].to_vec();
+    result.extend(comments);
+    result.extend(attributes);
+    result.extend(&new_lines[new_comments..]);
+    result.extend(&lines[block.end..]);
+    Ok(result.join("\n") + "\n")
+}
+
+/// Remove a recipe, with its doc block and the blank lines after it
+pub fn remove_recipe(content: &str, name: &str) -> Result<String> {
+    let block = find_recipe(content, name)?;
+    let lines: Vec<&str> = content.lines().collect();
+    let mut end = block.end;
+    while end < lines.len() && lines[end].trim().is_empty() {
+        end += 1;
+    }
+
+    let mut result: Vec<&str> = lines[..block.start].to_vec();
+    result.extend(&lines[end..]);
+    while result.last().is_some_and(|l| l.trim().is_empty()) {
+        result.pop();
+    }
+    if result.is_empty() {
+        return Ok(String::new());
+    }
+    Ok(result.join("\n") + "\n")
+}
+
+/// Whether justfile content defines a recipe
+pub fn has_recipe(content: &str, name: &str) -> bool {
+    recipe_blocks(content)
+        .iter()
+        .any(|block| block.name == name)
+}
+
+fn find_recipe(content: &str, name: &str) -> Result<RecipeBlock> {
+    recipe_blocks(content)
+        .into_iter()
+        .find(|block| block.name == name)
+        .ok_or_else(|| Error::InvalidParameter(format!("No recipe named '{name}' found")))
+}
+
+fn is_import_line(line: &str) -> bool {
+    extract_imports(line)
+        .iter()
+        .any(|statement| statement.module.is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const JUSTFILE: &str = r#"set shell := ["bash", "-c"]
+
+version := "1.0"
+
+# Build the project
+build:
+    cargo build
+
+# Run clippy
+[group('ci')]
+lint:
+    cargo clippy
+
+    cargo fmt --check
+
+test:
+    cargo test
+
+[group('ci')]
+audit: lint
+    cargo audit
+"#;
+
+    #[test]
+    fn test_recipe_blocks() {
+        let blocks = recipe_blocks(JUSTFILE);
+        let ranges: Vec<_> = blocks
+            .iter()
+            .map(|b| (b.name.as_str(), b.start, b.end))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                ("build", 4, 7),
+                ("lint", 8, 14),
+                ("test", 15, 17),
+                ("audit", 18, 21),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_group() {
+        let result = extract_group(JUSTFILE, "ci", "ci.just").unwrap();
+        assert_eq!(result.recipes, vec!["lint", "audit"]);
+        assert_eq!(
+            result.extracted,
+            "# Run clippy\n[group('ci')]\nlint:\n    cargo clippy\n\n    cargo fmt --check\n\n[group('ci')]\naudit: lint\n    cargo audit\n"
+        );
+        assert_eq!(
+            result.remaining,
+            "set shell := [\"bash\", \"-c\"]\n\nversion := \"1.0\"\n\nimport 'ci.just'\n\n# Build the project\nbuild:\n    cargo build\n\ntest:\n    cargo test\n"
+        );
+
+        assert!(extract_group(JUSTFILE, "release", "release.just").is_err());
+    }
+
+    #[test]
+    fn test_extract_group_after_existing_imports() {
+        let content = "import 'common.just'\n\n[group('docs')]\ndocs:\n    mdbook build\n";
+        let result = extract_group(content, "docs", "docs.just").unwrap();
+        assert_eq!(
+            result.remaining,
+            "import 'common.just'\nimport 'docs.just'\n"
+        );
+    }
+
+    #[test]
+    fn test_recipe_source() {
+        let source = recipe_source(
+            "deploy",
+            Some("Deploy the app"),
+            &[
+                RecipeParameter {
+                    name: "env".to_string(),
+                    default: None,
+                },
+                RecipeParameter {
+                    name: "tag".to_string(),
+                    default: Some("latest".to_string()),
+                },
+            ],
+            &["build".to_string()],
+            "echo {{env}}\necho {{tag}}",
+        );
+        assert_eq!(
+            source,
+            "# Deploy the app\ndeploy env tag=\"latest\": build\n    echo {{env}}\n    echo {{tag}}\n"
+        );
+        assert_eq!(
+            append_recipe("build:\n    cargo build", "test:\n    cargo test\n"),
+            "build:\n    cargo build\n\ntest:\n    cargo test\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_and_remove_recipe() {
+        let replaced = replace_recipe(
+            JUSTFILE,
+            "lint",
+            "lint: build\n    cargo clippy -- -D warnings\n",
+        )
+        .unwrap();
+        assert!(replaced.contains(
+            "# Run clippy\n[group('ci')]\nlint: build\n    cargo clippy -- -D warnings\n\ntest:"
+        ));
+
+        let replaced = replace_recipe(
+            JUSTFILE,
+            "build",
+            "# Build in release mode\nbuild:\n    cargo build -r\n",
+        )
+        .unwrap();
+        assert!(replaced.contains("\n# Build in release mode\nbuild:\n    cargo build -r\n\n# Run"));
+        assert!(!replaced.contains("# Build the project"));
+
+        let removed = remove_recipe(JUSTFILE, "lint").unwrap();
+        assert!(removed.contains("    cargo build\n\ntest:\n"), "{removed}");
+        assert!(!has_recipe(&removed, "lint"));
+        let removed = remove_recipe(&removed, "audit").unwrap();
+        assert!(removed.ends_with("test:\n    cargo test\n"));
+
+        assert!(replace_recipe(JUSTFILE, "release", "release:\n").is_err());
+        assert!(remove_recipe(JUSTFILE, "release").is_err());
+    }
+
+    #[test]
+    fn test_inline_import_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let extraction = extract_group(JUSTFILE, "ci", "ci.just").unwrap();
+        fs::write(temp_dir.path().join("ci.just"), &extraction.extracted).unwrap();
+
+        let line = find_import_line(
+            &extraction.remaining,
+            temp_dir.path(),
+            &temp_dir.path().join("ci.just"),
+        )
+        .unwrap();
+        assert_eq!(line, 4);
+        assert_eq!(
+            find_import_line(
+                "mod ci 'ci.just'\n",
+                temp_dir.path(),
+                &temp_dir.path().join("ci.just")
+            ),
+            None
+        );
+
+        let inlined = inline_import(&extraction.remaining, line, &extraction.extracted);
+        let names: Vec<_> = recipe_blocks(&inlined)
+            .into_iter()
+            .map(|b| b.name)
+            .collect();
+        assert_eq!(names, vec!["lint", "audit", "build", "test"]);
+        assert!(!inlined.contains("import"));
+        assert!(inlined.contains("\n\n# Run clippy\n[group('ci')]\nlint:"));
+    }
+}
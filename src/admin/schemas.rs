@@ -0,0 +1,524 @@
+//! Output schemas of the admin tools
+//!
+//! Admin tools answer with their result struct serialized as JSON. The
+//! schemas here describe those structs, so `tools/list` can advertise them
+//! as `outputSchema` and each result can be returned as MCP structured
+//! content that clients parse instead of scraping text. A schema must be
+//! kept in step with its struct; the tests check every admin result against
+//! its schema.
+
+use serde_json::{json, Value};
+
+/// JSON Schema of an admin tool's structured result
+///
+/// `None` for tools that answer with a text report (`_admin_parser_doctor`)
+/// or a recipe's output (`_admin_run_inline_justfile`), and for tools that
+/// aren't admin tools.
+pub fn output_schema(tool_name: &str) -> Option<Value> {
+    let schema = match tool_name {
+        "_admin_sync" => object(
+            json!({
+                "scanned_files": integer("Justfiles parsed"),
+                "found_recipes": integer("Recipes registered as tools"),
+                "errors": strings("Justfiles that failed to parse, with the reason"),
+                "duration_ms": integer("Time the sync took"),
+            }),
+            &["scanned_files", "found_recipes", "errors", "duration_ms"],
+        ),
+        "_admin_create_recipe" => object(
+            json!({
+                "recipe_name": string("Name of the new recipe"),
+                "justfile_path": string("File the recipe was added to"),
+                "backup_path": string("Backup of the file before the change"),
+                "registry_delta": registry_delta(),
+            }),
+            &[
+                "recipe_name",
+                "justfile_path",
+                "backup_path",
+                "registry_delta",
+            ],
+        ),
+        "_admin_set_watch_directory" => object(
+            json!({
+                "absolute_path": string("Directory now watched"),
+                "justfile_detected": boolean("Whether the directory has a justfile"),
+                "justfile_path": nullable("string", "The justfile, if one was found"),
+            }),
+            &["absolute_path", "justfile_detected", "justfile_path"],
+        ),
+        "_admin_extract_group" => object(
+            json!({
+                "group": string("Group that was moved"),
+                "recipes": strings("Recipes moved, in source order"),
+                "justfile_path": string("Justfile the recipes were moved out of"),
+                "import_path": string("File the recipes were moved to"),
+                "backup_path": string("Backup of the justfile before the change"),
+            }),
+            &[
+                "group",
+                "recipes",
+                "justfile_path",
+                "import_path",
+                "backup_path",
+            ],
+        ),
+        "_admin_inline_import" => object(
+            json!({
+                "recipes": strings("Recipes moved into the justfile"),
+                "justfile_path": string("Justfile the recipes were moved into"),
+                "import_path": string("Imported file the recipes came from"),
+                "file_removed": boolean("Whether the imported file was deleted"),
+                "backup_paths": strings("Backups of the changed files"),
+            }),
+            &[
+                "recipes",
+                "justfile_path",
+                "import_path",
+                "file_removed",
+                "backup_paths",
+            ],
+        ),
+        "_admin_apply_changes" => object(
+            json!({
+                "applied": strings("One entry per change, e.g. \"created deploy\""),
+                "changed_files": strings("Files written"),
+                "backup_paths": strings("Backups of the changed files"),
+            }),
+            &["applied", "changed_files", "backup_paths"],
+        ),
+        "_admin_status" => object(
+            json!({
+                "watch_directories": strings("Directories being watched"),
+                "tool_count": integer("Registered tools, excluding admin tools"),
+                "watcher": watcher_stats(),
+                "parsers": {
+                    "type": "array",
+                    "description": "Parser used for each watch directory",
+                    "items": object(
+                        json!({
+                            "watch_directory": string("Watch directory"),
+                            "name": nullable("string", "Name given to the directory"),
+                            "parser": string("Parser preference for its justfiles"),
+                            "overridden": boolean("Whether the parser comes from --project-parser"),
+                        }),
+                        &["watch_directory", "name", "parser", "overridden"],
+                    ),
+                },
+                "just": object(
+                    json!({
+                        "available": boolean("Whether just runs; recipes fail without it"),
+                        "path": nullable("string", "Where just was found on PATH"),
+                        "version": nullable("string", "Output of just --version"),
+                        "platform": string("Operating system"),
+                        "package_manager": nullable("string", "Package manager the install hint uses"),
+                        "install_hint": string("How to install just on this machine"),
+                    }),
+                    &[
+                        "available",
+                        "path",
+                        "version",
+                        "platform",
+                        "package_manager",
+                        "install_hint",
+                    ],
+                ),
+            }),
+            &[
+                "watch_directories",
+                "tool_count",
+                "watcher",
+                "parsers",
+                "just",
+            ],
+        ),
+        "_admin_set_limits" => object(
+            json!({
+                "max_concurrent_executions": integer("Recipes that may run at once"),
+                "timeout_seconds": integer("Time a recipe may run"),
+                "max_output_size": integer("Bytes of output kept per run"),
+                "bounds": object(
+                    json!({
+                        "max_concurrent_executions": integer("Ceiling for max_concurrent_executions"),
+                        "max_execution_time": object(
+                            json!({
+                                "secs": integer("Whole seconds"),
+                                "nanos": integer("Remaining nanoseconds"),
+                            }),
+                            &["secs", "nanos"],
+                        ),
+                        "max_output_size": integer("Ceiling for max_output_size"),
+                    }),
+                    &["max_concurrent_executions", "max_execution_time", "max_output_size"],
+                ),
+            }),
+            &[
+                "max_concurrent_executions",
+                "timeout_seconds",
+                "max_output_size",
+                "bounds",
+            ],
+        ),
+        "_admin_env_doctor" => object(
+            json!({
+                "shell": string("Shell the reference environment was captured from"),
+                "missing_variables": strings("Variables set in the shell but not for recipes"),
+                "missing_path_entries": strings("PATH entries recipes don't get, in shell order"),
+            }),
+            &["shell", "missing_variables", "missing_path_entries"],
+        ),
+        "_admin_gc" => object(
+            json!({
+                "dry_run": boolean("Whether nothing was removed"),
+                "orphan_tools": strings("Tools whose justfile is gone or no longer claims them"),
+                "stale_sources": strings("Source entries for tools no longer registered"),
+                "stale_names": strings("Names of watch directories that no longer exist"),
+                "outdated_justfiles": strings("Justfiles changed since their tools were generated"),
+            }),
+            &[
+                "dry_run",
+                "orphan_tools",
+                "stale_sources",
+                "stale_names",
+                "outdated_justfiles",
+            ],
+        ),
+        "_admin_access_log" => object(
+            json!({
+                "sessions": {
+                    "type": "array",
+                    "description": "Matching sessions, live ones first",
+                    "items": session_stats(),
+                },
+                "recent_events": {
+                    "type": "array",
+                    "description": "Most recent matching events, oldest first",
+                    "items": access_entry(),
+                },
+                "file": nullable("string", "File events are written to"),
+            }),
+            &["sessions", "recent_events", "file"],
+        ),
+        "_admin_running_executions" => object(
+            json!({
+                "executions": {
+                    "type": "array",
+                    "description": "Running executions, oldest first",
+                    "items": running_execution(),
+                },
+                "hang_timeout_seconds": integer("Idle time after which an execution is flagged; 0 if flagging is off"),
+            }),
+            &["executions", "hang_timeout_seconds"],
+        ),
+        "_admin_cancel_execution" => running_execution(),
+        "_admin_use_profile" => object(
+            json!({
+                "profile": nullable("string", "Profile the session's recipe calls now run under"),
+                "previous": nullable("string", "Profile they ran under before"),
+                "available": strings("Names of the profiles defined"),
+            }),
+            &["profile", "previous", "available"],
+        ),
+        "_admin_schema_mode" => object(
+            json!({
+                "mode": {"type": "string", "enum": ["full", "compact"]},
+                "previous": {"type": "string", "enum": ["full", "compact"]},
+                "full_bytes": integer("Size of the session's tool input schemas as generated, in bytes of JSON"),
+                "compact_bytes": integer("Size of the same schemas in compact mode"),
+            }),
+            &["mode", "previous", "full_bytes", "compact_bytes"],
+        ),
+        "_admin_coverage" => object(
+            json!({
+                "justfiles": {
+                    "type": "array",
+                    "description": "Coverage of each watched justfile",
+                    "items": justfile_coverage(),
+                },
+                "total_recipes": integer("Recipes just lists across all justfiles"),
+                "exposed_recipes": integer("Of those, recipes exposed as tools"),
+            }),
+            &["justfiles", "total_recipes", "exposed_recipes"],
+        ),
+        _ => return None,
+    };
+    Some(schema)
+}
+
+fn object(properties: Value, required: &[&str]) -> Value {
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+fn string(description: &str) -> Value {
+    json!({"type": "string", "description": description})
+}
+
+fn integer(description: &str) -> Value {
+    json!({"type": "integer", "minimum": 0, "description": description})
+}
+
+fn boolean(description: &str) -> Value {
+    json!({"type": "boolean", "description": description})
+}
+
+fn strings(description: &str) -> Value {
+    json!({"type": "array", "items": {"type": "string"}, "description": description})
+}
+
+fn nullable(kind: &str, description: &str) -> Value {
+    json!({"type": [kind, "null"], "description": description})
+}
+
+fn timestamp(description: &str) -> Value {
+    json!({"type": "string", "format": "date-time", "description": description})
+}
+
+/// [`WatcherStats`](crate::watcher::stats::WatcherStats)
+fn watcher_stats() -> Value {
+    object(
+        json!({
+            "events_received": integer("Events received from the filesystem watcher"),
+            "events_dropped": integer("Events lost, each triggering a full rescan"),
+            "queue_depth": integer("Events waiting in the queue"),
+            "queue_capacity": integer("Size of the event queue"),
+            "pending_updates": integer("Justfiles waiting for the debounce window"),
+            "updates_applied": integer("Batches of updates applied to the registry"),
+            "last_update_latency_ms": nullable("integer", "Latency of the last batch"),
+            "max_update_latency_ms": integer("Highest batch latency seen"),
+            "last_events": {
+                "type": "object",
+                "description": "Last event per justfile path",
+                "additionalProperties": object(
+                    json!({
+                        "kind": {"type": "string", "enum": ["create", "modify", "remove"]},
+                        "received_at": timestamp("When the event arrived"),
+                        "applied_at": nullable("string", "When its update was applied"),
+                    }),
+                    &["kind", "received_at", "applied_at"],
+                ),
+            },
+            "supervision": object(
+                json!({
+                    "state": {
+                        "type": "string",
+                        "enum": ["unsupervised", "running", "restarting"],
+                        "description": "Whether the watcher loop is running",
+                    },
+                    "restarts": integer("Times the watcher loop was restarted"),
+                    "last_failure": nullable("string", "Why the loop last ended"),
+                    "last_failure_at": nullable("string", "When the loop last ended"),
+                    "next_restart_at": nullable("string", "When a stopped loop is started again"),
+                }),
+                &["state", "restarts", "last_failure", "last_failure_at", "next_restart_at"],
+            ),
+        }),
+        &[
+            "events_received",
+            "events_dropped",
+            "queue_depth",
+            "queue_capacity",
+            "pending_updates",
+            "updates_applied",
+            "last_update_latency_ms",
+            "max_update_latency_ms",
+            "last_events",
+            "supervision",
+        ],
+    )
+}
+
+/// [`RunningExecution`](crate::executor::watchdog::RunningExecution)
+fn running_execution() -> Value {
+    object(
+        json!({
+            "id": integer("Identifier to pass to _admin_cancel_execution"),
+            "recipe": string("Recipe being run"),
+            "pid": nullable("integer", "Process id of just, once started"),
+            "started_at": timestamp("When the execution started"),
+            "runtime_ms": integer("Time since the execution started"),
+            "cpu_ms": nullable("integer", "CPU time used by the process tree; null where it can't be sampled"),
+            "rss_kb": nullable("integer", "Resident memory of the process tree"),
+            "idle_output_ms": integer("Time since the execution last printed anything"),
+            "idle_cpu_ms": nullable("integer", "Time since the process tree last used CPU"),
+            "likely_hung": boolean("No CPU use and no output for the hang timeout"),
+        }),
+        &[
+            "id",
+            "recipe",
+            "pid",
+            "started_at",
+            "runtime_ms",
+            "cpu_ms",
+            "rss_kb",
+            "idle_output_ms",
+            "idle_cpu_ms",
+            "likely_hung",
+        ],
+    )
+}
+
+/// [`JustfileCoverage`](crate::admin::coverage::JustfileCoverage)
+fn justfile_coverage() -> Value {
+    object(
+        json!({
+            "justfile": string("Justfile path"),
+            "parser": string("Parser the justfile's tools are generated with"),
+            "exposed": {
+                "type": "array",
+                "description": "Recipes with a tool",
+                "items": object(
+                    json!({
+                        "recipe": string("Recipe name"),
+                        "tool": string("Tool exposing it"),
+                    }),
+                    &["recipe", "tool"],
+                ),
+            },
+            "omitted": {
+                "type": "array",
+                "description": "Recipes without a tool",
+                "items": object(
+                    json!({
+                        "recipe": string("Recipe name"),
+                        "reason": {
+                            "type": "string",
+                            "enum": ["private", "parse_error", "not_parsed", "shadowed", "not_registered"],
+                        },
+                        "detail": string("Why the recipe was left out, and what to do about it"),
+                    }),
+                    &["recipe", "reason", "detail"],
+                ),
+            },
+            "unknown_to_just": strings("Tools generated from the justfile for recipes just doesn't list"),
+            "error": nullable("string", "Why just could not list the recipes"),
+        }),
+        &[
+            "justfile",
+            "parser",
+            "exposed",
+            "omitted",
+            "unknown_to_just",
+            "error",
+        ],
+    )
+}
+
+/// [`SessionStats`](crate::access_log::SessionStats)
+fn session_stats() -> Value {
+    object(
+        json!({
+            "session": string("Session id"),
+            "transport": string("Transport the session uses"),
+            "client_name": nullable("string", "Name the client gave in initialize"),
+            "client_version": nullable("string", "Version the client gave in initialize"),
+            "connected_at": timestamp("When the session started"),
+            "last_activity": timestamp("When the session last sent or received a message"),
+            "disconnected_at": nullable("string", "When the session ended"),
+            "messages_in": integer("Messages received"),
+            "messages_out": integer("Messages sent"),
+            "bytes_in": integer("Bytes received"),
+            "bytes_out": integer("Bytes sent"),
+            "tool_calls": {
+                "type": "object",
+                "description": "Number of calls per tool",
+                "additionalProperties": {"type": "integer", "minimum": 0},
+            },
+            "tool_errors": integer("Tool calls that failed"),
+        }),
+        &[
+            "session",
+            "transport",
+            "client_name",
+            "client_version",
+            "connected_at",
+            "last_activity",
+            "disconnected_at",
+            "messages_in",
+            "messages_out",
+            "bytes_in",
+            "bytes_out",
+            "tool_calls",
+            "tool_errors",
+        ],
+    )
+}
+
+/// [`RegistryDelta`](crate::watcher::delta::RegistryDelta)
+fn registry_delta() -> Value {
+    object(
+        json!({
+            "added": strings("Tools registered for the first time"),
+            "removed": strings("Tools whose recipe is gone"),
+            "modified": strings("Tools whose description, parameters or metadata changed"),
+            "unchanged": integer("Other tools of the justfile, which clients see no difference in"),
+        }),
+        &["added", "removed", "modified", "unchanged"],
+    )
+}
+
+/// [`AccessEntry`](crate::access_log::AccessEntry); fields other than the
+/// common ones depend on `event`
+fn access_entry() -> Value {
+    object(
+        json!({
+            "timestamp": timestamp("When the event happened"),
+            "session": string("Session id"),
+            "transport": string("Transport the session uses"),
+            "event": {
+                "type": "string",
+                "enum": ["connect", "initialize", "tool_call", "disconnect"],
+            },
+            "client_name": string("initialize: name the client gave"),
+            "client_version": string("initialize: version the client gave"),
+            "protocol_version": string("initialize: protocol version requested"),
+            "tool": string("tool_call: tool called"),
+            "bytes_in": integer("tool_call: size of the request"),
+            "bytes_out": integer("tool_call: size of the response"),
+            "duration_ms": integer("tool_call: time until the response"),
+            "is_error": boolean("tool_call: whether the call failed"),
+        }),
+        &["timestamp", "session", "transport", "event"],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_admin_tools_have_output_schemas() {
+        use crate::registry::ToolRegistry;
+        use crate::watcher::JustfileWatcher;
+        use std::sync::Arc;
+        use tokio::sync::Mutex;
+
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = Arc::new(JustfileWatcher::new(registry.clone()));
+        let admin_tools = super::super::AdminTools::new(registry.clone(), watcher, vec![], vec![]);
+        admin_tools.register_admin_tools().await.unwrap();
+
+        let registry = registry.lock().await;
+        for tool in registry.list_tools() {
+            let schema = output_schema(&tool.name);
+            if tool.name == "_admin_parser_doctor" {
+                assert!(schema.is_none());
+                continue;
+            }
+            let schema = schema.unwrap_or_else(|| panic!("{} has no output schema", tool.name));
+            assert_eq!(schema["type"], "object", "{}", tool.name);
+            for field in schema["required"].as_array().unwrap() {
+                let field = field.as_str().unwrap();
+                assert!(
+                    schema["properties"].get(field).is_some(),
+                    "{}: required field {field} is not described",
+                    tool.name
+                );
+            }
+        }
+        assert!(output_schema("build").is_none());
+    }
+}
@@ -0,0 +1,429 @@
+//! Administrative CLI commands
+//!
+//! Runs the same `AdminTools` operations that back the `_admin_*` MCP tools,
+//! but directly against the filesystem without starting a server.
+
+use super::{AdminCommands, CommandFailed, CommandOutput, OutputFormat};
+use crate::admin::coverage::CoverageReport;
+use crate::admin::{
+    AdminTools, ApplyChangesParams, ApplyChangesResult, CreateRecipeParams, CreateRecipeResult,
+    ExtractGroupParams, ExtractGroupResult, InlineImportParams, InlineImportResult,
+    ParserDoctorReport, RecipeParameter, SetWatchDirectoryParams, SetWatchDirectoryResult,
+    SyncResult,
+};
+use crate::executor::environment::EnvironmentDiff;
+use crate::parser::ParserPreference;
+use crate::registry::ToolRegistry;
+use crate::watcher::JustfileWatcher;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+impl CommandOutput for SyncResult {
+    fn print_text(&self) {
+        println!(
+            "Sync completed: {} files scanned, {} recipes found in {} ms",
+            self.scanned_files, self.found_recipes, self.duration_ms
+        );
+        for error in &self.errors {
+            eprintln!("  error: {error}");
+        }
+    }
+}
+
+impl CommandOutput for CreateRecipeResult {
+    fn print_text(&self) {
+        println!(
+            "Created recipe '{}' in {} (backup: {})",
+            self.recipe_name, self.justfile_path, self.backup_path
+        );
+    }
+}
+
+impl CommandOutput for ExtractGroupResult {
+    fn print_text(&self) {
+        println!(
+            "Moved {} recipe(s) in group '{}' to {} (backup: {})",
+            self.recipes.len(),
+            self.group,
+            self.import_path,
+            self.backup_path
+        );
+        for recipe in &self.recipes {
+            println!("  {recipe}");
+        }
+    }
+}
+
+impl CommandOutput for ApplyChangesResult {
+    fn print_text(&self) {
+        println!(
+            "Applied {} change(s) to {} (backups: {})",
+            self.applied.len(),
+            self.changed_files.join(", "),
+            self.backup_paths.join(", ")
+        );
+        for change in &self.applied {
+            println!("  {change}");
+        }
+    }
+}
+
+impl CommandOutput for InlineImportResult {
+    fn print_text(&self) {
+        println!(
+            "Inlined {} recipe(s) from {} into {} (backups: {})",
+            self.recipes.len(),
+            self.import_path,
+            self.justfile_path,
+            self.backup_paths.join(", ")
+        );
+    }
+}
+
+impl CommandOutput for SetWatchDirectoryResult {
+    fn print_text(&self) {
+        println!(
+            "Watch directory: {} (justfile detected: {})",
+            self.absolute_path, self.justfile_detected
+        );
+        if let Some(justfile_path) = &self.justfile_path {
+            println!("Justfile: {justfile_path}");
+        }
+    }
+}
+
+impl CommandOutput for EnvironmentDiff {
+    fn print_text(&self) {
+        if self.is_empty() {
+            println!(
+                "Recipes get the variables and PATH of your shell ({})",
+                self.shell
+            );
+        }
+        for warning in self.warnings() {
+            println!("{warning}");
+        }
+    }
+}
+
+impl CommandOutput for CoverageReport {
+    fn print_text(&self) {
+        println!(
+            "{} of {} recipe(s) exposed as tools",
+            self.exposed_recipes, self.total_recipes
+        );
+        for justfile in &self.justfiles {
+            println!(
+                "{} ({} parser): {} exposed, {} omitted",
+                justfile.justfile,
+                justfile.parser,
+                justfile.exposed.len(),
+                justfile.omitted.len()
+            );
+            if let Some(error) = &justfile.error {
+                eprintln!("  error: {error}");
+            }
+            for omitted in &justfile.omitted {
+                let reason = serde_json::to_value(omitted.reason).unwrap_or_default();
+                println!(
+                    "  {} [{}]: {}",
+                    omitted.recipe,
+                    reason.as_str().unwrap_or_default(),
+                    omitted.detail
+                );
+            }
+            for tool in &justfile.unknown_to_just {
+                println!("  tool '{tool}' has no recipe just lists");
+            }
+        }
+    }
+}
+
+/// Parser doctor report, with per-parser issues in text only if verbose
+///
+/// The JSON document always includes them.
+#[derive(Serialize)]
+struct ParserDoctorOutput {
+    #[serde(flatten)]
+    report: ParserDoctorReport,
+    #[serde(skip)]
+    verbose: bool,
+}
+
+impl CommandOutput for ParserDoctorOutput {
+    fn print_text(&self) {
+        println!("{}", self.report.to_markdown(self.verbose));
+    }
+}
+
+/// Handle admin CLI commands
+///
+/// # Arguments
+/// * `admin_command` - The subcommand to run
+/// * `watch_configs` - Watch directories with optional names (see `parse_watch_dirs`)
+/// * `parser` - Parser preference string (auto, ast, cli, regex)
+/// * `parser_overrides` - Parser preferences for specific watch directories
+/// * `output` - Format results are printed in
+pub async fn handle_admin_command(
+    admin_command: AdminCommands,
+    watch_configs: Vec<(PathBuf, Option<String>)>,
+    parser: &str,
+    parser_overrides: Vec<(PathBuf, ParserPreference)>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let admin_tools = create_admin_tools(watch_configs, parser, parser_overrides).await?;
+
+    match admin_command {
+        AdminCommands::Sync => {
+            let result = admin_tools.sync().await?;
+            output.print(&result)?;
+            if !result.errors.is_empty() {
+                return Err(CommandFailed(format!(
+                    "Sync finished with {} error(s)",
+                    result.errors.len()
+                ))
+                .into());
+            }
+        }
+
+        AdminCommands::CreateRecipe {
+            name,
+            recipe,
+            description,
+            params,
+            dependencies,
+            watch_name,
+            target_file,
+        } => {
+            // Load existing recipes so name conflicts are detected
+            admin_tools.sync().await?;
+
+            let parameters = params
+                .iter()
+                .map(|spec| parse_recipe_parameter(spec))
+                .collect::<Vec<_>>();
+
+            let result = admin_tools
+                .create_recipe(CreateRecipeParams {
+                    watch_name,
+                    recipe_name: name,
+                    description,
+                    recipe,
+                    parameters: (!parameters.is_empty()).then_some(parameters),
+                    dependencies: (!dependencies.is_empty()).then_some(dependencies),
+                    target_file,
+                })
+                .await?;
+            output.print(&result)?;
+        }
+
+        AdminCommands::ExtractGroup {
+            group,
+            target_file,
+            watch_name,
+        } => {
+            let result = admin_tools
+                .extract_group(ExtractGroupParams {
+                    watch_name,
+                    group,
+                    target_file,
+                })
+                .await?;
+            output.print(&result)?;
+        }
+
+        AdminCommands::ApplyChanges {
+            changes,
+            watch_name,
+        } => {
+            let json = if changes.as_os_str() == "-" {
+                std::io::read_to_string(std::io::stdin())?
+            } else {
+                std::fs::read_to_string(&changes)?
+            };
+            let changes = serde_json::from_str(&json)
+                .map_err(|e| anyhow::anyhow!("Invalid changes in {}: {e}", changes.display()))?;
+
+            let result = admin_tools
+                .apply_changes(ApplyChangesParams {
+                    watch_name,
+                    changes,
+                })
+                .await?;
+            output.print(&result)?;
+        }
+
+        AdminCommands::InlineImport {
+            import_path,
+            keep_file,
+            watch_name,
+        } => {
+            let result = admin_tools
+                .inline_import(InlineImportParams {
+                    watch_name,
+                    import_path,
+                    keep_file,
+                })
+                .await?;
+            output.print(&result)?;
+        }
+
+        AdminCommands::SetWatchDirectory { path } => {
+            let result = admin_tools
+                .set_watch_directory(SetWatchDirectoryParams { path })
+                .await?;
+            output.print(&result)?;
+        }
+
+        AdminCommands::EnvDoctor => {
+            output.print(&admin_tools.env_doctor().await?)?;
+        }
+
+        AdminCommands::ParserDoctor { verbose } => {
+            output.print(&ParserDoctorOutput {
+                report: admin_tools.parser_doctor_report().await?,
+                verbose,
+            })?;
+        }
+
+        AdminCommands::Coverage => {
+            admin_tools.sync().await?;
+            output.print(&admin_tools.coverage().await?)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Build an `AdminTools` instance backed by a fresh registry and watcher
+async fn create_admin_tools(
+    watch_configs: Vec<(PathBuf, Option<String>)>,
+    parser: &str,
+    parser_overrides: Vec<(PathBuf, ParserPreference)>,
+) -> anyhow::Result<AdminTools> {
+    let preference = ParserPreference::from_str(parser).map_err(|e| anyhow::anyhow!(e))?;
+
+    let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+    let mut watcher = JustfileWatcher::new_with_parser_preference(registry.clone(), preference);
+    watcher.set_parser_overrides(parser_overrides);
+    watcher.configure_names(&watch_configs).await;
+    watcher.set_multiple_dirs(watch_configs.len() > 1);
+
+    let watch_paths = watch_configs.iter().map(|(path, _)| path.clone()).collect();
+
+    Ok(AdminTools::new(
+        registry,
+        Arc::new(watcher),
+        watch_paths,
+        watch_configs,
+    ))
+}
+
+/// Parse a `NAME` or `NAME=DEFAULT` recipe parameter spec
+fn parse_recipe_parameter(spec: &str) -> RecipeParameter {
+    match spec.split_once('=') {
+        Some((name, default)) => RecipeParameter {
+            name: name.to_string(),
+            default: Some(default.to_string()),
+        },
+        None => RecipeParameter {
+            name: spec.to_string(),
+            default: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_recipe_parameter() {
+        let param = parse_recipe_parameter("env=staging");
+        assert_eq!(param.name, "env");
+        assert_eq!(param.default.as_deref(), Some("staging"));
+
+        let param = parse_recipe_parameter("target");
+        assert_eq!(param.name, "target");
+        assert!(param.default.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_recipe_command_writes_justfile() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(&justfile_path, "build:\n    echo build\n").unwrap();
+
+        handle_admin_command(
+            AdminCommands::CreateRecipe {
+                name: "deploy".to_string(),
+                recipe: "echo deploying {{env}}".to_string(),
+                description: Some("Deploy the app".to_string()),
+                params: vec!["env=staging".to_string()],
+                dependencies: vec!["build".to_string()],
+                watch_name: None,
+                target_file: None,
+            },
+            vec![(temp_dir.path().to_path_buf(), None)],
+            "auto",
+            Vec::new(),
+            OutputFormat::Text,
+        )
+        .await
+        .unwrap();
+
+        let content = fs::read_to_string(&justfile_path).unwrap();
+        assert!(content.contains("# Deploy the app"));
+        assert!(content.contains("deploy env=\"staging\": build"));
+        assert!(temp_dir.path().join(".justfile.bak").exists());
+    }
+
+    #[tokio::test]
+    async fn test_create_recipe_command_rejects_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(&justfile_path, "build:\n    echo build\n").unwrap();
+
+        let result = handle_admin_command(
+            AdminCommands::CreateRecipe {
+                name: "build".to_string(),
+                recipe: "echo again".to_string(),
+                description: None,
+                params: vec![],
+                dependencies: vec![],
+                watch_name: None,
+                target_file: None,
+            },
+            vec![(temp_dir.path().to_path_buf(), None)],
+            "auto",
+            Vec::new(),
+            OutputFormat::Text,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            fs::read_to_string(&justfile_path).unwrap(),
+            "build:\n    echo build\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_parser_preference() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = handle_admin_command(
+            AdminCommands::Sync,
+            vec![(temp_dir.path().to_path_buf(), None)],
+            "bogus",
+            Vec::new(),
+            OutputFormat::Text,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,242 @@
+//! Recipe CLI commands
+//!
+//! `list` and `run` start the MCP server in-process and talk to it through
+//! [`JustMcpClient`], so the CLI sees the same tools, schemas and results an
+//! agent does.
+
+use super::{CommandFailed, CommandOutput, OutputFormat};
+use crate::client::JustMcpClient;
+use crate::server::FrameworkServer;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Tools printed by `list`
+#[derive(Debug, Serialize)]
+pub struct ToolList {
+    /// Sorted by [`collation_key`], so the order is the same on every platform
+    pub tools: Vec<ToolSummary>,
+    /// Tools whose names clients that ignore case can't tell apart
+    pub collisions: Vec<NameCollision>,
+}
+
+/// Tool names that are the same when case is ignored
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct NameCollision {
+    /// The lowercased name they share
+    pub folded: String,
+    pub tools: Vec<String>,
+}
+
+impl ToolList {
+    /// Sort tools and find the names that collide
+    pub fn new(mut tools: Vec<ToolSummary>) -> Self {
+        tools.sort_by(|a, b| collation_key(&a.name).cmp(&collation_key(&b.name)));
+
+        let mut collisions: Vec<NameCollision> = Vec::new();
+        for pair in tools.windows(2) {
+            let folded = pair[0].name.to_lowercase();
+            if folded != pair[1].name.to_lowercase() {
+                continue;
+            }
+            match collisions.last_mut() {
+                Some(collision) if collision.folded == folded => {
+                    collision.tools.push(pair[1].name.clone())
+                }
+                _ => collisions.push(NameCollision {
+                    folded,
+                    tools: vec![pair[0].name.clone(), pair[1].name.clone()],
+                }),
+            }
+        }
+        Self { tools, collisions }
+    }
+}
+
+/// Locale-independent sort key: names ignoring case, then by exact name
+///
+/// Sorting by the system locale would order `list` output differently on
+/// Windows, macOS and Linux; byte order alone would put `Zeta` before
+/// `alpha`.
+fn collation_key(name: &str) -> (String, &str) {
+    (name.to_lowercase(), name)
+}
+
+/// A tool as printed by `list`
+#[derive(Debug, Serialize)]
+pub struct ToolSummary {
+    pub name: String,
+    pub description: String,
+    /// JSON Schema of the tool's arguments
+    pub input_schema: Value,
+}
+
+impl CommandOutput for ToolList {
+    fn print_text(&self) {
+        let width = self
+            .tools
+            .iter()
+            .map(|tool| tool.name.len())
+            .max()
+            .unwrap_or(0);
+        for tool in &self.tools {
+            let description = tool.description.lines().next().unwrap_or_default();
+            println!("{:width$}  {description}", tool.name);
+        }
+        for collision in &self.collisions {
+            let kind = if collision
+                .tools
+                .iter()
+                .all(|tool| *tool == collision.tools[0])
+            {
+                "are listed more than once"
+            } else {
+                "differ only in case; clients that ignore case will see one tool"
+            };
+            eprintln!("warning: {} {kind}", collision.tools.join(", "));
+        }
+    }
+}
+
+/// Result printed by `run`
+#[derive(Debug, Serialize)]
+pub struct RunResult {
+    pub tool: String,
+    /// Whether the recipe failed
+    pub is_error: bool,
+    /// Text output of the tool
+    pub output: String,
+}
+
+impl CommandOutput for RunResult {
+    fn print_text(&self) {
+        if self.is_error {
+            eprintln!("{}", self.output.trim_end());
+        } else {
+            println!("{}", self.output.trim_end());
+        }
+    }
+}
+
+/// Print the tools the server offers, one per line with their description
+pub async fn handle_list_command(
+    server: FrameworkServer,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let mut client = JustMcpClient::in_process(server).await?;
+    let tools = client.list_tools().await?;
+    client.close().await?;
+
+    output.print(&ToolList::new(
+        tools
+            .into_iter()
+            .map(|tool| ToolSummary {
+                name: tool.name,
+                description: tool.description,
+                input_schema: tool.input_schema,
+            })
+            .collect(),
+    ))
+}
+
+/// Run a tool with `KEY=VALUE` arguments and print its output
+///
+/// Fails if the recipe fails, so the exit status reflects the recipe's.
+pub async fn handle_run_command(
+    server: FrameworkServer,
+    tool: &str,
+    arguments: &[String],
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let arguments = parse_arguments(arguments)?;
+    let mut client = JustMcpClient::in_process(server).await?;
+    let run = client.run(tool, arguments).await?;
+    client.close().await?;
+
+    let result = RunResult {
+        tool: tool.to_string(),
+        is_error: run.is_error,
+        output: run.text,
+    };
+    output.print(&result)?;
+    if result.is_error {
+        return Err(CommandFailed(format!("{tool} failed")).into());
+    }
+    Ok(())
+}
+
+/// Parse `KEY=VALUE` pairs into tool arguments
+fn parse_arguments(arguments: &[String]) -> anyhow::Result<Map<String, Value>> {
+    arguments
+        .iter()
+        .map(|argument| match argument.split_once('=') {
+            Some((key, value)) if !key.is_empty() => {
+                Ok((key.to_string(), Value::String(value.to_string())))
+            }
+            _ => Err(anyhow::anyhow!(
+                "Invalid argument '{argument}', expected KEY=VALUE"
+            )),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_arguments() {
+        let arguments =
+            parse_arguments(&["env=staging".to_string(), "query=a=b".to_string()]).unwrap();
+        assert_eq!(arguments["env"], "staging");
+        assert_eq!(arguments["query"], "a=b");
+        assert!(parse_arguments(&["verbose".to_string()]).is_err());
+        assert!(parse_arguments(&["=1".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_tool_list_sorting_and_collisions() {
+        let tool = |name: &str| ToolSummary {
+            name: name.to_string(),
+            description: String::new(),
+            input_schema: Value::Null,
+        };
+        let list = ToolList::new(
+            [
+                "zeta", "Build", "alpha", "build", "deploy", "BUILD", "test", "test",
+            ]
+            .map(tool)
+            .into(),
+        );
+        let names: Vec<&str> = list.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["alpha", "BUILD", "Build", "build", "deploy", "test", "test", "zeta"]
+        );
+        assert_eq!(
+            list.collisions,
+            vec![
+                NameCollision {
+                    folded: "build".to_string(),
+                    tools: vec!["BUILD".into(), "Build".into(), "build".into()],
+                },
+                NameCollision {
+                    folded: "test".to_string(),
+                    tools: vec!["test".into(), "test".into()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_result_json_fields() {
+        let result = RunResult {
+            tool: "build".to_string(),
+            is_error: true,
+            output: "error: failed".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({"tool": "build", "is_error": true, "output": "error: failed"})
+        );
+    }
+}
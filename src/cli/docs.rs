@@ -0,0 +1,39 @@
+//! `docs` command
+//!
+//! Renders the documentation site of the watched justfiles; see
+//! [`crate::docs_site`].
+
+use super::{CommandOutput, DocsCommands, OutputFormat};
+use crate::docs_site::{DocsReport, Site};
+use crate::parser::ParserPreference;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+impl CommandOutput for DocsReport {
+    fn print_text(&self) {
+        println!(
+            "Documented {} recipe(s) of {} project(s) in {}",
+            self.recipes, self.projects, self.out_dir
+        );
+        for file in &self.files {
+            println!("  {file}");
+        }
+    }
+}
+
+/// Handle `docs` subcommands for the justfiles in `watch_configs`
+pub async fn handle_docs_command(
+    docs_command: DocsCommands,
+    watch_configs: Vec<(PathBuf, Option<String>)>,
+    parser: &str,
+    parser_overrides: Vec<(PathBuf, ParserPreference)>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let preference = ParserPreference::from_str(parser).map_err(|e| anyhow::anyhow!(e))?;
+    match docs_command {
+        DocsCommands::Generate { out, format, force } => {
+            let site = Site::collect(&watch_configs, preference, parser_overrides).await?;
+            output.print(&site.write(&out, format, force)?)
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! `init` command
+//!
+//! Installs a bundled project template: a justfile of curated recipes for
+//! the stack and an `.mcp.json` registering just-mcp for the project.
+
+use super::{CommandOutput, OutputFormat};
+use crate::embedded_content::templates::{
+    get_project_template, install_template, project_templates, InstallReport,
+};
+use serde::Serialize;
+use std::path::Path;
+
+/// Templates printed by `init` without `--template`
+#[derive(Debug, Serialize)]
+pub struct TemplateList {
+    pub templates: Vec<TemplateSummary>,
+}
+
+/// A bundled template as printed by `init`
+#[derive(Debug, Serialize)]
+pub struct TemplateSummary {
+    pub id: &'static str,
+    pub description: &'static str,
+}
+
+impl CommandOutput for TemplateList {
+    fn print_text(&self) {
+        println!("Available templates:");
+        for template in &self.templates {
+            println!("  {:<10} {}", template.id, template.description);
+        }
+        println!("\nInstall one with: just-mcp init --template <NAME>");
+    }
+}
+
+/// Result printed by `init --template`
+#[derive(Debug, Serialize)]
+pub struct InitResult {
+    pub template: &'static str,
+    #[serde(flatten)]
+    pub report: InstallReport,
+}
+
+impl CommandOutput for InitResult {
+    fn print_text(&self) {
+        for path in &self.report.written {
+            println!("Wrote {}", path.display());
+        }
+        for path in &self.report.skipped {
+            println!(
+                "Kept existing {}; add just-mcp to it or rerun with --force",
+                path.display()
+            );
+        }
+        println!("Run `just --list` to see the recipes, or start just-mcp in this directory.");
+    }
+}
+
+/// Install `template` into `dir`, or list the templates when none is given
+pub fn handle_init_command(
+    template: Option<&str>,
+    dir: &Path,
+    force: bool,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let Some(name) = template else {
+        return output.print(&TemplateList {
+            templates: project_templates()
+                .into_iter()
+                .map(|template| TemplateSummary {
+                    id: template.id,
+                    description: template.description,
+                })
+                .collect(),
+        });
+    };
+
+    let Some(template) = get_project_template(name) else {
+        let names: Vec<&str> = project_templates().iter().map(|t| t.id).collect();
+        anyhow::bail!(
+            "Unknown template '{name}', expected one of: {}",
+            names.join(", ")
+        );
+    };
+
+    let report = install_template(&template, dir, force)?;
+    output.print(&InitResult {
+        template: template.id,
+        report,
+    })
+}
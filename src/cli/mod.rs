@@ -1,14 +1,47 @@
-//! Command-line interface for just-mcp vector search functionality
+//! Command-line interface for just-mcp
 //!
 //! This module provides CLI commands for interacting with the vector search
-//! functionality outside of the MCP server mode.
+//! and administrative functionality outside of the MCP server mode.
 
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+#[cfg(feature = "admin")]
+mod admin;
+
+#[cfg(feature = "admin")]
+pub use admin::handle_admin_command;
+
+#[cfg(feature = "ultrafast-framework")]
+mod client;
+
+#[cfg(feature = "ultrafast-framework")]
+pub use client::{handle_list_command, handle_run_command};
+
+#[cfg(feature = "watcher")]
+mod docs;
+
+#[cfg(feature = "watcher")]
+pub use docs::handle_docs_command;
+
+mod init;
+
+pub use init::handle_init_command;
+
+mod output;
+
+pub use output::{CommandFailed, CommandOutput, OutputFormat};
+
+#[cfg(feature = "self-update")]
+mod self_update;
+
+#[cfg(feature = "self-update")]
+pub use self_update::handle_self_update_command;
 
 #[cfg(feature = "vector-search")]
 use anyhow::Result;
 #[cfg(feature = "vector-search")]
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 #[cfg(feature = "vector-search")]
 use crate::vector_search::{
@@ -17,7 +50,7 @@ use crate::vector_search::{
 };
 
 #[cfg(feature = "vector-search")]
-use crate::vector_search::Document;
+use crate::vector_search::{Document, SearchFilters, SearchResult};
 
 #[cfg(all(feature = "vector-search", feature = "local-embeddings"))]
 use crate::vector_search::LocalEmbeddingProvider;
@@ -39,6 +72,8 @@ EXAMPLES:
   just-mcp                                    # Start framework server in current directory
   just-mcp --watch-dir ./project              # Monitor specific directory
   just-mcp --admin                            # Enable admin tools for diagnostics
+  just-mcp --watch-dir ./project admin sync   # Re-scan justfiles without a server
+  just-mcp run build target=release           # Run a recipe tool once
   just-mcp search query --query 'build app'   # Search indexed justfiles
 ")]
 pub struct Args {
@@ -48,6 +83,7 @@ pub struct Args {
     #[arg(
         short = 'w',
         long = "watch-dir",
+        global = true,
         help = "Directory to watch for justfiles, optionally with name (path or path:name). Defaults to current directory if not specified"
     )]
     pub watch_dir: Vec<String>,
@@ -55,9 +91,24 @@ pub struct Args {
     #[arg(long, help = "Enable administrative tools")]
     pub admin: bool,
 
+    #[arg(
+        long,
+        help = "Offer _admin_run_inline_justfile, which runs recipes from justfile content the client sends, with the server's permissions (requires --admin)"
+    )]
+    pub allow_inline_justfiles: bool,
+
     #[arg(long, help = "Enable JSON output for logs")]
     pub json_logs: bool,
 
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value = "text",
+        help = "Format of command results: text, or json for one JSON document on stdout"
+    )]
+    pub output: OutputFormat,
+
     #[arg(
         long,
         default_value = "info",
@@ -67,10 +118,413 @@ pub struct Args {
 
     #[arg(
         long,
+        global = true,
         default_value = "auto",
         help = "Parser to use: auto (AST→CLI fallback), ast (AST only), cli (CLI only), regex (deprecated)"
     )]
     pub parser: String,
+
+    #[arg(
+        long,
+        help = "Maximum concurrent executions per project (defaults to the global limit)"
+    )]
+    pub max_concurrent_per_project: Option<usize>,
+
+    #[arg(
+        long,
+        default_value = "32",
+        help = "Highest concurrency limit _admin_set_limits may set"
+    )]
+    pub max_settable_concurrency: usize,
+
+    #[arg(
+        long,
+        default_value = "3600",
+        help = "Highest timeout in seconds _admin_set_limits may set"
+    )]
+    pub max_settable_timeout: u64,
+
+    #[arg(
+        long,
+        default_value = "104857600",
+        help = "Highest output size in bytes _admin_set_limits may set"
+    )]
+    pub max_settable_output_size: usize,
+
+    #[arg(
+        long = "project-concurrency",
+        value_parser = parse_project_limit,
+        help = "Concurrency limit for a named watch directory (NAME=N), may be repeated"
+    )]
+    pub project_concurrency: Vec<(String, usize)>,
+
+    #[arg(
+        long,
+        help = "Parallel jobs just may use for a recipe's dependencies (passed as --jobs when supported)"
+    )]
+    pub jobs: Option<usize>,
+
+    #[arg(
+        long = "project-jobs",
+        value_parser = parse_named_jobs,
+        help = "Parallel jobs for a named watch directory (NAME=N), overriding --jobs; may be repeated"
+    )]
+    pub project_jobs: Vec<(String, usize)>,
+
+    #[arg(
+        long = "group-jobs",
+        value_parser = parse_named_jobs,
+        help = "Parallel jobs for recipes in a [group('NAME')] (NAME=N), overriding --project-jobs; may be repeated"
+    )]
+    pub group_jobs: Vec<(String, usize)>,
+
+    #[arg(
+        long,
+        help = "Most parallel jobs any recipe may use [default: number of cores]"
+    )]
+    pub max_jobs: Option<usize>,
+
+    #[arg(
+        long = "project-parser",
+        global = true,
+        value_parser = parse_project_parser,
+        help = "Parser for one watch directory, by name or path (NAME=PARSER), overriding --parser; may be repeated"
+    )]
+    pub project_parsers: Vec<(String, crate::parser::ParserPreference)>,
+
+    #[arg(
+        long = "prompt-response",
+        value_parser = parse_prompt_response,
+        help = "Answer interactive prompts matching a regex (PATTERN=RESPONSE), may be repeated"
+    )]
+    pub prompt_responses: Vec<crate::executor::interactive::PromptResponse>,
+
+    #[arg(
+        long,
+        default_value = "5",
+        help = "Seconds of idle output before a recipe is checked for an interactive prompt"
+    )]
+    pub prompt_stall_timeout: u64,
+
+    #[arg(
+        long,
+        default_value = "explicit",
+        help = "Kill recipes whose output stalls on an unanswered interactive prompt instead of letting them run until the timeout: never, explicit (prompts like `[Y/n]` or `Password:`) or any (also lines ending in `:`, `?` or `>`)"
+    )]
+    pub reject_prompts: crate::executor::interactive::PromptRejection,
+
+    #[arg(
+        long,
+        default_value = "120",
+        help = "Seconds a running recipe may go without CPU use or output before it is flagged as likely hung in _admin_running_executions (0 disables)"
+    )]
+    pub hang_timeout: u64,
+
+    #[arg(
+        long,
+        default_value = "annotated",
+        help = "Run recipes in a pseudo-terminal: never, annotated (recipes marked `# [tty]`) or always"
+    )]
+    pub pty: crate::executor::interactive::PtyMode,
+
+    #[arg(
+        long,
+        global = true,
+        default_value = "strip",
+        help = "ANSI escape sequences (colours, cursor movement) in recipe output: strip, preserve, or markdown to keep bold and italic text as Markdown"
+    )]
+    pub ansi: crate::executor::ansi::AnsiMode,
+
+    #[arg(
+        long,
+        help = "Include the redacted just command line in tool results for local reproduction"
+    )]
+    pub echo_commands: bool,
+
+    #[arg(
+        long,
+        help = "Compare the recipe environment with your login shell and report missing variables and PATH entries on failed executions"
+    )]
+    pub check_env: bool,
+
+    #[arg(
+        long,
+        help = "Hold the project lock file while recipes marked `# [exclusive]` run"
+    )]
+    pub lock_exclusive_recipes: bool,
+
+    #[arg(
+        long,
+        default_value = "30",
+        help = "Seconds to wait for the project lock held by another just-mcp instance"
+    )]
+    pub lock_timeout: u64,
+
+    #[arg(
+        long,
+        default_value = "50",
+        help = "Maximum number of messages in a JSON-RPC batch"
+    )]
+    pub max_batch_size: usize,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "stdio",
+        help = "Transports to serve at once, comma-separated: stdio, http:PORT or http:HOST:PORT (HTTP binds 127.0.0.1 unless a host is given; needs the http feature)"
+    )]
+    #[cfg(feature = "ultrafast-framework")]
+    pub transport: Vec<crate::server::transports::TransportSpec>,
+
+    #[arg(
+        long,
+        value_name = "ORIGIN",
+        help = "Origin header HTTP requests may carry (* for any); defaults to the transport's own http://HOST:PORT"
+    )]
+    pub http_allow_origin: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Append an access log of client sessions (client, tools called, bytes exchanged) to this file as JSON lines"
+    )]
+    pub access_log: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value = "10485760",
+        help = "Size in bytes at which the access log is rotated"
+    )]
+    pub access_log_max_bytes: u64,
+
+    #[arg(
+        long,
+        default_value = "5",
+        help = "Number of rotated access log files to keep"
+    )]
+    pub access_log_max_files: usize,
+
+    #[arg(
+        long,
+        default_value = "262144",
+        help = "Output size in bytes above which tool results link to a just://output resource instead of inlining it (0 always inlines)"
+    )]
+    pub output_link_threshold: usize,
+
+    #[arg(
+        long,
+        help = "Don't offer argument values from earlier calls as schema defaults"
+    )]
+    pub no_parameter_memory: bool,
+
+    #[arg(
+        long,
+        env = "JUST_MCP_SCHEMA_MODE",
+        default_value = "full",
+        help = "Tool schemas in tools/list: full, or compact to drop $schema, additionalProperties: false and empty required lists (sessions can switch with _admin_schema_mode)"
+    )]
+    #[cfg(feature = "ultrafast-framework")]
+    pub schema_mode: crate::server::schema_mode::SchemaMode,
+
+    #[arg(
+        long,
+        help = "Show what changed in a tool's output since its previous run with the same arguments"
+    )]
+    pub diff_previous_run: bool,
+
+    #[arg(
+        long,
+        default_value = "0",
+        value_name = "SECS",
+        help = "Identical tool calls arriving within SECS of a still-running call share its result instead of running the recipe again (0 disables)"
+    )]
+    pub dedup_window: u64,
+
+    #[arg(
+        long,
+        default_value = "5",
+        value_name = "SECS",
+        help = "Serve recipe summaries and config.json from memory for up to SECS while no justfile changed (0 renders them on every read)"
+    )]
+    pub resource_cache_ttl: u64,
+
+    #[arg(
+        long,
+        help = "Print time spent in each startup phase and per justfile parse to stderr"
+    )]
+    pub startup_profile: bool,
+
+    #[arg(
+        long,
+        env = "JUST_MCP_PROMPTS_DIR",
+        help = "Directory of user-defined prompt files (*.md, *.yaml) [default: ~/.config/just-mcp/prompts]"
+    )]
+    pub prompts_dir: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "JUST_MCP_PROFILES_FILE",
+        help = "YAML file of named profiles bundling environment variables, parameter values and allowed recipes [default: ~/.config/just-mcp/profiles.yaml]"
+    )]
+    pub profiles_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "JUST_MCP_PROFILE",
+        help = "Named profile recipe calls run under unless they select another"
+    )]
+    pub profile: Option<String>,
+
+    #[cfg(feature = "telemetry")]
+    #[arg(
+        long,
+        requires = "telemetry_endpoint",
+        help = "Send one anonymous usage report (version, features, bucketed tool counts, parser success counts) to --telemetry-endpoint after startup. Off unless given"
+    )]
+    pub telemetry: bool,
+
+    #[cfg(feature = "telemetry")]
+    #[arg(long, help = "URL anonymous usage reports are POSTed to")]
+    pub telemetry_endpoint: Option<String>,
+
+    #[cfg(feature = "self-update")]
+    #[arg(
+        long,
+        env = "JUST_MCP_UPDATE_CHECK",
+        help = "Check GitHub at startup for releases that fix parsing of syntax in the watched justfiles. Off unless given"
+    )]
+    pub update_check: bool,
+}
+
+impl Args {
+    /// Whether stdout is reserved for command results, so logs must not
+    /// use it
+    pub fn prints_results(&self) -> bool {
+        match &self.command {
+            Some(Commands::Serve) | None => false,
+            Some(command) => command.prints_results() || self.output == OutputFormat::Json,
+        }
+    }
+
+    /// Build execution resource limits from the command line
+    pub fn resource_limits(&self) -> crate::resource_limits::ResourceLimits {
+        crate::resource_limits::ResourceLimits {
+            max_concurrent_per_project: self.max_concurrent_per_project,
+            project_concurrency_limits: self.project_concurrency.iter().cloned().collect(),
+            default_jobs: self.jobs,
+            project_jobs: self.project_jobs.iter().cloned().collect(),
+            group_jobs: self.group_jobs.iter().cloned().collect(),
+            max_jobs: self
+                .max_jobs
+                .unwrap_or_else(crate::resource_limits::available_cores),
+            runtime_bounds: crate::resource_limits::LimitBounds {
+                max_concurrent_executions: self.max_settable_concurrency,
+                max_execution_time: std::time::Duration::from_secs(self.max_settable_timeout),
+                max_output_size: self.max_settable_output_size,
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Resolve `--project-parser` overrides to watch directories
+    ///
+    /// Each override names a watch directory as given with `--watch-dir
+    /// PATH:NAME`, or gives its path (relative to the current directory).
+    pub fn parser_overrides(
+        &self,
+        watch_configs: &[(PathBuf, Option<String>)],
+    ) -> std::io::Result<Vec<(PathBuf, crate::parser::ParserPreference)>> {
+        let cwd = std::env::current_dir()?;
+        self.project_parsers
+            .iter()
+            .map(|(project, preference)| {
+                let named = watch_configs
+                    .iter()
+                    .find(|(_, name)| name.as_deref() == Some(project.as_str()))
+                    .map(|(path, _)| path.clone());
+                let path = named.unwrap_or_else(|| cwd.join(project));
+                if !path.exists() {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!(
+                            "--project-parser {project}: no watch directory named '{project}' and no such path"
+                        ),
+                    ));
+                }
+                Ok((path, preference.clone()))
+            })
+            .collect()
+    }
+
+    /// Build the advisory project lock settings from the command line
+    pub fn lock_config(&self) -> crate::project_lock::LockConfig {
+        crate::project_lock::LockConfig {
+            timeout: std::time::Duration::from_secs(self.lock_timeout),
+            ..Default::default()
+        }
+    }
+
+    /// Access log settings from the command line
+    pub fn access_log_config(&self) -> crate::access_log::AccessLogConfig {
+        crate::access_log::AccessLogConfig {
+            path: self.access_log.clone(),
+            max_bytes: self.access_log_max_bytes,
+            max_files: self.access_log_max_files,
+        }
+    }
+
+    /// Telemetry settings; `None` unless `--telemetry` was given
+    #[cfg(feature = "telemetry")]
+    pub fn telemetry_config(&self) -> Option<crate::telemetry::TelemetryConfig> {
+        self.telemetry
+            .then(|| self.telemetry_endpoint.clone())
+            .flatten()
+            .map(|endpoint| crate::telemetry::TelemetryConfig { endpoint })
+    }
+
+    /// Directory user-defined prompts are loaded from
+    pub fn prompts_dir(&self) -> Option<PathBuf> {
+        self.prompts_dir
+            .clone()
+            .or_else(crate::prompts::user::default_prompts_dir)
+    }
+
+    /// Named profiles from the profiles file
+    ///
+    /// The default file is optional; a file given with `--profiles-file`
+    /// must exist.
+    pub fn profiles(&self) -> crate::error::Result<crate::profiles::ProfileSet> {
+        match &self.profiles_file {
+            Some(path) if !path.is_file() => Err(crate::error::Error::Other(format!(
+                "Profiles file not found: {}",
+                path.display()
+            ))),
+            Some(path) => crate::profiles::ProfileSet::load(path),
+            None => match crate::profiles::default_profiles_file() {
+                Some(path) => crate::profiles::ProfileSet::load(&path),
+                None => Ok(Default::default()),
+            },
+        }
+    }
+
+    /// Build the execution watchdog settings from the command line
+    pub fn watchdog_config(&self) -> crate::executor::watchdog::WatchdogConfig {
+        crate::executor::watchdog::WatchdogConfig {
+            hang_timeout: std::time::Duration::from_secs(self.hang_timeout),
+            ..Default::default()
+        }
+    }
+
+    /// Build the interactive prompt policy from the command line
+    pub fn interactive_config(&self) -> crate::executor::interactive::InteractiveConfig {
+        crate::executor::interactive::InteractiveConfig {
+            stall_timeout: std::time::Duration::from_secs(self.prompt_stall_timeout),
+            reject_prompts: self.reject_prompts,
+            responses: self.prompt_responses.clone(),
+            pty: self.pty,
+            ..Default::default()
+        }
+    }
 }
 
 /// Available CLI commands
@@ -79,12 +533,241 @@ pub enum Commands {
     /// Start the MCP server (default mode)
     Serve,
 
+    /// List the tools the server offers
+    #[cfg(feature = "ultrafast-framework")]
+    List,
+
+    /// Run a tool once and print its output
+    #[cfg(feature = "ultrafast-framework")]
+    Run {
+        /// Tool name, as shown by `list`
+        tool: String,
+
+        /// Tool arguments as KEY=VALUE
+        arguments: Vec<String>,
+    },
+
     #[cfg(feature = "vector-search")]
     /// Vector search operations
     Search {
         #[command(subcommand)]
         search_command: SearchCommands,
     },
+
+    /// Administrative operations on the watched justfiles (no server required)
+    #[cfg(feature = "admin")]
+    Admin {
+        #[command(subcommand)]
+        admin_command: AdminCommands,
+    },
+
+    /// Generate documentation of the watched justfiles
+    #[cfg(feature = "watcher")]
+    Docs {
+        #[command(subcommand)]
+        docs_command: DocsCommands,
+    },
+
+    /// Create a justfile and MCP configuration for a common stack
+    Init {
+        /// Template to install: rust, node, python or terraform; lists the
+        /// templates when omitted
+        #[arg(long)]
+        template: Option<String>,
+
+        /// Project directory to install into
+        #[arg(long, default_value = ".")]
+        dir: PathBuf,
+
+        /// Replace an existing justfile and .mcp.json
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Update just-mcp to the newest GitHub release
+    #[cfg(feature = "self-update")]
+    SelfUpdate {
+        /// Release channel: stable or prerelease
+        #[arg(long, default_value = "stable")]
+        channel: crate::update::Channel,
+
+        /// Only report whether a newer release exists
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+impl Commands {
+    /// Whether the command prints its results on stdout, so logs must not
+    pub fn prints_results(&self) -> bool {
+        match self {
+            #[cfg(feature = "ultrafast-framework")]
+            Commands::List | Commands::Run { .. } => true,
+            Commands::Init { .. } => true,
+            #[cfg(feature = "watcher")]
+            Commands::Docs { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+/// Documentation subcommands
+#[cfg(feature = "watcher")]
+#[derive(Subcommand, Debug, Clone)]
+pub enum DocsCommands {
+    /// Write a static site of recipe reference pages: an index of projects,
+    /// a page per project with its groups, dependency graph and recipes, and
+    /// an index of groups
+    Generate {
+        /// Directory to write the pages to (created if missing; pages
+        /// written before are replaced)
+        #[arg(long, default_value = "docs/recipes")]
+        out: PathBuf,
+
+        /// Overwrite files in the output directory that weren't written by
+        /// an earlier run
+        #[arg(long)]
+        force: bool,
+
+        /// Page format
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: crate::docs_site::DocsFormat,
+    },
+}
+
+/// Administrative subcommands
+///
+/// These mirror the `_admin_*` MCP tools and operate directly on the
+/// justfiles in the `--watch-dir` directories.
+#[cfg(feature = "admin")]
+#[derive(Subcommand, Debug, Clone)]
+pub enum AdminCommands {
+    /// Re-scan justfiles and report the recipes found
+    Sync,
+
+    /// Append a new recipe to a justfile (a backup is written first)
+    CreateRecipe {
+        /// Name of the new recipe
+        #[arg(short, long)]
+        name: String,
+
+        /// Recipe body; each line is indented automatically
+        #[arg(short, long)]
+        recipe: String,
+
+        /// Description added as a comment above the recipe
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// Recipe parameter as NAME or NAME=DEFAULT (repeatable)
+        #[arg(short, long = "param")]
+        params: Vec<String>,
+
+        /// Recipe dependency (repeatable)
+        #[arg(long = "dep")]
+        dependencies: Vec<String>,
+
+        /// Name of the watch directory to add the recipe to [default: first watch directory]
+        #[arg(long)]
+        watch_name: Option<String>,
+
+        /// File imported by the justfile to add the recipe to, relative to the justfile [default: the justfile]
+        #[arg(long)]
+        target_file: Option<String>,
+    },
+
+    /// Move all recipes of a group into a new file imported by the justfile
+    ExtractGroup {
+        /// Group whose recipes to move
+        group: String,
+
+        /// File to create, relative to the justfile [default: <group>.just]
+        #[arg(short, long)]
+        target_file: Option<String>,
+
+        /// Name of the watch directory to restructure [default: first watch directory]
+        #[arg(long)]
+        watch_name: Option<String>,
+    },
+
+    /// Create, modify and remove several recipes atomically
+    ///
+    /// Reads a JSON array of changes, each with an "action" of create,
+    /// modify or remove and the same fields as `create-recipe`. Nothing is
+    /// written unless every change applies and `just` accepts the result.
+    ApplyChanges {
+        /// JSON file with the changes, or '-' for stdin
+        changes: PathBuf,
+
+        /// Name of the watch directory to edit [default: first watch directory]
+        #[arg(long)]
+        watch_name: Option<String>,
+    },
+
+    /// Replace an import statement with the contents of the imported file
+    InlineImport {
+        /// Imported file, relative to the justfile
+        import_path: String,
+
+        /// Keep the imported file instead of deleting it
+        #[arg(long)]
+        keep_file: bool,
+
+        /// Name of the watch directory to restructure [default: first watch directory]
+        #[arg(long)]
+        watch_name: Option<String>,
+    },
+
+    /// Validate a directory and report the justfile it would serve
+    SetWatchDirectory {
+        /// Directory path (relative paths are resolved against the current directory)
+        path: String,
+    },
+
+    /// Compare the recipe environment with your login shell's
+    EnvDoctor,
+
+    /// Diagnose parser issues by comparing parsed recipes with `just --summary`
+    ParserDoctor {
+        /// Include per-recipe details in the report
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
+    /// List the recipes just sees that are not exposed as tools, and why
+    Coverage,
+}
+
+/// Parse `--watch-dir` specs (`path` or `path:name`) into absolute paths with optional names
+///
+/// Defaults to the current directory when no spec is given.
+pub fn parse_watch_dirs(specs: &[String]) -> std::io::Result<Vec<(PathBuf, Option<String>)>> {
+    let cwd = std::env::current_dir()?;
+
+    if specs.is_empty() {
+        return Ok(vec![(cwd, None)]);
+    }
+
+    let configs = specs
+        .iter()
+        .map(|spec| {
+            let (path, name) = match spec.find(':') {
+                Some(colon_pos) => (
+                    PathBuf::from(&spec[..colon_pos]),
+                    Some(spec[colon_pos + 1..].to_string()),
+                ),
+                None => (PathBuf::from(spec), None),
+            };
+            let path = if path.is_absolute() {
+                path
+            } else {
+                cwd.join(path)
+            };
+            (path, name)
+        })
+        .collect();
+
+    Ok(configs)
 }
 
 /// Vector search subcommands
@@ -105,6 +788,22 @@ pub enum SearchCommands {
         #[arg(short, long, default_value = "0.0")]
         threshold: f32,
 
+        /// Only return recipes from this project (justfile directory name)
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Only return recipes in this group
+        #[arg(long)]
+        group: Option<String>,
+
+        /// Only return recipes with (true) or without (false) parameters
+        #[arg(long)]
+        has_params: Option<bool>,
+
+        /// Only return private (true) or public (false) recipes
+        #[arg(long)]
+        private: Option<bool>,
+
         /// Database path for vector storage [default: vector_search.db in current directory]
         #[arg(short = 'b', long, default_value = "vector_search.db")]
         database: PathBuf,
@@ -278,6 +977,44 @@ pub enum SearchCommands {
     },
 }
 
+/// Parse a `PATTERN=RESPONSE` predefined prompt response
+fn parse_prompt_response(s: &str) -> Result<crate::executor::interactive::PromptResponse, String> {
+    crate::executor::interactive::PromptResponse::parse(s).map_err(|e| e.to_string())
+}
+
+/// Parse a `NAME=N` per-project concurrency limit
+fn parse_project_limit(s: &str) -> Result<(String, usize), String> {
+    let (name, limit) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid NAME=N: no `=` found in `{s}`"))?;
+    let limit = limit
+        .parse::<usize>()
+        .ok()
+        .filter(|limit| *limit > 0)
+        .ok_or_else(|| format!("invalid concurrency limit `{limit}` for `{name}`"))?;
+    Ok((name.to_string(), limit))
+}
+
+/// Parse a `NAME=N` per-project or per-group job count
+fn parse_named_jobs(s: &str) -> Result<(String, usize), String> {
+    let (name, jobs) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid NAME=N: no `=` found in `{s}`"))?;
+    let jobs = jobs
+        .parse::<usize>()
+        .ok()
+        .filter(|jobs| *jobs > 0)
+        .ok_or_else(|| format!("invalid job count `{jobs}` for `{name}`"))?;
+    Ok((name.to_string(), jobs))
+}
+
+fn parse_project_parser(s: &str) -> Result<(String, crate::parser::ParserPreference), String> {
+    let (project, parser) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid NAME=PARSER: no `=` found in `{s}`"))?;
+    Ok((project.to_string(), parser.parse()?))
+}
+
 /// Parse a single key-value pair for metadata filters
 #[cfg(feature = "vector-search")]
 fn parse_key_val(s: &str) -> Result<(String, String), String> {
@@ -289,31 +1026,34 @@ fn parse_key_val(s: &str) -> Result<(String, String), String> {
 
 /// Execute query with fallback logic: local embeddings -> mock embeddings -> error
 #[cfg(feature = "vector-search")]
+#[allow(clippy::too_many_arguments)]
 async fn query_with_fallback(
     query: &str,
     limit: usize,
     threshold: f32,
+    filters: &SearchFilters,
     database: &PathBuf,
     prefer_local: bool,
     prefer_mock: bool,
     openai_api_key: Option<String>,
+    output: OutputFormat,
     #[cfg(feature = "local-embeddings")] cache_dir: Option<PathBuf>,
-) -> Result<()> {
+) -> Result<Vec<SearchResult>> {
     // If user explicitly requests a specific provider, use it directly
     if prefer_mock {
         let manager = create_search_manager_mock(database).await?;
-        return query_search(manager, query, limit, threshold).await;
+        return query_search(manager, query, limit, threshold, filters).await;
     }
 
     if let Some(api_key) = openai_api_key {
         let manager = create_search_manager_openai(database, api_key).await?;
-        return query_search(manager, query, limit, threshold).await;
+        return query_search(manager, query, limit, threshold, filters).await;
     }
 
     #[cfg(feature = "local-embeddings")]
     if prefer_local {
         let manager = create_search_manager_local(database, cache_dir).await?;
-        return query_search(manager, query, limit, threshold).await;
+        return query_search(manager, query, limit, threshold, filters).await;
     }
 
     // Fallback logic: try local -> mock -> error
@@ -321,8 +1061,8 @@ async fn query_with_fallback(
     {
         match create_search_manager_local(database, cache_dir).await {
             Ok(manager) => {
-                println!("Using local embeddings for vector search");
-                return query_search(manager, query, limit, threshold).await;
+                output.progress("Using local embeddings for vector search");
+                return query_search(manager, query, limit, threshold, filters).await;
             }
             Err(e) => {
                 eprintln!("Failed to initialize local embeddings: {}", e);
@@ -334,8 +1074,8 @@ async fn query_with_fallback(
     // Fall back to mock embeddings
     match create_search_manager_mock(database).await {
         Ok(manager) => {
-            println!("Using mock embeddings for vector search");
-            query_search(manager, query, limit, threshold).await
+            output.progress("Using mock embeddings for vector search");
+            query_search(manager, query, limit, threshold, filters).await
         }
         Err(e) => {
             #[cfg(feature = "local-embeddings")]
@@ -348,6 +1088,7 @@ async fn query_with_fallback(
 
 /// Execute indexing with fallback logic: local embeddings -> mock embeddings -> error
 #[cfg(feature = "vector-search")]
+#[allow(clippy::too_many_arguments)]
 async fn index_with_fallback(
     directory: &PathBuf,
     database: &PathBuf,
@@ -355,23 +1096,24 @@ async fn index_with_fallback(
     prefer_local: bool,
     prefer_mock: bool,
     openai_api_key: Option<String>,
+    output: OutputFormat,
     #[cfg(feature = "local-embeddings")] cache_dir: Option<PathBuf>,
-) -> Result<()> {
+) -> Result<IndexResult> {
     // If user explicitly requests a specific provider, use it directly
     if prefer_mock {
         let manager = create_search_manager_mock(database).await?;
-        return index_documents(&manager, directory, batch_size).await;
+        return index_documents(&manager, directory, batch_size, output).await;
     }
 
     if let Some(api_key) = openai_api_key {
         let manager = create_search_manager_openai(database, api_key).await?;
-        return index_documents(&manager, directory, batch_size).await;
+        return index_documents(&manager, directory, batch_size, output).await;
     }
 
     #[cfg(feature = "local-embeddings")]
     if prefer_local {
         let manager = create_search_manager_local(database, cache_dir).await?;
-        return index_documents(&manager, directory, batch_size).await;
+        return index_documents(&manager, directory, batch_size, output).await;
     }
 
     // Fallback logic: try local -> mock -> error
@@ -379,8 +1121,8 @@ async fn index_with_fallback(
     {
         match create_search_manager_local(database, cache_dir).await {
             Ok(manager) => {
-                println!("Using local embeddings for indexing");
-                return index_documents(&manager, directory, batch_size).await;
+                output.progress("Using local embeddings for indexing");
+                return index_documents(&manager, directory, batch_size, output).await;
             }
             Err(e) => {
                 eprintln!("Failed to initialize local embeddings: {}", e);
@@ -392,8 +1134,8 @@ async fn index_with_fallback(
     // Fall back to mock embeddings
     match create_search_manager_mock(database).await {
         Ok(manager) => {
-            println!("Using mock embeddings for indexing");
-            index_documents(&manager, directory, batch_size).await
+            output.progress("Using mock embeddings for indexing");
+            index_documents(&manager, directory, batch_size, output).await
         }
         Err(e) => {
             #[cfg(feature = "local-embeddings")]
@@ -406,6 +1148,7 @@ async fn index_with_fallback(
 
 /// Execute similar task search with fallback logic: local embeddings -> mock embeddings -> error
 #[cfg(feature = "vector-search")]
+#[allow(clippy::too_many_arguments)]
 async fn similar_with_fallback(
     task: &str,
     limit: usize,
@@ -413,8 +1156,9 @@ async fn similar_with_fallback(
     prefer_local: bool,
     prefer_mock: bool,
     openai_api_key: Option<String>,
+    output: OutputFormat,
     #[cfg(feature = "local-embeddings")] cache_dir: Option<PathBuf>,
-) -> Result<()> {
+) -> Result<Vec<SearchResult>> {
     // If user explicitly requests a specific provider, use it directly
     if prefer_mock {
         let manager = create_search_manager_mock(database).await?;
@@ -437,7 +1181,7 @@ async fn similar_with_fallback(
     {
         match create_search_manager_local(database, cache_dir).await {
             Ok(manager) => {
-                println!("Using local embeddings for similar task search");
+                output.progress("Using local embeddings for similar task search");
                 return similar_tasks(&manager, task, limit).await;
             }
             Err(e) => {
@@ -450,7 +1194,7 @@ async fn similar_with_fallback(
     // Fall back to mock embeddings
     match create_search_manager_mock(database).await {
         Ok(manager) => {
-            println!("Using mock embeddings for similar task search");
+            output.progress("Using mock embeddings for similar task search");
             similar_tasks(&manager, task, limit).await
         }
         Err(e) => {
@@ -465,32 +1209,11 @@ async fn similar_with_fallback(
 /// Generic similar tasks function that works with any embedding provider
 #[cfg(feature = "vector-search")]
 async fn similar_tasks<E: EmbeddingProvider>(
-    manager: &VectorSearchManager<E, LibSqlVectorStore>,
-    task: &str,
-    limit: usize,
-) -> Result<()> {
-    let results = manager.find_similar_tasks(task, limit).await?;
-
-    if results.is_empty() {
-        println!("No similar tasks found for: '{}'", task);
-    } else {
-        println!("Found {} similar tasks:", results.len());
-        println!();
-
-        for (i, result) in results.iter().enumerate() {
-            println!("{}. Similarity: {:.4}", i + 1, result.score);
-            if let Some(ref task_name) = result.document.task_name {
-                println!("   Task: {}", task_name);
-            }
-            if let Some(ref justfile) = result.document.justfile_name {
-                println!("   Justfile: {}", justfile);
-            }
-            println!("   Content: {}", result.document.content);
-            println!();
-        }
-    }
-
-    Ok(())
+    manager: &VectorSearchManager<E, LibSqlVectorStore>,
+    task: &str,
+    limit: usize,
+) -> Result<Vec<SearchResult>> {
+    manager.find_similar_tasks(task, limit).await
 }
 
 /// Generic indexing function that works with any embedding provider
@@ -499,16 +1222,23 @@ async fn index_documents<E: EmbeddingProvider>(
     manager: &VectorSearchManager<E, LibSqlVectorStore>,
     directory: &PathBuf,
     batch_size: usize,
-) -> Result<()> {
+    output: OutputFormat,
+) -> Result<IndexResult> {
+    let mut result = IndexResult {
+        directory: directory.display().to_string(),
+        justfiles: 0,
+        tasks: 0,
+        indexed: 0,
+    };
+
     // Find all justfiles in the directory
     let justfiles = find_justfiles(directory)?;
-
+    result.justfiles = justfiles.len();
     if justfiles.is_empty() {
-        println!("No justfiles found in directory: {}", directory.display());
-        return Ok(());
+        return Ok(result);
     }
 
-    println!("Found {} justfiles to index", justfiles.len());
+    output.progress(format!("Found {} justfiles to index", justfiles.len()));
 
     // Parse justfiles and create documents
     let mut documents = Vec::new();
@@ -520,43 +1250,376 @@ async fn index_documents<E: EmbeddingProvider>(
         }
     }
 
+    result.tasks = documents.len();
     if documents.is_empty() {
-        println!("No tasks found in justfiles");
-        return Ok(());
+        return Ok(result);
     }
 
-    println!("Found {} tasks to index", documents.len());
+    output.progress(format!("Found {} tasks to index", documents.len()));
 
     // Index in chunks
     let chunk_count = documents.len().div_ceil(batch_size);
-    println!(
-        "Indexing in {} batches of up to {} documents each",
-        chunk_count, batch_size
-    );
+    output.progress(format!(
+        "Indexing in {chunk_count} batches of up to {batch_size} documents each"
+    ));
 
-    let mut total_indexed = 0;
     for (chunk_idx, chunk) in documents.chunks(batch_size).enumerate() {
-        println!("Processing batch {}/{}", chunk_idx + 1, chunk_count);
+        output.progress(format!(
+            "Processing batch {}/{}",
+            chunk_idx + 1,
+            chunk_count
+        ));
         let chunk_docs = chunk.to_vec();
         let ids = manager
             .index_documents_chunked(chunk_docs, batch_size, "tasks")
             .await?;
-        total_indexed += ids.len();
-        println!("Indexed {} documents (total: {})", ids.len(), total_indexed);
+        result.indexed += ids.len();
+        output.progress(format!(
+            "Indexed {} documents (total: {})",
+            ids.len(),
+            result.indexed
+        ));
     }
 
-    println!("Successfully indexed {} tasks", total_indexed);
-    Ok(())
+    Ok(result)
+}
+
+/// Results printed by `search query`
+#[cfg(feature = "vector-search")]
+#[derive(Debug, serde::Serialize)]
+pub struct QueryResults {
+    pub query: String,
+    pub results: Vec<SearchResult>,
+}
+
+#[cfg(feature = "vector-search")]
+impl CommandOutput for QueryResults {
+    fn print_text(&self) {
+        if self.results.is_empty() {
+            println!("No results found for query: '{}'", self.query);
+            return;
+        }
+        println!(
+            "Found {} results for query: '{}'",
+            self.results.len(),
+            self.query
+        );
+        println!();
+
+        for (i, result) in self.results.iter().enumerate() {
+            println!("{}. Score: {:.4}", i + 1, result.score);
+            println!("   ID: {}", result.document.id);
+            print_document_source(&result.document);
+            println!(
+                "   Content: {}",
+                result
+                    .document
+                    .content
+                    .chars()
+                    .take(100)
+                    .collect::<String>()
+            );
+            if result.document.content.len() > 100 {
+                println!("   ...");
+            }
+            println!();
+        }
+    }
+}
+
+/// Results printed by `search similar`
+#[cfg(feature = "vector-search")]
+#[derive(Debug, serde::Serialize)]
+pub struct SimilarTasks {
+    pub task: String,
+    pub results: Vec<SearchResult>,
+}
+
+#[cfg(feature = "vector-search")]
+impl CommandOutput for SimilarTasks {
+    fn print_text(&self) {
+        if self.results.is_empty() {
+            println!("No similar tasks found for: '{}'", self.task);
+            return;
+        }
+        println!("Found {} similar tasks:", self.results.len());
+        println!();
+
+        for (i, result) in self.results.iter().enumerate() {
+            println!("{}. Similarity: {:.4}", i + 1, result.score);
+            print_document_source(&result.document);
+            println!("   Content: {}", result.document.content);
+            println!();
+        }
+    }
+}
+
+/// Documents printed by `search filter` and `search text`
+#[cfg(feature = "vector-search")]
+#[derive(Debug, serde::Serialize)]
+pub struct DocumentMatches {
+    /// Metadata filters, for `search filter`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filters: Option<std::collections::BTreeMap<String, String>>,
+    /// Searched text, for `search text`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    pub documents: Vec<Document>,
+}
+
+#[cfg(feature = "vector-search")]
+impl CommandOutput for DocumentMatches {
+    fn print_text(&self) {
+        let text = self.text.as_deref();
+        match (self.documents.is_empty(), text) {
+            (true, Some(text)) => println!("No documents found containing text: '{text}'"),
+            (true, None) => println!("No documents found matching filters"),
+            (false, Some(text)) => println!(
+                "Found {} documents containing text '{text}':",
+                self.documents.len()
+            ),
+            (false, None) => println!("Found {} documents matching filters:", self.documents.len()),
+        }
+        if self.documents.is_empty() {
+            return;
+        }
+        println!();
+
+        for (i, doc) in self.documents.iter().enumerate() {
+            println!("{}. ID: {}", i + 1, doc.id);
+            print_document_source(doc);
+            // Highlight the search text in the content
+            let (content, width) = match text {
+                Some(text) => (doc.content.replace(text, &format!("**{text}**")), 200),
+                None => (doc.content.clone(), 100),
+            };
+            println!(
+                "   Content: {}",
+                content.chars().take(width).collect::<String>()
+            );
+            if content.len() > width {
+                println!("   ...");
+            }
+            println!();
+        }
+    }
+}
+
+/// Print the task and justfile a document was indexed from
+#[cfg(feature = "vector-search")]
+fn print_document_source(doc: &Document) {
+    if let Some(ref task_name) = doc.task_name {
+        println!("   Task: {task_name}");
+    }
+    if let Some(ref justfile) = doc.justfile_name {
+        println!("   Justfile: {justfile}");
+    }
+}
+
+/// Result printed by `search index`
+#[cfg(feature = "vector-search")]
+#[derive(Debug, serde::Serialize)]
+pub struct IndexResult {
+    pub directory: String,
+    /// Justfiles found in the directory
+    pub justfiles: usize,
+    /// Tasks parsed from them
+    pub tasks: usize,
+    /// Tasks written to the database
+    pub indexed: usize,
+}
+
+#[cfg(feature = "vector-search")]
+impl CommandOutput for IndexResult {
+    fn print_text(&self) {
+        if self.justfiles == 0 {
+            println!("No justfiles found in directory: {}", self.directory);
+        } else if self.tasks == 0 {
+            println!("No tasks found in justfiles");
+        } else {
+            println!("Successfully indexed {} tasks", self.indexed);
+        }
+    }
+}
+
+/// Result printed by `search stats`
+#[cfg(feature = "vector-search")]
+#[derive(Debug, serde::Serialize)]
+pub struct DatabaseStats {
+    pub database: String,
+    pub total_documents: u64,
+    pub healthy: bool,
+}
+
+#[cfg(feature = "vector-search")]
+impl CommandOutput for DatabaseStats {
+    fn print_text(&self) {
+        println!("Vector Database Statistics");
+        println!("==========================");
+        println!("Database path: {}", self.database);
+        println!("Total documents: {}", self.total_documents);
+        println!(
+            "Health status: {}",
+            if self.healthy { "Healthy" } else { "Unhealthy" }
+        );
+    }
+}
+
+/// Result printed by `search cache-info`
+#[cfg(feature = "local-embeddings")]
+#[derive(Debug, serde::Serialize)]
+pub struct ModelCacheInfo {
+    pub cache_dir: String,
+    pub total_models: usize,
+    pub loaded_models: usize,
+    pub total_size_bytes: u64,
+    pub models: Vec<crate::vector_search::CachedModelInfo>,
+}
+
+#[cfg(feature = "local-embeddings")]
+impl CommandOutput for ModelCacheInfo {
+    fn print_text(&self) {
+        println!("Local Embedding Model Cache Information");
+        println!("======================================");
+        println!("Cache directory: {}", self.cache_dir);
+        println!("Total models: {}", self.total_models);
+        println!("Loaded models: {}", self.loaded_models);
+        println!(
+            "Total size: {:.2} MB",
+            self.total_size_bytes as f64 / 1024.0 / 1024.0
+        );
+
+        if !self.models.is_empty() {
+            println!("\nCached Models:");
+            for model in &self.models {
+                println!(
+                    "  • {} ({:.2} MB)",
+                    model.model_id,
+                    model.size_bytes as f64 / 1024.0 / 1024.0
+                );
+                println!(
+                    "    Last accessed: {}",
+                    model.last_accessed.format("%Y-%m-%d %H:%M:%S UTC")
+                );
+                if let Some(dimension) = model.dimension {
+                    println!("    Dimension: {dimension}");
+                }
+            }
+        }
+    }
+}
+
+/// Result printed by `search cache-list`
+#[cfg(feature = "local-embeddings")]
+#[derive(Debug, serde::Serialize)]
+pub struct CachedModels {
+    pub cache_dir: String,
+    pub models: Vec<crate::vector_search::CachedModelInfo>,
+}
+
+#[cfg(feature = "local-embeddings")]
+impl CommandOutput for CachedModels {
+    fn print_text(&self) {
+        if self.models.is_empty() {
+            println!("No cached models found.");
+            println!("Cache directory: {}", self.cache_dir);
+            return;
+        }
+
+        println!("Cached Local Embedding Models");
+        println!("=============================");
+        println!("Cache directory: {}", self.cache_dir);
+        println!();
+
+        for (i, model) in self.models.iter().enumerate() {
+            println!("{}. Model: {}", i + 1, model.model_id);
+            println!(
+                "   Size: {:.2} MB",
+                model.size_bytes as f64 / 1024.0 / 1024.0
+            );
+            println!(
+                "   Downloaded: {}",
+                model.downloaded_at.format("%Y-%m-%d %H:%M:%S UTC")
+            );
+            println!(
+                "   Last accessed: {}",
+                model.last_accessed.format("%Y-%m-%d %H:%M:%S UTC")
+            );
+            if let Some(dimension) = model.dimension {
+                println!("   Dimension: {dimension}");
+            }
+            if let Some(ref model_type) = model.model_type {
+                println!("   Type: {model_type}");
+            }
+            println!(
+                "   Status: {}",
+                if model.loaded { "Loaded" } else { "Cached" }
+            );
+            println!();
+        }
+
+        let total_size: u64 = self.models.iter().map(|m| m.size_bytes).sum();
+        println!(
+            "Total: {} models, {:.2} MB",
+            self.models.len(),
+            total_size as f64 / 1024.0 / 1024.0
+        );
+    }
+}
+
+/// Result printed by `search cache-clear`
+#[cfg(feature = "local-embeddings")]
+#[derive(Debug, serde::Serialize)]
+pub struct CacheCleared {
+    pub cache_dir: String,
+    /// Models removed; zero if the cache was empty or clearing was cancelled
+    pub removed_models: usize,
+    pub cancelled: bool,
+}
+
+#[cfg(feature = "local-embeddings")]
+impl CommandOutput for CacheCleared {
+    fn print_text(&self) {
+        if self.cancelled {
+            println!("Cache clear cancelled.");
+        } else if self.removed_models == 0 {
+            println!("No cached models found.");
+        } else {
+            println!(
+                "Successfully cleared {} models from cache.",
+                self.removed_models
+            );
+        }
+    }
+}
+
+/// Model cache configuration for an optional `--cache-dir`
+#[cfg(feature = "local-embeddings")]
+fn model_cache_config(cache_dir: Option<PathBuf>) -> crate::vector_search::ModelCacheConfig {
+    match cache_dir {
+        Some(cache_dir) => crate::vector_search::ModelCacheConfig {
+            cache_dir,
+            ..Default::default()
+        },
+        None => crate::vector_search::ModelCacheConfig::default(),
+    }
 }
 
 /// Handle vector search CLI commands
 #[cfg(feature = "vector-search")]
-pub async fn handle_search_command(search_command: SearchCommands) -> Result<()> {
+pub async fn handle_search_command(
+    search_command: SearchCommands,
+    output: OutputFormat,
+) -> Result<()> {
     match search_command {
         SearchCommands::Query {
             query,
             limit,
             threshold,
+            project,
+            group,
+            has_params,
+            private,
             database,
             openai_api_key,
             mock_embeddings,
@@ -565,34 +1628,43 @@ pub async fn handle_search_command(search_command: SearchCommands) -> Result<()>
             #[cfg(feature = "local-embeddings")]
             cache_dir,
         } => {
+            let filters = SearchFilters {
+                project,
+                group,
+                has_params,
+                private,
+            };
+
             #[cfg(feature = "local-embeddings")]
-            {
-                query_with_fallback(
-                    &query,
-                    limit,
-                    threshold,
-                    &database,
-                    local_embeddings,
-                    mock_embeddings,
-                    openai_api_key,
-                    cache_dir,
-                )
-                .await?;
-            }
+            let results = query_with_fallback(
+                &query,
+                limit,
+                threshold,
+                &filters,
+                &database,
+                local_embeddings,
+                mock_embeddings,
+                openai_api_key,
+                output,
+                cache_dir,
+            )
+            .await?;
 
             #[cfg(not(feature = "local-embeddings"))]
-            {
-                query_with_fallback(
-                    &query,
-                    limit,
-                    threshold,
-                    &database,
-                    false,
-                    mock_embeddings,
-                    openai_api_key,
-                )
-                .await?;
-            }
+            let results = query_with_fallback(
+                &query,
+                limit,
+                threshold,
+                &filters,
+                &database,
+                false,
+                mock_embeddings,
+                openai_api_key,
+                output,
+            )
+            .await?;
+
+            output.print(&QueryResults { query, results })?;
         }
 
         SearchCommands::Index {
@@ -606,52 +1678,44 @@ pub async fn handle_search_command(search_command: SearchCommands) -> Result<()>
             #[cfg(feature = "local-embeddings")]
             cache_dir,
         } => {
-            println!("Indexing justfiles from: {}", directory.display());
+            output.progress(format!("Indexing justfiles from: {}", directory.display()));
 
             #[cfg(feature = "local-embeddings")]
-            {
-                index_with_fallback(
-                    &directory,
-                    &database,
-                    batch_size,
-                    local_embeddings,
-                    mock_embeddings,
-                    openai_api_key,
-                    cache_dir,
-                )
-                .await?;
-            }
+            let result = index_with_fallback(
+                &directory,
+                &database,
+                batch_size,
+                local_embeddings,
+                mock_embeddings,
+                openai_api_key,
+                output,
+                cache_dir,
+            )
+            .await?;
 
             #[cfg(not(feature = "local-embeddings"))]
-            {
-                index_with_fallback(
-                    &directory,
-                    &database,
-                    batch_size,
-                    false,
-                    mock_embeddings,
-                    openai_api_key,
-                )
-                .await?;
-            }
+            let result = index_with_fallback(
+                &directory,
+                &database,
+                batch_size,
+                false,
+                mock_embeddings,
+                openai_api_key,
+                output,
+            )
+            .await?;
+
+            output.print(&result)?;
         }
 
         SearchCommands::Stats { database } => {
             let manager = create_search_manager_mock(&database).await?;
 
-            let count = manager.get_document_count().await?;
-            let healthy = manager.health_check().await?;
-
-            println!("Vector Database Statistics");
-            println!("==========================");
-            println!("Database path: {}", database.display());
-            println!("Total documents: {}", count);
-            println!(
-                "Health status: {}",
-                if healthy { "Healthy" } else { "Unhealthy" }
-            );
-
-            // Additional stats could be added here
+            output.print(&DatabaseStats {
+                database: database.display().to_string(),
+                total_documents: manager.get_document_count().await?,
+                healthy: manager.health_check().await?,
+            })?;
         }
 
         SearchCommands::Similar {
@@ -666,31 +1730,31 @@ pub async fn handle_search_command(search_command: SearchCommands) -> Result<()>
             cache_dir,
         } => {
             #[cfg(feature = "local-embeddings")]
-            {
-                similar_with_fallback(
-                    &task,
-                    limit,
-                    &database,
-                    local_embeddings,
-                    mock_embeddings,
-                    openai_api_key,
-                    cache_dir,
-                )
-                .await?;
-            }
+            let results = similar_with_fallback(
+                &task,
+                limit,
+                &database,
+                local_embeddings,
+                mock_embeddings,
+                openai_api_key,
+                output,
+                cache_dir,
+            )
+            .await?;
 
             #[cfg(not(feature = "local-embeddings"))]
-            {
-                similar_with_fallback(
-                    &task,
-                    limit,
-                    &database,
-                    false,
-                    mock_embeddings,
-                    openai_api_key,
-                )
-                .await?;
-            }
+            let results = similar_with_fallback(
+                &task,
+                limit,
+                &database,
+                false,
+                mock_embeddings,
+                openai_api_key,
+                output,
+            )
+            .await?;
+
+            output.print(&SimilarTasks { task, results })?;
         }
 
         SearchCommands::Filter {
@@ -704,32 +1768,13 @@ pub async fn handle_search_command(search_command: SearchCommands) -> Result<()>
                 .iter()
                 .map(|(k, v)| (k.as_str(), v.as_str()))
                 .collect();
-            let results = manager.search_by_metadata(&filter_refs, limit).await?;
+            let documents = manager.search_by_metadata(&filter_refs, limit).await?;
 
-            if results.is_empty() {
-                println!("No documents found matching filters");
-            } else {
-                println!("Found {} documents matching filters:", results.len());
-                println!();
-
-                for (i, doc) in results.iter().enumerate() {
-                    println!("{}. ID: {}", i + 1, doc.id);
-                    if let Some(ref task_name) = doc.task_name {
-                        println!("   Task: {}", task_name);
-                    }
-                    if let Some(ref justfile) = doc.justfile_name {
-                        println!("   Justfile: {}", justfile);
-                    }
-                    println!(
-                        "   Content: {}",
-                        doc.content.chars().take(100).collect::<String>()
-                    );
-                    if doc.content.len() > 100 {
-                        println!("   ...");
-                    }
-                    println!();
-                }
-            }
+            output.print(&DocumentMatches {
+                filters: Some(filter.into_iter().collect()),
+                text: None,
+                documents,
+            })?;
         }
 
         SearchCommands::Text {
@@ -739,109 +1784,50 @@ pub async fn handle_search_command(search_command: SearchCommands) -> Result<()>
         } => {
             let manager = create_search_manager_mock(&database).await?;
 
-            let results = manager.search_by_content(&text, limit).await?;
+            let documents = manager.search_by_content(&text, limit).await?;
 
-            if results.is_empty() {
-                println!("No documents found containing text: '{}'", text);
-            } else {
-                println!(
-                    "Found {} documents containing text '{}':",
-                    results.len(),
-                    text
-                );
-                println!();
-
-                for (i, doc) in results.iter().enumerate() {
-                    println!("{}. ID: {}", i + 1, doc.id);
-                    if let Some(ref task_name) = doc.task_name {
-                        println!("   Task: {}", task_name);
-                    }
-                    if let Some(ref justfile) = doc.justfile_name {
-                        println!("   Justfile: {}", justfile);
-                    }
-
-                    // Highlight the search text in the content
-                    let highlighted = doc.content.replace(&text, &format!("**{}**", text));
-                    println!(
-                        "   Content: {}",
-                        highlighted.chars().take(200).collect::<String>()
-                    );
-                    if highlighted.len() > 200 {
-                        println!("   ...");
-                    }
-                    println!();
-                }
-            }
+            output.print(&DocumentMatches {
+                filters: None,
+                text: Some(text),
+                documents,
+            })?;
         }
 
         #[cfg(feature = "local-embeddings")]
         SearchCommands::CacheInfo { cache_dir } => {
-            use crate::vector_search::{ModelCache, ModelCacheConfig};
-
-            let config = if let Some(cache_dir) = cache_dir {
-                ModelCacheConfig {
-                    cache_dir,
-                    ..Default::default()
-                }
-            } else {
-                ModelCacheConfig::default()
-            };
-
-            let cache = ModelCache::with_config(config).await?;
+            let cache =
+                crate::vector_search::ModelCache::with_config(model_cache_config(cache_dir))
+                    .await?;
             let stats = cache.get_stats().await;
 
-            println!("Local Embedding Model Cache Information");
-            println!("======================================");
-            println!("Cache directory: {}", stats.cache_dir.display());
-            println!("Total models: {}", stats.total_models);
-            println!("Loaded models: {}", stats.loaded_models);
-            println!(
-                "Total size: {:.2} MB",
-                stats.total_size_bytes as f64 / 1024.0 / 1024.0
-            );
-
-            if stats.total_models > 0 {
-                println!("\nCached Models:");
-                let models = cache.list_cached_models().await;
-                for model in models {
-                    println!(
-                        "  • {} ({:.2} MB)",
-                        model.model_id,
-                        model.size_bytes as f64 / 1024.0 / 1024.0
-                    );
-                    println!(
-                        "    Last accessed: {}",
-                        model.last_accessed.format("%Y-%m-%d %H:%M:%S UTC")
-                    );
-                    if let Some(dimension) = model.dimension {
-                        println!("    Dimension: {}", dimension);
-                    }
-                }
-            }
+            output.print(&ModelCacheInfo {
+                cache_dir: stats.cache_dir.display().to_string(),
+                total_models: stats.total_models,
+                loaded_models: stats.loaded_models,
+                total_size_bytes: stats.total_size_bytes,
+                models: cache.list_cached_models().await,
+            })?;
         }
 
         #[cfg(feature = "local-embeddings")]
         SearchCommands::CacheClear { cache_dir, force } => {
-            use crate::vector_search::{ModelCache, ModelCacheConfig};
-
-            let config = if let Some(cache_dir) = cache_dir {
-                ModelCacheConfig {
-                    cache_dir,
-                    ..Default::default()
-                }
-            } else {
-                ModelCacheConfig::default()
-            };
-
-            let cache = ModelCache::with_config(config).await?;
+            let cache =
+                crate::vector_search::ModelCache::with_config(model_cache_config(cache_dir))
+                    .await?;
             let stats = cache.get_stats().await;
+            let mut result = CacheCleared {
+                cache_dir: stats.cache_dir.display().to_string(),
+                removed_models: 0,
+                cancelled: false,
+            };
 
-            if stats.total_models == 0 {
-                println!("No cached models found.");
-                return Ok(());
-            }
-
-            if !force {
+            if stats.total_models > 0 && !force {
+                // The confirmation prompt would mix with the JSON document
+                if output == OutputFormat::Json {
+                    return Err(anyhow::anyhow!(
+                        "Clearing the cache with --output json requires --force"
+                    ));
+                }
                 println!(
                     "This will remove {} cached models ({:.2} MB total).",
                     stats.total_models,
@@ -853,79 +1839,28 @@ pub async fn handle_search_command(search_command: SearchCommands) -> Result<()>
                 let mut input = String::new();
                 std::io::stdin().read_line(&mut input)?;
                 if input.trim().to_lowercase() != "y" && input.trim().to_lowercase() != "yes" {
-                    println!("Cache clear cancelled.");
-                    return Ok(());
+                    result.cancelled = true;
+                    return output.print(&result);
                 }
             }
 
-            cache.clear_all().await?;
-            println!(
-                "Successfully cleared {} models from cache.",
-                stats.total_models
-            );
+            if stats.total_models > 0 {
+                cache.clear_all().await?;
+                result.removed_models = stats.total_models;
+            }
+            output.print(&result)?;
         }
 
         #[cfg(feature = "local-embeddings")]
         SearchCommands::CacheList { cache_dir } => {
-            use crate::vector_search::{ModelCache, ModelCacheConfig};
-
-            let config = if let Some(cache_dir) = cache_dir {
-                ModelCacheConfig {
-                    cache_dir,
-                    ..Default::default()
-                }
-            } else {
-                ModelCacheConfig::default()
-            };
-
-            let cache_dir_path = config.cache_dir.clone();
-            let cache = ModelCache::with_config(config).await?;
-            let models = cache.list_cached_models().await;
-
-            if models.is_empty() {
-                println!("No cached models found.");
-                println!("Cache directory: {}", cache_dir_path.display());
-                return Ok(());
-            }
-
-            println!("Cached Local Embedding Models");
-            println!("=============================");
-            println!("Cache directory: {}", cache_dir_path.display());
-            println!();
-
-            for (i, model) in models.iter().enumerate() {
-                println!("{}. Model: {}", i + 1, model.model_id);
-                println!(
-                    "   Size: {:.2} MB",
-                    model.size_bytes as f64 / 1024.0 / 1024.0
-                );
-                println!(
-                    "   Downloaded: {}",
-                    model.downloaded_at.format("%Y-%m-%d %H:%M:%S UTC")
-                );
-                println!(
-                    "   Last accessed: {}",
-                    model.last_accessed.format("%Y-%m-%d %H:%M:%S UTC")
-                );
-                if let Some(dimension) = model.dimension {
-                    println!("   Dimension: {}", dimension);
-                }
-                if let Some(ref model_type) = model.model_type {
-                    println!("   Type: {}", model_type);
-                }
-                println!(
-                    "   Status: {}",
-                    if model.loaded { "Loaded" } else { "Cached" }
-                );
-                println!();
-            }
-
-            let total_size: u64 = models.iter().map(|m| m.size_bytes).sum();
-            println!(
-                "Total: {} models, {:.2} MB",
-                models.len(),
-                total_size as f64 / 1024.0 / 1024.0
-            );
+            let config = model_cache_config(cache_dir);
+            let cache_dir = config.cache_dir.display().to_string();
+            let cache = crate::vector_search::ModelCache::with_config(config).await?;
+
+            output.print(&CachedModels {
+                cache_dir,
+                models: cache.list_cached_models().await,
+            })?;
         }
     }
 
@@ -1005,50 +1940,23 @@ async fn query_search<E: crate::vector_search::EmbeddingProvider>(
     query: &str,
     limit: usize,
     threshold: f32,
-) -> Result<()> {
-    let results = manager
-        .search_with_threshold(query, limit, threshold)
-        .await?;
-
-    if results.is_empty() {
-        println!("No results found for query: '{}'", query);
+    filters: &SearchFilters,
+) -> Result<Vec<SearchResult>> {
+    let results = if filters.is_empty() {
+        manager
+            .search_with_threshold(query, limit, threshold)
+            .await?
     } else {
-        println!("Found {} results for query: '{}'", results.len(), query);
-        println!();
-
-        for (i, result) in results.iter().enumerate() {
-            println!("{}. Score: {:.4}", i + 1, result.score);
-            println!("   ID: {}", result.document.id);
-            if let Some(ref task_name) = result.document.task_name {
-                println!("   Task: {}", task_name);
-            }
-            if let Some(ref justfile) = result.document.justfile_name {
-                println!("   Justfile: {}", justfile);
-            }
-            println!(
-                "   Content: {}",
-                result
-                    .document
-                    .content
-                    .chars()
-                    .take(100)
-                    .collect::<String>()
-            );
-            if result.document.content.len() > 100 {
-                println!("   ...");
-            }
-            println!();
-        }
-    }
-
-    Ok(())
+        manager
+            .filtered_search(query, filters, limit, threshold)
+            .await?
+    };
+    Ok(results)
 }
 
-/// Find all justfiles in a directory recursively
+/// Find all justfiles in a directory recursively, skipping ignored paths
 #[cfg(feature = "vector-search")]
 fn find_justfiles(dir: &PathBuf) -> Result<Vec<PathBuf>> {
-    let mut justfiles = Vec::new();
-
     if !dir.exists() {
         return Err(anyhow::anyhow!(
             "Directory does not exist: {}",
@@ -1063,19 +1971,7 @@ fn find_justfiles(dir: &PathBuf) -> Result<Vec<PathBuf>> {
         ));
     }
 
-    for entry in walkdir::WalkDir::new(dir) {
-        let entry = entry?;
-        if entry.file_type().is_file() {
-            let path = entry.path();
-            if let Some(filename) = path.file_name() {
-                if filename == "justfile" || filename == "Justfile" {
-                    justfiles.push(path.to_path_buf());
-                }
-            }
-        }
-    }
-
-    Ok(justfiles)
+    Ok(crate::parser::discovery::walk_justfiles(dir))
 }
 
 /// Parse a justfile and extract tasks as documents
@@ -1094,6 +1990,11 @@ fn parse_justfile(justfile_path: &PathBuf) -> Result<Vec<Document>> {
         .and_then(|n| n.to_str())
         .map(|s| s.to_string());
     let source_path = justfile_path.to_string_lossy().to_string();
+    let project_name = justfile_path
+        .canonicalize()
+        .ok()
+        .and_then(|p| p.parent().map(Path::to_path_buf))
+        .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().to_string()));
 
     for task in tasks {
         let mut metadata = std::collections::HashMap::new();
@@ -1105,6 +2006,22 @@ fn parse_justfile(justfile_path: &PathBuf) -> Result<Vec<Document>> {
 
         metadata.insert("source_path".to_string(), source_path.clone());
         metadata.insert("line_number".to_string(), task.line_number.to_string());
+        metadata.insert(
+            SearchFilters::HAS_PARAMS_KEY.to_string(),
+            (!task.parameters.is_empty()).to_string(),
+        );
+        metadata.insert(
+            SearchFilters::PRIVATE_KEY.to_string(),
+            task.is_private.to_string(),
+        );
+
+        if let Some(ref project) = project_name {
+            metadata.insert(SearchFilters::PROJECT_KEY.to_string(), project.clone());
+        }
+
+        if let Some(ref group) = task.group {
+            metadata.insert(SearchFilters::GROUP_KEY.to_string(), group.clone());
+        }
 
         // Create content from task details
         let mut content_parts = vec![task.name.clone()];
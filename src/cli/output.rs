@@ -0,0 +1,86 @@
+//! Output rendering shared by the CLI commands
+//!
+//! Commands build a serializable result and hand it to
+//! [`OutputFormat::print`], which writes it as text for people or, with
+//! `--output json`, as a single pretty-printed JSON document for scripts.
+//! Field names in the JSON documents are part of the CLI's interface; the
+//! admin commands print the same results the `_admin_*` tools return, which
+//! follow the output schemas in [`crate::admin::schemas`].
+//!
+//! In JSON mode stdout carries nothing but that document: progress messages
+//! go to stderr, and a command that fails before producing a result prints
+//! `{"error": "..."}` instead.
+
+use serde::Serialize;
+use std::fmt::Display;
+
+/// How commands print their results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// One JSON document on stdout
+    Json,
+}
+
+/// A command result that can be printed as text or JSON
+pub trait CommandOutput: Serialize {
+    /// Print the result for people
+    fn print_text(&self);
+}
+
+impl OutputFormat {
+    /// Print a command's result
+    pub fn print<T: CommandOutput>(self, output: &T) -> anyhow::Result<()> {
+        match self {
+            OutputFormat::Text => output.print_text(),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(output)?),
+        }
+        Ok(())
+    }
+
+    /// Print a progress message, which in JSON mode must stay off stdout
+    pub fn progress(self, message: impl Display) {
+        match self {
+            OutputFormat::Text => println!("{message}"),
+            OutputFormat::Json => eprintln!("{message}"),
+        }
+    }
+
+    /// Report a failed command
+    ///
+    /// In JSON mode a command that failed without printing its result
+    /// prints an error document, so scripts always get one document.
+    pub fn print_error(self, error: &anyhow::Error) {
+        if self == OutputFormat::Json && !error.is::<CommandFailed>() {
+            println!("{}", serde_json::json!({ "error": format!("{error:#}") }));
+        }
+    }
+}
+
+/// Error for a command that printed its result and only needs to fail,
+/// such as a recipe run that failed
+#[derive(Debug)]
+pub struct CommandFailed(pub String);
+
+impl Display for CommandFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for CommandFailed {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_failed_is_not_reported_twice() {
+        let failed = anyhow::Error::new(CommandFailed("build failed".to_string()));
+        assert!(failed.is::<CommandFailed>());
+        assert_eq!(failed.to_string(), "build failed");
+        assert!(!anyhow::anyhow!("no justfile").is::<CommandFailed>());
+    }
+}
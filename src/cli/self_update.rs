@@ -0,0 +1,68 @@
+//! `self-update` command
+//!
+//! Replaces the running binary with the newest GitHub release on the chosen
+//! channel, after verifying the archive's published SHA-256 checksum.
+
+use super::{CommandOutput, OutputFormat};
+use crate::update::{self, Channel};
+use serde::Serialize;
+
+/// Result printed by `self-update`
+#[derive(Debug, Serialize)]
+pub struct UpdateResult {
+    pub current_version: &'static str,
+    /// Newest release on the channel, if newer than the running version
+    pub available: Option<String>,
+    /// Tags of the justfile syntax whose parsing the release fixes
+    pub parser_fixes: Vec<String>,
+    /// Where the release was installed, unless only checking
+    pub installed_path: Option<String>,
+}
+
+impl CommandOutput for UpdateResult {
+    fn print_text(&self) {
+        let Some(available) = &self.available else {
+            println!("just-mcp {} is up to date", self.current_version);
+            return;
+        };
+        println!(
+            "just-mcp {available} is available (running {})",
+            self.current_version
+        );
+        if !self.parser_fixes.is_empty() {
+            println!(
+                "It includes parser fixes for: {}",
+                self.parser_fixes.join(", ")
+            );
+        }
+        if let Some(path) = &self.installed_path {
+            println!("Checksum verified; installed {available} at {path}");
+        }
+    }
+}
+
+/// Update to the newest release on `channel`, or only report it if `check`
+pub async fn handle_self_update_command(
+    channel: Channel,
+    check: bool,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let mut result = UpdateResult {
+        current_version: crate::VERSION,
+        available: None,
+        parser_fixes: Vec::new(),
+        installed_path: None,
+    };
+    let Some(release) = update::check(channel).await? else {
+        return output.print(&result);
+    };
+
+    result.available = Some(release.tag_name.clone());
+    result.parser_fixes = release.parser_fix_tags().into_iter().collect();
+    if !check {
+        output.progress(format!("Downloading just-mcp {}...", release.tag_name));
+        let path = update::install(&release).await?;
+        result.installed_path = Some(path.display().to_string());
+    }
+    output.print(&result)
+}
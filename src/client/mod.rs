@@ -0,0 +1,342 @@
+//! Typed client for just-mcp servers
+//!
+//! [`JustMcpClient`] speaks MCP over any [`Transport`] and wraps the calls
+//! consumers of just-mcp make — listing and running recipe tools, reading
+//! resources and recipe summaries — so they don't have to build JSON-RPC
+//! payloads by hand. The CLI's `list` and `run` commands use it against an
+//! in-process server; embedders can point it at a `just-mcp` child process
+//! or any other transport.
+//!
+//! The client is sequential: each call sends one request and waits for its
+//! response. Notifications received in between, such as tool list changes,
+//! are skipped.
+
+use crate::error::{Error, Result};
+use crate::types::SUMMARY_URI_PREFIX;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use ultrafast_mcp::protocol::{
+    InitializeRequest, InitializeResponse, JsonRpcError, JsonRpcMessage, JsonRpcRequest,
+    JsonRpcResponse, RequestId, PROTOCOL_VERSION,
+};
+use ultrafast_mcp::{
+    ClientCapabilities, ClientInfo, ListResourcesRequest, ListResourcesResponse, ListToolsRequest,
+    ListToolsResponse, ReadResourceRequest, ReadResourceResponse, Resource, ResourceContent,
+    ServerInfo, Tool, ToolCall, ToolContent, ToolResult,
+};
+use ultrafast_mcp_transport::Transport;
+
+const METHOD_NOT_FOUND: i32 = -32601;
+
+/// Bytes buffered in each direction of an in-process connection
+#[cfg(feature = "ultrafast-framework")]
+const IN_PROCESS_BUFFER: usize = 64 * 1024;
+
+/// Text output of a tool call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOutput {
+    /// Text content of the result, one block per line
+    pub text: String,
+    /// Whether the recipe failed
+    pub is_error: bool,
+}
+
+impl RunOutput {
+    pub fn from_result(result: &ToolResult) -> Self {
+        let text = result
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                ToolContent::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Self {
+            text,
+            is_error: result.is_error.unwrap_or(false),
+        }
+    }
+}
+
+/// MCP client for a just-mcp server
+pub struct JustMcpClient {
+    transport: Box<dyn Transport>,
+    next_id: i64,
+    server_info: ServerInfo,
+}
+
+impl JustMcpClient {
+    /// Connect over `transport` and complete the MCP initialization handshake
+    pub async fn connect(transport: Box<dyn Transport>) -> Result<Self> {
+        let mut client = Self {
+            transport,
+            next_id: 1,
+            server_info: ServerInfo::new(String::new(), String::new()),
+        };
+
+        let initialize = InitializeRequest {
+            protocol_version: PROTOCOL_VERSION.to_string(),
+            capabilities: ClientCapabilities::default(),
+            client_info: ClientInfo {
+                name: format!("{}-client", crate::PKG_NAME),
+                version: crate::VERSION.to_string(),
+                ..ClientInfo::default()
+            },
+        };
+        let response: InitializeResponse = client.request("initialize", initialize).await?;
+        client.server_info = response.server_info;
+        client
+            .send(JsonRpcMessage::Notification(JsonRpcRequest::notification(
+                "notifications/initialized".to_string(),
+                None,
+            )))
+            .await?;
+
+        Ok(client)
+    }
+
+    /// Start `server` in the background and connect to it in memory
+    ///
+    /// The server runs until the client is closed or dropped.
+    #[cfg(feature = "ultrafast-framework")]
+    pub async fn in_process(mut server: crate::server::FrameworkServer) -> Result<Self> {
+        use crate::server::batch_transport::BatchTransport;
+
+        let (client_io, server_io) = tokio::io::duplex(IN_PROCESS_BUFFER);
+        tokio::spawn(async move {
+            let (reader, writer) = tokio::io::split(server_io);
+            if let Err(e) = server
+                .run_with_transport(Box::new(BatchTransport::new(reader, writer)))
+                .await
+            {
+                tracing::debug!("In-process server stopped: {e}");
+            }
+        });
+
+        let (reader, writer) = tokio::io::split(client_io);
+        Self::connect(Box::new(BatchTransport::new(reader, writer))).await
+    }
+
+    /// Name and version the server reported during initialization
+    pub fn server_info(&self) -> &ServerInfo {
+        &self.server_info
+    }
+
+    /// Every tool the server offers, following pagination
+    pub async fn list_tools(&mut self) -> Result<Vec<Tool>> {
+        let mut tools = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page: ListToolsResponse = self
+                .request("tools/list", ListToolsRequest { cursor })
+                .await?;
+            tools.extend(page.tools);
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(tools),
+            }
+        }
+    }
+
+    /// Call a tool with raw JSON arguments
+    pub async fn call_tool(&mut self, name: &str, arguments: Value) -> Result<ToolResult> {
+        let call = ToolCall {
+            name: name.to_string(),
+            arguments: Some(arguments),
+        };
+        self.request("tools/call", call).await
+    }
+
+    /// Run a recipe tool and collect its text output
+    ///
+    /// A failing recipe is not an error here; check
+    /// [`RunOutput::is_error`].
+    pub async fn run(&mut self, name: &str, arguments: Map<String, Value>) -> Result<RunOutput> {
+        let result = self.call_tool(name, Value::Object(arguments)).await?;
+        Ok(RunOutput::from_result(&result))
+    }
+
+    /// Every resource the server offers, following pagination
+    pub async fn list_resources(&mut self) -> Result<Vec<Resource>> {
+        let mut resources = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page: ListResourcesResponse = self
+                .request("resources/list", ListResourcesRequest { cursor })
+                .await?;
+            resources.extend(page.resources);
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(resources),
+            }
+        }
+    }
+
+    /// Text of a resource; binary contents are skipped
+    pub async fn read_resource(&mut self, uri: &str) -> Result<String> {
+        let response: ReadResourceResponse = self
+            .request(
+                "resources/read",
+                ReadResourceRequest {
+                    uri: uri.to_string(),
+                },
+            )
+            .await?;
+        Ok(response
+            .contents
+            .into_iter()
+            .filter_map(|content| match content {
+                ResourceContent::Text { text, .. } => Some(text),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    /// Recipe catalog of a project, fitted to `max_tokens` if given
+    pub async fn summary(&mut self, project: &str, max_tokens: Option<usize>) -> Result<String> {
+        let mut uri = format!("{SUMMARY_URI_PREFIX}{project}");
+        if let Some(max_tokens) = max_tokens {
+            uri.push_str(&format!("?max_tokens={max_tokens}"));
+        }
+        self.read_resource(&uri).await
+    }
+
+    /// Close the transport, which ends the session on the server
+    pub async fn close(mut self) -> Result<()> {
+        self.transport
+            .close()
+            .await
+            .map_err(|e| Error::Server(format!("Failed to close transport: {e}")))
+    }
+
+    async fn send(&mut self, message: JsonRpcMessage) -> Result<()> {
+        self.transport
+            .send_message(message)
+            .await
+            .map_err(|e| Error::Server(format!("Failed to send request: {e}")))
+    }
+
+    /// Send a request and wait for its response
+    async fn request<P: Serialize, R: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        params: P,
+    ) -> Result<R> {
+        let id = RequestId::Number(self.next_id);
+        self.next_id += 1;
+        self.send(JsonRpcMessage::Request(JsonRpcRequest::new(
+            method.to_string(),
+            Some(serde_json::to_value(params)?),
+            Some(id.clone()),
+        )))
+        .await?;
+
+        loop {
+            let message =
+                self.transport.receive_message().await.map_err(|e| {
+                    Error::Server(format!("Connection lost waiting for {method}: {e}"))
+                })?;
+            match message {
+                JsonRpcMessage::Response(response) if response.id.as_ref() == Some(&id) => {
+                    if let Some(error) = response.error {
+                        return Err(Error::Server(format!(
+                            "{method} failed: {} (code {})",
+                            error.message, error.code
+                        )));
+                    }
+                    return Ok(serde_json::from_value(
+                        response.result.unwrap_or(Value::Null),
+                    )?);
+                }
+                JsonRpcMessage::Request(request) if request.id.is_some() => {
+                    self.answer_server_request(request).await?;
+                }
+                other => {
+                    tracing::trace!("Skipping message while waiting for {method}: {other:?}");
+                }
+            }
+        }
+    }
+
+    /// Answer pings; the client offers no other capabilities
+    async fn answer_server_request(&mut self, request: JsonRpcRequest) -> Result<()> {
+        let response = if request.method == "ping" {
+            JsonRpcResponse::success(Value::Object(Map::new()), request.id)
+        } else {
+            JsonRpcResponse::error(
+                JsonRpcError::new(
+                    METHOD_NOT_FOUND,
+                    format!("Client does not support {}", request.method),
+                ),
+                request.id,
+            )
+        };
+        self.send(JsonRpcMessage::Response(response)).await
+    }
+}
+
+#[cfg(all(test, feature = "ultrafast-framework"))]
+mod tests {
+    use super::*;
+    use crate::server::FrameworkServer;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    async fn connect(temp_dir: &TempDir) -> JustMcpClient {
+        let server = FrameworkServer::new()
+            .with_watch_paths(vec![temp_dir.path().to_path_buf()])
+            .with_watch_names(vec![(
+                temp_dir.path().to_path_buf(),
+                Some("demo".to_string()),
+            )])
+            .with_security_config(crate::security::SecurityConfig {
+                allowed_paths: vec![temp_dir.path().to_path_buf()],
+                ..Default::default()
+            });
+        JustMcpClient::in_process(server).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_and_run_tools() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("justfile"),
+            "# Say hello\nhello name:\n    echo \"Hello, {{name}}!\"\n\nfail:\n    exit 3\n",
+        )
+        .unwrap();
+
+        let mut client = connect(&temp_dir).await;
+        assert_eq!(client.server_info().name, crate::PKG_NAME);
+
+        let tools = client.list_tools().await.unwrap();
+        let hello = tools
+            .iter()
+            .find(|tool| tool.name.starts_with("hello"))
+            .expect("hello tool");
+        assert_eq!(hello.input_schema["required"], json!(["name"]));
+
+        let mut arguments = Map::new();
+        arguments.insert("name".to_string(), json!("client"));
+        let output = client.run(&hello.name, arguments).await.unwrap();
+        assert!(!output.is_error, "{}", output.text);
+        assert!(output.text.contains("Hello, client!"));
+
+        let fail = tools
+            .iter()
+            .find(|tool| tool.name.starts_with("fail"))
+            .unwrap()
+            .name
+            .clone();
+        assert!(client.run(&fail, Map::new()).await.unwrap().is_error);
+
+        let summary = client.summary("demo", None).await.unwrap();
+        assert!(summary.starts_with("# demo: 2 recipes"), "{summary}");
+        assert!(client.read_resource("just://summary/nope").await.is_err());
+
+        client.close().await.unwrap();
+    }
+}
@@ -213,6 +213,12 @@ impl ConfigDataCollector {
                 "max_concurrent_executions": limits.max_concurrent_executions,
                 "max_output_size_bytes": limits.max_output_size,
                 "enforce_hard_limits": limits.enforce_hard_limits,
+                "max_concurrent_per_project": limits.max_concurrent_per_project,
+                "project_concurrency_limits": limits.project_concurrency_limits,
+                "default_jobs": limits.default_jobs,
+                "project_jobs": limits.project_jobs,
+                "group_jobs": limits.group_jobs,
+                "max_jobs": limits.max_jobs,
                 "current_executions": current_executions
             })
         } else {
@@ -224,6 +230,12 @@ impl ConfigDataCollector {
                 "max_concurrent_executions": 10,
                 "max_output_size_bytes": 10485760,
                 "enforce_hard_limits": true,
+                "max_concurrent_per_project": null,
+                "project_concurrency_limits": {},
+                "default_jobs": null,
+                "project_jobs": {},
+                "group_jobs": {},
+                "max_jobs": crate::resource_limits::available_cores(),
                 "current_executions": 0
             })
         }
@@ -234,6 +246,8 @@ impl ConfigDataCollector {
         json!({
             "stdio_transport": cfg!(feature = "stdio"),
             "http_transport": cfg!(feature = "http"),
+            "watcher": cfg!(feature = "watcher"),
+            "admin_tools": cfg!(feature = "admin"),
             "vector_search": cfg!(feature = "vector-search"),
             "local_embeddings": cfg!(feature = "local-embeddings"),
             "ast_parser": cfg!(feature = "ast-parser")
@@ -412,6 +426,14 @@ impl ConfigDataCollector {
             _ => "Unknown parser preference",
         };
 
+        // Per-project overrides, keyed by watch directory name or path
+        let project_overrides: serde_json::Map<String, Value> = self
+            .args
+            .iter()
+            .flat_map(|args| &args.project_parsers)
+            .map(|(project, preference)| (project.clone(), json!(preference.to_string())))
+            .collect();
+
         json!({
             "ast_parser_available": cfg!(feature = "ast-parser"),
             "cli_parser_available": true,
@@ -420,6 +442,7 @@ impl ConfigDataCollector {
             "current_preference": current_preference,
             "preference_source": preference_source,
             "description": parser_description,
+            "project_overrides": project_overrides,
             "default_parser": if cfg!(feature = "ast-parser") { "auto" } else { "cli" },
             "parser_priority": if cfg!(feature = "ast-parser") {
                 vec!["ast", "cli"]
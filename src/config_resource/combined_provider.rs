@@ -65,7 +65,7 @@ impl ResourceProvider for CombinedResourceProvider {
         if let Some(provider) = self.route_uri(uri) {
             provider.read_resource(uri).await
         } else {
-            Err(anyhow::anyhow!("Resource not found: {}", uri))
+            Err(anyhow::anyhow!("Resource not found: {uri}"))
         }
     }
 
@@ -33,8 +33,7 @@ impl ConfigResourceProvider {
     fn validate_config_uri(uri: &str) -> Result<()> {
         if uri != "file:///config.json" {
             return Err(anyhow::anyhow!(
-                "Invalid config resource URI: {}. Expected: file:///config.json",
-                uri
+                "Invalid config resource URI: {uri}. Expected: file:///config.json"
             ));
         }
         Ok(())
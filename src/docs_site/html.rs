@@ -0,0 +1,210 @@
+//! HTML rendering of the documentation site
+//!
+//! Pages are self-contained apart from Mermaid, which is loaded from a CDN
+//! to draw dependency graphs; without network access the graph's source is
+//! shown instead.
+
+use super::{project_page, recipe_anchor, DocsFormat, ProjectDocs, RecipeDocs, Site};
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;line-height:1.5}\
+code,pre{background:#f4f4f4;border-radius:3px}pre{padding:.5rem;overflow-x:auto}\
+table{border-collapse:collapse}td,th{border:1px solid #ccc;padding:.25rem .5rem;text-align:left}\
+h3{border-top:1px solid #eee;padding-top:1rem}";
+
+const MERMAID: &str = "<script type=\"module\">import mermaid from 'https://cdn.jsdelivr.net/npm/mermaid@10/dist/mermaid.esm.min.mjs';mermaid.initialize({startOnLoad:true});</script>";
+
+pub(super) fn index(site: &Site) -> String {
+    let mut body = String::from("<h1>Recipe documentation</h1>\n");
+    if site.projects.is_empty() {
+        body.push_str("<p>No justfiles found in the watch directories.</p>\n");
+        return page("Recipe documentation", &body, false);
+    }
+    body.push_str("<table>\n<tr><th>Project</th><th>Justfile</th><th>Recipes</th></tr>\n");
+    for project in &site.projects {
+        let recipes = match &project.error {
+            Some(_) => "parse error".to_string(),
+            None => project.recipes.len().to_string(),
+        };
+        body.push_str(&format!(
+            "<tr><td><a href=\"{}\">{}</a></td><td><code>{}</code></td><td>{}</td></tr>\n",
+            project_page(project, DocsFormat::Html),
+            escape(&project.name),
+            escape(&project.justfile.display().to_string()),
+            recipes
+        ));
+    }
+    body.push_str("</table>\n<p>See also <a href=\"groups.html\">recipes by group</a>.</p>\n");
+    page("Recipe documentation", &body, false)
+}
+
+pub(super) fn groups(site: &Site) -> String {
+    let mut body =
+        String::from("<h1>Recipes by group</h1>\n<p><a href=\"index.html\">All projects</a></p>\n");
+    let index = site.group_index();
+    if index.is_empty() {
+        body.push_str("<p>No recipe has a group.</p>\n");
+    }
+    for (group, recipes) in index {
+        body.push_str(&format!("<h2>{}</h2>\n<ul>\n", escape(group)));
+        for (project, recipe) in recipes {
+            body.push_str(&format!(
+                "<li><a href=\"{}#{}\">{}</a> ({})",
+                project_page(project, DocsFormat::Html),
+                recipe_anchor(&recipe.name),
+                escape(&recipe.name),
+                escape(&project.name)
+            ));
+            if let Some(description) = &recipe.description {
+                body.push_str(&format!(": {}", escape(description)));
+            }
+            body.push_str("</li>\n");
+        }
+        body.push_str("</ul>\n");
+    }
+    page("Recipes by group", &body, false)
+}
+
+pub(super) fn project(project: &ProjectDocs) -> String {
+    let mut body = format!(
+        "<h1>{}</h1>\n<p><a href=\"index.html\">All projects</a> · <a href=\"groups.html\">Recipes by group</a></p>\n<p>Recipes of <code>{}</code>.</p>\n",
+        escape(&project.name),
+        escape(&project.justfile.display().to_string())
+    );
+    if let Some(error) = &project.error {
+        body.push_str(&format!(
+            "<p><strong>The justfile could not be parsed:</strong> {}</p>\n",
+            escape(error)
+        ));
+    }
+    if !project.imports.is_empty() {
+        body.push_str("<p>Imports:</p>\n<ul>\n");
+        for (path, exists) in &project.imports {
+            let missing = if *exists { "" } else { " (missing)" };
+            body.push_str(&format!(
+                "<li><code>{}</code>{missing}</li>\n",
+                escape(&path.display().to_string())
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    let sections = project.sections();
+    if !sections.is_empty() {
+        body.push_str("<h2>Groups</h2>\n<ul>\n");
+        for (group, recipes) in &sections {
+            let links: Vec<String> = recipes.iter().map(|r| recipe_link(&r.name)).collect();
+            body.push_str(&format!(
+                "<li><strong>{}</strong>: {}</li>\n",
+                escape(group),
+                links.join(", ")
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    let graph = project.has_dependencies();
+    if graph {
+        body.push_str(&format!(
+            "<h2>Dependency graph</h2>\n<pre class=\"mermaid\">\n{}</pre>\n",
+            escape(&project.mermaid_graph())
+        ));
+    }
+
+    if !project.recipes.is_empty() {
+        body.push_str("<h2>Recipes</h2>\n");
+        for recipe in &project.recipes {
+            body.push_str(&recipe_entry(project, recipe));
+        }
+    }
+    page(&project.name, &body, graph)
+}
+
+fn recipe_entry(project: &ProjectDocs, recipe: &RecipeDocs) -> String {
+    let mut entry = format!(
+        "<h3 id=\"{}\">{}</h3>\n",
+        recipe_anchor(&recipe.name),
+        escape(&recipe.name)
+    );
+    if let Some(description) = &recipe.description {
+        entry.push_str(&format!("<p>{}</p>\n", escape(description)));
+    }
+    if let Some(long) = &recipe.long_description {
+        if recipe.description.as_deref() != Some(long.as_str()) {
+            entry.push_str(&format!("<pre>{}</pre>\n", escape(long)));
+        }
+    }
+    entry.push_str(&format!(
+        "<pre><code>{}</code></pre>\n",
+        escape(&recipe.usage())
+    ));
+    if let Some(tool) = &recipe.tool {
+        entry.push_str(&format!("<p>MCP tool: <code>{}</code></p>\n", escape(tool)));
+    }
+    if let Some(confirm) = &recipe.confirm {
+        entry.push_str(&format!(
+            "<p>Asks for confirmation: {}</p>\n",
+            escape(confirm)
+        ));
+    }
+
+    if !recipe.parameters.is_empty() {
+        entry
+            .push_str("<table>\n<tr><th>Parameter</th><th>Default</th><th>Description</th></tr>\n");
+        for parameter in &recipe.parameters {
+            let default = match &parameter.default {
+                Some(default) => format!("<code>{}</code>", escape(default)),
+                None => "required".to_string(),
+            };
+            entry.push_str(&format!(
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>\n",
+                escape(&parameter.name),
+                default,
+                escape(parameter.description.as_deref().unwrap_or(""))
+            ));
+        }
+        entry.push_str("</table>\n");
+    }
+
+    let link = |name: &String| {
+        if project.recipes.iter().any(|r| &r.name == name) {
+            recipe_link(name)
+        } else {
+            format!("<code>{}</code>", escape(name))
+        }
+    };
+    if !recipe.dependencies.is_empty() {
+        let links: Vec<String> = recipe.dependencies.iter().map(link).collect();
+        entry.push_str(&format!("<p>Depends on: {}</p>\n", links.join(", ")));
+    }
+    if !recipe.required_by.is_empty() {
+        let links: Vec<String> = recipe.required_by.iter().map(link).collect();
+        entry.push_str(&format!("<p>Required by: {}</p>\n", links.join(", ")));
+    }
+    if !recipe.groups.is_empty() {
+        entry.push_str(&format!(
+            "<p>Groups: {}</p>\n",
+            escape(&recipe.groups.join(", "))
+        ));
+    }
+    entry
+}
+
+fn recipe_link(name: &str) -> String {
+    format!("<a href=\"#{}\">{}</a>", recipe_anchor(name), escape(name))
+}
+
+/// A complete page around `body`
+fn page(title: &str, body: &str, mermaid: bool) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{STYLE}</style>\n{}</head>\n<body>\n{body}</body>\n</html>\n",
+        escape(title),
+        if mermaid { MERMAID } else { "" }
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,167 @@
+//! Markdown rendering of the documentation site
+//!
+//! Recipe anchors are written as explicit `<a id>` tags, since renderers
+//! derive heading anchors differently. Dependency graphs are Mermaid code
+//! blocks, which GitHub and GitLab draw as diagrams.
+
+use super::{project_page, recipe_anchor, DocsFormat, ProjectDocs, RecipeDocs, Site};
+
+pub(super) fn index(site: &Site) -> String {
+    let mut page = String::from("# Recipe documentation\n\n");
+    if site.projects.is_empty() {
+        page.push_str("No justfiles found in the watch directories.\n");
+        return page;
+    }
+    page.push_str("| Project | Justfile | Recipes |\n|---|---|---|\n");
+    for project in &site.projects {
+        let recipes = match &project.error {
+            Some(_) => "parse error".to_string(),
+            None => project.recipes.len().to_string(),
+        };
+        page.push_str(&format!(
+            "| [{}]({}) | `{}` | {} |\n",
+            cell(&project.name),
+            project_page(project, DocsFormat::Markdown),
+            project.justfile.display(),
+            recipes
+        ));
+    }
+    page.push_str("\nSee also [recipes by group](groups.md).\n");
+    page
+}
+
+pub(super) fn groups(site: &Site) -> String {
+    let mut page = String::from("# Recipes by group\n\n[All projects](index.md)\n");
+    let index = site.group_index();
+    if index.is_empty() {
+        page.push_str("\nNo recipe has a group.\n");
+    }
+    for (group, recipes) in index {
+        page.push_str(&format!("\n## {group}\n\n"));
+        for (project, recipe) in recipes {
+            page.push_str(&format!(
+                "- [{}]({}#{}) ({})",
+                recipe.name,
+                project_page(project, DocsFormat::Markdown),
+                recipe_anchor(&recipe.name),
+                project.name
+            ));
+            if let Some(description) = &recipe.description {
+                page.push_str(&format!(": {description}"));
+            }
+            page.push('\n');
+        }
+    }
+    page
+}
+
+pub(super) fn project(project: &ProjectDocs) -> String {
+    let mut page = format!(
+        "# {}\n\n[All projects](index.md) · [Recipes by group](groups.md)\n\nRecipes of `{}`.\n",
+        project.name,
+        project.justfile.display()
+    );
+    if let Some(error) = &project.error {
+        page.push_str(&format!("\n> The justfile could not be parsed: {error}\n"));
+    }
+    if !project.imports.is_empty() {
+        page.push_str("\nImports:\n\n");
+        for (path, exists) in &project.imports {
+            let missing = if *exists { "" } else { " (missing)" };
+            page.push_str(&format!("- `{}`{missing}\n", path.display()));
+        }
+    }
+
+    let sections = project.sections();
+    if !sections.is_empty() {
+        page.push_str("\n## Groups\n\n");
+        for (group, recipes) in &sections {
+            let links: Vec<String> = recipes.iter().map(|r| recipe_link(&r.name)).collect();
+            page.push_str(&format!("- **{group}**: {}\n", links.join(", ")));
+        }
+    }
+
+    if project.has_dependencies() {
+        page.push_str(&format!(
+            "\n## Dependency graph\n\n```mermaid\n{}```\n",
+            project.mermaid_graph()
+        ));
+    }
+
+    if !project.recipes.is_empty() {
+        page.push_str("\n## Recipes\n");
+        for recipe in &project.recipes {
+            page.push_str(&recipe_entry(project, recipe));
+        }
+    }
+    page
+}
+
+fn recipe_entry(project: &ProjectDocs, recipe: &RecipeDocs) -> String {
+    let mut entry = format!(
+        "\n<a id=\"{}\"></a>\n### {}\n\n",
+        recipe_anchor(&recipe.name),
+        recipe.name
+    );
+    if let Some(description) = &recipe.description {
+        entry.push_str(&format!("{description}\n\n"));
+    }
+    if let Some(long) = &recipe.long_description {
+        if recipe.description.as_deref() != Some(long.as_str()) {
+            entry.push_str(&format!("{long}\n\n"));
+        }
+    }
+    entry.push_str(&format!("```sh\n{}\n```\n\n", recipe.usage()));
+    if let Some(tool) = &recipe.tool {
+        entry.push_str(&format!("MCP tool: `{tool}`\n\n"));
+    }
+    if let Some(confirm) = &recipe.confirm {
+        entry.push_str(&format!("Asks for confirmation: {confirm}\n\n"));
+    }
+
+    if !recipe.parameters.is_empty() {
+        entry.push_str("| Parameter | Default | Description |\n|---|---|---|\n");
+        for parameter in &recipe.parameters {
+            let default = match &parameter.default {
+                Some(default) => format!("`{}`", cell(default)),
+                None => "required".to_string(),
+            };
+            entry.push_str(&format!(
+                "| `{}` | {} | {} |\n",
+                parameter.name,
+                default,
+                cell(parameter.description.as_deref().unwrap_or(""))
+            ));
+        }
+        entry.push('\n');
+    }
+
+    let link = |name: &String| {
+        if project.recipes.iter().any(|r| &r.name == name) {
+            recipe_link(name)
+        } else {
+            format!("`{name}`")
+        }
+    };
+    if !recipe.dependencies.is_empty() {
+        let links: Vec<String> = recipe.dependencies.iter().map(link).collect();
+        entry.push_str(&format!("Depends on: {}\n\n", links.join(", ")));
+    }
+    if !recipe.required_by.is_empty() {
+        let links: Vec<String> = recipe.required_by.iter().map(link).collect();
+        entry.push_str(&format!("Required by: {}\n\n", links.join(", ")));
+    }
+    if !recipe.groups.is_empty() {
+        entry.push_str(&format!("Groups: {}\n", recipe.groups.join(", ")));
+    }
+    entry
+}
+
+fn recipe_link(name: &str) -> String {
+    format!("[{name}](#{})", recipe_anchor(name))
+}
+
+/// Text that fits in a table cell
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
@@ -0,0 +1,572 @@
+//! Static documentation site for the watched justfiles
+//!
+//! `just-mcp docs generate` writes browsable reference pages for the recipes
+//! the server exposes, for people rather than agents: an index of projects,
+//! a page per project with its import graph, group index, dependency graph
+//! and a reference entry per recipe, and an index of groups across projects.
+//!
+//! Recipes are loaded the way the server loads them: the watcher registers
+//! each project's justfile, so pages show the tool name agents call, and the
+//! same parser supplies parameters and dependencies. The site is rendered as
+//! Markdown (which GitHub and most wikis display directly) or as plain HTML.
+//!
+//! The output directory may hold hand-written documentation. The files a run
+//! writes are listed in [`MANIFEST`], and a later run only replaces (or
+//! removes) files listed there; any other file in the way is an error unless
+//! overwriting is forced.
+
+mod html;
+mod markdown;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::parser::discovery::find_justfile;
+use crate::parser::imports::resolve_import_graph;
+use crate::parser::{EnhancedJustfileParser, ParserPreference};
+use crate::registry::ToolRegistry;
+use crate::types::Parameter;
+use crate::watcher::JustfileWatcher;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Heading for recipes without a group, as in the recipe summary
+const UNGROUPED: &str = "other";
+
+/// File in the output directory listing the pages the last run wrote
+pub const MANIFEST: &str = ".just-mcp-docs";
+
+/// Format the site is written in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum DocsFormat {
+    /// Markdown pages
+    #[default]
+    Markdown,
+    /// HTML pages with a small embedded stylesheet
+    Html,
+}
+
+impl DocsFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            DocsFormat::Markdown => "md",
+            DocsFormat::Html => "html",
+        }
+    }
+}
+
+/// Everything the site documents
+#[derive(Debug, Clone)]
+pub struct Site {
+    pub projects: Vec<ProjectDocs>,
+}
+
+/// A watch directory and the recipes of its justfile
+#[derive(Debug, Clone)]
+pub struct ProjectDocs {
+    pub name: String,
+    /// Name of the project's page without extension, unique in the site
+    pub slug: String,
+    pub justfile: PathBuf,
+    /// Files the justfile imports, with whether they exist
+    pub imports: Vec<(PathBuf, bool)>,
+    /// Public recipes, in justfile order
+    pub recipes: Vec<RecipeDocs>,
+    /// Why the justfile couldn't be parsed, if it couldn't
+    pub error: Option<String>,
+}
+
+/// Reference entry of a recipe
+#[derive(Debug, Clone)]
+pub struct RecipeDocs {
+    pub name: String,
+    /// Tool agents call the recipe by, if it is registered
+    pub tool: Option<String>,
+    pub description: Option<String>,
+    pub long_description: Option<String>,
+    pub parameters: Vec<Parameter>,
+    pub dependencies: Vec<String>,
+    /// Recipes of the same justfile that depend on this one
+    pub required_by: Vec<String>,
+    pub groups: Vec<String>,
+    /// Prompt shown by `[confirm]`
+    pub confirm: Option<String>,
+}
+
+/// Files written by [`Site::write`]
+#[derive(Debug, Clone, Serialize)]
+pub struct DocsReport {
+    pub out_dir: String,
+    pub format: DocsFormat,
+    pub projects: usize,
+    pub recipes: usize,
+    pub files: Vec<String>,
+}
+
+impl Site {
+    /// Load the recipes of each watch directory's justfile
+    pub async fn collect(
+        watch_configs: &[(PathBuf, Option<String>)],
+        preference: ParserPreference,
+        parser_overrides: Vec<(PathBuf, ParserPreference)>,
+    ) -> Result<Self> {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let mut watcher =
+            JustfileWatcher::new_with_parser_preference(registry.clone(), preference.clone());
+        watcher.set_parser_overrides(parser_overrides);
+        watcher.configure_names(watch_configs).await;
+        watcher.set_multiple_dirs(watch_configs.len() > 1);
+        let parser = EnhancedJustfileParser::new_with_preference(preference)?;
+
+        let mut projects = Vec::new();
+        let mut slugs = HashSet::new();
+        for (path, name) in watch_configs {
+            let justfile = if path.is_dir() {
+                match find_justfile(path) {
+                    Some(justfile) => justfile,
+                    None => continue,
+                }
+            } else {
+                path.clone()
+            };
+            let name = name.clone().unwrap_or_else(|| project_name(&justfile));
+
+            let parsed = match watcher
+                .parse_and_update_justfile_without_notification(&justfile)
+                .await
+            {
+                Ok(_) => parser
+                    .parse_file_for_tools_using(&justfile, watcher.effective_parser(&justfile)),
+                Err(e) => Err(e),
+            };
+            let imports = resolve_import_graph(&justfile)
+                .map(|files| {
+                    files
+                        .into_iter()
+                        .skip(1)
+                        .map(|file| (file.path, file.exists))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            let (recipes, error) = match parsed {
+                Ok(tasks) => {
+                    let registry = registry.lock().await;
+                    let recipes = tasks
+                        .into_iter()
+                        .map(|task| {
+                            let internal_name = format!("{}_{}", task.name, justfile.display());
+                            let tool = registry
+                                .list_tools()
+                                .into_iter()
+                                .find(|tool| tool.internal_name.as_deref() == Some(&internal_name));
+                            let mut groups = tool
+                                .map(|tool| tool.metadata.groups.clone())
+                                .unwrap_or_default();
+                            if groups.is_empty() {
+                                groups.extend(task.group.clone());
+                            }
+                            let description = if !task.comments.is_empty() {
+                                Some(task.comments.join(". "))
+                            } else {
+                                task.doc.clone()
+                            };
+                            RecipeDocs {
+                                tool: tool.map(|tool| tool.name.clone()),
+                                description,
+                                long_description: task.long_description,
+                                parameters: task.parameters,
+                                dependencies: task.dependencies,
+                                required_by: Vec::new(),
+                                groups,
+                                confirm: task.confirm_message,
+                                name: task.name,
+                            }
+                        })
+                        .collect();
+                    (recipes, None)
+                }
+                Err(e) => (Vec::new(), Some(e.to_string())),
+            };
+
+            let mut project = ProjectDocs {
+                slug: unique_slug(&name, &mut slugs),
+                name,
+                justfile,
+                imports,
+                recipes,
+                error,
+            };
+            project.link_dependents();
+            projects.push(project);
+        }
+
+        Ok(Self { projects })
+    }
+
+    /// Pages of the site, by file name
+    pub fn render(&self, format: DocsFormat) -> Vec<(String, String)> {
+        let extension = format.extension();
+        let mut pages = vec![
+            (format!("index.{extension}"), self.render_index(format)),
+            (format!("groups.{extension}"), self.render_groups(format)),
+        ];
+        for project in &self.projects {
+            let page = match format {
+                DocsFormat::Markdown => markdown::project(project),
+                DocsFormat::Html => html::project(project),
+            };
+            pages.push((project_page(project, format), page));
+        }
+        pages
+    }
+
+    /// Render the site into `out_dir`, replacing pages written before
+    ///
+    /// Existing files that an earlier run didn't write are left alone and
+    /// reported as an error, unless `force` is set. Pages of an earlier run
+    /// that this one doesn't write, such as those of a removed project, are
+    /// deleted.
+    pub fn write(&self, out_dir: &Path, format: DocsFormat, force: bool) -> Result<DocsReport> {
+        let pages = self.render(format);
+        let previous = read_manifest(out_dir);
+        if !force {
+            let foreign: Vec<String> = pages
+                .iter()
+                .filter(|(name, _)| !previous.contains(name) && out_dir.join(name).exists())
+                .map(|(name, _)| out_dir.join(name).display().to_string())
+                .collect();
+            if !foreign.is_empty() {
+                return Err(Error::Other(format!(
+                    "Not overwriting files the docs generator didn't write: {}. \
+                     Choose another --out directory or pass --force",
+                    foreign.join(", ")
+                )));
+            }
+        }
+
+        std::fs::create_dir_all(out_dir)?;
+        let mut files = Vec::new();
+        for (name, content) in &pages {
+            let path = out_dir.join(name);
+            std::fs::write(&path, content)?;
+            files.push(path.to_string_lossy().to_string());
+        }
+        let written: HashSet<&str> = pages.iter().map(|(name, _)| name.as_str()).collect();
+        for stale in previous
+            .iter()
+            .filter(|name| !written.contains(name.as_str()))
+        {
+            let _ = std::fs::remove_file(out_dir.join(stale));
+        }
+        let manifest: Vec<&str> = pages.iter().map(|(name, _)| name.as_str()).collect();
+        std::fs::write(out_dir.join(MANIFEST), manifest.join("\n") + "\n")?;
+
+        Ok(DocsReport {
+            out_dir: out_dir.to_string_lossy().to_string(),
+            format,
+            projects: self.projects.len(),
+            recipes: self.projects.iter().map(|p| p.recipes.len()).sum(),
+            files,
+        })
+    }
+
+    fn render_index(&self, format: DocsFormat) -> String {
+        match format {
+            DocsFormat::Markdown => markdown::index(self),
+            DocsFormat::Html => html::index(self),
+        }
+    }
+
+    fn render_groups(&self, format: DocsFormat) -> String {
+        match format {
+            DocsFormat::Markdown => markdown::groups(self),
+            DocsFormat::Html => html::groups(self),
+        }
+    }
+
+    /// Recipes of each group across projects, groups by name
+    fn group_index(&self) -> BTreeMap<&str, Vec<(&ProjectDocs, &RecipeDocs)>> {
+        let mut index: BTreeMap<&str, Vec<(&ProjectDocs, &RecipeDocs)>> = BTreeMap::new();
+        for project in &self.projects {
+            for recipe in &project.recipes {
+                for group in &recipe.groups {
+                    index.entry(group).or_default().push((project, recipe));
+                }
+            }
+        }
+        index
+    }
+}
+
+impl ProjectDocs {
+    /// Fill in which recipes depend on each recipe
+    fn link_dependents(&mut self) {
+        let edges: Vec<(String, String)> = self
+            .recipes
+            .iter()
+            .flat_map(|recipe| {
+                recipe
+                    .dependencies
+                    .iter()
+                    .map(|dependency| (dependency.clone(), recipe.name.clone()))
+            })
+            .collect();
+        for (dependency, dependent) in edges {
+            if let Some(recipe) = self.recipes.iter_mut().find(|r| r.name == dependency) {
+                if !recipe.required_by.contains(&dependent) {
+                    recipe.required_by.push(dependent);
+                }
+            }
+        }
+    }
+
+    /// Sections like `just --list`: recipes without a group first, then one
+    /// per group in the order of its first recipe
+    fn sections(&self) -> Vec<(&str, Vec<&RecipeDocs>)> {
+        let mut sections: Vec<(&str, Vec<&RecipeDocs>)> = vec![(UNGROUPED, Vec::new())];
+        for recipe in &self.recipes {
+            if recipe.groups.is_empty() {
+                sections[0].1.push(recipe);
+            }
+            for group in &recipe.groups {
+                match sections[1..].iter_mut().find(|(name, _)| name == group) {
+                    Some((_, recipes)) => recipes.push(recipe),
+                    None => sections.push((group, vec![recipe])),
+                }
+            }
+        }
+        sections.retain(|(_, recipes)| !recipes.is_empty());
+        sections
+    }
+
+    /// Whether any recipe has dependencies to graph
+    fn has_dependencies(&self) -> bool {
+        self.recipes.iter().any(|r| !r.dependencies.is_empty())
+    }
+
+    /// Mermaid flowchart of the dependencies between recipes
+    fn mermaid_graph(&self) -> String {
+        let mut nodes: Vec<&str> = Vec::new();
+        let mut edges = Vec::new();
+        for recipe in &self.recipes {
+            for dependency in &recipe.dependencies {
+                let from = node_id(&mut nodes, &recipe.name);
+                let to = node_id(&mut nodes, dependency);
+                edges.push((from, to));
+            }
+        }
+
+        let mut graph = String::from("flowchart LR\n");
+        for (id, name) in nodes.iter().enumerate() {
+            graph.push_str(&format!("    r{id}[\"{}\"]\n", name.replace('"', "#quot;")));
+        }
+        for (from, to) in edges {
+            graph.push_str(&format!("    r{from} --> r{to}\n"));
+        }
+        graph
+    }
+}
+
+impl RecipeDocs {
+    /// Command line running the recipe, e.g. `just deploy <env> [region=eu]`
+    fn usage(&self) -> String {
+        let mut usage = format!("just {}", self.name);
+        for parameter in &self.parameters {
+            match &parameter.default {
+                Some(default) => usage.push_str(&format!(" [{}={}]", parameter.name, default)),
+                None => usage.push_str(&format!(" <{}>", parameter.name)),
+            }
+        }
+        usage
+    }
+}
+
+/// Index of `name` in `nodes`, added if it isn't there yet
+fn node_id<'a>(nodes: &mut Vec<&'a str>, name: &'a str) -> usize {
+    match nodes.iter().position(|node| *node == name) {
+        Some(id) => id,
+        None => {
+            nodes.push(name);
+            nodes.len() - 1
+        }
+    }
+}
+
+/// Project named after the directory of its justfile
+fn project_name(justfile: &Path) -> String {
+    justfile
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "default".to_string())
+}
+
+/// File name of a project's page
+fn project_page(project: &ProjectDocs, format: DocsFormat) -> String {
+    format!("project-{}.{}", project.slug, format.extension())
+}
+
+/// Page name for a project, numbered if another project already has it
+fn unique_slug(name: &str, taken: &mut HashSet<String>) -> String {
+    let base: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let mut slug = base.clone();
+    let mut number = 1;
+    while !taken.insert(slug.clone()) {
+        number += 1;
+        slug = format!("{base}-{number}");
+    }
+    slug
+}
+
+/// Pages listed in the manifest of an earlier run, if any
+fn read_manifest(out_dir: &Path) -> HashSet<String> {
+    std::fs::read_to_string(out_dir.join(MANIFEST))
+        .map(|manifest| {
+            manifest
+                .lines()
+                .filter(|name| !name.is_empty() && !name.contains(['/', '\\']))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Anchor of a recipe's reference entry on its project page
+fn recipe_anchor(recipe: &str) -> String {
+    format!("recipe-{recipe}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const JUSTFILE: &str = "\
+import? 'missing.just'
+
+# Build the project
+[group('dev')]
+build mode=\"debug\":
+    cargo build
+
+# Ship it <carefully>
+[group('ops')]
+[confirm]
+deploy env: build test
+    ./deploy.sh {{env}}
+
+test:
+    cargo test
+
+_helper:
+    true
+";
+
+    async fn site() -> (TempDir, Site) {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("shop");
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(dir.join("justfile"), JUSTFILE).unwrap();
+        let site = Site::collect(&[(dir, None)], ParserPreference::Auto, Vec::new())
+            .await
+            .unwrap();
+        (temp_dir, site)
+    }
+
+    #[tokio::test]
+    async fn test_collects_recipes_with_dependents() {
+        let (_temp_dir, site) = site().await;
+        let project = &site.projects[0];
+        assert_eq!(project.name, "shop");
+        assert!(project.error.is_none());
+        assert_eq!(project.imports.len(), 1);
+        assert!(!project.imports[0].1);
+
+        let names: Vec<&str> = project.recipes.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["build", "deploy", "test"]);
+        let build = &project.recipes[0];
+        assert_eq!(build.tool.as_deref(), Some("build"));
+        assert_eq!(build.required_by, ["deploy"]);
+        assert_eq!(build.usage(), "just build [mode=debug]");
+        assert_eq!(project.recipes[1].usage(), "just deploy <env>");
+
+        let sections: Vec<&str> = project.sections().iter().map(|(name, _)| *name).collect();
+        assert_eq!(sections, ["other", "dev", "ops"]);
+        assert_eq!(
+            project.mermaid_graph(),
+            "flowchart LR\n    r0[\"deploy\"]\n    r1[\"build\"]\n    r2[\"test\"]\n    r0 --> r1\n    r0 --> r2\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_writes_markdown_and_html_sites() {
+        let (temp_dir, site) = site().await;
+
+        let out = temp_dir.path().join("docs");
+        let report = site.write(&out, DocsFormat::Markdown, false).unwrap();
+        assert_eq!((report.projects, report.recipes), (1, 3));
+        assert_eq!(report.files.len(), 3);
+        let page = std::fs::read_to_string(out.join("project-shop.md")).unwrap();
+        assert!(page.contains("<a id=\"recipe-deploy\"></a>"), "{page}");
+        assert!(page.contains("just deploy <env>"));
+        assert!(page.contains("```mermaid"));
+        assert!(page.contains("[build](#recipe-build)"));
+        let groups = std::fs::read_to_string(out.join("groups.md")).unwrap();
+        assert!(groups.contains("## ops"));
+        assert!(groups.contains("[deploy](project-shop.md#recipe-deploy)"));
+
+        site.write(&out, DocsFormat::Html, false).unwrap();
+        let page = std::fs::read_to_string(out.join("project-shop.html")).unwrap();
+        assert!(page.contains("Ship it &lt;carefully&gt;"), "{page}");
+        assert!(page.contains("id=\"recipe-test\""));
+        let index = std::fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(index.contains("href=\"project-shop.html\""));
+        // The Markdown pages were replaced by the HTML ones
+        assert!(!out.join("index.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_keeps_hand_written_pages_and_unique_slugs() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut configs = Vec::new();
+        for name in ["my api", "my-api"] {
+            let dir = temp_dir.path().join(name);
+            std::fs::create_dir(&dir).unwrap();
+            std::fs::write(dir.join("justfile"), "build:\n    cargo build\n").unwrap();
+            configs.push((dir, Some(name.to_string())));
+        }
+        let site = Site::collect(&configs, ParserPreference::Auto, Vec::new())
+            .await
+            .unwrap();
+        let slugs: Vec<&str> = site.projects.iter().map(|p| p.slug.as_str()).collect();
+        assert_eq!(slugs, ["my-api", "my-api-2"]);
+
+        let out = temp_dir.path().join("docs");
+        std::fs::create_dir(&out).unwrap();
+        std::fs::write(out.join("index.md"), "# Our docs\n").unwrap();
+        let error = site.write(&out, DocsFormat::Markdown, false).unwrap_err();
+        assert!(error.to_string().contains("index.md"), "{error}");
+        assert_eq!(
+            std::fs::read_to_string(out.join("index.md")).unwrap(),
+            "# Our docs\n"
+        );
+
+        let report = site.write(&out, DocsFormat::Markdown, true).unwrap();
+        assert_eq!(report.files.len(), 4);
+        // Pages written before can be replaced without forcing
+        site.write(&out, DocsFormat::Markdown, false).unwrap();
+    }
+}
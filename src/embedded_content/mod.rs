@@ -10,6 +10,7 @@
 //! - **EmbeddedDocument**: Represents a single embedded document with metadata
 //! - **EmbeddedContentIndexer**: Handles automatic indexing in vector database
 //! - **EmbeddedResourceProvider**: Exposes content through MCP Resources API
+//! - **ProjectTemplate**: Stack-specific justfile bundles installed by `just-mcp init`
 //!
 //! # Usage
 //!
@@ -30,6 +31,7 @@
 pub mod documents;
 pub mod indexer;
 pub mod resources;
+pub mod templates;
 
 use std::collections::HashMap;
 
@@ -179,8 +179,7 @@ impl EmbeddedResourceProvider {
             Ok(parts[2].to_string())
         } else {
             Err(anyhow::anyhow!(
-                "Invalid template reference format: {}",
-                ref_str
+                "Invalid template reference format: {ref_str}"
             ))
         }
     }
@@ -189,30 +188,26 @@ impl EmbeddedResourceProvider {
     fn validate_and_extract_doc_id(uri: &str) -> Result<String> {
         // Only allow file:///docs/guides/{id}.md pattern
         if !uri.starts_with("file:///docs/guides/") || !uri.ends_with(".md") {
-            return Err(anyhow::anyhow!("Invalid resource URI format: {}", uri));
+            return Err(anyhow::anyhow!("Invalid resource URI format: {uri}"));
         }
 
         let doc_id = uri
             .strip_prefix("file:///docs/guides/")
             .and_then(|s| s.strip_suffix(".md"))
-            .ok_or_else(|| anyhow::anyhow!("Failed to extract document ID from URI: {}", uri))?;
+            .ok_or_else(|| anyhow::anyhow!("Failed to extract document ID from URI: {uri}"))?;
 
         // Validate document ID contains only safe characters
         if !doc_id
             .chars()
             .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
         {
-            return Err(anyhow::anyhow!(
-                "Invalid document ID characters: {}",
-                doc_id
-            ));
+            return Err(anyhow::anyhow!("Invalid document ID characters: {doc_id}"));
         }
 
         // Prevent path traversal attempts
         if doc_id.contains("..") || doc_id.contains('/') || doc_id.contains('\\') {
             return Err(anyhow::anyhow!(
-                "Path traversal attempt in document ID: {}",
-                doc_id
+                "Path traversal attempt in document ID: {doc_id}"
             ));
         }
 
@@ -289,7 +284,7 @@ impl ResourceProvider for EmbeddedResourceProvider {
         let document = self
             .registry
             .get_document_by_id(&doc_id)
-            .ok_or_else(|| anyhow::anyhow!("Embedded document not found: {}", doc_id))?;
+            .ok_or_else(|| anyhow::anyhow!("Embedded document not found: {doc_id}"))?;
 
         Ok(ResourceContent {
             uri: uri.to_string(),
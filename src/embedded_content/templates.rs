@@ -0,0 +1,229 @@
+//! Project templates
+//!
+//! Stack-specific bundles that `just-mcp init --template <stack>` installs
+//! into a project: a justfile of curated recipes and an `.mcp.json` that
+//! registers just-mcp for the project. The recipes are written for agent
+//! use: grouped with `[group(...)]`, documented with a comment and
+//! `# {{param}}: ...` parameter descriptions, and recipes that publish or
+//! destroy things carry `[confirm(...)]`, so `just` stops for a human
+//! before running them.
+
+use crate::error::{Error, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// MCP client configuration shared by every template
+pub static MCP_CONFIG: &str = include_str!("../../assets/templates/mcp.json");
+
+/// File name the MCP configuration is installed as
+pub const MCP_CONFIG_FILE: &str = ".mcp.json";
+
+/// File name the justfile is installed as
+pub const JUSTFILE: &str = "justfile";
+
+/// A justfile bundle for a common stack
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectTemplate {
+    /// Name passed to `--template`
+    pub id: &'static str,
+    /// Short description shown by `init --list`
+    pub description: &'static str,
+    /// Justfile content, embedded at compile time
+    pub justfile: &'static str,
+}
+
+/// All bundled project templates
+pub fn project_templates() -> Vec<ProjectTemplate> {
+    vec![
+        ProjectTemplate {
+            id: "rust",
+            description: "Cargo workspace: build, test, clippy, fmt, docs and publish",
+            justfile: include_str!("../../assets/templates/rust/justfile"),
+        },
+        ProjectTemplate {
+            id: "node",
+            description: "npm project: install, build, dev server, test, lint and publish",
+            justfile: include_str!("../../assets/templates/node/justfile"),
+        },
+        ProjectTemplate {
+            id: "python",
+            description: "Python package in a venv: pytest, ruff, mypy, build and publish",
+            justfile: include_str!("../../assets/templates/python/justfile"),
+        },
+        ProjectTemplate {
+            id: "terraform",
+            description: "Terraform configuration: validate, plan, apply and destroy",
+            justfile: include_str!("../../assets/templates/terraform/justfile"),
+        },
+    ]
+}
+
+/// Look up a bundled template by name
+pub fn get_project_template(id: &str) -> Option<ProjectTemplate> {
+    project_templates().into_iter().find(|t| t.id == id)
+}
+
+/// Files written by [`install_template`]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InstallReport {
+    /// Files created or, with `force`, overwritten
+    pub written: Vec<PathBuf>,
+    /// Existing MCP configurations left in place
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Install a template's justfile and MCP configuration into `dir`
+///
+/// An existing justfile (under any name `just` looks for) is an error unless
+/// `force` is set, since it would shadow or replace the user's recipes. An
+/// existing `.mcp.json` is kept unless `force` is set, as it may register
+/// other servers.
+pub fn install_template(
+    template: &ProjectTemplate,
+    dir: &Path,
+    force: bool,
+) -> Result<InstallReport> {
+    std::fs::create_dir_all(dir)?;
+    let mut report = InstallReport::default();
+
+    let justfile = match existing_justfile(dir) {
+        Some(existing) if !force => {
+            return Err(Error::InvalidParameter(format!(
+                "{} already exists; use --force to replace it",
+                existing.display()
+            )));
+        }
+        Some(existing) => existing,
+        None => dir.join(JUSTFILE),
+    };
+    std::fs::write(&justfile, template.justfile)?;
+    report.written.push(justfile);
+
+    let config = dir.join(MCP_CONFIG_FILE);
+    if config.exists() && !force {
+        report.skipped.push(config);
+    } else {
+        std::fs::write(&config, MCP_CONFIG)?;
+        report.written.push(config);
+    }
+    Ok(report)
+}
+
+/// A justfile `just` would pick up in `dir`, if there is one
+fn existing_justfile(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    matches!(name.to_lowercase().as_str(), "justfile" | ".justfile")
+                })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::docs::extract_docs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_templates_are_curated_for_agents() {
+        let templates = project_templates();
+        let ids: Vec<&str> = templates.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec!["rust", "node", "python", "terraform"]);
+
+        for template in &templates {
+            let docs = extract_docs(template.justfile);
+            let recipes: Vec<&str> = template
+                .justfile
+                .lines()
+                .filter(|line| !line.starts_with([' ', '#', '[']) && line.contains(':'))
+                .filter(|line| !line.contains(":="))
+                .map(|line| line.split([' ', ':']).next().unwrap())
+                .collect();
+            assert!(recipes.len() >= 8, "{}: {recipes:?}", template.id);
+
+            for recipe in &recipes {
+                assert!(
+                    docs.get(*recipe).is_some_and(|d| !d.comments.is_empty()),
+                    "{}: recipe '{recipe}' is undocumented",
+                    template.id
+                );
+            }
+            assert!(template.justfile.contains("[group('"), "{}", template.id);
+            assert!(template.justfile.contains("[confirm('"), "{}", template.id);
+            assert!(
+                docs.values().any(|d| !d.parameters.is_empty()),
+                "{}: no parameter descriptions",
+                template.id
+            );
+        }
+    }
+
+    #[test]
+    fn test_templates_are_valid_justfiles() {
+        if !crate::parser::EnhancedJustfileParser::is_just_available() {
+            return;
+        }
+        for template in project_templates() {
+            let dir = TempDir::new().unwrap();
+            install_template(&template, dir.path(), false).unwrap();
+            let output = std::process::Command::new("just")
+                .arg("--summary")
+                .current_dir(dir.path())
+                .output()
+                .unwrap();
+            assert!(
+                output.status.success(),
+                "{}: {}",
+                template.id,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+    }
+
+    #[test]
+    fn test_install_template() {
+        let dir = TempDir::new().unwrap();
+        let rust = get_project_template("rust").unwrap();
+        assert!(get_project_template("cobol").is_none());
+
+        let report = install_template(&rust, dir.path(), false).unwrap();
+        assert_eq!(report.written.len(), 2);
+        let config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join(".mcp.json")).unwrap())
+                .unwrap();
+        assert_eq!(config["mcpServers"]["just"]["command"], "just-mcp");
+
+        // Existing justfiles are never replaced without --force
+        let node = get_project_template("node").unwrap();
+        std::fs::remove_file(dir.path().join("justfile")).unwrap();
+        std::fs::write(dir.path().join("Justfile"), "mine:\n    true\n").unwrap();
+        let error = install_template(&node, dir.path(), false).unwrap_err();
+        assert!(error.to_string().contains("--force"), "{error}");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("Justfile")).unwrap(),
+            "mine:\n    true\n"
+        );
+
+        std::fs::remove_file(dir.path().join("Justfile")).unwrap();
+        std::fs::write(dir.path().join(".mcp.json"), "{}").unwrap();
+        let report = install_template(&node, dir.path(), false).unwrap();
+        assert_eq!(report.skipped, vec![dir.path().join(".mcp.json")]);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(".mcp.json")).unwrap(),
+            "{}"
+        );
+
+        let report = install_template(&node, dir.path(), true).unwrap();
+        assert!(report.skipped.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("justfile")).unwrap(),
+            node.justfile
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -25,12 +26,16 @@ pub enum Error {
     #[error("Just command error: {0}")]
     JustCommand(String),
 
+    #[error("just is not installed; it is needed {needed_for}. {hint}")]
+    JustNotInstalled { needed_for: String, hint: String },
+
     #[error("Registry error: {0}")]
     Registry(String),
 
     #[error("Server error: {0}")]
     Server(String),
 
+    #[cfg(feature = "watcher")]
     #[error("File watch error: {0}")]
     Watch(#[from] notify::Error),
 
@@ -52,6 +57,14 @@ pub enum Error {
     #[error("Timeout error: {0}")]
     Timeout(String),
 
+    /// The server's configuration refuses the operation
+    #[error("{0}")]
+    Policy(String),
+
+    /// A limit on concurrent work is reached; the operation may succeed later
+    #[error("{0}")]
+    Busy(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 
@@ -60,3 +73,31 @@ pub enum Error {
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+/// Kind of failure a tool result reports, so agent frameworks can decide
+/// whether to retry a call instead of looping on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// The call or the recipe needs to change: wrong arguments, unknown
+    /// tool, a recipe that reported failure. Errors with no more specific
+    /// kind are reported as this too, since repeating the call won't help
+    UserError,
+    /// A condition that may clear by itself: a busy system, a network
+    /// hiccup, a cancelled run
+    Transient,
+    /// Something must be set up first: an installed command, a file, a
+    /// variable in the environment, a non-interactive flag
+    Precondition,
+    /// The server's configuration refuses the call
+    Policy,
+    /// The call ran out of time
+    Timeout,
+}
+
+impl ErrorKind {
+    /// Whether sending the same call again may succeed
+    pub fn retry_advisable(self) -> bool {
+        matches!(self, ErrorKind::Transient | ErrorKind::Timeout)
+    }
+}
@@ -0,0 +1,291 @@
+//! ANSI escape sequence handling for recipe output
+//!
+//! Tools that detect a terminal, or are told to (`--color always`,
+//! `FORCE_COLOR`), colour their output and redraw progress lines with cursor
+//! movement. Clients read tool results as text, where these sequences are
+//! noise. Recipe output is post-processed according to an [`AnsiMode`]:
+//! escapes are stripped by default, kept as is, or turned into Markdown
+//! emphasis so bold and struck-through text survive.
+
+use crate::error::{Error, Result};
+use regex::Regex;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// CSI sequences (colours, cursor movement), OSC sequences (titles,
+/// hyperlinks) and the remaining two-byte escapes
+static ANSI_ESCAPE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[()][0-9A-Za-z]|\x1b[@-Z\\-_=>]",
+    )
+    .expect("valid ANSI pattern")
+});
+
+/// How ANSI escape sequences in recipe output are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnsiMode {
+    /// Remove every escape sequence
+    #[default]
+    Strip,
+    /// Pass output through unchanged
+    Preserve,
+    /// Render bold, italic and struck-through text as Markdown emphasis and
+    /// remove the other sequences
+    Markdown,
+}
+
+impl FromStr for AnsiMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "strip" => Ok(Self::Strip),
+            "preserve" => Ok(Self::Preserve),
+            "markdown" => Ok(Self::Markdown),
+            _ => Err(Error::InvalidParameter(format!(
+                "Unknown ANSI mode '{s}', expected strip, preserve or markdown"
+            ))),
+        }
+    }
+}
+
+impl AnsiMode {
+    /// Post-process recipe output
+    pub fn apply(self, text: &str) -> String {
+        if !text.contains('\x1b') {
+            return text.to_string();
+        }
+        match self {
+            Self::Strip => strip_ansi(text),
+            Self::Preserve => text.to_string(),
+            Self::Markdown => ansi_to_markdown(text),
+        }
+    }
+}
+
+/// Remove ANSI escape sequences from text
+pub fn strip_ansi(text: &str) -> String {
+    ANSI_ESCAPE.replace_all(text, "").into_owned()
+}
+
+/// Normalise terminal line endings: `\r\n` becomes `\n`
+///
+/// A terminal translates each newline a program writes into `\r\n`.
+pub fn normalize_newlines(text: &str) -> String {
+    text.replace("\r\n", "\n")
+}
+
+/// Text attributes that have a Markdown equivalent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    bold: bool,
+    italic: bool,
+    strike: bool,
+}
+
+impl Style {
+    /// Apply the parameters of an SGR (`ESC [ ... m`) sequence
+    fn update(&mut self, params: &str) {
+        let mut codes = params
+            .split([';', ':'])
+            .map(|p| p.parse::<u8>().unwrap_or(0));
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                22 => self.bold = false,
+                3 => self.italic = true,
+                23 => self.italic = false,
+                9 => self.strike = true,
+                29 => self.strike = false,
+                // Extended colours carry their value as further parameters
+                38 | 48 | 58 => match codes.next() {
+                    Some(5) => {
+                        codes.next();
+                    }
+                    Some(2) => {
+                        codes.nth(2);
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+    }
+
+    fn marker(self) -> String {
+        let mut marker = String::new();
+        if self.strike {
+            marker.push_str("~~");
+        }
+        match (self.bold, self.italic) {
+            (true, true) => marker.push_str("***"),
+            (true, false) => marker.push_str("**"),
+            (false, true) => marker.push('*'),
+            (false, false) => {}
+        }
+        marker
+    }
+}
+
+/// Convert styled terminal output to Markdown emphasis
+///
+/// Colours carry no Markdown meaning and are dropped. Emphasis is closed at
+/// the end of each line and kept off surrounding whitespace, where Markdown
+/// would not recognise it.
+pub fn ansi_to_markdown(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    // Style of the pending run of text, and the style set by the escapes
+    // read so far; a reset immediately followed by the same style keeps
+    // the run going
+    let mut run_style = Style::default();
+    let mut style = Style::default();
+    let mut run = String::new();
+    let mut last = 0;
+
+    let mut push = |output: &mut String, run: &mut String, segment: &str, style: Style| {
+        if segment.is_empty() {
+            return;
+        }
+        if style != run_style {
+            emphasize(output, run, run_style);
+            run.clear();
+            run_style = style;
+        }
+        run.push_str(segment);
+    };
+
+    for escape in ANSI_ESCAPE.find_iter(text) {
+        push(&mut output, &mut run, &text[last..escape.start()], style);
+        last = escape.end();
+
+        if let Some(params) = escape
+            .as_str()
+            .strip_prefix("\x1b[")
+            .and_then(|s| s.strip_suffix('m'))
+        {
+            style.update(params);
+        }
+    }
+    push(&mut output, &mut run, &text[last..], style);
+    emphasize(&mut output, &run, run_style);
+    output
+}
+
+/// Append `text` to `output`, wrapping each line in the style's markers
+fn emphasize(output: &mut String, text: &str, style: Style) {
+    let marker = style.marker();
+    if marker.is_empty() {
+        output.push_str(text);
+        return;
+    }
+    let closing: String = marker.chars().rev().collect();
+
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            output.push('\n');
+        }
+        let content = line.trim();
+        if content.is_empty() {
+            output.push_str(line);
+            continue;
+        }
+        let start = line.len() - line.trim_start().len();
+        let end = start + content.len();
+        output.push_str(&line[..start]);
+        output.push_str(&marker);
+        output.push_str(content);
+        output.push_str(&closing);
+        output.push_str(&line[end..]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `cargo build --color always` of a crate with an unresolved name
+    const CARGO_ERROR: &str = "\x1b[1m\x1b[92m   Compiling\x1b[0m ansicap v0.1.0 (/tmp/ansicap)
+\x1b[1m\x1b[91merror[E0425]\x1b[0m\x1b[1m: cannot find value `missing` in this scope\x1b[0m
+ \x1b[1m\x1b[94m--> \x1b[0msrc/main.rs:3:20
+  \x1b[1m\x1b[94m|\x1b[0m
+\x1b[1m\x1b[94m3\x1b[0m \x1b[1m\x1b[94m|\x1b[0m     println!(\"{}\", missing);
+  \x1b[1m\x1b[94m|\x1b[0m                    \x1b[1m\x1b[91m^^^^^^^\x1b[0m \x1b[1m\x1b[91mnot found in this scope\x1b[0m
+
+\x1b[1mFor more information about this error, try `rustc --explain E0425`.\x1b[0m
+\x1b[1m\x1b[91merror\x1b[0m: could not compile `ansicap` (bin \"ansicap\") due to 1 previous error
+";
+
+    /// `npm install --offline --color=always` of a package that isn't cached
+    const NPM_ERROR: &str = "\x1b[1mnpm\x1b[22m \x1b[31merror\x1b[39m \x1b[94mcode\x1b[39m ENOTCACHED
+\x1b[1mnpm\x1b[22m \x1b[31merror\x1b[39m request to https://registry.npmjs.org/left-pad failed: cache mode is 'only-if-cached' but no cached response is available.
+";
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(
+            strip_ansi(CARGO_ERROR),
+            "   Compiling ansicap v0.1.0 (/tmp/ansicap)
+error[E0425]: cannot find value `missing` in this scope
+ --> src/main.rs:3:20
+  |
+3 |     println!(\"{}\", missing);
+  |                    ^^^^^^^ not found in this scope
+
+For more information about this error, try `rustc --explain E0425`.
+error: could not compile `ansicap` (bin \"ansicap\") due to 1 previous error
+"
+        );
+        assert!(strip_ansi(NPM_ERROR).starts_with("npm error code ENOTCACHED\nnpm error request"));
+
+        assert_eq!(strip_ansi("50%\x1b[2K\x1b[1G100%"), "50%100%");
+        assert_eq!(
+            strip_ansi("\x1b]8;;https://example.com\x07link\x1b]8;;\x07"),
+            "link"
+        );
+        assert_eq!(strip_ansi("\x1b(Bplain\x1b="), "plain");
+        assert_eq!(strip_ansi("no escapes: [0m"), "no escapes: [0m");
+    }
+
+    #[test]
+    fn test_ansi_to_markdown() {
+        let markdown = ansi_to_markdown(CARGO_ERROR);
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines[0], "   **Compiling** ansicap v0.1.0 (/tmp/ansicap)");
+        assert_eq!(
+            lines[1],
+            "**error[E0425]: cannot find value `missing` in this scope**"
+        );
+        assert_eq!(lines[2], " **-->** src/main.rs:3:20");
+        assert_eq!(
+            lines[5],
+            "  **|**                    **^^^^^^^** **not found in this scope**"
+        );
+
+        let markdown = ansi_to_markdown(NPM_ERROR);
+        assert!(markdown.starts_with("**npm** error code ENOTCACHED\n**npm** error request"));
+
+        // 38;5;9 is a colour, not strikethrough
+        assert_eq!(
+            ansi_to_markdown("\x1b[38;5;9mred\x1b[0m \x1b[3;9mgone\x1b[23;29m"),
+            "red ~~*gone*~~"
+        );
+    }
+
+    #[test]
+    fn test_ansi_mode() {
+        let colored = "\x1b[1mbold\x1b[0m";
+        assert_eq!(AnsiMode::default().apply(colored), "bold");
+        assert_eq!(AnsiMode::Preserve.apply(colored), colored);
+        assert_eq!(AnsiMode::Markdown.apply(colored), "**bold**");
+        assert_eq!(AnsiMode::Markdown.apply("a * b"), "a * b");
+        assert_eq!("markdown".parse::<AnsiMode>().unwrap(), AnsiMode::Markdown);
+        assert!("html".parse::<AnsiMode>().is_err());
+    }
+
+    #[test]
+    fn test_normalize_newlines() {
+        assert_eq!(normalize_newlines("a\r\nb\r\n"), "a\nb\n");
+        assert_eq!(normalize_newlines("50%\r100%\n"), "50%\r100%\n");
+    }
+}
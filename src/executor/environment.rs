@@ -0,0 +1,240 @@
+//! Differences between the user's shell environment and the one recipes get
+//!
+//! MCP clients usually start just-mcp from a desktop app or a service
+//! manager rather than from a terminal, so recipes can see a much smaller
+//! environment than `just` does in the user's shell: a shorter `PATH`, no
+//! `SSH_AUTH_SOCK`, sometimes no `HOME`. A recipe that works in the terminal
+//! then fails under just-mcp with "command not found" or a git
+//! authentication error.
+//!
+//! The environment of the user's login shell is captured once and compared
+//! with the environment recipes run with. Differences are reported by the
+//! environment doctor and, when enabled, attached to failed executions.
+
+use crate::error::{Error, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Variables recipes commonly depend on without declaring them
+pub const COMMON_VARIABLES: &[&str] = &[
+    "HOME",
+    "USER",
+    "SHELL",
+    "LANG",
+    "SSH_AUTH_SOCK",
+    "XDG_RUNTIME_DIR",
+];
+
+/// How long the login shell may take to print its environment
+const SHELL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Separates the environment from anything the shell's startup files print
+const ENV_MARKER: &str = "__JUST_MCP_ENV__";
+
+/// Environment of the user's login shell
+#[derive(Debug, Clone)]
+pub struct ShellEnvironment {
+    pub shell: String,
+    pub variables: HashMap<String, String>,
+}
+
+impl ShellEnvironment {
+    /// Capture the environment of the user's interactive login shell
+    ///
+    /// Runs `$SHELL -l -i -c env` without a terminal, so profile and rc files
+    /// are sourced the way they are when the user runs `just` themselves.
+    #[cfg(unix)]
+    pub async fn capture() -> Result<Self> {
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+        Self::capture_from(shell).await
+    }
+
+    /// Capture the environment of `shell` started as a login shell
+    #[cfg(unix)]
+    pub async fn capture_from(shell: String) -> Result<Self> {
+        let script = format!("printf '\\0{ENV_MARKER}\\0'; env -0");
+
+        let output = tokio::process::Command::new(&shell)
+            .args(["-l", "-i", "-c", &script])
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .output();
+        let output = tokio::time::timeout(SHELL_TIMEOUT, output)
+            .await
+            .map_err(|_| {
+                Error::Other(format!(
+                    "Login shell {shell} did not print its environment within {SHELL_TIMEOUT:?}"
+                ))
+            })??;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let Some((_, env)) = stdout.split_once(&format!("\0{ENV_MARKER}\0")) else {
+            return Err(Error::Other(format!(
+                "Could not read the environment of login shell {shell}"
+            )));
+        };
+
+        Ok(Self {
+            shell,
+            variables: parse_env(env),
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub async fn capture() -> Result<Self> {
+        Err(Error::Other(
+            "Comparing with the login shell environment is only supported on Unix".to_string(),
+        ))
+    }
+}
+
+/// Parse NUL-separated `NAME=value` entries as printed by `env -0`
+fn parse_env(env: &str) -> HashMap<String, String> {
+    env.split('\0')
+        .filter_map(|entry| entry.split_once('='))
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+/// What recipes are missing compared to the user's shell
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct EnvironmentDiff {
+    /// Shell the reference environment was captured from
+    pub shell: String,
+    /// Commonly needed variables set in the shell but not for recipes
+    pub missing_variables: Vec<String>,
+    /// `PATH` entries of the shell that recipes don't get, in shell order
+    pub missing_path_entries: Vec<String>,
+}
+
+impl EnvironmentDiff {
+    /// Compare the shell's environment with the one recipes run with
+    pub fn compare(shell: &ShellEnvironment, recipe_env: &HashMap<String, String>) -> Self {
+        let missing_variables = COMMON_VARIABLES
+            .iter()
+            .filter(|name| shell.variables.contains_key(**name))
+            .filter(|name| recipe_env.get(**name).is_none_or(|value| value.is_empty()))
+            .map(|name| name.to_string())
+            .collect();
+
+        let recipe_path: Vec<_> = recipe_env
+            .get("PATH")
+            .map(|path| std::env::split_paths(path).collect())
+            .unwrap_or_default();
+        let mut missing_path_entries: Vec<String> = Vec::new();
+        if let Some(path) = shell.variables.get("PATH") {
+            for entry in std::env::split_paths(path) {
+                let entry_str = entry.to_string_lossy().to_string();
+                if !entry_str.is_empty()
+                    && !recipe_path.contains(&entry)
+                    && !missing_path_entries.contains(&entry_str)
+                {
+                    missing_path_entries.push(entry_str);
+                }
+            }
+        }
+
+        Self {
+            shell: shell.shell.clone(),
+            missing_variables,
+            missing_path_entries,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.missing_variables.is_empty() && self.missing_path_entries.is_empty()
+    }
+
+    /// One warning line per kind of difference
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if !self.missing_variables.is_empty() {
+            warnings.push(format!(
+                "Set in your shell ({}) but not for recipes: {}",
+                self.shell,
+                self.missing_variables.join(", ")
+            ));
+        }
+        if !self.missing_path_entries.is_empty() {
+            warnings.push(format!(
+                "PATH entries from your shell ({}) missing for recipes: {}",
+                self.shell,
+                self.missing_path_entries.join(", ")
+            ));
+        }
+        warnings
+    }
+}
+
+/// Environment recipes run with: the server's, plus per-execution overrides
+pub fn recipe_environment(overrides: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut env: HashMap<String, String> = std::env::vars().collect();
+    env.extend(overrides.iter().map(|(k, v)| (k.clone(), v.clone())));
+    env
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_compare_reports_missing_variables_and_path_entries() {
+        let shell = ShellEnvironment {
+            shell: "/bin/zsh".to_string(),
+            variables: env(&[
+                ("HOME", "/Users/dev"),
+                ("SSH_AUTH_SOCK", "/tmp/agent.sock"),
+                ("LANG", "en_US.UTF-8"),
+                ("EDITOR", "vim"),
+                ("PATH", "/opt/homebrew/bin:/usr/bin:/bin:/opt/homebrew/bin"),
+            ]),
+        };
+        let recipe_env = env(&[
+            ("HOME", "/Users/dev"),
+            ("LANG", ""),
+            ("PATH", "/usr/bin:/bin"),
+        ]);
+
+        let diff = EnvironmentDiff::compare(&shell, &recipe_env);
+        assert_eq!(diff.missing_variables, vec!["LANG", "SSH_AUTH_SOCK"]);
+        assert_eq!(diff.missing_path_entries, vec!["/opt/homebrew/bin"]);
+        let warnings = diff.warnings();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("/bin/zsh"));
+
+        let diff = EnvironmentDiff::compare(&shell, &shell.variables);
+        assert!(diff.is_empty());
+        assert!(diff.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_parse_env_output() {
+        let parsed = parse_env("A=1\0MULTI=line one\nline two\0EQ=a=b\0\0");
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed["MULTI"], "line one\nline two");
+        assert_eq!(parsed["EQ"], "a=b");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_capture_login_shell() {
+        // The user's shell varies between machines; `/bin/sh` is always there
+        let shell = ShellEnvironment::capture_from("/bin/sh".to_string())
+            .await
+            .unwrap();
+        assert_eq!(shell.shell, "/bin/sh");
+        assert!(shell.variables.contains_key("PATH"));
+
+        let missing = ShellEnvironment::capture_from("/nonexistent/shell".to_string()).await;
+        assert!(missing.is_err());
+    }
+}
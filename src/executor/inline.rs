@@ -0,0 +1,139 @@
+//! Recipes from justfile content supplied by the client
+//!
+//! Agents sometimes need one-off automation that doesn't belong in the
+//! repository. [`TaskExecutor::execute_inline`] writes the client's justfile
+//! to a fresh temporary directory, runs one recipe from it with the same
+//! parameter validation, resource limits, timeout and watchdog as any other
+//! recipe, and removes the directory afterwards.
+//!
+//! The directory is scratch space, not an isolation boundary: the recipe
+//! runs with the server's permissions. The server therefore only offers
+//! `_admin_run_inline_justfile` when started with both `--admin` and
+//! `--allow-inline-justfiles`.
+
+use super::TaskExecutor;
+use crate::error::{Error, Result};
+use crate::types::{ExecutionContext, ExecutionResult};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Largest justfile accepted inline
+pub const MAX_INLINE_JUSTFILE_BYTES: usize = 64 * 1024;
+
+/// Key inline executions share for the per-project limits
+const INLINE_PROJECT: &str = "inline";
+
+/// A recipe to run from justfile content
+#[derive(Debug, Clone)]
+pub struct InlineRequest {
+    /// Justfile source
+    pub content: String,
+    pub recipe: String,
+    pub parameters: HashMap<String, serde_json::Value>,
+    /// Environment and timeout; the working directory is always the
+    /// temporary directory
+    pub context: ExecutionContext,
+}
+
+impl TaskExecutor {
+    /// Run a recipe from justfile content in a temporary directory
+    pub async fn execute_inline(&mut self, request: InlineRequest) -> Result<ExecutionResult> {
+        if request.content.len() > MAX_INLINE_JUSTFILE_BYTES {
+            return Err(Error::InvalidParameter(format!(
+                "Inline justfile is {} bytes, more than the {MAX_INLINE_JUSTFILE_BYTES} allowed",
+                request.content.len()
+            )));
+        }
+        self.security_validator
+            .validate_task_name(&request.recipe)?;
+        self.security_validator
+            .validate_parameters(&request.parameters)?;
+
+        let sandbox = tempfile::Builder::new()
+            .prefix("just-mcp-inline-")
+            .tempdir()?;
+        let justfile = sandbox.path().join("justfile");
+        std::fs::write(&justfile, &request.content)?;
+        info!(
+            "Running recipe '{}' from an inline justfile in {}",
+            request.recipe,
+            sandbox.path().display()
+        );
+
+        let tasks = self.parser.parse_file(&justfile)?;
+        if !tasks.iter().any(|task| task.name == request.recipe) {
+            let defined: Vec<&str> = tasks.iter().map(|task| task.name.as_str()).collect();
+            return Err(Error::InvalidParameter(format!(
+                "Recipe '{}' is not defined in the inline justfile; it defines: {}",
+                request.recipe,
+                if defined.is_empty() {
+                    "no recipes".to_string()
+                } else {
+                    defined.join(", ")
+                }
+            )));
+        }
+
+        // `[no-cd]` recipes run in the working directory, which stays the
+        // temporary directory rather than the server's
+        let context = ExecutionContext {
+            working_directory: Some(sandbox.path().to_string_lossy().to_string()),
+            environment: request.context.environment,
+            timeout: request.context.timeout,
+            profile: None,
+        };
+        let _permit = self.resource_manager.acquire(INLINE_PROJECT).await;
+        self.execute_just_command(&justfile, &request.recipe, &request.parameters, &context)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(content: &str, recipe: &str) -> InlineRequest {
+        InlineRequest {
+            content: content.to_string(),
+            recipe: recipe.to_string(),
+            parameters: HashMap::new(),
+            context: ExecutionContext::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inline_recipe_runs_in_removed_temp_dir() {
+        if !crate::parser::EnhancedJustfileParser::is_just_available() {
+            return;
+        }
+        let mut executor = TaskExecutor::new();
+        let mut greet = request(
+            "greet name:\n    echo \"hello {{name}}\" && pwd\n\nother:\n    true\n",
+            "greet",
+        );
+        greet.parameters.insert("name".to_string(), json!("agent"));
+
+        let result = executor.execute_inline(greet).await.unwrap();
+        assert!(result.success, "{result:?}");
+        assert!(result.stdout.contains("hello agent"));
+        let dir = result.stdout.lines().last().unwrap();
+        assert!(dir.contains("just-mcp-inline-"), "{dir}");
+        assert!(!std::path::Path::new(dir).exists());
+
+        let error = executor
+            .execute_inline(request("other:\n    true\n", "missing"))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("it defines: other"), "{error}");
+
+        let mut injected = request("greet name:\n    echo {{name}}\n", "greet");
+        injected
+            .parameters
+            .insert("name".to_string(), json!("x; rm -rf /"));
+        assert!(executor.execute_inline(injected).await.is_err());
+
+        let huge = "x".repeat(MAX_INLINE_JUSTFILE_BYTES + 1);
+        assert!(executor.execute_inline(request(&huge, "x")).await.is_err());
+    }
+}
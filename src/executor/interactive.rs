@@ -0,0 +1,646 @@
+//! Interactive input handling for recipe execution
+//!
+//! Recipes run without a terminal, so a command that prompts for input
+//! (`Continue? [Y/n]`, `Password:`) would otherwise sit idle until the
+//! execution timeout. The runner in this module streams the child's output
+//! and, when output stalls on an unterminated line that looks like a prompt,
+//! answers it with a configured response or kills the process and reports
+//! the prompt. By default only unmistakable prompts (`[y/n]`, `Password:`)
+//! are rejected; `--reject-prompts any` also rejects lines that merely end
+//! like one. A recipe that times out reports the last prompt it stalled on.
+//! The child is also killed if the execution future is dropped, so cancelled
+//! requests do not leave recipes running.
+//!
+//! Recipes run in a pseudo-terminal (see [`super::pty`]) are monitored the
+//! same way. Both report their process and output activity to the
+//! [`watchdog`](super::watchdog), which can also cancel them.
+
+use super::watchdog::WatchedExecution;
+use crate::error::{Error, Result};
+use regex::Regex;
+use std::future::Future;
+use std::process::{ExitStatus, Stdio};
+use std::str::FromStr;
+use std::sync::LazyLock;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::time::{sleep_until, Duration, Instant};
+use tracing::{info, warn};
+
+/// Prompt wording recognised anywhere in the last, unterminated line
+static EXPLICIT_PROMPT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(\[(y/n|yes/no)\]|\((y/n|yes/no)\)|password|passphrase|press (enter|return|any key)|continue\?)",
+    )
+    .expect("valid prompt pattern")
+});
+
+/// Partial lines that look like they are waiting for input
+static TRAILING_PROMPT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[:?>]\s*$").expect("valid prompt pattern"));
+
+/// A predefined response for prompts matching a pattern
+#[derive(Debug, Clone)]
+pub struct PromptResponse {
+    pub pattern: Regex,
+    pub response: String,
+}
+
+impl PromptResponse {
+    pub fn new(pattern: &str, response: impl Into<String>) -> Result<Self> {
+        let pattern = Regex::new(pattern).map_err(|e| {
+            Error::InvalidParameter(format!("Invalid prompt pattern '{pattern}': {e}"))
+        })?;
+        Ok(Self {
+            pattern,
+            response: response.into(),
+        })
+    }
+
+    /// Parse a `PATTERN=RESPONSE` specification
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (pattern, response) = spec.rsplit_once('=').ok_or_else(|| {
+            Error::InvalidParameter(format!(
+                "Invalid prompt response '{spec}': expected PATTERN=RESPONSE"
+            ))
+        })?;
+        Self::new(pattern, response)
+    }
+}
+
+/// Policy for recipes that wait on interactive input
+#[derive(Debug, Clone)]
+pub struct InteractiveConfig {
+    /// How long output must be idle before the tail is checked for a prompt
+    pub stall_timeout: Duration,
+    /// Which unanswered prompts kill the recipe they stall
+    pub reject_prompts: PromptRejection,
+    /// Responses written to stdin for matching prompts
+    pub responses: Vec<PromptResponse>,
+    /// Maximum number of responses written during one execution
+    pub max_responses: usize,
+    /// Which recipes run in a pseudo-terminal
+    pub pty: PtyMode,
+}
+
+impl Default for InteractiveConfig {
+    fn default() -> Self {
+        Self {
+            stall_timeout: Duration::from_secs(5),
+            reject_prompts: PromptRejection::default(),
+            responses: Vec::new(),
+            max_responses: 16,
+            pty: PtyMode::default(),
+        }
+    }
+}
+
+impl InteractiveConfig {
+    /// Response configured for the given prompt, if any
+    pub fn response_for(&self, prompt: &str) -> Option<&str> {
+        self.responses
+            .iter()
+            .find(|r| r.pattern.is_match(prompt))
+            .map(|r| r.response.as_str())
+    }
+}
+
+/// Which unanswered prompts a stalled recipe is killed for
+///
+/// Explicit prompts such as `[Y/n]` or `Password:` almost always wait for
+/// input. Lines that only end in `:`, `?` or `>` may also be a slow recipe's
+/// progress output, so rejecting those is opt-in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PromptRejection {
+    /// Let recipes run until the timeout
+    Never,
+    /// Prompts with recognised wording
+    #[default]
+    Explicit,
+    /// Any unterminated line that looks like a prompt
+    Any,
+}
+
+impl PromptRejection {
+    /// Whether a recipe stalled on `prompt` is killed
+    pub fn rejects(self, prompt: &str) -> bool {
+        match self {
+            Self::Never => false,
+            Self::Explicit => EXPLICIT_PROMPT.is_match(prompt),
+            Self::Any => true,
+        }
+    }
+}
+
+impl FromStr for PromptRejection {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "never" => Ok(Self::Never),
+            "explicit" => Ok(Self::Explicit),
+            "any" => Ok(Self::Any),
+            _ => Err(Error::InvalidParameter(format!(
+                "Unknown prompt rejection '{s}', expected never, explicit or any"
+            ))),
+        }
+    }
+}
+
+/// Which recipes run in a pseudo-terminal instead of with piped output
+///
+/// Recipes in a pseudo-terminal see a TTY on stdin, stdout and stderr, so
+/// tools that refuse to run without one, or change their output when piped,
+/// behave as they do in a terminal. Stdout and stderr are merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PtyMode {
+    /// Never allocate a pseudo-terminal
+    Never,
+    /// Only recipes annotated with `# [tty]`
+    #[default]
+    Annotated,
+    /// Every recipe
+    Always,
+}
+
+impl FromStr for PtyMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "never" => Ok(Self::Never),
+            "annotated" => Ok(Self::Annotated),
+            "always" => Ok(Self::Always),
+            _ => Err(Error::InvalidParameter(format!(
+                "Unknown PTY mode '{s}', expected never, annotated or always"
+            ))),
+        }
+    }
+}
+
+/// Return the prompt line if stalled output appears to be waiting for input
+///
+/// Prompts leave the cursor on their line, so only a last line without a
+/// newline after it counts; complete lines mentioning a password or asking
+/// a question are ordinary log output.
+pub fn detect_prompt(output: &str) -> Option<String> {
+    let line = output.rsplit('\n').next()?.trim();
+    if line.is_empty() {
+        return None;
+    }
+
+    if EXPLICIT_PROMPT.is_match(line) || TRAILING_PROMPT.is_match(line) {
+        Some(line.to_string())
+    } else {
+        None
+    }
+}
+
+/// How a monitored execution ended
+#[derive(Debug)]
+pub enum RunOutcome {
+    Completed(ExitStatus),
+    /// The process ran past the time limit, possibly stalled on a prompt
+    /// that was not rejected
+    TimedOut {
+        prompt: Option<String>,
+    },
+    /// The process stalled on an unanswered prompt and was killed
+    PromptRejected(String),
+    /// The execution was cancelled through the watchdog and was killed
+    Cancelled,
+    /// A configured response could not be written to the process's stdin,
+    /// so it was killed
+    InputFailed {
+        prompt: String,
+        error: std::io::Error,
+    },
+}
+
+/// Responses on their way to a process's stdin, and the error that ended
+/// writing them
+pub(super) struct PromptInput {
+    responses: mpsc::UnboundedSender<String>,
+    failed: mpsc::UnboundedReceiver<std::io::Error>,
+}
+
+impl PromptInput {
+    /// Input for a writer that takes responses from the returned receiver
+    /// and reports a failed write on the returned sender
+    pub(super) fn channel() -> (
+        Self,
+        mpsc::UnboundedReceiver<String>,
+        mpsc::UnboundedSender<std::io::Error>,
+    ) {
+        let (responses, responses_rx) = mpsc::unbounded_channel();
+        let (failed_tx, failed) = mpsc::unbounded_channel();
+        (Self { responses, failed }, responses_rx, failed_tx)
+    }
+}
+
+/// Resolves with the error that stopped writing responses; never without
+/// input
+async fn input_failure(input: Option<&mut PromptInput>) -> std::io::Error {
+    match input {
+        Some(input) => match input.failed.recv().await {
+            Some(error) => error,
+            None => std::future::pending().await,
+        },
+        None => std::future::pending().await,
+    }
+}
+
+/// Captured output of a monitored execution
+#[derive(Debug)]
+pub struct MonitoredOutput {
+    pub outcome: RunOutcome,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Run a command, watching its output for interactive prompts
+///
+/// Stdin is only connected when responses are configured; otherwise it is
+/// null, matching the behaviour of a plain non-interactive run.
+pub async fn run_monitored(
+    mut cmd: Command,
+    limit: Duration,
+    config: &InteractiveConfig,
+    watch: Option<&WatchedExecution>,
+) -> std::io::Result<MonitoredOutput> {
+    let answering = !config.responses.is_empty();
+    cmd.stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .stdin(if answering {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .kill_on_drop(true);
+
+    let mut child = cmd.spawn()?;
+    if let Some(watch) = watch {
+        watch.set_pid(child.id());
+    }
+
+    let input = match child.stdin.take() {
+        Some(mut stdin) => {
+            let (input, mut responses, failed) = PromptInput::channel();
+            tokio::spawn(async move {
+                while let Some(response) = responses.recv().await {
+                    let written = match stdin.write_all(response.as_bytes()).await {
+                        Ok(()) => stdin.flush().await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = written {
+                        let _ = failed.send(e);
+                        break;
+                    }
+                }
+            });
+            Some(input)
+        }
+        None => None,
+    };
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    if let Some(stdout) = child.stdout.take() {
+        spawn_reader(stdout, Stream::Stdout, tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        spawn_reader(stderr, Stream::Stderr, tx.clone());
+    }
+    drop(tx);
+
+    let output = monitor(rx, input, child.wait(), limit, config, watch).await?;
+    if !matches!(output.outcome, RunOutcome::Completed(_)) {
+        if let (RunOutcome::Cancelled, Some(watch)) = (&output.outcome, watch) {
+            watch.kill_descendants();
+        }
+        let _ = child.start_kill();
+        let _ = child.wait().await;
+    }
+    Ok(output)
+}
+
+/// Collect a process's output until it exits, answering or rejecting
+/// prompts it stalls on
+///
+/// `exit` resolves once the process has exited; it is only polled after the
+/// output streams close. The caller kills the process unless the outcome is
+/// [`RunOutcome::Completed`].
+pub(super) async fn monitor(
+    mut rx: mpsc::UnboundedReceiver<(Stream, Vec<u8>)>,
+    mut input: Option<PromptInput>,
+    exit: impl Future<Output = std::io::Result<ExitStatus>>,
+    limit: Duration,
+    config: &InteractiveConfig,
+    watch: Option<&WatchedExecution>,
+) -> std::io::Result<MonitoredOutput> {
+    tokio::pin!(exit);
+
+    let deadline = Instant::now() + limit;
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut last_stream = Stream::Stdout;
+    let mut last_activity = Instant::now();
+    let mut streams_open = true;
+    let mut responses_sent = 0;
+    let mut answered_len = None;
+    let mut answered_prompt = String::new();
+    // Prompt the output is currently stalled on, for the timeout report
+    let mut stalled_prompt = None;
+
+    let outcome = loop {
+        let wake = deadline.min(last_activity + config.stall_timeout);
+
+        tokio::select! {
+            chunk = rx.recv(), if streams_open => match chunk {
+                Some((stream, data)) => {
+                    match stream {
+                        Stream::Stdout => stdout.extend_from_slice(&data),
+                        Stream::Stderr => stderr.extend_from_slice(&data),
+                    }
+                    last_stream = stream;
+                    last_activity = Instant::now();
+                    stalled_prompt = None;
+                    if let Some(watch) = watch {
+                        watch.record_output();
+                    }
+                }
+                None => streams_open = false,
+            },
+            status = &mut exit, if !streams_open => break RunOutcome::Completed(status?),
+            _ = cancelled(watch) => break RunOutcome::Cancelled,
+            error = input_failure(input.as_mut()) => {
+                warn!("Could not answer interactive prompt '{}': {}", answered_prompt, error);
+                break RunOutcome::InputFailed { prompt: answered_prompt, error };
+            }
+            _ = sleep_until(wake) => {
+                if Instant::now() >= deadline {
+                    break RunOutcome::TimedOut { prompt: stalled_prompt };
+                }
+                last_activity = Instant::now();
+
+                let tail = match last_stream {
+                    Stream::Stdout => &stdout,
+                    Stream::Stderr => &stderr,
+                };
+                let Some(prompt) = detect_prompt(&String::from_utf8_lossy(tail_bytes(tail))) else {
+                    continue;
+                };
+
+                // Only answer once per prompt; a repeated prompt with no new
+                // output means the response was not accepted
+                let output_len = stdout.len() + stderr.len();
+                if let (Some(response), Some(input)) = (config.response_for(&prompt), input.as_ref()) {
+                    if responses_sent < config.max_responses && answered_len != Some(output_len) {
+                        info!("Answering interactive prompt '{}'", prompt);
+                        if input.responses.send(format!("{response}\n")).is_err() {
+                            break RunOutcome::InputFailed {
+                                prompt,
+                                error: std::io::Error::new(
+                                    std::io::ErrorKind::BrokenPipe,
+                                    "stdin of the recipe is closed",
+                                ),
+                            };
+                        }
+                        responses_sent += 1;
+                        answered_len = Some(output_len);
+                        answered_prompt = prompt;
+                        continue;
+                    }
+                }
+
+                if config.reject_prompts.rejects(&prompt) {
+                    warn!("Killing recipe waiting for interactive input: '{}'", prompt);
+                    break RunOutcome::PromptRejected(prompt);
+                }
+                stalled_prompt = Some(prompt);
+            }
+        }
+    };
+
+    Ok(MonitoredOutput {
+        outcome,
+        stdout,
+        stderr,
+    })
+}
+
+/// Resolves when a watched execution is cancelled; never without a watch
+async fn cancelled(watch: Option<&WatchedExecution>) {
+    match watch {
+        Some(watch) => watch.cancelled().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Last few KB of output, enough to find the final line
+fn tail_bytes(output: &[u8]) -> &[u8] {
+    &output[output.len().saturating_sub(4096)..]
+}
+
+fn spawn_reader<R>(mut reader: R, stream: Stream, tx: mpsc::UnboundedSender<(Stream, Vec<u8>)>)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut buf = [0u8; 8192];
+        loop {
+            match reader.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if tx.send((stream, buf[..n].to_vec())).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_prompt() {
+        assert_eq!(
+            detect_prompt("Reading lists...\nDo you want to continue? [Y/n] "),
+            Some("Do you want to continue? [Y/n]".to_string())
+        );
+        assert_eq!(detect_prompt("Password: "), Some("Password:".to_string()));
+        assert_eq!(
+            detect_prompt("Proceed (yes/no) "),
+            Some("Proceed (yes/no)".to_string())
+        );
+        // Completed lines are ordinary output, whatever they mention
+        assert_eq!(detect_prompt("Proceed (yes/no)\n"), None);
+        assert_eq!(detect_prompt("checking password policy...\n"), None);
+        assert_eq!(detect_prompt("Results:\n"), None);
+        assert_eq!(detect_prompt("Compiling just-mcp v0.2.0\n"), None);
+        assert_eq!(detect_prompt(""), None);
+    }
+
+    #[test]
+    fn test_prompt_response_parse() {
+        let response = PromptResponse::parse(r"continue\?=y").unwrap();
+        assert_eq!(response.response, "y");
+
+        let config = InteractiveConfig {
+            responses: vec![response],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.response_for("Do you want to continue? [Y/n]"),
+            Some("y")
+        );
+        assert_eq!(config.response_for("Password:"), None);
+
+        assert!(PromptResponse::parse("no-separator").is_err());
+        assert!(PromptResponse::parse("([=y").is_err());
+    }
+
+    #[test]
+    fn test_prompt_rejection() {
+        assert!(PromptRejection::default().rejects("Continue? [Y/n]"));
+        assert!(PromptRejection::default().rejects("Password:"));
+        assert!(!PromptRejection::default().rejects("Waiting for lock:"));
+        assert!(PromptRejection::Any.rejects("Waiting for lock:"));
+        assert!(!PromptRejection::Never.rejects("Password:"));
+        assert_eq!(
+            "any".parse::<PromptRejection>().unwrap(),
+            PromptRejection::Any
+        );
+        assert!("always".parse::<PromptRejection>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_monitored_rejects_stalled_prompt() {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg("printf 'Password: '; sleep 10");
+        // Explicit prompts are rejected with the default policy
+        let config = InteractiveConfig {
+            stall_timeout: Duration::from_millis(200),
+            ..Default::default()
+        };
+
+        let started = Instant::now();
+        let output = run_monitored(cmd, Duration::from_secs(30), &config, None)
+            .await
+            .unwrap();
+
+        assert!(matches!(output.outcome, RunOutcome::PromptRejected(ref p) if p == "Password:"));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_run_monitored_ignores_completed_prompt_like_lines() {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg("echo 'checking password policy...'; sleep 1; echo done");
+        let config = InteractiveConfig {
+            stall_timeout: Duration::from_millis(200),
+            reject_prompts: PromptRejection::Any,
+            ..Default::default()
+        };
+
+        let output = run_monitored(cmd, Duration::from_secs(30), &config, None)
+            .await
+            .unwrap();
+
+        assert!(matches!(output.outcome, RunOutcome::Completed(status) if status.success()));
+        assert!(String::from_utf8_lossy(&output.stdout).ends_with("done\n"));
+    }
+
+    #[tokio::test]
+    async fn test_run_monitored_reports_failed_response() {
+        // The prompt's process closes stdin before it is answered
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg("exec 0<&-; printf 'Continue? [y/N] '; sleep 10");
+        let config = InteractiveConfig {
+            stall_timeout: Duration::from_millis(200),
+            responses: vec![PromptResponse::new(r"Continue\?", "y").unwrap()],
+            ..Default::default()
+        };
+
+        let output = run_monitored(cmd, Duration::from_secs(30), &config, None)
+            .await
+            .unwrap();
+
+        assert!(
+            matches!(output.outcome, RunOutcome::InputFailed { ref prompt, .. } if prompt.starts_with("Continue?")),
+            "{:?}",
+            output.outcome
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_monitored_answers_prompt() {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg("printf 'Continue? [y/N] '; read answer; echo \"answer=$answer\"");
+        let config = InteractiveConfig {
+            stall_timeout: Duration::from_millis(200),
+            responses: vec![PromptResponse::new(r"Continue\?", "y").unwrap()],
+            ..Default::default()
+        };
+
+        let output = run_monitored(cmd, Duration::from_secs(30), &config, None)
+            .await
+            .unwrap();
+
+        assert!(matches!(output.outcome, RunOutcome::Completed(status) if status.success()));
+        assert!(String::from_utf8_lossy(&output.stdout).contains("answer=y"));
+    }
+
+    #[tokio::test]
+    async fn test_run_monitored_timeout() {
+        let mut cmd = Command::new("sleep");
+        cmd.arg("10");
+
+        let output = run_monitored(
+            cmd,
+            Duration::from_millis(200),
+            &InteractiveConfig::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            output.outcome,
+            RunOutcome::TimedOut { prompt: None }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_run_monitored_timeout_reports_stalled_prompt() {
+        // Only ends like a prompt, so the default policy lets it run
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg("printf 'Enter build name: '; sleep 10");
+        let config = InteractiveConfig {
+            stall_timeout: Duration::from_millis(100),
+            ..Default::default()
+        };
+
+        let output = run_monitored(cmd, Duration::from_millis(500), &config, None)
+            .await
+            .unwrap();
+
+        assert!(
+            matches!(output.outcome, RunOutcome::TimedOut { prompt: Some(ref p) } if p == "Enter build name:"),
+            "{:?}",
+            output.outcome
+        );
+    }
+}
@@ -1,61 +1,266 @@
-use crate::error::{Error, Result};
+use self::ansi::AnsiMode;
+use self::environment::{recipe_environment, EnvironmentDiff, ShellEnvironment};
+use self::interactive::{run_monitored, InteractiveConfig, PtyMode, RunOutcome};
+use self::watchdog::{ExecutionWatchdog, WatchdogConfig};
+use crate::error::{Error, ErrorKind, Result};
+use crate::parser::annotations::{
+    EXCLUSIVE_ANNOTATION, NO_CD_ANNOTATION, PROFILE_ARGUMENT, SLOT_ANNOTATION, TTY_ANNOTATION,
+};
+use crate::parser::imports::GraphStamp;
+use crate::parser::variables::{extract_variables, VARIABLE_ARG_PREFIX};
 use crate::parser::EnhancedJustfileParser;
+use crate::profiles::ProfileSet;
+use crate::project_lock::{LockConfig, ProjectLock};
 use crate::resource_limits::{ResourceLimits, ResourceManager};
+use crate::security::redact::{redact_arg, redact_value};
 use crate::security::{SecurityConfig, SecurityValidator};
-use crate::types::{ExecutionContext, ExecutionRequest, ExecutionResult, JustTask};
+use crate::types::{
+    CommandInvocation, ExecutionContext, ExecutionRequest, ExecutionResult, JustTask,
+};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 use tokio::process::Command;
 use tokio::time::{timeout, Duration};
-use tracing::{error, info, warn};
+use tracing::{error, info, trace, warn};
+
+pub mod ansi;
+pub mod environment;
+pub mod inline;
+pub mod interactive;
+#[cfg(feature = "pty")]
+pub mod pty;
+pub mod watchdog;
 
 // Re-export for tests
 pub use crate::security::{SecurityConfig as SecConfig, SecurityValidator as SecValidator};
 
+/// Parsed tasks keyed by justfile path, with the stamp of the import graph
+/// they were parsed from
+type JustfileCache = HashMap<PathBuf, (GraphStamp, Vec<JustTask>)>;
+
+/// Executes justfile tasks
+///
+/// Cloning is cheap: clones share the parser, justfile cache, resource
+/// manager and watchdog, so a server can run executions concurrently from clones while
+/// the resource manager enforces the global and per-project limits.
+#[derive(Clone)]
 pub struct TaskExecutor {
-    default_timeout: Duration,
-    parser: EnhancedJustfileParser,
-    justfile_cache: HashMap<PathBuf, Vec<JustTask>>,
-    security_validator: SecurityValidator,
+    /// Timeout overriding the resource manager's, set by `with_timeout`
+    timeout_override: Option<Duration>,
+    parser: Arc<EnhancedJustfileParser>,
+    justfile_cache: Arc<std::sync::Mutex<JustfileCache>>,
+    security_validator: Arc<SecurityValidator>,
     resource_manager: Arc<ResourceManager>,
+    /// Watch directories and their names, used to key per-project limits
+    project_names: Vec<(PathBuf, String)>,
+    interactive: Arc<InteractiveConfig>,
+    /// Attach the redacted command line to execution results
+    echo_commands: bool,
+    /// Post-processing of ANSI escape sequences in recipe output
+    ansi_mode: AnsiMode,
+    /// Take the project lock while `# [exclusive]` recipes run
+    exclusive_lock: Option<LockConfig>,
+    /// Login shell environment, captured on first use, that failed
+    /// executions are compared against; `None` disables the check
+    shell_environment: Option<Arc<tokio::sync::OnceCell<Option<ShellEnvironment>>>>,
+    /// Running executions, sampled for signs of a hang
+    watchdog: Arc<ExecutionWatchdog>,
+    /// Whether the installed `just` accepts `--jobs`, checked on first use
+    jobs_flag: Arc<tokio::sync::OnceCell<bool>>,
+    /// Named profiles from the profiles file
+    profiles: Arc<ProfileSet>,
 }
 
 impl TaskExecutor {
     pub fn new() -> Self {
         let resource_manager = Arc::new(ResourceManager::with_default());
         Self {
-            default_timeout: resource_manager.get_timeout(),
-            parser: EnhancedJustfileParser::new().expect("Failed to create parser"),
-            justfile_cache: HashMap::new(),
-            security_validator: SecurityValidator::with_default(),
+            timeout_override: None,
+            parser: Arc::new(EnhancedJustfileParser::new().expect("Failed to create parser")),
+            justfile_cache: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            security_validator: Arc::new(SecurityValidator::with_default()),
             resource_manager,
+            project_names: Vec::new(),
+            interactive: Arc::new(InteractiveConfig::default()),
+            echo_commands: false,
+            ansi_mode: AnsiMode::default(),
+            exclusive_lock: None,
+            shell_environment: None,
+            watchdog: Arc::new(ExecutionWatchdog::default()),
+            jobs_flag: Arc::new(tokio::sync::OnceCell::new()),
+            profiles: Arc::new(ProfileSet::default()),
         }
     }
 
     pub fn with_security_config(mut self, config: SecurityConfig) -> Self {
-        self.security_validator = SecurityValidator::new(config);
+        self.security_validator = Arc::new(SecurityValidator::new(config));
         self
     }
 
     pub fn with_resource_limits(mut self, limits: ResourceLimits) -> Self {
         self.resource_manager = Arc::new(ResourceManager::new(limits));
-        self.default_timeout = self.resource_manager.get_timeout();
         self
     }
 
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.default_timeout = timeout;
+        self.timeout_override = Some(timeout);
+        self
+    }
+
+    /// Timeout for executions that don't request one
+    ///
+    /// Follows the resource manager, so limits changed at runtime apply to
+    /// the next execution.
+    pub fn default_timeout(&self) -> Duration {
+        self.timeout_override
+            .unwrap_or_else(|| self.resource_manager.get_timeout())
+    }
+
+    /// Configure how recipes waiting on interactive input are handled
+    pub fn with_interactive_config(mut self, config: InteractiveConfig) -> Self {
+        self.interactive = Arc::new(config);
+        self
+    }
+
+    /// Include the redacted `just` command line in execution results
+    ///
+    /// The command line is always logged at trace level; this also returns
+    /// it to clients so a run can be reproduced locally.
+    pub fn with_command_echo(mut self, enabled: bool) -> Self {
+        self.echo_commands = enabled;
+        self
+    }
+
+    /// Choose how ANSI escape sequences in recipe output are handled
+    pub fn with_ansi_mode(mut self, mode: AnsiMode) -> Self {
+        self.ansi_mode = mode;
+        self
+    }
+
+    /// Hold the advisory project lock while `# [exclusive]` recipes run
+    ///
+    /// This serialises exclusive recipes across just-mcp instances sharing a
+    /// project, and with admin edits. `None` disables the lock.
+    pub fn with_exclusive_lock(mut self, config: Option<LockConfig>) -> Self {
+        self.exclusive_lock = config;
+        self
+    }
+
+    /// Compare the recipe environment with the user's login shell
+    ///
+    /// Variables and `PATH` entries the shell has but recipes don't are
+    /// attached as warnings to failed executions, since they often explain
+    /// why a recipe that works in a terminal fails here.
+    pub fn with_environment_check(mut self, enabled: bool) -> Self {
+        self.shell_environment = enabled.then(|| Arc::new(tokio::sync::OnceCell::new()));
+        self
+    }
+
+    /// Compare against `shell` instead of capturing the login shell
+    #[cfg(test)]
+    fn with_shell_environment(mut self, shell: ShellEnvironment) -> Self {
+        self.shell_environment = Some(Arc::new(tokio::sync::OnceCell::new_with(Some(Some(shell)))));
         self
     }
 
+    /// Differences between the login shell and the environment of a recipe
+    ///
+    /// The shell environment is captured once; if that fails, the check is
+    /// skipped for the rest of the session.
+    async fn environment_diff(
+        &self,
+        overrides: &HashMap<String, String>,
+    ) -> Option<EnvironmentDiff> {
+        let shell = self
+            .shell_environment
+            .as_ref()?
+            .get_or_init(|| async {
+                ShellEnvironment::capture()
+                    .await
+                    .inspect_err(|e| warn!("Environment check disabled: {}", e))
+                    .ok()
+            })
+            .await
+            .as_ref()?;
+        let diff = EnvironmentDiff::compare(shell, &recipe_environment(overrides));
+        (!diff.is_empty()).then_some(diff)
+    }
+
+    /// Configure how running executions are sampled and when they count as
+    /// hung
+    pub fn with_watchdog_config(mut self, config: WatchdogConfig) -> Self {
+        self.watchdog = Arc::new(ExecutionWatchdog::new(config));
+        self
+    }
+
+    /// Shared watchdog tracking running executions
+    pub fn watchdog(&self) -> &Arc<ExecutionWatchdog> {
+        &self.watchdog
+    }
+
+    /// Whether the installed `just` supports `--jobs`
+    ///
+    /// Older releases run dependencies one at a time and reject the flag,
+    /// so it is only passed when `just --help` lists it.
+    async fn supports_jobs_flag(&self) -> bool {
+        *self
+            .jobs_flag
+            .get_or_init(|| async {
+                match Command::new("just").arg("--help").output().await {
+                    Ok(output) => help_lists_jobs_flag(&String::from_utf8_lossy(&output.stdout)),
+                    Err(e) => {
+                        warn!("Could not check just for --jobs support: {}", e);
+                        false
+                    }
+                }
+            })
+            .await
+    }
+
+    /// Name projects after their watch directories for per-project limits
+    ///
+    /// Justfiles outside any named watch directory are keyed by their parent
+    /// directory.
+    pub fn with_project_names(mut self, configs: &[(PathBuf, Option<String>)]) -> Self {
+        self.project_names = configs
+            .iter()
+            .filter_map(|(path, name)| Some((path.clone(), name.clone()?)))
+            .collect();
+        self
+    }
+
+    /// Apply the named profiles of the profiles file to calls selecting them
+    pub fn with_profiles(mut self, profiles: ProfileSet) -> Self {
+        self.profiles = Arc::new(profiles);
+        self
+    }
+
+    /// Named profiles calls can select
+    pub fn profiles(&self) -> &Arc<ProfileSet> {
+        &self.profiles
+    }
+
+    /// Shared resource manager enforcing execution limits
+    pub fn resource_manager(&self) -> &Arc<ResourceManager> {
+        &self.resource_manager
+    }
+
+    /// Project key used for per-project concurrency limits
+    fn project_key(&self, justfile_path: &Path) -> String {
+        let dir = justfile_path.parent().unwrap_or(justfile_path);
+        self.project_names
+            .iter()
+            .filter(|(path, _)| dir.starts_with(path))
+            .max_by_key(|(path, _)| path.components().count())
+            .map(|(_, name)| name.clone())
+            .unwrap_or_else(|| dir.display().to_string())
+    }
+
     pub async fn execute(&mut self, request: ExecutionRequest) -> Result<ExecutionResult> {
         info!("Executing task: {}", request.tool_name);
 
-        // Check resource limits before starting
-        self.resource_manager.can_execute()?;
-
         // Extract task name and justfile path from tool name
         // Tool names are in format: taskname_/path/to/justfile
         let (task_name, justfile_path) = self.parse_tool_name(&request.tool_name)?;
@@ -71,40 +276,93 @@ impl TaskExecutor {
         let justfile_path_buf = PathBuf::from(&justfile_path);
         self.security_validator.validate_path(&justfile_path_buf)?;
 
-        // Validate parameters
-        self.security_validator
-            .validate_parameters(&request.parameters)?;
-
         // Verify task exists
-        {
-            let tasks = self.get_or_parse_justfile(&justfile_path)?;
-            let _task = tasks
+        let tasks = self.get_or_parse_justfile(&justfile_path)?;
+        let Some(task) = tasks.iter().find(|t| t.name == task_name) else {
+            return Err(Error::TaskNotFound(task_name));
+        };
+
+        // Apply the selected named profile's parameters and environment
+        let mut parameters = request.parameters;
+        let mut environment = request.context.environment;
+        let profile = self.selected_profile(task, &mut parameters, request.context.profile);
+        if let Some(profile) = &profile {
+            // Slots are set through the argument named like their schema property
+            let slots: Vec<String> = task
+                .annotation_values(SLOT_ANNOTATION)
                 .iter()
-                .find(|t| t.name == task_name)
-                .ok_or_else(|| Error::TaskNotFound(task_name.clone()))?;
+                .map(|slot| self.security_validator.sanitize_parameter_name(slot))
+                .collect();
+            self.profiles.apply(
+                profile,
+                &task_name,
+                &mut parameters,
+                &mut environment,
+                |key| {
+                    key.starts_with(VARIABLE_ARG_PREFIX)
+                        || slots.iter().any(|slot| slot == key)
+                        || task.parameters.iter().any(|p| p.name == key)
+                },
+            )?;
         }
 
-        // Determine working directory
-        let working_dir = request
-            .context
-            .working_directory
-            .as_ref()
-            .map(PathBuf::from)
-            .or_else(|| Path::new(&justfile_path).parent().map(|p| p.to_path_buf()))
-            .unwrap_or_else(|| PathBuf::from("."));
+        // Validate parameters
+        self.security_validator.validate_parameters(&parameters)?;
+
+        // Determine working directory. `just` changes into the justfile's
+        // directory itself, except for `[no-cd]` recipes, which run where it
+        // was invoked: the requested directory or the server's own
+        let working_dir = match request.context.working_directory.as_ref() {
+            Some(dir) => PathBuf::from(dir),
+            None if task.has_annotation(NO_CD_ANNOTATION) => std::env::current_dir()?,
+            None => justfile_path_buf
+                .parent()
+                .map(|p| p.to_path_buf())
+                .unwrap_or_else(|| PathBuf::from(".")),
+        };
 
         // Execute the command
         let context = ExecutionContext {
             working_directory: Some(working_dir.to_string_lossy().to_string()),
-            environment: request.context.environment,
+            environment,
             timeout: request.context.timeout,
+            profile,
         };
 
-        // Start tracking this execution
-        let _execution_guard = self.resource_manager.start_execution();
+        // Serialize with executions in the same exclusion groups, then wait
+        // for a slot under the global and per-project limits
+        let project = self.project_key(&justfile_path_buf);
+        let _exclusive = self
+            .resource_manager
+            .acquire_exclusive(&project, &task.exclusion_groups())
+            .await;
+        let _permit = self.resource_manager.acquire(&project).await;
+
+        let environment = self.environment_diff(&context.environment).await;
+        let mut result = self
+            .execute_just_command(&justfile_path_buf, &task_name, &parameters, &context)
+            .await?;
+        if let (false, Some(environment)) = (result.success, environment) {
+            result.warnings.extend(environment.warnings());
+            result.error_kind.get_or_insert(ErrorKind::Precondition);
+        }
+        Ok(result)
+    }
 
-        self.execute_just_command(&task_name, &request.parameters, &context)
-            .await
+    /// `just` with the given arguments, in the context's directory and
+    /// environment
+    fn just_command(&self, args: &[String], context: &ExecutionContext) -> Command {
+        let mut cmd = Command::new("just");
+        if let Some(ref wd) = context.working_directory {
+            cmd.current_dir(wd);
+        }
+        cmd.args(args);
+
+        // Set environment variables
+        for (key, value) in &context.environment {
+            cmd.env(key, value);
+        }
+        cmd
     }
 
     fn parse_tool_name(&self, tool_name: &str) -> Result<(String, String)> {
@@ -139,17 +397,11 @@ impl TaskExecutor {
         }
     }
 
-    fn get_or_parse_justfile(&mut self, path: &str) -> Result<&Vec<JustTask>> {
+    fn get_or_parse_justfile(&self, path: &str) -> Result<Vec<JustTask>> {
         let path_buf = PathBuf::from(path);
 
         info!("Getting or parsing justfile at: {}", path_buf.display());
 
-        // Check cache first
-        if self.justfile_cache.contains_key(&path_buf) {
-            info!("Found in cache");
-            return Ok(self.justfile_cache.get(&path_buf).unwrap());
-        }
-
         // Check if file exists
         if !path_buf.exists() {
             error!("Justfile does not exist at: {}", path_buf.display());
@@ -159,16 +411,32 @@ impl TaskExecutor {
             )));
         }
 
+        // Check cache first; entries are invalidated when the justfile or
+        // any file it imports changes on disk
+        if let Some((stamp, tasks)) = self.justfile_cache.lock().unwrap().get(&path_buf) {
+            if stamp.is_current() {
+                info!("Found in cache");
+                return Ok(tasks.clone());
+            }
+        }
+
+        // Stamped before parsing, so edits made meanwhile invalidate the entry
+        let stamp = GraphStamp::of(&path_buf)?;
+
         // Parse the justfile
         info!("Parsing justfile...");
         let tasks = self.parser.parse_file(&path_buf)?;
         info!("Parsed {} tasks", tasks.len());
-        self.justfile_cache.insert(path_buf.clone(), tasks);
-        Ok(self.justfile_cache.get(&path_buf).unwrap())
+        self.justfile_cache
+            .lock()
+            .unwrap()
+            .insert(path_buf, (stamp, tasks.clone()));
+        Ok(tasks)
     }
 
     async fn execute_just_command(
         &self,
+        justfile_path: &Path,
         task_name: &str,
         parameters: &HashMap<String, serde_json::Value>,
         context: &ExecutionContext,
@@ -178,71 +446,213 @@ impl TaskExecutor {
             task_name, context
         );
 
-        // Use just to execute the command
-        let mut cmd = Command::new("just");
+        // Build the argument list alongside a redacted copy for echoing
+        let mut args = Vec::new();
+        let mut echoed_args = Vec::new();
+
+        // Name the justfile relative to the working directory when it is in
+        // it, so echoed commands stay short
+        let working_dir = Path::new(context.working_directory.as_deref().unwrap_or("."));
+        let justfile_arg = match justfile_path.parent() {
+            Some(dir) if dir == working_dir => justfile_path
+                .file_name()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| justfile_path.to_path_buf()),
+            _ => justfile_path.to_path_buf(),
+        };
+        let justfile_arg = justfile_arg.to_string_lossy().to_string();
+        args.extend(["--justfile".to_string(), justfile_arg.clone()]);
+        echoed_args.extend(["--justfile".to_string(), justfile_arg]);
 
-        // Set working directory if provided
-        if let Some(ref wd) = context.working_directory {
-            cmd.current_dir(wd);
-            // The justfile is in the working directory
-            cmd.arg("--justfile");
-            cmd.arg("justfile");
+        // Get task definition to know parameter order
+        let tasks = self.parser.parse_file(justfile_path)?;
+        let task = tasks.iter().find(|t| t.name == task_name);
+
+        let _project_lock = match (&self.exclusive_lock, task) {
+            (Some(config), Some(task)) if task.has_annotation(EXCLUSIVE_ANNOTATION) => Some(
+                ProjectLock::acquire(
+                    justfile_path.parent().unwrap_or(working_dir),
+                    &format!("exclusive recipe '{task_name}'"),
+                    config,
+                )
+                .await?,
+            ),
+            _ => None,
+        };
+
+        // Parallel jobs for the recipe's dependencies, capped by the limits
+        let mut warnings = Vec::new();
+        let limits = self.resource_manager.limits();
+        let requested_jobs = limits.requested_jobs(
+            &self.project_key(justfile_path),
+            task.and_then(|t| t.group.as_deref()),
+            task.and_then(JustTask::jobs),
+        );
+        if let Some(requested) = requested_jobs {
+            let jobs = requested.min(limits.max_jobs.max(1));
+            if jobs < requested {
+                warnings.push(format!(
+                    "Recipe '{task_name}' asked for {requested} parallel jobs; \
+                     it was limited to {jobs}"
+                ));
+            }
+            if self.supports_jobs_flag().await {
+                let jobs_args = ["--jobs".to_string(), jobs.to_string()];
+                args.extend(jobs_args.clone());
+                echoed_args.extend(jobs_args);
+            } else {
+                warnings.push(format!(
+                    "Recipe '{task_name}' is configured for {jobs} parallel jobs, but the \
+                     installed just does not support --jobs; its dependencies ran one at a time"
+                ));
+            }
         }
 
-        // Add the task name
-        cmd.arg(task_name);
+        // Expand a selected execution profile into its preset arguments
+        let parameters = &match task {
+            Some(task) => self.apply_profile(task, parameters)?,
+            None => parameters.clone(),
+        };
 
-        // Get task definition to know parameter order
-        let tasks = self.parser.parse_file(&PathBuf::from(format!(
-            "{}/justfile",
-            context.working_directory.as_ref().unwrap()
-        )))?;
-        let task = tasks.iter().find(|t| t.name == task_name);
+        // Template slots and `var_*` arguments are passed as just variable
+        // overrides (`NAME=VALUE`, the positional form of `--set`) before the
+        // task name, so values starting with `-` are not mistaken for flags
+        let mut overrides = Vec::new();
+        if let Some(task) = task {
+            overrides.extend(self.slot_overrides(task, parameters));
+        }
+        let content = std::fs::read_to_string(justfile_path)?;
+        overrides.extend(self.variable_overrides(&content, parameters, task));
+        for (name, value) in overrides {
+            let arg = format!("{name}={value}");
+            echoed_args.push(redact_arg(&arg));
+            args.push(arg);
+        }
+
+        // Add the task name
+        args.push(task_name.to_string());
+        echoed_args.push(task_name.to_string());
 
         if let Some(task) = task {
             // Add parameters in the order they're defined in the task
             for param in &task.parameters {
-                if let Some(value) = parameters.get(&param.name) {
+                let value = if let Some(value) = parameters.get(&param.name) {
                     let value_str = match value {
                         serde_json::Value::String(s) => s.clone(),
                         other => other.to_string(),
                     };
                     // Sanitize parameter value before passing to command
-                    let sanitized_value = self.security_validator.sanitize_parameter(&value_str);
-                    cmd.arg(sanitized_value);
+                    self.security_validator.sanitize_parameter(&value_str)
                 } else if let Some(default) = &param.default {
                     // Sanitize default value as well
-                    let sanitized_default = self.security_validator.sanitize_parameter(default);
-                    cmd.arg(sanitized_default);
-                }
+                    self.security_validator.sanitize_parameter(default)
+                } else {
+                    continue;
+                };
+                echoed_args.push(redact_value(&param.name, &value));
+                args.push(value);
             }
         }
 
-        cmd.stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .stdin(Stdio::null());
-
-        // Set environment variables
-        for (key, value) in &context.environment {
-            cmd.env(key, value);
-        }
+        let invocation = CommandInvocation {
+            program: "just".to_string(),
+            args: echoed_args,
+            cwd: context.working_directory.clone(),
+            env: context
+                .environment
+                .iter()
+                .map(|(key, value)| (key.clone(), redact_value(key, value)))
+                .collect(),
+        };
+        trace!("Reproduce with: {}", invocation.command_line());
+        let invocation = self.echo_commands.then_some(invocation);
 
         let timeout_duration = context
             .timeout
             .map(Duration::from_secs)
-            .unwrap_or(self.default_timeout);
+            .unwrap_or_else(|| self.default_timeout());
+
+        // Execute with timeout, watching for interactive prompts, in a
+        // pseudo-terminal for recipes that need one
+        let use_pty = match self.interactive.pty {
+            PtyMode::Never => false,
+            PtyMode::Annotated => task.is_some_and(|t| t.has_annotation(TTY_ANNOTATION)),
+            PtyMode::Always => true,
+        };
+        let execution = self.watchdog.start(task_name);
+        if use_pty && cfg!(not(feature = "pty")) {
+            warnings.push(format!(
+                "Recipe '{task_name}' needs a terminal, but this build has no pseudo-terminal \
+                 support (feature `pty`); it ran with piped output"
+            ));
+        }
+        #[cfg(feature = "pty")]
+        let output = if use_pty {
+            pty::run_in_pty(
+                "just",
+                &args,
+                context.working_directory.as_deref(),
+                &context.environment,
+                timeout_duration,
+                &self.interactive,
+                Some(&execution),
+            )
+            .await
+        } else {
+            run_monitored(
+                self.just_command(&args, context),
+                timeout_duration,
+                &self.interactive,
+                Some(&execution),
+            )
+            .await
+        };
+        #[cfg(not(feature = "pty"))]
+        let output = run_monitored(
+            self.just_command(&args, context),
+            timeout_duration,
+            &self.interactive,
+            Some(&execution),
+        )
+        .await;
+        drop(execution);
+        let output = match output {
+            Ok(output) => output,
+            Err(_) if !crate::just_binary::is_on_path() => {
+                return Err(crate::just_binary::not_installed(format!(
+                    "to run recipe '{task_name}'"
+                )));
+            }
+            Err(e) => {
+                error!("Failed to execute command: {}", e);
+                return Ok(ExecutionResult {
+                    success: false,
+                    exit_code: None,
+                    stdout: String::new(),
+                    stderr: String::new(),
+                    error: Some(format!("Failed to execute command: {e}")),
+                    invocation,
+                    warnings,
+                    error_kind: Some(ErrorKind::Transient),
+                });
+            }
+        };
 
-        // Execute with timeout
-        match timeout(timeout_duration, cmd.output()).await {
-            Ok(Ok(output)) => {
-                // Check output size limits
-                self.resource_manager
-                    .check_output_size(output.stdout.len(), output.stderr.len())?;
+        // Check output size limits
+        self.resource_manager
+            .check_output_size(output.stdout.len(), output.stderr.len())?;
 
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-                let exit_code = output.status.code();
-                let success = output.status.success();
+        let stdout = self
+            .ansi_mode
+            .apply(&String::from_utf8_lossy(&output.stdout));
+        let stderr = self
+            .ansi_mode
+            .apply(&String::from_utf8_lossy(&output.stderr));
+
+        match output.outcome {
+            RunOutcome::Completed(status) => {
+                let exit_code = status.code();
+                let success = status.success();
 
                 if !success {
                     warn!("Command failed with exit code {:?}: {}", exit_code, stderr);
@@ -258,29 +668,218 @@ impl TaskExecutor {
                     } else {
                         Some(format!("Command failed with exit code {exit_code:?}"))
                     },
+                    invocation,
+                    warnings,
+                    error_kind: None,
                 })
             }
-            Ok(Err(e)) => {
-                error!("Failed to execute command: {}", e);
-                Ok(ExecutionResult {
-                    success: false,
-                    exit_code: None,
-                    stdout: String::new(),
-                    stderr: String::new(),
-                    error: Some(format!("Failed to execute command: {e}")),
-                })
-            }
-            Err(_) => {
+            RunOutcome::TimedOut { prompt } => {
                 error!("Command timed out after {:?}", timeout_duration);
+                let mut error = format!("Command timed out after {timeout_duration:?}");
+                if let Some(prompt) = prompt {
+                    warnings.push(format!(
+                        "Recipe '{task_name}' stalled on what looks like an interactive prompt: \"{prompt}\""
+                    ));
+                    error.push_str(&format!(
+                        "; it was waiting for interactive input (prompt: \"{prompt}\"). \
+                         Use a non-interactive flag, configure a response with --prompt-response, \
+                         or start the server with --reject-prompts any to fail fast"
+                    ));
+                }
                 Ok(ExecutionResult {
                     success: false,
                     exit_code: None,
-                    stdout: String::new(),
-                    stderr: String::new(),
-                    error: Some(format!("Command timed out after {timeout_duration:?}")),
+                    stdout,
+                    stderr,
+                    error: Some(error),
+                    invocation,
+                    warnings,
+                    error_kind: Some(ErrorKind::Timeout),
                 })
             }
+            RunOutcome::PromptRejected(prompt) => Ok(ExecutionResult {
+                success: false,
+                exit_code: None,
+                stdout,
+                stderr,
+                error: Some(format!(
+                    "Recipe '{task_name}' is waiting for interactive input (prompt: \"{prompt}\"). \
+                     Recipes run without a terminal; use a non-interactive flag such as -y, \
+                     or configure a predefined response with --prompt-response"
+                )),
+                invocation,
+                warnings,
+                error_kind: Some(ErrorKind::Precondition),
+            }),
+            RunOutcome::InputFailed { prompt, error } => Ok(ExecutionResult {
+                success: false,
+                exit_code: None,
+                stdout,
+                stderr,
+                error: Some(format!(
+                    "Could not answer prompt \"{prompt}\" of recipe '{task_name}': \
+                     writing to its stdin failed: {error}"
+                )),
+                invocation,
+                warnings,
+                error_kind: Some(ErrorKind::Precondition),
+            }),
+            RunOutcome::Cancelled => Ok(ExecutionResult {
+                success: false,
+                exit_code: None,
+                stdout,
+                stderr,
+                error: Some(format!("Recipe '{task_name}' was cancelled")),
+                invocation,
+                warnings,
+                error_kind: Some(ErrorKind::Transient),
+            }),
+        }
+    }
+
+    /// Name of the named profile a call runs under
+    ///
+    /// A `profile` argument naming a named profile selects it and is
+    /// consumed; one naming a profile of the recipe's own is left for
+    /// [`Self::apply_profile`]. Without such an argument the context's
+    /// profile applies. Recipes with a `profile` parameter only get the
+    /// context's profile.
+    fn selected_profile(
+        &self,
+        task: &JustTask,
+        parameters: &mut HashMap<String, serde_json::Value>,
+        default: Option<String>,
+    ) -> Option<String> {
+        if task.parameters.iter().any(|p| p.name == PROFILE_ARGUMENT) {
+            return default;
         }
+        let selected = match parameters.get(PROFILE_ARGUMENT) {
+            None => return default,
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+        };
+        if self.profiles.get(&selected).is_none()
+            || task.profiles().iter().any(|p| p.name == selected)
+        {
+            return default;
+        }
+        parameters.remove(PROFILE_ARGUMENT);
+        Some(selected)
+    }
+
+    /// Merge the presets of the profile selected by the `profile` argument
+    ///
+    /// Arguments the caller supplied explicitly win over presets. The
+    /// `profile` argument itself is consumed unless the recipe has a
+    /// parameter of that name.
+    fn apply_profile(
+        &self,
+        task: &JustTask,
+        parameters: &HashMap<String, serde_json::Value>,
+    ) -> Result<HashMap<String, serde_json::Value>> {
+        let mut merged = parameters.clone();
+        if task.parameters.iter().any(|p| p.name == PROFILE_ARGUMENT) {
+            return Ok(merged);
+        }
+        let Some(selected) = merged.remove(PROFILE_ARGUMENT) else {
+            return Ok(merged);
+        };
+        let selected = match selected {
+            serde_json::Value::String(s) => s,
+            other => other.to_string(),
+        };
+
+        let profiles = task.profiles();
+        let profile = profiles
+            .iter()
+            .find(|p| p.name == selected)
+            .ok_or_else(|| {
+                let available: Vec<&str> = profiles
+                    .iter()
+                    .map(|p| p.name.as_str())
+                    .chain(self.profiles.for_recipe(&task.name).map(|(name, _)| name))
+                    .collect();
+                Error::InvalidParameter(format!(
+                    "Unknown profile '{selected}' for recipe '{}' (available: {})",
+                    task.name,
+                    if available.is_empty() {
+                        "none".to_string()
+                    } else {
+                        available.join(", ")
+                    }
+                ))
+            })?;
+
+        for (key, value) in &profile.arguments {
+            merged
+                .entry(key.clone())
+                .or_insert_with(|| serde_json::Value::String(value.clone()));
+        }
+        Ok(merged)
+    }
+
+    /// Collect `--set` overrides for template slots declared with `# [slot: name]`
+    ///
+    /// Only slots the caller supplied a value for are returned. Values are
+    /// read from the argument named like the slot's schema property, and
+    /// shell-escaped because recipes interpolate them into command lines.
+    fn slot_overrides(
+        &self,
+        task: &JustTask,
+        parameters: &HashMap<String, serde_json::Value>,
+    ) -> Vec<(String, String)> {
+        task.annotation_values(SLOT_ANNOTATION)
+            .into_iter()
+            .filter(|slot| !task.parameters.iter().any(|p| &p.name == slot))
+            .filter_map(|slot| {
+                let argument = self.security_validator.sanitize_parameter_name(&slot);
+                let value = match parameters.get(&argument)? {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                let sanitized = self.security_validator.sanitize_parameter(&value);
+                Some((slot, sanitized))
+            })
+            .collect()
+    }
+
+    /// Collect overrides for `var_<name>` arguments naming justfile variables
+    ///
+    /// Arguments for variables that are not assigned in the justfile are
+    /// ignored, since just rejects overrides of undefined variables, and so are
+    /// arguments the recipe declares as parameters itself. Values are
+    /// shell-escaped like slot values, since recipes interpolate variables into
+    /// command lines too.
+    fn variable_overrides(
+        &self,
+        content: &str,
+        parameters: &HashMap<String, serde_json::Value>,
+        task: Option<&JustTask>,
+    ) -> Vec<(String, String)> {
+        let variables = extract_variables(content);
+        let mut overrides: Vec<(String, String)> = parameters
+            .iter()
+            .filter(|(key, _)| {
+                task.is_none_or(|task| !task.parameters.iter().any(|p| &p.name == *key))
+            })
+            .filter_map(|(key, value)| {
+                let name = key.strip_prefix(VARIABLE_ARG_PREFIX)?;
+                if !variables.iter().any(|v| v.name == name) {
+                    warn!("Ignoring override for unknown justfile variable '{}'", name);
+                    return None;
+                }
+                let value = match value {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                Some((
+                    name.to_string(),
+                    self.security_validator.sanitize_parameter(&value),
+                ))
+            })
+            .collect();
+        overrides.sort();
+        overrides
     }
 
     pub async fn execute_command(
@@ -308,7 +907,7 @@ impl TaskExecutor {
         let timeout_duration = context
             .timeout
             .map(Duration::from_secs)
-            .unwrap_or(self.default_timeout);
+            .unwrap_or_else(|| self.default_timeout());
 
         // Execute with timeout
         match timeout(timeout_duration, cmd.output()).await {
@@ -332,6 +931,9 @@ impl TaskExecutor {
                     } else {
                         Some(format!("Command failed with exit code {exit_code:?}"))
                     },
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
                 })
             }
             Ok(Err(e)) => Ok(ExecutionResult {
@@ -340,6 +942,9 @@ impl TaskExecutor {
                 stdout: String::new(),
                 stderr: String::new(),
                 error: Some(format!("Failed to execute command: {e}")),
+                invocation: None,
+                warnings: Vec::new(),
+                error_kind: Some(ErrorKind::Transient),
             }),
             Err(_) => Ok(ExecutionResult {
                 success: false,
@@ -347,6 +952,9 @@ impl TaskExecutor {
                 stdout: String::new(),
                 stderr: String::new(),
                 error: Some(format!("Command timed out after {timeout_duration:?}")),
+                invocation: None,
+                warnings: Vec::new(),
+                error_kind: Some(ErrorKind::Timeout),
             }),
         }
     }
@@ -358,6 +966,14 @@ impl Default for TaskExecutor {
     }
 }
 
+/// Whether `just --help` output lists the `--jobs` option
+fn help_lists_jobs_flag(help: &str) -> bool {
+    help.split_whitespace().any(|word| {
+        let word = word.trim_end_matches(',');
+        word == "--jobs" || word.starts_with("--jobs=")
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -367,7 +983,7 @@ mod tests {
     #[test]
     fn test_executor_creation() {
         let executor = TaskExecutor::new();
-        assert_eq!(executor.default_timeout, Duration::from_secs(300));
+        assert_eq!(executor.default_timeout(), Duration::from_secs(300));
     }
 
     #[test]
@@ -397,6 +1013,19 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_project_key_uses_watch_names() {
+        let executor = TaskExecutor::new().with_project_names(&[
+            (PathBuf::from("/repo"), Some("repo".to_string())),
+            (PathBuf::from("/repo/web"), Some("web".to_string())),
+            (PathBuf::from("/other"), None),
+        ]);
+
+        assert_eq!(executor.project_key(Path::new("/repo/web/justfile")), "web");
+        assert_eq!(executor.project_key(Path::new("/repo/justfile")), "repo");
+        assert_eq!(executor.project_key(Path::new("/other/justfile")), "/other");
+    }
+
     #[tokio::test]
     async fn test_execute_simple_command() {
         let executor = TaskExecutor::new();
@@ -404,6 +1033,7 @@ mod tests {
             working_directory: None,
             environment: HashMap::new(),
             timeout: Some(5),
+            profile: None,
         };
 
         let result = executor
@@ -426,6 +1056,7 @@ mod tests {
             working_directory: None,
             environment: HashMap::new(),
             timeout: Some(1), // 1 second timeout
+            profile: None,
         };
 
         // Command that takes longer than timeout
@@ -451,6 +1082,7 @@ mod tests {
             working_directory: Some(temp_dir.path().to_string_lossy().to_string()),
             environment: HashMap::new(),
             timeout: None,
+            profile: None,
         };
 
         #[cfg(target_os = "windows")]
@@ -468,4 +1100,782 @@ mod tests {
         assert!(exec_result.success);
         assert!(exec_result.stdout.contains("test content"));
     }
+
+    #[tokio::test]
+    async fn test_execute_with_template_slot() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "extra_flags := \"\"\n\n# Greet someone\n# [slot: extra_flags]\ngreet name=\"World\":\n    echo \"Hello {{name}}\" {{extra_flags}}\n",
+        )
+        .unwrap();
+
+        let mut executor = TaskExecutor::new().with_security_config(SecurityConfig {
+            allowed_paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        });
+        let mut parameters = HashMap::new();
+        parameters.insert("name".to_string(), serde_json::json!("Agent"));
+        parameters.insert("extra_flags".to_string(), serde_json::json!("--loud"));
+
+        let result = executor
+            .execute(ExecutionRequest {
+                tool_name: format!("greet_{}", justfile_path.display()),
+                parameters,
+                context: ExecutionContext::default(),
+            })
+            .await
+            .unwrap();
+
+        assert!(result.success, "stderr: {}", result.stderr);
+        assert!(result.stdout.contains("Hello Agent --loud"));
+    }
+
+    #[tokio::test]
+    async fn test_no_cd_recipe_runs_in_invocation_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        fs::create_dir(&project).unwrap();
+        let justfile_path = project.join("justfile");
+        fs::write(
+            &justfile_path,
+            "[no-cd]\nwhere:\n    pwd\n\nhere:\n    pwd\n",
+        )
+        .unwrap();
+
+        let mut executor = TaskExecutor::new().with_security_config(SecurityConfig {
+            allowed_paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        });
+        let run = |task: &str| ExecutionRequest {
+            tool_name: format!("{task}_{}", justfile_path.display()),
+            parameters: HashMap::new(),
+            context: ExecutionContext {
+                working_directory: Some(temp_dir.path().to_string_lossy().to_string()),
+                ..Default::default()
+            },
+        };
+        let dir = |stdout: &str| PathBuf::from(stdout.trim()).canonicalize().unwrap();
+
+        let result = executor.execute(run("where")).await.unwrap();
+        assert!(result.success, "stderr: {}", result.stderr);
+        assert_eq!(dir(&result.stdout), temp_dir.path().canonicalize().unwrap());
+
+        // Other recipes still run in the justfile's directory
+        let result = executor.execute(run("here")).await.unwrap();
+        assert!(result.success, "stderr: {}", result.stderr);
+        assert_eq!(dir(&result.stdout), project.canonicalize().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_failed_execution_reports_environment_differences() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(&justfile_path, "fail:\n    exit 1\n\nok:\n    true\n").unwrap();
+
+        let mut executor = TaskExecutor::new()
+            .with_security_config(SecurityConfig {
+                allowed_paths: vec![temp_dir.path().to_path_buf()],
+                ..Default::default()
+            })
+            .with_shell_environment(environment::ShellEnvironment {
+                shell: "/bin/zsh".to_string(),
+                variables: HashMap::from([
+                    ("HOME".to_string(), "/home/me".to_string()),
+                    (
+                        "PATH".to_string(),
+                        std::env::var("PATH").unwrap_or_default(),
+                    ),
+                ]),
+            });
+        let run = |task: &str| ExecutionRequest {
+            tool_name: format!("{task}_{}", justfile_path.display()),
+            parameters: HashMap::new(),
+            context: ExecutionContext {
+                environment: HashMap::from([("HOME".to_string(), String::new())]),
+                ..Default::default()
+            },
+        };
+
+        let result = executor.execute(run("ok")).await.unwrap();
+        assert!(result.success);
+        assert!(result.warnings.is_empty());
+
+        let result = executor.execute(run("fail")).await.unwrap();
+        assert!(!result.success);
+        assert!(
+            result.warnings.iter().any(|w| w.contains("HOME")),
+            "warnings: {:?}",
+            result.warnings
+        );
+        assert_eq!(result.error_kind, Some(ErrorKind::Precondition));
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "# [profile: fast(fast=true, target=debug)]\ntest fast=\"false\" target=\"release\":\n    echo \"fast={{fast}} target={{target}}\"\n",
+        )
+        .unwrap();
+
+        let mut executor = TaskExecutor::new().with_security_config(SecurityConfig {
+            allowed_paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        });
+        let run = |parameters: HashMap<String, serde_json::Value>| ExecutionRequest {
+            tool_name: format!("test_{}", justfile_path.display()),
+            parameters,
+            context: ExecutionContext::default(),
+        };
+
+        let mut parameters = HashMap::new();
+        parameters.insert("profile".to_string(), serde_json::json!("fast"));
+        parameters.insert("target".to_string(), serde_json::json!("bench"));
+        let result = executor.execute(run(parameters)).await.unwrap();
+        assert!(result.success, "stderr: {}", result.stderr);
+        assert!(result.stdout.contains("fast=true target=bench"));
+
+        let result = executor.execute(run(HashMap::new())).await.unwrap();
+        assert!(result.stdout.contains("fast=false target=release"));
+
+        let mut parameters = HashMap::new();
+        parameters.insert("profile".to_string(), serde_json::json!("slow"));
+        let err = executor.execute(run(parameters)).await.unwrap_err();
+        assert!(err.to_string().contains("available: fast"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_named_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "deploy region=\"local\":\n    echo \"region={{region}} env=$DEPLOY_ENV\"\n\nclean:\n    echo cleaned\n",
+        )
+        .unwrap();
+
+        let profiles = ProfileSet::parse(
+            "staging:\n  env:\n    DEPLOY_ENV: staging\n  parameters:\n    region: eu-west-1\n  recipes: [deploy]\n",
+        )
+        .unwrap();
+        let mut executor = TaskExecutor::new()
+            .with_security_config(SecurityConfig {
+                allowed_paths: vec![temp_dir.path().to_path_buf()],
+                ..Default::default()
+            })
+            .with_profiles(profiles);
+        let run = |task: &str, parameters: HashMap<String, serde_json::Value>, profile| {
+            ExecutionRequest {
+                tool_name: format!("{task}_{}", justfile_path.display()),
+                parameters,
+                context: ExecutionContext {
+                    profile,
+                    ..Default::default()
+                },
+            }
+        };
+
+        let mut parameters = HashMap::new();
+        parameters.insert("profile".to_string(), serde_json::json!("staging"));
+        let result = executor
+            .execute(run("deploy", parameters, None))
+            .await
+            .unwrap();
+        assert!(result.success, "stderr: {}", result.stderr);
+        assert!(result.stdout.contains("region=eu-west-1 env=staging"));
+
+        // The session's profile applies without an argument; explicit arguments win
+        let mut parameters = HashMap::new();
+        parameters.insert("region".to_string(), serde_json::json!("us-east-1"));
+        let staging = Some("staging".to_string());
+        let result = executor
+            .execute(run("deploy", parameters, staging.clone()))
+            .await
+            .unwrap();
+        assert!(result.stdout.contains("region=us-east-1 env=staging"));
+
+        let err = executor
+            .execute(run("clean", HashMap::new(), staging))
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("not allowed under profile 'staging'"));
+
+        let mut parameters = HashMap::new();
+        parameters.insert("profile".to_string(), serde_json::json!("prod"));
+        let err = executor
+            .execute(run("deploy", parameters, None))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("available: staging"));
+    }
+
+    #[tokio::test]
+    async fn test_exclusive_recipe_holds_project_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "# [exclusive]\nmigrate:\n    test -f .just-mcp.lock && echo locked\n\ncheck:\n    test ! -f .just-mcp.lock && echo unlocked\n",
+        )
+        .unwrap();
+
+        let mut executor = TaskExecutor::new()
+            .with_security_config(SecurityConfig {
+                allowed_paths: vec![temp_dir.path().to_path_buf()],
+                ..Default::default()
+            })
+            .with_exclusive_lock(Some(LockConfig::default()));
+
+        for (task, expected) in [("migrate", "locked"), ("check", "unlocked")] {
+            let result = executor
+                .execute(ExecutionRequest {
+                    tool_name: format!("{task}_{}", justfile_path.display()),
+                    parameters: HashMap::new(),
+                    context: ExecutionContext::default(),
+                })
+                .await
+                .unwrap();
+            assert!(result.success, "{task}: {}", result.stderr);
+            assert_eq!(result.stdout.trim(), expected);
+        }
+        assert!(!temp_dir.path().join(".just-mcp.lock").exists());
+    }
+
+    #[tokio::test]
+    async fn test_exclusion_group_serializes_executions() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        // Each recipe fails if the other is running at the same time
+        let body = "    mkdir running\n    sleep 0.3\n    rmdir running\n";
+        fs::write(
+            &justfile_path,
+            format!("# [exclusive: db]\nmigrate:\n{body}\n# [exclusive: db]\nseed:\n{body}"),
+        )
+        .unwrap();
+
+        let executor = TaskExecutor::new().with_security_config(SecurityConfig {
+            allowed_paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        });
+        let run = |task: &str| {
+            let mut executor = executor.clone();
+            let request = ExecutionRequest {
+                tool_name: format!("{task}_{}", justfile_path.display()),
+                parameters: HashMap::new(),
+                context: ExecutionContext::default(),
+            };
+            async move { executor.execute(request).await.unwrap() }
+        };
+
+        let (migrate, seed) = tokio::join!(run("migrate"), run("seed"));
+        assert!(migrate.success, "migrate: {}", migrate.stderr);
+        assert!(seed.success, "seed: {}", seed.stderr);
+    }
+
+    #[test]
+    fn test_slot_overrides_are_escaped() {
+        let executor = TaskExecutor::new();
+        let task = JustTask {
+            name: "build".to_string(),
+            body: String::new(),
+            parameters: vec![],
+            dependencies: vec![],
+            comments: vec![],
+            line_number: 1,
+            group: None,
+            is_private: false,
+            confirm_message: None,
+            doc: None,
+            attributes: vec![],
+            annotations: vec![crate::parser::RecipeAnnotation::new(
+                SLOT_ANNOTATION,
+                Some("flags, unused, max$jobs".to_string()),
+            )],
+            long_description: None,
+        };
+
+        let mut parameters = HashMap::new();
+        parameters.insert("flags".to_string(), serde_json::json!("a; rm -rf /"));
+        // Slots are read from the argument named like their schema property
+        parameters.insert("maxjobs".to_string(), serde_json::json!(4));
+
+        let mut overrides = executor.slot_overrides(&task, &parameters);
+        overrides.sort();
+        assert_eq!(
+            overrides,
+            [
+                ("flags".to_string(), "'a; rm -rf /'".to_string()),
+                ("max$jobs".to_string(), "4".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_variable_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "version := \"1.0.0\"\n\nrelease:\n    echo \"version={{version}}\"\n",
+        )
+        .unwrap();
+
+        let mut executor = TaskExecutor::new().with_security_config(SecurityConfig {
+            allowed_paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        });
+        let mut parameters = HashMap::new();
+        parameters.insert("var_version".to_string(), serde_json::json!("2.3.4"));
+        parameters.insert("var_missing".to_string(), serde_json::json!("ignored"));
+
+        let result = executor
+            .execute(ExecutionRequest {
+                tool_name: format!("release_{}", justfile_path.display()),
+                parameters,
+                context: ExecutionContext::default(),
+            })
+            .await
+            .unwrap();
+
+        assert!(result.success, "stderr: {}", result.stderr);
+        assert!(result.stdout.contains("version=2.3.4"));
+    }
+
+    #[tokio::test]
+    async fn test_variable_override_values_cannot_inject_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "message := \"hi\"\n\nsay:\n    echo {{message}}\n",
+        )
+        .unwrap();
+
+        // Without strict mode nothing rejects the value; escaping alone must
+        // keep it a single shell word
+        let mut executor = TaskExecutor::new().with_security_config(SecurityConfig {
+            allowed_paths: vec![temp_dir.path().to_path_buf()],
+            strict_mode: false,
+            ..Default::default()
+        });
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "var_message".to_string(),
+            serde_json::json!("x\ntouch pwned"),
+        );
+
+        let result = executor
+            .execute(ExecutionRequest {
+                tool_name: format!("say_{}", justfile_path.display()),
+                parameters,
+                context: ExecutionContext::default(),
+            })
+            .await
+            .unwrap();
+
+        assert!(result.success, "stderr: {}", result.stderr);
+        assert_eq!(result.stdout.trim(), "x\ntouch pwned");
+        assert!(!temp_dir.path().join("pwned").exists());
+    }
+
+    #[tokio::test]
+    async fn test_recipe_parameter_named_like_variable_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "x := \"default\"\n\nshow var_x:\n    echo \"param={{var_x}} x={{x}}\"\n",
+        )
+        .unwrap();
+
+        let mut executor = TaskExecutor::new().with_security_config(SecurityConfig {
+            allowed_paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        });
+        let mut parameters = HashMap::new();
+        parameters.insert("var_x".to_string(), serde_json::json!("given"));
+
+        let result = executor
+            .execute(ExecutionRequest {
+                tool_name: format!("show_{}", justfile_path.display()),
+                parameters,
+                context: ExecutionContext::default(),
+            })
+            .await
+            .unwrap();
+
+        assert!(result.success, "stderr: {}", result.stderr);
+        assert!(
+            result.stdout.contains("param=given x=default"),
+            "{}",
+            result.stdout
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_invalidated_by_imported_file_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        let imported_path = temp_dir.path().join("extra.just");
+        fs::write(
+            &justfile_path,
+            "import 'extra.just'
+",
+        )
+        .unwrap();
+        fs::write(
+            &imported_path,
+            "one:
+    echo one
+",
+        )
+        .unwrap();
+
+        let mut executor = TaskExecutor::new().with_security_config(SecurityConfig {
+            allowed_paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        });
+        let request = |task: &str| ExecutionRequest {
+            tool_name: format!("{task}_{}", justfile_path.display()),
+            parameters: HashMap::new(),
+            context: ExecutionContext::default(),
+        };
+
+        let result = executor.execute(request("one")).await.unwrap();
+        assert!(result.success, "stderr: {}", result.stderr);
+
+        // Only the imported file changes; the new recipe must still be found
+        fs::write(&imported_path, "one:\n    echo one\n\ntwo:\n    echo two\n").unwrap();
+        let result = executor.execute(request("two")).await.unwrap();
+        assert!(result.success, "stderr: {}", result.stderr);
+        assert!(result.stdout.contains("two"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_interactive_prompt() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "install:
+    @printf 'Do you want to continue? [Y/n] '
+    @sleep 10
+",
+        )
+        .unwrap();
+
+        let mut executor = TaskExecutor::new()
+            .with_security_config(SecurityConfig {
+                allowed_paths: vec![temp_dir.path().to_path_buf()],
+                ..Default::default()
+            })
+            // Default policy: explicit prompts are rejected
+            .with_interactive_config(InteractiveConfig {
+                stall_timeout: Duration::from_millis(300),
+                ..Default::default()
+            });
+
+        let result = executor
+            .execute(ExecutionRequest {
+                tool_name: format!("install_{}", justfile_path.display()),
+                parameters: HashMap::new(),
+                context: ExecutionContext::default(),
+            })
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        let error = result.error.unwrap();
+        assert!(error.contains("interactive input"), "error: {error}");
+        assert!(error.contains("[Y/n]"));
+    }
+
+    #[tokio::test]
+    async fn test_running_execution_can_be_cancelled() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(&justfile_path, "wait:\n    @sleep 30\n").unwrap();
+
+        let mut executor = TaskExecutor::new().with_security_config(SecurityConfig {
+            allowed_paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        });
+        let watchdog = executor.watchdog().clone();
+        let request = ExecutionRequest {
+            tool_name: format!("wait_{}", justfile_path.display()),
+            parameters: HashMap::new(),
+            context: ExecutionContext::default(),
+        };
+        let run = tokio::spawn(async move { executor.execute(request).await });
+
+        let execution = loop {
+            match watchdog.running().pop() {
+                Some(execution) if execution.pid.is_some() => break execution,
+                _ => tokio::time::sleep(Duration::from_millis(20)).await,
+            }
+        };
+        assert_eq!(execution.recipe, "wait");
+        watchdog.cancel(execution.id).unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(10), run)
+            .await
+            .expect("cancelled execution should stop")
+            .unwrap()
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("Recipe 'wait' was cancelled"));
+        assert!(watchdog.running().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tty_recipe_runs_in_terminal() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "# [tty]
+check:
+    @if [ -t 1 ]; then echo terminal; else echo piped; fi
+
+plain:
+    @if [ -t 1 ]; then echo terminal; else echo piped; fi
+",
+        )
+        .unwrap();
+
+        let executor = TaskExecutor::new().with_security_config(SecurityConfig {
+            allowed_paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        });
+        let run = |executor: &TaskExecutor, task: &str| {
+            let request = ExecutionRequest {
+                tool_name: format!("{task}_{}", justfile_path.display()),
+                parameters: HashMap::new(),
+                context: ExecutionContext::default(),
+            };
+            let mut executor = executor.clone();
+            async move { executor.execute(request).await.unwrap() }
+        };
+
+        let plain = run(&executor, "plain").await;
+        assert_eq!(plain.stdout, "piped\n");
+        assert!(plain.warnings.is_empty());
+
+        let check = run(&executor, "check").await;
+        assert!(check.success);
+        if cfg!(feature = "pty") {
+            assert_eq!(check.stdout, "terminal\n");
+        } else {
+            assert_eq!(check.stdout, "piped\n");
+            assert!(check.warnings[0].contains("needs a terminal"));
+        }
+
+        let executor = executor.with_interactive_config(InteractiveConfig {
+            pty: PtyMode::Never,
+            ..Default::default()
+        });
+        let check = run(&executor, "check").await;
+        assert_eq!(check.stdout, "piped\n");
+    }
+
+    #[tokio::test]
+    async fn test_ansi_output_modes() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "lint:
+    @printf '\\033[1m\\033[33mwarning\\033[0m: unused import\\n'
+    @printf '\\033[31mfailed\\033[0m\\n' >&2
+",
+        )
+        .unwrap();
+
+        let run = |mode: AnsiMode| {
+            let mut executor = TaskExecutor::new()
+                .with_security_config(SecurityConfig {
+                    allowed_paths: vec![temp_dir.path().to_path_buf()],
+                    ..Default::default()
+                })
+                .with_ansi_mode(mode);
+            let request = ExecutionRequest {
+                tool_name: format!("lint_{}", justfile_path.display()),
+                parameters: HashMap::new(),
+                context: ExecutionContext::default(),
+            };
+            async move { executor.execute(request).await.unwrap() }
+        };
+
+        let stripped = run(AnsiMode::Strip).await;
+        assert_eq!(stripped.stdout, "warning: unused import\n");
+        assert_eq!(stripped.stderr, "failed\n");
+
+        let preserved = run(AnsiMode::Preserve).await;
+        assert_eq!(
+            preserved.stdout,
+            "\x1b[1m\x1b[33mwarning\x1b[0m: unused import\n"
+        );
+
+        let markdown = run(AnsiMode::Markdown).await;
+        assert_eq!(markdown.stdout, "**warning**: unused import\n");
+        assert_eq!(markdown.stderr, "failed\n");
+    }
+
+    #[tokio::test]
+    async fn test_command_echo_redacts_secrets() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "deploy env api_token:
+    @echo deploying {{env}}
+",
+        )
+        .unwrap();
+
+        let mut executor = TaskExecutor::new()
+            .with_security_config(SecurityConfig {
+                allowed_paths: vec![temp_dir.path().to_path_buf()],
+                ..Default::default()
+            })
+            .with_command_echo(true);
+
+        let mut parameters = HashMap::new();
+        parameters.insert("env".to_string(), serde_json::json!("staging"));
+        parameters.insert("api_token".to_string(), serde_json::json!("s3cr3t"));
+        let result = executor
+            .execute(ExecutionRequest {
+                tool_name: format!("deploy_{}", justfile_path.display()),
+                parameters,
+                context: ExecutionContext {
+                    environment: HashMap::from([
+                        ("DEPLOY_PASSWORD".to_string(), "hunter2".to_string()),
+                        ("REGION".to_string(), "eu-west-1".to_string()),
+                    ]),
+                    ..Default::default()
+                },
+            })
+            .await
+            .unwrap();
+
+        assert!(result.success, "stderr: {}", result.stderr);
+        let invocation = result.invocation.unwrap();
+        assert_eq!(invocation.program, "just");
+        assert_eq!(
+            invocation.args,
+            vec!["--justfile", "justfile", "deploy", "staging", "[REDACTED]"]
+        );
+        assert_eq!(invocation.env["DEPLOY_PASSWORD"], "[REDACTED]");
+        assert_eq!(invocation.env["REGION"], "eu-west-1");
+
+        let line = invocation.command_line();
+        assert!(line.starts_with("cd "), "line: {line}");
+        assert!(line.ends_with("DEPLOY_PASSWORD='[REDACTED]' REGION=eu-west-1 just --justfile justfile deploy staging '[REDACTED]'"));
+        assert!(!line.contains("s3cr3t") && !line.contains("hunter2"));
+
+        // Without command echo the invocation is only logged
+        let mut executor = executor.with_command_echo(false);
+        let result = executor
+            .execute(ExecutionRequest {
+                tool_name: format!("deploy_{}", justfile_path.display()),
+                parameters: HashMap::from([
+                    ("env".to_string(), serde_json::json!("dev")),
+                    ("api_token".to_string(), serde_json::json!("x")),
+                ]),
+                context: ExecutionContext::default(),
+            })
+            .await
+            .unwrap();
+        assert!(result.invocation.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_jobs_are_passed_when_supported() {
+        assert!(help_lists_jobs_flag(
+            "Options:\n  -j, --jobs <JOBS>  Run dependencies in parallel"
+        ));
+        assert!(!help_lists_jobs_flag("Options:\n  --justfile <JUSTFILE>"));
+
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "# [jobs: 64]\nbuild:\n    @echo built\n\ntest:\n    @echo tested\n",
+        )
+        .unwrap();
+        let executor = TaskExecutor::new()
+            .with_security_config(SecurityConfig {
+                allowed_paths: vec![temp_dir.path().to_path_buf()],
+                ..Default::default()
+            })
+            .with_resource_limits(ResourceLimits {
+                default_jobs: Some(2),
+                max_jobs: 4,
+                ..Default::default()
+            })
+            .with_command_echo(true);
+        let run = |task: &str| ExecutionRequest {
+            tool_name: format!("{task}_{}", justfile_path.display()),
+            parameters: HashMap::new(),
+            context: ExecutionContext::default(),
+        };
+
+        // A just without --jobs runs the recipe anyway, with a warning
+        let mut unsupported = executor.clone();
+        unsupported.jobs_flag = Arc::new(tokio::sync::OnceCell::new_with(Some(false)));
+        let result = unsupported.execute(run("test")).await.unwrap();
+        assert!(result.success, "stderr: {}", result.stderr);
+        assert!(!result
+            .invocation
+            .unwrap()
+            .args
+            .contains(&"--jobs".to_string()));
+        assert!(result.warnings[0].contains("does not support --jobs"));
+
+        // The recipe's annotation wins over the default, capped by max_jobs
+        let mut supported = executor.clone();
+        supported.jobs_flag = Arc::new(tokio::sync::OnceCell::new_with(Some(true)));
+        let result = supported.execute(run("build")).await.unwrap();
+        assert_eq!(
+            result.invocation.unwrap().args,
+            vec!["--justfile", "justfile", "--jobs", "4", "build"]
+        );
+        assert!(result.warnings[0].contains("asked for 64 parallel jobs"));
+        let result = supported.execute(run("test")).await.unwrap();
+        assert_eq!(
+            result.invocation.unwrap().args,
+            vec!["--justfile", "justfile", "--jobs", "2", "test"]
+        );
+        assert!(result.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_execute_capitalized_justfile() {
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("Justfile");
+        fs::write(&justfile_path, "hello:\n    @echo hello from Justfile\n").unwrap();
+
+        let mut executor = TaskExecutor::new().with_security_config(SecurityConfig {
+            allowed_paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        });
+
+        let result = executor
+            .execute(ExecutionRequest {
+                tool_name: format!("hello_{}", justfile_path.display()),
+                parameters: HashMap::new(),
+                context: ExecutionContext::default(),
+            })
+            .await
+            .unwrap();
+
+        assert!(result.success, "stderr: {}", result.stderr);
+        assert_eq!(result.stdout.trim(), "hello from Justfile");
+    }
 }
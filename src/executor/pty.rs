@@ -0,0 +1,233 @@
+//! Recipe execution in a pseudo-terminal
+//!
+//! Some tools refuse to run without a terminal (`docker run -t`, full-screen
+//! installers) or change their output when piped. Recipes annotated with
+//! `# [tty]`, or every recipe with `--pty always`, are run with a
+//! pseudo-terminal as stdin, stdout and stderr instead of pipes. The two
+//! output streams are merged, as they would be on screen, and are reported
+//! as stdout. Prompts are detected and answered as for piped runs.
+
+use super::ansi::normalize_newlines;
+use super::interactive::{
+    monitor, InteractiveConfig, MonitoredOutput, PromptInput, RunOutcome, Stream,
+};
+use super::watchdog::WatchedExecution;
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, PtySize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::process::ExitStatus;
+use tokio::sync::mpsc;
+use tokio::time::Duration;
+
+/// Terminal size reported to recipes; wide enough that progress output is
+/// rarely wrapped
+const PTY_SIZE: PtySize = PtySize {
+    rows: 50,
+    cols: 200,
+    pixel_width: 0,
+    pixel_height: 0,
+};
+
+/// Kills the child if the execution is dropped before it exits
+struct KillOnDrop(Option<Box<dyn ChildKiller + Send + Sync>>);
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        if let Some(killer) = self.0.as_mut() {
+            let _ = killer.kill();
+        }
+    }
+}
+
+/// Run `program` in a pseudo-terminal, watching its output for prompts
+pub async fn run_in_pty(
+    program: &str,
+    args: &[String],
+    working_dir: Option<&str>,
+    environment: &HashMap<String, String>,
+    limit: Duration,
+    config: &InteractiveConfig,
+    watch: Option<&WatchedExecution>,
+) -> std::io::Result<MonitoredOutput> {
+    let pair = native_pty_system().openpty(PTY_SIZE).map_err(pty_error)?;
+
+    let mut cmd = CommandBuilder::new(program);
+    cmd.args(args);
+    if let Some(dir) = working_dir {
+        cmd.cwd(dir);
+    } else {
+        cmd.cwd(std::env::current_dir()?);
+    }
+    if std::env::var_os("TERM").is_none() {
+        cmd.env("TERM", "xterm-256color");
+    }
+    for (key, value) in environment {
+        cmd.env(key, value);
+    }
+
+    let mut child = pair.slave.spawn_command(cmd).map_err(pty_error)?;
+    if let Some(watch) = watch {
+        watch.set_pid(child.process_id());
+    }
+    // The reader only sees end of file once every handle on the terminal
+    // is closed, including ours
+    drop(pair.slave);
+    let mut guard = KillOnDrop(Some(child.clone_killer()));
+
+    let mut reader = pair.master.try_clone_reader().map_err(pty_error)?;
+    let (tx, rx) = mpsc::unbounded_channel();
+    std::thread::spawn(move || {
+        let mut buf = [0u8; 8192];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if tx.send((Stream::Stdout, buf[..n].to_vec())).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    let input = if config.responses.is_empty() {
+        None
+    } else {
+        let mut writer = pair.master.take_writer().map_err(pty_error)?;
+        let (input, mut responses, failed) = PromptInput::channel();
+        std::thread::spawn(move || {
+            while let Some(response) = responses.blocking_recv() {
+                if let Err(e) = writer
+                    .write_all(response.as_bytes())
+                    .and_then(|()| writer.flush())
+                {
+                    let _ = failed.send(e);
+                    break;
+                }
+            }
+        });
+        Some(input)
+    };
+
+    let mut wait = tokio::task::spawn_blocking(move || child.wait());
+    let exit = async {
+        match (&mut wait).await {
+            Ok(status) => status.map(exit_status),
+            Err(e) => Err(std::io::Error::other(e)),
+        }
+    };
+    let mut output = monitor(rx, input, exit, limit, config, watch).await?;
+
+    if !matches!(output.outcome, RunOutcome::Completed(_)) {
+        if let (RunOutcome::Cancelled, Some(watch)) = (&output.outcome, watch) {
+            watch.kill_descendants();
+        }
+        if let Some(mut killer) = guard.0.take() {
+            let _ = killer.kill();
+        }
+        let _ = wait.await;
+    }
+    guard.0 = None;
+    drop(pair.master);
+
+    output.stdout = normalize_newlines(&String::from_utf8_lossy(&output.stdout)).into_bytes();
+    Ok(output)
+}
+
+fn pty_error(e: anyhow::Error) -> std::io::Error {
+    std::io::Error::other(format!("Failed to allocate a pseudo-terminal: {e}"))
+}
+
+/// Convert a pseudo-terminal exit status
+///
+/// Processes killed by a signal are reported without an exit code.
+fn exit_status(status: portable_pty::ExitStatus) -> ExitStatus {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        match status.signal() {
+            // Wait status of a process killed by SIGKILL
+            Some(_) => ExitStatus::from_raw(9),
+            None => ExitStatus::from_raw((status.exit_code() as i32 & 0xff) << 8),
+        }
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::ExitStatusExt;
+        ExitStatus::from_raw(status.exit_code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::interactive::PromptResponse;
+
+    #[tokio::test]
+    async fn test_recipe_sees_a_terminal() {
+        let args = vec![
+            "-c".to_string(),
+            "if [ -t 1 ]; then printf '\\033[32mtty\\033[0m\\n'; else echo pipe; fi; echo err >&2; exit 3"
+                .to_string(),
+        ];
+        let output = run_in_pty(
+            "sh",
+            &args,
+            None,
+            &HashMap::new(),
+            Duration::from_secs(30),
+            &InteractiveConfig::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            matches!(output.outcome, RunOutcome::Completed(status) if status.code() == Some(3))
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "\x1b[32mtty\x1b[0m\nerr\n"
+        );
+        assert!(output.stderr.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pty_prompts_and_timeout() {
+        let config = InteractiveConfig {
+            stall_timeout: Duration::from_millis(200),
+            responses: vec![PromptResponse::new(r"Continue\?", "y").unwrap()],
+            ..Default::default()
+        };
+        let args = vec![
+            "-c".to_string(),
+            "printf 'Continue? [y/N] '; read answer; echo \"answer=$answer\"".to_string(),
+        ];
+        let output = run_in_pty(
+            "sh",
+            &args,
+            None,
+            &HashMap::new(),
+            Duration::from_secs(30),
+            &config,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(output.outcome, RunOutcome::Completed(status) if status.success()));
+        assert!(String::from_utf8_lossy(&output.stdout).contains("answer=y"));
+
+        let output = run_in_pty(
+            "sleep",
+            &["10".to_string()],
+            None,
+            &HashMap::new(),
+            Duration::from_millis(300),
+            &InteractiveConfig::default(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(output.outcome, RunOutcome::TimedOut { .. }));
+    }
+}
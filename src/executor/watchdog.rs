@@ -0,0 +1,519 @@
+//! Watchdog for running recipes
+//!
+//! A recipe stuck on a lock, a network call without a timeout or a child
+//! waiting for input it will never get holds its execution slot until the
+//! execution timeout, which is often many minutes. The watchdog keeps a
+//! record of every running execution and samples its process tree (the
+//! `just` process and everything it started) for CPU time and resident
+//! memory. An execution whose processes have used no CPU and that has
+//! printed nothing for [`WatchdogConfig::hang_timeout`] is flagged as likely
+//! hung. `_admin_running_executions` lists executions with these samples and
+//! `_admin_cancel_execution` stops one without waiting for the timeout.
+//!
+//! Process sampling reads `/proc` and is only available on Linux; elsewhere
+//! executions are listed without CPU and memory figures and are never
+//! flagged.
+
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// How long an execution may go without CPU use or output before it is
+/// flagged as likely hung
+pub const DEFAULT_HANG_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How often running process trees are sampled
+pub const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Clock ticks per second of the CPU times in `/proc/<pid>/stat`
+#[cfg(target_os = "linux")]
+const CLOCK_TICKS_PER_SECOND: u64 = 100;
+
+/// Sampling and hang detection settings
+#[derive(Debug, Clone)]
+pub struct WatchdogConfig {
+    pub sample_interval: Duration,
+    /// Idle time after which an execution is flagged; zero disables flagging
+    pub hang_timeout: Duration,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            sample_interval: DEFAULT_SAMPLE_INTERVAL,
+            hang_timeout: DEFAULT_HANG_TIMEOUT,
+        }
+    }
+}
+
+/// A running execution as reported by `_admin_running_executions`
+#[derive(Debug, Clone, Serialize)]
+pub struct RunningExecution {
+    /// Identifier to pass to `_admin_cancel_execution`
+    pub id: u64,
+    pub recipe: String,
+    /// Process id of `just`, once started
+    pub pid: Option<u32>,
+    pub started_at: DateTime<Utc>,
+    pub runtime_ms: u64,
+    /// CPU time used by the process tree, at the last sample
+    pub cpu_ms: Option<u64>,
+    /// Resident memory of the process tree, at the last sample
+    pub rss_kb: Option<u64>,
+    /// Time since the execution last printed anything
+    pub idle_output_ms: u64,
+    /// Time since the process tree last used CPU
+    pub idle_cpu_ms: Option<u64>,
+    /// No CPU use and no output for the hang timeout
+    pub likely_hung: bool,
+}
+
+/// Bookkeeping for one running execution
+struct Tracked {
+    recipe: String,
+    pid: Option<u32>,
+    started_at: DateTime<Utc>,
+    started: Instant,
+    last_output: Instant,
+    cpu_ticks: Option<u64>,
+    last_cpu_change: Instant,
+    rss_kb: Option<u64>,
+    hung: bool,
+    cancel: Arc<Notify>,
+}
+
+/// Registry of running executions, shared by executor clones
+#[derive(Default)]
+pub struct ExecutionWatchdog {
+    config: WatchdogConfig,
+    next_id: AtomicU64,
+    executions: Mutex<BTreeMap<u64, Tracked>>,
+}
+
+impl ExecutionWatchdog {
+    pub fn new(config: WatchdogConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    pub fn config(&self) -> &WatchdogConfig {
+        &self.config
+    }
+
+    /// Record an execution of `recipe`, until the returned handle is dropped
+    pub fn start(self: &Arc<Self>, recipe: &str) -> WatchedExecution {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let now = Instant::now();
+        let cancel = Arc::new(Notify::new());
+        self.executions.lock().unwrap().insert(
+            id,
+            Tracked {
+                recipe: recipe.to_string(),
+                pid: None,
+                started_at: Utc::now(),
+                started: now,
+                last_output: now,
+                cpu_ticks: None,
+                last_cpu_change: now,
+                rss_kb: None,
+                hung: false,
+                cancel: cancel.clone(),
+            },
+        );
+        WatchedExecution {
+            id,
+            watchdog: self.clone(),
+            cancel,
+            sampler: Mutex::new(None),
+        }
+    }
+
+    /// Running executions, oldest first
+    pub fn running(&self) -> Vec<RunningExecution> {
+        let now = Instant::now();
+        self.executions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, tracked)| report(*id, tracked, now))
+            .collect()
+    }
+
+    /// Stop a running execution
+    ///
+    /// Its process tree is killed and the tool call returns an error saying
+    /// it was cancelled.
+    pub fn cancel(&self, id: u64) -> Result<RunningExecution> {
+        let executions = self.executions.lock().unwrap();
+        let tracked = executions
+            .get(&id)
+            .ok_or_else(|| Error::InvalidParameter(format!("No running execution with id {id}")))?;
+        warn!("Cancelling execution {} of recipe '{}'", id, tracked.recipe);
+        tracked.cancel.notify_one();
+        Ok(report(id, tracked, Instant::now()))
+    }
+
+    /// Sample an execution's process tree and update its hang flag
+    fn sample(&self, id: u64) {
+        let Some(pid) = self
+            .executions
+            .lock()
+            .unwrap()
+            .get(&id)
+            .and_then(|tracked| tracked.pid)
+        else {
+            return;
+        };
+        let usage = process_tree_usage(pid);
+
+        let mut executions = self.executions.lock().unwrap();
+        let Some(tracked) = executions.get_mut(&id) else {
+            return;
+        };
+        let now = Instant::now();
+        if let Some(usage) = usage {
+            if tracked.cpu_ticks != Some(usage.cpu_ticks) {
+                tracked.cpu_ticks = Some(usage.cpu_ticks);
+                tracked.last_cpu_change = now;
+            }
+            tracked.rss_kb = Some(usage.rss_kb);
+        }
+
+        let hang_timeout = self.config.hang_timeout;
+        let hung = !hang_timeout.is_zero()
+            && tracked.cpu_ticks.is_some()
+            && now.duration_since(tracked.last_cpu_change) >= hang_timeout
+            && now.duration_since(tracked.last_output) >= hang_timeout;
+        if hung && !tracked.hung {
+            warn!(
+                "Recipe '{}' (execution {}) has used no CPU and printed nothing for {:?}; \
+                 it is likely hung",
+                tracked.recipe, id, hang_timeout
+            );
+        }
+        tracked.hung = hung;
+    }
+
+    fn update(&self, id: u64, f: impl FnOnce(&mut Tracked)) {
+        if let Some(tracked) = self.executions.lock().unwrap().get_mut(&id) {
+            f(tracked);
+        }
+    }
+}
+
+fn report(id: u64, tracked: &Tracked, now: Instant) -> RunningExecution {
+    let millis = |since: Instant| now.duration_since(since).as_millis() as u64;
+    RunningExecution {
+        id,
+        recipe: tracked.recipe.clone(),
+        pid: tracked.pid,
+        started_at: tracked.started_at,
+        runtime_ms: millis(tracked.started),
+        cpu_ms: tracked.cpu_ticks.map(ticks_to_ms),
+        rss_kb: tracked.rss_kb,
+        idle_output_ms: millis(tracked.last_output),
+        idle_cpu_ms: tracked.cpu_ticks.map(|_| millis(tracked.last_cpu_change)),
+        likely_hung: tracked.hung,
+    }
+}
+
+/// A running execution's entry in the watchdog, removed when dropped
+pub struct WatchedExecution {
+    id: u64,
+    watchdog: Arc<ExecutionWatchdog>,
+    cancel: Arc<Notify>,
+    sampler: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl WatchedExecution {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Record the process started for the execution and begin sampling it
+    pub fn set_pid(&self, pid: Option<u32>) {
+        self.watchdog.update(self.id, |tracked| tracked.pid = pid);
+        if pid.is_none() {
+            return;
+        }
+        let watchdog = self.watchdog.clone();
+        let id = self.id;
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(watchdog.config.sample_interval);
+            loop {
+                interval.tick().await;
+                watchdog.sample(id);
+            }
+        });
+        if let Some(previous) = self.sampler.lock().unwrap().replace(handle) {
+            previous.abort();
+        }
+    }
+
+    /// Note that the execution printed something
+    pub fn record_output(&self) {
+        self.watchdog
+            .update(self.id, |tracked| tracked.last_output = Instant::now());
+    }
+
+    /// Resolves once the execution is cancelled
+    pub async fn cancelled(&self) {
+        self.cancel.notified().await
+    }
+
+    /// Kill every process the execution's process started
+    ///
+    /// Called before the process itself is killed, while its descendants can
+    /// still be found by their parent.
+    pub fn kill_descendants(&self) {
+        let pid = self
+            .watchdog
+            .executions
+            .lock()
+            .unwrap()
+            .get(&self.id)
+            .and_then(|tracked| tracked.pid);
+        if let Some(pid) = pid {
+            kill_descendants(pid);
+        }
+    }
+}
+
+impl Drop for WatchedExecution {
+    fn drop(&mut self) {
+        if let Some(sampler) = self.sampler.lock().unwrap().take() {
+            sampler.abort();
+        }
+        self.watchdog.executions.lock().unwrap().remove(&self.id);
+    }
+}
+
+/// Resources used by a process and its descendants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ProcessUsage {
+    cpu_ticks: u64,
+    rss_kb: u64,
+}
+
+#[cfg(target_os = "linux")]
+fn ticks_to_ms(ticks: u64) -> u64 {
+    ticks * 1000 / CLOCK_TICKS_PER_SECOND
+}
+
+#[cfg(not(target_os = "linux"))]
+fn ticks_to_ms(ticks: u64) -> u64 {
+    ticks
+}
+
+/// Fields of `/proc/<pid>/stat` the watchdog uses
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ProcStat {
+    ppid: u32,
+    /// User and system CPU time in clock ticks
+    cpu_ticks: u64,
+}
+
+/// Parse `/proc/<pid>/stat`; the command name may contain spaces and
+/// parentheses, so fields are counted from the last `)`
+#[cfg(target_os = "linux")]
+fn parse_stat(stat: &str) -> Option<ProcStat> {
+    let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+    let field = |i: usize| fields.get(i)?.parse::<u64>().ok();
+    Some(ProcStat {
+        ppid: field(1)? as u32,
+        cpu_ticks: field(11)? + field(12)?,
+    })
+}
+
+/// Resident memory from `/proc/<pid>/status`, in KiB
+#[cfg(target_os = "linux")]
+fn parse_rss_kb(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// `root` and all its descendants, with their stat fields
+#[cfg(target_os = "linux")]
+fn process_tree(root: u32) -> Option<Vec<(u32, ProcStat)>> {
+    let stats: Vec<(u32, ProcStat)> = std::fs::read_dir("/proc")
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_str()?.parse().ok()?;
+            let stat = std::fs::read_to_string(entry.path().join("stat")).ok()?;
+            Some((pid, parse_stat(&stat)?))
+        })
+        .collect();
+    let mut tree: Vec<(u32, ProcStat)> = stats
+        .iter()
+        .filter(|(pid, _)| *pid == root)
+        .copied()
+        .collect();
+    let mut i = 0;
+    while i < tree.len() {
+        let parent = tree[i].0;
+        tree.extend(stats.iter().filter(|(_, stat)| stat.ppid == parent));
+        i += 1;
+    }
+    (!tree.is_empty()).then_some(tree)
+}
+
+/// CPU time and memory of `root` and all its descendants
+#[cfg(target_os = "linux")]
+fn process_tree_usage(root: u32) -> Option<ProcessUsage> {
+    let mut usage = ProcessUsage {
+        cpu_ticks: 0,
+        rss_kb: 0,
+    };
+    for (pid, stat) in process_tree(root)? {
+        usage.cpu_ticks += stat.cpu_ticks;
+        usage.rss_kb += std::fs::read_to_string(format!("/proc/{pid}/status"))
+            .ok()
+            .and_then(|status| parse_rss_kb(&status))
+            .unwrap_or(0);
+    }
+    Some(usage)
+}
+
+#[cfg(target_os = "linux")]
+fn kill_descendants(root: u32) {
+    let descendants: Vec<String> = process_tree(root)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(pid, _)| *pid != root)
+        .map(|(pid, _)| pid.to_string())
+        .collect();
+    if descendants.is_empty() {
+        return;
+    }
+    if let Err(e) = std::process::Command::new("kill")
+        .arg("-KILL")
+        .args(&descendants)
+        .stderr(std::process::Stdio::null())
+        .status()
+    {
+        warn!("Failed to kill processes {:?}: {}", descendants, e);
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn kill_descendants(_root: u32) {}
+
+#[cfg(not(target_os = "linux"))]
+fn process_tree_usage(_root: u32) -> Option<ProcessUsage> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_proc_files() {
+        let stat = "4242 (sh (x) y) S 4100 4242 4100 0 -1 4194560 120 0 0 0 7 3 0 0 20 0 1 0 \
+                    1234 2449408 221 18446744073709551615";
+        assert_eq!(
+            parse_stat(stat),
+            Some(ProcStat {
+                ppid: 4100,
+                cpu_ticks: 10
+            })
+        );
+        assert_eq!(parse_stat("garbage"), None);
+        assert_eq!(
+            parse_rss_kb("Name:\tsh\nVmPeak:\t  2392 kB\nVmRSS:\t   884 kB\n"),
+            Some(884)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_executions_are_tracked_until_dropped() {
+        let watchdog = Arc::new(ExecutionWatchdog::default());
+        let first = watchdog.start("build");
+        let second = watchdog.start("test");
+        assert_ne!(first.id(), second.id());
+        let running = watchdog.running();
+        assert_eq!(
+            running
+                .iter()
+                .map(|e| e.recipe.as_str())
+                .collect::<Vec<_>>(),
+            vec!["build", "test"]
+        );
+        assert!(running.iter().all(|e| e.pid.is_none() && !e.likely_hung));
+
+        drop(first);
+        assert_eq!(watchdog.running().len(), 1);
+        assert!(watchdog.cancel(second.id() + 1).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_idle_process_is_flagged_and_cancelled() {
+        use crate::executor::interactive::{run_monitored, InteractiveConfig, RunOutcome};
+
+        let watchdog = Arc::new(ExecutionWatchdog::new(WatchdogConfig {
+            sample_interval: Duration::from_millis(50),
+            hang_timeout: Duration::from_millis(300),
+        }));
+        let execution = watchdog.start("stuck");
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg("echo started; sleep 30; echo done");
+
+        let config = InteractiveConfig::default();
+        let run = run_monitored(cmd, Duration::from_secs(30), &config, Some(&execution));
+        let check = async {
+            let deadline = Instant::now() + Duration::from_secs(10);
+            loop {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                let running = watchdog.running();
+                if running[0].likely_hung {
+                    assert!(running[0].rss_kb.is_some_and(|kb| kb > 0));
+                    let shell = running[0].pid.unwrap();
+                    watchdog.cancel(execution.id()).unwrap();
+                    break process_tree(shell).unwrap();
+                }
+                assert!(Instant::now() < deadline, "never flagged: {running:?}");
+            }
+        };
+
+        let started = Instant::now();
+        let (output, tree) = tokio::join!(run, check);
+        let output = output.unwrap();
+        assert!(matches!(output.outcome, RunOutcome::Cancelled));
+        assert!(String::from_utf8_lossy(&output.stdout).contains("started"));
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        // The shell's `sleep` was killed too, not left running; signals are
+        // delivered asynchronously, so allow it a moment to exit
+        assert!(tree.len() > 1, "{tree:?}");
+        let running = || {
+            tree.iter()
+                .map(|(pid, _)| std::fs::read_to_string(format!("/proc/{pid}/stat")))
+                .filter_map(|stat| stat.ok())
+                .filter(|stat| !stat.rsplit(") ").next().unwrap_or("").starts_with('Z'))
+                .count()
+        };
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while running() > 0 && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(running(), 0, "{tree:?}");
+    }
+}
@@ -0,0 +1,181 @@
+//! Detection of the `just` binary
+//!
+//! Recipes run through `just`, so without it every recipe call fails. The
+//! server checks for it at startup, `_admin_status` and the parser doctor
+//! report it, and a recipe call that cannot start `just` fails with
+//! [`Error::JustNotInstalled`] instead of a bare OS error. Each carries a
+//! hint on installing `just` with a package manager found on this machine.
+
+use crate::error::Error;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Where to read about other ways of installing `just`
+pub const INSTALL_DOCS: &str = "https://github.com/casey/just#installation";
+
+/// Package managers that carry `just`, in order of preference per OS:
+/// (OS, program, install command)
+const PACKAGE_MANAGERS: &[(&str, &str, &str)] = &[
+    ("macos", "brew", "brew install just"),
+    ("macos", "port", "sudo port install just"),
+    ("macos", "nix-env", "nix-env -iA nixpkgs.just"),
+    ("linux", "apt-get", "sudo apt-get install just"),
+    ("linux", "dnf", "sudo dnf install just"),
+    ("linux", "pacman", "sudo pacman -S just"),
+    ("linux", "apk", "sudo apk add just"),
+    ("linux", "zypper", "sudo zypper install just"),
+    ("linux", "nix-env", "nix-env -iA nixpkgs.just"),
+    ("linux", "brew", "brew install just"),
+    ("linux", "snap", "sudo snap install --edge --classic just"),
+    (
+        "windows",
+        "winget",
+        "winget install --id Casey.Just --exact",
+    ),
+    ("windows", "scoop", "scoop install just"),
+    ("windows", "choco", "choco install just"),
+    ("freebsd", "pkg", "pkg install just"),
+];
+
+/// Whether `just` is installed, and how to install it
+#[derive(Debug, Clone, Serialize)]
+pub struct JustBinary {
+    pub available: bool,
+    /// Where `just` was found on `PATH`
+    pub path: Option<String>,
+    /// Output of `just --version`
+    pub version: Option<String>,
+    /// Operating system, as in `std::env::consts::OS`
+    pub platform: String,
+    /// Package manager the install hint uses, if one was found on `PATH`
+    pub package_manager: Option<String>,
+    /// How to install `just` on this machine
+    pub install_hint: String,
+}
+
+impl JustBinary {
+    /// Look for `just` on `PATH` and ask it for its version
+    pub fn detect() -> Self {
+        let path = find_in_path("just");
+        let version = path.as_ref().and_then(|path| {
+            let output = std::process::Command::new(path)
+                .arg("--version")
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        });
+        let platform = std::env::consts::OS;
+        let (package_manager, install_hint) =
+            install_hint(platform, |program| find_in_path(program).is_some());
+        Self {
+            available: version.is_some(),
+            path: path.map(|path| path.display().to_string()),
+            version,
+            platform: platform.to_string(),
+            package_manager: package_manager.map(str::to_string),
+            install_hint,
+        }
+    }
+
+    /// Warning describing the missing binary, or `None` if `just` works
+    pub fn warning(&self) -> Option<String> {
+        if self.available {
+            return None;
+        }
+        let problem = match &self.path {
+            Some(path) => format!("'{path}' does not run"),
+            None => "just was not found on PATH".to_string(),
+        };
+        Some(format!(
+            "{problem}; recipes cannot run until it is installed. {}",
+            self.install_hint
+        ))
+    }
+}
+
+/// Error for an operation that needs `just` when it is not installed
+///
+/// `needed_for` completes "it is needed ...", e.g. `to run recipe 'build'`.
+pub fn not_installed(needed_for: impl Into<String>) -> Error {
+    let binary = JustBinary::detect();
+    Error::JustNotInstalled {
+        needed_for: needed_for.into(),
+        hint: binary.install_hint,
+    }
+}
+
+/// Whether `just` can be found on `PATH`
+pub fn is_on_path() -> bool {
+    find_in_path("just").is_some()
+}
+
+/// Install hint for `os`, using the first of its package managers for which
+/// `installed` holds, or cargo
+fn install_hint(os: &str, installed: impl Fn(&str) -> bool) -> (Option<&'static str>, String) {
+    let command = PACKAGE_MANAGERS
+        .iter()
+        .filter(|(platform, _, _)| *platform == os)
+        .chain(std::iter::once(&("", "cargo", "cargo install just")))
+        .find(|(_, program, _)| installed(program));
+    match command {
+        Some((_, program, command)) => (
+            Some(program),
+            format!("Install it with `{command}` (see {INSTALL_DOCS} for other options)"),
+        ),
+        None => (
+            None,
+            format!("Install it from a package manager or prebuilt binary: {INSTALL_DOCS}"),
+        ),
+    }
+}
+
+/// First executable named `program` in the directories on `PATH`
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    let names: Vec<String> = if cfg!(windows) {
+        vec![format!("{program}.exe"), program.to_string()]
+    } else {
+        vec![program.to_string()]
+    };
+    std::env::split_paths(&path)
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_hint_prefers_platform_package_manager() {
+        let (manager, hint) = install_hint("macos", |program| program != "port");
+        assert_eq!(manager, Some("brew"));
+        assert!(hint.contains("`brew install just`"));
+
+        let (manager, hint) = install_hint("linux", |program| program == "dnf");
+        assert_eq!(manager, Some("dnf"));
+        assert!(hint.contains("`sudo dnf install just`"));
+
+        // Cargo is the fallback everywhere, then the installation docs
+        let (manager, _) = install_hint("windows", |program| program == "cargo");
+        assert_eq!(manager, Some("cargo"));
+        let (manager, hint) = install_hint("linux", |_| false);
+        assert_eq!(manager, None);
+        assert!(hint.contains(INSTALL_DOCS));
+    }
+
+    #[test]
+    fn test_missing_binary_error() {
+        let error = Error::JustNotInstalled {
+            needed_for: "to run recipe 'build'".to_string(),
+            hint: "Install it with `brew install just`".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "just is not installed; it is needed to run recipe 'build'. Install it with `brew install just`"
+        );
+    }
+}
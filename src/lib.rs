@@ -1,16 +1,31 @@
+pub mod access_log;
+#[cfg(feature = "admin")]
 pub mod admin;
 pub mod cli;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod config_resource;
+#[cfg(feature = "watcher")]
+pub mod docs_site;
 pub mod embedded_content;
 pub mod error;
 pub mod executor;
+pub mod just_binary;
+#[cfg(feature = "watcher")]
 mod notification;
 pub mod parser;
+pub mod profiles;
+pub mod project_lock;
 pub mod prompts;
 pub mod registry;
 pub mod resource_limits;
 pub mod security;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
 pub mod types;
+#[cfg(feature = "self-update")]
+pub mod update;
+#[cfg(feature = "watcher")]
 pub mod watcher;
 
 #[cfg(feature = "vector-search")]
@@ -23,3 +38,7 @@ pub use error::{Error, Result};
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const PKG_NAME: &str = env!("CARGO_PKG_NAME");
+/// Tracing target for startup and watcher metrics events
+pub const METRICS_TARGET: &str = "just_mcp::metrics";
+/// Git commit the binary was built from, if built from a checkout
+pub const GIT_HASH: Option<&str> = option_env!("JUST_MCP_GIT_HASH");
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use clap::Parser;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 use just_mcp::cli::{Args, Commands};
@@ -11,15 +12,73 @@ async fn main() -> Result<()> {
     // Initialize logging
     init_logging(&args)?;
 
-    // Handle different commands
+    let result = run_command(&args).await;
+    if let Err(e) = &result {
+        args.output.print_error(e);
+    }
+    result
+}
+
+/// Run the command given on the command line
+async fn run_command(args: &Args) -> Result<()> {
+    let output = args.output;
     match args.command {
         #[cfg(feature = "vector-search")]
-        Some(Commands::Search { search_command }) => {
-            just_mcp::cli::handle_search_command(search_command).await?;
+        Some(Commands::Search { ref search_command }) => {
+            just_mcp::cli::handle_search_command(search_command.clone(), output).await?;
+        }
+        #[cfg(feature = "admin")]
+        Some(Commands::Admin { ref admin_command }) => {
+            let watch_configs = just_mcp::cli::parse_watch_dirs(&args.watch_dir)?;
+            let parser_overrides = args.parser_overrides(&watch_configs)?;
+            just_mcp::cli::handle_admin_command(
+                admin_command.clone(),
+                watch_configs,
+                &args.parser,
+                parser_overrides,
+                output,
+            )
+            .await?;
+        }
+        #[cfg(feature = "watcher")]
+        Some(Commands::Docs { ref docs_command }) => {
+            let watch_configs = just_mcp::cli::parse_watch_dirs(&args.watch_dir)?;
+            let parser_overrides = args.parser_overrides(&watch_configs)?;
+            just_mcp::cli::handle_docs_command(
+                docs_command.clone(),
+                watch_configs,
+                &args.parser,
+                parser_overrides,
+                output,
+            )
+            .await?;
+        }
+        #[cfg(feature = "ultrafast-framework")]
+        Some(Commands::List) => {
+            just_mcp::cli::handle_list_command(framework_server(args)?, output).await?;
+        }
+        #[cfg(feature = "ultrafast-framework")]
+        Some(Commands::Run {
+            ref tool,
+            ref arguments,
+        }) => {
+            just_mcp::cli::handle_run_command(framework_server(args)?, tool, arguments, output)
+                .await?;
+        }
+        Some(Commands::Init {
+            ref template,
+            ref dir,
+            force,
+        }) => {
+            just_mcp::cli::handle_init_command(template.as_deref(), dir, force, output)?;
+        }
+        #[cfg(feature = "self-update")]
+        Some(Commands::SelfUpdate { channel, check }) => {
+            just_mcp::cli::handle_self_update_command(channel, check, output).await?;
         }
         Some(Commands::Serve) | None => {
             // Start framework server (only option available)
-            start_framework_server(&args).await?;
+            start_framework_server(args).await?;
         }
     }
 
@@ -36,81 +95,109 @@ async fn start_framework_server(args: &Args) -> Result<()> {
             just_mcp::VERSION
         );
 
-        // Parse watch directories with optional names (same logic as custom server)
-        let mut watch_configs = Vec::new();
-
-        if args.watch_dir.is_empty() {
-            // Default to current working directory with no name
-            let cwd = std::env::current_dir()?;
-            tracing::info!(
-                "No --watch-dir specified, using current directory: {}",
-                cwd.display()
-            );
-            watch_configs.push((cwd, None));
-        } else {
-            for dir_spec in &args.watch_dir {
-                if let Some(colon_pos) = dir_spec.find(':') {
-                    // Format: path:name
-                    let path = std::path::PathBuf::from(&dir_spec[..colon_pos]);
-                    let name = Some(dir_spec[colon_pos + 1..].to_string());
-                    watch_configs.push((path, name));
-                } else {
-                    // Just a path, no name
-                    watch_configs.push((std::path::PathBuf::from(dir_spec), None));
-                }
-            }
-        }
-
-        // Convert all paths to absolute paths
-        let mut absolute_configs = Vec::new();
-        for (path, name) in watch_configs {
-            let abs_path = if path.is_absolute() {
-                path
-            } else {
-                std::env::current_dir()?.join(path)
-            };
-            absolute_configs.push((abs_path, name));
-        }
-
-        // Extract just the paths for the server
-        let watch_paths: Vec<std::path::PathBuf> = absolute_configs
-            .iter()
-            .map(|(path, _)| path.clone())
-            .collect();
-
-        // Log the absolute paths being watched
-        tracing::info!("Watch directories (framework server):");
-        for (path, name) in &absolute_configs {
-            if let Some(n) = name {
-                tracing::info!("  {} (name: {})", path.display(), n);
-            } else {
-                tracing::info!("  {}", path.display());
-            }
-        }
-
-        // Create and configure the framework server
-        let mut framework_server = just_mcp::server::FrameworkServer::new()
-            .with_watch_paths(watch_paths)
-            .with_watch_names(absolute_configs)
-            .with_admin_enabled(args.admin);
-
         // Run the framework server
+        let mut framework_server = framework_server(args)?;
         framework_server.run().await?;
         Ok(())
     }
 
     #[cfg(not(feature = "ultrafast-framework"))]
     {
-        return Err(anyhow::anyhow!("Framework server not available: ultrafast-framework feature not enabled. Please rebuild with --features ultrafast-framework"));
+        let _ = args;
+        Err(anyhow::anyhow!("Framework server not available: ultrafast-framework feature not enabled. Please rebuild with --features ultrafast-framework"))
     }
 }
 
+/// Build the framework server configured by the command line
+#[cfg(feature = "ultrafast-framework")]
+fn framework_server(args: &Args) -> Result<just_mcp::server::FrameworkServer> {
+    // Parse watch directories with optional names, resolved to absolute paths
+    if args.watch_dir.is_empty() {
+        tracing::info!(
+            "No --watch-dir specified, using current directory: {}",
+            std::env::current_dir()?.display()
+        );
+    }
+    let absolute_configs = just_mcp::cli::parse_watch_dirs(&args.watch_dir)?;
+
+    // Extract just the paths for the server
+    let watch_paths: Vec<std::path::PathBuf> = absolute_configs
+        .iter()
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    // Log the absolute paths being watched
+    tracing::info!("Watch directories (framework server):");
+    for (path, name) in &absolute_configs {
+        if let Some(n) = name {
+            tracing::info!("  {} (name: {})", path.display(), n);
+        } else {
+            tracing::info!("  {}", path.display());
+        }
+    }
+
+    let parser_overrides = args.parser_overrides(&absolute_configs)?;
+    let parser_preference = args
+        .parser
+        .parse::<just_mcp::parser::ParserPreference>()
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    // Create and configure the framework server
+    let framework_server = just_mcp::server::FrameworkServer::new()
+        .with_watch_paths(watch_paths)
+        .with_watch_names(absolute_configs)
+        .with_parser_preference(parser_preference)
+        .with_parser_overrides(parser_overrides)
+        .with_resource_limits(args.resource_limits())
+        .with_interactive_config(args.interactive_config())
+        .with_watchdog_config(args.watchdog_config())
+        .with_command_echo(args.echo_commands)
+        .with_ansi_mode(args.ansi)
+        .with_environment_check(args.check_env)
+        .with_lock_config(args.lock_config())
+        .with_exclusive_recipe_lock(args.lock_exclusive_recipes)
+        .with_startup_profile(args.startup_profile)
+        .with_max_batch_size(args.max_batch_size)
+        .with_transports(args.transport.clone())
+        .with_schema_mode(args.schema_mode)
+        .with_access_log(args.access_log_config())
+        .with_output_link_threshold(args.output_link_threshold)
+        .with_parameter_memory(!args.no_parameter_memory)
+        .with_result_diff(args.diff_previous_run)
+        .with_dedup_window(std::time::Duration::from_secs(args.dedup_window))
+        .with_resource_cache_ttl(std::time::Duration::from_secs(args.resource_cache_ttl))
+        .with_http_allow_origin(args.http_allow_origin.clone())
+        .with_prompts_dir(args.prompts_dir())
+        .with_profiles(args.profiles()?)
+        .with_default_profile(args.profile.clone())
+        .with_admin_enabled(args.admin)
+        .with_inline_justfiles(args.allow_inline_justfiles);
+    #[cfg(feature = "telemetry")]
+    let framework_server = framework_server.with_telemetry(args.telemetry_config());
+    // One-shot commands like `list` and `run` skip the release check
+    #[cfg(feature = "self-update")]
+    let framework_server =
+        framework_server.with_update_check(args.update_check && !args.prints_results());
+
+    Ok(framework_server)
+}
+
 fn init_logging(args: &Args) -> Result<()> {
     let env_filter =
         EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&args.log_level));
 
+    // Commands that print results keep stdout for them
+    let writer = || {
+        if args.prints_results() {
+            BoxMakeWriter::new(std::io::stderr)
+        } else {
+            BoxMakeWriter::new(std::io::stdout)
+        }
+    };
+
     let fmt_layer = if args.json_logs {
         fmt::layer()
+            .with_writer(writer())
             .json()
             .with_target(false)
             .with_thread_ids(false)
@@ -118,6 +205,7 @@ fn init_logging(args: &Args) -> Result<()> {
             .boxed()
     } else {
         fmt::layer()
+            .with_writer(writer())
             .with_target(false)
             .with_thread_ids(false)
             .with_thread_names(false)
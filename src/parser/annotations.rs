@@ -0,0 +1,372 @@
+//! Recipe annotations read directly from justfile source
+//!
+//! just-mcp recognises two kinds of per-recipe annotations on the lines
+//! directly above a recipe header:
+//!
+//! - Comment annotations such as `# [slot: extra_flags]`, which `just` itself
+//!   ignores but just-mcp interprets
+//! - Native attributes such as `[no-cd]` or `[group('ci')]`
+//!
+//! Execution profiles, `# [profile: fast(fast=true, target=debug)]`, are
+//! comment annotations naming a bundle of preset arguments. Callers pick one
+//! with the `profile` argument instead of spelling out every value.
+//!
+//! Annotations are extracted from the raw source so they are available no
+//! matter which parser backend produced the recipe list.
+
+use crate::types::JustTask;
+use std::collections::HashMap;
+
+/// Annotation name for template slots (`# [slot: name]`)
+pub const SLOT_ANNOTATION: &str = "slot";
+
+/// Annotation marking recipes that must not run concurrently
+///
+/// `# [exclusive]` keeps a recipe from overlapping with itself;
+/// `# [exclusive: db]` serializes every recipe in the `db` group.
+pub const EXCLUSIVE_ANNOTATION: &str = "exclusive";
+
+/// Annotation name for execution profiles (`# [profile: fast(fast=true)]`)
+pub const PROFILE_ANNOTATION: &str = "profile";
+
+/// Native attribute for recipes that run in the invocation directory
+///
+/// `just` normally changes into the justfile's directory before running a
+/// recipe; `[no-cd]` recipes run where `just` was invoked instead, so their
+/// path arguments are relative to that directory.
+pub const NO_CD_ANNOTATION: &str = "no-cd";
+
+/// Annotation for recipes that need a terminal (`# [tty]`)
+///
+/// These recipes run in a pseudo-terminal unless `--pty never` is given.
+pub const TTY_ANNOTATION: &str = "tty";
+
+/// Annotation for the parallel jobs `just` may use (`# [jobs: 4]`)
+///
+/// Overrides the `--jobs`, `--project-jobs` and `--group-jobs` settings for
+/// one recipe.
+pub const JOBS_ANNOTATION: &str = "jobs";
+
+/// Tool argument that selects an execution profile
+pub const PROFILE_ARGUMENT: &str = "profile";
+
+/// A named bundle of preset recipe arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionProfile {
+    pub name: String,
+    /// Preset `(argument, value)` pairs, in declaration order
+    pub arguments: Vec<(String, String)>,
+}
+
+impl ExecutionProfile {
+    /// Parse an annotation value such as `fast(fast=true, target=debug)`
+    ///
+    /// A bare name such as `full` declares a profile without presets, which
+    /// runs the recipe with its defaults.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (name, arguments) = match value.split_once('(') {
+            Some((name, rest)) => (name.trim(), rest.strip_suffix(')')?),
+            None => (value, ""),
+        };
+        if !is_annotation_name(name) {
+            return None;
+        }
+
+        let arguments = split_top_level(arguments)
+            .into_iter()
+            .filter(|item| !item.trim().is_empty())
+            .map(|item| {
+                let (key, value) = item.split_once('=')?;
+                let key = key.trim();
+                is_annotation_name(key)
+                    .then(|| (key.to_string(), unquote(value.trim()).to_string()))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            name: name.to_string(),
+            arguments,
+        })
+    }
+
+    /// Render the presets as `name=value, ...` for descriptions
+    pub fn summary(&self) -> String {
+        self.arguments
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// A single annotation attached to a recipe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecipeAnnotation {
+    /// Annotation name, e.g. `slot` or `no-cd`
+    pub name: String,
+    /// Optional value, e.g. `extra_flags` for `# [slot: extra_flags]`
+    pub value: Option<String>,
+}
+
+impl RecipeAnnotation {
+    pub fn new(name: impl Into<String>, value: Option<String>) -> Self {
+        Self {
+            name: name.into(),
+            value,
+        }
+    }
+
+    /// Split the value on commas, returning trimmed non-empty entries
+    pub fn values(&self) -> Vec<String> {
+        self.value
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect()
+    }
+}
+
+/// Extract annotations for every recipe in the given justfile content
+///
+/// Returns a map from recipe name to the annotations found in the comment
+/// and attribute block immediately above the recipe header. A blank line or
+/// any other statement ends the block.
+pub fn extract_annotations(content: &str) -> HashMap<String, Vec<RecipeAnnotation>> {
+    let mut result: HashMap<String, Vec<RecipeAnnotation>> = HashMap::new();
+    let mut pending = Vec::new();
+
+    for line in content.lines() {
+        // Indented lines belong to recipe bodies
+        if line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+            pending.clear();
+            continue;
+        }
+
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            pending.clear();
+        } else if let Some(comment) = trimmed.strip_prefix('#') {
+            if let Some(inner) = bracketed(comment.trim()) {
+                pending.extend(parse_annotation_list(inner));
+            }
+        } else if let Some(inner) = bracketed(trimmed) {
+            pending.extend(parse_annotation_list(inner));
+        } else if let Some(name) = recipe_header_name(trimmed) {
+            if !pending.is_empty() {
+                result
+                    .entry(name)
+                    .or_default()
+                    .extend(std::mem::take(&mut pending));
+            }
+        } else {
+            pending.clear();
+        }
+    }
+
+    result
+}
+
+/// Attach annotations extracted from `content` to the matching tasks
+pub fn apply_annotations(tasks: &mut [JustTask], content: &str) {
+    let mut annotations = extract_annotations(content);
+    for task in tasks {
+        if let Some(found) = annotations.remove(&task.name) {
+            task.annotations = found;
+        }
+    }
+}
+
+/// Return the text between `[` and `]` if the line is a bracketed block
+fn bracketed(text: &str) -> Option<&str> {
+    text.strip_prefix('[')?.strip_suffix(']').map(str::trim)
+}
+
+/// Parse the contents of an annotation block
+///
+/// `name: value` is a single annotation whose value may contain commas;
+/// otherwise the block is a comma-separated list of `name` or `name(value)`.
+fn parse_annotation_list(inner: &str) -> Vec<RecipeAnnotation> {
+    if let Some((name, value)) = inner.split_once(':') {
+        if is_annotation_name(name.trim()) {
+            return vec![RecipeAnnotation::new(
+                name.trim(),
+                Some(unquote(value.trim()).to_string()),
+            )];
+        }
+    }
+
+    split_top_level(inner)
+        .into_iter()
+        .filter_map(|item| {
+            let item = item.trim();
+            if let Some((name, rest)) = item.split_once('(') {
+                let value = rest.strip_suffix(')')?.trim();
+                is_annotation_name(name.trim())
+                    .then(|| RecipeAnnotation::new(name.trim(), Some(unquote(value).to_string())))
+            } else {
+                is_annotation_name(item).then(|| RecipeAnnotation::new(item, None))
+            }
+        })
+        .collect()
+}
+
+/// Split on commas that are not inside parentheses or quotes
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+fn is_annotation_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+pub(crate) fn unquote(value: &str) -> &str {
+    for q in ['"', '\''] {
+        if let Some(inner) = value.strip_prefix(q).and_then(|v| v.strip_suffix(q)) {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Return the recipe name if the line is a recipe header
+pub(crate) fn recipe_header_name(line: &str) -> Option<String> {
+    let line = line.strip_prefix('@').unwrap_or(line);
+    let name_len = line
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+        .unwrap_or(line.len());
+    let name = &line[..name_len];
+
+    if name.is_empty() || name.starts_with('-') {
+        return None;
+    }
+
+    // Recipe headers have a ':' that is not part of ':='
+    let colon = line.find(':')?;
+    if line[colon + 1..].starts_with('=') {
+        return None;
+    }
+
+    Some(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comment_annotations() {
+        let content = r#"
+flags := ""
+
+# Build the project
+# [slot: extra_flags, features]
+# [exclusive]
+build target="debug":
+    cargo build {{flags}}
+"#;
+        let annotations = extract_annotations(content);
+        let build = &annotations["build"];
+        assert_eq!(build.len(), 2);
+        assert_eq!(build[0].name, "slot");
+        assert_eq!(build[0].values(), vec!["extra_flags", "features"]);
+        assert_eq!(build[1], RecipeAnnotation::new("exclusive", None));
+    }
+
+    #[test]
+    fn test_native_attributes() {
+        let content = r#"
+[no-cd]
+[group('ci'), private]
+@lint:
+    cargo clippy
+"#;
+        let annotations = extract_annotations(content);
+        let lint = &annotations["lint"];
+        assert_eq!(lint[0], RecipeAnnotation::new("no-cd", None));
+        assert_eq!(lint[1], RecipeAnnotation::new("group", Some("ci".into())));
+        assert_eq!(lint[2], RecipeAnnotation::new("private", None));
+    }
+
+    #[test]
+    fn test_blank_line_and_body_reset_block() {
+        let content = r#"
+# [slot: detached]
+
+first:
+    # [slot: in_body]
+    echo first
+second:
+    echo second
+"#;
+        let annotations = extract_annotations(content);
+        assert!(annotations.is_empty());
+    }
+
+    #[test]
+    fn test_execution_profiles() {
+        let content = r#"
+# [profile: fast(fast=true, target="debug")]
+# [profile: full]
+test fast="false" target="release":
+    cargo test
+"#;
+        let annotations = extract_annotations(content);
+        let profiles: Vec<_> = annotations["test"]
+            .iter()
+            .filter(|a| a.name == PROFILE_ANNOTATION)
+            .filter_map(|a| ExecutionProfile::parse(a.value.as_deref()?))
+            .collect();
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].name, "fast");
+        assert_eq!(
+            profiles[0].arguments,
+            vec![
+                ("fast".to_string(), "true".to_string()),
+                ("target".to_string(), "debug".to_string())
+            ]
+        );
+        assert_eq!(profiles[0].summary(), "fast=true, target=debug");
+        assert!(profiles[1].arguments.is_empty());
+
+        assert_eq!(ExecutionProfile::parse("fast(true)"), None);
+        assert_eq!(ExecutionProfile::parse("fast(x=1"), None);
+    }
+
+    #[test]
+    fn test_assignments_are_not_headers() {
+        assert_eq!(recipe_header_name("version := \"1.0\""), None);
+        assert_eq!(recipe_header_name("alias b := build"), None);
+        assert_eq!(
+            recipe_header_name("deploy env=\"a:b\": build"),
+            Some("deploy".to_string())
+        );
+        assert_eq!(recipe_header_name("@quiet:"), Some("quiet".to_string()));
+    }
+}
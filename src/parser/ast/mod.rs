@@ -84,7 +84,7 @@ mod tests {
         // This test exists to verify that the module properly compiles
         // when the ast-parser feature is enabled
         assert!(
-            true,
+            cfg!(feature = "ast-parser"),
             "AST parser module properly compiled with feature flag"
         );
     }
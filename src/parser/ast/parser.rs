@@ -503,6 +503,8 @@ impl ASTJustParser {
                 confirm_message,
                 doc,
                 attributes: recipe_attributes,
+                annotations: Vec::new(),
+                long_description: None,
             };
 
             just_tasks.push(just_task);
@@ -752,6 +754,8 @@ impl ASTJustParser {
             confirm_message: None,
             doc: None,
             attributes: Vec::new(),
+            annotations: Vec::new(),
+            long_description: None,
         })
     }
 
@@ -381,6 +381,8 @@ impl ASTJustParser {
                 confirm_message,
                 doc,
                 attributes: recipe_attributes,
+                annotations: Vec::new(),
+                long_description: None,
             };
 
             just_tasks.push(just_task);
@@ -619,6 +621,8 @@ impl ASTJustParser {
             confirm_message: None,
             doc: None,
             attributes: Vec::new(),
+            annotations: Vec::new(),
+            long_description: None,
         })
     }
 
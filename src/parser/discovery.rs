@@ -0,0 +1,153 @@
+//! Justfile name matching shared by discovery, the watcher and the executor
+//!
+//! `just` looks for a file named `justfile` in any casing (`justfile`,
+//! `Justfile`, `JUSTFILE`, ...) or a hidden `.justfile`. Every component that
+//! finds justfiles or filters file events goes through these helpers so they
+//! agree on which files count. Recursive walks also share one set of ignore
+//! rules, see [`walk_justfiles`].
+
+use ignore::WalkBuilder;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Whether a file name is one `just` recognises as a justfile
+pub fn is_justfile_name(name: &OsStr) -> bool {
+    name.to_str().is_some_and(|name| {
+        let name = name.strip_prefix('.').unwrap_or(name);
+        name.eq_ignore_ascii_case("justfile")
+    })
+}
+
+/// Whether a path names a justfile
+pub fn is_justfile(path: &Path) -> bool {
+    path.file_name().is_some_and(is_justfile_name)
+}
+
+/// Find the justfiles directly inside a directory
+///
+/// Entries are read from the directory rather than probed by name, so a
+/// case-insensitive filesystem does not report the same file twice. Results
+/// are sorted, putting `Justfile` before `justfile`.
+pub fn find_justfiles(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut justfiles: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| !t.is_dir()))
+        .map(|entry| entry.path())
+        .filter(|path| is_justfile(path))
+        .collect();
+    justfiles.sort();
+    justfiles
+}
+
+/// Find the justfile `just` would use in a directory, preferring `justfile`
+pub fn find_justfile(dir: &Path) -> Option<PathBuf> {
+    let justfiles = find_justfiles(dir);
+    justfiles
+        .iter()
+        .find(|path| path.file_name() == Some(OsStr::new("justfile")))
+        .or_else(|| justfiles.first())
+        .cloned()
+}
+
+/// Find justfiles anywhere under a directory
+///
+/// `.gitignore`, `.ignore` and git exclude rules are honoured even outside a
+/// git repository, and hidden directories such as `.git/` are skipped, so
+/// build output and vendored trees are not walked. Hidden `.justfile`s are
+/// still found. Unreadable entries are logged and skipped.
+pub fn walk_justfiles(root: &Path) -> Vec<PathBuf> {
+    let mut justfiles = Vec::new();
+    let walker = WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| {
+            let hidden_dir = entry.depth() > 0
+                && entry.file_type().is_some_and(|t| t.is_dir())
+                && entry.file_name().to_string_lossy().starts_with('.');
+            !hidden_dir
+        })
+        .build();
+
+    for entry in walker {
+        match entry {
+            Ok(entry) if entry.file_type().is_some_and(|t| t.is_file()) => {
+                if is_justfile(entry.path()) {
+                    justfiles.push(entry.into_path());
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Skipping entry while searching for justfiles: {}", e),
+        }
+    }
+
+    justfiles.sort();
+    justfiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_justfile_name_case_variants() {
+        for name in ["justfile", "Justfile", "JUSTFILE", "JustFile", ".justfile"] {
+            assert!(is_justfile_name(OsStr::new(name)), "{name}");
+        }
+        for name in [
+            "justfile.bak",
+            "my-justfile",
+            "just",
+            "..justfile",
+            "mod.just",
+        ] {
+            assert!(!is_justfile_name(OsStr::new(name)), "{name}");
+        }
+        assert!(is_justfile(Path::new("/project/Justfile")));
+    }
+
+    #[test]
+    fn test_find_justfiles() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Justfile"), "build:\n").unwrap();
+        fs::write(temp_dir.path().join("README.md"), "").unwrap();
+        fs::create_dir(temp_dir.path().join("justfile.d")).unwrap();
+
+        assert_eq!(
+            find_justfiles(temp_dir.path()),
+            vec![temp_dir.path().join("Justfile")]
+        );
+        assert_eq!(
+            find_justfile(temp_dir.path()),
+            Some(temp_dir.path().join("Justfile"))
+        );
+        assert!(find_justfiles(&temp_dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_walk_justfiles_honors_ignore_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join(".gitignore"), "target/\nvendor/\n").unwrap();
+        for dir in ["app", "target/debug", "vendor/dep", ".git", ".cache/tool"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(root.join(dir).join("justfile"), "build:\n").unwrap();
+        }
+        fs::write(root.join("Justfile"), "test:\n").unwrap();
+        fs::write(root.join("app/.justfile"), "lint:\n").unwrap();
+
+        assert_eq!(
+            walk_justfiles(root),
+            vec![
+                root.join("Justfile"),
+                root.join("app/.justfile"),
+                root.join("app/justfile"),
+            ]
+        );
+    }
+}
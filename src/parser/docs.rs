@@ -0,0 +1,266 @@
+//! Recipe and parameter documentation read directly from justfile source
+//!
+//! Not every parser backend keeps the comment block above a recipe, so the
+//! documentation is extracted from the raw source, like annotations, and
+//! attached to the parsed tasks. Parameter descriptions can be written as:
+//!
+//! - `# {{target}}: Build target`, optionally continued on following comment
+//!   lines indented past the `#`
+//! - `# [doc(target): Build target]`, the parameter form of `[doc]`
+//!
+//! Multi-line comment blocks are also kept verbatim as a long description.
+
+use super::annotations::{recipe_header_name, unquote};
+use super::variables::is_identifier;
+use crate::types::JustTask;
+use std::collections::HashMap;
+
+/// Documentation found above a single recipe
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecipeDocs {
+    /// Non-empty comment lines, excluding annotations and parameter docs
+    pub comments: Vec<String>,
+    /// Text of a native `[doc('...')]` attribute
+    pub doc: Option<String>,
+    /// Parameter descriptions keyed by parameter name
+    pub parameters: HashMap<String, String>,
+    /// The comment block with line breaks preserved, if it spans several lines
+    pub long_description: Option<String>,
+}
+
+/// Extract documentation for every recipe in the given justfile content
+pub fn extract_docs(content: &str) -> HashMap<String, RecipeDocs> {
+    let mut result = HashMap::new();
+    let mut block: Vec<&str> = Vec::new();
+
+    for line in content.lines() {
+        // Indented lines belong to recipe bodies
+        if line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+            block.clear();
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            block.clear();
+        } else if trimmed.starts_with('#') || trimmed.starts_with('[') {
+            if !trimmed.starts_with("#!") {
+                block.push(trimmed);
+            }
+        } else if let Some(name) = recipe_header_name(trimmed) {
+            let docs = parse_block(&block);
+            if docs != RecipeDocs::default() {
+                result.insert(name, docs);
+            }
+            block.clear();
+        } else {
+            block.clear();
+        }
+    }
+
+    result
+}
+
+/// Attach documentation extracted from `content` to the matching tasks
+///
+/// Source-level parameter descriptions replace parser-generated ones;
+/// comments and `[doc]` text only fill in what the parser left empty.
+pub fn apply_docs(tasks: &mut [JustTask], content: &str) {
+    let mut docs = extract_docs(content);
+    for task in tasks {
+        let Some(found) = docs.remove(&task.name) else {
+            continue;
+        };
+        if task.comments.is_empty() {
+            task.comments = found.comments;
+        }
+        if task.doc.is_none() {
+            task.doc = found.doc;
+        }
+        for param in &mut task.parameters {
+            if let Some(description) = found.parameters.get(&param.name) {
+                param.description = Some(description.clone());
+            }
+        }
+        task.long_description = found.long_description;
+    }
+}
+
+fn parse_block(lines: &[&str]) -> RecipeDocs {
+    let mut docs = RecipeDocs::default();
+    let mut text_lines: Vec<String> = Vec::new();
+    let mut last_param: Option<String> = None;
+
+    for line in lines {
+        if let Some(comment) = line.strip_prefix('#') {
+            let text = comment.strip_prefix(' ').unwrap_or(comment);
+
+            if let Some((name, description)) = bracketed_param_doc(text.trim()) {
+                docs.parameters.insert(name, description);
+                last_param = None;
+            } else if text.trim().starts_with('[') && text.trim().ends_with(']') {
+                // Other comment annotations, e.g. `# [slot: flags]`
+                last_param = None;
+            } else if let Some((name, description)) = interpolated_param_doc(text) {
+                docs.parameters.insert(name.clone(), description);
+                last_param = Some(name);
+            } else if last_param.is_some() && text.starts_with(char::is_whitespace) {
+                // Indented continuation of a parameter description
+                let name = last_param.as_deref().unwrap_or_default();
+                if let Some(description) = docs.parameters.get_mut(name) {
+                    description.push(' ');
+                    description.push_str(text.trim());
+                }
+            } else {
+                last_param = None;
+                text_lines.push(text.trim_end().to_string());
+            }
+        } else if let Some(inner) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            last_param = None;
+            if let Some(text) = doc_attribute(inner) {
+                docs.doc = Some(text);
+            }
+        }
+    }
+
+    // Trim blank comment lines around the text, keeping inner paragraph breaks
+    while text_lines.first().is_some_and(|l| l.trim().is_empty()) {
+        text_lines.remove(0);
+    }
+    while text_lines.last().is_some_and(|l| l.trim().is_empty()) {
+        text_lines.pop();
+    }
+
+    docs.comments = text_lines
+        .iter()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    if text_lines.len() > 1 {
+        docs.long_description = Some(text_lines.join("\n"));
+    }
+    docs
+}
+
+/// Parse `{{name}}: description`
+fn interpolated_param_doc(text: &str) -> Option<(String, String)> {
+    let rest = text.trim().strip_prefix("{{")?;
+    let (name, rest) = rest.split_once("}}")?;
+    let description = rest.trim_start().strip_prefix(':')?.trim();
+    let name = name.trim();
+    (is_identifier(name) && !description.is_empty())
+        .then(|| (name.to_string(), description.to_string()))
+}
+
+/// Parse `[doc(name): description]`
+fn bracketed_param_doc(text: &str) -> Option<(String, String)> {
+    let inner = text.strip_prefix('[')?.strip_suffix(']')?.trim();
+    let rest = inner.strip_prefix("doc(")?;
+    let (name, rest) = rest.split_once(')')?;
+    let description = unquote(rest.trim_start().strip_prefix(':')?.trim());
+    let name = unquote(name.trim());
+    (is_identifier(name) && !description.is_empty())
+        .then(|| (name.to_string(), description.to_string()))
+}
+
+/// Text of a native `[doc('text')]` attribute, which may share the brackets
+/// with other attributes
+fn doc_attribute(inner: &str) -> Option<String> {
+    let start = inner.find("doc(")?;
+    if start > 0 && !inner[..start].trim_end().ends_with(',') {
+        return None;
+    }
+    let rest = &inner[start + 4..];
+    let quote = rest
+        .trim_start()
+        .chars()
+        .next()
+        .filter(|c| *c == '\'' || *c == '"')?;
+    let rest = &rest.trim_start()[1..];
+    let end = rest.find(quote)?;
+    Some(rest[..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parameter_docs() {
+        let content = r#"
+# Build the project
+# [slot: flags]
+# {{target}}: Build profile, either debug
+#     or release
+# [doc(features): "Comma-separated cargo features"]
+[doc('Build artifacts')]
+build target="debug" features="":
+    cargo build
+"#;
+        let docs = &extract_docs(content)["build"];
+        assert_eq!(docs.comments, vec!["Build the project"]);
+        assert_eq!(docs.doc.as_deref(), Some("Build artifacts"));
+        assert_eq!(
+            docs.parameters["target"],
+            "Build profile, either debug or release"
+        );
+        assert_eq!(
+            docs.parameters["features"],
+            "Comma-separated cargo features"
+        );
+        assert_eq!(docs.long_description, None);
+    }
+
+    #[test]
+    fn test_long_description_keeps_paragraphs() {
+        let content = r#"
+# Deploy the service.
+#
+# Runs migrations first, then rolls pods:
+#   - staging is deployed immediately
+#   - production waits for approval
+deploy env:
+    ./deploy.sh {{env}}
+"#;
+        let docs = &extract_docs(content)["deploy"];
+        assert_eq!(docs.comments[0], "Deploy the service.");
+        assert_eq!(
+            docs.long_description.as_deref(),
+            Some(
+                "Deploy the service.\n\nRuns migrations first, then rolls pods:\n  - staging is deployed immediately\n  - production waits for approval"
+            )
+        );
+    }
+
+    #[test]
+    fn test_apply_docs_overrides_generated_descriptions() {
+        let mut tasks = vec![JustTask {
+            name: "test".to_string(),
+            body: String::new(),
+            parameters: vec![crate::types::Parameter {
+                name: "filter".to_string(),
+                default: Some(String::new()),
+                description: Some("(default: )".to_string()),
+            }],
+            dependencies: vec![],
+            comments: vec![],
+            line_number: 1,
+            group: None,
+            is_private: false,
+            confirm_message: None,
+            doc: None,
+            attributes: vec![],
+            annotations: vec![],
+            long_description: None,
+        }];
+        apply_docs(
+            &mut tasks,
+            "# Run tests\n# {{filter}}: Test name filter\ntest filter='':\n    cargo test {{filter}}\n",
+        );
+        assert_eq!(tasks[0].comments, vec!["Run tests"]);
+        assert_eq!(
+            tasks[0].parameters[0].description.as_deref(),
+            Some("Test name filter")
+        );
+    }
+}
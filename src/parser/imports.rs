@@ -0,0 +1,354 @@
+//! Justfile import graph resolution and composite source hashing
+//!
+//! A justfile's recipes can come from files pulled in with `import` or `mod`.
+//! Hashing only the top-level file misses edits to those files, so tool
+//! source hashes use [`composite_hash`], which covers every file in the
+//! resolved import graph. Parse caches check a [`GraphStamp`] instead, which
+//! only looks at the files' metadata.
+
+use crate::error::Result;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// An `import` or `mod` statement found in a justfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportStatement {
+    /// Explicit path as written, if any (`import 'x.just'`, `mod foo 'x.just'`)
+    pub path: Option<String>,
+    /// Module name for `mod` statements
+    pub module: Option<String>,
+    /// Whether the statement is optional (`import?` / `mod?`)
+    pub optional: bool,
+}
+
+/// A file in a resolved import graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedFile {
+    pub path: PathBuf,
+    /// False when the import could not be found on disk
+    pub exists: bool,
+}
+
+/// Extract `import` and `mod` statements from justfile content
+pub fn extract_imports(content: &str) -> Vec<ImportStatement> {
+    let mut statements = Vec::new();
+
+    for line in content.lines() {
+        if line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let line = line.split(" #").next().unwrap_or(line).trim();
+
+        if let Some(rest) = line.strip_prefix("import") {
+            let (optional, rest) = strip_optional(rest);
+            if let Some(path) = quoted(rest) {
+                statements.push(ImportStatement {
+                    path: Some(path),
+                    module: None,
+                    optional,
+                });
+            }
+        } else if let Some(rest) = line.strip_prefix("mod") {
+            let (optional, rest) = strip_optional(rest);
+            let mut parts = rest.splitn(2, char::is_whitespace);
+            let Some(name) = parts.next().filter(|n| is_module_name(n)) else {
+                continue;
+            };
+            statements.push(ImportStatement {
+                path: parts.next().and_then(quoted),
+                module: Some(name.to_string()),
+                optional,
+            });
+        }
+    }
+
+    statements
+}
+
+/// Resolve the import graph of a justfile
+///
+/// Returns the root first, followed by imported files in depth-first order.
+/// Each file appears once, so cycles terminate. Imports that cannot be found
+/// are included with `exists: false` so that creating them changes the hash.
+pub fn resolve_import_graph(root: &Path) -> Result<Vec<ImportedFile>> {
+    let mut files = Vec::new();
+    let mut seen = HashSet::new();
+    visit(root, true, &mut files, &mut seen)?;
+    Ok(files)
+}
+
+fn visit(
+    path: &Path,
+    is_root: bool,
+    files: &mut Vec<ImportedFile>,
+    seen: &mut HashSet<PathBuf>,
+) -> Result<()> {
+    let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if !seen.insert(key) {
+        return Ok(());
+    }
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if is_root => return Err(e.into()),
+        Err(_) => {
+            files.push(ImportedFile {
+                path: path.to_path_buf(),
+                exists: false,
+            });
+            return Ok(());
+        }
+    };
+
+    files.push(ImportedFile {
+        path: path.to_path_buf(),
+        exists: true,
+    });
+
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    for statement in extract_imports(&content) {
+        if let Some(target) = resolve_statement(&statement, base_dir) {
+            visit(&target, false, files, seen)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve the file an import statement refers to
+fn resolve_statement(statement: &ImportStatement, base_dir: &Path) -> Option<PathBuf> {
+    if let Some(ref raw) = statement.path {
+        let expanded = match raw.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir()?.join(rest),
+            None => PathBuf::from(raw),
+        };
+        return Some(base_dir.join(expanded));
+    }
+
+    // `mod name` searches name.just, name/mod.just and name/justfile
+    let name = statement.module.as_ref()?;
+    let candidates = [
+        base_dir.join(format!("{name}.just")),
+        base_dir.join(name).join("mod.just"),
+        base_dir.join(name).join("justfile"),
+        base_dir.join(name).join("Justfile"),
+    ];
+    let fallback = candidates[0].clone();
+    Some(
+        candidates
+            .into_iter()
+            .find(|candidate| candidate.is_file())
+            .unwrap_or(fallback),
+    )
+}
+
+/// Compute a hash over a justfile and every file in its import graph
+///
+/// Paths are hashed relative to the root justfile's directory, so the hash
+/// is stable across checkouts in different locations.
+pub fn composite_hash(root: &Path) -> Result<String> {
+    composite_hash_of(root, &resolve_import_graph(root)?)
+}
+
+/// [`composite_hash`] of an import graph that is already resolved
+pub fn composite_hash_of(root: &Path, files: &[ImportedFile]) -> Result<String> {
+    let base_dir = root.parent().unwrap_or(Path::new("."));
+    let mut hasher = Sha256::new();
+
+    for file in files {
+        let relative = file.path.strip_prefix(base_dir).unwrap_or(&file.path);
+        hasher.update(relative.to_string_lossy().as_bytes());
+        hasher.update([0]);
+        if file.exists {
+            hasher.update(std::fs::read(&file.path)?);
+        } else {
+            hasher.update(b"<missing>");
+        }
+        hasher.update([0]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Modification time and size of every file in an import graph
+///
+/// Which files belong to the graph only depends on their contents, so while
+/// none of them changes on disk the graph and its parse stay the same. That
+/// can be checked with one `stat` per file instead of reading and hashing
+/// the whole graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphStamp(Vec<(PathBuf, Option<(SystemTime, u64)>)>);
+
+impl GraphStamp {
+    /// Stamp the files of `root`'s import graph as they are now
+    pub fn of(root: &Path) -> Result<Self> {
+        Ok(Self::of_files(&resolve_import_graph(root)?))
+    }
+
+    /// Stamp files that are already resolved
+    pub fn of_files(files: &[ImportedFile]) -> Self {
+        Self(
+            files
+                .iter()
+                .map(|file| (file.path.clone(), file_stamp(&file.path)))
+                .collect(),
+        )
+    }
+
+    /// Whether no file of the graph was modified, created or removed since
+    pub fn is_current(&self) -> bool {
+        self.0
+            .iter()
+            .all(|(path, stamp)| file_stamp(path) == *stamp)
+    }
+}
+
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Strip an optional `?` after the keyword, requiring whitespace after it
+fn strip_optional(rest: &str) -> (bool, &str) {
+    let (optional, rest) = match rest.strip_prefix('?') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    if rest.starts_with(char::is_whitespace) {
+        (optional, rest.trim_start())
+    } else {
+        (optional, "")
+    }
+}
+
+/// Return the contents of a leading quoted string, if the text is one
+fn quoted(text: &str) -> Option<String> {
+    let text = text.trim();
+    let quote = text.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+    let inner = &text[1..];
+    let end = inner.find(quote)?;
+    Some(inner[..end].to_string())
+}
+
+fn is_module_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_extract_imports() {
+        let content = r#"
+import 'common.just'
+import? "local.just"  # optional overrides
+mod docker
+mod? deploy 'ops/deploy.just'
+mode arg:
+    echo {{arg}}
+important:
+    import 'not-a-statement.just'
+"#;
+        let imports = extract_imports(content);
+        assert_eq!(imports.len(), 4);
+        assert_eq!(imports[0].path.as_deref(), Some("common.just"));
+        assert!(!imports[0].optional);
+        assert_eq!(imports[1].path.as_deref(), Some("local.just"));
+        assert!(imports[1].optional);
+        assert_eq!(imports[2].module.as_deref(), Some("docker"));
+        assert_eq!(imports[2].path, None);
+        assert_eq!(imports[3].module.as_deref(), Some("deploy"));
+        assert_eq!(imports[3].path.as_deref(), Some("ops/deploy.just"));
+        assert!(imports[3].optional);
+    }
+
+    #[test]
+    fn test_resolve_import_graph_handles_cycles_and_modules() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("justfile");
+        fs::write(&root, "import 'a.just'\nmod tools\n").unwrap();
+        fs::write(temp_dir.path().join("a.just"), "import 'justfile'\n").unwrap();
+        fs::create_dir(temp_dir.path().join("tools")).unwrap();
+        fs::write(temp_dir.path().join("tools/mod.just"), "lint:\n").unwrap();
+
+        let files = resolve_import_graph(&root).unwrap();
+        let names: Vec<_> = files
+            .iter()
+            .map(|f| f.path.strip_prefix(temp_dir.path()).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                PathBuf::from("justfile"),
+                PathBuf::from("a.just"),
+                PathBuf::from("tools/mod.just"),
+            ]
+        );
+        assert!(files.iter().all(|f| f.exists));
+    }
+
+    #[test]
+    fn test_composite_hash_tracks_imported_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("justfile");
+        fs::write(&root, "import 'common.just'\nimport? 'local.just'\n").unwrap();
+        fs::write(
+            temp_dir.path().join("common.just"),
+            "build:\n    cargo build\n",
+        )
+        .unwrap();
+
+        let initial = composite_hash(&root).unwrap();
+        assert_eq!(initial, composite_hash(&root).unwrap());
+
+        // Editing an imported file changes the hash
+        fs::write(temp_dir.path().join("common.just"), "build:\n    make\n").unwrap();
+        let edited = composite_hash(&root).unwrap();
+        assert_ne!(initial, edited);
+
+        // So does creating a previously missing optional import
+        fs::write(temp_dir.path().join("local.just"), "").unwrap();
+        assert_ne!(edited, composite_hash(&root).unwrap());
+    }
+
+    #[test]
+    fn test_graph_stamp_notices_imported_file_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("justfile");
+        fs::write(&root, "import 'common.just'\nimport? 'local.just'\n").unwrap();
+        fs::write(temp_dir.path().join("common.just"), "build:\n").unwrap();
+
+        let stamp = GraphStamp::of(&root).unwrap();
+        assert!(stamp.is_current());
+
+        fs::write(temp_dir.path().join("common.just"), "build:\n    make\n").unwrap();
+        assert!(!stamp.is_current());
+
+        let stamp = GraphStamp::of(&root).unwrap();
+        fs::write(temp_dir.path().join("local.just"), "").unwrap();
+        assert!(!stamp.is_current());
+    }
+
+    #[test]
+    fn test_composite_hash_is_location_independent() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        for dir in [&first, &second] {
+            fs::write(dir.path().join("justfile"), "import 'common.just'\n").unwrap();
+            fs::write(dir.path().join("common.just"), "test:\n").unwrap();
+        }
+
+        assert_eq!(
+            composite_hash(&first.path().join("justfile")).unwrap(),
+            composite_hash(&second.path().join("justfile")).unwrap()
+        );
+    }
+}
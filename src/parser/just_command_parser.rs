@@ -48,7 +48,11 @@ impl JustCommandParser {
     /// Parse justfile using Just CLI commands for complete import resolution
     pub fn parse_file(&self, path: &Path) -> Result<Vec<JustTask>> {
         // Change to the directory containing the justfile
-        let working_dir = path.parent().unwrap_or(Path::new("."));
+        // A bare file name has an empty parent, which is not a valid directory
+        let working_dir = path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
 
         // Get all recipe names using --summary (handles imports automatically)
         let recipe_names = self.get_recipe_names(working_dir)?;
@@ -77,6 +81,8 @@ impl JustCommandParser {
                         confirm_message: None,
                         doc: None,
                         attributes: Vec::new(),
+                        annotations: Vec::new(),
+                        long_description: None,
                     };
                     tasks.push(minimal_task);
                 }
@@ -369,6 +375,8 @@ impl JustCommandParser {
             confirm_message: None, // Command parser doesn't extract this currently
             doc: None,             // Command parser doesn't extract this currently
             attributes: Vec::new(), // Command parser doesn't extract raw attributes
+            annotations: Vec::new(),
+            long_description: None,
         })
     }
 
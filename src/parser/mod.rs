@@ -58,12 +58,18 @@ impl std::fmt::Display for ParserPreference {
     }
 }
 
+pub mod annotations;
+pub mod discovery;
+pub mod docs;
+pub mod imports;
 mod just_command_parser;
+pub mod variables;
 
 // AST parser module (feature-gated)
 #[cfg(feature = "ast-parser")]
 pub mod ast;
 
+pub use annotations::RecipeAnnotation;
 pub use just_command_parser::JustCommandParser;
 
 // Re-export AST parser types when feature is enabled
@@ -324,6 +330,8 @@ impl JustfileParser {
                 confirm_message: None, // Legacy parser doesn't extract this
                 doc: None,   // Legacy parser doesn't extract this
                 attributes: Vec::new(), // Legacy parser doesn't extract raw attributes
+                annotations: Vec::new(),
+                long_description: None,
             }))
         } else {
             *index = current_index + 1;
@@ -599,7 +607,36 @@ impl EnhancedJustfileParser {
     }
 
     /// Parse justfile using preference-based parsing with two-tier fallback
+    ///
+    /// Recipe annotations (`# [slot: x]`, `[no-cd]`, ...) and documentation
+    /// are read from the file source and attached to the returned tasks.
     pub fn parse_file(&self, path: &Path) -> Result<Vec<JustTask>> {
+        self.parse_file_using(path, &self.parser_preference)
+    }
+
+    /// Parse justfile with a preference other than the parser's own
+    ///
+    /// Used for per-project overrides, so one justfile that only parses with
+    /// a specific parser doesn't force that parser on every project.
+    pub fn parse_file_using(
+        &self,
+        path: &Path,
+        preference: &ParserPreference,
+    ) -> Result<Vec<JustTask>> {
+        let mut tasks = self.parse_file_with_preference(path, preference)?;
+        if let Ok(content) = std::fs::read_to_string(path) {
+            annotations::apply_annotations(&mut tasks, &content);
+            docs::apply_docs(&mut tasks, &content);
+            sort_by_source(&mut tasks, &content);
+        }
+        Ok(tasks)
+    }
+
+    fn parse_file_with_preference(
+        &self,
+        path: &Path,
+        preference: &ParserPreference,
+    ) -> Result<Vec<JustTask>> {
         // Check if file is empty - return empty task list rather than creating error task
         if let Ok(content) = std::fs::read_to_string(path) {
             if content.trim().is_empty() {
@@ -614,7 +651,7 @@ impl EnhancedJustfileParser {
         let start_time = std::time::Instant::now();
         let mut last_error = None;
 
-        match &self.parser_preference {
+        match preference {
             ParserPreference::Auto => {
                 // Auto mode: AST → CLI fallback
 
@@ -829,12 +866,29 @@ impl EnhancedJustfileParser {
     /// The CLI parser uses `just --summary` which automatically excludes private recipes,
     /// so this method ensures consistent behavior across all parser types.
     pub fn parse_file_for_tools(&self, path: &Path) -> Result<Vec<JustTask>> {
-        let tasks = self.parse_file(path)?;
+        self.parse_file_for_tools_using(path, &self.parser_preference)
+    }
+
+    /// Parse justfile for MCP tool generation with a specific preference
+    pub fn parse_file_for_tools_using(
+        &self,
+        path: &Path,
+        preference: &ParserPreference,
+    ) -> Result<Vec<JustTask>> {
+        let tasks = self.parse_file_using(path, preference)?;
         Ok(tasks.into_iter().filter(|task| !task.is_private).collect())
     }
 
     /// Parse content string using preference-based parsing with two-tier fallback
     pub fn parse_content(&self, content: &str) -> Result<Vec<JustTask>> {
+        let mut tasks = self.parse_content_with_preference(content)?;
+        annotations::apply_annotations(&mut tasks, content);
+        docs::apply_docs(&mut tasks, content);
+        sort_by_source(&mut tasks, content);
+        Ok(tasks)
+    }
+
+    fn parse_content_with_preference(&self, content: &str) -> Result<Vec<JustTask>> {
         // Handle empty content - return empty task list rather than creating error task
         if content.trim().is_empty() {
             tracing::debug!("Content is empty, returning empty task list");
@@ -1202,6 +1256,8 @@ impl EnhancedJustfileParser {
             confirm_message: None,
             doc: None,
             attributes: Vec::new(),
+            annotations: Vec::new(),
+            long_description: None,
         }
     }
 
@@ -1237,6 +1293,8 @@ impl EnhancedJustfileParser {
             confirm_message: None,
             doc: None,
             attributes: Vec::new(),
+            annotations: Vec::new(),
+            long_description: None,
         }
     }
 
@@ -1361,10 +1419,53 @@ impl Default for EnhancedJustfileParser {
     }
 }
 
+/// Put tasks in the order their headers appear in the justfile
+///
+/// `just --summary` lists recipes by name, so the CLI parser loses the
+/// source order `just --list --unsorted` shows. Recipes without a header in
+/// `content`, such as imported ones, keep their order after the others.
+fn sort_by_source(tasks: &mut [JustTask], content: &str) {
+    let mut positions = HashMap::new();
+    for line in content.lines() {
+        // Indented lines belong to recipe bodies
+        if line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        if let Some(name) = annotations::recipe_header_name(line.trim()) {
+            let position = positions.len();
+            positions.entry(name).or_insert(position);
+        }
+    }
+    tasks.sort_by_key(|task| positions.get(&task.name).copied().unwrap_or(usize::MAX));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_tasks_in_source_order() {
+        let content = "zeta:\n    echo z\n\nvalue := \"x\"\n\n[group('ops')]\ndeploy:\n    echo d\n\nalpha:\n    echo a\n";
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("justfile");
+        std::fs::write(&path, content).unwrap();
+
+        let mut preferences = vec![ParserPreference::Auto];
+        if EnhancedJustfileParser::is_just_available() {
+            preferences.push(ParserPreference::Cli);
+        }
+        for preference in preferences {
+            let parser = EnhancedJustfileParser::new_with_preference(preference.clone()).unwrap();
+            let names: Vec<String> = parser
+                .parse_file(&path)
+                .unwrap()
+                .into_iter()
+                .map(|task| task.name)
+                .collect();
+            assert_eq!(names, ["zeta", "deploy", "alpha"], "{preference}");
+        }
+    }
+
     #[test]
     fn test_parser_creation() {
         let parser = JustfileParser::new();
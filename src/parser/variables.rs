@@ -0,0 +1,122 @@
+//! Justfile variable discovery for `var_*` override arguments
+//!
+//! Top-level assignments (`version := "1.0"`, `export TAG := ...`) can be
+//! overridden at call time. Recipes that reference a variable in an
+//! interpolation (`{{version}}`) expose it as an optional `var_<name>` tool
+//! argument, which the executor forwards as a just variable override.
+
+use std::collections::HashSet;
+
+/// Prefix for tool arguments that override justfile variables
+pub const VARIABLE_ARG_PREFIX: &str = "var_";
+
+/// A top-level variable assignment in a justfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JustfileVariable {
+    pub name: String,
+    /// Right-hand side of the assignment as written in the source
+    pub expression: String,
+}
+
+impl JustfileVariable {
+    /// Tool argument name for this variable (e.g. `var_version`)
+    pub fn argument_name(&self) -> String {
+        format!("{VARIABLE_ARG_PREFIX}{}", self.name)
+    }
+}
+
+/// Extract top-level variable assignments from justfile content
+pub fn extract_variables(content: &str) -> Vec<JustfileVariable> {
+    let mut variables = Vec::new();
+    let mut seen = HashSet::new();
+
+    for line in content.lines() {
+        if line.starts_with(char::is_whitespace) {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line).trim();
+        let Some((name, expression)) = line.split_once(":=") else {
+            continue;
+        };
+        let name = name.trim();
+
+        if is_identifier(name) && seen.insert(name.to_string()) {
+            variables.push(JustfileVariable {
+                name: name.to_string(),
+                expression: expression.trim().to_string(),
+            });
+        }
+    }
+
+    variables
+}
+
+/// Return the variables that are interpolated (`{{ name }}`) in a recipe body
+pub fn referenced_variables<'a>(
+    body: &str,
+    variables: &'a [JustfileVariable],
+) -> Vec<&'a JustfileVariable> {
+    let mut identifiers = HashSet::new();
+    let mut rest = body;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let expression = &after[..end];
+        identifiers.extend(
+            expression
+                .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+                .filter(|s| is_identifier(s)),
+        );
+        rest = &after[end + 2..];
+    }
+
+    variables
+        .iter()
+        .filter(|v| identifiers.contains(v.name.as_str()))
+        .collect()
+}
+
+pub(crate) fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_variables() {
+        let content = r#"
+version := "1.0.0"
+export TAG := version + "-dev"
+set shell := ["bash", "-c"]
+alias b := build
+
+build target="debug":
+    echo {{version}}
+"#;
+        let variables = extract_variables(content);
+        let names: Vec<_> = variables.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["version", "TAG"]);
+        assert_eq!(variables[0].expression, "\"1.0.0\"");
+        assert_eq!(variables[1].argument_name(), "var_TAG");
+    }
+
+    #[test]
+    fn test_referenced_variables() {
+        let variables =
+            extract_variables("version := \"1\"\nregistry := \"ghcr.io\"\nunused := \"x\"\n");
+        let referenced = referenced_variables(
+            "docker push {{ registry }}/app:{{version}} {{target}}",
+            &variables,
+        );
+        let names: Vec<_> = referenced.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["version", "registry"]);
+    }
+}
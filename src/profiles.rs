@@ -0,0 +1,269 @@
+//! Named Execution Profiles
+//!
+//! A profiles file (`~/.config/just-mcp/profiles.yaml` by default) bundles
+//! the environment variables, default argument values and allowed recipes of
+//! a context such as `staging` or `prod`:
+//!
+//! ```yaml
+//! staging:
+//!   description: Staging cluster
+//!   env:
+//!     DEPLOY_ENV: staging
+//!   parameters:
+//!     region: eu-west-1
+//!   recipes: [deploy, status]
+//! ```
+//!
+//! Clients select a profile with a `profile` argument on a recipe call, or
+//! for the rest of their session with `_admin_use_profile`; `--profile` sets
+//! the profile every session starts with. The executor then sets the
+//! profile's environment variables, fills in its parameter values where the
+//! caller gave none, and refuses recipes missing from `recipes` when that
+//! list is given. Explicit arguments and environment variables win.
+//!
+//! These are distinct from the `# [profile: ...]` annotations of a single
+//! recipe. A `profile` argument naming one of the recipe's own profiles
+//! selects that one.
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// One named profile as written in the profiles file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NamedProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Environment variables set for recipes run under the profile
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Argument values used when the caller gives none
+    #[serde(default)]
+    pub parameters: BTreeMap<String, serde_json::Value>,
+    /// Recipes that may run under the profile; all when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipes: Option<Vec<String>>,
+}
+
+impl NamedProfile {
+    /// Whether `recipe` may run under this profile
+    pub fn allows(&self, recipe: &str) -> bool {
+        self.recipes
+            .as_ref()
+            .is_none_or(|recipes| recipes.iter().any(|r| r == recipe))
+    }
+
+    /// One-line description for tool schemas
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(description) = &self.description {
+            parts.push(description.clone());
+        }
+        if !self.env.is_empty() {
+            let keys: Vec<&str> = self.env.keys().map(String::as_str).collect();
+            parts.push(format!("sets {}", keys.join(", ")));
+        }
+        if !self.parameters.is_empty() {
+            let presets: Vec<String> = self
+                .parameters
+                .iter()
+                .map(|(key, value)| match value {
+                    serde_json::Value::String(s) => format!("{key}={s}"),
+                    other => format!("{key}={other}"),
+                })
+                .collect();
+            parts.push(presets.join(", "));
+        }
+        parts.join("; ")
+    }
+}
+
+/// The profiles loaded from the profiles file, by name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProfileSet {
+    profiles: BTreeMap<String, NamedProfile>,
+}
+
+impl ProfileSet {
+    /// Parse a profiles file's contents
+    pub fn parse(content: &str) -> Result<Self> {
+        let profiles: Option<BTreeMap<String, NamedProfile>> = serde_yaml::from_str(content)
+            .map_err(|e| Error::Other(format!("Invalid profiles file: {e}")))?;
+        Ok(Self {
+            profiles: profiles.unwrap_or_default(),
+        })
+    }
+
+    /// Load the profiles file at `path`; a missing file defines no profiles
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => {
+                Self::parse(&content).map_err(|e| Error::Other(format!("{}: {e}", path.display())))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&NamedProfile> {
+        self.profiles.get(name)
+    }
+
+    /// Profile names, sorted
+    pub fn names(&self) -> Vec<&str> {
+        self.profiles.keys().map(String::as_str).collect()
+    }
+
+    /// Profiles `recipe` may run under, sorted by name
+    pub fn for_recipe<'a>(
+        &'a self,
+        recipe: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a NamedProfile)> + 'a {
+        self.profiles
+            .iter()
+            .filter(move |(_, profile)| profile.allows(recipe))
+            .map(|(name, profile)| (name.as_str(), profile))
+    }
+
+    /// Look up a profile, failing with the available names
+    pub fn require(&self, name: &str) -> Result<&NamedProfile> {
+        self.get(name).ok_or_else(|| {
+            Error::InvalidParameter(format!(
+                "Unknown profile '{name}' (available: {})",
+                if self.is_empty() {
+                    "none".to_string()
+                } else {
+                    self.names().join(", ")
+                }
+            ))
+        })
+    }
+
+    /// Apply profile `name` to a call of `recipe`
+    ///
+    /// Fills in the profile's parameter values for which `accepts` holds
+    /// and the caller gave none, and adds its environment variables unless
+    /// the caller set them.
+    pub fn apply(
+        &self,
+        name: &str,
+        recipe: &str,
+        parameters: &mut HashMap<String, serde_json::Value>,
+        environment: &mut HashMap<String, String>,
+        accepts: impl Fn(&str) -> bool,
+    ) -> Result<()> {
+        let profile = self.require(name)?;
+        if !profile.allows(recipe) {
+            return Err(Error::InvalidParameter(format!(
+                "Recipe '{recipe}' is not allowed under profile '{name}'"
+            )));
+        }
+        for (key, value) in &profile.parameters {
+            if accepts(key) {
+                parameters
+                    .entry(key.clone())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+        for (key, value) in &profile.env {
+            environment
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Result of `_admin_use_profile`
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileSelection {
+    /// Profile the session's recipe calls now run under
+    pub profile: Option<String>,
+    /// Profile they ran under before
+    pub previous: Option<String>,
+    /// Names of the profiles defined
+    pub available: Vec<String>,
+}
+
+/// Default profiles file: `$XDG_CONFIG_HOME/just-mcp/profiles.yaml`, or
+/// `~/.config/just-mcp/profiles.yaml`
+pub fn default_profiles_file() -> Option<PathBuf> {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| Some(dirs::home_dir()?.join(".config")))?;
+    Some(config.join("just-mcp").join("profiles.yaml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROFILES: &str = r#"
+staging:
+  description: Staging cluster
+  env:
+    DEPLOY_ENV: staging
+  parameters:
+    region: eu-west-1
+    replicas: 2
+prod:
+  env:
+    DEPLOY_ENV: prod
+  recipes: [status]
+"#;
+
+    #[test]
+    fn test_parse_profiles() {
+        let profiles = ProfileSet::parse(PROFILES).unwrap();
+        assert_eq!(profiles.names(), vec!["prod", "staging"]);
+        let staging = profiles.get("staging").unwrap();
+        assert_eq!(staging.parameters["replicas"], serde_json::json!(2));
+        assert_eq!(
+            staging.summary(),
+            "Staging cluster; sets DEPLOY_ENV; region=eu-west-1, replicas=2"
+        );
+        let names: Vec<&str> = profiles.for_recipe("deploy").map(|(n, _)| n).collect();
+        assert_eq!(names, vec!["staging"]);
+
+        assert!(ProfileSet::parse("").unwrap().is_empty());
+        assert!(ProfileSet::parse("staging:\n  envs: {}\n").is_err());
+    }
+
+    #[test]
+    fn test_apply_profile() {
+        let profiles = ProfileSet::parse(PROFILES).unwrap();
+        let mut parameters = HashMap::new();
+        parameters.insert("region".to_string(), serde_json::json!("us-east-1"));
+        let mut environment = HashMap::new();
+        profiles
+            .apply(
+                "staging",
+                "deploy",
+                &mut parameters,
+                &mut environment,
+                |key| key != "replicas",
+            )
+            .unwrap();
+        assert_eq!(parameters["region"], serde_json::json!("us-east-1"));
+        assert!(!parameters.contains_key("replicas"));
+        assert_eq!(environment["DEPLOY_ENV"], "staging");
+
+        let err = profiles
+            .apply("prod", "deploy", &mut parameters, &mut environment, |_| {
+                true
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("not allowed under profile 'prod'"));
+        let err = profiles
+            .apply("dev", "deploy", &mut parameters, &mut environment, |_| true)
+            .unwrap_err();
+        assert!(err.to_string().contains("available: prod, staging"));
+    }
+}
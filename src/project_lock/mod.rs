@@ -0,0 +1,326 @@
+//! Advisory cross-process locks on project directories
+//!
+//! Several just-mcp instances, or a server and someone running the admin CLI,
+//! can operate on the same repository. A [`ProjectLock`] serialises justfile
+//! edits (and, optionally, `# [exclusive]` recipes) between them through a
+//! lock file in the project directory.
+//!
+//! The lock is advisory: only processes that use it are excluded. Holders
+//! refresh the lock file's modification time while they run, so a lock whose
+//! holder crashed is detected as stale, either because the holder process is
+//! gone (same host) or because the heartbeat stopped, and is taken over.
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+/// Name of the lock file created in the project directory
+pub const LOCK_FILE_NAME: &str = ".just-mcp.lock";
+
+/// How project locks are acquired
+#[derive(Debug, Clone)]
+pub struct LockConfig {
+    /// How long to wait for another holder before giving up
+    pub timeout: Duration,
+    /// Age of the last heartbeat after which a lock is considered abandoned
+    pub stale_after: Duration,
+    /// Delay between acquisition attempts
+    pub poll_interval: Duration,
+}
+
+impl Default for LockConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            stale_after: Duration::from_secs(30),
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+impl LockConfig {
+    /// Interval at which holders refresh their heartbeat
+    fn heartbeat_interval(&self) -> Duration {
+        (self.stale_after / 3).max(Duration::from_millis(10))
+    }
+}
+
+/// Holder information recorded in the lock file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockHolder {
+    pub pid: u32,
+    pub hostname: String,
+    /// What the holder is doing, e.g. `create_recipe`
+    pub purpose: String,
+    /// Unix timestamp (seconds) at which the lock was acquired
+    pub acquired_at: u64,
+}
+
+impl LockHolder {
+    fn current(purpose: &str) -> Self {
+        Self {
+            pid: std::process::id(),
+            hostname: hostname(),
+            purpose: purpose.to_string(),
+            acquired_at: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl std::fmt::Display for LockHolder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "pid {} on {} ({})",
+            self.pid, self.hostname, self.purpose
+        )
+    }
+}
+
+/// A held project lock, released on drop
+#[derive(Debug)]
+pub struct ProjectLock {
+    path: PathBuf,
+    heartbeat: JoinHandle<()>,
+}
+
+impl ProjectLock {
+    /// Acquire the lock for a project directory, waiting up to `config.timeout`
+    ///
+    /// Stale locks are removed and taken over. Fails with
+    /// [`Error::Timeout`] naming the current holder if the lock stays busy.
+    pub async fn acquire(dir: &Path, purpose: &str, config: &LockConfig) -> Result<Self> {
+        let path = dir.join(LOCK_FILE_NAME);
+        let holder = LockHolder::current(purpose);
+        let deadline = tokio::time::Instant::now() + config.timeout;
+
+        loop {
+            match try_create(&path, &holder) {
+                Ok(()) => {
+                    debug!("Acquired project lock {} for {}", path.display(), purpose);
+                    let heartbeat = spawn_heartbeat(path.clone(), config.heartbeat_interval());
+                    return Ok(Self { path, heartbeat });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e.into()),
+            }
+
+            let current = read_holder(&path);
+            if is_stale(&path, current.as_ref(), config.stale_after) {
+                warn!(
+                    "Removing stale project lock {} held by {}",
+                    path.display(),
+                    current
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_else(|| "unknown holder".to_string())
+                );
+                // Only remove the lock we judged stale, not a fresh one that
+                // replaced it in the meantime
+                if read_holder(&path) == current {
+                    let _ = std::fs::remove_file(&path);
+                }
+                continue;
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                return Err(Error::Timeout(format!(
+                    "Project lock {} is held by {}",
+                    path.display(),
+                    current
+                        .map(|h| h.to_string())
+                        .unwrap_or_else(|| "another process".to_string())
+                )));
+            }
+            tokio::time::sleep(config.poll_interval).await;
+        }
+    }
+
+    /// Path of the lock file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ProjectLock {
+    fn drop(&mut self) {
+        self.heartbeat.abort();
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            warn!(
+                "Failed to release project lock {}: {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+}
+
+fn try_create(path: &Path, holder: &LockHolder) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(serde_json::to_string(holder)?.as_bytes())
+}
+
+fn read_holder(path: &Path) -> Option<LockHolder> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Refresh the lock file's modification time until aborted
+fn spawn_heartbeat(path: PathBuf, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            let touched = File::options()
+                .write(true)
+                .open(&path)
+                .and_then(|file| file.set_modified(SystemTime::now()));
+            if let Err(e) = touched {
+                warn!("Failed to refresh project lock {}: {}", path.display(), e);
+            }
+        }
+    })
+}
+
+/// Whether a lock was abandoned by its holder
+///
+/// A lock on this host whose holder process no longer exists is stale
+/// immediately; otherwise it is stale once its heartbeat is older than
+/// `stale_after`. An unreadable lock file is judged by age alone.
+fn is_stale(path: &Path, holder: Option<&LockHolder>, stale_after: Duration) -> bool {
+    if let Some(holder) = holder {
+        if holder.hostname == hostname() && !process_alive(holder.pid) {
+            return true;
+        }
+    }
+
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age > stale_after)
+}
+
+/// Whether a process exists; assumed alive where this cannot be checked
+fn process_alive(pid: u32) -> bool {
+    if cfg!(target_os = "linux") {
+        Path::new("/proc").join(pid.to_string()).exists()
+    } else {
+        true
+    }
+}
+
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn quick_config() -> LockConfig {
+        LockConfig {
+            timeout: Duration::from_millis(200),
+            stale_after: Duration::from_secs(30),
+            poll_interval: Duration::from_millis(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lock_excludes_second_holder() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = quick_config();
+
+        let lock = ProjectLock::acquire(temp_dir.path(), "first", &config)
+            .await
+            .unwrap();
+        assert!(lock.path().exists());
+        let holder = read_holder(lock.path()).unwrap();
+        assert_eq!(holder.pid, std::process::id());
+        assert_eq!(holder.purpose, "first");
+
+        let err = ProjectLock::acquire(temp_dir.path(), "second", &config)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Timeout(_)));
+        assert!(err.to_string().contains("(first)"));
+
+        drop(lock);
+        assert!(!temp_dir.path().join(LOCK_FILE_NAME).exists());
+        ProjectLock::acquire(temp_dir.path(), "second", &config)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_waiter_acquires_after_release() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = LockConfig {
+            timeout: Duration::from_secs(5),
+            ..quick_config()
+        };
+
+        let lock = ProjectLock::acquire(temp_dir.path(), "first", &config)
+            .await
+            .unwrap();
+        let dir = temp_dir.path().to_path_buf();
+        let waiter_config = config.clone();
+        let waiter = tokio::spawn(async move {
+            ProjectLock::acquire(&dir, "second", &waiter_config)
+                .await
+                .map(|_| ())
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        drop(lock);
+        waiter.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stale_locks_are_taken_over() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(LOCK_FILE_NAME);
+
+        // A holder on this host whose process is gone
+        let dead = LockHolder {
+            pid: u32::MAX,
+            ..LockHolder::current("crashed")
+        };
+        try_create(&path, &dead).unwrap();
+        if cfg!(target_os = "linux") {
+            ProjectLock::acquire(temp_dir.path(), "next", &quick_config())
+                .await
+                .unwrap();
+        }
+
+        // A holder elsewhere whose heartbeat stopped
+        let remote = LockHolder {
+            hostname: "elsewhere".to_string(),
+            ..LockHolder::current("remote")
+        };
+        let _ = std::fs::remove_file(&path);
+        try_create(&path, &remote).unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(60))
+            .unwrap();
+        ProjectLock::acquire(temp_dir.path(), "next", &quick_config())
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,390 @@
+//! Argument Extraction from Natural Language Requests
+//!
+//! Fills a recipe's parameters from the words of a do-it request by matching
+//! them against the tool's input schema, so "deploy to staging with version
+//! 1.2.3" becomes `env=staging`, `version=1.2.3`. Matching is heuristic and
+//! conservative; each word is used for at most one parameter. In order of
+//! precedence a value comes from:
+//!
+//! 1. An explicit `name=value`, `--name=value` or `--name value`
+//! 2. The parameter's name followed by a value ("version 1.2.3", "env is prod")
+//! 3. One of the parameter's `enum` values anywhere in the request
+//! 4. A destination after "to", "on", "in" or "against", for environment-like
+//!    parameters (`env`, `environment`, `stage`, `target`, ...)
+//! 5. A version-like word (`1.2.3`, `v2.0`) for version-like parameters
+
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Parameter names that take a deployment destination
+const DESTINATION_PARAMETERS: &[&str] = &[
+    "env",
+    "environment",
+    "stage",
+    "target",
+    "destination",
+    "dest",
+    "cluster",
+    "region",
+];
+
+/// Words that introduce a destination
+const DESTINATION_PREPOSITIONS: &[&str] = &["to", "on", "in", "into", "against"];
+
+/// Words between a parameter name and its value
+const CONNECTORS: &[&str] = &["=", ":", "is", "to", "of", "as", "set", "at"];
+
+/// Words skipped between a preposition and a destination
+const ARTICLES: &[&str] = &["a", "an", "the"];
+
+/// Words that are never values
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "the", "and", "or", "with", "for", "please", "it", "this", "that", "using",
+];
+
+/// Parameter values found in a request
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExtractedArguments {
+    /// Values by parameter name, converted to the schema's type
+    pub values: HashMap<String, Value>,
+    /// Required parameters without a value, in schema order
+    pub missing: Vec<String>,
+}
+
+impl ExtractedArguments {
+    /// Whether every required parameter has a value
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// `name=value` pairs, sorted by name
+    pub fn summary(&self) -> String {
+        let mut pairs: Vec<String> = self
+            .values
+            .iter()
+            .map(|(name, value)| match value {
+                Value::String(value) => format!("{name}={value}"),
+                value => format!("{name}={value}"),
+            })
+            .collect();
+        pairs.sort();
+        pairs.join(", ")
+    }
+}
+
+struct Parameter<'a> {
+    name: &'a str,
+    schema: &'a Value,
+}
+
+impl Parameter<'_> {
+    fn normalized(&self) -> String {
+        normalize(self.name)
+    }
+
+    fn enum_values(&self) -> Vec<&str> {
+        self.schema["enum"]
+            .as_array()
+            .map(|values| values.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    fn is_destination(&self) -> bool {
+        let name = self.normalized();
+        DESTINATION_PARAMETERS
+            .iter()
+            .any(|candidate| name == *candidate || name.ends_with(&format!("_{candidate}")))
+    }
+
+    fn is_version(&self) -> bool {
+        let name = self.normalized();
+        name.contains("version") || name == "tag" || name == "release"
+    }
+
+    /// Convert a word to the parameter's type; `None` if it doesn't fit
+    fn convert(&self, word: &str) -> Option<Value> {
+        let enum_values = self.enum_values();
+        if !enum_values.is_empty() {
+            return enum_values
+                .iter()
+                .find(|value| value.eq_ignore_ascii_case(word))
+                .map(|value| Value::String(value.to_string()));
+        }
+        match self.schema["type"].as_str() {
+            Some("integer") => word.parse::<i64>().ok().map(Value::from),
+            Some("number") => word.parse::<f64>().ok().map(Value::from),
+            Some("boolean") => match word.to_ascii_lowercase().as_str() {
+                "true" | "yes" | "on" => Some(Value::Bool(true)),
+                "false" | "no" | "off" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            _ => Some(Value::String(word.to_string())),
+        }
+    }
+}
+
+/// Extract values for the parameters in `schema` from `request`
+pub fn extract_arguments(request: &str, schema: &Value) -> ExtractedArguments {
+    let parameters: Vec<Parameter> = schema["properties"]
+        .as_object()
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, schema)| Parameter { name, schema })
+                .collect()
+        })
+        .unwrap_or_default();
+    let words: Vec<&str> = request
+        .split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| "\"'`()[]".contains(c))
+                .trim_end_matches([',', ';', '!', '?', '.'])
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let mut used = vec![false; words.len()];
+    let mut values = HashMap::new();
+
+    // 1. Explicit name=value and --name value
+    for parameter in &parameters {
+        let name = parameter.normalized();
+        for i in 0..words.len() {
+            if used[i] {
+                continue;
+            }
+            let word = words[i].trim_start_matches("--");
+            if let Some((key, value)) = word.split_once('=') {
+                if normalize(key) == name {
+                    if let Some(value) = parameter.convert(value) {
+                        used[i] = true;
+                        values.insert(parameter.name.to_string(), value);
+                        break;
+                    }
+                }
+            } else if words[i].starts_with("--") && normalize(word) == name && i + 1 < words.len() {
+                if let Some(value) = parameter.convert(words[i + 1]) {
+                    used[i] = true;
+                    used[i + 1] = true;
+                    values.insert(parameter.name.to_string(), value);
+                    break;
+                }
+            }
+        }
+    }
+
+    // 2. The parameter's name followed by its value
+    for parameter in &parameters {
+        if values.contains_key(parameter.name) {
+            continue;
+        }
+        let name_words: Vec<String> = parameter
+            .normalized()
+            .split('_')
+            .map(str::to_string)
+            .collect();
+        'search: for start in 0..words.len() {
+            let end = start + name_words.len();
+            if end > words.len()
+                || used[start..end].iter().any(|used| *used)
+                || !words[start..end]
+                    .iter()
+                    .zip(&name_words)
+                    .all(|(word, name)| word.eq_ignore_ascii_case(name))
+            {
+                continue;
+            }
+            let mut i = end;
+            while i < words.len() && is_connector(words[i]) {
+                i += 1;
+            }
+            if i < words.len() && !used[i] && !is_stop_word(words[i]) {
+                if let Some(value) = parameter.convert(words[i]) {
+                    used[start..=i].iter_mut().for_each(|used| *used = true);
+                    values.insert(parameter.name.to_string(), value);
+                    break 'search;
+                }
+            }
+        }
+    }
+
+    // 3. Enum values anywhere in the request
+    for parameter in &parameters {
+        if values.contains_key(parameter.name) || parameter.enum_values().is_empty() {
+            continue;
+        }
+        if let Some(i) =
+            (0..words.len()).find(|&i| !used[i] && parameter.convert(words[i]).is_some())
+        {
+            used[i] = true;
+            values.insert(
+                parameter.name.to_string(),
+                parameter.convert(words[i]).unwrap(),
+            );
+        }
+    }
+
+    // 4. Destinations after a preposition
+    for parameter in &parameters {
+        if values.contains_key(parameter.name) || !parameter.is_destination() {
+            continue;
+        }
+        let found = (0..words.len())
+            .filter(|&i| DESTINATION_PREPOSITIONS.contains(&words[i].to_ascii_lowercase().as_str()))
+            .filter_map(|i| {
+                (i + 1..words.len())
+                    .find(|&j| !ARTICLES.contains(&words[j].to_ascii_lowercase().as_str()))
+            })
+            .find(|&j| {
+                !used[j]
+                    && !is_stop_word(words[j])
+                    && !is_version(words[j])
+                    && parameter.convert(words[j]).is_some()
+            });
+        if let Some(j) = found {
+            used[j] = true;
+            values.insert(
+                parameter.name.to_string(),
+                parameter.convert(words[j]).unwrap(),
+            );
+        }
+    }
+
+    // 5. Version-like words
+    for parameter in &parameters {
+        if values.contains_key(parameter.name) || !parameter.is_version() {
+            continue;
+        }
+        if let Some(i) = (0..words.len()).find(|&i| !used[i] && is_version(words[i])) {
+            used[i] = true;
+            values.insert(parameter.name.to_string(), Value::from(words[i]));
+        }
+    }
+
+    let missing = schema["required"]
+        .as_array()
+        .map(|required| {
+            required
+                .iter()
+                .filter_map(Value::as_str)
+                .filter(|name| !values.contains_key(*name))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ExtractedArguments { values, missing }
+}
+
+/// Lowercase with `-` as `_`, so `target-env`, `TARGET_ENV` and
+/// `target_env` compare equal
+fn normalize(name: &str) -> String {
+    name.to_ascii_lowercase().replace('-', "_")
+}
+
+fn is_connector(word: &str) -> bool {
+    CONNECTORS.contains(&word.to_ascii_lowercase().as_str())
+}
+
+fn is_stop_word(word: &str) -> bool {
+    STOP_WORDS.contains(&word.to_ascii_lowercase().as_str()) || is_connector(word)
+}
+
+fn is_version(word: &str) -> bool {
+    Regex::new(r"^v?\d+(\.\d+){1,3}([-+][0-9A-Za-z.-]+)?$")
+        .unwrap()
+        .is_match(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(properties: Value, required: &[&str]) -> Value {
+        json!({"type": "object", "properties": properties, "required": required})
+    }
+
+    #[test]
+    fn test_destination_and_version_from_prose() {
+        let schema = schema(
+            json!({"env": {"type": "string"}, "version": {"type": "string"}}),
+            &["env", "version"],
+        );
+        let extracted = extract_arguments("deploy to staging with version 1.2.3", &schema);
+        assert!(extracted.is_complete());
+        assert_eq!(extracted.values["env"], json!("staging"));
+        assert_eq!(extracted.values["version"], json!("1.2.3"));
+        assert_eq!(extracted.summary(), "env=staging, version=1.2.3");
+
+        let extracted = extract_arguments("ship v2.0.1 to prod.", &schema);
+        assert_eq!(extracted.values["env"], json!("prod"));
+        assert_eq!(extracted.values["version"], json!("v2.0.1"));
+    }
+
+    #[test]
+    fn test_explicit_and_named_values() {
+        let schema = schema(
+            json!({
+                "target_env": {"type": "string"},
+                "replicas": {"type": "integer"},
+                "dry-run": {"type": "boolean"}
+            }),
+            &["target_env", "replicas"],
+        );
+        let extracted = extract_arguments(
+            "scale target env is canary, replicas=3 --dry-run yes",
+            &schema,
+        );
+        assert_eq!(extracted.values["target_env"], json!("canary"));
+        assert_eq!(extracted.values["replicas"], json!(3));
+        assert_eq!(extracted.values["dry-run"], json!(true));
+
+        // A value of the wrong type isn't taken
+        let extracted = extract_arguments("scale replicas=many", &schema);
+        assert_eq!(extracted.missing, vec!["target_env", "replicas"]);
+    }
+
+    #[test]
+    fn test_enum_values_match_anywhere() {
+        let schema = schema(
+            json!({"profile": {"type": "string", "enum": ["debug", "release"]}}),
+            &[],
+        );
+        let extracted = extract_arguments("build a Release binary", &schema);
+        assert_eq!(extracted.values["profile"], json!("release"));
+        assert!(extract_arguments("build it", &schema).values.is_empty());
+    }
+
+    #[test]
+    fn test_only_missing_required_parameters_are_reported() {
+        let schema = schema(
+            json!({
+                "env": {"type": "string"},
+                "version": {"type": "string"},
+                "notes": {"type": "string", "default": ""}
+            }),
+            &["env", "version"],
+        );
+        let extracted = extract_arguments("deploy to the staging cluster", &schema);
+        assert_eq!(extracted.values["env"], json!("staging"));
+        assert_eq!(extracted.missing, vec!["version"]);
+        assert!(!extracted.values.contains_key("notes"));
+
+        let extracted = extract_arguments("deploy it please", &schema);
+        assert!(extracted.values.is_empty());
+        assert_eq!(extracted.missing, vec!["env", "version"]);
+    }
+
+    #[test]
+    fn test_words_are_used_once() {
+        let schema = schema(
+            json!({"env": {"type": "string"}, "target": {"type": "string"}}),
+            &["env", "target"],
+        );
+        let extracted = extract_arguments("deploy to staging", &schema);
+        assert_eq!(extracted.values.len(), 1);
+        assert_eq!(extracted.missing.len(), 1);
+    }
+}
@@ -2,15 +2,19 @@
 //!
 //! This module implements the main "do-it" prompt that provides natural language
 //! task execution. It uses semantic search to find matching justfile tasks and
-//! provides intelligent explanations and safety checks.
+//! provides intelligent explanations and safety checks. Parameter values are
+//! extracted from the request against the matched recipe's schema, and only
+//! required parameters the request doesn't mention are asked for.
 
 use crate::error::Result;
 use crate::prompts::{
+    arguments::{extract_arguments, ExtractedArguments},
     confirmation::ConfirmationManager,
     search_adapter::{SearchAdapter, SearchResponse},
     traits::{Prompt, PromptConfig, PromptResult, ToolCall},
     PromptArgument, PromptDefinition, PromptMessage, PromptRequest,
 };
+use crate::registry::ToolRegistry;
 use async_trait::async_trait;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -24,6 +28,8 @@ pub struct DoItPrompt {
     confirmation_manager: ConfirmationManager,
     /// Prompt configuration
     config: PromptConfig,
+    /// Tool schemas to extract arguments against
+    tool_registry: Option<Arc<tokio::sync::Mutex<ToolRegistry>>>,
 }
 
 impl DoItPrompt {
@@ -33,9 +39,18 @@ impl DoItPrompt {
             search_adapter,
             confirmation_manager: ConfirmationManager::new(),
             config,
+            tool_registry: None,
         }
     }
 
+    /// Extract arguments for matched tasks from the schemas in `registry`
+    ///
+    /// Without a registry, matched tasks are called without arguments.
+    pub fn with_tool_registry(mut self, registry: Arc<tokio::sync::Mutex<ToolRegistry>>) -> Self {
+        self.tool_registry = Some(registry);
+        self
+    }
+
     /// Create with custom confirmation manager
     pub fn with_confirmation_manager(
         search_adapter: Arc<SearchAdapter>,
@@ -46,6 +61,7 @@ impl DoItPrompt {
             search_adapter,
             confirmation_manager,
             config,
+            tool_registry: None,
         }
     }
 
@@ -92,12 +108,70 @@ impl DoItPrompt {
         // Create explanation message
         let explanation = self.create_explanation_message(best_match, user_request);
 
+        // Fill the task's parameters from the request
+        let (tool_name, arguments) = match self.find_tool_schema(task_name).await {
+            Some((tool_name, schema)) => {
+                let extracted = extract_arguments(user_request, &schema);
+                if !extracted.is_complete() {
+                    return Ok(self.create_missing_arguments_response(
+                        explanation,
+                        &tool_name,
+                        &extracted,
+                        &schema,
+                    ));
+                }
+                (tool_name, extracted)
+            }
+            None => (task_name.clone(), ExtractedArguments::default()),
+        };
+
         // Check if confirmation is needed
         if safety_assessment.should_confirm {
-            self.create_confirmation_response(best_match, explanation, safety_assessment.reason)
+            self.create_confirmation_response(
+                best_match,
+                explanation,
+                safety_assessment.reason,
+                &arguments,
+            )
         } else {
-            self.create_execution_response(best_match, explanation, task_name)
+            self.create_execution_response(best_match, explanation, &tool_name, arguments)
+        }
+    }
+
+    /// Name and input schema of the tool for a matched task
+    ///
+    /// Returns `None` without a tool registry, or if the task has no tool
+    /// or several (the same recipe in more than one watch directory).
+    async fn find_tool_schema(&self, task_name: &str) -> Option<(String, Value)> {
+        let registry = self.tool_registry.as_ref()?.lock().await;
+        match registry.find_recipe_tools(task_name).as_slice() {
+            [tool] => Some((tool.name.clone(), tool.input_schema.clone())),
+            _ => None,
+        }
+    }
+
+    /// Ask for the required parameters the request didn't provide
+    fn create_missing_arguments_response(
+        &self,
+        explanation: String,
+        tool_name: &str,
+        extracted: &ExtractedArguments,
+        schema: &Value,
+    ) -> PromptResult {
+        let mut message = explanation;
+        if !extracted.values.is_empty() {
+            message.push_str(&format!("\n\nFrom your request: {}.", extracted.summary()));
         }
+        message.push_str(&format!("\n\nTo run `{tool_name}` I still need:\n"));
+        for name in &extracted.missing {
+            match schema["properties"][name]["description"].as_str() {
+                Some(description) => message.push_str(&format!("- `{name}`: {description}\n")),
+                None => message.push_str(&format!("- `{name}`\n")),
+            }
+        }
+        message.push_str("\nWhat values should I use?");
+
+        PromptResult::messages(vec![PromptMessage::assistant(message)])
     }
 
     /// Handle case where we don't have a confident match
@@ -155,11 +229,13 @@ impl DoItPrompt {
         result: &crate::prompts::search_adapter::SearchResult,
         explanation: String,
         safety_reason: String,
+        arguments: &ExtractedArguments,
     ) -> Result<PromptResult> {
         let task_name = result.metadata.task_name.as_ref().unwrap();
+        let with_arguments = describe_arguments(arguments);
 
         let message = format!(
-            "{explanation}\n\n⚠️  **SAFETY WARNING**: {safety_reason}\n\nThis task appears to be potentially destructive. Should I proceed with executing `just {task_name}`?"
+            "{explanation}\n\n⚠️  **SAFETY WARNING**: {safety_reason}\n\nThis task appears to be potentially destructive. Should I proceed with executing `just {task_name}`{with_arguments}?"
         );
 
         Ok(PromptResult::messages(vec![PromptMessage::assistant(
@@ -173,12 +249,15 @@ impl DoItPrompt {
         _result: &crate::prompts::search_adapter::SearchResult,
         explanation: String,
         task_name: &str,
+        arguments: ExtractedArguments,
     ) -> Result<PromptResult> {
-        let execution_message =
-            format!("{explanation}\n\nI'll execute `just {task_name}` for you.");
+        let execution_message = format!(
+            "{explanation}\n\nI'll execute `just {task_name}`{} for you.",
+            describe_arguments(&arguments)
+        );
 
         // Create tool call for the task
-        let tool_call = ToolCall::simple(task_name);
+        let tool_call = ToolCall::new(task_name, arguments.values);
 
         Ok(PromptResult::with_tool_calls(
             vec![PromptMessage::assistant(execution_message)],
@@ -206,6 +285,15 @@ impl DoItPrompt {
     }
 }
 
+/// " with a=1, b=2" for extracted arguments, or nothing
+fn describe_arguments(arguments: &ExtractedArguments) -> String {
+    if arguments.values.is_empty() {
+        String::new()
+    } else {
+        format!(" with {}", arguments.summary())
+    }
+}
+
 #[async_trait]
 impl Prompt for DoItPrompt {
     fn definition(&self) -> PromptDefinition {
@@ -466,6 +554,90 @@ mod tests {
         assert!(prompt.validate_arguments(&wrong_type).await.is_err());
     }
 
+    fn create_deploy_prompt() -> DoItPrompt {
+        let mut mock_provider = MockSearchProvider::new();
+        for request in ["deploy to staging with version 1.2.3", "deploy to staging"] {
+            mock_provider.add_response(
+                &format!("a command to do {request}"),
+                vec![MockSearchProvider::create_result(
+                    "Deploy a version",
+                    0.9,
+                    Some("deploy"),
+                )],
+            );
+        }
+
+        let mut registry = ToolRegistry::new();
+        registry
+            .add_tool(crate::types::ToolDefinition {
+                name: "deploy".to_string(),
+                description: "Deploy a version".to_string(),
+                input_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "env": {"type": "string"},
+                        "version": {"type": "string", "description": "Version to deploy"}
+                    },
+                    "required": ["env", "version"]
+                }),
+                dependencies: Vec::new(),
+                source_hash: String::new(),
+                last_modified: std::time::SystemTime::now(),
+                internal_name: None,
+                metadata: Default::default(),
+            })
+            .unwrap();
+
+        let config = PromptConfig::default();
+        let adapter = Arc::new(SearchAdapter::with_provider(
+            Arc::new(mock_provider),
+            config.clone(),
+        ));
+        DoItPrompt::new(adapter, config)
+            .with_tool_registry(Arc::new(tokio::sync::Mutex::new(registry)))
+    }
+
+    fn do_it_request(text: &str) -> PromptRequest {
+        PromptRequest {
+            name: "do-it".to_string(),
+            arguments: HashMap::from([("request".to_string(), Value::from(text))]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_arguments_extracted_from_request() {
+        let prompt = create_deploy_prompt();
+
+        let result = prompt
+            .execute(do_it_request("deploy to staging with version 1.2.3"))
+            .await
+            .unwrap();
+        assert_eq!(result.tool_calls.len(), 1);
+        let call = &result.tool_calls[0];
+        assert_eq!(call.tool_name, "deploy");
+        assert_eq!(call.arguments["env"], Value::from("staging"));
+        assert_eq!(call.arguments["version"], Value::from("1.2.3"));
+        assert!(result.messages[0]
+            .content
+            .text
+            .contains("with env=staging, version=1.2.3"));
+    }
+
+    #[tokio::test]
+    async fn test_asks_only_for_missing_required_arguments() {
+        let prompt = create_deploy_prompt();
+
+        let result = prompt
+            .execute(do_it_request("deploy to staging"))
+            .await
+            .unwrap();
+        assert!(result.tool_calls.is_empty());
+        let text = &result.messages[0].content.text;
+        assert!(text.contains("From your request: env=staging."), "{text}");
+        assert!(text.contains("- `version`: Version to deploy"), "{text}");
+        assert!(!text.contains("- `env`"), "{text}");
+    }
+
     #[test]
     fn test_do_it_prompt_builder() {
         let config = PromptConfig::default();
@@ -10,6 +10,7 @@
 //! - **DoItPrompt**: Main prompt for natural language task execution
 //! - **ConfirmationManager**: Safety mechanism for dangerous command detection
 //! - **SearchAdapter**: Bridge between prompts and vector search system
+//! - **UserPrompt**: Team-defined prompts loaded from the user's prompts directory
 //!
 //! # Usage
 //!
@@ -40,12 +41,14 @@
 //! # }
 //! ```
 
+pub mod arguments;
 pub mod confirmation;
 pub mod do_it;
 pub mod registry;
 pub mod search_adapter;
 pub mod templates;
 pub mod traits;
+pub mod user;
 
 use std::collections::HashMap;
 
@@ -55,6 +58,7 @@ pub use registry::PromptRegistry;
 pub use search_adapter::SearchAdapter;
 pub use templates::{create_embedded_prompts, EmbeddedPrompt};
 pub use traits::{Prompt, PromptResult};
+pub use user::UserPrompt;
 
 /// MCP Prompt definition for the protocol
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
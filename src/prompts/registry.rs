@@ -9,9 +9,11 @@ use crate::prompts::{
     do_it::DoItPrompt,
     search_adapter::SearchAdapter,
     traits::{Prompt, PromptConfig, PromptResult},
-    PromptDefinition, PromptRequest, PromptResponse,
+    user, PromptDefinition, PromptMessage, PromptRequest, PromptResponse,
 };
+use crate::registry::ToolRegistry;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -21,6 +23,8 @@ pub struct PromptRegistry {
     prompts: RwLock<HashMap<String, Arc<dyn Prompt>>>,
     /// Configuration for prompt execution
     config: PromptConfig,
+    /// Tool schemas for prompts that fill in recipe arguments
+    tool_registry: Option<Arc<tokio::sync::Mutex<ToolRegistry>>>,
 }
 
 impl PromptRegistry {
@@ -29,9 +33,16 @@ impl PromptRegistry {
         Self {
             prompts: RwLock::new(HashMap::new()),
             config,
+            tool_registry: None,
         }
     }
 
+    /// Give prompts registered afterwards access to the tool registry
+    pub fn with_tool_registry(mut self, registry: Arc<tokio::sync::Mutex<ToolRegistry>>) -> Self {
+        self.tool_registry = Some(registry);
+        self
+    }
+
     /// Create a prompt registry with default configuration
     pub fn with_default_config() -> Self {
         Self::new(PromptConfig::default())
@@ -54,7 +65,10 @@ impl PromptRegistry {
 
     /// Register the do-it prompt with search adapter
     pub async fn register_do_it_prompt(&self, search_adapter: Arc<SearchAdapter>) -> Result<()> {
-        let do_it_prompt = DoItPrompt::new(search_adapter, self.config.clone());
+        let mut do_it_prompt = DoItPrompt::new(search_adapter, self.config.clone());
+        if let Some(registry) = &self.tool_registry {
+            do_it_prompt = do_it_prompt.with_tool_registry(registry.clone());
+        }
         self.register_prompt(Arc::new(do_it_prompt)).await
     }
 
@@ -84,6 +98,50 @@ impl PromptRegistry {
         prompt.execute(request).await
     }
 
+    /// Messages for `prompts/get`, if the prompt renders its own
+    pub async fn get_prompt_messages(
+        &self,
+        request: &PromptRequest,
+    ) -> Result<Option<Vec<PromptMessage>>> {
+        let prompts = self.prompts.read().await;
+        let prompt = prompts.get(&request.name).ok_or_else(|| {
+            crate::error::Error::Other(format!("Prompt '{}' not found", request.name))
+        })?;
+
+        prompt.messages(&request.arguments).await
+    }
+
+    /// Register the user-defined prompts in `dir`
+    ///
+    /// Prompts whose name is already taken, such as `do-it`, are skipped
+    /// with a warning. Returns the number of prompts registered.
+    pub async fn register_user_prompts(
+        &self,
+        dir: &Path,
+        tool_registry: Option<Arc<tokio::sync::Mutex<ToolRegistry>>>,
+    ) -> usize {
+        let mut registered = 0;
+        for prompt in user::load_prompts_dir(dir) {
+            let source = prompt.source().to_path_buf();
+            let prompt = match &tool_registry {
+                Some(registry) => prompt.with_tool_registry(registry.clone()),
+                None => prompt,
+            };
+            match self.register_prompt(Arc::new(prompt)).await {
+                Ok(()) => registered += 1,
+                Err(e) => tracing::warn!("Skipping prompt file {}: {}", source.display(), e),
+            }
+        }
+        if registered > 0 {
+            tracing::info!(
+                "Registered {} user prompts from {}",
+                registered,
+                dir.display()
+            );
+        }
+        registered
+    }
+
     /// Check if a prompt exists
     pub async fn has_prompt(&self, name: &str) -> bool {
         let prompts = self.prompts.read().await;
@@ -173,6 +231,7 @@ pub struct PromptRegistryBuilder {
     config: PromptConfig,
     initialize_defaults: bool,
     search_adapter: Option<Arc<SearchAdapter>>,
+    tool_registry: Option<Arc<tokio::sync::Mutex<ToolRegistry>>>,
 }
 
 impl PromptRegistryBuilder {
@@ -182,6 +241,7 @@ impl PromptRegistryBuilder {
             config: PromptConfig::default(),
             initialize_defaults: true,
             search_adapter: None,
+            tool_registry: None,
         }
     }
 
@@ -203,9 +263,18 @@ impl PromptRegistryBuilder {
         self
     }
 
+    /// Set the tool registry prompts read recipe schemas from
+    pub fn with_tool_registry(mut self, registry: Arc<tokio::sync::Mutex<ToolRegistry>>) -> Self {
+        self.tool_registry = Some(registry);
+        self
+    }
+
     /// Build the prompt registry
     pub async fn build(self) -> Result<PromptRegistry> {
-        let registry = PromptRegistry::new(self.config);
+        let mut registry = PromptRegistry::new(self.config);
+        if let Some(tool_registry) = self.tool_registry {
+            registry = registry.with_tool_registry(tool_registry);
+        }
 
         if self.initialize_defaults {
             if let Some(adapter) = self.search_adapter {
@@ -416,4 +485,42 @@ mod tests {
         assert!(!response.description.is_empty());
         assert!(!response.messages.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_register_user_prompts() {
+        let registry = create_test_registry().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("greet.yaml"),
+            "arguments:\n  - name: who\n    required: true\ntemplate: Greet {{who}}\n",
+        )
+        .unwrap();
+        // Built-in prompts can't be replaced
+        std::fs::write(temp_dir.path().join("do-it.md"), "Something else\n").unwrap();
+
+        assert_eq!(
+            registry.register_user_prompts(temp_dir.path(), None).await,
+            1
+        );
+        assert_eq!(registry.prompt_count().await, 2);
+
+        let mut request = PromptRequest {
+            name: "greet".to_string(),
+            arguments: HashMap::new(),
+        };
+        assert!(registry.get_prompt_messages(&request).await.is_err());
+
+        request
+            .arguments
+            .insert("who".to_string(), Value::String("team".to_string()));
+        let messages = registry.get_prompt_messages(&request).await.unwrap();
+        assert_eq!(messages.unwrap()[0].content.text, "Greet team");
+
+        request.name = "do-it".to_string();
+        assert!(registry
+            .get_prompt_messages(&request)
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
@@ -129,6 +129,18 @@ pub trait Prompt: Send + Sync {
         let _ = arguments;
         Ok(())
     }
+
+    /// Messages returned by `prompts/get` (optional)
+    ///
+    /// Prompts that render a template from their arguments return it here;
+    /// `None` lets the server build a generic request message.
+    async fn messages(
+        &self,
+        arguments: &HashMap<String, Value>,
+    ) -> Result<Option<Vec<PromptMessage>>> {
+        let _ = arguments;
+        Ok(None)
+    }
 }
 
 /// Configuration for prompt execution
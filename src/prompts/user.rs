@@ -0,0 +1,419 @@
+//! User-Defined Prompts
+//!
+//! Teams can codify their own "do-it"-style workflows by dropping prompt
+//! definitions into a prompts directory (`~/.config/just-mcp/prompts/` by
+//! default). Each file defines one prompt:
+//!
+//! - `*.md`: YAML front matter with the prompt's `name`, `description` and
+//!   `arguments`, followed by the template as the Markdown body
+//! - `*.yaml` / `*.yml`: the same fields plus a `template` field
+//!
+//! ```markdown
+//! ---
+//! name: ship-it
+//! description: Test, build and release a version
+//! arguments:
+//!   - name: version
+//!     description: Version to release
+//!     required: true
+//! ---
+//! Release {{version}}: run {{recipe:test}}, then {{recipe:release}} with
+//! version={{version}}. Check {{resource:just://summary/app}} first.
+//! ```
+//!
+//! Templates use `{{...}}` placeholders:
+//!
+//! - `{{argument}}`: the value of a declared argument (empty if omitted)
+//! - `{{recipe:NAME}}`: the MCP tool name of recipe `NAME`, so templates
+//!   don't depend on how tools are named across watch directories
+//! - `{{resource:URI}}`: the resource URI, for the client to read
+//!
+//! Anything else, such as justfile interpolations, is left unchanged.
+
+use crate::error::{Error, Result};
+use crate::prompts::{
+    traits::{Prompt, PromptResult},
+    PromptArgument, PromptDefinition, PromptMessage, PromptRequest,
+};
+use crate::registry::ToolRegistry;
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Prompt definition as written in a prompt file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PromptFile {
+    /// Defaults to the file name without extension
+    name: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    arguments: Vec<PromptFileArgument>,
+    /// Required in YAML files; the body of Markdown files
+    #[serde(default)]
+    template: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PromptFileArgument {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    required: bool,
+}
+
+/// A prompt loaded from a user's prompt file
+pub struct UserPrompt {
+    definition: PromptDefinition,
+    template: String,
+    source: PathBuf,
+    /// Resolves `{{recipe:NAME}}` to tool names
+    tool_registry: Option<Arc<tokio::sync::Mutex<ToolRegistry>>>,
+}
+
+impl UserPrompt {
+    /// Load a prompt from a `.md`, `.yaml` or `.yml` file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content, path)
+    }
+
+    /// Parse a prompt definition; `path` selects the format and provides
+    /// the default name
+    pub fn parse(content: &str, path: &Path) -> Result<Self> {
+        let markdown = path.extension().is_some_and(|ext| ext == "md");
+        let (file, body) = if markdown {
+            let (front_matter, body) = split_front_matter(content);
+            let file = match front_matter {
+                Some(yaml) => parse_yaml(yaml, path)?,
+                None => PromptFile {
+                    name: None,
+                    description: None,
+                    arguments: Vec::new(),
+                    template: None,
+                },
+            };
+            if file.template.is_some() {
+                return Err(invalid(
+                    path,
+                    "Markdown prompts take their template from the body, not a `template` field",
+                ));
+            }
+            (file, Some(body.trim().to_string()))
+        } else {
+            let mut file = parse_yaml(content, path)?;
+            let template = file.template.take();
+            (file, template)
+        };
+
+        let template = body
+            .filter(|template| !template.trim().is_empty())
+            .ok_or_else(|| invalid(path, "prompt has no template"))?;
+        let name = match file.name {
+            Some(name) => name,
+            None => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        };
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_.:".contains(c))
+        {
+            return Err(invalid(
+                path,
+                &format!("invalid prompt name '{name}': use letters, digits, '-', '_', '.' or ':'"),
+            ));
+        }
+
+        Ok(Self {
+            definition: PromptDefinition {
+                description: file
+                    .description
+                    .unwrap_or_else(|| format!("User prompt from {}", path.display())),
+                name,
+                arguments: file
+                    .arguments
+                    .into_iter()
+                    .map(|arg| PromptArgument {
+                        name: arg.name,
+                        description: arg.description,
+                        required: arg.required,
+                    })
+                    .collect(),
+            },
+            template,
+            source: path.to_path_buf(),
+            tool_registry: None,
+        })
+    }
+
+    /// Resolve `{{recipe:NAME}}` placeholders against the tools in `registry`
+    pub fn with_tool_registry(mut self, registry: Arc<tokio::sync::Mutex<ToolRegistry>>) -> Self {
+        self.tool_registry = Some(registry);
+        self
+    }
+
+    /// File the prompt was loaded from
+    pub fn source(&self) -> &Path {
+        &self.source
+    }
+
+    /// Fill the template's placeholders from `arguments` and the tool registry
+    pub async fn render(&self, arguments: &HashMap<String, Value>) -> String {
+        let registry = match &self.tool_registry {
+            Some(registry) => Some(registry.lock().await),
+            None => None,
+        };
+
+        let placeholder = Regex::new(r"\{\{\s*([^{}]+?)\s*\}\}").unwrap();
+        placeholder
+            .replace_all(&self.template, |captures: &regex::Captures| {
+                let key = &captures[1];
+                if let Some(recipe) = key.strip_prefix("recipe:") {
+                    return resolve_recipe(recipe.trim(), registry.as_deref());
+                }
+                if let Some(uri) = key.strip_prefix("resource:") {
+                    return uri.trim().to_string();
+                }
+                if self.definition.arguments.iter().any(|arg| arg.name == key) {
+                    return match arguments.get(key) {
+                        Some(Value::String(value)) => value.clone(),
+                        Some(Value::Null) | None => String::new(),
+                        Some(value) => value.to_string(),
+                    };
+                }
+                captures[0].to_string()
+            })
+            .into_owned()
+    }
+}
+
+#[async_trait]
+impl Prompt for UserPrompt {
+    fn definition(&self) -> PromptDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, request: PromptRequest) -> Result<PromptResult> {
+        Ok(PromptResult::messages(vec![PromptMessage::user(
+            self.render(&request.arguments).await,
+        )]))
+    }
+
+    async fn validate_arguments(&self, arguments: &HashMap<String, Value>) -> Result<()> {
+        for arg in self.definition.arguments.iter().filter(|arg| arg.required) {
+            if matches!(arguments.get(&arg.name), None | Some(Value::Null)) {
+                return Err(Error::InvalidParameter(format!(
+                    "Prompt '{}' requires argument '{}'",
+                    self.definition.name, arg.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    async fn messages(
+        &self,
+        arguments: &HashMap<String, Value>,
+    ) -> Result<Option<Vec<PromptMessage>>> {
+        self.validate_arguments(arguments).await?;
+        Ok(Some(vec![PromptMessage::user(
+            self.render(arguments).await,
+        )]))
+    }
+}
+
+/// Default prompts directory: `$XDG_CONFIG_HOME/just-mcp/prompts`, or
+/// `~/.config/just-mcp/prompts`
+pub fn default_prompts_dir() -> Option<PathBuf> {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| Some(dirs::home_dir()?.join(".config")))?;
+    Some(config.join("just-mcp").join("prompts"))
+}
+
+/// Load every prompt file in `dir`, in file name order
+///
+/// Files that fail to load are logged and skipped so one bad definition
+/// doesn't hide the others. A missing directory yields no prompts.
+pub fn load_prompts_dir(dir: &Path) -> Vec<UserPrompt> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext == "md" || ext == "yaml" || ext == "yml")
+        })
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .filter_map(|path| match UserPrompt::from_file(path) {
+            Ok(prompt) => Some(prompt),
+            Err(e) => {
+                tracing::warn!("Skipping prompt file {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Split `---`-delimited front matter from a Markdown document
+fn split_front_matter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+fn parse_yaml(yaml: &str, path: &Path) -> Result<PromptFile> {
+    serde_yaml::from_str(yaml).map_err(|e| {
+        let location = e.location();
+        Error::Parse {
+            message: format!("{}: {e}", path.display()),
+            line: location.as_ref().map_or(0, |l| l.line()),
+            column: location.as_ref().map_or(0, |l| l.column()),
+        }
+    })
+}
+
+fn invalid(path: &Path, message: &str) -> Error {
+    Error::Other(format!("{}: {message}", path.display()))
+}
+
+/// Tool names for a recipe, or the recipe name if it has no tools
+fn resolve_recipe(recipe: &str, registry: Option<&ToolRegistry>) -> String {
+    let tools = registry
+        .map(|registry| registry.find_recipe_tools(recipe))
+        .unwrap_or_default();
+    if tools.is_empty() {
+        tracing::debug!("Prompt references unknown recipe '{}'", recipe);
+        return recipe.to_string();
+    }
+    tools
+        .iter()
+        .map(|tool| tool.name.as_str())
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ToolDefinition;
+    use tempfile::TempDir;
+
+    const SHIP_IT: &str = "---\nname: ship-it\ndescription: Test and release a version\narguments:\n  - name: version\n    description: Version to release\n    required: true\n  - name: notes\n---\nRelease {{version}}{{notes}}: run {{recipe:test}}, then {{ recipe:release }}.\nSee {{resource:just://summary/app}}; keep {{target}} as is.\n";
+
+    fn tool(name: &str) -> ToolDefinition {
+        ToolDefinition {
+            name: name.to_string(),
+            description: String::new(),
+            input_schema: serde_json::json!({}),
+            dependencies: Vec::new(),
+            source_hash: String::new(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_markdown_prompt_renders_placeholders() {
+        let mut registry = ToolRegistry::new();
+        registry.add_tool(tool("test")).unwrap();
+        registry.add_tool(tool("release@api")).unwrap();
+        registry.add_tool(tool("release@web")).unwrap();
+        registry.add_tool(tool("release-notes")).unwrap();
+
+        let prompt = UserPrompt::parse(SHIP_IT, Path::new("prompts/ship.md"))
+            .unwrap()
+            .with_tool_registry(Arc::new(tokio::sync::Mutex::new(registry)));
+        let definition = prompt.definition();
+        assert_eq!(definition.name, "ship-it");
+        assert_eq!(definition.arguments.len(), 2);
+        assert!(definition.arguments[0].required);
+        assert!(!definition.arguments[1].required);
+
+        let arguments = HashMap::from([("version".to_string(), Value::from("1.2.0"))]);
+        let rendered = prompt.render(&arguments).await;
+        assert_eq!(
+            rendered,
+            "Release 1.2.0: run test, then release@api or release@web.\nSee just://summary/app; keep {{target}} as is."
+        );
+
+        assert!(prompt.validate_arguments(&HashMap::new()).await.is_err());
+        assert!(prompt.validate_arguments(&arguments).await.is_ok());
+    }
+
+    #[test]
+    fn test_yaml_prompt_and_defaults() {
+        let prompt = UserPrompt::parse(
+            "description: Morning checks\ntemplate: Run {{recipe:doctor}}\n",
+            Path::new("morning.yaml"),
+        )
+        .unwrap();
+        assert_eq!(prompt.name(), "morning");
+        assert_eq!(prompt.template, "Run {{recipe:doctor}}");
+
+        let prompt = UserPrompt::parse("Just a template\n", Path::new("plain.md")).unwrap();
+        assert_eq!(prompt.name(), "plain");
+        assert!(prompt.description().contains("plain.md"));
+    }
+
+    #[test]
+    fn test_invalid_prompt_files_are_rejected() {
+        let parse = |content: &str, file: &str| UserPrompt::parse(content, Path::new(file));
+
+        assert!(parse("description: no template\n", "a.yaml").is_err());
+        assert!(parse("---\ndescription: x\n---\n\n", "a.md").is_err());
+        assert!(parse("---\nname: bad name\n---\nbody\n", "a.md").is_err());
+        assert!(parse("---\ntemplate: x\n---\nbody\n", "a.md").is_err());
+        assert!(parse("template: x\nunknown: y\n", "a.yaml").is_err());
+        assert!(matches!(
+            parse("template: [unclosed\n", "a.yaml"),
+            Err(Error::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_load_prompts_dir_skips_invalid_files() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("b.md"), SHIP_IT).unwrap();
+        std::fs::write(temp_dir.path().join("a.yml"), "template: Hi\n").unwrap();
+        std::fs::write(temp_dir.path().join("broken.yaml"), "template: [\n").unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let names: Vec<String> = load_prompts_dir(temp_dir.path())
+            .iter()
+            .map(|prompt| prompt.name())
+            .collect();
+        assert_eq!(names, vec!["a", "ship-it"]);
+        assert!(load_prompts_dir(&temp_dir.path().join("missing")).is_empty());
+    }
+}
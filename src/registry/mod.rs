@@ -8,6 +8,8 @@ use tokio::sync::broadcast;
 pub struct ToolRegistry {
     tools: HashMap<String, ToolDefinition>,
     change_tx: broadcast::Sender<ChangeEvent>,
+    /// Bumped on every change, so cached renderings can tell they're stale
+    revision: u64,
 }
 
 impl ToolRegistry {
@@ -16,6 +18,7 @@ impl ToolRegistry {
         Self {
             tools: HashMap::new(),
             change_tx: tx,
+            revision: 0,
         }
     }
 
@@ -24,6 +27,7 @@ impl ToolRegistry {
         let is_new = !self.tools.contains_key(&display_name);
 
         self.tools.insert(display_name.clone(), tool);
+        self.revision += 1;
 
         if is_new {
             self.notify_change(ChangeType::Added, display_name)?;
@@ -36,11 +40,19 @@ impl ToolRegistry {
 
     pub fn remove_tool(&mut self, name: &str) -> Result<()> {
         if self.tools.remove(name).is_some() {
+            self.revision += 1;
             self.notify_change(ChangeType::Removed, name.to_string())?;
         }
         Ok(())
     }
 
+    /// Replace a tool's definition without a change event, for updates
+    /// clients see no difference in (a new source hash, a new position)
+    pub fn refresh_tool(&mut self, tool: ToolDefinition) {
+        self.tools.insert(tool.name.clone(), tool);
+        self.revision += 1;
+    }
+
     pub fn get_tool(&self, name: &str) -> Option<&ToolDefinition> {
         self.tools.get(name)
     }
@@ -49,12 +61,38 @@ impl ToolRegistry {
         self.tools.values().collect()
     }
 
+    /// Tools for a recipe: the tool named `recipe`, or else the recipe's
+    /// tool in every watch directory (`build@api`, `build_/path/justfile`),
+    /// sorted by name
+    pub fn find_recipe_tools(&self, recipe: &str) -> Vec<&ToolDefinition> {
+        if let Some(tool) = self.tools.get(recipe) {
+            return vec![tool];
+        }
+        let mut tools: Vec<&ToolDefinition> = self
+            .tools
+            .values()
+            .filter(|tool| {
+                tool.name
+                    .strip_prefix(recipe)
+                    .is_some_and(|rest| rest.starts_with('@') || rest.starts_with("_/"))
+            })
+            .collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        tools
+    }
+
     pub fn get_all_tools(&self) -> Vec<ToolDefinition> {
         self.tools.values().cloned().collect()
     }
 
     pub fn clear(&mut self) {
         self.tools.clear();
+        self.revision += 1;
+    }
+
+    /// Counter that changes whenever any tool is added, replaced or removed
+    pub fn revision(&self) -> u64 {
+        self.revision
     }
 
     pub fn subscribe_changes(&self) -> broadcast::Receiver<ChangeEvent> {
@@ -96,6 +134,42 @@ mod tests {
         assert!(registry.list_tools().is_empty());
     }
 
+    #[test]
+    fn test_find_recipe_tools() {
+        let mut registry = ToolRegistry::new();
+        for name in [
+            "build@web",
+            "build@api",
+            "build-docs",
+            "test_/repo/justfile",
+        ] {
+            registry
+                .add_tool(ToolDefinition {
+                    name: name.to_string(),
+                    description: String::new(),
+                    input_schema: serde_json::json!({}),
+                    dependencies: Vec::new(),
+                    source_hash: String::new(),
+                    last_modified: std::time::SystemTime::now(),
+                    internal_name: None,
+                    metadata: Default::default(),
+                })
+                .unwrap();
+        }
+
+        let names = |recipe| -> Vec<String> {
+            registry
+                .find_recipe_tools(recipe)
+                .iter()
+                .map(|tool| tool.name.clone())
+                .collect()
+        };
+        assert_eq!(names("build"), vec!["build@api", "build@web"]);
+        assert_eq!(names("build@web"), vec!["build@web"]);
+        assert_eq!(names("test"), vec!["test_/repo/justfile"]);
+        assert!(names("deploy").is_empty());
+    }
+
     #[test]
     fn test_hash_computation() {
         let hash1 = ToolRegistry::compute_hash("test content");
@@ -1,6 +1,10 @@
 use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
 use std::time::Duration;
-use tracing::{info, warn};
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
+use tracing::{debug, info, warn};
 
 /// Resource limits configuration for task execution
 #[derive(Debug, Clone)]
@@ -17,6 +21,24 @@ pub struct ResourceLimits {
     pub max_output_size: usize,
     /// Kill tasks that exceed limits (vs just warning)
     pub enforce_hard_limits: bool,
+    /// Default maximum number of concurrent executions per project
+    ///
+    /// Projects are keyed by watch directory name (or justfile directory).
+    /// `None` lets a single project use the whole global limit.
+    pub max_concurrent_per_project: Option<usize>,
+    /// Per-project overrides of `max_concurrent_per_project`, keyed by project
+    pub project_concurrency_limits: HashMap<String, usize>,
+    /// Ceilings for limits changed at runtime through `_admin_set_limits`
+    pub runtime_bounds: LimitBounds,
+    /// Parallel jobs passed to `just --jobs` for every recipe; `None` leaves
+    /// just's default
+    pub default_jobs: Option<usize>,
+    /// Per-project overrides of `default_jobs`, keyed by project
+    pub project_jobs: HashMap<String, usize>,
+    /// Per-group overrides, keyed by the recipe's `[group('name')]`
+    pub group_jobs: HashMap<String, usize>,
+    /// Most parallel jobs a single execution may ask for
+    pub max_jobs: usize,
 }
 
 impl Default for ResourceLimits {
@@ -28,21 +50,149 @@ impl Default for ResourceLimits {
             max_concurrent_executions: 10,
             max_output_size: 10 * 1024 * 1024, // 10MB
             enforce_hard_limits: true,
+            max_concurrent_per_project: None,
+            project_concurrency_limits: HashMap::new(),
+            runtime_bounds: LimitBounds::default(),
+            default_jobs: None,
+            project_jobs: HashMap::new(),
+            group_jobs: HashMap::new(),
+            max_jobs: available_cores(),
         }
     }
 }
 
+/// Number of cores available to this process, at least one
+pub fn available_cores() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+impl ResourceLimits {
+    /// Concurrency limit that applies to the given project
+    pub fn project_limit(&self, project: &str) -> usize {
+        self.project_concurrency_limits
+            .get(project)
+            .copied()
+            .or(self.max_concurrent_per_project)
+            .unwrap_or(self.max_concurrent_executions)
+            .max(1)
+    }
+
+    /// Parallel jobs requested for a recipe, before `max_jobs` is applied
+    ///
+    /// The recipe's own `# [jobs: N]` wins over its group's setting, which
+    /// wins over its project's and then the default.
+    pub fn requested_jobs(
+        &self,
+        project: &str,
+        group: Option<&str>,
+        recipe: Option<usize>,
+    ) -> Option<usize> {
+        recipe
+            .or_else(|| group.and_then(|group| self.group_jobs.get(group).copied()))
+            .or_else(|| self.project_jobs.get(project).copied())
+            .or(self.default_jobs)
+            .filter(|jobs| *jobs > 0)
+    }
+
+    /// Apply a runtime change, rejecting values outside `runtime_bounds`
+    pub fn apply(&mut self, changes: &LimitChanges) -> Result<()> {
+        let bounds = &self.runtime_bounds;
+        if let Some(max) = changes.max_concurrent_executions {
+            check_bound(
+                "max_concurrent_executions",
+                max,
+                bounds.max_concurrent_executions,
+            )?;
+        }
+        if let Some(seconds) = changes.timeout_seconds {
+            check_bound(
+                "timeout_seconds",
+                seconds,
+                bounds.max_execution_time.as_secs(),
+            )?;
+        }
+        if let Some(size) = changes.max_output_size {
+            check_bound("max_output_size", size, bounds.max_output_size)?;
+        }
+
+        if let Some(max) = changes.max_concurrent_executions {
+            self.max_concurrent_executions = max;
+        }
+        if let Some(seconds) = changes.timeout_seconds {
+            self.max_execution_time = Duration::from_secs(seconds);
+        }
+        if let Some(size) = changes.max_output_size {
+            self.max_output_size = size;
+        }
+        Ok(())
+    }
+}
+
+fn check_bound<T: PartialOrd + Default + std::fmt::Display>(
+    name: &str,
+    value: T,
+    max: T,
+) -> Result<()> {
+    if value <= T::default() || value > max {
+        return Err(Error::InvalidParameter(format!(
+            "{name} must be between 1 and {max}, got {value}"
+        )));
+    }
+    Ok(())
+}
+
+/// Operator-configured ceilings for limits changed at runtime
+#[derive(Debug, Clone, Serialize)]
+pub struct LimitBounds {
+    pub max_concurrent_executions: usize,
+    pub max_execution_time: Duration,
+    pub max_output_size: usize,
+}
+
+impl Default for LimitBounds {
+    fn default() -> Self {
+        Self {
+            max_concurrent_executions: 32,
+            max_execution_time: Duration::from_secs(3600), // 1 hour
+            max_output_size: 100 * 1024 * 1024,            // 100MB
+        }
+    }
+}
+
+/// A runtime change to resource limits; `None` leaves a limit unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LimitChanges {
+    pub max_concurrent_executions: Option<usize>,
+    pub timeout_seconds: Option<u64>,
+    pub max_output_size: Option<usize>,
+}
+
+/// Fair scheduler state: running counts and waiter queues per project
+#[derive(Default)]
+struct SchedulerState {
+    running: HashMap<String, usize>,
+    waiting: HashMap<String, VecDeque<oneshot::Sender<()>>>,
+    /// Projects with waiters, in round-robin order
+    rotation: VecDeque<String>,
+}
+
 /// Manages resource limits and tracks usage
 pub struct ResourceManager {
-    limits: ResourceLimits,
+    limits: std::sync::RwLock<ResourceLimits>,
     current_executions: std::sync::atomic::AtomicUsize,
+    scheduler: std::sync::Mutex<SchedulerState>,
+    /// One single-permit semaphore per `project/group` exclusion group
+    exclusion_groups: std::sync::Mutex<HashMap<String, Arc<Semaphore>>>,
 }
 
 impl ResourceManager {
     pub fn new(limits: ResourceLimits) -> Self {
         Self {
-            limits,
+            limits: std::sync::RwLock::new(limits),
             current_executions: std::sync::atomic::AtomicUsize::new(0),
+            scheduler: std::sync::Mutex::new(SchedulerState::default()),
+            exclusion_groups: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
@@ -56,10 +206,10 @@ impl ResourceManager {
             .current_executions
             .load(std::sync::atomic::Ordering::Relaxed);
 
-        if current >= self.limits.max_concurrent_executions {
-            return Err(Error::Other(format!(
-                "Maximum concurrent executions ({}) reached",
-                self.limits.max_concurrent_executions
+        let max_concurrent = self.max_concurrent();
+        if current >= max_concurrent {
+            return Err(Error::Busy(format!(
+                "Maximum concurrent executions ({max_concurrent}) reached"
             )));
         }
 
@@ -79,23 +229,217 @@ impl ResourceManager {
         ExecutionGuard { manager: self }
     }
 
+    /// Wait for an execution slot for the given project
+    ///
+    /// Slots are granted immediately while both the global and the project
+    /// limit have room. Otherwise the caller is queued, and freed slots are
+    /// handed out round-robin across projects so a burst against one project
+    /// cannot starve the others.
+    pub async fn acquire(&self, project: &str) -> ExecutionPermit<'_> {
+        let receiver = {
+            let mut state = self.scheduler.lock().unwrap();
+            let queued = state.waiting.get(project).is_some_and(|q| !q.is_empty());
+            if !queued && self.has_capacity(&state, project) {
+                self.grant(&mut state, project);
+                return ExecutionPermit::new(self, project);
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            state
+                .waiting
+                .entry(project.to_string())
+                .or_default()
+                .push_back(sender);
+            if !state.rotation.iter().any(|p| p == project) {
+                state.rotation.push_back(project.to_string());
+            }
+            debug!("Queued execution for project '{}'", project);
+            receiver
+        };
+
+        let mut pending = PendingSlot {
+            manager: self,
+            project,
+            receiver: Some(receiver),
+        };
+        let receiver = pending.receiver.as_mut().unwrap();
+        // The sender is only dropped once the manager is gone, which cannot
+        // happen while we hold a reference to it
+        let _ = receiver.await;
+        pending.receiver = None;
+
+        ExecutionPermit::new(self, project)
+    }
+
+    /// Wait until no other execution in the project holds any of `groups`
+    ///
+    /// Exclusion groups come from `# [exclusive: db]` annotations and are
+    /// scoped to a project. Groups are taken in sorted order, so recipes in
+    /// several overlapping groups cannot deadlock each other. Call this before
+    /// [`acquire`](Self::acquire) so waiting executions don't hold a slot.
+    pub async fn acquire_exclusive(&self, project: &str, groups: &[String]) -> ExclusionPermit {
+        let mut keys: Vec<String> = groups.iter().map(|g| format!("{project}/{g}")).collect();
+        keys.sort();
+        keys.dedup();
+
+        let mut permits = Vec::with_capacity(keys.len());
+        for key in keys {
+            let semaphore = self
+                .exclusion_groups
+                .lock()
+                .unwrap()
+                .entry(key.clone())
+                .or_insert_with(|| Arc::new(Semaphore::new(1)))
+                .clone();
+            if semaphore.available_permits() == 0 {
+                debug!("Waiting for exclusion group '{}'", key);
+            }
+            // The semaphores are never closed
+            let permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("exclusion group semaphore closed");
+            permits.push(permit);
+        }
+
+        ExclusionPermit { _permits: permits }
+    }
+
+    /// Number of executions currently running for a project
+    pub fn project_execution_count(&self, project: &str) -> usize {
+        let state = self.scheduler.lock().unwrap();
+        state.running.get(project).copied().unwrap_or(0)
+    }
+
+    /// Number of executions waiting for a slot across all projects
+    pub fn queued_execution_count(&self) -> usize {
+        let state = self.scheduler.lock().unwrap();
+        state.waiting.values().map(VecDeque::len).sum()
+    }
+
+    fn max_concurrent(&self) -> usize {
+        self.limits.read().unwrap().max_concurrent_executions
+    }
+
+    fn has_capacity(&self, state: &SchedulerState, project: &str) -> bool {
+        let limits = self.limits.read().unwrap();
+        self.current_execution_count() < limits.max_concurrent_executions
+            && state.running.get(project).copied().unwrap_or(0) < limits.project_limit(project)
+    }
+
+    fn grant(&self, state: &mut SchedulerState, project: &str) {
+        *state.running.entry(project.to_string()).or_default() += 1;
+        self.current_executions
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    fn release(&self, project: &str) {
+        let mut state = self.scheduler.lock().unwrap();
+        self.ungrant(&mut state, project);
+        self.dispatch(&mut state);
+    }
+
+    /// Hand free slots to queued executions, one project at a time
+    fn dispatch(&self, state: &mut SchedulerState) {
+        while self.current_execution_count() < self.max_concurrent() {
+            let mut granted = false;
+
+            for _ in 0..state.rotation.len() {
+                let Some(project) = state.rotation.pop_front() else {
+                    break;
+                };
+
+                if !self.has_capacity(state, &project) {
+                    state.rotation.push_back(project);
+                    continue;
+                }
+
+                let mut queue = state.waiting.remove(&project).unwrap_or_default();
+                while let Some(sender) = queue.pop_front() {
+                    if sender.is_closed() {
+                        continue;
+                    }
+                    self.grant(state, &project);
+                    if sender.send(()).is_ok() {
+                        granted = true;
+                        break;
+                    }
+                    // Waiter went away between the check and the send
+                    self.ungrant(state, &project);
+                }
+
+                if !queue.is_empty() {
+                    state.waiting.insert(project.clone(), queue);
+                    state.rotation.push_back(project);
+                }
+                if granted {
+                    break;
+                }
+            }
+
+            if !granted {
+                break;
+            }
+        }
+    }
+
+    fn ungrant(&self, state: &mut SchedulerState, project: &str) {
+        if let Some(running) = state.running.get_mut(project) {
+            *running = running.saturating_sub(1);
+            if *running == 0 {
+                state.running.remove(project);
+            }
+        }
+        self.current_executions
+            .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Current limits
+    pub fn limits(&self) -> ResourceLimits {
+        self.limits.read().unwrap().clone()
+    }
+
+    /// Change limits for new executions without a restart
+    ///
+    /// Values outside the operator's `runtime_bounds` are rejected and
+    /// nothing is changed. Running executions keep the limits they started
+    /// with; a raised concurrency limit admits queued executions right away.
+    pub fn update_limits(&self, changes: &LimitChanges) -> Result<ResourceLimits> {
+        let updated = {
+            let mut limits = self.limits.write().unwrap();
+            let mut updated = limits.clone();
+            updated.apply(changes)?;
+            *limits = updated.clone();
+            updated
+        };
+        info!(
+            "Updated resource limits: {} concurrent, {:?} timeout, {} bytes output",
+            updated.max_concurrent_executions, updated.max_execution_time, updated.max_output_size
+        );
+
+        let mut state = self.scheduler.lock().unwrap();
+        self.dispatch(&mut state);
+        Ok(updated)
+    }
+
     /// Get the configured timeout for executions
     pub fn get_timeout(&self) -> Duration {
-        self.limits.max_execution_time
+        self.limits.read().unwrap().max_execution_time
     }
 
     /// Check if output size is within limits
     pub fn check_output_size(&self, stdout_len: usize, stderr_len: usize) -> Result<()> {
         let total_size = stdout_len + stderr_len;
+        let limits = self.limits();
 
-        if total_size > self.limits.max_output_size {
+        if total_size > limits.max_output_size {
             let msg = format!(
                 "Output size ({} bytes) exceeds limit ({} bytes)",
-                total_size, self.limits.max_output_size
+                total_size, limits.max_output_size
             );
 
-            if self.limits.enforce_hard_limits {
-                return Err(Error::Other(msg));
+            if limits.enforce_hard_limits {
+                return Err(Error::Policy(msg));
             } else {
                 warn!("{}", msg);
             }
@@ -127,6 +471,72 @@ impl<'a> Drop for ExecutionGuard<'a> {
                 .current_executions
                 .load(std::sync::atomic::Ordering::Relaxed)
         );
+        let mut state = self.manager.scheduler.lock().unwrap();
+        self.manager.dispatch(&mut state);
+    }
+}
+
+/// RAII permit holding a set of exclusion groups
+pub struct ExclusionPermit {
+    _permits: Vec<OwnedSemaphorePermit>,
+}
+
+/// RAII permit for an execution slot granted by the fair scheduler
+pub struct ExecutionPermit<'a> {
+    manager: &'a ResourceManager,
+    project: String,
+}
+
+impl<'a> ExecutionPermit<'a> {
+    fn new(manager: &'a ResourceManager, project: &str) -> Self {
+        info!(
+            "Started execution for project '{}'. Current count: {}",
+            project,
+            manager.current_execution_count()
+        );
+        Self {
+            manager,
+            project: project.to_string(),
+        }
+    }
+
+    /// Project this permit was granted for
+    pub fn project(&self) -> &str {
+        &self.project
+    }
+}
+
+impl<'a> Drop for ExecutionPermit<'a> {
+    fn drop(&mut self) {
+        self.manager.release(&self.project);
+        info!(
+            "Finished execution for project '{}'. Current count: {}",
+            self.project,
+            self.manager.current_execution_count()
+        );
+    }
+}
+
+/// Releases a slot that was granted to a waiter that stopped waiting
+struct PendingSlot<'a> {
+    manager: &'a ResourceManager,
+    project: &'a str,
+    receiver: Option<oneshot::Receiver<()>>,
+}
+
+impl<'a> Drop for PendingSlot<'a> {
+    fn drop(&mut self) {
+        if let Some(mut receiver) = self.receiver.take() {
+            receiver.close();
+            if receiver.try_recv().is_ok() {
+                self.manager.release(self.project);
+            } else {
+                let mut state = self.manager.scheduler.lock().unwrap();
+                if let Some(queue) = state.waiting.get_mut(self.project) {
+                    queue.retain(|sender| !sender.is_closed());
+                }
+            }
+        }
     }
 }
 
@@ -216,6 +626,183 @@ mod tests {
         assert!(manager.can_execute().is_ok());
     }
 
+    #[test]
+    fn test_project_limit_resolution() {
+        let limits = ResourceLimits {
+            max_concurrent_executions: 8,
+            max_concurrent_per_project: Some(3),
+            project_concurrency_limits: HashMap::from([("web".to_string(), 1)]),
+            ..Default::default()
+        };
+        assert_eq!(limits.project_limit("web"), 1);
+        assert_eq!(limits.project_limit("api"), 3);
+        assert_eq!(ResourceLimits::default().project_limit("api"), 10);
+    }
+
+    #[tokio::test]
+    async fn test_project_sub_limit() {
+        let manager = ResourceManager::new(ResourceLimits {
+            max_concurrent_executions: 4,
+            max_concurrent_per_project: Some(1),
+            ..Default::default()
+        });
+
+        let web = manager.acquire("web").await;
+        // A second "web" execution must wait even though global slots are free
+        let blocked = tokio::time::timeout(Duration::from_millis(50), manager.acquire("web")).await;
+        assert!(blocked.is_err());
+        assert_eq!(manager.queued_execution_count(), 0);
+
+        // Other projects are unaffected
+        let api = manager.acquire("api").await;
+        assert_eq!(manager.current_execution_count(), 2);
+        assert_eq!(manager.project_execution_count("web"), 1);
+
+        drop(web);
+        let web = manager.acquire("web").await;
+        assert_eq!(web.project(), "web");
+        drop((web, api));
+        assert_eq!(manager.current_execution_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_fair_scheduling_interleaves_projects() {
+        let manager = std::sync::Arc::new(ResourceManager::new(ResourceLimits {
+            max_concurrent_executions: 1,
+            ..Default::default()
+        }));
+        let order = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let holder = manager.acquire("busy").await;
+
+        // A burst against "busy" is queued before a single "quiet" call
+        let mut handles = Vec::new();
+        for project in ["busy", "busy", "busy", "quiet"] {
+            let manager = manager.clone();
+            let order = order.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = manager.acquire(project).await;
+                order.lock().unwrap().push(project);
+                tokio::task::yield_now().await;
+            }));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(manager.queued_execution_count(), 4);
+
+        drop(holder);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        let order = order.lock().unwrap().clone();
+        assert_eq!(order, vec!["busy", "quiet", "busy", "busy"]);
+        assert_eq!(manager.current_execution_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_releases_slot() {
+        let manager = ResourceManager::new(ResourceLimits {
+            max_concurrent_executions: 1,
+            ..Default::default()
+        });
+
+        let permit = manager.acquire("a").await;
+        let cancelled = tokio::time::timeout(Duration::from_millis(20), manager.acquire("b")).await;
+        assert!(cancelled.is_err());
+
+        drop(permit);
+        assert_eq!(manager.current_execution_count(), 0);
+        let _permit = manager.acquire("b").await;
+        assert_eq!(manager.current_execution_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_exclusion_groups_serialize() {
+        let manager = Arc::new(ResourceManager::with_default());
+        let db = vec!["db".to_string()];
+
+        let first = manager.acquire_exclusive("app", &db).await;
+
+        // Same group in another project, and other groups, are unaffected
+        let _other_project = manager.acquire_exclusive("api", &db).await;
+        let _cache = manager
+            .acquire_exclusive("app", &["cache".to_string()])
+            .await;
+
+        let waiter = {
+            let manager = manager.clone();
+            let groups = vec!["cache".to_string(), "db".to_string()];
+            tokio::spawn(async move {
+                let _permit = manager.acquire_exclusive("app", &groups).await;
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        drop(first);
+        drop(_cache);
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_raising_limit_admits_queued_executions() {
+        let manager = std::sync::Arc::new(ResourceManager::new(ResourceLimits {
+            max_concurrent_executions: 1,
+            ..Default::default()
+        }));
+        let _running = manager.acquire("web").await;
+
+        let waiter = {
+            let manager = manager.clone();
+            tokio::spawn(async move {
+                let _permit = manager.acquire("web").await;
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(manager.queued_execution_count(), 1);
+
+        manager
+            .update_limits(&LimitChanges {
+                max_concurrent_executions: Some(2),
+                ..Default::default()
+            })
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_limit_changes_respect_bounds() {
+        let mut limits = ResourceLimits::default();
+        let too_long = LimitChanges {
+            timeout_seconds: Some(7200),
+            ..Default::default()
+        };
+        assert!(limits.apply(&too_long).is_err());
+        assert!(limits
+            .apply(&LimitChanges {
+                max_output_size: Some(0),
+                ..Default::default()
+            })
+            .is_err());
+
+        limits
+            .apply(&LimitChanges {
+                timeout_seconds: Some(3600),
+                max_output_size: Some(1024),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(limits.max_execution_time, Duration::from_secs(3600));
+        assert_eq!(limits.max_output_size, 1024);
+        assert_eq!(limits.max_concurrent_executions, 10);
+    }
+
     #[test]
     fn test_output_size_limits() {
         let limits = ResourceLimits {
@@ -244,4 +831,27 @@ mod tests {
         // Exceeds limits but only warns
         assert!(manager.check_output_size(600, 600).is_ok());
     }
+
+    #[test]
+    fn test_requested_jobs_precedence() {
+        let limits = ResourceLimits {
+            default_jobs: Some(2),
+            project_jobs: HashMap::from([("api".to_string(), 4)]),
+            group_jobs: HashMap::from([("build".to_string(), 8)]),
+            ..Default::default()
+        };
+        assert_eq!(limits.requested_jobs("web", None, None), Some(2));
+        assert_eq!(limits.requested_jobs("api", None, None), Some(4));
+        assert_eq!(limits.requested_jobs("api", Some("build"), None), Some(8));
+        assert_eq!(limits.requested_jobs("api", Some("test"), None), Some(4));
+        assert_eq!(
+            limits.requested_jobs("api", Some("build"), Some(3)),
+            Some(3)
+        );
+        assert_eq!(
+            ResourceLimits::default().requested_jobs("api", Some("build"), None),
+            None
+        );
+        assert!(ResourceLimits::default().max_jobs >= 1);
+    }
 }
@@ -1,3 +1,5 @@
+pub mod redact;
+
 use crate::error::{Error, Result};
 use regex::Regex;
 use std::path::{Path, PathBuf};
@@ -81,7 +83,7 @@ impl SecurityValidator {
         });
 
         if !is_allowed {
-            return Err(Error::Other(format!(
+            return Err(Error::Policy(format!(
                 "Access denied: Path {} is outside allowed directories",
                 path.display()
             )));
@@ -90,7 +92,7 @@ impl SecurityValidator {
         // Additional checks for suspicious patterns
         let path_str = path.to_string_lossy();
         if path_str.contains("..") || path_str.contains('~') {
-            return Err(Error::Other(format!(
+            return Err(Error::Policy(format!(
                 "Suspicious path pattern detected: {}",
                 path.display()
             )));
@@ -269,7 +271,7 @@ impl SecurityValidator {
                     pattern
                 );
                 if self.config.strict_mode {
-                    return Err(Error::Other(format!(
+                    return Err(Error::Policy(format!(
                         "Command contains potentially dangerous pattern: {pattern}"
                     )));
                 }
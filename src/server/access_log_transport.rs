@@ -0,0 +1,222 @@
+//! Transport wrapper that feeds the access log
+//!
+//! [`AccessLogTransport`] sits between a session's MCP server and its
+//! transport. It counts the messages and bytes going each way, picks the
+//! client name and version out of `initialize`, and pairs each `tools/call`
+//! request with its response to record the tool, data volumes, duration and
+//! outcome. The session opens in the [`AccessLog`] when the wrapper is
+//! created and closes when it is dropped, which happens when the session's
+//! server stops.
+
+use crate::access_log::{AccessEvent, AccessLog};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Instant;
+use ultrafast_mcp::protocol::{JsonRpcMessage, RequestId};
+use ultrafast_mcp_transport::{ConnectionState, Result, Transport};
+
+/// A `tools/call` request waiting for its response
+struct PendingCall {
+    id: RequestId,
+    tool: String,
+    bytes_in: usize,
+    started: Instant,
+}
+
+/// Transport that records a session's traffic in the access log
+pub struct AccessLogTransport {
+    inner: Box<dyn Transport>,
+    log: Arc<AccessLog>,
+    session: String,
+    pending: Vec<PendingCall>,
+}
+
+impl AccessLogTransport {
+    /// Wrap `inner`, opening `session` in the access log
+    pub fn new(
+        inner: Box<dyn Transport>,
+        log: Arc<AccessLog>,
+        session: String,
+        transport: &str,
+    ) -> Self {
+        log.open_session(&session, transport);
+        Self {
+            inner,
+            log,
+            session,
+            pending: Vec::new(),
+        }
+    }
+
+    fn observe_request(&mut self, message: &JsonRpcMessage, bytes: usize) {
+        let (JsonRpcMessage::Request(request) | JsonRpcMessage::Notification(request)) = message
+        else {
+            return;
+        };
+        let param = |name: &str| {
+            request
+                .params
+                .as_ref()
+                .and_then(|params| params.pointer(name))
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        match (request.method.as_str(), &request.id) {
+            ("initialize", _) => self.log.record(
+                &self.session,
+                AccessEvent::Initialize {
+                    client_name: param("/clientInfo/name"),
+                    client_version: param("/clientInfo/version"),
+                    protocol_version: param("/protocolVersion"),
+                },
+            ),
+            ("tools/call", Some(id)) => self.pending.push(PendingCall {
+                id: id.clone(),
+                tool: param("/name"),
+                bytes_in: bytes,
+                started: Instant::now(),
+            }),
+            _ => {}
+        }
+    }
+
+    fn observe_response(&mut self, message: &JsonRpcMessage, bytes: usize) {
+        let JsonRpcMessage::Response(response) = message else {
+            return;
+        };
+        let Some(position) = self
+            .pending
+            .iter()
+            .position(|call| Some(&call.id) == response.id.as_ref())
+        else {
+            return;
+        };
+        let call = self.pending.remove(position);
+        let is_error = response.error.is_some()
+            || response
+                .result
+                .as_ref()
+                .and_then(|result| result.get("isError"))
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false);
+        self.log.record(
+            &self.session,
+            AccessEvent::ToolCall {
+                tool: call.tool,
+                bytes_in: call.bytes_in,
+                bytes_out: bytes,
+                duration_ms: call.started.elapsed().as_millis() as u64,
+                is_error,
+            },
+        );
+    }
+}
+
+/// Size of a message as JSON on the wire
+fn message_size(message: &JsonRpcMessage) -> usize {
+    serde_json::to_vec(message).map_or(0, |bytes| bytes.len())
+}
+
+#[async_trait]
+impl Transport for AccessLogTransport {
+    async fn send_message(&mut self, message: JsonRpcMessage) -> Result<()> {
+        let bytes = message_size(&message);
+        self.log.record_message(&self.session, false, bytes);
+        self.observe_response(&message, bytes);
+        self.inner.send_message(message).await
+    }
+
+    async fn receive_message(&mut self) -> Result<JsonRpcMessage> {
+        let message = self.inner.receive_message().await?;
+        let bytes = message_size(&message);
+        self.log.record_message(&self.session, true, bytes);
+        self.observe_request(&message, bytes);
+        Ok(message)
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.inner.close().await
+    }
+
+    fn get_state(&self) -> ConnectionState {
+        self.inner.get_state()
+    }
+}
+
+impl Drop for AccessLogTransport {
+    fn drop(&mut self) {
+        self.log.close_session(&self.session);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access_log::AccessEntry;
+    use crate::server::batch_transport::BatchTransport;
+    use serde_json::json;
+    use ultrafast_mcp::protocol::JsonRpcResponse;
+
+    const CLIENT: &str = concat!(
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-06-18","capabilities":{},"clientInfo":{"name":"cursor","version":"1.2"}}}"#,
+        "\n",
+        r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"build","arguments":{}}}"#,
+        "\n",
+        r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"deploy","arguments":{}}}"#,
+        "\n",
+    );
+
+    #[tokio::test]
+    async fn test_tool_calls_are_logged() {
+        let log = Arc::new(AccessLog::default());
+        let (writer, _output) = tokio::io::duplex(64 * 1024);
+        let mut transport = AccessLogTransport::new(
+            Box::new(BatchTransport::new(CLIENT.as_bytes(), writer)),
+            log.clone(),
+            "s1".to_string(),
+            "stdio",
+        );
+
+        for _ in 0..3 {
+            transport.receive_message().await.unwrap();
+        }
+        // Answered out of order; the second call fails
+        for (id, result) in [
+            (3, json!({"content": [], "isError": true})),
+            (2, json!({"content": [{"type": "text", "text": "built"}]})),
+        ] {
+            let response = JsonRpcResponse::success(result, Some(RequestId::Number(id)));
+            transport
+                .send_message(JsonRpcMessage::Response(response))
+                .await
+                .unwrap();
+        }
+
+        let report = log.query(Some("s1"), 10);
+        let stats = &report.sessions[0];
+        assert_eq!(stats.client_name.as_deref(), Some("cursor"));
+        assert_eq!(stats.client_version.as_deref(), Some("1.2"));
+        assert_eq!((stats.messages_in, stats.messages_out), (3, 2));
+        assert!(stats.bytes_in > 0 && stats.bytes_out > 0);
+        assert_eq!(stats.tool_errors, 1);
+
+        let calls: Vec<(String, bool)> = report
+            .recent_events
+            .iter()
+            .filter_map(|entry: &AccessEntry| match &entry.event {
+                AccessEvent::ToolCall { tool, is_error, .. } => Some((tool.clone(), *is_error)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            calls,
+            vec![("deploy".to_string(), true), ("build".to_string(), false)]
+        );
+
+        drop(transport);
+        assert!(log.query(Some("s1"), 0).sessions[0]
+            .disconnected_at
+            .is_some());
+    }
+}
@@ -0,0 +1,357 @@
+//! Newline-delimited JSON-RPC transport with batch support
+//!
+//! The framework's stdio transport parses every line as a single message, so
+//! a JSON-RPC batch (an array of messages on one line) fails to parse and
+//! ends the session. [`BatchTransport`] splits batches into individual
+//! messages for the server, collects the responses to the batch's requests
+//! and writes them back as one array once all of them are ready, as the
+//! JSON-RPC 2.0 specification requires.
+//!
+//! Malformed lines are answered with a JSON-RPC error instead of closing the
+//! connection, and batches larger than the configured limit are rejected as
+//! a whole.
+
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::VecDeque;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tracing::{debug, trace, warn};
+use ultrafast_mcp::protocol::{JsonRpcError, JsonRpcMessage, JsonRpcResponse, RequestId};
+use ultrafast_mcp_transport::{ConnectionState, Result, Transport, TransportError};
+
+/// Default maximum number of messages in one batch
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 50;
+
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+
+/// A batch whose responses are still being collected
+#[derive(Debug, Default)]
+struct PendingBatch {
+    /// Ids of batch requests that have not been answered yet
+    awaiting: Vec<RequestId>,
+    responses: Vec<Value>,
+}
+
+/// Line-oriented JSON-RPC transport that understands batches
+pub struct BatchTransport<R, W> {
+    reader: BufReader<R>,
+    writer: W,
+    max_batch_size: usize,
+    /// Messages of the current batch not yet handed to the server
+    queued: VecDeque<JsonRpcMessage>,
+    batch: Option<PendingBatch>,
+    state: ConnectionState,
+}
+
+impl BatchTransport<tokio::io::Stdin, tokio::io::Stdout> {
+    /// Transport over the process's stdin and stdout
+    pub fn stdio() -> Self {
+        Self::new(tokio::io::stdin(), tokio::io::stdout())
+    }
+}
+
+impl<R, W> BatchTransport<R, W>
+where
+    R: AsyncRead + Unpin + Send + Sync,
+    W: AsyncWrite + Unpin + Send + Sync,
+{
+    pub fn new(reader: R, writer: W) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            writer,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            queued: VecDeque::new(),
+            batch: None,
+            state: ConnectionState::Connected,
+        }
+    }
+
+    /// Reject batches with more than `max` messages
+    pub fn with_max_batch_size(mut self, max: usize) -> Self {
+        self.max_batch_size = max.max(1);
+        self
+    }
+
+    async fn write_line(&mut self, value: &impl Serialize) -> Result<()> {
+        let json =
+            serde_json::to_string(value).map_err(|e| TransportError::SerializationError {
+                message: format!("Failed to serialize message: {e}"),
+            })?;
+        trace!("Sending message: {}", json);
+
+        let written = async {
+            self.writer.write_all(json.as_bytes()).await?;
+            self.writer.write_all(b"\n").await?;
+            self.writer.flush().await
+        }
+        .await;
+        written.map_err(|e| {
+            self.state = ConnectionState::Failed(format!("Write failed: {e}"));
+            TransportError::NetworkError {
+                message: format!("Failed to write message: {e}"),
+            }
+        })
+    }
+
+    async fn write_error(&mut self, code: i32, message: String) -> Result<()> {
+        warn!("Rejecting JSON-RPC input: {}", message);
+        self.write_line(&error_response(code, message)).await
+    }
+
+    /// Queue a batch's messages, answering invalid entries in the batch response
+    async fn start_batch(&mut self, items: Vec<Value>) -> Result<()> {
+        if items.is_empty() {
+            return self
+                .write_error(INVALID_REQUEST, "Empty batch".to_string())
+                .await;
+        }
+        if items.len() > self.max_batch_size {
+            return self
+                .write_error(
+                    INVALID_REQUEST,
+                    format!(
+                        "Batch of {} messages exceeds the limit of {}",
+                        items.len(),
+                        self.max_batch_size
+                    ),
+                )
+                .await;
+        }
+
+        debug!("Received batch of {} messages", items.len());
+        let mut batch = PendingBatch::default();
+        for item in items {
+            match serde_json::from_value::<JsonRpcMessage>(item) {
+                Ok(message) => {
+                    if let JsonRpcMessage::Request(request) = &message {
+                        if let Some(id) = &request.id {
+                            batch.awaiting.push(id.clone());
+                        }
+                    }
+                    self.queued.push_back(message);
+                }
+                Err(e) => batch.responses.push(
+                    serde_json::to_value(error_response(
+                        INVALID_REQUEST,
+                        format!("Invalid batch entry: {e}"),
+                    ))
+                    .unwrap_or(Value::Null),
+                ),
+            }
+        }
+
+        // A batch of notifications gets no response at all
+        if !batch.awaiting.is_empty() {
+            self.batch = Some(batch);
+        } else if !batch.responses.is_empty() {
+            self.write_line(&batch.responses).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<R, W> Transport for BatchTransport<R, W>
+where
+    R: AsyncRead + Unpin + Send + Sync,
+    W: AsyncWrite + Unpin + Send + Sync,
+{
+    async fn send_message(&mut self, message: JsonRpcMessage) -> Result<()> {
+        if let (Some(batch), JsonRpcMessage::Response(response)) = (&mut self.batch, &message) {
+            let position = response
+                .id
+                .as_ref()
+                .and_then(|id| batch.awaiting.iter().position(|pending| pending == id));
+            if let Some(position) = position {
+                batch.awaiting.remove(position);
+                batch
+                    .responses
+                    .push(serde_json::to_value(&message).map_err(|e| {
+                        TransportError::SerializationError {
+                            message: format!("Failed to serialize message: {e}"),
+                        }
+                    })?);
+
+                if batch.awaiting.is_empty() {
+                    let responses = self.batch.take().unwrap_or_default().responses;
+                    return self.write_line(&responses).await;
+                }
+                return Ok(());
+            }
+        }
+
+        // Notifications and responses outside a batch go out immediately
+        self.write_line(&message).await
+    }
+
+    async fn receive_message(&mut self) -> Result<JsonRpcMessage> {
+        loop {
+            if let Some(message) = self.queued.pop_front() {
+                return Ok(message);
+            }
+
+            let mut line = String::new();
+            let bytes_read = self.reader.read_line(&mut line).await.map_err(|e| {
+                TransportError::NetworkError {
+                    message: format!("Failed to read line: {e}"),
+                }
+            })?;
+            if bytes_read == 0 {
+                self.state = ConnectionState::Disconnected;
+                return Err(TransportError::ConnectionClosed);
+            }
+
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            trace!("Received message: {}", line);
+
+            match serde_json::from_str::<Value>(line) {
+                Ok(Value::Array(items)) => self.start_batch(items).await?,
+                Ok(value) => match serde_json::from_value::<JsonRpcMessage>(value) {
+                    Ok(message) => return Ok(message),
+                    Err(e) => {
+                        self.write_error(INVALID_REQUEST, format!("Invalid message: {e}"))
+                            .await?
+                    }
+                },
+                Err(e) => {
+                    self.write_error(PARSE_ERROR, format!("Parse error: {e}"))
+                        .await?
+                }
+            }
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.state = ConnectionState::Disconnected;
+        Ok(())
+    }
+
+    fn get_state(&self) -> ConnectionState {
+        self.state.clone()
+    }
+}
+
+fn error_response(code: i32, message: String) -> JsonRpcResponse {
+    JsonRpcResponse::error(JsonRpcError::new(code, message), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tokio::io::{AsyncReadExt, DuplexStream};
+
+    fn transport(input: &str) -> (BatchTransport<&[u8], DuplexStream>, DuplexStream) {
+        let (writer, output) = tokio::io::duplex(64 * 1024);
+        (BatchTransport::new(input.as_bytes(), writer), output)
+    }
+
+    async fn written(
+        transport: BatchTransport<&[u8], DuplexStream>,
+        mut output: DuplexStream,
+    ) -> Vec<Value> {
+        drop(transport);
+        let mut text = String::new();
+        output.read_to_string(&mut text).await.unwrap();
+        text.lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    fn request_id(message: &JsonRpcMessage) -> Option<RequestId> {
+        match message {
+            JsonRpcMessage::Request(request) => request.id.clone(),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_responses_are_aggregated() {
+        let input = json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "tools/list"},
+            {"jsonrpc": "2.0", "method": "notifications/initialized"},
+            {"jsonrpc": "2.0", "id": "b", "method": "prompts/list"},
+        ])
+        .to_string()
+            + "\n";
+        let (mut transport, output) = transport(&input);
+
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            ids.push(request_id(&transport.receive_message().await.unwrap()));
+        }
+        assert_eq!(
+            ids,
+            vec![
+                Some(RequestId::Number(1)),
+                None,
+                Some(RequestId::String("b".to_string()))
+            ]
+        );
+        assert!(matches!(
+            transport.receive_message().await,
+            Err(TransportError::ConnectionClosed)
+        ));
+
+        // Out-of-batch messages pass straight through; batch responses are
+        // held until the last one arrives
+        let progress = JsonRpcMessage::Notification(ultrafast_mcp::protocol::JsonRpcRequest::new(
+            "notifications/progress".to_string(),
+            None,
+            None,
+        ));
+        transport.send_message(progress).await.unwrap();
+        for id in [RequestId::String("b".to_string()), RequestId::Number(1)] {
+            let response = JsonRpcResponse::success(json!({}), Some(id));
+            transport
+                .send_message(JsonRpcMessage::Response(response))
+                .await
+                .unwrap();
+        }
+
+        let lines = written(transport, output).await;
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["method"], "notifications/progress");
+        let responses = lines[1].as_array().unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], "b");
+        assert_eq!(responses[1]["id"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_input_is_answered_not_fatal() {
+        let oversized = Value::Array(vec![json!({"jsonrpc": "2.0", "method": "ping"}); 3]);
+        let input = format!(
+            "not json\n[]\n{oversized}\n\n{}\n",
+            json!({"jsonrpc": "2.0", "id": 7, "method": "ping"})
+        );
+        let (transport, output) = transport(&input);
+        let mut transport = transport.with_max_batch_size(2);
+
+        let message = transport.receive_message().await.unwrap();
+        assert_eq!(request_id(&message), Some(RequestId::Number(7)));
+
+        let lines = written(transport, output).await;
+        let codes: Vec<_> = lines
+            .iter()
+            .map(|line| line["error"]["code"].clone())
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                json!(PARSE_ERROR),
+                json!(INVALID_REQUEST),
+                json!(INVALID_REQUEST)
+            ]
+        );
+        assert!(lines[2]["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("exceeds the limit of 2"));
+    }
+}
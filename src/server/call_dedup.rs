@@ -0,0 +1,412 @@
+//! Sharing one execution between identical tool calls
+//!
+//! Client retry logic sometimes sends the same tool call twice in quick
+//! succession, and running a deploy or migration recipe twice is worse than
+//! waiting. With a dedup window of N seconds, a call identical to one that
+//! started less than N seconds ago and is still running waits for that
+//! execution and returns its result instead of spawning another process.
+//! Calls are identical when tool, arguments and profile match; the window
+//! spans all sessions of a server, since a retry may come over a new
+//! connection.
+//!
+//! Once the first call finishes, the next identical call runs the recipe
+//! again. If the first call is cancelled, one waiting call runs it and the
+//! others wait for that run. A shared failure reaches every call as the same
+//! kind of error, so it is classified the same way.
+
+use super::run_history::call_key;
+use crate::error::{Error, Result};
+use crate::types::ExecutionResult;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// Result of an execution as handed to the calls that share it
+type SharedResult = std::result::Result<ExecutionResult, Arc<Error>>;
+
+/// What a call does about an identical call
+enum Role {
+    /// Wait for the execution with this id, started at the given time
+    Wait(u64, Instant, watch::Receiver<Option<SharedResult>>),
+    /// Run the execution, registered under this id
+    Run(u64, watch::Sender<Option<SharedResult>>),
+}
+
+struct InFlight {
+    id: u64,
+    started: Instant,
+    result: watch::Receiver<Option<SharedResult>>,
+}
+
+#[derive(Default)]
+struct Calls {
+    in_flight: HashMap<String, InFlight>,
+    next_id: u64,
+}
+
+/// Executions of tool calls that are still running, by call
+pub struct CallDedup {
+    window: Duration,
+    calls: Mutex<Calls>,
+}
+
+impl CallDedup {
+    /// Share executions with identical calls arriving within `window`
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            calls: Mutex::new(Calls::default()),
+        }
+    }
+
+    /// Run `execute` for a call, or wait for an identical call's execution
+    /// if one started within the window and is still running
+    pub async fn run<F>(
+        &self,
+        tool: &str,
+        arguments: &Map<String, Value>,
+        profile: Option<&str>,
+        execute: F,
+    ) -> Result<ExecutionResult>
+    where
+        F: Future<Output = Result<ExecutionResult>>,
+    {
+        let key = format!("{}\0{}", call_key(tool, arguments), profile.unwrap_or(""));
+
+        let (id, sender) = loop {
+            let (id, started, mut result) = match self.join(&key) {
+                Role::Run(id, sender) => break (id, sender),
+                Role::Wait(id, started, result) => (id, started, result),
+            };
+            tracing::info!(
+                "Identical call to {} started {:?} ago is still running, sharing its result",
+                tool,
+                started.elapsed()
+            );
+            // An error means the running call was cancelled; the first
+            // waiter to notice runs it, the others wait for that run
+            let shared = match result.wait_for(Option::is_some).await {
+                Ok(shared) => shared.clone(),
+                Err(_) => None,
+            };
+            match shared {
+                Some(Ok(mut result)) => {
+                    result.warnings.push(format!(
+                        "An identical call started {:.1}s earlier was still running; this is its result, the recipe was not run again",
+                        started.elapsed().as_secs_f64()
+                    ));
+                    return Ok(result);
+                }
+                Some(Err(error)) => return Err(duplicate(&error)),
+                None => self.forget(&key, id),
+            }
+        };
+        // Declared after the sender so the entry is gone by the time waiters
+        // see the sender dropped
+        let _guard = Finished {
+            dedup: self,
+            key: &key,
+            id,
+        };
+
+        let result = execute.await;
+        sender.send_replace(Some(match &result {
+            Ok(result) => Ok(result.clone()),
+            Err(e) => Err(Arc::new(duplicate(e))),
+        }));
+        result
+    }
+
+    /// Wait for the running execution of a call if it started within the
+    /// window, or register this call as running it, under one lock so two
+    /// identical calls can't both run
+    fn join(&self, key: &str) -> Role {
+        let mut calls = self.calls.lock().unwrap();
+        if let Some(call) = calls
+            .in_flight
+            .get(key)
+            .filter(|call| call.started.elapsed() <= self.window)
+        {
+            return Role::Wait(call.id, call.started, call.result.clone());
+        }
+        let (sender, receiver) = watch::channel(None);
+        calls.next_id += 1;
+        let id = calls.next_id;
+        calls.in_flight.insert(
+            key.to_string(),
+            InFlight {
+                id,
+                started: Instant::now(),
+                result: receiver,
+            },
+        );
+        Role::Run(id, sender)
+    }
+
+    /// Remove the entry of an execution, unless another replaced it
+    fn forget(&self, key: &str, id: u64) {
+        let mut calls = self.calls.lock().unwrap();
+        if calls.in_flight.get(key).is_some_and(|call| call.id == id) {
+            calls.in_flight.remove(key);
+        }
+    }
+}
+
+/// A copy of a shared execution's error for each call sharing it
+///
+/// Errors that can't be copied become the variant they are classified
+/// like, with the same message.
+fn duplicate(error: &Error) -> Error {
+    match error {
+        Error::Io(e) => Error::Io(std::io::Error::new(e.kind(), e.to_string())),
+        Error::Parse {
+            message,
+            line,
+            column,
+        } => Error::Parse {
+            message: message.clone(),
+            line: *line,
+            column: *column,
+        },
+        Error::Execution {
+            command,
+            exit_code,
+            stderr,
+        } => Error::Execution {
+            command: command.clone(),
+            exit_code: *exit_code,
+            stderr: stderr.clone(),
+        },
+        Error::JustCommand(message) => Error::JustCommand(message.clone()),
+        Error::JustNotInstalled { needed_for, hint } => Error::JustNotInstalled {
+            needed_for: needed_for.clone(),
+            hint: hint.clone(),
+        },
+        Error::Registry(message) => Error::Registry(message.clone()),
+        Error::Server(message) => Error::Server(message.clone()),
+        #[cfg(feature = "watcher")]
+        Error::Watch(e) => Error::Io(std::io::Error::other(e.to_string())),
+        Error::Regex(e) => Error::Regex(e.clone()),
+        Error::InvalidToolName(message) => Error::InvalidToolName(message.clone()),
+        Error::ToolNotFound(message) => Error::ToolNotFound(message.clone()),
+        Error::InvalidParameter(message) => Error::InvalidParameter(message.clone()),
+        Error::TaskNotFound(message) => Error::TaskNotFound(message.clone()),
+        Error::Timeout(message) => Error::Timeout(message.clone()),
+        Error::Policy(message) => Error::Policy(message.clone()),
+        Error::Busy(message) => Error::Busy(message.clone()),
+        Error::Internal(message) => Error::Internal(message.clone()),
+        Error::Json(e) => Error::Other(e.to_string()),
+        Error::Other(message) => Error::Other(message.clone()),
+    }
+}
+
+/// Forgets an execution once it finished or was cancelled
+struct Finished<'a> {
+    dedup: &'a CallDedup,
+    key: &'a str,
+    id: u64,
+}
+
+impl Drop for Finished<'_> {
+    fn drop(&mut self) {
+        // A later call may have replaced the entry after the window closed
+        self.dedup.forget(self.key, self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn arguments(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    /// An execution that counts its runs and takes `duration`
+    async fn execution(runs: Arc<AtomicUsize>, duration: Duration) -> Result<ExecutionResult> {
+        let run = runs.fetch_add(1, Ordering::SeqCst) + 1;
+        tokio::time::sleep(duration).await;
+        Ok(ExecutionResult {
+            success: true,
+            exit_code: Some(0),
+            stdout: format!("run {run}"),
+            stderr: String::new(),
+            error: None,
+            invocation: None,
+            warnings: Vec::new(),
+            error_kind: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_identical_calls_share_one_execution() {
+        let dedup = CallDedup::new(Duration::from_secs(5));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let args = arguments(json!({"env": "prod", "force": true}));
+        let reordered = arguments(json!({"force": true, "env": "prod"}));
+        let slow = Duration::from_millis(200);
+
+        let (first, retry, other_profile, other_args) = tokio::join!(
+            dedup.run("deploy", &args, None, execution(runs.clone(), slow)),
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                dedup
+                    .run("deploy", &reordered, None, execution(runs.clone(), slow))
+                    .await
+            },
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                dedup
+                    .run("deploy", &args, Some("ci"), execution(runs.clone(), slow))
+                    .await
+            },
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                let args = arguments(json!({"env": "staging"}));
+                dedup
+                    .run("deploy", &args, None, execution(runs.clone(), slow))
+                    .await
+            },
+        );
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        let (first, retry) = (first.unwrap(), retry.unwrap());
+        assert_eq!(retry.stdout, first.stdout);
+        assert!(first.warnings.is_empty());
+        assert!(retry.warnings[0].contains("not run again"));
+        assert_ne!(other_profile.unwrap().stdout, first.stdout);
+        assert_ne!(other_args.unwrap().stdout, first.stdout);
+
+        // Finished executions are not shared
+        let again = dedup
+            .run(
+                "deploy",
+                &args,
+                None,
+                execution(runs.clone(), Duration::ZERO),
+            )
+            .await
+            .unwrap();
+        assert_eq!(again.stdout, "run 4");
+    }
+
+    #[tokio::test]
+    async fn test_simultaneous_calls_run_once_and_share_typed_failures() {
+        let dedup = CallDedup::new(Duration::from_secs(5));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let args = Map::new();
+        let slow = Duration::from_millis(100);
+
+        let (first, second) = tokio::join!(
+            dedup.run("build", &args, None, execution(runs.clone(), slow)),
+            dedup.run("build", &args, None, execution(runs.clone(), slow)),
+        );
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert_eq!(first.unwrap().stdout, second.unwrap().stdout);
+
+        let busy = || async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Err(Error::Busy(
+                "Maximum concurrent executions (1) reached".to_string(),
+            ))
+        };
+        let (first, second) = tokio::join!(
+            dedup.run("build", &args, None, busy()),
+            dedup.run("build", &args, None, busy()),
+        );
+        assert!(matches!(first, Err(Error::Busy(_))));
+        assert!(matches!(second, Err(Error::Busy(message)) if message.contains("reached")));
+    }
+
+    #[tokio::test]
+    async fn test_calls_outside_window_run_again() {
+        let dedup = CallDedup::new(Duration::from_millis(20));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let args = Map::new();
+        let slow = Duration::from_millis(150);
+
+        let (first, late) = tokio::join!(
+            dedup.run("build", &args, None, execution(runs.clone(), slow)),
+            async {
+                tokio::time::sleep(Duration::from_millis(60)).await;
+                dedup
+                    .run("build", &args, None, execution(runs.clone(), slow))
+                    .await
+            },
+        );
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_ne!(first.unwrap().stdout, late.unwrap().stdout);
+        assert!(dedup.calls.lock().unwrap().in_flight.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_execution_is_run_by_waiting_call() {
+        let dedup = Arc::new(CallDedup::new(Duration::from_secs(5)));
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let first = {
+            let (dedup, runs) = (dedup.clone(), runs.clone());
+            tokio::spawn(async move {
+                dedup
+                    .run(
+                        "test",
+                        &Map::new(),
+                        None,
+                        execution(runs, Duration::from_secs(60)),
+                    )
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let retry = {
+            let (dedup, runs) = (dedup.clone(), runs.clone());
+            tokio::spawn(async move {
+                dedup
+                    .run("test", &Map::new(), None, execution(runs, Duration::ZERO))
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        first.abort();
+
+        let retry = retry.await.unwrap().unwrap();
+        assert_eq!(retry.stdout, "run 2");
+        assert!(retry.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_execution_is_handed_to_one_waiter() {
+        let dedup = Arc::new(CallDedup::new(Duration::from_secs(5)));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let call = |duration| {
+            let (dedup, runs) = (dedup.clone(), runs.clone());
+            tokio::spawn(async move {
+                dedup
+                    .run("test", &Map::new(), None, execution(runs, duration))
+                    .await
+            })
+        };
+
+        let first = call(Duration::from_secs(60));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let waiters = [
+            call(Duration::from_millis(100)),
+            call(Duration::from_millis(100)),
+            call(Duration::from_millis(100)),
+        ];
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        first.abort();
+
+        let mut outputs = Vec::new();
+        for waiter in waiters {
+            outputs.push(waiter.await.unwrap().unwrap().stdout);
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert!(outputs.iter().all(|stdout| stdout == "run 2"));
+    }
+}
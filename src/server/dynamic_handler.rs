@@ -6,12 +6,18 @@
 //! The key challenge is that ultrafast-mcp assumes static tool registration,
 //! but just-mcp needs dynamic updates when justfiles change.
 
-use super::error_adapter::ErrorAdapter;
+use super::call_dedup::CallDedup;
+use super::error_adapter::{ErrorAdapter, ErrorKind, FailureClass};
+use super::output_store::OutputStore;
+use super::parameter_memory::ParameterMemory;
+use super::run_history::RunHistory;
+use super::schema_mode::{SchemaMode, SchemaModeSelection};
 use crate::admin::AdminTools;
 use crate::error::Result;
 use crate::executor::TaskExecutor;
 use crate::registry::ToolRegistry;
-use crate::types::{ExecutionRequest, ExecutionResult, ToolDefinition};
+use crate::types::{ExecutionContext, ExecutionRequest, ExecutionResult, ToolDefinition};
+use crate::watcher::supervisor::Supervision;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -41,6 +47,32 @@ pub struct DynamicToolHandler {
     /// Admin tools for admin command execution
     admin_tools: Option<Arc<AdminTools>>,
 
+    /// Store for outputs too large to inline, and the size above which
+    /// results link to it instead
+    output_store: Option<Arc<OutputStore>>,
+    output_link_threshold: usize,
+
+    /// Last-used argument values offered as schema defaults
+    parameter_memory: Option<Arc<ParameterMemory>>,
+
+    /// Previous output of each tool call, diffed against the next run
+    run_history: Option<Arc<RunHistory>>,
+
+    /// Named profile recipe calls run under unless they select another,
+    /// changed with `_admin_use_profile`
+    profile: std::sync::Mutex<Option<String>>,
+
+    /// How tool schemas are sent in `tools/list`, changed with
+    /// `_admin_schema_mode`
+    schema_mode: std::sync::Mutex<SchemaMode>,
+
+    /// Executions shared with identical calls that arrive while they run
+    call_dedup: Option<Arc<CallDedup>>,
+
+    /// State of the justfile watcher, warned about in recipe results while
+    /// it is being restarted
+    watcher_supervision: Option<Arc<Supervision>>,
+
     /// Handle to the framework for notifying of tool changes
     #[cfg(feature = "ultrafast-framework")]
     framework_handle: Option<FrameworkHandle>,
@@ -92,6 +124,14 @@ impl DynamicToolHandler {
             registry,
             executor,
             admin_tools: None,
+            output_store: None,
+            output_link_threshold: super::output_store::DEFAULT_LINK_THRESHOLD,
+            parameter_memory: None,
+            run_history: None,
+            profile: std::sync::Mutex::new(None),
+            schema_mode: std::sync::Mutex::new(SchemaMode::default()),
+            call_dedup: None,
+            watcher_supervision: None,
             #[cfg(feature = "ultrafast-framework")]
             framework_handle: None,
         }
@@ -110,6 +150,136 @@ impl DynamicToolHandler {
         self
     }
 
+    /// Link to outputs larger than `threshold` bytes instead of inlining them
+    ///
+    /// The full output is kept in `store` and served as a resource; the tool
+    /// result only carries its tail and the resource URI.
+    pub fn with_output_links(mut self, store: Arc<OutputStore>, threshold: usize) -> Self {
+        self.output_store = Some(store);
+        self.output_link_threshold = threshold;
+        self
+    }
+
+    /// Offer argument values from earlier successful calls as schema defaults
+    pub fn with_parameter_memory(mut self, memory: Arc<ParameterMemory>) -> Self {
+        self.parameter_memory = Some(memory);
+        self
+    }
+
+    /// Add what changed since the previous run of the same tool with the
+    /// same arguments to each result
+    pub fn with_run_history(mut self, history: Arc<RunHistory>) -> Self {
+        self.run_history = Some(history);
+        self
+    }
+
+    /// Run recipe calls under this named profile unless they select another
+    pub fn with_profile(self, profile: Option<String>) -> Self {
+        *self.profile.lock().unwrap() = profile;
+        self
+    }
+
+    /// Send tool schemas in `mode` until the session changes it
+    pub fn with_schema_mode(self, mode: SchemaMode) -> Self {
+        *self.schema_mode.lock().unwrap() = mode;
+        self
+    }
+
+    /// Share a running execution with identical calls instead of running the
+    /// recipe again
+    pub fn with_call_dedup(mut self, dedup: Arc<CallDedup>) -> Self {
+        self.call_dedup = Some(dedup);
+        self
+    }
+
+    /// Warn in recipe results while the watcher is down and tools may be stale
+    pub fn with_watcher_supervision(mut self, supervision: Arc<Supervision>) -> Self {
+        self.watcher_supervision = Some(supervision);
+        self
+    }
+
+    /// Remembered argument values of this session, if enabled
+    pub fn parameter_memory(&self) -> Option<&Arc<ParameterMemory>> {
+        self.parameter_memory.as_ref()
+    }
+
+    /// Input schema of a tool as generated for `tools/list`, with remembered
+    /// argument values as defaults
+    fn listed_input_schema(&self, tool: &ToolDefinition) -> serde_json::Value {
+        match &self.parameter_memory {
+            Some(memory) if !tool.name.starts_with("_admin_") => {
+                memory.apply_defaults(&tool.input_schema)
+            }
+            _ => tool.input_schema.clone(),
+        }
+    }
+
+    /// Switch the session's schema mode, measuring what each mode costs
+    async fn select_schema_mode(&self, mode: SchemaMode) -> SchemaModeSelection {
+        let (full_bytes, compact_bytes) =
+            self.tools
+                .read()
+                .await
+                .values()
+                .fold((0, 0), |(full, compact), tool| {
+                    let schema = self.listed_input_schema(tool);
+                    (
+                        full + schema.to_string().len(),
+                        compact + SchemaMode::Compact.apply(&schema).to_string().len(),
+                    )
+                });
+        let previous = std::mem::replace(&mut *self.schema_mode.lock().unwrap(), mode);
+        SchemaModeSelection {
+            mode,
+            previous,
+            full_bytes,
+            compact_bytes,
+        }
+    }
+
+    /// Record a run's output and describe what changed since the previous
+    /// run of the same call
+    #[cfg(feature = "ultrafast-framework")]
+    fn run_changes(
+        &self,
+        tool_name: &str,
+        arguments: &serde_json::Map<String, serde_json::Value>,
+        result: &ExecutionResult,
+    ) -> Option<ToolContent> {
+        let history = self.run_history.as_ref()?;
+        let output = if result.stderr.is_empty() {
+            result.stdout.clone()
+        } else {
+            format!("{}\n--- stderr ---\n{}", result.stdout, result.stderr)
+        };
+        let previous = history.record(tool_name, arguments, &output, result.exit_code)?;
+        Some(ToolContent::text(super::run_history::change_summary(
+            &previous,
+            &output,
+            result.exit_code,
+        )))
+    }
+
+    /// Content for a tool's output text, linking to it if it is too large
+    #[cfg(feature = "ultrafast-framework")]
+    fn output_content(&self, tool_name: &str, text: String) -> Vec<ToolContent> {
+        match &self.output_store {
+            Some(store) if text.len() > self.output_link_threshold => {
+                let size = text.len();
+                let tail = super::output_store::tail(&text).to_string();
+                let uri = store.store(tool_name, text);
+                vec![
+                    ToolContent::text(super::output_store::preview(size, &tail, &uri)),
+                    ToolContent::resource_with_description(
+                        uri,
+                        format!("Full output of {tool_name} ({size} bytes)"),
+                    ),
+                ]
+            }
+            _ => vec![ToolContent::text(text)],
+        }
+    }
+
     /// Check if admin tools are available
     pub fn has_admin_tools(&self) -> bool {
         self.admin_tools.is_some()
@@ -156,26 +326,48 @@ impl DynamicToolHandler {
         );
 
         // Convert parameters to HashMap<String, serde_json::Value>
-        let params = if let serde_json::Value::Object(map) = parameters {
-            map.into_iter().collect()
-        } else {
-            HashMap::new()
+        let arguments = match parameters {
+            serde_json::Value::Object(map) => map,
+            _ => serde_json::Map::new(),
         };
+        let remembered = self.parameter_memory.as_ref().map(|_| arguments.clone());
+        let dedup_arguments = self.call_dedup.as_ref().map(|_| arguments.clone());
+        let params = arguments.into_iter().collect();
+        let profile = self.profile.lock().unwrap().clone();
 
         // Create execution request with the correct tool name format
         let request = ExecutionRequest {
             tool_name: execution_tool_name,
             parameters: params,
-            context: Default::default(),
+            context: ExecutionContext {
+                profile: profile.clone(),
+                ..Default::default()
+            },
         };
 
         tracing::debug!("Created execution request: {:?}", request);
 
         // Execute using the existing TaskExecutor
         // This preserves ALL existing security validation, resource limits,
-        // parameter sanitization, path validation, and error handling
-        let mut executor = self.executor.lock().await;
-        let result = executor.execute(request).await;
+        // parameter sanitization, path validation, and error handling.
+        // The executor is cloned out of the lock so executions run
+        // concurrently, scheduled fairly across projects by its resource manager
+        let mut executor = self.executor.lock().await.clone();
+        let execution = executor.execute(request);
+        let mut result = match (&self.call_dedup, &dedup_arguments) {
+            (Some(dedup), Some(arguments)) => {
+                dedup
+                    .run(tool_name, arguments, profile.as_deref(), execution)
+                    .await
+            }
+            _ => execution.await,
+        };
+        if let (Ok(exec_result), Some(warning)) = (
+            &mut result,
+            self.watcher_supervision.as_ref().and_then(|s| s.warning()),
+        ) {
+            exec_result.warnings.push(warning);
+        }
 
         match &result {
             Ok(exec_result) => {
@@ -185,6 +377,11 @@ impl DynamicToolHandler {
                     exec_result.success,
                     exec_result.exit_code
                 );
+                if let (true, Some(memory), Some(arguments)) =
+                    (exec_result.success, &self.parameter_memory, &remembered)
+                {
+                    memory.record(arguments);
+                }
                 if !exec_result.success {
                     tracing::warn!(
                         "Tool execution failed: {} - stderr: {}, error: {:?}",
@@ -225,18 +422,16 @@ impl DynamicToolHandler {
                 ExecutionResult {
                     success: true,
                     exit_code: Some(0),
-                    stdout: format!(
-                        "Sync completed: {} files scanned, {} recipes found in {} ms",
-                        sync_result.scanned_files,
-                        sync_result.found_recipes,
-                        sync_result.duration_ms
-                    ),
+                    stdout: serde_json::to_string_pretty(&sync_result)?,
                     stderr: if sync_result.errors.is_empty() {
                         String::new()
                     } else {
                         sync_result.errors.join("; ")
                     },
                     error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
                 }
             }
             "_admin_parser_doctor" => {
@@ -252,8 +447,221 @@ impl DynamicToolHandler {
                     stdout: report,
                     stderr: String::new(),
                     error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
+                }
+            }
+            "_admin_set_limits" => {
+                let changes: crate::resource_limits::LimitChanges =
+                    serde_json::from_value(parameters.clone()).map_err(|e| {
+                        crate::error::Error::InvalidParameter(format!("Invalid limits: {e}"))
+                    })?;
+
+                let result = admin_tools.set_limits(changes)?;
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&result)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
+                }
+            }
+            "_admin_env_doctor" => {
+                let diff = admin_tools.env_doctor().await?;
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&diff)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: diff.warnings(),
+                    error_kind: None,
+                }
+            }
+            "_admin_status" => {
+                let status = admin_tools.status().await?;
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&status)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
+                }
+            }
+            "_admin_gc" => {
+                let dry_run = parameters
+                    .get("dry_run")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+
+                let report = admin_tools.gc(dry_run).await?;
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&report)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
+                }
+            }
+            "_admin_access_log" => {
+                let session = parameters.get("session").and_then(|v| v.as_str());
+                let limit = parameters
+                    .get("limit")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(20) as usize;
+
+                let report = admin_tools.access_log(session, limit)?;
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&report)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
+                }
+            }
+            "_admin_running_executions" => {
+                let report = admin_tools.running_executions()?;
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&report)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
+                }
+            }
+            "_admin_cancel_execution" => {
+                let id = parameters
+                    .get("id")
+                    .and_then(|v| v.as_u64())
+                    .ok_or_else(|| {
+                        crate::error::Error::InvalidParameter("Missing 'id' parameter".to_string())
+                    })?;
+
+                let cancelled = admin_tools.cancel_execution(id)?;
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&cancelled)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
+                }
+            }
+            "_admin_use_profile" => {
+                let profile = parameters
+                    .get("profile")
+                    .and_then(|v| v.as_str())
+                    .filter(|name| !name.is_empty());
+
+                let profiles = self.executor.lock().await.profiles().clone();
+                if let Some(name) = profile {
+                    profiles.require(name)?;
+                }
+                let previous = std::mem::replace(
+                    &mut *self.profile.lock().unwrap(),
+                    profile.map(str::to_string),
+                );
+                let selection = crate::profiles::ProfileSelection {
+                    profile: profile.map(str::to_string),
+                    previous,
+                    available: profiles.names().into_iter().map(str::to_string).collect(),
+                };
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&selection)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
+                }
+            }
+            "_admin_schema_mode" => {
+                let mode = parameters
+                    .get("mode")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        crate::error::Error::InvalidParameter(
+                            "Missing 'mode' parameter".to_string(),
+                        )
+                    })?
+                    .parse()?;
+                let selection = self.select_schema_mode(mode).await;
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&selection)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
+                }
+            }
+            "_admin_coverage" => {
+                let report = admin_tools.coverage().await?;
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&report)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
                 }
             }
+            "_admin_run_inline_justfile" => {
+                if !admin_tools.inline_justfiles_enabled() {
+                    return Err(crate::error::Error::Policy(
+                        "Inline justfiles are disabled; start the server with --allow-inline-justfiles"
+                            .to_string(),
+                    ));
+                }
+                let field = |name: &str| {
+                    parameters
+                        .get(name)
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string())
+                        .ok_or_else(|| {
+                            crate::error::Error::InvalidParameter(format!(
+                                "Missing '{name}' parameter"
+                            ))
+                        })
+                };
+                let request = crate::executor::inline::InlineRequest {
+                    content: field("content")?,
+                    recipe: field("recipe")?,
+                    parameters: parameters
+                        .get("arguments")
+                        .and_then(|v| v.as_object())
+                        .map(|arguments| arguments.clone().into_iter().collect())
+                        .unwrap_or_default(),
+                    context: ExecutionContext::default(),
+                };
+                let mut executor = self.executor.lock().await.clone();
+                executor.execute_inline(request).await?
+            }
             "_admin_set_watch_directory" => {
                 let path = parameters
                     .get("path")
@@ -270,12 +678,96 @@ impl DynamicToolHandler {
                 ExecutionResult {
                     success: true,
                     exit_code: Some(0),
-                    stdout: format!(
-                        "Watch directory set to: {} (justfile detected: {})",
-                        result.absolute_path, result.justfile_detected
-                    ),
+                    stdout: serde_json::to_string_pretty(&result)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
+                }
+            }
+            "_admin_extract_group" => {
+                let group = parameters
+                    .get("group")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        crate::error::Error::Other("Missing 'group' parameter".to_string())
+                    })?;
+
+                let params = crate::admin::ExtractGroupParams {
+                    watch_name: parameters
+                        .get("watch_name")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string()),
+                    group: group.to_string(),
+                    target_file: parameters
+                        .get("target_file")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string()),
+                };
+
+                let result = admin_tools.extract_group(params).await?;
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&result)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
+                }
+            }
+            "_admin_inline_import" => {
+                let import_path = parameters
+                    .get("import_path")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        crate::error::Error::Other("Missing 'import_path' parameter".to_string())
+                    })?;
+
+                let params = crate::admin::InlineImportParams {
+                    watch_name: parameters
+                        .get("watch_name")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string()),
+                    import_path: import_path.to_string(),
+                    keep_file: parameters
+                        .get("keep_file")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                };
+
+                let result = admin_tools.inline_import(params).await?;
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&result)?,
+                    stderr: String::new(),
+                    error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
+                }
+            }
+            "_admin_apply_changes" => {
+                let params: crate::admin::ApplyChangesParams = serde_json::from_value(parameters)
+                    .map_err(|e| {
+                    crate::error::Error::InvalidParameter(format!(
+                        "Invalid _admin_apply_changes parameters: {e}"
+                    ))
+                })?;
+
+                let result = admin_tools.apply_changes(params).await?;
+                ExecutionResult {
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: serde_json::to_string_pretty(&result)?,
                     stderr: String::new(),
                     error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
                 }
             }
             "_admin_create_recipe" => {
@@ -341,18 +833,27 @@ impl DynamicToolHandler {
                     recipe: recipe.to_string(),
                     parameters: parameters_array,
                     dependencies,
+                    target_file: parameters
+                        .get("target_file")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string()),
                 };
 
                 let result = admin_tools.create_recipe(params).await?;
+                // The new tool is callable in this session right away; other
+                // sessions pick up the delta on their next sync
+                if !result.registry_delta.is_empty() {
+                    self.sync_tools_from_registry().await?;
+                }
                 ExecutionResult {
                     success: true,
                     exit_code: Some(0),
-                    stdout: format!(
-                        "Recipe '{}' created in {} (backup: {})",
-                        result.recipe_name, result.justfile_path, result.backup_path
-                    ),
+                    stdout: serde_json::to_string_pretty(&result)?,
                     stderr: String::new(),
                     error: None,
+                    invocation: None,
+                    warnings: Vec::new(),
+                    error_kind: None,
                 }
             }
             _ => {
@@ -387,6 +888,16 @@ impl DynamicToolHandler {
         // Calculate detailed diff with current tools
         let diff = self.calculate_tool_diff(&registry_tools).await;
 
+        // Update internal state with new tools, also when only bookkeeping
+        // such as source hashes changed
+        {
+            let mut tools = self.tools.write().await;
+            tools.clear();
+            for tool in registry_tools {
+                tools.insert(tool.name.clone(), tool);
+            }
+        }
+
         if diff.is_empty() {
             tracing::debug!("No tool changes detected, skipping framework update");
             return Ok(());
@@ -399,15 +910,6 @@ impl DynamicToolHandler {
             diff.modified.len()
         );
 
-        // Update internal state with new tools
-        {
-            let mut tools = self.tools.write().await;
-            tools.clear();
-            for tool in registry_tools {
-                tools.insert(tool.name.clone(), tool);
-            }
-        }
-
         // Notify framework of changes with batching
         self.notify_framework_of_changes_batched(diff).await?;
 
@@ -444,23 +946,14 @@ impl DynamicToolHandler {
             .map(|&tool| tool.clone())
             .collect();
 
-        // Find modified tools (same name, different content)
+        // Find modified tools (same name, different content as clients see
+        // it; a new source hash alone is not a change)
         let modified: Vec<_> = current_names
             .intersection(&new_names)
             .filter_map(|name| {
                 let current = current_tools.get(name)?;
                 let new = new_tools_map.get(name)?;
-
-                // Compare relevant fields for changes
-                if current.description != new.description
-                    || current.input_schema != new.input_schema
-                    || current.source_hash != new.source_hash
-                    || current.dependencies != new.dependencies
-                {
-                    Some((*new).clone())
-                } else {
-                    None
-                }
+                current.differs_for_clients(new).then(|| (*new).clone())
             })
             .collect();
 
@@ -589,6 +1082,27 @@ impl DynamicToolHandler {
         self.notify_framework_of_changes_batched(diff).await
     }
 
+    /// Tool description as presented to MCP clients
+    ///
+    /// MCP tools only carry a single description, so a long description
+    /// from the tool metadata is appended after the summary line, followed
+    /// by a note on where `[no-cd]` recipes run.
+    pub fn full_description(tool: &ToolDefinition) -> String {
+        let mut description = match &tool.metadata.long_description {
+            Some(long) if long.trim() != tool.description.trim() => {
+                format!("{}\n\n{}", tool.description, long)
+            }
+            _ => tool.description.clone(),
+        };
+        if tool.metadata.no_cd {
+            description.push_str(
+                "\n\nRuns in the invocation directory ([no-cd]), not the justfile's directory; \
+                 relative path arguments are resolved from the server's working directory.",
+            );
+        }
+        description
+    }
+
     /// Convert our ToolDefinition to framework-compatible format
     #[cfg(feature = "ultrafast-framework")]
     fn convert_to_framework_tool(&self, tool: &ToolDefinition) -> Result<FrameworkTool> {
@@ -596,7 +1110,7 @@ impl DynamicToolHandler {
         // For now, create a placeholder that represents what we'd need
         Ok(FrameworkTool {
             name: tool.name.clone(),
-            description: tool.description.clone(),
+            description: Self::full_description(tool),
             input_schema: tool.input_schema.clone(),
         })
     }
@@ -679,6 +1193,8 @@ pub struct McpToolCall {
 pub struct McpToolResult {
     pub content: Vec<McpContent>,
     pub is_error: Option<bool>,
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<serde_json::Value>,
 }
 
 /// MCP Content type for tool results
@@ -817,9 +1333,22 @@ impl FrameworkToolHandler {
             text: format!("Troubleshooting: {troubleshooting_hint}"),
         });
 
+        if let Some(invocation) = &result.invocation {
+            content.push(McpContent::Text {
+                text: format!("Command: {}", invocation.command_line()),
+            });
+        }
+
+        for warning in &result.warnings {
+            content.push(McpContent::Text {
+                text: format!("Warning: {warning}"),
+            });
+        }
+
         Ok(McpToolResult {
             content,
             is_error: Some(true),
+            meta: Some(FailureClass::from(ErrorAdapter::classify_failure(&result)).to_meta()),
         })
     }
 
@@ -862,9 +1391,17 @@ impl FrameworkToolHandler {
             });
         }
 
+        // Redacted command line, when command echo is enabled
+        if let Some(invocation) = &result.invocation {
+            content.push(McpContent::Text {
+                text: format!("Command: {}", invocation.command_line()),
+            });
+        }
+
         Ok(McpToolResult {
             content,
             is_error: Some(false), // Explicitly mark as successful
+            meta: None,
         })
     }
 
@@ -959,13 +1496,14 @@ impl ToolHandler for DynamicToolHandler {
         tracing::debug!("ToolHandler::list_tools called");
 
         let tools = self.tools.read().await;
+        let mode = *self.schema_mode.lock().unwrap();
         let framework_tools: Vec<Tool> = tools
             .values()
             .map(|tool| Tool {
                 name: tool.name.clone(),
-                description: tool.description.clone(),
-                input_schema: tool.input_schema.clone(),
-                output_schema: None,
+                description: Self::full_description(tool),
+                input_schema: mode.apply(&self.listed_input_schema(tool)),
+                output_schema: crate::admin::schemas::output_schema(&tool.name),
                 annotations: None,
             })
             .collect();
@@ -980,31 +1518,77 @@ impl ToolHandler for DynamicToolHandler {
     async fn handle_tool_call(&self, call: ToolCall) -> MCPResult<ToolResult> {
         tracing::info!("ToolHandler::handle_tool_call: {}", call.name);
 
-        match self
-            .execute_tool(&call.name, call.arguments.unwrap_or_default())
-            .await
-        {
+        let arguments = call.arguments.unwrap_or_default();
+        let call_arguments = match &arguments {
+            serde_json::Value::Object(map) if self.run_history.is_some() => map.clone(),
+            _ => serde_json::Map::new(),
+        };
+        match self.execute_tool(&call.name, arguments).await {
             Ok(execution_result) => {
+                let changes = self.run_changes(&call.name, &call_arguments, &execution_result);
+                let command = execution_result.invocation.as_ref().map(|invocation| {
+                    ToolContent::text(format!("Command: {}", invocation.command_line()))
+                });
+                let warnings = execution_result
+                    .warnings
+                    .iter()
+                    .map(|warning| ToolContent::text(format!("Warning: {warning}")))
+                    .collect::<Vec<_>>();
                 if execution_result.success {
+                    // Structured results are returned whole, so the transport
+                    // can parse them into structured content
+                    let mut content = match crate::admin::schemas::output_schema(&call.name) {
+                        Some(_) => vec![ToolContent::text(execution_result.stdout)],
+                        None => self.output_content(&call.name, execution_result.stdout),
+                    };
+                    content.extend(changes);
+                    content.extend(command);
+                    content.extend(warnings);
                     Ok(ToolResult {
-                        content: vec![ToolContent::text(execution_result.stdout)],
+                        content,
                         is_error: Some(false),
                     })
                 } else {
-                    Ok(ToolResult {
-                        content: vec![ToolContent::text(format!(
+                    let mut content = self.output_content(
+                        &call.name,
+                        format!(
                             "Tool execution failed:\nstdout: {}\nstderr: {}\nexit_code: {:?}",
                             execution_result.stdout,
                             execution_result.stderr,
                             execution_result.exit_code
-                        ))],
+                        ),
+                    );
+                    content.extend(changes);
+                    content.extend(command);
+                    content.extend(warnings);
+                    content.push(ToolContent::text(
+                        FailureClass::from(ErrorAdapter::classify_failure(&execution_result))
+                            .to_envelope(),
+                    ));
+                    Ok(ToolResult {
+                        content,
                         is_error: Some(true),
                     })
                 }
             }
+            Err(crate::error::Error::TaskNotFound(name)) => {
+                let tools = self.tools.read().await;
+                Err(MCPError::invalid_params(format!(
+                    "{}\n{}",
+                    super::tool_suggestions::unknown_tool_message(
+                        &name,
+                        tools.keys().map(String::as_str),
+                    ),
+                    FailureClass::from(ErrorKind::UserError).to_envelope()
+                )))
+            }
             Err(e) => {
                 tracing::error!("Tool execution error: {}", e);
-                Err(MCPError::internal_error(e.to_string()))
+                let class = FailureClass::from(ErrorAdapter::classify_error(&e));
+                Err(MCPError::internal_error(format!(
+                    "{e}\n{}",
+                    class.to_envelope()
+                )))
             }
         }
     }
@@ -1031,6 +1615,7 @@ mod tests {
             source_hash: format!("hash_{name}"),
             last_modified: SystemTime::now(),
             internal_name: None,
+            metadata: Default::default(),
         }
     }
 
@@ -1069,6 +1654,68 @@ mod tests {
         assert_eq!(tools[0].name, "test_tool");
     }
 
+    #[tokio::test]
+    async fn test_unknown_tool_error_suggests_close_matches() {
+        let registry = Arc::new(tokio::sync::Mutex::new(ToolRegistry::new()));
+        let executor = Arc::new(tokio::sync::Mutex::new(TaskExecutor::new()));
+        for name in ["build@api", "build@web", "test"] {
+            registry
+                .lock()
+                .await
+                .add_tool(create_test_tool(name))
+                .unwrap();
+        }
+        let handler = DynamicToolHandler::new(registry, executor);
+        handler.sync_tools_from_registry().await.unwrap();
+
+        let call = ToolCall {
+            name: "build".to_string(),
+            arguments: Some(json!({})),
+        };
+        let error = ToolHandler::handle_tool_call(&handler, call)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("Did you mean: build@api, build@web?"),
+            "{error}"
+        );
+        assert!(error.contains("task@project"));
+    }
+
+    #[tokio::test]
+    async fn test_compact_schema_mode() {
+        let registry = Arc::new(tokio::sync::Mutex::new(ToolRegistry::new()));
+        let executor = Arc::new(tokio::sync::Mutex::new(TaskExecutor::new()));
+        let mut tool = create_test_tool("build");
+        tool.input_schema["$schema"] = json!("http://json-schema.org/draft-07/schema#");
+        tool.input_schema["additionalProperties"] = json!(false);
+        registry.lock().await.add_tool(tool).unwrap();
+        let handler = DynamicToolHandler::new(registry, executor);
+        handler.sync_tools_from_registry().await.unwrap();
+
+        assert!(
+            ToolHandler::list_tools(&handler, ListToolsRequest::default())
+                .await
+                .unwrap()
+                .tools[0]
+                .input_schema["$schema"]
+                .is_string()
+        );
+
+        let selection = handler.select_schema_mode(SchemaMode::Compact).await;
+        assert_eq!(selection.previous, SchemaMode::Full);
+        assert!(selection.compact_bytes < selection.full_bytes);
+        assert_eq!(
+            ToolHandler::list_tools(&handler, ListToolsRequest::default())
+                .await
+                .unwrap()
+                .tools[0]
+                .input_schema,
+            json!({"type": "object"})
+        );
+    }
+
     #[tokio::test]
     async fn test_tool_diff_calculation() {
         let registry = Arc::new(tokio::sync::Mutex::new(ToolRegistry::new()));
@@ -1241,6 +1888,7 @@ mod tests {
             // Use a valid tool name format that won't be found during execution
             // This tests the execution path without requiring a real justfile
             internal_name: Some("echo_test_/tmp/nonexistent/justfile".to_string()),
+            metadata: Default::default(),
         };
 
         {
@@ -1315,6 +1963,64 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "ultrafast-framework")]
+    #[test]
+    fn test_large_outputs_are_linked() {
+        let registry = Arc::new(tokio::sync::Mutex::new(ToolRegistry::new()));
+        let executor = Arc::new(tokio::sync::Mutex::new(TaskExecutor::new()));
+        let store = Arc::new(OutputStore::default());
+        let handler =
+            DynamicToolHandler::new(registry, executor).with_output_links(store.clone(), 16);
+
+        let content = handler.output_content("build", "short".to_string());
+        assert!(matches!(&content[..], [ToolContent::Text { text }] if text == "short"));
+
+        let output = "line\n".repeat(10);
+        let content = handler.output_content("build", output.clone());
+        let [ToolContent::Text { text }, ToolContent::Resource { resource }] = &content[..] else {
+            panic!("expected a preview and a resource link, got {content:?}");
+        };
+        assert!(text.contains(&resource.uri));
+        assert_eq!(store.get(&resource.uri).unwrap().text, output);
+    }
+
+    #[cfg(feature = "ultrafast-framework")]
+    #[test]
+    fn test_repeated_runs_are_diffed() {
+        let registry = Arc::new(tokio::sync::Mutex::new(ToolRegistry::new()));
+        let executor = Arc::new(tokio::sync::Mutex::new(TaskExecutor::new()));
+        let handler = DynamicToolHandler::new(registry, executor)
+            .with_run_history(Arc::new(RunHistory::default()));
+        let result = |stdout: &str, exit_code| ExecutionResult {
+            success: exit_code == 0,
+            exit_code: Some(exit_code),
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            error: None,
+            invocation: None,
+            warnings: Vec::new(),
+            error_kind: None,
+        };
+        let arguments = json!({"fix": "false"}).as_object().unwrap().clone();
+
+        let first = result("a.rs: 1 warning\nb.rs: ok\n", 1);
+        assert!(handler.run_changes("lint", &arguments, &first).is_none());
+
+        let second = result("a.rs: ok\nb.rs: ok\n", 0);
+        let Some(ToolContent::Text { text }) = handler.run_changes("lint", &arguments, &second)
+        else {
+            panic!("expected a change summary");
+        };
+        assert!(text.contains("exit code 1 → 0"), "{text}");
+        assert!(text.contains("-a.rs: 1 warning\n+a.rs: ok"), "{text}");
+
+        let Some(ToolContent::Text { text }) = handler.run_changes("lint", &arguments, &second)
+        else {
+            panic!("expected a change summary");
+        };
+        assert!(text.starts_with("Output unchanged"), "{text}");
+    }
+
     #[tokio::test]
     async fn test_execution_result_to_mcp_conversion() {
         use crate::types::ExecutionResult;
@@ -1335,6 +2041,9 @@ mod tests {
                 stdout: "Operation completed successfully".to_string(),
                 stderr: String::new(),
                 error: None,
+                invocation: None,
+                warnings: Vec::new(),
+                error_kind: None,
             };
 
             let mcp_result = framework_handler
@@ -1360,6 +2069,9 @@ mod tests {
                 stdout: String::new(),
                 stderr: "Command failed".to_string(),
                 error: Some("Tool execution failed".to_string()),
+                invocation: None,
+                warnings: Vec::new(),
+                error_kind: None,
             };
 
             let mcp_error_result = framework_handler
@@ -1385,6 +2097,13 @@ mod tests {
             assert!(all_content.contains("Tool execution failed"));
             // The new error format includes different content
             assert!(all_content.contains("failed with exit code"));
+            assert_eq!(
+                mcp_error_result
+                    .meta
+                    .as_ref()
+                    .and_then(FailureClass::from_meta),
+                Some(FailureClass::from(ErrorKind::UserError))
+            );
         }
     }
 
@@ -1422,6 +2141,7 @@ mod tests {
             source_hash: "test_hash".to_string(),
             last_modified: SystemTime::now(),
             internal_name: Some("valid_tool_/tmp/test/justfile".to_string()),
+            metadata: Default::default(),
         };
 
         {
@@ -1492,6 +2212,7 @@ mod tests {
             source_hash: "build_hash".to_string(),
             last_modified: SystemTime::now(),
             internal_name: Some("build_task_/tmp/project/justfile".to_string()),
+            metadata: Default::default(),
         };
 
         {
@@ -7,8 +7,10 @@
 //! messages while ensuring compatibility with MCP protocol standards and
 //! framework expectations.
 
+pub use crate::error::ErrorKind;
 use crate::error::{Error as JustMcpError, Result as JustMcpResult};
 use crate::types::ExecutionResult;
+use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "ultrafast-framework")]
 use ultrafast_mcp::{MCPError, MCPResult};
@@ -73,6 +75,9 @@ impl ErrorAdapter {
                 ))
             }
 
+            // Missing just binary, with install instructions
+            JustMcpError::JustNotInstalled { .. } => MCPError::internal_error(error.to_string()),
+
             // Parse errors with location information
             JustMcpError::Parse { message, line, column } => {
                 MCPError::invalid_request(format!(
@@ -94,6 +99,14 @@ impl ErrorAdapter {
                 ))
             }
 
+            // Refusals by the server's configuration
+            JustMcpError::Policy(_) => MCPError::invalid_request(error.to_string()),
+
+            // Concurrency limits
+            JustMcpError::Busy(msg) => MCPError::internal_error(format!(
+                "Server busy: {msg}. Retry once running executions finish."
+            )),
+
             // Timeout errors
             JustMcpError::Timeout(msg) => {
                 MCPError::internal_error(format!(
@@ -249,6 +262,13 @@ impl ErrorAdapter {
                 is_user_error: true, // User needs to fix justfile
                 is_retryable: false,
             },
+            JustMcpError::JustNotInstalled { needed_for, hint } => ErrorInfo {
+                error_type: "just_not_installed".to_string(),
+                user_message: error.to_string(),
+                technical_details: format!("JustNotInstalled: needed {needed_for}; {hint}"),
+                is_user_error: true, // Installing just fixes it
+                is_retryable: false,
+            },
             JustMcpError::Timeout(msg) => ErrorInfo {
                 error_type: "timeout".to_string(),
                 user_message: "Operation timed out".to_string(),
@@ -284,6 +304,7 @@ impl ErrorAdapter {
                 | JustMcpError::InvalidParameter(_)
                 | JustMcpError::Parse { .. }
                 | JustMcpError::InvalidToolName(_)
+                | JustMcpError::JustNotInstalled { .. }
         )
     }
 
@@ -339,9 +360,12 @@ impl ErrorAdapter {
             | JustMcpError::Parse { .. }
             | JustMcpError::InvalidToolName(_) => ErrorCategory::UserError,
 
-            JustMcpError::Io(_) | JustMcpError::Watch(_) | JustMcpError::Timeout(_) => {
-                ErrorCategory::SystemError
-            }
+            JustMcpError::Io(_)
+            | JustMcpError::Watch(_)
+            | JustMcpError::Timeout(_)
+            | JustMcpError::Policy(_)
+            | JustMcpError::Busy(_)
+            | JustMcpError::JustNotInstalled { .. } => ErrorCategory::SystemError,
 
             JustMcpError::Registry(_)
             | JustMcpError::Server(_)
@@ -357,6 +381,150 @@ impl ErrorAdapter {
     }
 }
 
+/// Key of the failure classification in the `_meta` of failed tool results
+/// and in the `data` of tool call errors
+pub const FAILURE_META_KEY: &str = "just-mcp/failure";
+
+/// Machine-readable classification of a failed tool call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FailureClass {
+    pub error_kind: ErrorKind,
+    pub retry_advisable: bool,
+}
+
+impl From<ErrorKind> for FailureClass {
+    fn from(error_kind: ErrorKind) -> Self {
+        Self {
+            error_kind,
+            retry_advisable: error_kind.retry_advisable(),
+        }
+    }
+}
+
+impl FailureClass {
+    /// `_meta` object, or error `data`, holding this class
+    pub fn to_meta(self) -> serde_json::Value {
+        serde_json::json!({ FAILURE_META_KEY: self })
+    }
+
+    /// Class held by a `_meta` object or error `data`
+    pub fn from_meta(meta: &serde_json::Value) -> Option<Self> {
+        serde_json::from_value(meta.get(FAILURE_META_KEY)?.clone()).ok()
+    }
+
+    /// Text the tool handler attaches to a failure for
+    /// [`super::structured_content::StructuredContentTransport`], which
+    /// moves it into the result's `_meta` or the error's `data`: the
+    /// framework's result and error types have room for neither
+    pub fn to_envelope(self) -> String {
+        self.to_meta().to_string()
+    }
+
+    /// Class carried by text from [`Self::to_envelope`]
+    pub fn from_envelope(text: &str) -> Option<Self> {
+        let meta = serde_json::from_str::<serde_json::Value>(text).ok()?;
+        if meta.as_object()?.len() != 1 {
+            return None;
+        }
+        Self::from_meta(&meta)
+    }
+}
+
+/// Output of commands failing for reasons that tend to clear by themselves
+const TRANSIENT_OUTPUT: &[&str] = &[
+    "connection refused",
+    "connection reset",
+    "temporary failure",
+    "temporarily unavailable",
+    "network is unreachable",
+    "could not resolve host",
+    "service unavailable",
+    "too many requests",
+    "rate limit",
+    "text file busy",
+    "resource busy",
+    "lock file exists",
+];
+
+/// Output of `just` rejecting how a recipe was called
+const USAGE_OUTPUT: &[&str] = &[
+    "does not contain recipe",
+    "got 0 arguments",
+    "arguments but takes",
+    "argument but takes",
+];
+
+impl ErrorAdapter {
+    /// Classify an error a tool call returned instead of a result
+    pub fn classify_error(error: &JustMcpError) -> ErrorKind {
+        match error {
+            JustMcpError::TaskNotFound(_)
+            | JustMcpError::ToolNotFound(_)
+            | JustMcpError::InvalidToolName(_)
+            | JustMcpError::InvalidParameter(_)
+            | JustMcpError::Parse { .. }
+            | JustMcpError::Json(_)
+            | JustMcpError::JustCommand(_) => ErrorKind::UserError,
+
+            JustMcpError::JustNotInstalled { .. } => ErrorKind::Precondition,
+            JustMcpError::Policy(_) => ErrorKind::Policy,
+            JustMcpError::Busy(_) => ErrorKind::Transient,
+            JustMcpError::Timeout(_) => ErrorKind::Timeout,
+
+            // A missing justfile or an unwritable directory stays that way
+            JustMcpError::Io(io_error) => match io_error.kind() {
+                std::io::ErrorKind::NotFound | std::io::ErrorKind::PermissionDenied => {
+                    ErrorKind::Precondition
+                }
+                _ => ErrorKind::Transient,
+            },
+            JustMcpError::Watch(_) => ErrorKind::Transient,
+
+            JustMcpError::Execution {
+                exit_code, stderr, ..
+            } => Self::classify_exit(*exit_code, stderr),
+
+            JustMcpError::Registry(_)
+            | JustMcpError::Server(_)
+            | JustMcpError::Regex(_)
+            | JustMcpError::Internal(_)
+            | JustMcpError::Other(_) => ErrorKind::UserError,
+        }
+    }
+
+    /// Classify a failed execution: by the cause the executor recorded, or
+    /// else by the exit code and stderr of the command
+    pub fn classify_failure(result: &ExecutionResult) -> ErrorKind {
+        result
+            .error_kind
+            .unwrap_or_else(|| Self::classify_exit(result.exit_code, &result.stderr))
+    }
+
+    /// Classify a command's exit from its code and error output
+    fn classify_exit(exit_code: Option<i32>, stderr: &str) -> ErrorKind {
+        let stderr = stderr.to_lowercase();
+        match exit_code {
+            // Not found or not executable
+            Some(126) | Some(127) => ErrorKind::Precondition,
+            // Killed by a signal
+            None => ErrorKind::Transient,
+            Some(_) if USAGE_OUTPUT.iter().any(|pattern| stderr.contains(pattern)) => {
+                ErrorKind::UserError
+            }
+            Some(_)
+                if TRANSIENT_OUTPUT
+                    .iter()
+                    .any(|pattern| stderr.contains(pattern)) =>
+            {
+                ErrorKind::Transient
+            }
+            Some(_) if stderr.contains("command not found") => ErrorKind::Precondition,
+            Some(_) => ErrorKind::UserError,
+        }
+    }
+}
+
 /// Trait for framework-compatible error conversion
 ///
 /// This trait allows other modules to easily convert their errors to
@@ -403,6 +571,99 @@ mod tests {
     use super::*;
     use crate::error::Error as JustMcpError;
 
+    #[test]
+    fn test_failure_classification() {
+        let failure = |exit_code, stderr: &str, error_kind| ExecutionResult {
+            success: false,
+            exit_code,
+            stdout: String::new(),
+            stderr: stderr.to_string(),
+            error: None,
+            invocation: None,
+            warnings: Vec::new(),
+            error_kind,
+        };
+        let kind = |result: ExecutionResult| ErrorAdapter::classify_failure(&result);
+
+        // A cause the executor reports wins over the output
+        assert_eq!(
+            kind(failure(None, "", Some(ErrorKind::Timeout))),
+            ErrorKind::Timeout
+        );
+        assert_eq!(
+            kind(failure(
+                Some(1),
+                "test failed",
+                Some(ErrorKind::Precondition)
+            )),
+            ErrorKind::Precondition
+        );
+        assert_eq!(
+            kind(failure(Some(1), "curl: (7) Connection refused", None)),
+            ErrorKind::Transient
+        );
+        assert_eq!(
+            kind(failure(Some(127), "sh: cargo: not found", None)),
+            ErrorKind::Precondition
+        );
+        assert_eq!(
+            kind(failure(
+                Some(1),
+                "error: Justfile does not contain recipe `biuld`",
+                None
+            )),
+            ErrorKind::UserError
+        );
+        assert_eq!(
+            kind(failure(Some(1), "test failed", None)),
+            ErrorKind::UserError
+        );
+
+        let io = |kind| JustMcpError::Io(std::io::Error::new(kind, "justfile"));
+        for (error, expected) in [
+            (
+                JustMcpError::Policy("Inline justfiles are disabled".to_string()),
+                ErrorKind::Policy,
+            ),
+            (
+                JustMcpError::Busy("Maximum concurrent executions (1) reached".to_string()),
+                ErrorKind::Transient,
+            ),
+            (
+                JustMcpError::Other("Unknown admin tool: _admin_nope".to_string()),
+                ErrorKind::UserError,
+            ),
+            (
+                JustMcpError::InvalidParameter("x".to_string()),
+                ErrorKind::UserError,
+            ),
+            (io(std::io::ErrorKind::NotFound), ErrorKind::Precondition),
+            (
+                io(std::io::ErrorKind::PermissionDenied),
+                ErrorKind::Precondition,
+            ),
+            (io(std::io::ErrorKind::Interrupted), ErrorKind::Transient),
+        ] {
+            assert_eq!(ErrorAdapter::classify_error(&error), expected, "{error}");
+        }
+
+        let class = FailureClass::from(ErrorKind::Timeout);
+        assert_eq!(
+            class.to_envelope(),
+            r#"{"just-mcp/failure":{"error_kind":"timeout","retry_advisable":true}}"#
+        );
+        assert_eq!(
+            FailureClass::from_envelope(&class.to_envelope()),
+            Some(class)
+        );
+        assert_eq!(
+            FailureClass::from_envelope(r#"{"error_kind":"timeout"}"#),
+            None
+        );
+        assert_eq!(FailureClass::from_envelope("Warning: slow"), None);
+        assert!(!FailureClass::from(ErrorKind::Policy).retry_advisable);
+    }
+
     #[test]
     fn test_error_info_extraction() {
         let error = JustMcpError::TaskNotFound("build".to_string());
@@ -435,6 +696,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_missing_just_is_a_correctable_system_error() {
+        let error = JustMcpError::JustNotInstalled {
+            needed_for: "to run recipe 'build'".to_string(),
+            hint: "Install it with `brew install just`".to_string(),
+        };
+        let info = ErrorAdapter::extract_error_info(&error);
+        assert_eq!(info.error_type, "just_not_installed");
+        assert!(info.user_message.contains("brew install just"));
+        assert!(ErrorAdapter::is_user_correctable(&error));
+        assert!(!ErrorAdapter::is_retryable(&error));
+        assert_eq!(
+            ErrorAdapter::categorize_error(&error),
+            ErrorCategory::SystemError
+        );
+    }
+
     #[test]
     fn test_user_correctable_detection() {
         assert!(ErrorAdapter::is_user_correctable(
@@ -485,6 +763,9 @@ mod tests {
             stdout: "Task completed".to_string(),
             stderr: String::new(),
             error: None,
+            invocation: None,
+            warnings: Vec::new(),
+            error_kind: None,
         };
 
         let mcp_result = ErrorAdapter::execution_result_to_mcp_result(success_result.clone());
@@ -498,6 +779,9 @@ mod tests {
             stdout: String::new(),
             stderr: "Command failed".to_string(),
             error: Some("Task execution failed".to_string()),
+            invocation: None,
+            warnings: Vec::new(),
+            error_kind: None,
         };
 
         let mcp_error_result = ErrorAdapter::execution_result_to_mcp_result(error_result);
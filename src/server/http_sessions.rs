@@ -0,0 +1,165 @@
+//! Streamable HTTP endpoint with a session per client
+//!
+//! The framework's HTTP server feeds every client's messages to a single MCP
+//! server, so clients would share protocol state and could not be told apart
+//! in the access log. Here the framework only handles HTTP: messages are
+//! routed by their `mcp-session-id` to a session of their own, built on
+//! first contact, with its own parameter memory, run history and stored
+//! outputs. Sessions idle for [`SESSION_IDLE_TIMEOUT`] are dropped.
+//!
+//! The framework's HTTP handler waits for a response to every POST, even
+//! one carrying a notification, and hands the next message the session
+//! sends to whichever POST is waiting. Notifications are therefore
+//! acknowledged right away, so their POST returns instead of taking the
+//! response to the client's next request.
+
+use super::access_log_transport::AccessLogTransport;
+use super::{FrameworkServer, SessionHandlers};
+use crate::access_log::AccessLog;
+use crate::error::Result;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use ultrafast_mcp::protocol::{JsonRpcMessage, JsonRpcResponse};
+use ultrafast_mcp::{HttpTransportConfig, HttpTransportServer};
+use ultrafast_mcp_transport::{Transport, TransportError};
+
+/// How long an HTTP session may go without a message before it is dropped
+pub const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// One HTTP client's side of the framework's HTTP server
+struct HttpSessionTransport {
+    session: String,
+    incoming: mpsc::UnboundedReceiver<JsonRpcMessage>,
+    responses: broadcast::Sender<(String, JsonRpcMessage)>,
+}
+
+#[async_trait]
+impl Transport for HttpSessionTransport {
+    async fn send_message(
+        &mut self,
+        message: JsonRpcMessage,
+    ) -> ultrafast_mcp_transport::Result<()> {
+        // Nobody listens for notifications between requests
+        let _ = self.responses.send((self.session.clone(), message));
+        Ok(())
+    }
+
+    async fn receive_message(&mut self) -> ultrafast_mcp_transport::Result<JsonRpcMessage> {
+        match tokio::time::timeout(SESSION_IDLE_TIMEOUT, self.incoming.recv()).await {
+            Ok(Some(message)) => Ok(message),
+            Ok(None) | Err(_) => Err(TransportError::ConnectionClosed),
+        }
+    }
+
+    async fn close(&mut self) -> ultrafast_mcp_transport::Result<()> {
+        Ok(())
+    }
+}
+
+impl FrameworkServer {
+    /// Serve Streamable HTTP on `config`, starting a session per client
+    pub(super) async fn serve_http(
+        &self,
+        config: HttpTransportConfig,
+        handlers: SessionHandlers,
+        access_log: Arc<AccessLog>,
+    ) -> Result<()> {
+        tracing::info!(
+            "Serving Streamable HTTP on http://{}:{}/mcp",
+            config.host,
+            config.port
+        );
+        let server = HttpTransportServer::new(config);
+        let mut messages = server.get_message_receiver();
+        let responses = server.get_response_sender();
+        let mut listener = tokio::spawn(server.run());
+        let mut sessions: HashMap<String, mpsc::UnboundedSender<JsonRpcMessage>> = HashMap::new();
+
+        loop {
+            let (id, message) = tokio::select! {
+                result = &mut listener => {
+                    return match result {
+                        Ok(Ok(())) => Ok(()),
+                        Ok(Err(e)) => Err(Self::framework_error(format!("HTTP server error: {e}"))),
+                        Err(e) => Err(Self::framework_error(format!("HTTP server task failed: {e}"))),
+                    };
+                }
+                received = messages.recv() => match received {
+                    Ok(received) => received,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("HTTP endpoint dropped {} messages", skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return Ok(()),
+                },
+            };
+
+            sessions.retain(|_, sender| !sender.is_closed());
+            if !sessions.contains_key(&id) {
+                let sender = self
+                    .start_http_session(&id, &responses, &handlers, &access_log)
+                    .await?;
+                sessions.insert(id.clone(), sender);
+            }
+            let is_notification = match &message {
+                JsonRpcMessage::Request(request) => request.id.is_none(),
+                JsonRpcMessage::Notification(_) => true,
+                JsonRpcMessage::Response(_) => false,
+            };
+            let _ = sessions[&id].send(message);
+            if is_notification {
+                let ack = JsonRpcResponse::success(serde_json::json!({}), None);
+                let _ = responses.send((id, JsonRpcMessage::Response(ack)));
+            }
+        }
+    }
+
+    /// Build a session for a new HTTP client and run it in the background
+    async fn start_http_session(
+        &self,
+        id: &str,
+        responses: &broadcast::Sender<(String, JsonRpcMessage)>,
+        handlers: &SessionHandlers,
+        access_log: &Arc<AccessLog>,
+    ) -> Result<mpsc::UnboundedSender<JsonRpcMessage>> {
+        tracing::info!("Starting HTTP session {}", id);
+        let session = self.build_session(None).await?;
+        let dynamic_handler = session.dynamic_tool_handler;
+        if let Err(e) = dynamic_handler.sync_tools_from_registry().await {
+            tracing::warn!("Failed to sync tools for HTTP session {}: {}", id, e);
+        }
+        handlers.lock().unwrap().push(dynamic_handler.clone());
+
+        let (sender, incoming) = mpsc::unbounded_channel();
+        let transport = AccessLogTransport::new(
+            Box::new(self.structured_content_transport(
+                Box::new(HttpSessionTransport {
+                    session: id.to_string(),
+                    incoming,
+                    responses: responses.clone(),
+                }),
+                &dynamic_handler,
+            )),
+            access_log.clone(),
+            id.to_string(),
+            "http",
+        );
+        let mcp_server = session.mcp_server;
+        let handlers = handlers.clone();
+        let id = id.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = mcp_server.run_with_transport(Box::new(transport)).await {
+                tracing::warn!("HTTP session {} failed: {}", id, e);
+            }
+            tracing::info!("HTTP session {} ended", id);
+            handlers
+                .lock()
+                .unwrap()
+                .retain(|handler| !Arc::ptr_eq(handler, &dynamic_handler));
+        });
+        Ok(sender)
+    }
+}
@@ -10,18 +10,46 @@
 //! - Seamless migration from custom implementation
 
 use self::error_adapter::{ErrorAdapter, ErrorCategory};
+use crate::access_log::{AccessLog, AccessLogConfig};
 use crate::admin::AdminTools;
 use crate::error::Result;
+use crate::executor::ansi::AnsiMode;
+use crate::executor::interactive::InteractiveConfig;
+use crate::executor::watchdog::WatchdogConfig;
 use crate::executor::TaskExecutor;
+use crate::parser::ParserPreference;
+use crate::profiles::ProfileSet;
+use crate::project_lock::LockConfig;
 use crate::registry::ToolRegistry;
+use crate::resource_limits::ResourceLimits;
+use crate::security::SecurityConfig;
+use crate::watcher::supervisor::RestartPolicy;
 use crate::watcher::JustfileWatcher;
+use startup_profile::StartupProfile;
 use std::path::PathBuf;
 use std::sync::Arc;
+use transports::TransportSpec;
 
+pub mod access_log_transport;
+pub mod batch_transport;
+pub mod call_dedup;
 pub mod dynamic_handler;
 pub mod error_adapter;
+#[cfg(feature = "http")]
+mod http_sessions;
+pub mod output_store;
+pub mod parameter_memory;
 pub mod prompts;
+pub mod recipe_summary;
+pub mod resource_cache;
 pub mod resources;
+pub mod run_history;
+pub mod schema_mode;
+pub mod server_info;
+pub mod startup_profile;
+pub mod structured_content;
+pub mod tool_suggestions;
+pub mod transports;
 
 // Import ultrafast-mcp framework components
 #[cfg(feature = "ultrafast-framework")]
@@ -30,6 +58,30 @@ use ultrafast_mcp::{
     UltraFastServer,
 };
 
+/// A connection point served by [`FrameworkServer::serve`]
+#[cfg(feature = "ultrafast-framework")]
+pub enum Endpoint {
+    /// A single client over an established transport, such as stdio
+    Transport(Box<dyn ultrafast_mcp_transport::Transport>),
+    /// A Streamable HTTP listener
+    #[cfg(feature = "http")]
+    Http(ultrafast_mcp::HttpTransportConfig),
+}
+
+/// Tool handlers of the live sessions, kept in sync with the registry by
+/// the watcher
+#[cfg(feature = "ultrafast-framework")]
+type SessionHandlers = Arc<std::sync::Mutex<Vec<Arc<dynamic_handler::DynamicToolHandler>>>>;
+
+/// MCP server and handlers serving one client
+#[cfg(feature = "ultrafast-framework")]
+struct Session {
+    mcp_server: UltraFastServer,
+    dynamic_tool_handler: Arc<dynamic_handler::DynamicToolHandler>,
+    resource_provider: Arc<resources::FrameworkResourceProvider>,
+    prompt_provider: Arc<prompts::FrameworkPromptProvider>,
+}
+
 /// Framework-based MCP server implementation
 ///
 /// This server replaces the custom MCP protocol handling with the ultrafast-mcp
@@ -38,6 +90,8 @@ pub struct FrameworkServer {
     watch_paths: Vec<PathBuf>,
     watch_configs: Vec<(PathBuf, Option<String>)>,
     admin_enabled: bool,
+    /// Offer `_admin_run_inline_justfile`; only takes effect with admin tools
+    inline_justfiles: bool,
     #[cfg(feature = "ultrafast-framework")]
     mcp_server: Option<UltraFastServer>,
     #[cfg(feature = "ultrafast-framework")]
@@ -48,8 +102,44 @@ pub struct FrameworkServer {
     prompt_provider: Option<Arc<prompts::FrameworkPromptProvider>>,
     registry: Arc<tokio::sync::Mutex<ToolRegistry>>,
     executor: Arc<tokio::sync::Mutex<TaskExecutor>>,
+    resource_limits: ResourceLimits,
+    interactive_config: InteractiveConfig,
+    watchdog_config: WatchdogConfig,
+    echo_commands: bool,
+    ansi_mode: AnsiMode,
+    check_environment: bool,
+    lock_config: LockConfig,
+    lock_exclusive_recipes: bool,
     watcher: Option<Arc<JustfileWatcher>>,
     admin_tools: Option<Arc<AdminTools>>,
+    startup_profile: std::sync::Mutex<StartupProfile>,
+    print_startup_profile: bool,
+    max_batch_size: usize,
+    transports: Vec<TransportSpec>,
+    access_log_config: AccessLogConfig,
+    access_log: Option<Arc<AccessLog>>,
+    output_link_threshold: usize,
+    remember_parameters: bool,
+    diff_previous_run: bool,
+    /// Executions shared by identical calls, when a dedup window is set
+    call_dedup: Option<Arc<call_dedup::CallDedup>>,
+    resource_cache_ttl: std::time::Duration,
+    /// Origin HTTP requests may carry, instead of the transport's own
+    http_allow_origin: Option<String>,
+    parser_preference: Option<ParserPreference>,
+    parser_overrides: Vec<(PathBuf, ParserPreference)>,
+    schema_mode: schema_mode::SchemaMode,
+    /// Build and runtime facts, collected by `initialize`
+    server_info: Option<Arc<server_info::ServerInfoReport>>,
+    prompts_dir: Option<PathBuf>,
+    profiles: ProfileSet,
+    default_profile: Option<String>,
+    /// Paths and argument limits recipe calls are checked against
+    security_config: SecurityConfig,
+    #[cfg(feature = "telemetry")]
+    telemetry: Option<crate::telemetry::TelemetryConfig>,
+    #[cfg(feature = "self-update")]
+    update_check: bool,
 }
 
 impl FrameworkServer {
@@ -62,6 +152,7 @@ impl FrameworkServer {
             watch_paths: vec![PathBuf::from(".")],
             watch_configs: vec![(PathBuf::from("."), None)],
             admin_enabled: false,
+            inline_justfiles: false,
             #[cfg(feature = "ultrafast-framework")]
             mcp_server: None,
             #[cfg(feature = "ultrafast-framework")]
@@ -72,8 +163,40 @@ impl FrameworkServer {
             prompt_provider: None,
             registry,
             executor,
+            resource_limits: ResourceLimits::default(),
+            interactive_config: InteractiveConfig::default(),
+            watchdog_config: WatchdogConfig::default(),
+            echo_commands: false,
+            ansi_mode: AnsiMode::default(),
+            check_environment: false,
+            lock_config: LockConfig::default(),
+            lock_exclusive_recipes: false,
             watcher: None,
             admin_tools: None,
+            startup_profile: std::sync::Mutex::new(StartupProfile::new()),
+            print_startup_profile: false,
+            max_batch_size: batch_transport::DEFAULT_MAX_BATCH_SIZE,
+            transports: vec![TransportSpec::Stdio],
+            access_log_config: AccessLogConfig::default(),
+            access_log: None,
+            output_link_threshold: output_store::DEFAULT_LINK_THRESHOLD,
+            remember_parameters: true,
+            diff_previous_run: false,
+            call_dedup: None,
+            resource_cache_ttl: resource_cache::DEFAULT_RESOURCE_CACHE_TTL,
+            http_allow_origin: None,
+            parser_preference: None,
+            parser_overrides: Vec::new(),
+            schema_mode: Default::default(),
+            server_info: None,
+            prompts_dir: None,
+            profiles: ProfileSet::default(),
+            default_profile: None,
+            security_config: SecurityConfig::default(),
+            #[cfg(feature = "telemetry")]
+            telemetry: None,
+            #[cfg(feature = "self-update")]
+            update_check: false,
         }
     }
 
@@ -86,6 +209,219 @@ impl FrameworkServer {
     /// Configure watch names for multi-directory support
     pub fn with_watch_names(mut self, configs: Vec<(PathBuf, Option<String>)>) -> Self {
         self.watch_configs = configs;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Configure execution resource limits, including per-project concurrency
+    pub fn with_resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.resource_limits = limits;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Configure how recipes waiting on interactive input are handled
+    pub fn with_interactive_config(mut self, config: InteractiveConfig) -> Self {
+        self.interactive_config = config;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Configure how running executions are sampled and flagged as hung
+    pub fn with_watchdog_config(mut self, config: WatchdogConfig) -> Self {
+        self.watchdog_config = config;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Include the redacted `just` command line in tool results
+    pub fn with_command_echo(mut self, enabled: bool) -> Self {
+        self.echo_commands = enabled;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Send tool schemas in `mode` unless a session picks another with
+    /// `_admin_schema_mode`
+    pub fn with_schema_mode(mut self, mode: schema_mode::SchemaMode) -> Self {
+        self.schema_mode = mode;
+        self
+    }
+
+    /// Choose how ANSI escape sequences in recipe output are handled
+    pub fn with_ansi_mode(mut self, mode: AnsiMode) -> Self {
+        self.ansi_mode = mode;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Report differences from the login shell environment on failed executions
+    pub fn with_environment_check(mut self, enabled: bool) -> Self {
+        self.check_environment = enabled;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Configure the advisory project lock used for admin edits and exclusive recipes
+    pub fn with_lock_config(mut self, config: LockConfig) -> Self {
+        self.lock_config = config;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Hold the project lock while `# [exclusive]` recipes run
+    pub fn with_exclusive_recipe_lock(mut self, enabled: bool) -> Self {
+        self.lock_exclusive_recipes = enabled;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Check recipe calls against `config`, such as the directories
+    /// justfiles may be run from
+    pub fn with_security_config(mut self, config: SecurityConfig) -> Self {
+        self.security_config = config;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Named profiles recipe calls can select
+    pub fn with_profiles(mut self, profiles: ProfileSet) -> Self {
+        self.profiles = profiles;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Named profile every session's recipe calls run under until the
+    /// session selects another
+    pub fn with_default_profile(mut self, profile: Option<String>) -> Self {
+        self.default_profile = profile;
+        self
+    }
+
+    /// Recreate the executor from the current configuration
+    fn rebuild_executor(&mut self) {
+        let executor = TaskExecutor::new()
+            .with_resource_limits(self.resource_limits.clone())
+            .with_interactive_config(self.interactive_config.clone())
+            .with_watchdog_config(self.watchdog_config.clone())
+            .with_command_echo(self.echo_commands)
+            .with_ansi_mode(self.ansi_mode)
+            .with_environment_check(self.check_environment)
+            .with_exclusive_lock(
+                self.lock_exclusive_recipes
+                    .then(|| self.lock_config.clone()),
+            )
+            .with_project_names(&self.watch_configs)
+            .with_profiles(self.profiles.clone())
+            .with_security_config(self.security_config.clone());
+        self.executor = Arc::new(tokio::sync::Mutex::new(executor));
+    }
+
+    /// Print the startup timing report to stderr once the initial scan is done
+    pub fn with_startup_profile(mut self, enabled: bool) -> Self {
+        self.print_startup_profile = enabled;
+        self
+    }
+
+    /// Startup phase and per-justfile parse timings recorded so far
+    pub fn startup_profile(&self) -> StartupProfile {
+        self.startup_profile.lock().unwrap().clone()
+    }
+
+    /// Reject JSON-RPC batches with more than `max` messages
+    pub fn with_max_batch_size(mut self, max: usize) -> Self {
+        self.max_batch_size = max;
+        self
+    }
+
+    /// Set the transports `run` serves, all sharing this server's registry
+    /// and executor
+    pub fn with_transports(mut self, transports: Vec<TransportSpec>) -> Self {
+        self.transports = transports;
+        self
+    }
+
+    /// Configure the file the access log of client sessions is written to
+    pub fn with_access_log(mut self, config: AccessLogConfig) -> Self {
+        self.access_log_config = config;
+        self
+    }
+
+    /// Return outputs above `threshold` bytes as a link to a `just://output/{id}`
+    /// resource instead of inline text; 0 always inlines
+    pub fn with_output_link_threshold(mut self, threshold: usize) -> Self {
+        self.output_link_threshold = threshold;
+        self
+    }
+
+    /// Send one anonymous usage report after the initial scan; `None` (the
+    /// default) sends nothing
+    #[cfg(feature = "telemetry")]
+    pub fn with_telemetry(mut self, config: Option<crate::telemetry::TelemetryConfig>) -> Self {
+        self.telemetry = config;
+        self
+    }
+
+    /// Check for newer releases that fix parsing of syntax in the watched
+    /// justfiles after the initial scan, and log a notice if there are any
+    #[cfg(feature = "self-update")]
+    pub fn with_update_check(mut self, enabled: bool) -> Self {
+        self.update_check = enabled;
+        self
+    }
+
+    /// Serve the user-defined prompts in `dir` alongside the built-in ones
+    pub fn with_prompts_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.prompts_dir = dir;
+        self
+    }
+
+    /// Offer last-used argument values as schema defaults in `tools/list`
+    pub fn with_parameter_memory(mut self, enabled: bool) -> Self {
+        self.remember_parameters = enabled;
+        self
+    }
+
+    /// Parser for justfiles without an override
+    ///
+    /// `auto` keeps the watcher's own selection, which falls back to the
+    /// legacy parser when `just` is not installed.
+    pub fn with_parser_preference(mut self, preference: ParserPreference) -> Self {
+        self.parser_preference = (preference != ParserPreference::Auto).then_some(preference);
+        self
+    }
+
+    /// Parse justfiles under these watch directories with their own parser
+    pub fn with_parser_overrides(mut self, overrides: Vec<(PathBuf, ParserPreference)>) -> Self {
+        self.parser_overrides = overrides;
+        self
+    }
+
+    /// Diff each tool result against the previous run of the same tool with
+    /// the same arguments
+    pub fn with_result_diff(mut self, enabled: bool) -> Self {
+        self.diff_previous_run = enabled;
+        self
+    }
+
+    /// Let identical tool calls arriving within `window` of a still-running
+    /// call share its execution; zero (the default) runs every call
+    pub fn with_dedup_window(mut self, window: std::time::Duration) -> Self {
+        self.call_dedup = (!window.is_zero()).then(|| Arc::new(call_dedup::CallDedup::new(window)));
+        self
+    }
+
+    /// Serve rendered summaries and config.json from memory for up to `ttl`
+    /// while the registry is unchanged; zero renders them on every read
+    pub fn with_resource_cache_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.resource_cache_ttl = ttl;
+        self
+    }
+
+    /// Accept HTTP requests whose `Origin` header is `origin` (`*` for any)
+    /// rather than the transport's own `http://host:port`
+    pub fn with_http_allow_origin(mut self, origin: Option<String>) -> Self {
+        self.http_allow_origin = origin;
         self
     }
 
@@ -95,6 +431,12 @@ impl FrameworkServer {
         self
     }
 
+    /// Let admin clients run recipes from justfile content they send
+    pub fn with_inline_justfiles(mut self, enabled: bool) -> Self {
+        self.inline_justfiles = enabled;
+        self
+    }
+
     /// Initialize the framework server
     ///
     /// Sets up the ultrafast-mcp framework with our dynamic tool handlers,
@@ -102,6 +444,138 @@ impl FrameworkServer {
     #[cfg(feature = "ultrafast-framework")]
     pub async fn initialize(&mut self) -> Result<()> {
         tracing::info!("Initializing ultrafast-mcp framework server");
+        // Server configuration, including executor and parser setup
+        self.startup_profile
+            .lock()
+            .unwrap()
+            .record_phase_since_start("server_setup");
+        let framework_start = std::time::Instant::now();
+
+        if let Some(profile) = &self.default_profile {
+            self.profiles.require(profile)?;
+        }
+        let server_info = server_info::ServerInfoReport::collect(
+            self.parser_preference
+                .as_ref()
+                .unwrap_or(&ParserPreference::Auto),
+            &self.parser_overrides,
+        );
+        tracing::info!(
+            "just-mcp {} ({}), features: {}",
+            server_info.version,
+            server_info.git_hash.as_deref().unwrap_or("unknown commit"),
+            server_info.features.join(", ")
+        );
+        if let Some(warning) = server_info.just.warning() {
+            tracing::warn!("{}", warning);
+        }
+        self.server_info = Some(Arc::new(server_info));
+
+        // Create watcher first (needed for admin tools)
+        let mut watcher = match &self.parser_preference {
+            Some(preference) => JustfileWatcher::new_with_parser_preference(
+                self.registry.clone(),
+                preference.clone(),
+            ),
+            None => JustfileWatcher::new(self.registry.clone()),
+        };
+
+        // Configure the watcher before putting it in an Arc
+        watcher.configure_names(&self.watch_configs).await;
+        watcher.set_parser_overrides(self.parser_overrides.clone());
+        watcher.set_profiles(self.executor.lock().await.profiles().clone());
+        watcher.set_multiple_dirs(self.watch_configs.len() > 1);
+
+        self.watcher = Some(Arc::new(watcher));
+
+        let access_log = Arc::new(AccessLog::open(self.access_log_config.clone())?);
+        self.access_log = Some(access_log.clone());
+
+        // Initialize admin tools (only if admin flag is enabled)
+        if self.admin_enabled {
+            tracing::info!("Admin tools enabled for framework server");
+            let (resource_manager, watchdog) = {
+                let executor = self.executor.lock().await;
+                (
+                    executor.resource_manager().clone(),
+                    executor.watchdog().clone(),
+                )
+            };
+            let admin_tools = Arc::new(
+                AdminTools::new(
+                    self.registry.clone(),
+                    self.watcher.as_ref().unwrap().clone(),
+                    self.watch_paths.clone(),
+                    self.watch_configs.clone(),
+                )
+                .with_lock_config(self.lock_config.clone())
+                .with_access_log(access_log)
+                .with_resource_manager(resource_manager)
+                .with_watchdog(watchdog)
+                .with_inline_justfiles(self.inline_justfiles),
+            );
+
+            // Register admin tools in the registry
+            admin_tools.register_admin_tools().await?;
+
+            self.admin_tools = Some(admin_tools.clone());
+            tracing::info!("Admin tools registered successfully");
+        } else {
+            tracing::info!("Admin tools disabled for framework server");
+            if self.inline_justfiles {
+                tracing::warn!(
+                    "Inline justfiles are only available with --admin; ignoring --allow-inline-justfiles"
+                );
+            }
+        }
+
+        let session = self.build_session(Some(framework_start)).await?;
+
+        // Store references
+        self.mcp_server = Some(session.mcp_server);
+        self.dynamic_tool_handler = Some(session.dynamic_tool_handler);
+        self.resource_provider = Some(session.resource_provider);
+        self.prompt_provider = Some(session.prompt_provider);
+
+        tracing::info!("Framework server initialized successfully");
+        Ok(())
+    }
+
+    /// Wrap a session's transport to add structured content to tool results
+    /// and the server info to the initialize result
+    #[cfg(feature = "ultrafast-framework")]
+    fn structured_content_transport(
+        &self,
+        transport: Box<dyn ultrafast_mcp_transport::Transport>,
+        handler: &dynamic_handler::DynamicToolHandler,
+    ) -> structured_content::StructuredContentTransport {
+        let mut transport = structured_content::StructuredContentTransport::new(transport);
+        if let Some(memory) = handler.parameter_memory() {
+            transport = transport.with_parameter_memory(memory.clone());
+        }
+        match self
+            .server_info
+            .as_ref()
+            .and_then(|info| serde_json::to_value(info.as_ref()).ok())
+        {
+            Some(server_info) => transport.with_server_info(server_info),
+            None => transport,
+        }
+    }
+
+    /// Build the MCP server and handlers for one transport
+    ///
+    /// Sessions share the registry, executor and admin tools; parameter
+    /// memory, run history and stored outputs belong to the session. Startup
+    /// phases are recorded only for the session built by `initialize`, which
+    /// passes the time framework setup started.
+    #[cfg(feature = "ultrafast-framework")]
+    async fn build_session(&self, framework_start: Option<std::time::Instant>) -> Result<Session> {
+        let record_phase = |name, start| {
+            if framework_start.is_some() {
+                self.record_startup_phase(name, start);
+            }
+        };
 
         // Create MCP server with our capabilities
         let capabilities = ServerCapabilities {
@@ -129,34 +603,6 @@ impl FrameworkServer {
             repository: Some("https://github.com/toolprint/just-mcp".to_string()),
         };
 
-        // Create watcher first (needed for admin tools)
-        let mut watcher = JustfileWatcher::new(self.registry.clone());
-
-        // Configure the watcher before putting it in an Arc
-        watcher.configure_names(&self.watch_configs).await;
-        watcher.set_multiple_dirs(self.watch_configs.len() > 1);
-
-        self.watcher = Some(Arc::new(watcher));
-
-        // Initialize admin tools (only if admin flag is enabled)
-        if self.admin_enabled {
-            tracing::info!("Admin tools enabled for framework server");
-            let admin_tools = Arc::new(AdminTools::new(
-                self.registry.clone(),
-                self.watcher.as_ref().unwrap().clone(),
-                self.watch_paths.clone(),
-                self.watch_configs.clone(),
-            ));
-
-            // Register admin tools in the registry
-            admin_tools.register_admin_tools().await?;
-
-            self.admin_tools = Some(admin_tools.clone());
-            tracing::info!("Admin tools registered successfully");
-        } else {
-            tracing::info!("Admin tools disabled for framework server");
-        }
-
         // Now create dynamic tool handler with admin tools
         let mut dynamic_handler =
             dynamic_handler::DynamicToolHandler::new(self.registry.clone(), self.executor.clone());
@@ -167,28 +613,82 @@ impl FrameworkServer {
             tracing::info!("Admin tools connected to dynamic handler");
         }
 
-        let dynamic_handler_arc = Arc::new(dynamic_handler);
+        dynamic_handler = dynamic_handler
+            .with_profile(self.default_profile.clone())
+            .with_schema_mode(self.schema_mode);
 
-        // Create framework tool handler for MCP integration
-        let _framework_tool_handler = dynamic_handler_arc.clone().create_framework_tool_handler();
+        if let Some(watcher) = &self.watcher {
+            dynamic_handler = dynamic_handler.with_watcher_supervision(watcher.supervision());
+        }
+
+        if self.remember_parameters {
+            dynamic_handler = dynamic_handler
+                .with_parameter_memory(Arc::new(parameter_memory::ParameterMemory::new()));
+        }
+
+        if self.diff_previous_run {
+            dynamic_handler =
+                dynamic_handler.with_run_history(Arc::new(run_history::RunHistory::default()));
+        }
+
+        if let Some(dedup) = &self.call_dedup {
+            dynamic_handler = dynamic_handler.with_call_dedup(dedup.clone());
+        }
+
+        let output_store = (self.output_link_threshold > 0)
+            .then(|| Arc::new(output_store::OutputStore::default()));
+        if let Some(store) = &output_store {
+            dynamic_handler =
+                dynamic_handler.with_output_links(store.clone(), self.output_link_threshold);
+        }
+
+        let dynamic_handler_arc = Arc::new(dynamic_handler);
+        if let Some(start) = framework_start {
+            record_phase("framework_init", start);
+        }
 
         // Initialize resource provider
+        let phase_start = std::time::Instant::now();
         let resource_provider = resources::create_framework_resource_provider(
             None, // args
             None, // security_config
-            None, // resource_limits
+            Some(&self.resource_limits),
             self.registry.clone(),
         )
         .await?;
+        let resource_provider = match output_store {
+            Some(store) => resource_provider.with_output_store(store),
+            None => resource_provider,
+        }
+        .with_recipe_summary(Arc::new(recipe_summary::RecipeSummary::new(
+            self.registry.clone(),
+            &self.watch_configs,
+        )));
+        let resource_provider = match &self.server_info {
+            Some(server_info) => resource_provider.with_server_info(server_info.clone()),
+            None => resource_provider,
+        };
+        let resource_provider = if self.resource_cache_ttl.is_zero() {
+            resource_provider
+        } else {
+            resource_provider.with_resource_cache(Arc::new(resource_cache::ResourceCache::new(
+                self.registry.clone(),
+                self.resource_cache_ttl,
+            )))
+        };
         let resource_provider_arc = Arc::new(resource_provider);
+        record_phase("resource_provider_init", phase_start);
 
         // Initialize prompt provider with search adapter
+        let phase_start = std::time::Instant::now();
         let prompt_provider = prompts::create_framework_prompt_provider(
             self.registry.clone(),
             None, // Will use mock search adapter for now
+            self.prompts_dir.as_deref(),
         )
         .await?;
         let prompt_provider_arc = Arc::new(prompt_provider);
+        record_phase("prompt_search_init", phase_start);
 
         // Create the UltraFastServer with our handlers
         let mcp_server = UltraFastServer::new(server_info, capabilities)
@@ -196,16 +696,12 @@ impl FrameworkServer {
             .with_resource_handler(resource_provider_arc.clone())
             .with_prompt_handler(prompt_provider_arc.clone());
 
-        // Store references
-        self.mcp_server = Some(mcp_server);
-        self.dynamic_tool_handler = Some(dynamic_handler_arc.clone());
-        self.resource_provider = Some(resource_provider_arc.clone());
-        self.prompt_provider = Some(prompt_provider_arc.clone());
-
-        // Handlers are now registered with the UltraFastServer during creation
-
-        tracing::info!("Framework server initialized successfully");
-        Ok(())
+        Ok(Session {
+            mcp_server,
+            dynamic_tool_handler: dynamic_handler_arc,
+            resource_provider: resource_provider_arc,
+            prompt_provider: prompt_provider_arc,
+        })
     }
 
     /// Initialize the framework server (feature disabled)
@@ -221,138 +717,231 @@ impl FrameworkServer {
 
     /// Run the framework server
     ///
-    /// Starts the main server loop with framework-based message handling.
+    /// Starts the main server loop with framework-based message handling,
+    /// serving every configured transport.
     pub async fn run(&mut self) -> Result<()> {
-        self.initialize().await?;
-
-        tracing::info!("Starting framework-based MCP server");
-
         #[cfg(feature = "ultrafast-framework")]
         {
-            if let Some(mut mcp_server) = self.mcp_server.take() {
-                // Start the watcher before starting the framework server
-                if let (Some(watcher), Some(dynamic_handler)) =
-                    (&self.watcher, &self.dynamic_tool_handler)
-                {
-                    self.start_watcher_with_dynamic_integration(
-                        watcher.clone(),
-                        dynamic_handler.clone(),
-                    )
-                    .await?;
-                }
-
-                tracing::info!("Starting ultrafast-mcp server directly");
+            transports::validate_transports(&self.transports)?;
+            let mut endpoints = Vec::new();
+            for transport in &self.transports {
+                endpoints.push(match transport {
+                    TransportSpec::Stdio => Endpoint::Transport(Box::new(
+                        batch_transport::BatchTransport::stdio()
+                            .with_max_batch_size(self.max_batch_size),
+                    )),
+                    #[cfg(feature = "http")]
+                    TransportSpec::Http { host, port } => {
+                        let allow_origin = self
+                            .http_allow_origin
+                            .clone()
+                            .or_else(|| transport.origin());
+                        if transport.requires_origin() {
+                            tracing::warn!(
+                                "Clients of {} must send an Origin header ({}); requests without one are only accepted on {}",
+                                transport,
+                                allow_origin.as_deref().unwrap_or_default(),
+                                transports::HEADERLESS_HTTP_HOSTS.join(", ")
+                            );
+                        }
+                        Endpoint::Http(ultrafast_mcp::HttpTransportConfig {
+                            host: host.clone(),
+                            port: *port,
+                            allow_origin,
+                            ..Default::default()
+                        })
+                    }
+                    #[cfg(not(feature = "http"))]
+                    TransportSpec::Http { .. } => {
+                        return Err(crate::error::Error::InvalidParameter(format!(
+                            "Transport '{transport}' requires a build with the `http` feature"
+                        )));
+                    }
+                });
+            }
+            self.serve(endpoints).await
+        }
 
-                // Add resource provider if available
-                if let Some(resource_provider) = &self.resource_provider {
-                    tracing::info!("Registering resource provider with framework server");
-                    mcp_server = mcp_server.with_resource_handler(resource_provider.clone());
-                }
+        #[cfg(not(feature = "ultrafast-framework"))]
+        {
+            self.initialize().await
+        }
+    }
 
-                // Add prompt provider if available (including /just:do-it)
-                if let Some(prompt_provider) = &self.prompt_provider {
-                    tracing::info!("Registering prompt provider with framework server");
-                    mcp_server = mcp_server.with_prompt_handler(prompt_provider.clone());
+    /// Run the framework server over the given transport
+    ///
+    /// Used with an in-memory transport to serve an in-process client, such
+    /// as the CLI's `list` and `run` commands.
+    #[cfg(feature = "ultrafast-framework")]
+    pub async fn run_with_transport(
+        &mut self,
+        transport: Box<dyn ultrafast_mcp_transport::Transport>,
+    ) -> Result<()> {
+        self.serve(vec![Endpoint::Transport(transport)]).await
+    }
 
-                    // Log available prompts
-                    let prompts = prompt_provider.list_prompts().await?;
-                    tracing::info!("Framework server has {} prompts available", prompts.len());
-                    for prompt in prompts.iter() {
-                        tracing::debug!("Available prompt: {}", prompt);
-                    }
+    /// Serve MCP on several endpoints at once
+    ///
+    /// Each transport endpoint, and each client of an HTTP endpoint, gets
+    /// its own session (see [`transports`]). Returns once every endpoint has
+    /// finished, so HTTP clients are still served after the stdio client
+    /// disconnects; the first endpoint to fail stops the rest.
+    #[cfg(feature = "ultrafast-framework")]
+    pub async fn serve(&mut self, endpoints: Vec<Endpoint>) -> Result<()> {
+        self.initialize().await?;
 
-                    // Verify /just:do-it is available
-                    if prompts.iter().any(|p| p.contains("do-it")) {
-                        tracing::info!(
-                            "✓ /just:do-it slash command is available through framework"
-                        );
-                    }
-                }
+        tracing::info!("Starting framework-based MCP server");
 
-                // Add our dynamic tool handler to the framework server
-                if let Some(dynamic_handler) = &self.dynamic_tool_handler {
-                    let tool_count = dynamic_handler.tool_count().await;
-                    tracing::info!(
-                        "Framework server starting with {} dynamic tools available",
-                        tool_count
+        let mut first_server = Some(self.mcp_server.take().ok_or_else(|| {
+            crate::error::Error::Other("Framework server not initialized".into())
+        })?);
+        let this = &*self;
+        let access_log = this.access_log.clone().unwrap_or_default();
+        let handlers: SessionHandlers = Arc::new(std::sync::Mutex::new(
+            this.dynamic_tool_handler.iter().cloned().collect(),
+        ));
+
+        // The framework handles the MCP protocol; our transport adds
+        // JSON-RPC batches. HTTP endpoints start a session per client.
+        let mut runs: Vec<futures::future::BoxFuture<'_, Result<()>>> = Vec::new();
+        for endpoint in endpoints {
+            match endpoint {
+                Endpoint::Transport(transport) => {
+                    let (mcp_server, handler) = match first_server.take() {
+                        Some(server) => (
+                            server,
+                            this.dynamic_tool_handler.clone().ok_or_else(|| {
+                                crate::error::Error::Other(
+                                    "Framework server not initialized".into(),
+                                )
+                            })?,
+                        ),
+                        None => {
+                            let session = this.build_session(None).await?;
+                            handlers
+                                .lock()
+                                .unwrap()
+                                .push(session.dynamic_tool_handler.clone());
+                            (session.mcp_server, session.dynamic_tool_handler)
+                        }
+                    };
+                    let transport = access_log_transport::AccessLogTransport::new(
+                        Box::new(this.structured_content_transport(transport, &handler)),
+                        access_log.clone(),
+                        uuid::Uuid::new_v4().to_string(),
+                        "stdio",
                     );
-
-                    // Register the dynamic handler directly as the tool handler
-                    mcp_server = mcp_server.with_tool_handler(dynamic_handler.clone());
-
-                    // Log tool details for debugging
-                    let tools = dynamic_handler.get_tool_definitions().await;
-                    for tool in tools.iter().take(5) {
-                        // Log first 5 tools
-                        tracing::debug!("Available tool: {} - {}", tool.name, tool.description);
-                    }
-                    if tools.len() > 5 {
-                        tracing::debug!("... and {} more tools", tools.len() - 5);
-                    }
+                    runs.push(Box::pin(async move {
+                        tracing::info!("Starting framework server");
+                        mcp_server
+                            .run_with_transport(Box::new(transport))
+                            .await
+                            .map_err(|e| {
+                                Self::framework_error(format!("Framework server error: {e}"))
+                            })
+                    }));
+                }
+                #[cfg(feature = "http")]
+                Endpoint::Http(config) => {
+                    runs.push(Box::pin(this.serve_http(
+                        config,
+                        handlers.clone(),
+                        access_log.clone(),
+                    )));
                 }
+            }
+        }
 
-                // Start the framework server with stdio transport
-                // This handles the MCP protocol automatically
-                tracing::info!("Starting framework server with stdio transport");
+        // Start the watcher before starting the framework server
+        if let Some(watcher) = &this.watcher {
+            this.start_watcher_with_dynamic_integration(watcher.clone(), handlers.clone())
+                .await?;
+        }
 
-                match mcp_server.run_stdio().await {
-                    Ok(()) => {
-                        tracing::info!("Framework server completed successfully");
-                    }
-                    Err(e) => {
-                        // Create a framework error and analyze it
-                        let framework_error =
-                            crate::error::Error::Other(format!("Framework server error: {e}"));
-                        let error_info = ErrorAdapter::extract_error_info(&framework_error);
-                        let error_category = ErrorAdapter::categorize_error(&framework_error);
-
-                        tracing::error!(
-                            "Framework server failed: {} (category: {:?}, retryable: {})",
-                            error_info.user_message,
-                            error_category,
-                            error_info.is_retryable
-                        );
-                        tracing::debug!(
-                            "Framework server technical error: {}",
-                            error_info.technical_details
-                        );
-
-                        // Provide actionable error information
-                        match error_category {
-                            ErrorCategory::SystemError => {
-                                tracing::error!("System-level error - check system resources, permissions, or environment");
-                            }
-                            ErrorCategory::ExternalError => {
-                                tracing::error!("External dependency error - check network connectivity or external tools");
-                            }
-                            ErrorCategory::UserError => {
-                                tracing::error!("Configuration error - check server settings and command-line arguments");
-                            }
-                            ErrorCategory::InternalError => {
-                                tracing::error!("Internal framework error - this may be a bug, please report with logs");
-                            }
-                        }
+        if let Some(prompt_provider) = &this.prompt_provider {
+            // Log available prompts
+            let prompts = prompt_provider.list_prompts().await?;
+            tracing::info!("Framework server has {} prompts available", prompts.len());
+            for prompt in prompts.iter() {
+                tracing::debug!("Available prompt: {}", prompt);
+            }
 
-                        return Err(framework_error);
-                    }
-                }
-            } else {
-                return Err(crate::error::Error::Other(
-                    "Framework server not initialized".into(),
-                ));
+            // Verify /just:do-it is available
+            if prompts.iter().any(|p| p.contains("do-it")) {
+                tracing::info!("✓ /just:do-it slash command is available through framework");
             }
         }
 
-        #[cfg(not(feature = "ultrafast-framework"))]
-        {
-            return Err(crate::error::Error::Other(
-                "ultrafast-framework feature not enabled".into(),
-            ));
+        if let Some(dynamic_handler) = &this.dynamic_tool_handler {
+            let tool_count = dynamic_handler.tool_count().await;
+            tracing::info!(
+                "Framework server starting with {} dynamic tools available",
+                tool_count
+            );
+
+            // Log tool details for debugging
+            let tools = dynamic_handler.get_tool_definitions().await;
+            for tool in tools.iter().take(5) {
+                // Log first 5 tools
+                tracing::debug!("Available tool: {} - {}", tool.name, tool.description);
+            }
+            if tools.len() > 5 {
+                tracing::debug!("... and {} more tools", tools.len() - 5);
+            }
         }
 
+        futures::future::try_join_all(runs).await?;
+
+        tracing::info!("Framework server completed successfully");
         Ok(())
     }
 
+    /// Log a framework failure with actionable hints and turn it into an error
+    #[cfg(feature = "ultrafast-framework")]
+    fn framework_error(message: String) -> crate::error::Error {
+        // Create a framework error and analyze it
+        let framework_error = crate::error::Error::Other(message);
+        let error_info = ErrorAdapter::extract_error_info(&framework_error);
+        let error_category = ErrorAdapter::categorize_error(&framework_error);
+
+        tracing::error!(
+            "Framework server failed: {} (category: {:?}, retryable: {})",
+            error_info.user_message,
+            error_category,
+            error_info.is_retryable
+        );
+        tracing::debug!(
+            "Framework server technical error: {}",
+            error_info.technical_details
+        );
+
+        // Provide actionable error information
+        match error_category {
+            ErrorCategory::SystemError => {
+                tracing::error!(
+                    "System-level error - check system resources, permissions, or environment"
+                );
+            }
+            ErrorCategory::ExternalError => {
+                tracing::error!(
+                    "External dependency error - check network connectivity or external tools"
+                );
+            }
+            ErrorCategory::UserError => {
+                tracing::error!(
+                    "Configuration error - check server settings and command-line arguments"
+                );
+            }
+            ErrorCategory::InternalError => {
+                tracing::error!(
+                    "Internal framework error - this may be a bug, please report with logs"
+                );
+            }
+        }
+
+        framework_error
+    }
+
     /// Get access to the tool registry
     pub fn registry(&self) -> &Arc<tokio::sync::Mutex<ToolRegistry>> {
         &self.registry
@@ -363,6 +952,11 @@ impl FrameworkServer {
         &self.executor
     }
 
+    /// Get access to the access log, once initialized
+    pub fn access_log(&self) -> Option<&Arc<AccessLog>> {
+        self.access_log.as_ref()
+    }
+
     /// Get access to the dynamic tool handler
     #[cfg(feature = "ultrafast-framework")]
     pub fn dynamic_tool_handler(&self) -> Option<&Arc<dynamic_handler::DynamicToolHandler>> {
@@ -406,67 +1000,125 @@ impl FrameworkServer {
         Ok(())
     }
 
+    fn record_startup_phase(&self, name: &'static str, start: std::time::Instant) {
+        self.startup_profile
+            .lock()
+            .unwrap()
+            .record_phase(name, start);
+    }
+
+    /// Close the startup profile, emit its metrics and print it if requested
+    fn finish_startup_profile(&self) {
+        let mut profile = self.startup_profile.lock().unwrap();
+        profile.finish();
+        profile.emit_metrics();
+        if self.print_startup_profile {
+            // stdout carries the MCP protocol
+            eprint!("{}", profile.report());
+        }
+    }
+
     /// Start the watcher with dynamic tool handler integration
     ///
     /// This method sets up the file watcher to monitor justfiles and automatically
-    /// sync changes to the dynamic tool handler, which then notifies the framework.
+    /// sync changes to the dynamic tool handler of every session, which then
+    /// notifies the framework.
     async fn start_watcher_with_dynamic_integration(
         &self,
         watcher: Arc<JustfileWatcher>,
-        dynamic_handler: Arc<dynamic_handler::DynamicToolHandler>,
+        dynamic_handlers: SessionHandlers,
     ) -> Result<()> {
         tracing::info!("Starting watcher with dynamic tool handler integration");
 
         // Do an initial scan of justfiles and sync to dynamic handler
-        for path in &self.watch_paths {
-            if path.exists() && path.is_dir() {
-                // Scan for existing justfiles in directory
-                let justfile_path = path.join("justfile");
-                if justfile_path.exists() {
-                    tracing::info!("Found justfile: {}", justfile_path.display());
-                    if let Err(e) = watcher.parse_and_update_justfile(&justfile_path).await {
-                        tracing::warn!("Error parsing justfile: {}", e);
-                    }
+        let phase_start = std::time::Instant::now();
+        let justfiles: Vec<PathBuf> = self
+            .watch_paths
+            .iter()
+            .filter(|path| path.is_dir())
+            // Scan for existing justfiles in directory, in any casing
+            .flat_map(|path| crate::parser::discovery::find_justfiles(path))
+            .collect();
+        self.record_startup_phase("discovery", phase_start);
+
+        let phase_start = std::time::Instant::now();
+        for justfile_path in &justfiles {
+            tracing::info!("Found justfile: {}", justfile_path.display());
+            let file_start = std::time::Instant::now();
+            let parsed = watcher.parse_and_update_justfile(justfile_path).await;
+            if let Err(e) = &parsed {
+                tracing::warn!("Error parsing justfile: {}", e);
+            }
+            self.startup_profile.lock().unwrap().record_file(
+                justfile_path,
+                file_start,
+                parsed.is_ok(),
+            );
+        }
+        self.record_startup_phase("parsing", phase_start);
+
+        // Sync initial tools to dynamic handlers
+        let phase_start = std::time::Instant::now();
+        let mut synced = Ok(());
+        let initial_handlers = dynamic_handlers.lock().unwrap().clone();
+        for dynamic_handler in &initial_handlers {
+            synced = synced.and(dynamic_handler.sync_tools_from_registry().await);
+        }
+        self.record_startup_phase("registry_build", phase_start);
+        self.finish_startup_profile();
+
+        #[cfg(feature = "telemetry")]
+        if let Some(config) = self.telemetry.clone() {
+            let tool_count = match initial_handlers.first() {
+                Some(dynamic_handler) => dynamic_handler.tool_count().await,
+                None => 0,
+            };
+            let report = crate::telemetry::TelemetryReport::new(
+                tool_count,
+                justfiles.len(),
+                &watcher.parsing_metrics(),
+            );
+            tokio::spawn(async move {
+                if let Err(e) = crate::telemetry::send(&config, &report).await {
+                    tracing::debug!("{}", e);
                 }
+            });
+        }
 
-                // Also check for capitalized Justfile
-                let justfile_cap = path.join("Justfile");
-                if justfile_cap.exists() {
-                    tracing::info!("Found Justfile: {}", justfile_cap.display());
-                    if let Err(e) = watcher.parse_and_update_justfile(&justfile_cap).await {
-                        tracing::warn!("Error parsing Justfile: {}", e);
-                    }
+        #[cfg(feature = "self-update")]
+        if self.update_check {
+            let justfiles = justfiles.clone();
+            tokio::spawn(async move {
+                if let Some(notice) = crate::update::startup_notice(&justfiles).await {
+                    tracing::warn!("{}", notice);
                 }
-            }
+            });
         }
 
-        // Sync initial tools to dynamic handler
-        if let Err(e) = dynamic_handler.sync_tools_from_registry().await {
+        if let Err(e) = synced {
             tracing::warn!("Failed to sync initial tools to dynamic handler: {}", e);
         } else {
             tracing::info!("Initial tools synced to dynamic handler");
 
             // Log the tools that are now available for execution
-            let tool_count = dynamic_handler.tool_count().await;
-            tracing::info!(
-                "Dynamic handler now has {} tools available for framework execution",
-                tool_count
-            );
+            if let Some(dynamic_handler) = initial_handlers.first() {
+                let tool_count = dynamic_handler.tool_count().await;
+                tracing::info!(
+                    "Dynamic handler now has {} tools available for framework execution",
+                    tool_count
+                );
+            }
         }
 
         // Start the watcher in the background with dynamic handler integration
         let watch_paths = self.watch_paths.clone();
         let watcher_for_task = watcher.clone();
-        let dynamic_handler_for_task = dynamic_handler.clone();
 
         tokio::spawn(async move {
             // Create a custom watcher loop that integrates with dynamic handler
-            if let Err(e) = Self::run_watcher_with_dynamic_sync(
-                watcher_for_task,
-                dynamic_handler_for_task,
-                watch_paths,
-            )
-            .await
+            if let Err(e) =
+                Self::run_watcher_with_dynamic_sync(watcher_for_task, dynamic_handlers, watch_paths)
+                    .await
             {
                 tracing::error!("Watcher with dynamic sync error: {}", e);
             }
@@ -483,7 +1135,7 @@ impl FrameworkServer {
     /// adding integration with the dynamic tool handler.
     async fn run_watcher_with_dynamic_sync(
         watcher: Arc<JustfileWatcher>,
-        dynamic_handler: Arc<dynamic_handler::DynamicToolHandler>,
+        dynamic_handlers: SessionHandlers,
         watch_paths: Vec<PathBuf>,
     ) -> Result<()> {
         tracing::info!("Starting simplified watcher loop with dynamic handler sync");
@@ -491,11 +1143,10 @@ impl FrameworkServer {
         // Use a simpler approach: start the watcher and periodically sync
         let watcher_for_watching = watcher.clone();
         let watch_paths_clone = watch_paths.clone();
-        let watcher_task = tokio::spawn(async move {
-            if let Err(e) = watcher_for_watching.watch_paths(watch_paths_clone).await {
-                tracing::error!("File watcher error: {}", e);
-            }
-        });
+        // Restarted with backoff if the notify backend fails or the loop panics
+        let watcher_task = tokio::spawn(
+            watcher_for_watching.watch_supervised(watch_paths_clone, RestartPolicy::default()),
+        );
 
         // Periodically sync tools from registry to dynamic handler
         let mut sync_interval = tokio::time::interval(tokio::time::Duration::from_secs(2));
@@ -503,9 +1154,12 @@ impl FrameworkServer {
         loop {
             tokio::select! {
                 _ = sync_interval.tick() => {
-                    // Periodically sync tools from registry to dynamic handler
-                    if let Err(e) = dynamic_handler.sync_tools_from_registry().await {
-                        tracing::debug!("Failed to sync tools to dynamic handler: {}", e);
+                    // Periodically sync tools from registry to dynamic handlers
+                    let handlers = dynamic_handlers.lock().unwrap().clone();
+                    for dynamic_handler in &handlers {
+                        if let Err(e) = dynamic_handler.sync_tools_from_registry().await {
+                            tracing::debug!("Failed to sync tools to dynamic handler: {}", e);
+                        }
                     }
                 }
                 _ = tokio::signal::ctrl_c() => {
@@ -651,6 +1305,7 @@ mod tests {
                 source_hash: "test_hash".to_string(),
                 last_modified: SystemTime::now(),
                 internal_name: Some("test_build_/Users/test/justfile".to_string()),
+                metadata: Default::default(),
             };
             registry.add_tool(test_tool).unwrap();
         }
@@ -721,7 +1376,8 @@ test:
 
         let execution_result = result.unwrap();
         assert!(execution_result.success);
-        assert!(execution_result.stdout.contains("Sync completed"));
+        let sync: serde_json::Value = serde_json::from_str(&execution_result.stdout).unwrap();
+        assert!(sync["found_recipes"].is_u64());
 
         // Test parser doctor with verbose = false
         let parser_doctor_params = serde_json::json!({"verbose": false});
@@ -755,7 +1411,8 @@ test:
 
         let execution_result = result.unwrap();
         assert!(execution_result.success);
-        assert!(execution_result.stdout.contains("Watch directory set to"));
+        let watch: serde_json::Value = serde_json::from_str(&execution_result.stdout).unwrap();
+        assert_eq!(watch["justfile_detected"], true);
 
         // Test admin tool validation for unknown tool
         let result = dynamic_handler
@@ -768,6 +1425,49 @@ test:
             .contains("Unknown admin tool"));
     }
 
+    #[tokio::test]
+    #[cfg(feature = "ultrafast-framework")]
+    async fn test_startup_profile_records_phases_and_files() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        std::fs::write(&justfile_path, "build:\n    echo build\n").unwrap();
+
+        let mut server = FrameworkServer::new()
+            .with_watch_paths(vec![temp_dir.path().to_path_buf()])
+            .with_watch_names(vec![(temp_dir.path().to_path_buf(), None)]);
+        server.initialize().await.unwrap();
+        server
+            .start_watcher_with_dynamic_integration(
+                server.watcher.clone().unwrap(),
+                Arc::new(std::sync::Mutex::new(vec![server
+                    .dynamic_tool_handler()
+                    .unwrap()
+                    .clone()])),
+            )
+            .await
+            .unwrap();
+
+        let profile = server.startup_profile();
+        let phases: Vec<_> = profile.phases().iter().map(|p| p.name).collect();
+        assert_eq!(
+            phases,
+            vec![
+                "server_setup",
+                "framework_init",
+                "resource_provider_init",
+                "prompt_search_init",
+                "discovery",
+                "parsing",
+                "registry_build"
+            ]
+        );
+        assert_eq!(profile.files().len(), 1);
+        assert_eq!(profile.files()[0].path, justfile_path);
+        assert!(profile.files()[0].success);
+    }
+
     #[tokio::test]
     #[cfg(feature = "ultrafast-framework")]
     async fn test_watcher_integration_with_dynamic_handler() {
@@ -823,7 +1523,7 @@ test:
         let tools = dynamic_handler.get_tool_definitions().await;
         assert_eq!(tools.len(), 1);
         assert!(tools[0].name == "test@test" || tools[0].name == "test");
-        assert!(tools[0].description.contains("test"));
+        assert!(tools[0].description.to_lowercase().contains("test"));
 
         // Test dynamic update - modify the justfile
         let updated_content = r#"
@@ -863,4 +1563,222 @@ build:
         assert!(tool_names.contains(&"test@test") || tool_names.contains(&"test"));
         assert!(tool_names.contains(&"build@test") || tool_names.contains(&"build"));
     }
+
+    #[tokio::test]
+    #[cfg(feature = "ultrafast-framework")]
+    async fn test_serve_isolates_sessions_per_endpoint() {
+        use crate::client::JustMcpClient;
+        use batch_transport::BatchTransport;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("justfile"),
+            "greet name:\n    echo hello {{name}}\n",
+        )
+        .unwrap();
+
+        let mut server = FrameworkServer::new()
+            .with_watch_paths(vec![temp_dir.path().to_path_buf()])
+            .with_watch_names(vec![(temp_dir.path().to_path_buf(), None)])
+            .with_security_config(SecurityConfig {
+                allowed_paths: vec![temp_dir.path().to_path_buf()],
+                ..Default::default()
+            });
+
+        let mut endpoints = Vec::new();
+        let mut clients = Vec::new();
+        for _ in 0..2 {
+            let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+            let (reader, writer) = tokio::io::split(server_io);
+            endpoints.push(Endpoint::Transport(Box::new(BatchTransport::new(
+                reader, writer,
+            ))));
+            clients.push(client_io);
+        }
+        tokio::spawn(async move { server.serve(endpoints).await });
+
+        let mut connected = Vec::new();
+        for client_io in clients {
+            let (reader, writer) = tokio::io::split(client_io);
+            connected.push(
+                JustMcpClient::connect(Box::new(BatchTransport::new(reader, writer)))
+                    .await
+                    .unwrap(),
+            );
+        }
+        let [mut first, mut second] = <[JustMcpClient; 2]>::try_from(connected).ok().unwrap();
+
+        // Both sessions see the shared registry
+        let greet = |tools: Vec<ultrafast_mcp::Tool>| {
+            tools
+                .into_iter()
+                .find(|t| t.name.starts_with("greet"))
+                .expect("greet tool")
+        };
+        let tool = greet(first.list_tools().await.unwrap());
+        assert_eq!(greet(second.list_tools().await.unwrap()).name, tool.name);
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("name".to_string(), "alice".into());
+        let output = first.run(&tool.name, arguments).await.unwrap();
+        assert!(output.text.contains("hello alice"), "{}", output.text);
+
+        // Remembered parameters stay with the session that used them
+        let default =
+            |tool: &ultrafast_mcp::Tool| tool.input_schema["properties"]["name"]["default"].clone();
+        assert_eq!(default(&greet(first.list_tools().await.unwrap())), "alice");
+        assert!(default(&greet(second.list_tools().await.unwrap())).is_null());
+    }
+
+    /// Check `value` against the parts of JSON Schema the admin output
+    /// schemas use, failing on fields the schema doesn't describe
+    fn assert_conforms(value: &serde_json::Value, schema: &serde_json::Value, path: &str) {
+        use serde_json::Value;
+        let kind = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(n) if n.is_u64() => "integer",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        let allowed = match &schema["type"] {
+            Value::Array(kinds) => kinds.iter().any(|k| k == kind),
+            expected => expected == kind || (expected == "number" && kind == "integer"),
+        };
+        assert!(allowed, "{path} is {kind}, expected {}", schema["type"]);
+
+        match value {
+            Value::Object(map) => {
+                for field in schema["required"].as_array().into_iter().flatten() {
+                    let field = field.as_str().unwrap();
+                    assert!(map.contains_key(field), "{path}.{field} is missing");
+                }
+                for (key, field) in map {
+                    let field_schema = match schema.get("properties") {
+                        Some(properties) => properties
+                            .get(key)
+                            .unwrap_or_else(|| panic!("{path}.{key} is not in the schema")),
+                        None => &schema["additionalProperties"],
+                    };
+                    assert_conforms(field, field_schema, &format!("{path}.{key}"));
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    assert_conforms(item, &schema["items"], &format!("{path}[{i}]"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[tokio::test]
+    async fn test_admin_results_are_structured() {
+        use batch_transport::BatchTransport;
+        use serde_json::{json, Value};
+        use tempfile::TempDir;
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("justfile"), "build:\n    echo build\n").unwrap();
+        let mut server = FrameworkServer::new()
+            .with_watch_paths(vec![temp_dir.path().to_path_buf()])
+            .with_watch_names(vec![(temp_dir.path().to_path_buf(), None)])
+            .with_admin_enabled(true)
+            .with_security_config(SecurityConfig {
+                allowed_paths: vec![temp_dir.path().to_path_buf()],
+                ..Default::default()
+            });
+
+        let (client_io, server_io) = tokio::io::duplex(256 * 1024);
+        let (reader, writer) = tokio::io::split(server_io);
+        let endpoint = Endpoint::Transport(Box::new(BatchTransport::new(reader, writer)));
+        tokio::spawn(async move { server.serve(vec![endpoint]).await });
+
+        let (reader, mut writer) = tokio::io::split(client_io);
+        let mut lines = BufReader::new(reader).lines();
+        let request = |id: u64, method: &str, params: Value| {
+            let mut line =
+                json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}).to_string();
+            line.push('\n');
+            line
+        };
+        let initialize = request(
+            0,
+            "initialize",
+            json!({"protocolVersion": "2025-06-18", "capabilities": {},
+                   "clientInfo": {"name": "test", "version": "1"}}),
+        );
+        writer.write_all(initialize.as_bytes()).await.unwrap();
+        let initialized: Value =
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(
+            initialized["result"]["_meta"][server_info::SERVER_INFO_META_KEY]["version"],
+            crate::VERSION
+        );
+        writer
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"method\":\"notifications/initialized\"}\n")
+            .await
+            .unwrap();
+
+        let list = request(1, "tools/list", json!({}));
+        writer.write_all(list.as_bytes()).await.unwrap();
+        let tools: Value =
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        let schema_of = |name: &str| {
+            tools["result"]["tools"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|tool| tool["name"] == name)
+                .map(|tool| tool.get("outputSchema").cloned())
+                .unwrap_or_else(|| panic!("{name} is not listed"))
+        };
+        assert!(schema_of("_admin_parser_doctor").is_none());
+        assert!(schema_of("build").is_none());
+
+        let calls = [
+            ("_admin_status", json!({})),
+            ("_admin_sync", json!({})),
+            ("_admin_gc", json!({"dry_run": true})),
+            ("_admin_set_limits", json!({"timeout_seconds": 60})),
+            ("_admin_access_log", json!({})),
+            ("_admin_running_executions", json!({})),
+            ("_admin_use_profile", json!({})),
+            ("_admin_coverage", json!({})),
+            ("_admin_schema_mode", json!({"mode": "compact"})),
+            (
+                "_admin_create_recipe",
+                json!({"recipe_name": "lint", "recipe": "echo lint"}),
+            ),
+            (
+                "_admin_apply_changes",
+                json!({"changes": [{"action": "remove", "recipe_name": "lint"}]}),
+            ),
+        ];
+        for (id, (name, arguments)) in calls.into_iter().enumerate() {
+            let call = request(
+                id as u64 + 2,
+                "tools/call",
+                json!({"name": name, "arguments": arguments}),
+            );
+            writer.write_all(call.as_bytes()).await.unwrap();
+            let response: Value =
+                serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+            let result = &response["result"];
+            assert_eq!(result["isError"], false, "{name}: {response}");
+
+            let schema = schema_of(name).unwrap_or_else(|| panic!("{name} has no output schema"));
+            let structured = &result["structuredContent"];
+            assert!(structured.is_object(), "{name}: {response}");
+            assert_conforms(structured, &schema, name);
+            // The same result stays readable as text
+            let text: Value =
+                serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+            assert_eq!(&text, structured);
+        }
+    }
 }
@@ -0,0 +1,167 @@
+//! Server-hosted output of tool executions
+//!
+//! Recipes such as full builds can print megabytes of logs. Inlining all of
+//! that into a tool result floods the client's context, so outputs above a
+//! threshold are kept here and served as `just://output/{id}` resources; the
+//! tool result carries a short preview and a link to the full output.
+//!
+//! Outputs are held in memory and the oldest are evicted once the store
+//! exceeds its size budget.
+
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// URI prefix of stored outputs
+pub const OUTPUT_URI_PREFIX: &str = "just://output/";
+
+/// Default output size above which results link to the stored output
+pub const DEFAULT_LINK_THRESHOLD: usize = 256 * 1024;
+
+/// Default total size of outputs kept before the oldest are evicted
+pub const DEFAULT_STORE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Bytes from the end of the output included in the inline preview
+const PREVIEW_BYTES: usize = 4 * 1024;
+
+/// A stored tool output
+#[derive(Debug, Clone)]
+pub struct StoredOutput {
+    pub uri: String,
+    /// Tool that produced the output
+    pub tool: String,
+    pub created_at: DateTime<Utc>,
+    pub text: String,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    outputs: VecDeque<StoredOutput>,
+    total_bytes: usize,
+}
+
+/// Bounded in-memory store of large tool outputs
+#[derive(Debug)]
+pub struct OutputStore {
+    entries: Mutex<Entries>,
+    next_id: AtomicU64,
+    max_bytes: usize,
+}
+
+impl OutputStore {
+    /// Create a store keeping up to `max_bytes` of output
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            entries: Mutex::new(Entries::default()),
+            next_id: AtomicU64::new(1),
+            max_bytes,
+        }
+    }
+
+    /// Store an output and return its resource URI
+    ///
+    /// The oldest outputs are evicted to make room; the new output is always
+    /// kept, even if it alone exceeds the budget.
+    pub fn store(&self, tool: &str, text: String) -> String {
+        let uri = format!(
+            "{OUTPUT_URI_PREFIX}{}",
+            self.next_id.fetch_add(1, Ordering::Relaxed)
+        );
+
+        let mut entries = self.entries.lock().unwrap();
+        while entries.total_bytes + text.len() > self.max_bytes {
+            let Some(evicted) = entries.outputs.pop_front() else {
+                break;
+            };
+            entries.total_bytes -= evicted.text.len();
+            tracing::debug!("Evicted stored output {}", evicted.uri);
+        }
+        entries.total_bytes += text.len();
+        entries.outputs.push_back(StoredOutput {
+            uri: uri.clone(),
+            tool: tool.to_string(),
+            created_at: Utc::now(),
+            text,
+        });
+        uri
+    }
+
+    /// Stored output for a `just://output/{id}` URI, if not yet evicted
+    pub fn get(&self, uri: &str) -> Option<StoredOutput> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .outputs
+            .iter()
+            .find(|output| output.uri == uri)
+            .cloned()
+    }
+
+    /// URI, tool and size of every stored output, oldest first
+    pub fn list(&self) -> Vec<(String, String, usize)> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .outputs
+            .iter()
+            .map(|output| (output.uri.clone(), output.tool.clone(), output.text.len()))
+            .collect()
+    }
+}
+
+impl Default for OutputStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_STORE_BYTES)
+    }
+}
+
+/// End of an output, where failures are usually reported
+pub fn tail(text: &str) -> &str {
+    let mut start = text.len().saturating_sub(PREVIEW_BYTES);
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    &text[start..]
+}
+
+/// Inline text shown in place of a stored output of `size` bytes
+pub fn preview(size: usize, tail: &str, uri: &str) -> String {
+    format!(
+        "Output is {size} bytes; the full output is available as resource {uri}. Last {} bytes:\n...{tail}",
+        tail.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_evicts_oldest_outputs() {
+        let store = OutputStore::new(10);
+        let first = store.store("build", "aaaaaa".to_string());
+        let second = store.store("test", "bbbbbb".to_string());
+
+        assert_ne!(first, second);
+        assert!(second.starts_with(OUTPUT_URI_PREFIX));
+        assert!(store.get(&first).is_none());
+        let stored = store.get(&second).unwrap();
+        assert_eq!(stored.tool, "test");
+        assert_eq!(stored.text, "bbbbbb");
+
+        // Outputs larger than the budget are still kept
+        let large = store.store("build", "c".repeat(20));
+        assert_eq!(store.list(), vec![(large, "build".to_string(), 20)]);
+    }
+
+    #[test]
+    fn test_preview_keeps_tail() {
+        let text = format!("{}é{}", "x".repeat(PREVIEW_BYTES), "done");
+        let tail = tail(&text);
+        assert!(tail.ends_with("édone"));
+        assert!(tail.len() <= PREVIEW_BYTES);
+
+        let preview = preview(text.len(), tail, "just://output/1");
+        assert!(preview.starts_with("Output is 4102 bytes"));
+        assert!(preview.contains("just://output/1"));
+    }
+}
@@ -117,6 +117,44 @@ impl PromptHandler for FrameworkPromptProvider {
             .await
         {
             Some(def) => {
+                // Prompts with their own template, such as user prompts,
+                // render it from the request's arguments
+                let arguments = match request.arguments {
+                    Some(serde_json::Value::Object(map)) => map.into_iter().collect(),
+                    _ => std::collections::HashMap::new(),
+                };
+                let prompt_request = crate::prompts::PromptRequest {
+                    name: request.name.clone(),
+                    arguments,
+                };
+                match self
+                    .prompt_registry
+                    .get_prompt_messages(&prompt_request)
+                    .await
+                {
+                    Ok(Some(messages)) => {
+                        return Ok(GetPromptResponse {
+                            description: Some(def.description),
+                            messages: messages
+                                .into_iter()
+                                .map(|message| FrameworkPromptMessage {
+                                    // MCP prompts only have user and assistant messages
+                                    role: if message.role == "assistant" {
+                                        PromptRole::Assistant
+                                    } else {
+                                        PromptRole::User
+                                    },
+                                    content: PromptContent::Text {
+                                        text: message.content.text,
+                                    },
+                                })
+                                .collect(),
+                        });
+                    }
+                    Ok(None) => {}
+                    Err(e) => return Err(e.to_mcp_error()),
+                }
+
                 // Convert prompt arguments to framework format
                 let _arguments: Vec<FrameworkPromptArgument> = def
                     .arguments
@@ -174,8 +212,9 @@ impl PromptHandler for FrameworkPromptProvider {
 /// This function sets up the complete prompt provider with all existing
 /// functionality preserved, including the /just:do-it slash command.
 pub async fn create_framework_prompt_provider(
-    _tool_registry: Arc<tokio::sync::Mutex<crate::registry::ToolRegistry>>,
+    tool_registry: Arc<tokio::sync::Mutex<crate::registry::ToolRegistry>>,
     search_adapter: Option<Arc<crate::prompts::search_adapter::SearchAdapter>>,
+    user_prompts_dir: Option<&std::path::Path>,
 ) -> Result<FrameworkPromptProvider> {
     // Create prompt registry with default config
     let prompt_config = crate::prompts::traits::PromptConfig::default();
@@ -196,11 +235,19 @@ pub async fn create_framework_prompt_provider(
         crate::prompts::registry::PromptRegistryBuilder::new()
             .with_config(prompt_config)
             .with_search_adapter(adapter)
+            .with_tool_registry(tool_registry.clone())
             .with_defaults(true) // KEY: Automatically register do-it prompt
             .build()
             .await?,
     );
 
+    // Team-defined prompts from the user's prompts directory
+    if let Some(dir) = user_prompts_dir {
+        prompt_registry
+            .register_user_prompts(dir, Some(tool_registry))
+            .await;
+    }
+
     let prompt_count = prompt_registry.list_prompts().await.len();
     tracing::info!(
         "Framework prompt provider created with {} prompts",
@@ -245,7 +292,7 @@ mod tests {
     async fn test_framework_prompt_provider_creation() {
         let tool_registry = Arc::new(tokio::sync::Mutex::new(ToolRegistry::new()));
 
-        let provider = create_framework_prompt_provider(tool_registry, None).await;
+        let provider = create_framework_prompt_provider(tool_registry, None, None).await;
         assert!(provider.is_ok());
     }
 
@@ -253,7 +300,7 @@ mod tests {
     async fn test_prompt_listing() {
         let tool_registry = Arc::new(tokio::sync::Mutex::new(ToolRegistry::new()));
 
-        let provider = create_framework_prompt_provider(tool_registry, None)
+        let provider = create_framework_prompt_provider(tool_registry, None, None)
             .await
             .unwrap();
 
@@ -278,7 +325,7 @@ mod tests {
             ),
         );
 
-        let provider = create_framework_prompt_provider(tool_registry, Some(search_adapter))
+        let provider = create_framework_prompt_provider(tool_registry, Some(search_adapter), None)
             .await
             .unwrap();
 
@@ -291,7 +338,7 @@ mod tests {
     async fn test_prompt_retrieval() {
         let tool_registry = Arc::new(tokio::sync::Mutex::new(ToolRegistry::new()));
 
-        let provider = create_framework_prompt_provider(tool_registry, None)
+        let provider = create_framework_prompt_provider(tool_registry, None, None)
             .await
             .unwrap();
 
@@ -301,4 +348,46 @@ mod tests {
             assert!(content.is_some());
         }
     }
+
+    #[cfg(feature = "ultrafast-framework")]
+    #[tokio::test]
+    async fn test_user_prompt_is_rendered_by_get_prompt() {
+        let tool_registry = Arc::new(tokio::sync::Mutex::new(ToolRegistry::new()));
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("review.md"),
+            "---\ndescription: Review a branch\narguments:\n  - name: branch\n    required: true\n---\nReview {{branch}} with {{recipe:lint}}.\n",
+        )
+        .unwrap();
+
+        let provider = create_framework_prompt_provider(tool_registry, None, Some(temp_dir.path()))
+            .await
+            .unwrap();
+        let mut prompts = provider.list_prompts().await.unwrap();
+        prompts.sort();
+        assert_eq!(prompts, vec!["do-it", "review"]);
+
+        let response = PromptHandler::get_prompt(
+            &provider,
+            GetPromptRequest {
+                name: "review".to_string(),
+                arguments: Some(serde_json::json!({"branch": "main"})),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.description.as_deref(), Some("Review a branch"));
+        match &response.messages[0].content {
+            PromptContent::Text { text } => assert_eq!(text, "Review main with lint."),
+            other => panic!("unexpected content {other:?}"),
+        }
+
+        let missing_argument = GetPromptRequest {
+            name: "review".to_string(),
+            arguments: None,
+        };
+        assert!(PromptHandler::get_prompt(&provider, missing_argument)
+            .await
+            .is_err());
+    }
 }
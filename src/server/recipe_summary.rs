@@ -0,0 +1,493 @@
+//! Token-budgeted recipe catalog served as `just://summary/{project}`
+//!
+//! `tools/list` carries full schemas and descriptions, which can take
+//! thousands of tokens for a large justfile. The summary resource renders a
+//! compact catalog instead: recipe names grouped by `[group]`, each with a
+//! few words of description. With `?max_tokens=N` it drops detail until the
+//! catalog fits the budget, so agents with a small context still see every
+//! recipe that exists, or at least how many.
+//!
+//! The layout follows `just --list`: recipes without a group first, then a
+//! section per group, with a recipe in several groups listed in each. Recipes
+//! and groups are sorted by name, or with `?order=source` kept in justfile
+//! order like `just --list --unsorted`.
+//!
+//! Token counts are estimated at four characters per token, which is close
+//! enough for English text and recipe names across common tokenizers.
+
+use crate::error::{Error, Result};
+use crate::registry::ToolRegistry;
+use crate::types::ToolDefinition;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+pub use crate::types::SUMMARY_URI_PREFIX;
+
+/// URI template advertised for recipe summaries
+pub const SUMMARY_URI_TEMPLATE: &str = "just://summary/{project}{?max_tokens,order}";
+
+/// Estimated characters per token
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Heading for recipes without a group
+const UNGROUPED: &str = "other";
+
+/// Estimate the number of tokens in a text
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// A recipe as shown in the summary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogEntry {
+    /// Tool name agents call the recipe by
+    pub name: String,
+    pub groups: Vec<String>,
+    pub parameters: Vec<String>,
+    /// First sentence of the description; empty for generated descriptions
+    pub description: String,
+}
+
+impl CatalogEntry {
+    pub fn from_tool(tool: &ToolDefinition) -> Self {
+        let parameters = tool.input_schema["properties"]
+            .as_object()
+            .map(|properties| properties.keys().cloned().collect())
+            .unwrap_or_default();
+        let description = if tool.description.starts_with("Execute '") {
+            String::new()
+        } else {
+            first_sentence(&tool.description).to_string()
+        };
+        Self {
+            name: tool.name.clone(),
+            groups: tool.metadata.groups.clone(),
+            parameters,
+            description,
+        }
+    }
+}
+
+/// How recipes and groups are ordered in the summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListOrder {
+    /// By name, like `just --list`
+    #[default]
+    Name,
+    /// As in the justfile, like `just --list --unsorted`
+    Source,
+}
+
+/// Levels of detail, from most to least
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Detail {
+    /// Parameters and descriptions of up to 80 characters
+    Full,
+    /// Descriptions of up to 30 characters, no parameters
+    Short,
+    /// Names only, comma-separated per group
+    Names,
+}
+
+/// Sections of the catalog: recipes without a group, then one per group
+///
+/// With [`ListOrder::Source`], `entries` are expected in justfile order and
+/// groups appear in the order of their first recipe.
+fn group_entries(entries: &[CatalogEntry], order: ListOrder) -> Vec<(&str, Vec<&CatalogEntry>)> {
+    let mut sorted: Vec<&CatalogEntry> = entries.iter().collect();
+    if order == ListOrder::Name {
+        sorted.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    let mut groups: Vec<(&str, Vec<&CatalogEntry>)> = vec![(UNGROUPED, Vec::new())];
+    for entry in sorted {
+        if entry.groups.is_empty() {
+            groups[0].1.push(entry);
+        }
+        for group in &entry.groups {
+            match groups[1..].iter_mut().find(|(name, _)| name == group) {
+                Some((_, recipes)) => recipes.push(entry),
+                None => groups.push((group, vec![entry])),
+            }
+        }
+    }
+    if order == ListOrder::Name {
+        groups[1..].sort_by(|a, b| a.0.cmp(b.0));
+    }
+    groups.retain(|(_, recipes)| !recipes.is_empty());
+    groups
+}
+
+/// Render a catalog of `entries`, fitted to `max_tokens` if given
+///
+/// Detail is reduced until the catalog fits. If even the bare names don't
+/// fit, groups are cut off and the number of omitted recipes is noted.
+pub fn render_catalog(
+    project: &str,
+    entries: &[CatalogEntry],
+    max_tokens: Option<usize>,
+    order: ListOrder,
+) -> String {
+    let groups = group_entries(entries, order);
+
+    let header = format!("# {project}: {} recipes\n", entries.len());
+    let Some(max_tokens) = max_tokens else {
+        return header + &render_groups(&groups, Detail::Full);
+    };
+
+    for detail in [Detail::Full, Detail::Short, Detail::Names] {
+        let text = header.clone() + &render_groups(&groups, detail);
+        if estimate_tokens(&text) <= max_tokens {
+            return text;
+        }
+    }
+
+    // Cut off names once the budget runs out, keeping room for the note
+    let note = |omitted: usize| {
+        format!("... {omitted} more recipes; raise max_tokens or use tools/list\n")
+    };
+    let budget = (max_tokens * CHARS_PER_TOKEN).saturating_sub(note(entries.len()).len() + 1);
+    let mut text = header;
+    let mut shown = HashSet::new();
+    'groups: for (group, recipes) in &groups {
+        let heading = format!("{group}:");
+        if text.len() + heading.len() > budget {
+            break;
+        }
+        text.push_str(&heading);
+        for (i, entry) in recipes.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            if text.len() + separator.len() + entry.name.len() > budget {
+                text.push('\n');
+                break 'groups;
+            }
+            text.push_str(separator);
+            text.push_str(&entry.name);
+            shown.insert(&entry.name);
+        }
+        text.push('\n');
+    }
+    text.push_str(&note(entries.len() - shown.len()));
+    text
+}
+
+fn render_groups(groups: &[(&str, Vec<&CatalogEntry>)], detail: Detail) -> String {
+    let mut text = String::new();
+    for (group, recipes) in groups {
+        if detail == Detail::Names {
+            let names: Vec<&str> = recipes.iter().map(|entry| entry.name.as_str()).collect();
+            text.push_str(&format!("{group}: {}\n", names.join(", ")));
+            continue;
+        }
+
+        text.push_str(&format!("## {group}\n"));
+        for entry in recipes {
+            let mut line = format!("- {}", entry.name);
+            let max_description = match detail {
+                Detail::Full => {
+                    if !entry.parameters.is_empty() {
+                        line.push_str(&format!("({})", entry.parameters.join(", ")));
+                    }
+                    80
+                }
+                _ => 30,
+            };
+            if !entry.description.is_empty() {
+                line.push_str(": ");
+                line.push_str(&truncate(&entry.description, max_description));
+            }
+            text.push_str(&line);
+            text.push('\n');
+        }
+    }
+    text
+}
+
+fn first_sentence(text: &str) -> &str {
+    let text = text.lines().next().unwrap_or_default().trim();
+    match text.find(". ") {
+        Some(end) => &text[..end],
+        None => text.trim_end_matches('.'),
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// Parsed `just://summary/{project}?max_tokens=N` URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummaryRequest {
+    pub project: String,
+    pub max_tokens: Option<usize>,
+    pub order: ListOrder,
+}
+
+impl SummaryRequest {
+    /// Parse a summary URI; `None` if the URI is not a summary URI
+    pub fn parse(uri: &str) -> Option<Result<Self>> {
+        let rest = uri.strip_prefix(SUMMARY_URI_PREFIX)?;
+        let (project, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+        let mut max_tokens = None;
+        let mut order = ListOrder::default();
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            match pair.split_once('=') {
+                Some(("max_tokens", value)) => match value.parse::<usize>() {
+                    Ok(value) if value > 0 => max_tokens = Some(value),
+                    _ => {
+                        return Some(Err(Error::InvalidParameter(format!(
+                            "max_tokens must be a positive integer, got '{value}'"
+                        ))))
+                    }
+                },
+                Some(("order", "name")) => order = ListOrder::Name,
+                Some(("order", "source")) => order = ListOrder::Source,
+                Some(("order", value)) => {
+                    return Some(Err(Error::InvalidParameter(format!(
+                        "order must be name or source, got '{value}'"
+                    ))))
+                }
+                _ => {
+                    return Some(Err(Error::InvalidParameter(format!(
+                        "Unknown summary parameter: {pair}"
+                    ))))
+                }
+            }
+        }
+
+        Some(Ok(Self {
+            project: project.to_string(),
+            max_tokens,
+            order,
+        }))
+    }
+}
+
+/// Renders summaries of the registered recipes, one per watch directory
+pub struct RecipeSummary {
+    registry: Arc<Mutex<ToolRegistry>>,
+    /// Watch directories and their project names
+    projects: Vec<(PathBuf, String)>,
+}
+
+impl RecipeSummary {
+    /// Projects are named after their watch directory's configured name, or
+    /// the directory's name when none is configured
+    pub fn new(
+        registry: Arc<Mutex<ToolRegistry>>,
+        watch_configs: &[(PathBuf, Option<String>)],
+    ) -> Self {
+        let projects = watch_configs
+            .iter()
+            .map(|(path, name)| {
+                let name = name.clone().unwrap_or_else(|| {
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "default".to_string())
+                });
+                (path.clone(), name)
+            })
+            .collect();
+        Self { registry, projects }
+    }
+
+    pub fn project_names(&self) -> Vec<String> {
+        self.projects.iter().map(|(_, name)| name.clone()).collect()
+    }
+
+    /// Project a justfile belongs to: its innermost watch directory
+    fn project_of(&self, justfile: &Path) -> Option<&str> {
+        self.projects
+            .iter()
+            .filter(|(path, _)| justfile.starts_with(path))
+            .max_by_key(|(path, _)| path.components().count())
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Render the summary a `just://summary/...` URI asks for
+    pub async fn render(&self, request: &SummaryRequest) -> Result<String> {
+        if !self
+            .projects
+            .iter()
+            .any(|(_, name)| *name == request.project)
+        {
+            return Err(Error::InvalidParameter(format!(
+                "Unknown project '{}'; available: {}",
+                request.project,
+                self.project_names().join(", ")
+            )));
+        }
+
+        let mut tools: Vec<(PathBuf, ToolDefinition)> = self
+            .registry
+            .lock()
+            .await
+            .list_tools()
+            .into_iter()
+            .filter(|tool| !tool.name.starts_with("_admin_"))
+            .filter_map(|tool| Some((justfile_of(tool)?, tool.clone())))
+            .filter(|(path, _)| self.project_of(path) == Some(request.project.as_str()))
+            .collect();
+        // Justfile order, with the justfiles of a project by path
+        tools.sort_by(|(a_path, a), (b_path, b)| {
+            (a_path, a.metadata.source_order, &a.name).cmp(&(
+                b_path,
+                b.metadata.source_order,
+                &b.name,
+            ))
+        });
+        let entries: Vec<CatalogEntry> = tools
+            .iter()
+            .map(|(_, tool)| CatalogEntry::from_tool(tool))
+            .collect();
+
+        Ok(render_catalog(
+            &request.project,
+            &entries,
+            request.max_tokens,
+            request.order,
+        ))
+    }
+}
+
+/// Justfile a tool was generated from, from its `task_/path/justfile` internal name
+fn justfile_of(tool: &ToolDefinition) -> Option<PathBuf> {
+    let internal = tool.internal_name.as_deref()?;
+    let start = internal.find("_/")?;
+    Some(PathBuf::from(&internal[start + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, groups: &[&str], parameters: &[&str], description: &str) -> CatalogEntry {
+        CatalogEntry {
+            name: name.to_string(),
+            groups: groups.iter().map(|g| g.to_string()).collect(),
+            parameters: parameters.iter().map(|p| p.to_string()).collect(),
+            description: description.to_string(),
+        }
+    }
+
+    fn catalog() -> Vec<CatalogEntry> {
+        let mut entries = vec![
+            entry(
+                "build",
+                &["dev"],
+                &["target"],
+                "Build the project in the given profile",
+            ),
+            entry(
+                "test",
+                &["dev"],
+                &[],
+                "Run the whole test suite with coverage enabled",
+            ),
+            entry("deploy", &["ops"], &["env"], "Deploy to an environment"),
+            entry("clean", &[], &[], ""),
+        ];
+        for i in 0..40 {
+            entries.push(entry(
+                &format!("task{i:02}"),
+                &["misc"],
+                &[],
+                "Generated task",
+            ));
+        }
+        entries
+    }
+
+    #[test]
+    fn test_catalog_fits_budget() {
+        let entries = catalog();
+
+        let full = render_catalog("app", &entries, None, ListOrder::Name);
+        assert!(full.starts_with("# app: 44 recipes\n"));
+        assert!(full.contains("## dev\n- build(target): Build the project in the given profile\n"));
+        assert!(full.contains("## other\n- clean\n"));
+
+        for budget in [400, 150, 60, 20] {
+            let text = render_catalog("app", &entries, Some(budget), ListOrder::Name);
+            assert!(
+                estimate_tokens(&text) <= budget,
+                "{budget}: {} tokens\n{text}",
+                estimate_tokens(&text)
+            );
+        }
+
+        let names = render_catalog("app", &entries, Some(150), ListOrder::Name);
+        assert!(names.contains("dev: build, test\n"));
+        let cut = render_catalog("app", &entries, Some(60), ListOrder::Name);
+        assert!(cut.contains("more recipes; raise max_tokens"));
+    }
+
+    #[test]
+    fn test_catalog_follows_just_list_layout() {
+        // In justfile order, as `just --list --unsorted` shows them
+        let entries = vec![
+            entry("zeta", &[], &[], ""),
+            entry("deploy", &["ops"], &[], ""),
+            entry("build", &["dev", "ops"], &[], ""),
+            entry("alpha", &[], &[], ""),
+            entry("lint", &["dev"], &[], ""),
+        ];
+
+        let sorted = render_catalog("app", &entries, None, ListOrder::Name);
+        assert_eq!(
+            sorted,
+            "# app: 5 recipes\n## other\n- alpha\n- zeta\n## dev\n- build\n- lint\n## ops\n- build\n- deploy\n"
+        );
+        let unsorted = render_catalog("app", &entries, None, ListOrder::Source);
+        assert_eq!(
+            unsorted,
+            "# app: 5 recipes\n## other\n- zeta\n- alpha\n## ops\n- deploy\n- build\n## dev\n- build\n- lint\n"
+        );
+
+        // Recipes listed in two groups count once when names are cut off
+        let cut = render_catalog("app", &entries, Some(14), ListOrder::Name);
+        assert!(cut.contains("more recipes"), "{cut}");
+    }
+
+    #[test]
+    fn test_parse_summary_uri() {
+        assert_eq!(
+            SummaryRequest::parse("just://summary/app?max_tokens=500")
+                .unwrap()
+                .unwrap(),
+            SummaryRequest {
+                project: "app".to_string(),
+                max_tokens: Some(500),
+                order: ListOrder::Name,
+            }
+        );
+        assert_eq!(
+            SummaryRequest::parse("just://summary/app?order=source")
+                .unwrap()
+                .unwrap()
+                .order,
+            ListOrder::Source
+        );
+        assert!(SummaryRequest::parse("just://summary/app?order=random")
+            .unwrap()
+            .is_err());
+        assert_eq!(
+            SummaryRequest::parse("just://summary/app")
+                .unwrap()
+                .unwrap()
+                .max_tokens,
+            None
+        );
+        assert!(SummaryRequest::parse("just://summary/app?max_tokens=0")
+            .unwrap()
+            .is_err());
+        assert!(SummaryRequest::parse("just://output/1").is_none());
+    }
+}
@@ -0,0 +1,196 @@
+//! Recently rendered resources
+//!
+//! Agents tend to poll resources such as `just://summary/{project}` and
+//! `file:///config.json` between tool calls, and each read walked the whole
+//! registry and collected the configuration again. Rendered text is kept
+//! for a short time instead: an entry is served while it is younger than the
+//! TTL and the registry's revision still matches the one it was rendered
+//! at, so an edited justfile shows up on the next read rather than after
+//! the TTL. At most [`MAX_CACHED_RESOURCES`] entries are kept, evicting the
+//! least recently read.
+//!
+//! Configuration values that change without a registry change, like
+//! resource limit usage, may be up to one TTL old.
+
+use crate::error::Result;
+use crate::registry::ToolRegistry;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default time a rendered resource is served from the cache
+pub const DEFAULT_RESOURCE_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Most resources the cache holds at once
+pub const MAX_CACHED_RESOURCES: usize = 64;
+
+struct Entry {
+    text: String,
+    /// Registry revision the text was rendered at
+    revision: u64,
+    rendered: Instant,
+    last_read: Instant,
+}
+
+/// Rendered resource text by URI
+pub struct ResourceCache {
+    registry: Arc<tokio::sync::Mutex<ToolRegistry>>,
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl ResourceCache {
+    /// Serve rendered resources for up to `ttl` while `registry` is unchanged
+    pub fn new(registry: Arc<tokio::sync::Mutex<ToolRegistry>>, ttl: Duration) -> Self {
+        Self {
+            registry,
+            ttl,
+            capacity: MAX_CACHED_RESOURCES,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Keep at most `capacity` resources
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Cached text for `uri`, or the text `render` produces, which is cached
+    /// unless rendering fails
+    pub async fn get_or_render<F, Fut>(&self, uri: &str, render: F) -> Result<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        let revision = self.registry.lock().await.revision();
+        if let Some(text) = self.fresh(uri, revision) {
+            return Ok(text);
+        }
+
+        let text = render().await?;
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(
+            uri.to_string(),
+            Entry {
+                text: text.clone(),
+                revision,
+                rendered: now,
+                last_read: now,
+            },
+        );
+        while entries.len() > self.capacity {
+            let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_read)
+                .map(|(uri, _)| uri.clone())
+            else {
+                break;
+            };
+            entries.remove(&oldest);
+        }
+        Ok(text)
+    }
+
+    /// Text of an entry that is neither expired nor from an older registry
+    fn fresh(&self, uri: &str, revision: u64) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get_mut(uri)?;
+        if entry.revision != revision || entry.rendered.elapsed() >= self.ttl {
+            entries.remove(uri);
+            return None;
+        }
+        entry.last_read = Instant::now();
+        Some(entry.text.clone())
+    }
+
+    /// Number of cached resources
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ToolDefinition;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn tool(name: &str) -> ToolDefinition {
+        ToolDefinition {
+            name: name.to_string(),
+            description: String::new(),
+            input_schema: serde_json::json!({"type": "object"}),
+            dependencies: vec![],
+            source_hash: String::new(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_entries_are_validated_by_revision_ttl_and_capacity() {
+        let registry = Arc::new(tokio::sync::Mutex::new(ToolRegistry::new()));
+        let cache = ResourceCache::new(registry.clone(), Duration::from_secs(60)).with_capacity(2);
+        let renders = AtomicUsize::new(0);
+        let render = |text: &'static str| {
+            renders.fetch_add(1, Ordering::SeqCst);
+            async move { Ok(text.to_string()) }
+        };
+
+        assert_eq!(
+            cache.get_or_render("a", || render("a1")).await.unwrap(),
+            "a1"
+        );
+        assert_eq!(
+            cache.get_or_render("a", || render("a2")).await.unwrap(),
+            "a1"
+        );
+        assert_eq!(renders.load(Ordering::SeqCst), 1);
+
+        // A registry change invalidates entries rendered before it
+        registry.lock().await.add_tool(tool("build")).unwrap();
+        assert_eq!(
+            cache.get_or_render("a", || render("a3")).await.unwrap(),
+            "a3"
+        );
+
+        // Failed renders aren't cached
+        let failed = cache
+            .get_or_render("b", || async {
+                Err(crate::error::Error::Other("unavailable".to_string()))
+            })
+            .await;
+        assert!(failed.is_err());
+        assert_eq!(cache.len(), 1);
+
+        // The least recently read entry goes when the cache is full
+        cache.get_or_render("b", || render("b1")).await.unwrap();
+        cache.get_or_render("a", || render("unused")).await.unwrap();
+        cache.get_or_render("c", || render("c1")).await.unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(
+            cache.get_or_render("a", || render("a4")).await.unwrap(),
+            "a3"
+        );
+        assert_eq!(
+            cache.get_or_render("b", || render("b2")).await.unwrap(),
+            "b2"
+        );
+
+        let expiring = ResourceCache::new(registry, Duration::ZERO);
+        expiring.get_or_render("a", || render("x1")).await.unwrap();
+        assert_eq!(
+            expiring.get_or_render("a", || render("x2")).await.unwrap(),
+            "x2"
+        );
+    }
+}
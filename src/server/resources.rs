@@ -7,8 +7,14 @@
 //! rewriting them, as they already provide comprehensive justfile metadata
 //! and configuration data.
 
+use super::output_store::{OutputStore, OUTPUT_URI_PREFIX};
+use super::recipe_summary::{
+    RecipeSummary, SummaryRequest, SUMMARY_URI_PREFIX, SUMMARY_URI_TEMPLATE,
+};
+use super::resource_cache::ResourceCache;
+use super::server_info::{ServerInfoReport, SERVER_INFO_URI};
 use crate::embedded_content::resources::ResourceProvider;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use std::sync::Arc;
 
 #[cfg(feature = "ultrafast-framework")]
@@ -30,16 +36,128 @@ use ultrafast_mcp::types::{
 pub struct FrameworkResourceProvider {
     /// Existing combined resource provider with all functionality
     combined_provider: Arc<crate::config_resource::CombinedResourceProvider>,
+    /// Large tool outputs served as `just://output/{id}`
+    output_store: Option<Arc<OutputStore>>,
+    /// Recipe catalogs served as `just://summary/{project}`
+    recipe_summary: Option<Arc<RecipeSummary>>,
+    /// Build and runtime facts served as `just://server-info`
+    server_info: Option<Arc<ServerInfoReport>>,
+    /// Recently rendered summaries and config.json
+    cache: Option<Arc<ResourceCache>>,
 }
 
+/// URI of the configuration resource
+const CONFIG_URI: &str = "file:///config.json";
+
 impl FrameworkResourceProvider {
     /// Create a new framework resource provider
     pub fn new(combined_provider: Arc<crate::config_resource::CombinedResourceProvider>) -> Self {
-        Self { combined_provider }
+        Self {
+            combined_provider,
+            output_store: None,
+            recipe_summary: None,
+            server_info: None,
+            cache: None,
+        }
+    }
+
+    /// Serve stored tool outputs as resources
+    pub fn with_output_store(mut self, output_store: Arc<OutputStore>) -> Self {
+        self.output_store = Some(output_store);
+        self
+    }
+
+    /// Serve token-budgeted recipe catalogs as resources
+    pub fn with_recipe_summary(mut self, recipe_summary: Arc<RecipeSummary>) -> Self {
+        self.recipe_summary = Some(recipe_summary);
+        self
+    }
+
+    /// Serve the server's build and runtime facts as a resource
+    pub fn with_server_info(mut self, server_info: Arc<ServerInfoReport>) -> Self {
+        self.server_info = Some(server_info);
+        self
+    }
+
+    /// Serve rendered summaries and config.json from `cache` while fresh
+    pub fn with_resource_cache(mut self, cache: Arc<ResourceCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Text `render` produces for `uri`, from the cache when there is one
+    async fn cached<F, Fut>(&self, uri: &str, render: F) -> Result<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<String>>,
+    {
+        match &self.cache {
+            Some(cache) => cache.get_or_render(uri, render).await,
+            None => render().await,
+        }
+    }
+
+    /// Cached config.json; without a cache it's read like any other
+    /// resource of the combined provider
+    async fn config_json(&self, uri: &str) -> Option<Result<String>> {
+        if uri != CONFIG_URI || self.cache.is_none() {
+            return None;
+        }
+        Some(
+            self.cached(uri, || async {
+                let content = self
+                    .combined_provider
+                    .read_resource(uri)
+                    .await
+                    .map_err(|e| Error::Other(format!("Resource read failed: {e}")))?;
+                Ok(content.text.unwrap_or_default())
+            })
+            .await,
+        )
+    }
+
+    /// Server info as JSON for the `just://server-info` URI
+    fn server_info_json(&self, uri: &str) -> Option<Result<String>> {
+        if uri != SERVER_INFO_URI {
+            return None;
+        }
+        let server_info = self.server_info.as_ref()?;
+        Some(serde_json::to_string_pretty(server_info.as_ref()).map_err(Into::into))
+    }
+
+    /// Rendered summary for a `just://summary/{project}` URI
+    async fn summary(&self, uri: &str) -> Option<Result<String>> {
+        let recipe_summary = self.recipe_summary.as_ref()?;
+        Some(match SummaryRequest::parse(uri)? {
+            Ok(request) => self.cached(uri, || recipe_summary.render(&request)).await,
+            Err(e) => Err(e),
+        })
+    }
+
+    /// Stored output for a `just://output/{id}` URI
+    fn stored_output(&self, uri: &str) -> Option<Option<String>> {
+        if !uri.starts_with(OUTPUT_URI_PREFIX) {
+            return None;
+        }
+        let store = self.output_store.as_ref()?;
+        Some(store.get(uri).map(|output| output.text))
     }
 
     /// Get resource by URI (framework-independent interface)
     pub async fn get_resource_by_uri(&self, uri: &str) -> Result<Option<String>> {
+        if let Some(output) = self.stored_output(uri) {
+            return Ok(output);
+        }
+        if let Some(summary) = self.summary(uri).await {
+            return summary.map(Some);
+        }
+        if let Some(server_info) = self.server_info_json(uri) {
+            return server_info.map(Some);
+        }
+        if let Some(config) = self.config_json(uri).await {
+            return config.map(Some);
+        }
+
         // Use existing resource provider logic
         match self.combined_provider.read_resource(uri).await {
             Ok(resource_content) => Ok(resource_content.text),
@@ -68,6 +186,52 @@ impl FrameworkResourceProvider {
 impl ResourceHandler for FrameworkResourceProvider {
     /// Read resource content by URI
     async fn read_resource(&self, request: ReadResourceRequest) -> MCPResult<ReadResourceResponse> {
+        if let Some(output) = self.stored_output(&request.uri) {
+            let text = output.ok_or_else(|| {
+                MCPError::invalid_params(format!(
+                    "Output {} is no longer available; re-run the tool",
+                    request.uri
+                ))
+            })?;
+            return Ok(ReadResourceResponse {
+                contents: vec![ResourceContent::Text {
+                    uri: request.uri,
+                    text,
+                    mime_type: Some("text/plain".to_string()),
+                }],
+            });
+        }
+        if let Some(summary) = self.summary(&request.uri).await {
+            let text = summary.map_err(|e| MCPError::invalid_params(e.to_string()))?;
+            return Ok(ReadResourceResponse {
+                contents: vec![ResourceContent::Text {
+                    uri: request.uri,
+                    text,
+                    mime_type: Some("text/markdown".to_string()),
+                }],
+            });
+        }
+        if let Some(server_info) = self.server_info_json(&request.uri) {
+            let text = server_info.map_err(|e| MCPError::internal_error(e.to_string()))?;
+            return Ok(ReadResourceResponse {
+                contents: vec![ResourceContent::Text {
+                    uri: request.uri,
+                    text,
+                    mime_type: Some("application/json".to_string()),
+                }],
+            });
+        }
+        if let Some(config) = self.config_json(&request.uri).await {
+            let text = config.map_err(|e| MCPError::internal_error(e.to_string()))?;
+            return Ok(ReadResourceResponse {
+                contents: vec![ResourceContent::Text {
+                    uri: request.uri,
+                    text,
+                    mime_type: Some("application/json".to_string()),
+                }],
+            });
+        }
+
         match self.combined_provider.read_resource(&request.uri).await {
             Ok(resource_content) => {
                 let content = if let Some(text) = resource_content.text {
@@ -109,7 +273,7 @@ impl ResourceHandler for FrameworkResourceProvider {
     ) -> MCPResult<ListResourcesResponse> {
         match self.combined_provider.list_resources().await {
             Ok(resources) => {
-                let framework_resources = resources
+                let mut framework_resources: Vec<Resource> = resources
                     .into_iter()
                     .map(|r| Resource {
                         uri: r.uri,
@@ -118,6 +282,38 @@ impl ResourceHandler for FrameworkResourceProvider {
                         mime_type: r.mime_type,
                     })
                     .collect();
+                if let Some(store) = &self.output_store {
+                    framework_resources.extend(store.list().into_iter().map(
+                        |(uri, tool, size)| Resource {
+                            name: format!("{tool} output"),
+                            description: Some(format!("Full output of {tool} ({size} bytes)")),
+                            uri,
+                            mime_type: Some("text/plain".to_string()),
+                        },
+                    ));
+                }
+                if let Some(summary) = &self.recipe_summary {
+                    framework_resources.extend(summary.project_names().into_iter().map(
+                        |project| Resource {
+                            uri: format!("{SUMMARY_URI_PREFIX}{project}"),
+                            name: format!("{project} recipe summary"),
+                            description: Some(format!(
+                                "Compact catalog of the recipes in {project}; add ?max_tokens=N to fit a token budget"
+                            )),
+                            mime_type: Some("text/markdown".to_string()),
+                        },
+                    ));
+                }
+                if self.server_info.is_some() {
+                    framework_resources.push(Resource {
+                        uri: SERVER_INFO_URI.to_string(),
+                        name: "Server info".to_string(),
+                        description: Some(
+                            "just-mcp version, git commit, cargo features, just version and parser configuration, for bug reports".to_string(),
+                        ),
+                        mime_type: Some("application/json".to_string()),
+                    });
+                }
                 Ok(ListResourcesResponse {
                     resources: framework_resources,
                     next_cursor: None,
@@ -136,7 +332,7 @@ impl ResourceHandler for FrameworkResourceProvider {
     ) -> MCPResult<ListResourceTemplatesResponse> {
         match self.combined_provider.list_resource_templates().await {
             Ok(templates) => {
-                let framework_templates = templates
+                let mut framework_templates: Vec<ResourceTemplate> = templates
                     .into_iter()
                     .map(|t| ResourceTemplate {
                         uri_template: t.uri_template,
@@ -145,6 +341,16 @@ impl ResourceHandler for FrameworkResourceProvider {
                         mime_type: t.mime_type,
                     })
                     .collect();
+                if self.recipe_summary.is_some() {
+                    framework_templates.push(ResourceTemplate {
+                        uri_template: SUMMARY_URI_TEMPLATE.to_string(),
+                        name: "Recipe summary".to_string(),
+                        description: Some(
+                            "Recipes of a project in `just --list` layout with short descriptions, fitted to max_tokens; order=source keeps justfile order".to_string(),
+                        ),
+                        mime_type: Some("text/markdown".to_string()),
+                    });
+                }
                 Ok(ListResourceTemplatesResponse {
                     resource_templates: framework_templates,
                     next_cursor: None,
@@ -259,4 +465,76 @@ mod tests {
             assert!(content.is_some());
         }
     }
+
+    #[tokio::test]
+    async fn test_server_info_resource() {
+        let tool_registry = Arc::new(tokio::sync::Mutex::new(ToolRegistry::new()));
+        let provider = create_framework_resource_provider(None, None, None, tool_registry)
+            .await
+            .unwrap();
+        assert!(provider
+            .get_resource_by_uri(SERVER_INFO_URI)
+            .await
+            .unwrap()
+            .is_none());
+
+        let report = ServerInfoReport::collect(&crate::parser::ParserPreference::Ast, &[]);
+        let provider = provider.with_server_info(Arc::new(report));
+        let text = provider
+            .get_resource_by_uri(SERVER_INFO_URI)
+            .await
+            .unwrap()
+            .unwrap();
+        let info: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(info["version"], crate::VERSION);
+        assert_eq!(info["parser"], "ast");
+        assert!(info["just"]["install_hint"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_recipe_summary_resource() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        std::fs::write(
+            &justfile_path,
+            "# Build the project. Uses cargo.\n[group('dev')]\nbuild target=\"debug\":\n    cargo build\n\n[group('ops')]\ndeploy:\n    echo deploy\n",
+        )
+        .unwrap();
+
+        let tool_registry = Arc::new(tokio::sync::Mutex::new(ToolRegistry::new()));
+        let watcher = crate::watcher::JustfileWatcher::new(tool_registry.clone());
+        watcher
+            .parse_and_update_justfile(&justfile_path)
+            .await
+            .unwrap();
+        let summary = RecipeSummary::new(
+            tool_registry.clone(),
+            &[(temp_dir.path().to_path_buf(), Some("app".to_string()))],
+        );
+        let provider = create_framework_resource_provider(None, None, None, tool_registry)
+            .await
+            .unwrap()
+            .with_recipe_summary(Arc::new(summary));
+
+        let text = provider
+            .get_resource_by_uri("just://summary/app")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(text.starts_with("# app: 2 recipes\n"), "{text}");
+        assert!(text.contains("## dev\n- build(target): Build the project\n"));
+        assert!(text.contains("## ops\n- deploy\n"));
+
+        let text = provider
+            .get_resource_by_uri("just://summary/app?max_tokens=12")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(text.contains("dev: build\nops: deploy\n"), "{text}");
+
+        assert!(provider
+            .get_resource_by_uri("just://summary/other")
+            .await
+            .is_err());
+    }
 }
@@ -0,0 +1,244 @@
+//! Previous outputs of tool calls, for diffing repeated runs
+//!
+//! Check-style recipes (lint, `terraform plan`, test suites) are run again
+//! and again, and what an agent cares about is what changed since the last
+//! run. With run diffs enabled, the server remembers the output of each
+//! tool call, keyed by tool and arguments, and adds a unified diff against
+//! the previous run of the same call to the next result.
+//!
+//! Outputs are kept in memory for the session. The least recently run calls
+//! are forgotten once more than [`DEFAULT_MAX_RUNS`] are remembered.
+
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use similar::TextDiff;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Default number of distinct tool calls remembered
+pub const DEFAULT_MAX_RUNS: usize = 256;
+
+/// Outputs larger than this are compared but not diffed
+const MAX_DIFF_BYTES: usize = 1024 * 1024;
+
+/// Diff lines included in a result before the rest is elided
+const MAX_DIFF_LINES: usize = 200;
+
+/// Lines of unchanged context around each change
+const CONTEXT_LINES: usize = 3;
+
+/// Output of an earlier run of a tool call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviousRun {
+    pub output: String,
+    pub exit_code: Option<i32>,
+    pub finished_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+struct Runs {
+    by_call: HashMap<String, (u64, PreviousRun)>,
+    sequence: u64,
+}
+
+/// Last output of each tool call made this session
+#[derive(Debug)]
+pub struct RunHistory {
+    runs: Mutex<Runs>,
+    max_runs: usize,
+}
+
+impl RunHistory {
+    /// Remember the last output of up to `max_runs` distinct calls
+    pub fn new(max_runs: usize) -> Self {
+        Self {
+            runs: Mutex::new(Runs::default()),
+            max_runs,
+        }
+    }
+
+    /// Record a run's output and return the previous run of the same tool
+    /// with the same arguments, if any
+    pub fn record(
+        &self,
+        tool: &str,
+        arguments: &Map<String, Value>,
+        output: &str,
+        exit_code: Option<i32>,
+    ) -> Option<PreviousRun> {
+        let key = call_key(tool, arguments);
+        let run = PreviousRun {
+            output: output.to_string(),
+            exit_code,
+            finished_at: Utc::now(),
+        };
+
+        let mut runs = self.runs.lock().unwrap();
+        runs.sequence += 1;
+        let sequence = runs.sequence;
+        let previous = runs
+            .by_call
+            .insert(key, (sequence, run))
+            .map(|(_, run)| run);
+
+        if runs.by_call.len() > self.max_runs {
+            let oldest = runs
+                .by_call
+                .iter()
+                .min_by_key(|(_, (sequence, _))| *sequence)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                runs.by_call.remove(&oldest);
+            }
+        }
+        previous
+    }
+}
+
+impl Default for RunHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_RUNS)
+    }
+}
+
+/// Identity of a call: the tool and its arguments with keys sorted
+pub(crate) fn call_key(tool: &str, arguments: &Map<String, Value>) -> String {
+    let mut arguments: Vec<(&String, &Value)> = arguments.iter().collect();
+    arguments.sort_by_key(|(name, _)| *name);
+    let arguments: Vec<String> = arguments
+        .into_iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect();
+    format!("{tool}\0{}", arguments.join("\0"))
+}
+
+/// What changed between `previous` and this run, for the tool result
+pub fn change_summary(previous: &PreviousRun, output: &str, exit_code: Option<i32>) -> String {
+    let since = format!(
+        "since the previous run at {}",
+        previous.finished_at.format("%H:%M:%S UTC")
+    );
+    let exit = if previous.exit_code != exit_code {
+        format!(
+            "; exit code {} → {}",
+            describe_exit(previous.exit_code),
+            describe_exit(exit_code)
+        )
+    } else {
+        String::new()
+    };
+
+    if previous.output == output {
+        return format!("Output unchanged {since}{exit}");
+    }
+    if previous.output.len() > MAX_DIFF_BYTES || output.len() > MAX_DIFF_BYTES {
+        return format!(
+            "Output changed {since} ({} → {} bytes{exit}); too large to diff",
+            previous.output.len(),
+            output.len()
+        );
+    }
+
+    let diff = TextDiff::from_lines(previous.output.as_str(), output);
+    let (mut added, mut removed) = (0, 0);
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            similar::ChangeTag::Insert => added += 1,
+            similar::ChangeTag::Delete => removed += 1,
+            similar::ChangeTag::Equal => {}
+        }
+    }
+    let unified = diff
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .header("previous", "current")
+        .to_string();
+    let lines: Vec<&str> = unified.lines().collect();
+    let mut shown = lines[..lines.len().min(MAX_DIFF_LINES)].join("\n");
+    if lines.len() > MAX_DIFF_LINES {
+        shown.push_str(&format!(
+            "\n... {} more diff lines",
+            lines.len() - MAX_DIFF_LINES
+        ));
+    }
+
+    format!("Changes {since} (+{added} -{removed} lines{exit}):\n```diff\n{shown}\n```")
+}
+
+fn describe_exit(exit_code: Option<i32>) -> String {
+    exit_code.map_or_else(|| "none".to_string(), |code| code.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn arguments(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_previous_run_is_per_tool_and_arguments() {
+        let history = RunHistory::default();
+        let staging = arguments(json!({"env": "staging", "verbose": true}));
+        let reordered = arguments(json!({"verbose": true, "env": "staging"}));
+        let prod = arguments(json!({"env": "prod", "verbose": true}));
+
+        assert!(history.record("plan", &staging, "a\n", Some(0)).is_none());
+        assert!(history.record("plan", &prod, "b\n", Some(0)).is_none());
+        assert!(history.record("lint", &staging, "c\n", Some(0)).is_none());
+
+        let previous = history.record("plan", &reordered, "a2\n", Some(2)).unwrap();
+        assert_eq!(previous.output, "a\n");
+        assert_eq!(previous.exit_code, Some(0));
+        assert_eq!(
+            history
+                .record("plan", &staging, "a3\n", Some(0))
+                .unwrap()
+                .output,
+            "a2\n"
+        );
+    }
+
+    #[test]
+    fn test_least_recently_run_calls_are_forgotten() {
+        let history = RunHistory::new(2);
+        let none = Map::new();
+        history.record("a", &none, "1", None);
+        history.record("b", &none, "1", None);
+        history.record("a", &none, "2", None);
+        history.record("c", &none, "1", None);
+
+        assert!(history.record("b", &none, "2", None).is_none());
+        assert!(history.record("c", &none, "2", None).is_some());
+    }
+
+    #[test]
+    fn test_change_summary() {
+        let previous = PreviousRun {
+            output: "src/a.rs: 2 warnings\nsrc/b.rs: ok\nsrc/c.rs: ok\n".to_string(),
+            exit_code: Some(1),
+            finished_at: Utc::now(),
+        };
+
+        let unchanged = change_summary(&previous, &previous.output, Some(1));
+        assert!(unchanged.starts_with("Output unchanged since the previous run at "));
+
+        let summary = change_summary(
+            &previous,
+            "src/a.rs: ok\nsrc/b.rs: ok\nsrc/c.rs: ok\n",
+            Some(0),
+        );
+        assert!(
+            summary.contains("(+1 -1 lines; exit code 1 → 0)"),
+            "{summary}"
+        );
+        assert!(summary.contains("--- previous\n+++ current\n"), "{summary}");
+        assert!(summary.contains("\n-src/a.rs: 2 warnings\n+src/a.rs: ok\n"));
+
+        let long: String = (0..1000).map(|i| format!("line {i}\n")).collect();
+        let summary = change_summary(&previous, &long, Some(1));
+        assert!(summary.contains("more diff lines"), "{summary}");
+    }
+}
@@ -0,0 +1,165 @@
+//! Compact tool schemas for token-constrained clients
+//!
+//! Every recipe's input schema carries the same boilerplate: a `$schema`
+//! URI, `"additionalProperties": false` and, for recipes without required
+//! parameters, empty `required` lists. On a registry of a few hundred
+//! recipes that adds up in each `tools/list` an LLM client reads. In
+//! [`SchemaMode::Compact`] those keywords are dropped on the way out. The
+//! result is still valid JSON Schema; the only loss is that clients no
+//! longer learn extra arguments are unwanted, and the executor ignores
+//! them anyway.
+//!
+//! `--schema-mode` sets the mode sessions start with, and
+//! `_admin_schema_mode` changes it for one session.
+
+use crate::error::{Error, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::str::FromStr;
+
+/// How tool schemas are sent in `tools/list`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaMode {
+    /// Schemas as generated
+    #[default]
+    Full,
+    /// Schemas without keywords that don't change which calls are valid
+    Compact,
+}
+
+impl FromStr for SchemaMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "full" => Ok(Self::Full),
+            "compact" => Ok(Self::Compact),
+            _ => Err(Error::InvalidParameter(format!(
+                "Unknown schema mode '{s}', expected full or compact"
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full => write!(f, "full"),
+            Self::Compact => write!(f, "compact"),
+        }
+    }
+}
+
+impl SchemaMode {
+    /// A schema as sent in this mode
+    pub fn apply(self, schema: &Value) -> Value {
+        match self {
+            Self::Full => schema.clone(),
+            Self::Compact => compact(schema),
+        }
+    }
+}
+
+/// Result of `_admin_schema_mode`
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaModeSelection {
+    /// Mode the session's `tools/list` responses now use
+    pub mode: SchemaMode,
+    /// Mode they used before
+    pub previous: SchemaMode,
+    /// Size of the session's tool schemas as generated, in bytes of JSON
+    pub full_bytes: usize,
+    /// Size of the same schemas in compact mode
+    pub compact_bytes: usize,
+}
+
+/// Drop `$schema`, `"additionalProperties": false` and empty `required` and
+/// `properties` from a schema and the schemas nested in it
+pub fn compact(schema: &Value) -> Value {
+    let Value::Object(object) = schema else {
+        return schema.clone();
+    };
+    object
+        .iter()
+        .filter(|(key, value)| match key.as_str() {
+            "$schema" => false,
+            "additionalProperties" => value != &&Value::Bool(false),
+            "required" => value.as_array().is_none_or(|r| !r.is_empty()),
+            "properties" => value.as_object().is_none_or(|p| !p.is_empty()),
+            _ => true,
+        })
+        .map(|(key, value)| {
+            let value = match key.as_str() {
+                // Maps from property names to schemas
+                "properties" | "definitions" | "$defs" => match value {
+                    Value::Object(schemas) => Value::Object(
+                        schemas
+                            .iter()
+                            .map(|(name, schema)| (name.clone(), compact(schema)))
+                            .collect(),
+                    ),
+                    other => other.clone(),
+                },
+                "items" | "additionalProperties" | "not" => compact(value),
+                "anyOf" | "oneOf" | "allOf" => match value {
+                    Value::Array(schemas) => Value::Array(schemas.iter().map(compact).collect()),
+                    other => other.clone(),
+                },
+                _ => value.clone(),
+            };
+            (key.clone(), value)
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_compact_schema() {
+        let schema = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": {
+                "target": {"type": "string", "default": "debug"},
+                "options": {
+                    "type": "object",
+                    "properties": {},
+                    "required": [],
+                    "additionalProperties": false,
+                },
+                "tags": {"type": "array", "items": {"$schema": "x", "type": "string"}},
+            },
+            "required": [],
+            "additionalProperties": false
+        });
+        let compacted = compact(&schema);
+        assert_eq!(
+            compacted,
+            json!({
+                "type": "object",
+                "properties": {
+                    "target": {"type": "string", "default": "debug"},
+                    "options": {"type": "object"},
+                    "tags": {"type": "array", "items": {"type": "string"}},
+                },
+            })
+        );
+        assert!(compacted.to_string().len() < schema.to_string().len());
+
+        // Keywords that change which arguments are valid stay
+        let strict = json!({
+            "type": "object",
+            "properties": {"name": {"type": "string"}},
+            "required": ["name"],
+            "additionalProperties": {"type": "string"},
+        });
+        assert_eq!(compact(&strict), strict);
+        assert_eq!(SchemaMode::Full.apply(&schema), schema);
+        assert!("tiny".parse::<SchemaMode>().is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! Build and runtime facts about the server, for bug reports
+//!
+//! Served as the `just://server-info` resource and added to the `_meta` of
+//! the initialize result under [`SERVER_INFO_META_KEY`], so a client can
+//! attach the version, commit, features, `just` version and parser
+//! configuration to a report without asking the user for them.
+
+use crate::just_binary::JustBinary;
+use crate::parser::ParserPreference;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// URI of the server info resource
+pub const SERVER_INFO_URI: &str = "just://server-info";
+
+/// Key of the server info in the `_meta` of the initialize result
+pub const SERVER_INFO_META_KEY: &str = "just-mcp/server-info";
+
+/// Cargo features a binary may be built with, in `Cargo.toml` order
+const FEATURES: &[(&str, bool)] = &[
+    ("stdio", cfg!(feature = "stdio")),
+    ("http", cfg!(feature = "http")),
+    ("watcher", cfg!(feature = "watcher")),
+    ("admin", cfg!(feature = "admin")),
+    ("vector-search", cfg!(feature = "vector-search")),
+    ("local-embeddings", cfg!(feature = "local-embeddings")),
+    ("ast-parser", cfg!(feature = "ast-parser")),
+    ("telemetry", cfg!(feature = "telemetry")),
+    ("self-update", cfg!(feature = "self-update")),
+    ("pty", cfg!(feature = "pty")),
+    ("client", cfg!(feature = "client")),
+    ("ultrafast-framework", cfg!(feature = "ultrafast-framework")),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerInfoReport {
+    pub version: String,
+    /// Commit the binary was built from; absent for builds outside git
+    pub git_hash: Option<String>,
+    /// `debug` or `release`
+    pub build_profile: String,
+    /// Operating system and architecture, e.g. `linux-x86_64`
+    pub target: String,
+    /// Cargo features the binary was built with
+    pub features: Vec<String>,
+    /// The `just` binary recipes run with
+    pub just: JustBinary,
+    /// Parser used for justfiles without an override
+    pub parser: String,
+    /// Parser per watch directory, where overridden
+    pub parser_overrides: BTreeMap<String, String>,
+}
+
+impl ServerInfoReport {
+    /// Collect the report for a server with the given parser configuration
+    pub fn collect(parser: &ParserPreference, overrides: &[(PathBuf, ParserPreference)]) -> Self {
+        Self {
+            version: crate::VERSION.to_string(),
+            git_hash: crate::GIT_HASH.map(str::to_string),
+            build_profile: if cfg!(debug_assertions) {
+                "debug"
+            } else {
+                "release"
+            }
+            .to_string(),
+            target: format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+            features: enabled_features().into_iter().map(str::to_string).collect(),
+            just: JustBinary::detect(),
+            parser: parser.to_string(),
+            parser_overrides: overrides
+                .iter()
+                .map(|(path, parser)| (path.display().to_string(), parser.to_string()))
+                .collect(),
+        }
+    }
+}
+
+/// Cargo features this binary was built with
+pub fn enabled_features() -> Vec<&'static str> {
+    FEATURES
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_server_info() {
+        let overrides = [(PathBuf::from("/app"), ParserPreference::Cli)];
+        let report = ServerInfoReport::collect(&ParserPreference::Auto, &overrides);
+        assert_eq!(report.version, crate::VERSION);
+        assert_eq!(report.parser, "auto");
+        assert_eq!(report.parser_overrides["/app"], "cli");
+        // Built as part of the server, which needs the framework
+        assert!(report.features.contains(&"ultrafast-framework".to_string()));
+        assert_eq!(
+            report.features.contains(&"ast-parser".to_string()),
+            cfg!(feature = "ast-parser")
+        );
+    }
+}
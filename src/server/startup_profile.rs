@@ -0,0 +1,179 @@
+//! Startup timing for cold-start profiling
+//!
+//! The server records how long each startup phase takes, and how long each
+//! justfile takes to parse, so users of large monorepos can see what to
+//! exclude or cache. With `--startup-profile` the report is printed to stderr
+//! once the initial scan is done; the numbers are always emitted as
+//! structured events under the `just_mcp::metrics` tracing target.
+
+use crate::METRICS_TARGET;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Duration of one startup phase
+#[derive(Debug, Clone)]
+pub struct PhaseTiming {
+    pub name: &'static str,
+    pub duration: Duration,
+}
+
+/// Time taken to parse one justfile during the initial scan
+#[derive(Debug, Clone)]
+pub struct FileTiming {
+    pub path: PathBuf,
+    pub duration: Duration,
+    pub success: bool,
+}
+
+/// Startup phase and per-file timings
+#[derive(Debug, Clone)]
+pub struct StartupProfile {
+    started: Instant,
+    phases: Vec<PhaseTiming>,
+    files: Vec<FileTiming>,
+    finished: Option<Duration>,
+}
+
+impl StartupProfile {
+    /// Start profiling from now
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            phases: Vec::new(),
+            files: Vec::new(),
+            finished: None,
+        }
+    }
+
+    /// Record a phase that started at `start`
+    pub fn record_phase(&mut self, name: &'static str, start: Instant) {
+        self.phases.push(PhaseTiming {
+            name,
+            duration: start.elapsed(),
+        });
+    }
+
+    /// Record a phase that began when profiling started
+    pub fn record_phase_since_start(&mut self, name: &'static str) {
+        self.record_phase(name, self.started);
+    }
+
+    /// Record the parse of a single justfile that started at `start`
+    pub fn record_file(&mut self, path: &Path, start: Instant, success: bool) {
+        self.files.push(FileTiming {
+            path: path.to_path_buf(),
+            duration: start.elapsed(),
+            success,
+        });
+    }
+
+    /// Mark startup as complete, fixing the total duration
+    pub fn finish(&mut self) {
+        self.finished = Some(self.started.elapsed());
+    }
+
+    pub fn phases(&self) -> &[PhaseTiming] {
+        &self.phases
+    }
+
+    pub fn files(&self) -> &[FileTiming] {
+        &self.files
+    }
+
+    /// Time from server creation to the end of startup (or now, if still running)
+    pub fn total(&self) -> Duration {
+        self.finished.unwrap_or_else(|| self.started.elapsed())
+    }
+
+    /// Human-readable report, with the slowest justfiles first
+    pub fn report(&self) -> String {
+        let mut report = format!("Startup profile ({})\n", format_duration(self.total()));
+        for phase in &self.phases {
+            report.push_str(&format!(
+                "  {:<24} {:>10}\n",
+                phase.name,
+                format_duration(phase.duration)
+            ));
+        }
+
+        if !self.files.is_empty() {
+            let mut files = self.files.clone();
+            files.sort_by(|a, b| b.duration.cmp(&a.duration));
+            report.push_str(&format!("Justfiles parsed ({}):\n", files.len()));
+            for file in &files {
+                report.push_str(&format!(
+                    "  {:>10}  {}{}\n",
+                    format_duration(file.duration),
+                    file.path.display(),
+                    if file.success { "" } else { " (failed)" }
+                ));
+            }
+        }
+        report
+    }
+
+    /// Emit every timing as a structured event under [`METRICS_TARGET`]
+    pub fn emit_metrics(&self) {
+        for phase in &self.phases {
+            tracing::info!(
+                target: METRICS_TARGET,
+                phase = phase.name,
+                duration_ms = phase.duration.as_secs_f64() * 1000.0,
+                "startup phase"
+            );
+        }
+        for file in &self.files {
+            tracing::info!(
+                target: METRICS_TARGET,
+                justfile = %file.path.display(),
+                duration_ms = file.duration.as_secs_f64() * 1000.0,
+                success = file.success,
+                "startup parse"
+            );
+        }
+        tracing::info!(
+            target: METRICS_TARGET,
+            duration_ms = self.total().as_secs_f64() * 1000.0,
+            justfiles = self.files.len(),
+            "startup complete"
+        );
+    }
+}
+
+impl Default for StartupProfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_orders_files_by_duration() {
+        let mut profile = StartupProfile::new();
+        let earlier = Instant::now() - Duration::from_millis(50);
+        profile.record_phase("discovery", Instant::now());
+        profile.record_file(Path::new("/repo/fast/justfile"), Instant::now(), true);
+        profile.record_file(Path::new("/repo/slow/justfile"), earlier, false);
+        profile.finish();
+
+        let report = profile.report();
+        assert!(report.starts_with("Startup profile ("));
+        assert!(report.contains("  discovery "));
+        assert!(report.contains("Justfiles parsed (2):"));
+        let slow = report.find("/repo/slow/justfile (failed)").unwrap();
+        let fast = report.find("/repo/fast/justfile").unwrap();
+        assert!(slow < fast);
+
+        // The total is fixed once startup finishes
+        let total = profile.total();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(profile.total(), total);
+    }
+}
@@ -0,0 +1,417 @@
+//! Transport wrapper that adds structured content to tool results
+//!
+//! Tools with an output schema (see [`crate::admin::schemas`]) answer with
+//! their result serialized as JSON in a text block. MCP clients expect the
+//! same value as `structuredContent` next to the content blocks, but the
+//! framework's tool result type has no such field. [`StructuredContentTransport`]
+//! pairs each `tools/call` request for such a tool with its response and
+//! parses the text block into `structuredContent` on the way out. The text
+//! block stays, for clients that only read content.
+//!
+//! The framework's initialize result has no room for extra data either, so
+//! the server's build and runtime facts (see [`super::server_info`]) are
+//! added to its `_meta` here as well.
+//!
+//! Failed tool calls are classified (see [`FailureClass`]), but neither the
+//! framework's tool result nor its error carries metadata. The handler
+//! attaches the class as a final text block or error message line, which
+//! is moved into the result's `_meta` or the error's `data` here, under
+//! [`super::error_adapter::FAILURE_META_KEY`].
+//!
+//! With parameter memory (see [`super::parameter_memory`]), a call that
+//! changes a remembered value changes the defaults `tools/list` reports, so
+//! its response is followed by `notifications/tools/list_changed`.
+
+use super::error_adapter::FailureClass;
+use super::parameter_memory::ParameterMemory;
+use super::server_info::SERVER_INFO_META_KEY;
+use crate::admin::schemas::output_schema;
+use async_trait::async_trait;
+use std::sync::Arc;
+use ultrafast_mcp::protocol::{JsonRpcMessage, JsonRpcRequest, RequestId};
+use ultrafast_mcp_transport::{ConnectionState, Result, Transport};
+
+/// Transport that returns structured results as `structuredContent`
+pub struct StructuredContentTransport {
+    inner: Box<dyn Transport>,
+    /// `tools/call` requests awaiting their response, and whether the tool
+    /// has an output schema
+    pending: Vec<(RequestId, bool)>,
+    /// Server info added to the `_meta` of the initialize result
+    initialize_meta: Option<serde_json::Value>,
+    /// The `initialize` request, awaiting its response
+    pending_initialize: Option<RequestId>,
+    /// The session's parameter memory, and the revision clients last heard of
+    parameter_memory: Option<(Arc<ParameterMemory>, u64)>,
+}
+
+impl StructuredContentTransport {
+    pub fn new(inner: Box<dyn Transport>) -> Self {
+        Self {
+            inner,
+            pending: Vec::new(),
+            initialize_meta: None,
+            pending_initialize: None,
+            parameter_memory: None,
+        }
+    }
+
+    /// Announce tool list changes when `memory` remembers new values
+    pub fn with_parameter_memory(mut self, memory: Arc<ParameterMemory>) -> Self {
+        let revision = memory.revision();
+        self.parameter_memory = Some((memory, revision));
+        self
+    }
+
+    /// Whether remembered values changed since clients were last told
+    fn memory_changed(&mut self) -> bool {
+        let Some((memory, seen)) = &mut self.parameter_memory else {
+            return false;
+        };
+        let revision = memory.revision();
+        std::mem::replace(seen, revision) != revision
+    }
+
+    /// Add `server_info` to the initialize result's `_meta`
+    pub fn with_server_info(mut self, server_info: serde_json::Value) -> Self {
+        self.initialize_meta = Some(server_info);
+        self
+    }
+
+    fn observe_request(&mut self, message: &JsonRpcMessage) {
+        let JsonRpcMessage::Request(request) = message else {
+            return;
+        };
+        let Some(id) = &request.id else {
+            return;
+        };
+        if request.method == "initialize" {
+            self.pending_initialize = Some(id.clone());
+            return;
+        }
+        let tool = request
+            .params
+            .as_ref()
+            .and_then(|params| params.get("name"))
+            .and_then(|name| name.as_str());
+        if request.method == "tools/call" {
+            let structured = tool.and_then(output_schema).is_some();
+            self.pending.push((id.clone(), structured));
+        }
+    }
+
+    fn add_initialize_meta(&mut self, message: &mut JsonRpcMessage) {
+        let JsonRpcMessage::Response(response) = message else {
+            return;
+        };
+        if self.pending_initialize.is_none() || self.pending_initialize != response.id {
+            return;
+        }
+        self.pending_initialize = None;
+        let Some(server_info) = &self.initialize_meta else {
+            return;
+        };
+        let Some(result) = response.result.as_mut().and_then(|r| r.as_object_mut()) else {
+            return;
+        };
+        let meta = result
+            .entry("_meta")
+            .or_insert_with(|| serde_json::json!({}));
+        if let Some(meta) = meta.as_object_mut() {
+            meta.insert(SERVER_INFO_META_KEY.to_string(), server_info.clone());
+        }
+    }
+
+    /// Move the failure class and structured result of a `tools/call`
+    /// response into place, returning whether it was one
+    fn finish_tool_call(&mut self, message: &mut JsonRpcMessage) -> bool {
+        let JsonRpcMessage::Response(response) = message else {
+            return false;
+        };
+        let Some(position) = self
+            .pending
+            .iter()
+            .position(|(id, _)| Some(id) == response.id.as_ref())
+        else {
+            return false;
+        };
+        let (_, structured) = self.pending.remove(position);
+
+        if let Some(error) = response.error.as_mut() {
+            let class = error
+                .message
+                .rsplit_once('\n')
+                .and_then(|(message, envelope)| {
+                    Some((message.len(), FailureClass::from_envelope(envelope)?))
+                });
+            if let Some((len, class)) = class {
+                error.message.truncate(len);
+                error.data = Some(class.to_meta());
+            }
+        }
+        let Some(result) = response.result.as_mut().and_then(|r| r.as_object_mut()) else {
+            return true;
+        };
+        let class = result
+            .get("content")
+            .and_then(|content| content.as_array())
+            .and_then(|content| content.last())
+            .and_then(|block| block.get("text"))
+            .and_then(|text| text.as_str())
+            .and_then(FailureClass::from_envelope);
+        if let Some(class) = class {
+            if let Some(content) = result.get_mut("content").and_then(|c| c.as_array_mut()) {
+                content.pop();
+            }
+            let meta = result
+                .entry("_meta")
+                .or_insert_with(|| serde_json::json!({}));
+            if let (Some(meta), Some(class)) = (meta.as_object_mut(), class.to_meta().as_object()) {
+                meta.extend(class.clone());
+            }
+        }
+        if !structured || result.get("isError").and_then(|v| v.as_bool()) == Some(true) {
+            return true;
+        }
+        let structured = result
+            .get("content")
+            .and_then(|content| content.get(0))
+            .and_then(|block| block.get("text"))
+            .and_then(|text| text.as_str())
+            .and_then(|text| serde_json::from_str::<serde_json::Value>(text).ok());
+        match structured {
+            Some(structured) => {
+                result.insert("structuredContent".to_string(), structured);
+            }
+            None => tracing::warn!("Tool result is not the JSON its output schema describes"),
+        }
+        true
+    }
+}
+
+#[async_trait]
+impl Transport for StructuredContentTransport {
+    async fn send_message(&mut self, mut message: JsonRpcMessage) -> Result<()> {
+        self.add_initialize_meta(&mut message);
+        let tool_call = self.finish_tool_call(&mut message);
+        self.inner.send_message(message).await?;
+        if tool_call && self.memory_changed() {
+            let notification =
+                JsonRpcRequest::notification("notifications/tools/list_changed".to_string(), None);
+            self.inner
+                .send_message(JsonRpcMessage::Notification(notification))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn receive_message(&mut self) -> Result<JsonRpcMessage> {
+        let message = self.inner.receive_message().await?;
+        self.observe_request(&message);
+        Ok(message)
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.inner.close().await
+    }
+
+    fn get_state(&self) -> ConnectionState {
+        self.inner.get_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    use crate::server::batch_transport::BatchTransport;
+    use crate::server::error_adapter::FAILURE_META_KEY;
+    use serde_json::json;
+    use tokio::io::AsyncReadExt;
+    use ultrafast_mcp::protocol::{JsonRpcError, JsonRpcResponse};
+
+    const CLIENT: &str = concat!(
+        r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"_admin_gc","arguments":{}}}"#,
+        "\n",
+        r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"build","arguments":{}}}"#,
+        "\n",
+        r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"_admin_sync","arguments":{}}}"#,
+        "\n",
+    );
+
+    #[tokio::test]
+    async fn test_structured_results_get_structured_content() {
+        let (writer, mut output) = tokio::io::duplex(64 * 1024);
+        let mut transport = StructuredContentTransport::new(Box::new(BatchTransport::new(
+            CLIENT.as_bytes(),
+            writer,
+        )));
+        for _ in 0..3 {
+            transport.receive_message().await.unwrap();
+        }
+
+        let report = r#"{"dry_run": true}"#;
+        for (id, result) in [
+            (
+                3,
+                json!({"content": [{"type": "text", "text": "failed"}], "isError": true}),
+            ),
+            (2, json!({"content": [{"type": "text", "text": report}]})),
+            (1, json!({"content": [{"type": "text", "text": report}]})),
+        ] {
+            let response = JsonRpcResponse::success(result, Some(RequestId::Number(id)));
+            transport
+                .send_message(JsonRpcMessage::Response(response))
+                .await
+                .unwrap();
+        }
+        drop(transport);
+
+        let mut written = String::new();
+        output.read_to_string(&mut written).await.unwrap();
+        let responses: Vec<serde_json::Value> = written
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        // Errors and tools without an output schema are left alone
+        assert!(responses[0]["result"].get("structuredContent").is_none());
+        assert!(responses[0]["result"].get("_meta").is_none());
+        assert!(responses[1]["result"].get("structuredContent").is_none());
+        assert_eq!(
+            responses[2]["result"]["structuredContent"],
+            json!({"dry_run": true})
+        );
+        assert_eq!(responses[2]["result"]["content"][0]["text"], report);
+    }
+
+    #[tokio::test]
+    async fn test_initialize_result_gets_server_info() {
+        let client = concat!(
+            r#"{"jsonrpc":"2.0","id":"init","method":"initialize","params":{}}"#,
+            "\n",
+        );
+        let (writer, mut output) = tokio::io::duplex(64 * 1024);
+        let mut transport = StructuredContentTransport::new(Box::new(BatchTransport::new(
+            client.as_bytes(),
+            writer,
+        )))
+        .with_server_info(json!({"version": "1.2.3"}));
+        transport.receive_message().await.unwrap();
+
+        for id in ["other", "init"] {
+            let result = json!({"protocolVersion": "2025-06-18", "capabilities": {}});
+            let response =
+                JsonRpcResponse::success(result, Some(RequestId::String(id.to_string())));
+            transport
+                .send_message(JsonRpcMessage::Response(response))
+                .await
+                .unwrap();
+        }
+        drop(transport);
+
+        let mut written = String::new();
+        output.read_to_string(&mut written).await.unwrap();
+        let responses: Vec<serde_json::Value> = written
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert!(responses[0]["result"].get("_meta").is_none());
+        assert_eq!(
+            responses[1]["result"]["_meta"][SERVER_INFO_META_KEY]["version"],
+            "1.2.3"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failure_class_moves_to_meta_and_error_data() {
+        let (writer, mut output) = tokio::io::duplex(64 * 1024);
+        let mut transport = StructuredContentTransport::new(Box::new(BatchTransport::new(
+            CLIENT.as_bytes(),
+            writer,
+        )));
+        for _ in 0..3 {
+            transport.receive_message().await.unwrap();
+        }
+
+        let class = FailureClass::from(ErrorKind::Timeout);
+        let result = json!({
+            "content": [
+                {"type": "text", "text": "Command timed out"},
+                {"type": "text", "text": class.to_envelope()},
+            ],
+            "isError": true,
+        });
+        let error = JsonRpcError::new(
+            -32602,
+            format!(
+                "Unknown tool\n{}",
+                FailureClass::from(ErrorKind::UserError).to_envelope()
+            ),
+        );
+        for response in [
+            JsonRpcResponse::success(result, Some(RequestId::Number(2))),
+            JsonRpcResponse::error(error, Some(RequestId::Number(3))),
+        ] {
+            transport
+                .send_message(JsonRpcMessage::Response(response))
+                .await
+                .unwrap();
+        }
+        drop(transport);
+
+        let mut written = String::new();
+        output.read_to_string(&mut written).await.unwrap();
+        let responses: Vec<serde_json::Value> = written
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let result = &responses[0]["result"];
+        assert_eq!(result["content"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            result["_meta"][FAILURE_META_KEY],
+            json!({"error_kind": "timeout", "retry_advisable": true})
+        );
+        let error = &responses[1]["error"];
+        assert_eq!(error["message"], "Unknown tool");
+        assert_eq!(
+            error["data"][FAILURE_META_KEY],
+            json!({"error_kind": "user_error", "retry_advisable": false})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_changed_parameter_memory_announces_tool_list_change() {
+        let (writer, mut output) = tokio::io::duplex(64 * 1024);
+        let memory = Arc::new(ParameterMemory::new());
+        let mut transport = StructuredContentTransport::new(Box::new(BatchTransport::new(
+            CLIENT.as_bytes(),
+            writer,
+        )))
+        .with_parameter_memory(memory.clone());
+        for _ in 0..3 {
+            transport.receive_message().await.unwrap();
+        }
+
+        for (id, env) in [(2, "staging"), (3, "staging")] {
+            memory.record(json!({ "env": env }).as_object().unwrap());
+            let result = json!({"content": [{"type": "text", "text": "ok"}]});
+            let response = JsonRpcResponse::success(result, Some(RequestId::Number(id)));
+            transport
+                .send_message(JsonRpcMessage::Response(response))
+                .await
+                .unwrap();
+        }
+        drop(transport);
+
+        let mut written = String::new();
+        output.read_to_string(&mut written).await.unwrap();
+        let messages: Vec<serde_json::Value> = written
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0]["id"], 2);
+        assert_eq!(messages[1]["method"], "notifications/tools/list_changed");
+        assert_eq!(messages[2]["id"], 3);
+    }
+}
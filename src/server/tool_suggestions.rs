@@ -0,0 +1,117 @@
+//! Close matches for calls to tools that don't exist
+//!
+//! Calls to missing tools are usually near misses: a typo, a recipe renamed
+//! since the client last listed tools, or a project suffix left off
+//! (`build` when only `build@api` and `build@web` exist). The error for such
+//! a call names the closest tools, by edit distance ignoring case, so the
+//! client can retry without listing every tool again.
+
+/// Suggestions offered at most
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Tools whose names are close to `name`, closest first
+pub fn close_matches<'a>(name: &str, tools: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let name = name.to_lowercase();
+    let (recipe, project) = split_project(&name);
+    // Allow about one edit per three characters, and at least two
+    let max_distance = (name.chars().count() / 3).max(2);
+
+    let mut matches: Vec<(usize, &str)> = tools
+        .into_iter()
+        .filter_map(|tool| {
+            let lower = tool.to_lowercase();
+            let distance = edit_distance(&name, &lower);
+            // A project suffix left off is free, a wrong one counts as one edit
+            let recipe_distance =
+                edit_distance(recipe, split_project(&lower).0) + usize::from(project.is_some());
+            let distance = distance.min(recipe_distance);
+            (distance <= max_distance).then_some((distance, tool))
+        })
+        .collect();
+    matches.sort();
+    matches
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, tool)| tool)
+        .collect()
+}
+
+/// Error message for a call to `name`, which is not a tool
+pub fn unknown_tool_message<'a>(name: &str, tools: impl IntoIterator<Item = &'a str>) -> String {
+    let tools: Vec<&str> = tools.into_iter().collect();
+    let matches = close_matches(name, tools.iter().copied());
+    let mut message = if matches.is_empty() {
+        format!(
+            "Tool '{name}' not found; {} tools are available in tools/list.",
+            tools.len()
+        )
+    } else {
+        format!(
+            "Tool '{name}' not found. Did you mean: {}?",
+            matches.join(", ")
+        )
+    };
+    if name.contains('@') || tools.iter().any(|tool| tool.contains('@')) {
+        message.push_str(
+            " Recipes of named watch directories are called as task@project, e.g. build@api.",
+        );
+    }
+    message
+}
+
+/// Split `task@project` into the task and the project, if any
+fn split_project(name: &str) -> (&str, Option<&str>) {
+    match name.split_once('@') {
+        Some((recipe, project)) => (recipe, Some(project)),
+        None => (name, None),
+    }
+}
+
+/// Levenshtein distance between two strings, by character
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_close_matches() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+
+        let tools = ["build@api", "build@web", "test", "lint", "deploy_prod"];
+        assert_eq!(
+            close_matches("biuld@api", tools),
+            ["build@api", "build@web"]
+        );
+        assert_eq!(close_matches("build", tools), ["build@api", "build@web"]);
+        assert_eq!(close_matches("TEST", tools), ["test"]);
+        assert_eq!(close_matches("deploy-prod", tools), ["deploy_prod"]);
+        assert!(close_matches("release", tools).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_tool_message() {
+        let message = unknown_tool_message("buidl", ["build@api", "test"]);
+        assert!(message.contains("Did you mean: build@api?"), "{message}");
+        assert!(message.contains("task@project"));
+
+        let message = unknown_tool_message("release", ["test"]);
+        assert_eq!(
+            message,
+            "Tool 'release' not found; 1 tools are available in tools/list."
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Transports the server listens on
+//!
+//! `--transport stdio,http:8900` serves a local client over stdio and remote
+//! clients over Streamable HTTP from one process. Every transport shares the
+//! registry, executor, watcher and admin tools, so a recipe edit made through
+//! one is visible through the others, and resource limits apply across all
+//! of them. Each transport gets its own MCP server instance, with its own
+//! protocol state, remembered parameters, run history and stored outputs.
+
+use crate::error::{Error, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Host HTTP transports bind to when none is given
+pub const DEFAULT_HTTP_HOST: &str = "127.0.0.1";
+
+/// Hosts on which the framework accepts HTTP requests without an `Origin`
+/// header; on any other address clients must send one it allows
+pub const HEADERLESS_HTTP_HOSTS: &[&str] = &["127.0.0.1", "localhost", "0.0.0.0"];
+
+/// A transport to serve MCP on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransportSpec {
+    /// JSON-RPC over stdin and stdout
+    Stdio,
+    /// Streamable HTTP on `host:port`, at the `/mcp` endpoint
+    Http { host: String, port: u16 },
+}
+
+impl FromStr for TransportSpec {
+    type Err = Error;
+
+    /// Parse `stdio`, `http:PORT` or `http:HOST:PORT`
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            Error::InvalidParameter(format!(
+                "Invalid transport '{s}', expected stdio, http:PORT or http:HOST:PORT"
+            ))
+        };
+        if s == "stdio" {
+            return Ok(Self::Stdio);
+        }
+        let address = s.strip_prefix("http:").ok_or_else(invalid)?;
+        let (host, port) = match address.rsplit_once(':') {
+            Some((host, port)) if !host.is_empty() => (host, port),
+            Some(_) => return Err(invalid()),
+            None => (DEFAULT_HTTP_HOST, address),
+        };
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let port = port.parse().map_err(|_| invalid())?;
+        Ok(Self::Http {
+            host: host.to_string(),
+            port,
+        })
+    }
+}
+
+impl TransportSpec {
+    /// Origin of an HTTP transport's own address, `http://host:port`
+    pub fn origin(&self) -> Option<String> {
+        match self {
+            Self::Stdio => None,
+            Self::Http { host, port } if host.contains(':') => {
+                Some(format!("http://[{host}]:{port}"))
+            }
+            Self::Http { host, port } => Some(format!("http://{host}:{port}")),
+        }
+    }
+
+    /// Whether clients of this transport must send an `Origin` header
+    pub fn requires_origin(&self) -> bool {
+        match self {
+            Self::Stdio => false,
+            Self::Http { host, .. } => !HEADERLESS_HTTP_HOSTS.contains(&host.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for TransportSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdio => write!(f, "stdio"),
+            Self::Http { host, port } if host.contains(':') => write!(f, "http:[{host}]:{port}"),
+            Self::Http { host, port } => write!(f, "http:{host}:{port}"),
+        }
+    }
+}
+
+/// Check a transport list given to `--transport`
+///
+/// Each transport may be given once; HTTP transports must use distinct
+/// addresses.
+pub fn validate_transports(transports: &[TransportSpec]) -> Result<()> {
+    if transports.is_empty() {
+        return Err(Error::InvalidParameter(
+            "At least one transport is required".to_string(),
+        ));
+    }
+    for (i, transport) in transports.iter().enumerate() {
+        if transports[..i].contains(transport) {
+            return Err(Error::InvalidParameter(format!(
+                "Transport '{transport}' is listed more than once"
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parse a comma-separated list, as clap does for `--transport`
+    fn parse_transports(list: &str) -> Result<Vec<TransportSpec>> {
+        let transports = list
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<TransportSpec>>>()?;
+        validate_transports(&transports)?;
+        Ok(transports)
+    }
+
+    #[test]
+    fn test_parse_transports() {
+        assert_eq!(
+            parse_transports("stdio,http:8900").unwrap(),
+            vec![
+                TransportSpec::Stdio,
+                TransportSpec::Http {
+                    host: "127.0.0.1".to_string(),
+                    port: 8900
+                }
+            ]
+        );
+        assert_eq!(
+            parse_transports("http:0.0.0.0:8900,http:[::1]:8901").unwrap(),
+            vec![
+                TransportSpec::Http {
+                    host: "0.0.0.0".to_string(),
+                    port: 8900
+                },
+                TransportSpec::Http {
+                    host: "::1".to_string(),
+                    port: 8901
+                }
+            ]
+        );
+
+        assert!(parse_transports("").is_err());
+        assert!(parse_transports("stdio,stdio").is_err());
+        assert!(parse_transports("http:8900,http:127.0.0.1:8900").is_err());
+        assert!(parse_transports("http").is_err());
+        assert!(parse_transports("http:99999").is_err());
+        assert!(parse_transports("http::8900").is_err());
+        assert!(parse_transports("sse:8900").is_err());
+    }
+
+    #[test]
+    fn test_transport_origin() {
+        let origin = |spec: &str| spec.parse::<TransportSpec>().unwrap().origin();
+        assert_eq!(origin("stdio"), None);
+        assert_eq!(
+            origin("http:127.0.0.2:8900").as_deref(),
+            Some("http://127.0.0.2:8900")
+        );
+        assert_eq!(
+            origin("http:[::1]:8901").as_deref(),
+            Some("http://[::1]:8901")
+        );
+
+        let requires = |spec: &str| spec.parse::<TransportSpec>().unwrap().requires_origin();
+        assert!(!requires("http:8900"));
+        assert!(!requires("http:0.0.0.0:8900"));
+        assert!(requires("http:127.0.0.2:8900"));
+        assert!(requires("http:[::1]:8901"));
+    }
+
+    #[test]
+    fn test_transport_display_round_trips() {
+        for spec in ["stdio", "http:127.0.0.1:8900", "http:[::1]:8901"] {
+            let transport: TransportSpec = spec.parse().unwrap();
+            assert_eq!(transport.to_string(), spec);
+        }
+    }
+}
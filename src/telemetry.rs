@@ -0,0 +1,219 @@
+//! Opt-in anonymous usage statistics
+//!
+//! Nothing is collected or sent unless the server is started with
+//! `--telemetry` and a `--telemetry-endpoint`. When enabled, one report is
+//! sent after the initial justfile scan. It holds only aggregate, anonymous
+//! numbers that help maintainers decide which parser failures to fix first:
+//! the just-mcp version, platform, compiled features, bucketed tool and
+//! justfile counts, and parser success counts. It carries no paths, recipe
+//! names, arguments, hostnames or installation identifiers.
+//!
+//! The exact payload is logged before it is sent.
+
+use crate::error::{Error, Result};
+use crate::parser::ParsingMetrics;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Version of the report format
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// How long sending a report may take before it is abandoned
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where to send reports; its presence is the user's consent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelemetryConfig {
+    pub endpoint: String,
+}
+
+/// Parser attempts and successes since startup
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct ParserStats {
+    pub ast_attempts: u64,
+    pub ast_successes: u64,
+    pub cli_attempts: u64,
+    pub cli_successes: u64,
+    /// Files no parser could read, which fell back to a placeholder task
+    pub minimal_fallbacks: u64,
+}
+
+impl From<&ParsingMetrics> for ParserStats {
+    fn from(metrics: &ParsingMetrics) -> Self {
+        Self {
+            ast_attempts: metrics.ast_attempts,
+            ast_successes: metrics.ast_successes,
+            cli_attempts: metrics.command_attempts,
+            cli_successes: metrics.command_successes,
+            minimal_fallbacks: metrics.minimal_task_creations,
+        }
+    }
+}
+
+/// Anonymous report sent to the telemetry endpoint
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct TelemetryReport {
+    pub schema_version: u32,
+    pub version: &'static str,
+    pub os: &'static str,
+    pub arch: &'static str,
+    /// Cargo features just-mcp was built with
+    pub features: Vec<&'static str>,
+    /// Bucketed number of recipe tools, e.g. `11-50`
+    pub tools: &'static str,
+    /// Bucketed number of justfiles found at startup
+    pub justfiles: &'static str,
+    pub parser: ParserStats,
+}
+
+impl TelemetryReport {
+    pub fn new(tool_count: usize, justfile_count: usize, metrics: &ParsingMetrics) -> Self {
+        Self {
+            schema_version: REPORT_SCHEMA_VERSION,
+            version: crate::VERSION,
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            features: enabled_features(),
+            tools: bucket(tool_count),
+            justfiles: bucket(justfile_count),
+            parser: ParserStats::from(metrics),
+        }
+    }
+}
+
+/// Coarse range a count falls into, so exact sizes aren't reported
+pub fn bucket(count: usize) -> &'static str {
+    match count {
+        0 => "0",
+        1..=10 => "1-10",
+        11..=50 => "11-50",
+        51..=200 => "51-200",
+        _ => "200+",
+    }
+}
+
+fn enabled_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    if cfg!(feature = "ast-parser") {
+        features.push("ast-parser");
+    }
+    if cfg!(feature = "watcher") {
+        features.push("watcher");
+    }
+    if cfg!(feature = "admin") {
+        features.push("admin");
+    }
+    if cfg!(feature = "vector-search") {
+        features.push("vector-search");
+    }
+    if cfg!(feature = "local-embeddings") {
+        features.push("local-embeddings");
+    }
+    if cfg!(feature = "http") {
+        features.push("http");
+    }
+    features
+}
+
+/// Send a report as JSON to the configured endpoint
+pub async fn send(config: &TelemetryConfig, report: &TelemetryReport) -> Result<()> {
+    tracing::info!(
+        "Sending anonymous telemetry to {}: {}",
+        config.endpoint,
+        serde_json::to_string(report)?
+    );
+
+    let response = reqwest::Client::new()
+        .post(&config.endpoint)
+        .timeout(SEND_TIMEOUT)
+        .json(report)
+        .send()
+        .await
+        .map_err(|e| Error::Other(format!("Failed to send telemetry: {e}")))?;
+    if !response.status().is_success() {
+        return Err(Error::Other(format!(
+            "Telemetry endpoint returned {}",
+            response.status()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_is_anonymous_and_bucketed() {
+        let metrics = ParsingMetrics {
+            ast_attempts: 12,
+            ast_successes: 11,
+            command_attempts: 1,
+            command_successes: 1,
+            ..Default::default()
+        };
+        let report = TelemetryReport::new(37, 3, &metrics);
+        assert_eq!(report.tools, "11-50");
+        assert_eq!(report.justfiles, "1-10");
+        assert_eq!(report.parser.ast_successes, 11);
+
+        let json = serde_json::to_value(&report).unwrap();
+        let mut fields: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        fields.sort();
+        assert_eq!(
+            fields,
+            vec![
+                "arch",
+                "features",
+                "justfiles",
+                "os",
+                "parser",
+                "schema_version",
+                "tools",
+                "version"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_telemetry_requires_flag_and_endpoint() {
+        use clap::Parser;
+        let parse = |args: &[&str]| crate::cli::Args::try_parse_from(args);
+
+        assert_eq!(parse(&["just-mcp"]).unwrap().telemetry_config(), None);
+        assert_eq!(
+            parse(&["just-mcp", "--telemetry-endpoint", "https://example.com"])
+                .unwrap()
+                .telemetry_config(),
+            None
+        );
+        assert!(parse(&["just-mcp", "--telemetry"]).is_err());
+        assert_eq!(
+            parse(&[
+                "just-mcp",
+                "--telemetry",
+                "--telemetry-endpoint",
+                "https://example.com"
+            ])
+            .unwrap()
+            .telemetry_config(),
+            Some(TelemetryConfig {
+                endpoint: "https://example.com".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_bucket_boundaries() {
+        assert_eq!(bucket(0), "0");
+        assert_eq!(bucket(10), "1-10");
+        assert_eq!(bucket(11), "11-50");
+        assert_eq!(bucket(200), "51-200");
+        assert_eq!(bucket(201), "200+");
+    }
+}
@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 // use uuid::Uuid;
 
+/// URI prefix of recipe summaries, shared by the server and the client
+pub const SUMMARY_URI_PREFIX: &str = "just://summary/";
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ToolDefinition {
     pub name: String,
@@ -17,6 +20,53 @@ pub struct ToolDefinition {
     // Internal name used for execution (includes full path)
     #[serde(skip)]
     pub internal_name: Option<String>,
+    /// Extended metadata that does not fit the MCP tool description
+    #[serde(default, skip_serializing_if = "ToolMetadata::is_empty")]
+    pub metadata: ToolMetadata,
+}
+
+/// Extended tool metadata carried alongside the short description
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ToolMetadata {
+    /// Full recipe documentation with line breaks preserved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long_description: Option<String>,
+    /// The recipe has `[no-cd]` and runs in the invocation directory rather
+    /// than the justfile's directory
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_cd: bool,
+    /// Recipe groups from `[group('name')]`, in attribute order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
+    /// Position of the recipe in its justfile, counting from zero, as
+    /// `just --list --unsorted` orders recipes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_order: Option<usize>,
+}
+
+impl ToolDefinition {
+    /// Whether clients would see a difference between the two definitions
+    ///
+    /// Bookkeeping that isn't published is ignored: the source hash, the
+    /// modification time, the internal name and the recipe's position.
+    pub fn differs_for_clients(&self, other: &ToolDefinition) -> bool {
+        self.name != other.name
+            || self.description != other.description
+            || self.input_schema != other.input_schema
+            || self.dependencies != other.dependencies
+            || self.metadata.long_description != other.metadata.long_description
+            || self.metadata.no_cd != other.metadata.no_cd
+            || self.metadata.groups != other.metadata.groups
+    }
+}
+
+impl ToolMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.long_description.is_none()
+            && !self.no_cd
+            && self.groups.is_empty()
+            && self.source_order.is_none()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -40,6 +90,88 @@ pub struct JustTask {
     pub attributes: Vec<crate::parser::ast::queries::AttributeInfo>,
     #[cfg(not(feature = "ast-parser"))]
     pub attributes: Vec<String>, // Simplified representation when AST parser is not available
+    /// Annotations from the lines above the recipe header (`# [slot: x]`, `[no-cd]`, ...)
+    pub annotations: Vec<crate::parser::annotations::RecipeAnnotation>,
+    /// Full documentation from the comment block above the recipe, with line
+    /// breaks preserved, when it spans more than one line
+    pub long_description: Option<String>,
+}
+
+impl JustTask {
+    /// Find the first annotation with the given name
+    pub fn annotation(&self, name: &str) -> Option<&crate::parser::annotations::RecipeAnnotation> {
+        self.annotations.iter().find(|a| a.name == name)
+    }
+
+    /// Check whether the recipe carries the given annotation
+    pub fn has_annotation(&self, name: &str) -> bool {
+        self.annotation(name).is_some()
+    }
+
+    /// Collect the comma-separated values of every annotation with the given name
+    pub fn annotation_values(&self, name: &str) -> Vec<String> {
+        self.annotations
+            .iter()
+            .filter(|a| a.name == name)
+            .flat_map(|a| a.values())
+            .collect()
+    }
+
+    /// Exclusion groups from `# [exclusive: db, cache]` annotations
+    ///
+    /// A bare `# [exclusive]` puts the recipe in a group of its own name, so
+    /// it never runs concurrently with itself.
+    pub fn exclusion_groups(&self) -> Vec<String> {
+        let mut groups = Vec::new();
+        for annotation in &self.annotations {
+            if annotation.name != crate::parser::annotations::EXCLUSIVE_ANNOTATION {
+                continue;
+            }
+            match annotation.value {
+                Some(_) => groups.extend(annotation.values()),
+                None => groups.push(self.name.clone()),
+            }
+        }
+        groups.sort();
+        groups.dedup();
+        groups
+    }
+
+    /// Parallel jobs requested with `# [jobs: N]`
+    ///
+    /// Values that are not a positive integer are ignored.
+    pub fn jobs(&self) -> Option<usize> {
+        self.annotation(crate::parser::annotations::JOBS_ANNOTATION)?
+            .value
+            .as_deref()?
+            .trim()
+            .parse()
+            .ok()
+            .filter(|jobs| *jobs > 0)
+    }
+
+    /// Execution profiles declared with `# [profile: name(arg=value, ...)]`
+    ///
+    /// Malformed declarations are skipped, as are repeated profile names.
+    pub fn profiles(&self) -> Vec<crate::parser::annotations::ExecutionProfile> {
+        let mut profiles: Vec<crate::parser::annotations::ExecutionProfile> = Vec::new();
+        for annotation in &self.annotations {
+            if annotation.name != crate::parser::annotations::PROFILE_ANNOTATION {
+                continue;
+            }
+            let Some(profile) = annotation
+                .value
+                .as_deref()
+                .and_then(crate::parser::annotations::ExecutionProfile::parse)
+            else {
+                continue;
+            };
+            if !profiles.iter().any(|p| p.name == profile.name) {
+                profiles.push(profile);
+            }
+        }
+        profiles
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -65,6 +197,10 @@ pub struct ExecutionContext {
     pub environment: HashMap<String, String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u64>,
+    /// Named profile applied when the call doesn't select one with its
+    /// `profile` argument
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub profile: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,6 +211,61 @@ pub struct ExecutionResult {
     pub stderr: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// The redacted `just` command line, when command echo is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invocation: Option<CommandInvocation>,
+    /// Notes on conditions that may explain the result, such as variables
+    /// missing from the recipe environment
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Why a failed execution failed, when the executor knows without
+    /// looking at the output (a timeout, a cancellation, a prompt)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<crate::error::ErrorKind>,
+}
+
+/// A redacted record of the `just` command an execution ran
+///
+/// Secret-looking arguments and environment values are already replaced, so
+/// the record is safe to log and return to clients.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandInvocation {
+    pub program: String,
+    pub args: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Environment variables set on top of the server's environment
+    #[serde(skip_serializing_if = "std::collections::BTreeMap::is_empty", default)]
+    pub env: std::collections::BTreeMap<String, String>,
+}
+
+impl CommandInvocation {
+    /// Shell command line that reproduces the execution
+    pub fn command_line(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(ref cwd) = self.cwd {
+            parts.push(format!("cd {} &&", shell_quote(cwd)));
+        }
+        for (key, value) in &self.env {
+            parts.push(format!("{key}={}", shell_quote(value)));
+        }
+        parts.push(shell_quote(&self.program));
+        parts.extend(self.args.iter().map(|arg| shell_quote(arg)));
+        parts.join(" ")
+    }
+}
+
+/// Quote a word for POSIX shells, leaving plain words unchanged
+fn shell_quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./=:@%+,".contains(c));
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -0,0 +1,562 @@
+//! Release checks and self-update from GitHub releases
+//!
+//! `just-mcp self-update` finds the newest release on the chosen channel,
+//! downloads the archive for this platform, verifies it against the
+//! published SHA-256 checksum and replaces the running binary. Releases are
+//! not signed, so the checksum is the only integrity check; it is fetched
+//! over HTTPS from the same release.
+//!
+//! With `--update-check` the server also checks for newer stable releases at
+//! startup and logs a notice when one of them fixes parsing of syntax the watched justfiles
+//! use. Parser fixes are found through tags in the release notes: a line
+//! such as `- Handle quoted group names (parser:attributes)` marks a fix to
+//! attribute parsing. The tags understood are listed in [`PARSER_TAGS`].
+
+use crate::error::{Error, Result};
+use regex::Regex;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// GitHub API endpoint listing just-mcp releases
+pub const RELEASES_URL: &str = "https://api.github.com/repos/toolprint/just-mcp/releases";
+
+/// How long release checks and downloads may take
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long the startup check may take before it is abandoned
+const STARTUP_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+const BINARY_NAME: &str = "just-mcp";
+
+/// Which releases to update to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Channel {
+    #[default]
+    Stable,
+    /// Stable releases and pre-releases such as `v0.3.0-beta.1`
+    Prerelease,
+}
+
+impl FromStr for Channel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "stable" => Ok(Self::Stable),
+            "prerelease" => Ok(Self::Prerelease),
+            _ => Err(Error::InvalidParameter(format!(
+                "Unknown release channel '{s}', expected stable or prerelease"
+            ))),
+        }
+    }
+}
+
+/// `major.minor.patch[-pre]` version of a release
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Option<String>,
+}
+
+impl Version {
+    /// Parse a version or release tag such as `v0.2.1` or `0.3.0-beta.1`
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().trim_start_matches('v');
+        let (numbers, pre) = match text.split_once('-') {
+            Some((numbers, pre)) => (numbers, Some(pre.to_string())),
+            None => (text, None),
+        };
+        let mut parts = numbers.split('.').map(|part| part.parse::<u64>().ok());
+        let version = Self {
+            major: parts.next()??,
+            minor: parts.next()??,
+            patch: parts.next().unwrap_or(Some(0))?,
+            pre,
+        };
+        parts.next().is_none().then_some(version)
+    }
+
+    /// Version of this build
+    pub fn current() -> Self {
+        Self::parse(crate::VERSION).expect("crate version is valid")
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                // A release ranks above its pre-releases
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => compare_prerelease(a, b),
+            })
+    }
+}
+
+/// Semver precedence of pre-release strings such as `beta.2` and `beta.10`
+///
+/// Dot-separated identifiers are compared in turn: numerically when both are
+/// numbers, otherwise as text, with numbers ranking below text. If all shared
+/// identifiers are equal, the one with more identifiers is greater.
+fn compare_prerelease(a: &str, b: &str) -> Ordering {
+    let mut a_ids = a.split('.');
+    let mut b_ids = b.split('.');
+    loop {
+        let ordering = match (a_ids.next(), b_ids.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            },
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A GitHub release
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub draft: bool,
+    /// Release notes
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    pub fn version(&self) -> Option<Version> {
+        Version::parse(&self.tag_name)
+    }
+
+    fn asset(&self, name: &str) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+
+    /// Parser tags (`parser:<tag>`) in the release notes
+    pub fn parser_fix_tags(&self) -> BTreeSet<String> {
+        let tag = Regex::new(r"\bparser:([a-z][a-z-]*)").unwrap();
+        tag.captures_iter(self.body.as_deref().unwrap_or_default())
+            .map(|captures| captures[1].to_string())
+            .collect()
+    }
+}
+
+/// Published releases on `channel` newer than `current`, newest first
+pub fn newer_releases(releases: &[Release], channel: Channel, current: &Version) -> Vec<Release> {
+    let mut newer: Vec<(Version, &Release)> = releases
+        .iter()
+        .filter(|release| !release.draft)
+        .filter(|release| channel == Channel::Prerelease || !release.prerelease)
+        .filter_map(|release| Some((release.version()?, release)))
+        .filter(|(version, _)| version > current)
+        .collect();
+    newer.sort_by(|(a, _), (b, _)| b.cmp(a));
+    newer
+        .into_iter()
+        .map(|(_, release)| release.clone())
+        .collect()
+}
+
+/// Release target of this platform, as used in archive names
+pub fn release_target() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Some("x86_64-unknown-linux-gnu"),
+        ("linux", "aarch64") => Some("aarch64-unknown-linux-gnu"),
+        ("macos", "x86_64") => Some("x86_64-apple-darwin"),
+        ("macos", "aarch64") => Some("aarch64-apple-darwin"),
+        _ => None,
+    }
+}
+
+/// Name of the release archive for `target`
+pub fn archive_name(release: &Release, target: &str) -> String {
+    format!("{BINARY_NAME}-{}-{target}.tar.gz", release.tag_name)
+}
+
+/// Check `data` against a `sha256sum` line (`<hex>  <file>`)
+pub fn verify_checksum(data: &[u8], checksum_file: &str) -> Result<()> {
+    let expected = checksum_file
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| Error::Other("Checksum file is empty".to_string()))?;
+    let actual = format!("{:x}", Sha256::digest(data));
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(Error::Other(format!(
+            "Checksum mismatch: expected {expected}, downloaded archive has {actual}"
+        )));
+    }
+    Ok(())
+}
+
+/// The `just-mcp` binary inside a release archive
+pub fn extract_binary(archive: &[u8]) -> Result<Vec<u8>> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry.path()?.file_name() == Some(BINARY_NAME.as_ref()) {
+            let mut binary = Vec::new();
+            entry.read_to_end(&mut binary)?;
+            return Ok(binary);
+        }
+    }
+    Err(Error::Other(format!(
+        "Release archive does not contain {BINARY_NAME}"
+    )))
+}
+
+/// Replace the binary at `path` with `binary`
+///
+/// The new binary is written next to the old one and renamed over it, so a
+/// running just-mcp keeps its old executable until it restarts.
+#[cfg(unix)]
+pub fn replace_binary(path: &Path, binary: &[u8]) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let staged = path.with_file_name(format!(".{BINARY_NAME}.update-{}", std::process::id()));
+    std::fs::write(&staged, binary)?;
+    std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+    std::fs::rename(&staged, path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&staged);
+    })?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn replace_binary(_path: &Path, _binary: &[u8]) -> Result<()> {
+    Err(Error::Other(
+        "Self-update is only supported on Linux and macOS".to_string(),
+    ))
+}
+
+/// Whether a trimmed justfile line uses some syntax
+type SyntaxMatcher = fn(&str) -> bool;
+
+/// Justfile syntax with parser fix tags, and how to detect it in a justfile
+pub const PARSER_TAGS: &[(&str, SyntaxMatcher)] = &[
+    ("attributes", |line| line.starts_with('[')),
+    ("imports", |line| line.starts_with("import ")),
+    ("modules", |line| {
+        line.starts_with("mod ") || line.starts_with("mod? ")
+    }),
+    ("aliases", |line| line.starts_with("alias ")),
+    ("settings", |line| line.starts_with("set ")),
+    ("conditionals", |line| {
+        line.contains("if ") && line.contains('{')
+    }),
+    ("backticks", |line| line.contains('`')),
+    ("shebang", |line| line.starts_with("#!")),
+    ("variadic", |line| {
+        !line.starts_with('#')
+            && line.contains(':')
+            && line
+                .split_whitespace()
+                .any(|word| word.len() > 1 && (word.starts_with('*') || word.starts_with('+')))
+    }),
+];
+
+/// Parser tags for the syntax a justfile uses
+pub fn justfile_syntax(content: &str) -> BTreeSet<&'static str> {
+    let mut syntax = BTreeSet::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        for (tag, used) in PARSER_TAGS {
+            // The file's own shebang line isn't recipe syntax
+            if *tag == "shebang" && i == 0 {
+                continue;
+            }
+            if used(line) {
+                syntax.insert(*tag);
+            }
+        }
+    }
+    syntax
+}
+
+/// Notice for releases that fix parsing of syntax in `syntax`
+pub fn parser_fix_notice(newer: &[Release], syntax: &BTreeSet<&str>) -> Option<String> {
+    let latest = newer.first()?;
+    let fixed: BTreeSet<String> = newer
+        .iter()
+        .flat_map(Release::parser_fix_tags)
+        .filter(|tag| syntax.contains(tag.as_str()))
+        .collect();
+    if fixed.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "just-mcp {} is available and fixes parsing of {} used in your justfiles (running {}); run `just-mcp self-update` to upgrade",
+        latest.tag_name,
+        fixed.into_iter().collect::<Vec<_>>().join(", "),
+        crate::VERSION
+    ))
+}
+
+fn http_client(timeout: Duration) -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .user_agent(format!("{BINARY_NAME}/{}", crate::VERSION))
+        .timeout(timeout)
+        .build()
+        .map_err(|e| Error::Other(format!("Failed to create HTTP client: {e}")))
+}
+
+async fn fetch_releases(client: &reqwest::Client) -> Result<Vec<Release>> {
+    client
+        .get(RELEASES_URL)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| Error::Other(format!("Failed to list releases: {e}")))?
+        .json()
+        .await
+        .map_err(|e| Error::Other(format!("Failed to read releases: {e}")))
+}
+
+async fn download(client: &reqwest::Client, asset: &Asset) -> Result<Vec<u8>> {
+    let bytes = client
+        .get(&asset.browser_download_url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| Error::Other(format!("Failed to download {}: {e}", asset.name)))?
+        .bytes()
+        .await
+        .map_err(|e| Error::Other(format!("Failed to download {}: {e}", asset.name)))?;
+    Ok(bytes.to_vec())
+}
+
+/// Newest release on `channel` that is newer than this build
+pub async fn check(channel: Channel) -> Result<Option<Release>> {
+    let client = http_client(REQUEST_TIMEOUT)?;
+    let releases = fetch_releases(&client).await?;
+    Ok(newer_releases(&releases, channel, &Version::current())
+        .into_iter()
+        .next())
+}
+
+/// Download, verify and install `release` over the running binary
+///
+/// Returns the path of the replaced binary.
+pub async fn install(release: &Release) -> Result<PathBuf> {
+    let target = release_target().ok_or_else(|| {
+        Error::Other(format!(
+            "No prebuilt binaries for {}-{}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        ))
+    })?;
+    let name = archive_name(release, target);
+    let archive_asset = release
+        .asset(&name)
+        .ok_or_else(|| Error::Other(format!("Release {} has no {name}", release.tag_name)))?;
+    let checksum_asset = release.asset(&format!("{name}.sha256")).ok_or_else(|| {
+        Error::Other(format!(
+            "Release {} has no checksum for {name}; refusing to install it",
+            release.tag_name
+        ))
+    })?;
+
+    let client = http_client(REQUEST_TIMEOUT)?;
+    let archive = download(&client, archive_asset).await?;
+    let checksum = download(&client, checksum_asset).await?;
+    verify_checksum(&archive, &String::from_utf8_lossy(&checksum))?;
+    let binary = extract_binary(&archive)?;
+
+    let path = std::env::current_exe()?.canonicalize()?;
+    replace_binary(&path, &binary)?;
+    Ok(path)
+}
+
+/// Notice to log at startup if a newer stable release fixes parsing of
+/// syntax used in `justfiles`
+///
+/// Gives up quietly on network errors; this must never delay or break
+/// startup.
+pub async fn startup_notice(justfiles: &[PathBuf]) -> Option<String> {
+    let syntax: BTreeSet<&str> = justfiles
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .flat_map(|content| justfile_syntax(&content))
+        .collect();
+    if syntax.is_empty() {
+        return None;
+    }
+
+    let client = http_client(STARTUP_CHECK_TIMEOUT).ok()?;
+    let releases = match fetch_releases(&client).await {
+        Ok(releases) => releases,
+        Err(e) => {
+            tracing::debug!("Update check failed: {}", e);
+            return None;
+        }
+    };
+    let newer = newer_releases(&releases, Channel::Stable, &Version::current());
+    parser_fix_notice(&newer, &syntax)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn release(tag: &str, prerelease: bool, body: &str) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            prerelease,
+            draft: false,
+            body: Some(body.to_string()),
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        let v = |text| Version::parse(text).unwrap();
+        assert!(v("v0.2.1") > v("0.2.0"));
+        assert!(v("0.10.0") > v("0.9.9"));
+        assert!(v("0.3.0") > v("0.3.0-beta.2"));
+        assert!(v("0.3.0-beta.2") > v("0.3.0-beta.1"));
+        assert_eq!(v("1.2"), v("1.2.0"));
+        assert!(Version::parse("latest").is_none());
+        assert!(Version::parse("1.2.3.4").is_none());
+    }
+
+    #[test]
+    fn test_prerelease_identifiers_compare_numerically() {
+        let v = |text| Version::parse(text).unwrap();
+        assert!(v("0.3.0-beta.10") > v("0.3.0-beta.2"));
+        assert!(v("0.3.0-rc.1") > v("0.3.0-beta.10"));
+        assert!(v("0.3.0-beta") > v("0.3.0-1"));
+        assert!(v("0.3.0-beta.1") > v("0.3.0-beta"));
+        assert_eq!(v("0.3.0-beta.2"), v("0.3.0-beta.2"));
+    }
+
+    #[test]
+    fn test_newer_releases_follow_channel() {
+        let releases = vec![
+            release("v0.2.0", false, ""),
+            release("v0.3.0-beta.1", true, ""),
+            release("v0.2.2", false, ""),
+            release("v0.2.1", false, ""),
+            release("nightly", true, ""),
+        ];
+        let current = Version::parse("0.2.0").unwrap();
+
+        let tags = |channel| -> Vec<String> {
+            newer_releases(&releases, channel, &current)
+                .into_iter()
+                .map(|release| release.tag_name)
+                .collect()
+        };
+        assert_eq!(tags(Channel::Stable), vec!["v0.2.2", "v0.2.1"]);
+        assert_eq!(
+            tags(Channel::Prerelease),
+            vec!["v0.3.0-beta.1", "v0.2.2", "v0.2.1"]
+        );
+        assert!("beta".parse::<Channel>().is_err());
+    }
+
+    #[test]
+    fn test_checksum_verification() {
+        let data = b"archive";
+        let checksum = format!("{:x}  just-mcp.tar.gz\n", Sha256::digest(data));
+        assert!(verify_checksum(data, &checksum).is_ok());
+        assert!(verify_checksum(b"tampered", &checksum).is_err());
+        assert!(verify_checksum(data, "").is_err());
+    }
+
+    #[test]
+    fn test_extract_binary_from_archive() {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, content) in [("README.md", &b"readme"[..]), ("just-mcp", b"\x7fELF")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append_data(&mut header, name, content).unwrap();
+        }
+        let tar = builder.into_inner().unwrap();
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gz.write_all(&tar).unwrap();
+        let archive = gz.finish().unwrap();
+
+        assert_eq!(extract_binary(&archive).unwrap(), b"\x7fELF");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_replace_binary() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("just-mcp");
+        std::fs::write(&path, "old").unwrap();
+
+        replace_binary(&path, b"new").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"new");
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_parser_fix_notice_matches_justfile_syntax() {
+        let content = "#!/usr/bin/env -S just --justfile\nimport 'common.just'\n\n[group('dev')]\nbuild *args:\n    cargo build {{args}}\n";
+        let syntax = justfile_syntax(content);
+        assert_eq!(
+            syntax.iter().copied().collect::<Vec<_>>(),
+            vec!["attributes", "imports", "variadic"]
+        );
+
+        let newer = vec![
+            release(
+                "v0.2.2",
+                false,
+                "- Handle quoted group names (parser:attributes)\n- Faster startup",
+            ),
+            release("v0.2.1", false, "- Fix module paths (parser:modules)"),
+        ];
+        let notice = parser_fix_notice(&newer, &syntax).unwrap();
+        assert!(notice.contains("just-mcp v0.2.2"));
+        assert!(notice.contains("parsing of attributes used"));
+        assert!(!notice.contains("modules"));
+
+        let unrelated = vec![release("v0.2.1", false, "- Fix (parser:modules)")];
+        assert!(parser_fix_notice(&unrelated, &syntax).is_none());
+        assert!(parser_fix_notice(&[], &syntax).is_none());
+    }
+}
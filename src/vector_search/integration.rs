@@ -3,7 +3,7 @@
 //! This module provides high-level integration components that combine
 //! vector stores and embedding providers for use with justfile analysis.
 
-use crate::vector_search::types::{Document, SearchResult};
+use crate::vector_search::types::{Document, SearchFilters, SearchResult};
 use crate::vector_search::{EmbeddingProvider, VectorStore};
 use anyhow::Result;
 use std::sync::Arc;
@@ -288,6 +288,35 @@ impl<E: EmbeddingProvider, V: VectorStore> VectorSearchManager<E, V> {
             .await
     }
 
+    /// Semantic search scoped by structured recipe filters
+    ///
+    /// Combines a natural-language query with project, group, has-params and
+    /// private filters, e.g. "a deploy-ish recipe in the backend project".
+    ///
+    /// # Arguments
+    /// * `query` - Semantic search query
+    /// * `filters` - Structured filters applied before similarity scoring
+    /// * `limit` - Maximum number of results to return
+    /// * `threshold` - Minimum similarity threshold
+    pub async fn filtered_search(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+        limit: usize,
+        threshold: f32,
+    ) -> Result<Vec<SearchResult>> {
+        if !self.initialized {
+            return Err(anyhow::anyhow!("VectorSearchManager not initialized"));
+        }
+
+        let query_embedding = self.embedding_provider.embed(query).await?;
+
+        let store = self.vector_store.lock().await;
+        store
+            .search_with_filters(query_embedding, filters, limit, threshold)
+            .await
+    }
+
     /// Build SQL WHERE conditions from metadata filters
     ///
     /// This method converts key-value metadata filters into SQL WHERE clauses
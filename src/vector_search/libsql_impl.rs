@@ -3,7 +3,7 @@
 //! This module provides the concrete implementation of the VectorStore trait
 //! using libSQL/SQLite with vector search extensions.
 
-use crate::vector_search::types::{Document, SearchResult};
+use crate::vector_search::types::{Document, SearchFilters, SearchResult};
 use anyhow::Result;
 use async_trait::async_trait;
 
@@ -127,6 +127,28 @@ pub trait VectorStore: Send + Sync {
         threshold: f32,
     ) -> Result<Vec<SearchResult>>;
 
+    /// Semantic search restricted by structured recipe filters
+    ///
+    /// Filters (project, group, has-params, private) are applied as metadata
+    /// predicates before similarity scoring, so a query like "deploy" can be
+    /// scoped to a single project or group.
+    ///
+    /// # Arguments
+    /// * `query_embedding` - The query vector to search for
+    /// * `filters` - Structured filters; an empty set matches every document
+    /// * `limit` - Maximum number of results to return
+    /// * `threshold` - Minimum similarity threshold (0.0 to 1.0)
+    ///
+    /// # Returns
+    /// Vector of search results ordered by similarity (most similar first)
+    async fn search_with_filters(
+        &self,
+        query_embedding: Vec<f32>,
+        filters: &SearchFilters,
+        limit: usize,
+        threshold: f32,
+    ) -> Result<Vec<SearchResult>>;
+
     /// Search documents by metadata filters only (no vector search)
     ///
     /// This method searches documents purely based on metadata criteria
@@ -617,6 +639,26 @@ impl LibSqlVectorStore {
     pub fn distance_to_similarity(distance: f32) -> f32 {
         1.0 - distance
     }
+
+    /// Build SQL conditions for structured search filters
+    ///
+    /// Each filter becomes an `EXISTS` predicate against `document_metadata`,
+    /// joined with `AND`. Returns an empty string when no filter is set.
+    pub fn build_filter_conditions(filters: &SearchFilters) -> String {
+        filters
+            .metadata_predicates()
+            .iter()
+            .enumerate()
+            .map(|(i, (key, value))| {
+                format!(
+                    "EXISTS (SELECT 1 FROM document_metadata f{i} WHERE f{i}.document_id = d.id AND f{i}.key = '{}' AND f{i}.value = '{}')",
+                    key.replace('\'', "''"),
+                    value.replace('\'', "''")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    }
 }
 
 #[cfg(feature = "vector-search")]
@@ -1175,6 +1217,18 @@ impl VectorStore for LibSqlVectorStore {
         Ok(candidates)
     }
 
+    async fn search_with_filters(
+        &self,
+        query_embedding: Vec<f32>,
+        filters: &SearchFilters,
+        limit: usize,
+        threshold: f32,
+    ) -> Result<Vec<SearchResult>> {
+        let conditions = Self::build_filter_conditions(filters);
+        self.search_with_sql_filter(query_embedding, &conditions, limit, threshold)
+            .await
+    }
+
     async fn search_by_metadata(
         &self,
         metadata_filters: &[(&str, &str)],
@@ -44,7 +44,7 @@ pub use integration::VectorSearchManager;
 
 // Public types for document representation
 #[cfg(feature = "vector-search")]
-pub use types::{Document, SearchResult};
+pub use types::{Document, SearchFilters, SearchResult};
 
 // Version and compatibility info
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
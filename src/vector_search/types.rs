@@ -48,6 +48,95 @@ pub struct SearchResult {
     pub distance: f32,
 }
 
+/// Structured filters that narrow a semantic search to matching recipes
+///
+/// Each populated field becomes a metadata predicate that is evaluated in the
+/// database before similarity scoring, so filters combine with any query.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchFilters {
+    /// Project (watch directory) name the recipe belongs to
+    pub project: Option<String>,
+
+    /// Recipe group from the `[group(...)]` attribute
+    pub group: Option<String>,
+
+    /// Whether the recipe takes parameters
+    pub has_params: Option<bool>,
+
+    /// Whether the recipe is private
+    pub private: Option<bool>,
+}
+
+impl SearchFilters {
+    /// Metadata key holding the project name
+    pub const PROJECT_KEY: &'static str = "project";
+    /// Metadata key holding the recipe group
+    pub const GROUP_KEY: &'static str = "group";
+    /// Metadata key holding `"true"`/`"false"` for parameterized recipes
+    pub const HAS_PARAMS_KEY: &'static str = "has_params";
+    /// Metadata key holding `"true"`/`"false"` for private recipes
+    pub const PRIVATE_KEY: &'static str = "private";
+
+    /// Create an empty filter set that matches every document
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict results to a project
+    pub fn with_project(mut self, project: impl Into<String>) -> Self {
+        self.project = Some(project.into());
+        self
+    }
+
+    /// Restrict results to a recipe group
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// Restrict results to recipes with (or without) parameters
+    pub fn with_has_params(mut self, has_params: bool) -> Self {
+        self.has_params = Some(has_params);
+        self
+    }
+
+    /// Restrict results to private (or public) recipes
+    pub fn with_private(mut self, private: bool) -> Self {
+        self.private = Some(private);
+        self
+    }
+
+    /// Check whether no filter is set
+    pub fn is_empty(&self) -> bool {
+        self.metadata_predicates().is_empty()
+    }
+
+    /// Convert the filters into metadata key/value pairs
+    pub fn metadata_predicates(&self) -> Vec<(&'static str, String)> {
+        let mut predicates = Vec::new();
+        if let Some(project) = &self.project {
+            predicates.push((Self::PROJECT_KEY, project.clone()));
+        }
+        if let Some(group) = &self.group {
+            predicates.push((Self::GROUP_KEY, group.clone()));
+        }
+        if let Some(has_params) = self.has_params {
+            predicates.push((Self::HAS_PARAMS_KEY, has_params.to_string()));
+        }
+        if let Some(private) = self.private {
+            predicates.push((Self::PRIVATE_KEY, private.to_string()));
+        }
+        predicates
+    }
+
+    /// Check whether a document's metadata satisfies every filter
+    pub fn matches(&self, document: &Document) -> bool {
+        self.metadata_predicates()
+            .iter()
+            .all(|(key, value)| document.metadata.get(*key) == Some(value))
+    }
+}
+
 impl Document {
     /// Create a new document
     pub fn new(id: String, content: String) -> Self {
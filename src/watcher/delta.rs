@@ -0,0 +1,153 @@
+//! Minimal registry updates when a justfile is parsed again
+//!
+//! Every edit of a justfile changes the source hash of all its tools, and
+//! replacing all of them made each edit look like a change to every tool of
+//! the project. Tools are instead compared with the registered ones as
+//! clients see them: only added, removed and changed tools are reported to
+//! registry subscribers and trigger `tools/list_changed`, while bookkeeping
+//! such as the source hash is refreshed silently.
+
+use crate::error::Result;
+use crate::registry::ToolRegistry;
+use crate::types::ToolDefinition;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Tools of a justfile that changed when it was parsed again
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryDelta {
+    /// Tools registered for the first time
+    pub added: Vec<String>,
+    /// Tools whose recipe is gone
+    pub removed: Vec<String>,
+    /// Tools whose description, parameters or metadata changed
+    pub modified: Vec<String>,
+    /// Tools clients see no difference in
+    pub unchanged: usize,
+}
+
+impl RegistryDelta {
+    /// Whether clients see the same tools as before
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Number of tools the justfile now has
+    pub fn tool_count(&self) -> usize {
+        self.added.len() + self.modified.len() + self.unchanged
+    }
+}
+
+/// Replace the tools of the justfile at `path` with `tools`, touching only
+/// registry entries clients see a difference in
+pub(super) fn apply(
+    registry: &mut ToolRegistry,
+    tool_map: &mut HashMap<String, PathBuf>,
+    path: &Path,
+    tools: Vec<ToolDefinition>,
+) -> Result<RegistryDelta> {
+    let mut delta = RegistryDelta::default();
+    let mut current = HashSet::new();
+
+    for tool in tools {
+        let name = tool.name.clone();
+        // A tool another justfile registered under this name is taken over
+        let previous = tool_map
+            .get(&name)
+            .filter(|source| source.as_path() == path)
+            .and_then(|_| registry.get_tool(&name));
+        match previous {
+            Some(previous) if !previous.differs_for_clients(&tool) => {
+                delta.unchanged += 1;
+                registry.refresh_tool(tool);
+            }
+            Some(_) => {
+                delta.modified.push(name.clone());
+                registry.add_tool(tool)?;
+            }
+            None => {
+                delta.added.push(name.clone());
+                registry.add_tool(tool)?;
+            }
+        }
+        tool_map.insert(name.clone(), path.to_path_buf());
+        current.insert(name);
+    }
+
+    let gone: Vec<String> = tool_map
+        .iter()
+        .filter(|(name, source)| source.as_path() == path && !current.contains(*name))
+        .map(|(name, _)| name.clone())
+        .collect();
+    for name in gone {
+        registry.remove_tool(&name)?;
+        tool_map.remove(&name);
+        delta.removed.push(name);
+    }
+
+    delta.added.sort();
+    delta.removed.sort();
+    delta.modified.sort();
+    Ok(delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ChangeType;
+
+    fn tool(name: &str, description: &str, hash: &str) -> ToolDefinition {
+        ToolDefinition {
+            name: name.to_string(),
+            description: description.to_string(),
+            input_schema: serde_json::json!({"type": "object"}),
+            dependencies: vec![],
+            source_hash: hash.to_string(),
+            last_modified: std::time::SystemTime::now(),
+            internal_name: None,
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_only_changed_tools_are_touched() {
+        let mut registry = ToolRegistry::new();
+        let mut tool_map = HashMap::new();
+        let path = Path::new("/project/justfile");
+        let first = vec![
+            tool("build", "Build", "v1"),
+            tool("test", "Test", "v1"),
+            tool("lint", "Lint", "v1"),
+        ];
+        let delta = apply(&mut registry, &mut tool_map, path, first).unwrap();
+        assert_eq!(delta.added, ["build", "lint", "test"]);
+
+        let mut changes = registry.subscribe_changes();
+        let second = vec![
+            tool("build", "Build", "v2"),
+            tool("test", "Run the tests", "v2"),
+            tool("deploy", "Deploy", "v2"),
+        ];
+        let delta = apply(&mut registry, &mut tool_map, path, second).unwrap();
+        assert_eq!(
+            delta,
+            RegistryDelta {
+                added: vec!["deploy".to_string()],
+                removed: vec!["lint".to_string()],
+                modified: vec!["test".to_string()],
+                unchanged: 1,
+            }
+        );
+        assert_eq!(delta.tool_count(), 3);
+        // The unchanged tool carries the new hash without a change event
+        assert_eq!(registry.get_tool("build").unwrap().source_hash, "v2");
+        let mut events = Vec::new();
+        while let Ok(event) = changes.try_recv() {
+            events.push((event.change_type, event.tool_name));
+        }
+        assert_eq!(events.len(), 3);
+        assert!(!events.iter().any(|(_, name)| name == "build"));
+        assert!(events.contains(&(ChangeType::Removed, "lint".to_string())));
+    }
+}
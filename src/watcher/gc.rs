@@ -0,0 +1,243 @@
+//! Consistency sweeps of the registry and watcher bookkeeping
+//!
+//! The registry, the tool → justfile map and the watch directory names are
+//! kept in step by filesystem events. Over a long uptime some of those events
+//! are missed or never arrive (a project deleted while its watch was lost, a
+//! watch directory switched through `_admin_set_watch_directory`), and
+//! entries for projects that no longer exist accumulate. A sweep compares the
+//! three with the filesystem and removes what no longer has a source, so
+//! `tools/list` never shows tools that cannot run.
+//!
+//! The watcher sweeps every [`SWEEP_INTERVAL`] while watching, and
+//! `_admin_gc` runs a sweep on demand.
+
+use super::JustfileWatcher;
+use crate::error::Result;
+use crate::parser::imports::composite_hash;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashSet};
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Time between sweeps of a running watcher
+pub const SWEEP_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Orphans found by a sweep, and whether they were removed
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct GcReport {
+    /// Whether this was a dry run that removed nothing
+    pub dry_run: bool,
+    /// Tools whose justfile no longer exists, or that no justfile claims
+    pub orphan_tools: Vec<String>,
+    /// Source entries for tools that are no longer registered
+    pub stale_sources: Vec<String>,
+    /// Names of watch directories that no longer exist and aren't watched
+    pub stale_names: Vec<String>,
+    /// Justfiles changed since their tools were generated, which are parsed
+    /// again so removed recipes disappear
+    pub outdated_justfiles: Vec<String>,
+}
+
+impl GcReport {
+    /// Whether the sweep found nothing to clean up
+    pub fn is_clean(&self) -> bool {
+        self.orphan_tools.is_empty()
+            && self.stale_sources.is_empty()
+            && self.stale_names.is_empty()
+            && self.outdated_justfiles.is_empty()
+    }
+
+    /// Total number of orphaned or outdated entries found
+    pub fn total(&self) -> usize {
+        self.orphan_tools.len()
+            + self.stale_sources.len()
+            + self.stale_names.len()
+            + self.outdated_justfiles.len()
+    }
+}
+
+impl JustfileWatcher {
+    /// Find, and unless `dry_run` is set remove, orphaned registry entries
+    ///
+    /// Admin tools (`_admin_*`) are never touched. Justfiles whose source
+    /// hash no longer matches their tools are parsed again rather than
+    /// dropped. The caller is responsible for notifying clients.
+    pub async fn collect_garbage(&self, dry_run: bool) -> Result<GcReport> {
+        let mut report = GcReport {
+            dry_run,
+            ..Default::default()
+        };
+        let mut outdated = BTreeSet::new();
+
+        {
+            let mut registry = self.registry.lock().await;
+            let mut tool_map = self.tool_source_map.lock().await;
+
+            let registered: HashSet<String> = registry
+                .list_tools()
+                .iter()
+                .map(|tool| tool.name.clone())
+                .collect();
+            let mut unclaimed: Vec<String> = registered
+                .iter()
+                .filter(|name| !name.starts_with("_admin_") && !tool_map.contains_key(*name))
+                .cloned()
+                .collect();
+            unclaimed.sort();
+            let mut sources: Vec<(String, PathBuf)> = tool_map
+                .iter()
+                .map(|(name, path)| (name.clone(), path.clone()))
+                .collect();
+            sources.sort();
+
+            for (name, path) in sources {
+                if !registered.contains(&name) {
+                    report.stale_sources.push(name.clone());
+                    if !dry_run {
+                        tool_map.remove(&name);
+                    }
+                } else if !path.is_file() {
+                    report.orphan_tools.push(name.clone());
+                    if !dry_run {
+                        registry.remove_tool(&name)?;
+                        tool_map.remove(&name);
+                    }
+                } else {
+                    let current = composite_hash(&path).ok();
+                    let generated = registry.get_tool(&name).map(|tool| &tool.source_hash);
+                    if current.is_some() && current.as_ref() != generated {
+                        outdated.insert(path);
+                    }
+                }
+            }
+
+            for name in unclaimed {
+                if !dry_run {
+                    registry.remove_tool(&name)?;
+                }
+                report.orphan_tools.push(name);
+            }
+        }
+
+        {
+            let watched = self.watched_paths.lock().await;
+            let mut path_names = self.path_names.lock().await;
+            let mut stale: Vec<PathBuf> = path_names
+                .keys()
+                .filter(|path| !path.exists() && !watched.contains(*path))
+                .cloned()
+                .collect();
+            stale.sort();
+            for path in stale {
+                if !dry_run {
+                    path_names.remove(&path);
+                }
+                report.stale_names.push(path.display().to_string());
+            }
+        }
+
+        for path in outdated {
+            report.outdated_justfiles.push(path.display().to_string());
+            if !dry_run {
+                if let Err(e) = self
+                    .parse_and_update_justfile_without_notification(&path)
+                    .await
+                {
+                    warn!("Failed to refresh outdated {}: {}", path.display(), e);
+                }
+            }
+        }
+
+        if !report.is_clean() {
+            info!(
+                "Registry sweep{}: {} orphan tool(s), {} stale source(s), {} stale name(s), {} outdated justfile(s)",
+                if dry_run { " (dry run)" } else { "" },
+                report.orphan_tools.len(),
+                report.stale_sources.len(),
+                report.stale_names.len(),
+                report.outdated_justfiles.len()
+            );
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::ToolRegistry;
+    use std::fs;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+    use tokio::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_sweep_removes_orphans() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let mut watcher = JustfileWatcher::new(registry.clone());
+        watcher.set_multiple_dirs(true);
+
+        let temp_dir = TempDir::new().unwrap();
+        let kept = temp_dir.path().join("kept");
+        let deleted = temp_dir.path().join("deleted");
+        for dir in [&kept, &deleted] {
+            fs::create_dir_all(dir).unwrap();
+            fs::write(dir.join("justfile"), "build:\n    echo build\n").unwrap();
+        }
+        watcher
+            .configure_names(&[
+                (kept.clone(), Some("kept".to_string())),
+                (deleted.clone(), Some("deleted".to_string())),
+            ])
+            .await;
+        for dir in [&kept, &deleted] {
+            watcher
+                .parse_and_update_justfile(&dir.join("justfile"))
+                .await
+                .unwrap();
+        }
+        assert!(watcher.collect_garbage(false).await.unwrap().is_clean());
+
+        fs::remove_dir_all(&deleted).unwrap();
+        registry.lock().await.remove_tool("build@kept").unwrap();
+
+        let report = watcher.collect_garbage(true).await.unwrap();
+        assert_eq!(report.orphan_tools, vec!["build@deleted"]);
+        assert_eq!(report.stale_sources, vec!["build@kept"]);
+        assert_eq!(report.stale_names, vec![deleted.display().to_string()]);
+        assert_eq!(registry.lock().await.list_tools().len(), 1);
+
+        let report = watcher.collect_garbage(false).await.unwrap();
+        assert_eq!(report.total(), 3);
+        assert!(registry.lock().await.list_tools().is_empty());
+        assert!(watcher.collect_garbage(false).await.unwrap().is_clean());
+    }
+
+    #[tokio::test]
+    async fn test_sweep_refreshes_outdated_justfiles() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = JustfileWatcher::new(registry.clone());
+
+        let temp_dir = TempDir::new().unwrap();
+        let justfile = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile,
+            "build:\n    echo build\n\ntest:\n    echo test\n",
+        )
+        .unwrap();
+        watcher.parse_and_update_justfile(&justfile).await.unwrap();
+
+        // An edit whose event was lost
+        fs::write(&justfile, "build:\n    echo build\n").unwrap();
+
+        let report = watcher.collect_garbage(false).await.unwrap();
+        assert_eq!(
+            report.outdated_justfiles,
+            vec![justfile.display().to_string()]
+        );
+        let registry = registry.lock().await;
+        assert!(registry.get_tool("test").is_none());
+        assert!(registry.get_tool("build").is_some());
+    }
+}
@@ -1,9 +1,17 @@
 use crate::error::{Error, Result};
 use crate::notification::{Notification, NotificationSender};
+use crate::parser::annotations::{
+    ExecutionProfile, NO_CD_ANNOTATION, PROFILE_ARGUMENT, SLOT_ANNOTATION,
+};
+use crate::parser::discovery::{find_justfiles, is_justfile};
+use crate::parser::imports::{composite_hash_of, resolve_import_graph, ImportedFile};
+use crate::parser::variables::{extract_variables, referenced_variables, JustfileVariable};
 use crate::parser::{EnhancedJustfileParser, ParserPreference};
+use crate::profiles::{NamedProfile, ProfileSet};
 use crate::registry::ToolRegistry;
 use crate::security::SecurityValidator;
-use crate::types::{JustTask, Parameter, ToolDefinition};
+use crate::types::{JustTask, Parameter, ToolDefinition, ToolMetadata};
+use notify::event::ModifyKind;
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use serde_json::json;
 use std::collections::{HashMap, HashSet};
@@ -14,6 +22,18 @@ use tokio::sync::{mpsc, Mutex};
 use tokio::time::sleep;
 use tracing::{error, info, warn};
 
+pub mod delta;
+pub mod gc;
+pub mod stats;
+pub mod supervisor;
+
+use self::delta::RegistryDelta;
+use self::stats::{WatcherMetrics, WatcherStats};
+use self::supervisor::Supervision;
+
+/// Capacity of the queue between the filesystem watcher and the update loop
+const EVENT_QUEUE_CAPACITY: usize = 100;
+
 pub struct JustfileWatcher {
     registry: Arc<Mutex<ToolRegistry>>,
     parser: EnhancedJustfileParser,
@@ -24,10 +44,23 @@ pub struct JustfileWatcher {
     tool_source_map: Arc<Mutex<HashMap<String, PathBuf>>>,
     // Maps justfile paths to their assigned names
     path_names: Arc<Mutex<HashMap<PathBuf, Option<String>>>>,
+    // Maps files pulled in with `import` or `mod` (normalized) to the
+    // justfiles importing them
+    imported_by: Arc<Mutex<HashMap<PathBuf, HashSet<PathBuf>>>>,
     // Whether we have multiple watch directories
     has_multiple_dirs: bool,
     // Security validator for parameter name sanitization
     security_validator: SecurityValidator,
+    // Queue, latency and per-path event metrics
+    metrics: Arc<WatcherMetrics>,
+    // Update latency above which a warning is logged
+    lag_warning_threshold: Duration,
+    // Parser preferences for justfiles under specific watch directories
+    parser_overrides: Vec<(PathBuf, ParserPreference)>,
+    // Named profiles offered in the `profile` argument of the recipes they allow
+    profiles: Arc<ProfileSet>,
+    // Whether the watcher loop is running, when started under supervision
+    supervision: Arc<Supervision>,
 }
 
 impl JustfileWatcher {
@@ -51,8 +84,14 @@ impl JustfileWatcher {
             notification_sender: None,
             tool_source_map: Arc::new(Mutex::new(HashMap::new())),
             path_names: Arc::new(Mutex::new(HashMap::new())),
+            imported_by: Arc::new(Mutex::new(HashMap::new())),
             has_multiple_dirs: false,
             security_validator: SecurityValidator::with_default(),
+            metrics: Arc::new(WatcherMetrics::new(EVENT_QUEUE_CAPACITY)),
+            lag_warning_threshold: Duration::from_secs(5),
+            parser_overrides: Vec::new(),
+            profiles: Arc::new(ProfileSet::default()),
+            supervision: Arc::new(Supervision::default()),
         }
     }
 
@@ -77,8 +116,14 @@ impl JustfileWatcher {
             notification_sender: None,
             tool_source_map: Arc::new(Mutex::new(HashMap::new())),
             path_names: Arc::new(Mutex::new(HashMap::new())),
+            imported_by: Arc::new(Mutex::new(HashMap::new())),
             has_multiple_dirs: false,
             security_validator: SecurityValidator::with_default(),
+            metrics: Arc::new(WatcherMetrics::new(EVENT_QUEUE_CAPACITY)),
+            lag_warning_threshold: Duration::from_secs(5),
+            parser_overrides: Vec::new(),
+            profiles: Arc::new(ProfileSet::default()),
+            supervision: Arc::new(Supervision::default()),
         }
     }
 
@@ -102,19 +147,78 @@ impl JustfileWatcher {
         self
     }
 
+    /// Log a warning when applying justfile changes takes longer than this
+    ///
+    /// Latency is measured from the first event of a debounced batch until
+    /// the registry is updated, so it includes the debounce window.
+    pub fn with_lag_warning_threshold(mut self, threshold: Duration) -> Self {
+        self.lag_warning_threshold = threshold;
+        self
+    }
+
+    /// Parse justfiles under these watch directories with their own parser
+    ///
+    /// A justfile that only parses with one parser then doesn't force that
+    /// parser, or a global fallback, on every other project. The most
+    /// specific directory containing a justfile wins.
+    pub fn set_parser_overrides(&mut self, overrides: Vec<(PathBuf, ParserPreference)>) {
+        for (dir, preference) in &overrides {
+            info!("Using {} parser for {}", preference, dir.display());
+        }
+        self.parser_overrides = overrides;
+    }
+
+    /// Offer named profiles in the `profile` argument of the recipes they allow
+    pub fn set_profiles(&mut self, profiles: Arc<ProfileSet>) {
+        self.profiles = profiles;
+    }
+
+    /// Parser override that applies to a justfile, if any
+    pub fn parser_override(&self, justfile_path: &Path) -> Option<&ParserPreference> {
+        let dir = if justfile_path.is_dir() {
+            justfile_path
+        } else {
+            justfile_path.parent().unwrap_or(justfile_path)
+        };
+        self.parser_overrides
+            .iter()
+            .filter(|(path, _)| {
+                let path = if is_justfile(path) {
+                    path.parent().unwrap_or(path)
+                } else {
+                    path
+                };
+                dir.starts_with(path)
+            })
+            .max_by_key(|(path, _)| path.components().count())
+            .map(|(_, preference)| preference)
+    }
+
+    /// Parser used for a justfile: its override or the default preference
+    pub fn effective_parser(&self, justfile_path: &Path) -> &ParserPreference {
+        self.parser_override(justfile_path)
+            .unwrap_or_else(|| self.parser.get_parser_preference())
+    }
+
+    /// Parser attempts, successes and timings since the watcher was created
+    pub fn parsing_metrics(&self) -> crate::parser::ParsingMetrics {
+        self.parser.get_metrics()
+    }
+
+    /// Snapshot of queue, latency and per-path event metrics and supervision state
+    pub fn stats(&self) -> WatcherStats {
+        WatcherStats {
+            supervision: self.supervision.status(),
+            ..self.metrics.snapshot()
+        }
+    }
+
     pub async fn configure_names(&self, configs: &[(PathBuf, Option<String>)]) {
         let mut path_names = self.path_names.lock().await;
         for (path, name) in configs {
-            // Store names for each watched directory
-            if path.is_dir() {
-                let justfile_path = path.join("justfile");
-                path_names.insert(justfile_path.clone(), name.clone());
-
-                let justfile_cap = path.join("Justfile");
-                path_names.insert(justfile_cap, name.clone());
-            } else {
-                path_names.insert(path.clone(), name.clone());
-            }
+            // Directories are stored as-is and matched against a justfile's
+            // parent, so every casing of the justfile gets the name
+            path_names.insert(path.clone(), name.clone());
         }
     }
 
@@ -123,13 +227,22 @@ impl JustfileWatcher {
     }
 
     pub async fn watch_paths(&self, paths: Vec<PathBuf>) -> Result<()> {
-        let (tx, mut rx) = mpsc::channel(100);
+        let (tx, mut rx) = mpsc::channel(EVENT_QUEUE_CAPACITY);
 
-        // Create watcher
+        // Create watcher. Events that can't be queued, and watcher errors
+        // such as a kernel queue overflow, are counted and trigger a rescan
+        // instead of blocking the notify thread
+        let metrics = self.metrics.clone();
         let mut watcher = RecommendedWatcher::new(
-            move |res: std::result::Result<Event, notify::Error>| {
-                if let Ok(event) = res {
-                    let _ = tx.blocking_send(event);
+            move |res: std::result::Result<Event, notify::Error>| match res {
+                Ok(event) => match tx.try_send(event) {
+                    Ok(()) => metrics.enqueued(),
+                    Err(mpsc::error::TrySendError::Full(_)) => metrics.dropped(),
+                    Err(mpsc::error::TrySendError::Closed(_)) => {}
+                },
+                Err(e) => {
+                    warn!("File watcher error, rescanning justfiles: {}", e);
+                    metrics.dropped();
                 }
             },
             Config::default(),
@@ -137,24 +250,26 @@ impl JustfileWatcher {
         .map_err(|e| Error::Io(std::io::Error::other(e)))?;
 
         // Watch each path
+        let mut watched_dirs = HashSet::new();
         for path in paths {
             if path.exists() {
                 if path.is_dir() {
                     watcher
                         .watch(&path, RecursiveMode::NonRecursive)
                         .map_err(|e| Error::Io(std::io::Error::other(e)))?;
+                    watched_dirs.insert(normalize_path(&path));
                     info!("Watching directory: {}", path.display());
 
                     // Scan for existing justfiles in directory
-                    let justfile_path = path.join("justfile");
-                    if justfile_path.exists() {
+                    for justfile_path in find_justfiles(&path) {
                         self.parse_and_update_justfile(&justfile_path).await?;
                     }
-                } else if path.file_name() == Some(std::ffi::OsStr::new("justfile")) {
+                } else if is_justfile(&path) {
                     let parent = path.parent().unwrap_or(Path::new("."));
                     watcher
                         .watch(parent, RecursiveMode::NonRecursive)
                         .map_err(|e| Error::Io(std::io::Error::other(e)))?;
+                    watched_dirs.insert(normalize_path(parent));
                     info!("Watching justfile: {}", path.display());
 
                     // Parse the justfile
@@ -167,37 +282,275 @@ impl JustfileWatcher {
 
         // Handle events with debouncing
         let mut pending_updates = HashSet::new();
+        let mut batch_started = None;
+        let mut missing_paths = HashSet::new();
+        let mut last_sweep = std::time::Instant::now();
         let debounce_duration = self.debounce_duration;
 
         loop {
             tokio::select! {
                 Some(event) = rx.recv() => {
-                    if let Some(path) = self.extract_justfile_path(&event) {
-                        pending_updates.insert(path);
+                    if matches!(event.kind, EventKind::Any | EventKind::Other) {
+                        // Backends report overflows and rescans this way
+                        self.metrics.request_rescan();
+                    }
+                    let paths = self.affected_justfiles(&event).await;
+                    self.metrics.received(&event.kind, &paths);
+                    if !paths.is_empty() {
+                        batch_started.get_or_insert_with(std::time::Instant::now);
                     }
+                    pending_updates.extend(paths);
+                    self.metrics.set_pending(pending_updates.len());
                 }
                 _ = sleep(debounce_duration) => {
+                    self.watch_import_dirs(&mut watcher, &mut watched_dirs).await;
+                    let moved = self.watched_path_changes(&mut watcher, &mut missing_paths).await;
+                    if !moved.is_empty() {
+                        pending_updates.extend(moved);
+                        batch_started.get_or_insert_with(std::time::Instant::now);
+                    }
+                    if self.metrics.take_rescan() {
+                        pending_updates.extend(self.rescan_targets().await);
+                        batch_started.get_or_insert_with(std::time::Instant::now);
+                    }
                     if !pending_updates.is_empty() {
-                        let updates = pending_updates.drain().collect::<Vec<_>>();
-                        for path in updates {
-                            if let Err(e) = self.handle_justfile_change(&path).await {
+                        // Handle removals first, so a rename such as
+                        // `justfile` -> `Justfile` drops the old tools before
+                        // the new file registers its own
+                        let mut updates = pending_updates.drain().collect::<Vec<_>>();
+                        updates.sort_by_key(|path| path.exists());
+                        for path in &updates {
+                            if let Err(e) = self.handle_justfile_change(path).await {
                                 error!("Error handling justfile change: {}", e);
                             }
                         }
+                        self.metrics.set_pending(0);
+
+                        let started = batch_started.take().unwrap_or_else(std::time::Instant::now);
+                        let latency = self.metrics.applied(&updates, started);
+                        let stats = self.metrics.snapshot();
+                        tracing::info!(
+                            target: crate::METRICS_TARGET,
+                            latency_ms = latency.as_secs_f64() * 1000.0,
+                            justfiles = updates.len(),
+                            queue_depth = stats.queue_depth,
+                            events_received = stats.events_received,
+                            events_dropped = stats.events_dropped,
+                            "watcher update"
+                        );
+                        if latency > self.lag_warning_threshold {
+                            warn!(
+                                "Justfile updates took {:?} to apply (threshold {:?}); tool lists may have been stale",
+                                latency, self.lag_warning_threshold
+                            );
+                        }
+                    }
+                    if last_sweep.elapsed() >= gc::SWEEP_INTERVAL {
+                        last_sweep = std::time::Instant::now();
+                        match self.collect_garbage(false).await {
+                            Ok(report)
+                                if !report.orphan_tools.is_empty()
+                                    || !report.outdated_justfiles.is_empty() =>
+                            {
+                                self.send_tools_changed_notification();
+                            }
+                            Ok(_) => {}
+                            Err(e) => warn!("Registry sweep failed: {}", e),
+                        }
                     }
                 }
             }
         }
     }
 
-    fn extract_justfile_path(&self, event: &Event) -> Option<PathBuf> {
+    /// Justfiles to update because a watched path disappeared or came back
+    ///
+    /// Deleting or moving a watched directory, or one of its parents, is not
+    /// reported on every platform, and the OS watch is lost with it. Watched
+    /// paths are therefore checked on every idle tick: tools of a vanished
+    /// path are removed, and a path that reappears is watched and scanned
+    /// again.
+    async fn watched_path_changes(
+        &self,
+        watcher: &mut RecommendedWatcher,
+        missing: &mut HashSet<PathBuf>,
+    ) -> HashSet<PathBuf> {
+        let watched: Vec<PathBuf> = self.watched_paths.lock().await.iter().cloned().collect();
+        let mut changed = HashSet::new();
+
+        for path in watched {
+            let exists = path.exists();
+            if !exists && missing.insert(path.clone()) {
+                warn!("Watched path disappeared: {}", path.display());
+                changed.extend(
+                    self.tool_source_map
+                        .lock()
+                        .await
+                        .values()
+                        .filter(|source| *source == &path || source.starts_with(&path))
+                        .cloned(),
+                );
+            } else if exists && missing.remove(&path) {
+                info!("Watched path reappeared: {}", path.display());
+                let target = if path.is_dir() {
+                    changed.extend(find_justfiles(&path));
+                    path.as_path()
+                } else {
+                    changed.insert(path.clone());
+                    path.parent().unwrap_or(Path::new("."))
+                };
+                if let Err(e) = watcher.watch(target, RecursiveMode::NonRecursive) {
+                    warn!("Failed to watch {} again: {}", target.display(), e);
+                }
+            }
+        }
+        changed
+    }
+
+    /// Watch the directories of imported files that aren't watched yet
+    ///
+    /// Imports may live outside the watched directories, such as
+    /// `import 'ci/common.just'`. Directories that don't exist yet are tried
+    /// again on the next tick.
+    async fn watch_import_dirs(
+        &self,
+        watcher: &mut RecommendedWatcher,
+        watched: &mut HashSet<PathBuf>,
+    ) {
+        let dirs: HashSet<PathBuf> = self
+            .imported_by
+            .lock()
+            .await
+            .keys()
+            .filter_map(|path| path.parent().map(Path::to_path_buf))
+            .filter(|dir| !watched.contains(dir))
+            .collect();
+        for dir in dirs.into_iter().filter(|dir| dir.is_dir()) {
+            match watcher.watch(&dir, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    info!("Watching imports in: {}", dir.display());
+                    watched.insert(dir);
+                }
+                Err(e) => warn!("Failed to watch imports in {}: {}", dir.display(), e),
+            }
+        }
+    }
+
+    /// Record the files `root` imports, replacing those recorded before
+    async fn record_imports(&self, root: &Path, imports: &[ImportedFile]) {
+        let mut imported_by = self.imported_by.lock().await;
+        imported_by.retain(|_, roots| {
+            roots.remove(root);
+            !roots.is_empty()
+        });
+        for file in imports {
+            imported_by
+                .entry(normalize_path(&file.path))
+                .or_default()
+                .insert(root.to_path_buf());
+        }
+    }
+
+    /// Justfiles affected by an event
+    ///
+    /// Besides the justfiles named in the event, this includes justfiles
+    /// importing a file named in the event and known justfiles inside a
+    /// directory that was removed or renamed. Paths the
+    /// platform reports in another form than they were registered under
+    /// (such as `/private/var` for `/var` on macOS) are mapped back, so
+    /// their tools are found.
+    async fn affected_justfiles(&self, event: &Event) -> Vec<PathBuf> {
+        let known: Vec<(PathBuf, PathBuf)> = self
+            .tool_source_map
+            .lock()
+            .await
+            .values()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(|path| (normalize_path(path), path.clone()))
+            .collect();
+
+        let mut affected: Vec<PathBuf> = self
+            .extract_justfile_paths(event)
+            .into_iter()
+            .map(|path| {
+                let normalized = normalize_path(&path);
+                known
+                    .iter()
+                    .find(|(known, _)| *known == normalized)
+                    .map(|(_, registered)| registered.clone())
+                    .unwrap_or(path)
+            })
+            .collect();
+
+        if matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        ) {
+            let imported_by = self.imported_by.lock().await;
+            for path in &event.paths {
+                if let Some(roots) = imported_by.get(&normalize_path(path)) {
+                    affected.extend(roots.iter().cloned());
+                }
+            }
+        }
+
+        if matches!(
+            event.kind,
+            EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_))
+        ) {
+            for path in event.paths.iter().filter(|path| !is_justfile(path)) {
+                let normalized = normalize_path(path);
+                affected.extend(
+                    known
+                        .iter()
+                        .filter(|(known, _)| known.starts_with(&normalized))
+                        .map(|(_, registered)| registered.clone()),
+                );
+            }
+        }
+
+        affected.sort();
+        affected.dedup();
+        affected
+    }
+
+    /// Every justfile that may have changed while events were lost
+    ///
+    /// Includes justfiles currently in watched directories and those that
+    /// already have tools, so deletions are noticed too.
+    async fn rescan_targets(&self) -> HashSet<PathBuf> {
+        let mut targets: HashSet<PathBuf> = self
+            .tool_source_map
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for path in self.watched_paths.lock().await.iter() {
+            if path.is_dir() {
+                targets.extend(find_justfiles(path));
+            } else {
+                targets.insert(path.clone());
+            }
+        }
+        info!("Rescanning {} justfile(s) after lost events", targets.len());
+        targets
+    }
+
+    /// Justfile paths affected by an event
+    ///
+    /// Rename events carry both the old and new path; both are returned so
+    /// the old file's tools are removed and the new file is parsed.
+    fn extract_justfile_paths(&self, event: &Event) -> Vec<PathBuf> {
         match &event.kind {
             EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => event
                 .paths
                 .iter()
-                .find(|p| p.file_name() == Some(std::ffi::OsStr::new("justfile")))
-                .cloned(),
-            _ => None,
+                .filter(|p| is_justfile(p))
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
         }
     }
 
@@ -229,6 +582,15 @@ impl JustfileWatcher {
         &self,
         path: &Path,
     ) -> Result<usize> {
+        Ok(self
+            .parse_and_update_justfile_internal(path, false)
+            .await?
+            .tool_count())
+    }
+
+    /// Parse a justfile again without sending notifications, reporting which
+    /// of its tools clients see a change in
+    pub async fn parse_and_diff_justfile(&self, path: &Path) -> Result<RegistryDelta> {
         self.parse_and_update_justfile_internal(path, false).await
     }
 
@@ -236,49 +598,40 @@ impl JustfileWatcher {
         &self,
         path: &Path,
         send_notification: bool,
-    ) -> Result<usize> {
+    ) -> Result<RegistryDelta> {
         let content = std::fs::read_to_string(path)?;
-        let hash = ToolRegistry::compute_hash(&content);
-        let tasks = self.parser.parse_file_for_tools(path)?;
-
-        let mut registry = self.registry.lock().await;
-        let mut tool_map = self.tool_source_map.lock().await;
-
-        // First, remove all tools from this justfile
-        let tools_to_remove: Vec<String> = tool_map
-            .iter()
-            .filter(|(_, source_path)| source_path == &path)
-            .map(|(name, _)| name.clone())
-            .collect();
-
-        for tool_name in &tools_to_remove {
-            registry.remove_tool(tool_name)?;
-            tool_map.remove(tool_name);
+        // Hash the whole import graph so edits to imported files are
+        // detected, and remember it so their events re-parse this justfile
+        let graph = resolve_import_graph(path)?;
+        self.record_imports(path, &graph[1..]).await;
+        let hash = composite_hash_of(path, &graph)?;
+        let tasks = self
+            .parser
+            .parse_file_for_tools_using(path, self.effective_parser(path))?;
+        let variables = extract_variables(&content);
+
+        // Private recipes are already filtered out
+        let mut tools = Vec::with_capacity(tasks.len());
+        for (source_order, task) in tasks.into_iter().enumerate() {
+            let mut tool = self.task_to_tool(task, &hash, path, &variables).await?;
+            tool.metadata.source_order = Some(source_order);
+            tools.push(tool);
         }
 
-        // Track which tools we've seen
-        let mut seen_tools = HashSet::new();
-
-        // Add or update tools from parsed tasks (private recipes already filtered)
-        for task in tasks {
-            let tool = self.task_to_tool(task, &hash, path).await?;
-            let tool_name = tool.name.clone();
-            seen_tools.insert(tool_name.clone());
-
-            // Track the source path
-            tool_map.insert(tool_name.clone(), path.to_path_buf());
-
-            registry.add_tool(tool)?;
-        }
+        let delta = {
+            let mut registry = self.registry.lock().await;
+            let mut tool_map = self.tool_source_map.lock().await;
+            delta::apply(&mut registry, &mut tool_map, path, tools)?
+        };
 
-        // Send notification if we made any changes and notifications are enabled
-        if send_notification && (!seen_tools.is_empty() || !tools_to_remove.is_empty()) {
+        // Only notify when clients would see a different tool list
+        if send_notification && !delta.is_empty() {
             if let Some(ref sender) = self.notification_sender {
                 let _ = sender.send(Notification::ToolsListChanged);
             }
         }
 
-        Ok(seen_tools.len())
+        Ok(delta)
     }
 
     /// Send a tools list changed notification
@@ -289,6 +642,7 @@ impl JustfileWatcher {
     }
 
     async fn remove_justfile_tools(&self, path: &Path) -> Result<()> {
+        self.record_imports(path, &[]).await;
         let mut registry = self.registry.lock().await;
         let mut tool_map = self.tool_source_map.lock().await;
 
@@ -320,10 +674,14 @@ impl JustfileWatcher {
         task: JustTask,
         hash: &str,
         path: &Path,
+        variables: &[JustfileVariable],
     ) -> Result<ToolDefinition> {
         // Get the configured name for this path
         let path_names = self.path_names.lock().await;
-        let configured_name = path_names.get(path).and_then(|n| n.as_ref());
+        let configured_name = path_names
+            .get(path)
+            .or_else(|| path_names.get(path.parent()?))
+            .and_then(|n| n.as_ref());
 
         // Always create the internal name with full path for execution (keep underscore format for parsing)
         let internal_name = format!("{}_{}", task.name, path.display());
@@ -342,24 +700,51 @@ impl JustfileWatcher {
             task.name.clone()
         };
 
-        // Generate description from comments or use default
-        let description = if task.comments.is_empty() {
-            if self.has_multiple_dirs {
-                if let Some(name) = configured_name {
-                    format!("Execute '{}' task from {}", task.name, name)
-                } else {
-                    format!("Execute '{}' task", task.name)
-                }
+        // Generate description from comments, then `[doc]`, or use default
+        let description = if !task.comments.is_empty() {
+            task.comments.join(". ")
+        } else if let Some(doc) = &task.doc {
+            doc.clone()
+        } else if self.has_multiple_dirs {
+            if let Some(name) = configured_name {
+                format!("Execute '{}' task from {}", task.name, name)
             } else {
                 format!("Execute '{}' task", task.name)
             }
         } else {
-            task.comments.join(". ")
+            format!("Execute '{}' task", task.name)
         };
 
         // Generate JSON schema for parameters
-        let input_schema = self.generate_input_schema(&task.parameters);
+        let mut input_schema = self.generate_input_schema(&task.parameters);
+
+        // Expose template slots as optional arguments
+        let slots = task.annotation_values(SLOT_ANNOTATION);
+        if !slots.is_empty() {
+            self.add_slot_properties(&mut input_schema, &slots, &task.parameters);
+        }
+
+        // Let callers pick a preset argument bundle with `profile`
+        let profiles = task.profiles();
+        let named: Vec<_> = self.profiles.for_recipe(&task.name).collect();
+        if !profiles.is_empty() || !named.is_empty() {
+            self.add_profile_property(&mut input_schema, &profiles, &named, &task.parameters);
+        }
 
+        // Expose referenced justfile variables as optional `var_*` overrides
+        let referenced = referenced_variables(&task.body, variables);
+        if !referenced.is_empty() {
+            self.add_variable_properties(&mut input_schema, &referenced);
+        }
+
+        let no_cd = task.has_annotation(NO_CD_ANNOTATION);
+        // A recipe can be in several groups, each from its own attribute
+        let mut groups = task.annotation_values("group");
+        if let Some(group) = &task.group {
+            if !groups.contains(group) {
+                groups.insert(0, group.clone());
+            }
+        }
         Ok(ToolDefinition {
             name: display_name,
             description,
@@ -368,6 +753,12 @@ impl JustfileWatcher {
             source_hash: hash.to_string(),
             last_modified: SystemTime::now(),
             internal_name: Some(internal_name),
+            metadata: ToolMetadata {
+                no_cd,
+                groups,
+                long_description: task.long_description,
+                source_order: None,
+            },
         })
     }
 
@@ -403,6 +794,141 @@ impl JustfileWatcher {
             "additionalProperties": false
         })
     }
+
+    /// Add optional `var_<name>` properties for justfile variables the recipe references
+    fn add_variable_properties(
+        &self,
+        schema: &mut serde_json::Value,
+        variables: &[&JustfileVariable],
+    ) {
+        let Some(properties) = schema.get_mut("properties").and_then(|p| p.as_object_mut()) else {
+            return;
+        };
+
+        for variable in variables {
+            let sanitized_name = self
+                .security_validator
+                .sanitize_parameter_name(&variable.argument_name());
+            properties.entry(sanitized_name).or_insert_with(|| {
+                json!({
+                    "type": "string",
+                    "description": format!(
+                        "Override justfile variable '{}' (default: {})",
+                        variable.name, variable.expression
+                    )
+                })
+            });
+        }
+    }
+
+    /// Add an optional `profile` property listing the recipe's execution
+    /// profiles and the named profiles that allow it
+    ///
+    /// Skipped when a recipe parameter is itself named `profile`. A named
+    /// profile sharing a name with one of the recipe's own is left out.
+    fn add_profile_property(
+        &self,
+        schema: &mut serde_json::Value,
+        profiles: &[ExecutionProfile],
+        named: &[(&str, &NamedProfile)],
+        parameters: &[Parameter],
+    ) {
+        let Some(properties) = schema.get_mut("properties").and_then(|p| p.as_object_mut()) else {
+            return;
+        };
+        if parameters.iter().any(|p| p.name == PROFILE_ARGUMENT) {
+            if !profiles.is_empty() {
+                warn!("Ignoring execution profiles for a recipe with a 'profile' parameter");
+            }
+            return;
+        }
+
+        let own = profiles.iter().map(|p| (p.name.as_str(), p.summary()));
+        let named = named
+            .iter()
+            .filter(|(name, _)| !profiles.iter().any(|p| p.name == *name))
+            .map(|(name, profile)| (*name, profile.summary()));
+        let entries: Vec<(&str, String)> = own.chain(named).collect();
+        let names: Vec<&str> = entries.iter().map(|(name, _)| *name).collect();
+        let presets = entries
+            .iter()
+            .map(|(name, summary)| {
+                if summary.is_empty() {
+                    format!("{name} (recipe defaults)")
+                } else {
+                    format!("{name} ({summary})")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+        properties.insert(
+            PROFILE_ARGUMENT.to_string(),
+            json!({
+                "type": "string",
+                "enum": names,
+                "description": format!(
+                    "Execution profile presetting arguments: {presets}. Explicit arguments take precedence"
+                )
+            }),
+        );
+    }
+
+    /// Add optional properties for template slots declared with `# [slot: name]`
+    ///
+    /// Slot values are passed to just as variable overrides (`name=value`, like
+    /// `--set`) rather than positional recipe arguments. Slots that collide with a recipe
+    /// parameter are ignored.
+    fn add_slot_properties(
+        &self,
+        schema: &mut serde_json::Value,
+        slots: &[String],
+        parameters: &[Parameter],
+    ) {
+        let Some(properties) = schema.get_mut("properties").and_then(|p| p.as_object_mut()) else {
+            return;
+        };
+
+        for slot in slots {
+            if parameters.iter().any(|p| &p.name == slot) {
+                warn!("Ignoring slot '{}' that shadows a recipe parameter", slot);
+                continue;
+            }
+
+            let sanitized_name = self.security_validator.sanitize_parameter_name(slot);
+            properties.insert(
+                sanitized_name,
+                json!({
+                    "type": "string",
+                    "description": format!("Template slot, passed to just as the variable override `{slot}=<value>`")
+                }),
+            );
+        }
+    }
+}
+
+/// Canonical form of a path that may no longer exist
+///
+/// The longest existing ancestor is canonicalized and the remaining
+/// components are appended, so a deleted file and the event reporting its
+/// deletion normalize to the same path.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut suffix = Vec::new();
+    let mut current = path;
+    loop {
+        if let Ok(canonical) = current.canonicalize() {
+            return suffix
+                .iter()
+                .rev()
+                .fold(canonical, |acc, part| acc.join(part));
+        }
+        match (current.parent(), current.file_name()) {
+            (Some(parent), Some(name)) => {
+                suffix.push(name.to_os_string());
+                current = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -444,6 +970,56 @@ test:
         assert_eq!(tools[0].name, "test");
     }
 
+    #[cfg(feature = "ast-parser")]
+    #[tokio::test]
+    async fn test_parser_overrides_apply_per_watch_directory() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let mut watcher =
+            JustfileWatcher::new_with_parser_preference(registry.clone(), ParserPreference::Ast);
+        watcher.set_multiple_dirs(true);
+
+        let temp_dir = TempDir::new().unwrap();
+        let web = temp_dir.path().join("web");
+        let legacy = temp_dir.path().join("legacy");
+        let nested = legacy.join("tools");
+        for dir in [&web, &legacy, &nested] {
+            fs::create_dir_all(dir).unwrap();
+            fs::write(dir.join("justfile"), "build:\n    echo build\n").unwrap();
+        }
+        watcher.set_parser_overrides(vec![
+            (legacy.clone(), ParserPreference::Cli),
+            (nested.join("justfile"), ParserPreference::Auto),
+        ]);
+
+        assert_eq!(watcher.parser_override(&web.join("justfile")), None);
+        assert_eq!(watcher.effective_parser(&web), &ParserPreference::Ast);
+        assert_eq!(
+            watcher.effective_parser(&legacy.join("justfile")),
+            &ParserPreference::Cli
+        );
+        // The most specific override wins
+        assert_eq!(
+            watcher.effective_parser(&nested.join("justfile")),
+            &ParserPreference::Auto
+        );
+
+        if !EnhancedJustfileParser::is_just_available() {
+            return;
+        }
+        watcher
+            .parse_and_update_justfile(&web.join("justfile"))
+            .await
+            .unwrap();
+        watcher
+            .parse_and_update_justfile(&legacy.join("justfile"))
+            .await
+            .unwrap();
+        let metrics = watcher.parsing_metrics();
+        assert_eq!(metrics.ast_attempts, 1);
+        assert_eq!(metrics.command_attempts, 1);
+        assert_eq!(registry.lock().await.list_tools().len(), 2);
+    }
+
     #[tokio::test]
     async fn test_task_to_tool() {
         let registry = Arc::new(Mutex::new(ToolRegistry::new()));
@@ -472,16 +1048,19 @@ test:
             confirm_message: None,
             doc: Some("Test task documentation".to_string()),
             attributes: vec![],
+            annotations: Vec::new(),
+            long_description: None,
         };
 
         let tool = watcher
-            .task_to_tool(task, "hash123", Path::new("justfile"))
+            .task_to_tool(task, "hash123", Path::new("justfile"), &[])
             .await
             .unwrap();
 
         assert_eq!(tool.description, "Test task");
         assert_eq!(tool.dependencies, vec!["dep1"]);
         assert_eq!(tool.source_hash, "hash123");
+        assert_eq!(tool.metadata.groups, ["test"]);
 
         let schema = tool.input_schema.as_object().unwrap();
         let properties = schema["properties"].as_object().unwrap();
@@ -491,4 +1070,480 @@ test:
         assert_eq!(required.len(), 1);
         assert_eq!(required[0], "arg1");
     }
+
+    #[tokio::test]
+    async fn test_slots_in_input_schema() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = JustfileWatcher::new(registry.clone());
+
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "extra_flags := \"\"\n\n# [slot: extra_flags]\nbuild target=\"debug\":\n    echo {{target}} {{extra_flags}}\n",
+        )
+        .unwrap();
+
+        watcher
+            .parse_and_update_justfile(&justfile_path)
+            .await
+            .unwrap();
+
+        let reg = registry.lock().await;
+        let tool = reg.get_tool("build").unwrap();
+        let properties = tool.input_schema["properties"].as_object().unwrap();
+        assert!(properties.contains_key("target"));
+        assert!(properties.contains_key("extra_flags"));
+
+        let required = tool.input_schema["required"].as_array().unwrap();
+        assert!(!required.iter().any(|r| r == "extra_flags"));
+    }
+
+    #[tokio::test]
+    async fn test_profiles_in_input_schema() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = JustfileWatcher::new(registry.clone());
+
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "# [profile: fast(fast=true)]\n# [profile: full]\ntest fast=\"false\":\n    echo {{fast}}\n",
+        )
+        .unwrap();
+
+        watcher
+            .parse_and_update_justfile(&justfile_path)
+            .await
+            .unwrap();
+
+        let reg = registry.lock().await;
+        let tool = reg.get_tool("test").unwrap();
+        let profile = &tool.input_schema["properties"]["profile"];
+        assert_eq!(profile["enum"], json!(["fast", "full"]));
+        assert!(profile["description"]
+            .as_str()
+            .unwrap()
+            .contains("fast (fast=true); full (recipe defaults)"));
+
+        let required = tool.input_schema["required"].as_array().unwrap();
+        assert!(!required.iter().any(|r| r == "profile"));
+    }
+
+    #[tokio::test]
+    async fn test_named_profiles_in_input_schema() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let mut watcher = JustfileWatcher::new(registry.clone());
+        watcher.set_profiles(Arc::new(
+            ProfileSet::parse(
+                "fast:\n  env:\n    CI: '1'\nprod:\n  description: Production\n  recipes: [deploy]\n",
+            )
+            .unwrap(),
+        ));
+
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "# [profile: fast(fast=true)]\ntest fast=\"false\":\n    echo {{fast}}\n\ndeploy:\n    echo deploy\n",
+        )
+        .unwrap();
+
+        watcher
+            .parse_and_update_justfile(&justfile_path)
+            .await
+            .unwrap();
+
+        let reg = registry.lock().await;
+        // The recipe's own `fast` profile hides the named one
+        let profile = &reg.get_tool("test").unwrap().input_schema["properties"]["profile"];
+        assert_eq!(profile["enum"], json!(["fast"]));
+        let profile = &reg.get_tool("deploy").unwrap().input_schema["properties"]["profile"];
+        assert_eq!(profile["enum"], json!(["fast", "prod"]));
+        assert!(profile["description"]
+            .as_str()
+            .unwrap()
+            .contains("fast (sets CI); prod (Production)"));
+    }
+
+    #[tokio::test]
+    async fn test_source_hash_covers_imports() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = JustfileWatcher::new(registry.clone());
+
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        let imported_path = temp_dir.path().join("common.just");
+        fs::write(
+            &justfile_path,
+            "import 'common.just'
+
+build:\n    echo build\n",
+        )
+        .unwrap();
+        fs::write(&imported_path, "lint:\n    echo lint\n").unwrap();
+
+        watcher
+            .parse_and_update_justfile(&justfile_path)
+            .await
+            .unwrap();
+        let before = registry
+            .lock()
+            .await
+            .get_tool("build")
+            .unwrap()
+            .source_hash
+            .clone();
+
+        fs::write(&imported_path, "lint:\n    echo lint --strict\n").unwrap();
+        watcher
+            .parse_and_update_justfile(&justfile_path)
+            .await
+            .unwrap();
+        let after = registry
+            .lock()
+            .await
+            .get_tool("build")
+            .unwrap()
+            .source_hash
+            .clone();
+
+        assert_ne!(before, after);
+    }
+
+    #[tokio::test]
+    async fn test_variable_overrides_in_input_schema() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = JustfileWatcher::new(registry.clone());
+
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "version := \"1.0.0\"\nunused := \"x\"\n\nrelease:\n    echo {{version}}\n",
+        )
+        .unwrap();
+
+        watcher
+            .parse_and_update_justfile(&justfile_path)
+            .await
+            .unwrap();
+
+        let reg = registry.lock().await;
+        let tool = reg.get_tool("release").unwrap();
+        let properties = tool.input_schema["properties"].as_object().unwrap();
+        assert!(properties.contains_key("var_version"));
+        assert!(!properties.contains_key("var_unused"));
+        assert!(properties["var_version"]["description"]
+            .as_str()
+            .unwrap()
+            .contains("1.0.0"));
+    }
+
+    #[tokio::test]
+    async fn test_extract_justfile_paths_case_variants() {
+        use notify::event::{AccessKind, DataChange, ModifyKind, RenameMode};
+
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = JustfileWatcher::new(registry);
+
+        for name in ["justfile", "Justfile", "JUSTFILE", ".justfile"] {
+            let path = PathBuf::from("/project").join(name);
+            let event = Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Content)))
+                .add_path(path.clone());
+            assert_eq!(watcher.extract_justfile_paths(&event), vec![path]);
+        }
+
+        // Renames report both the old and the new name
+        let rename = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path(PathBuf::from("/project/justfile"))
+            .add_path(PathBuf::from("/project/Justfile"));
+        assert_eq!(
+            watcher.extract_justfile_paths(&rename),
+            vec![
+                PathBuf::from("/project/justfile"),
+                PathBuf::from("/project/Justfile")
+            ]
+        );
+
+        let unrelated = Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Content)))
+            .add_path(PathBuf::from("/project/justfile.bak"));
+        assert!(watcher.extract_justfile_paths(&unrelated).is_empty());
+        let access = Event::new(EventKind::Access(AccessKind::Any))
+            .add_path(PathBuf::from("/project/Justfile"));
+        assert!(watcher.extract_justfile_paths(&access).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rename_to_capitalized_justfile() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let mut watcher = JustfileWatcher::new(registry.clone());
+        watcher.set_multiple_dirs(true);
+
+        let temp_dir = TempDir::new().unwrap();
+        watcher
+            .configure_names(&[(temp_dir.path().to_path_buf(), Some("app".to_string()))])
+            .await;
+
+        let old_path = temp_dir.path().join("justfile");
+        let new_path = temp_dir.path().join("Justfile");
+        fs::write(&old_path, "build:\n    echo build\n").unwrap();
+        watcher.parse_and_update_justfile(&old_path).await.unwrap();
+
+        fs::rename(&old_path, &new_path).unwrap();
+        for path in [&new_path, &old_path] {
+            watcher.handle_justfile_change(path).await.unwrap();
+        }
+
+        let reg = registry.lock().await;
+        let tools = reg.list_tools();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "build@app");
+        assert_eq!(
+            tools[0].internal_name.as_deref(),
+            Some(format!("build_{}", new_path.display()).as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parameter_docs_and_long_description() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = JustfileWatcher::new(registry.clone());
+
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            r#"# Release a new version.
+#
+# Tags the commit and pushes the tag.
+# {{version}}: Semantic version to release,
+#     without the leading v
+# [doc(remote): Git remote to push to]
+release version remote="origin":
+    git tag v{{version}} && git push {{remote}} v{{version}}
+"#,
+        )
+        .unwrap();
+
+        watcher
+            .parse_and_update_justfile(&justfile_path)
+            .await
+            .unwrap();
+
+        let reg = registry.lock().await;
+        let tool = reg.get_tool("release").unwrap();
+        let properties = &tool.input_schema["properties"];
+        assert_eq!(
+            properties["version"]["description"],
+            "Semantic version to release, without the leading v"
+        );
+        assert_eq!(properties["remote"]["description"], "Git remote to push to");
+        assert_eq!(
+            tool.metadata.long_description.as_deref(),
+            Some("Release a new version.\n\nTags the commit and pushes the tag.")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_cd_in_tool_metadata() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = JustfileWatcher::new(registry.clone());
+
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "# Format the given files\n[no-cd]\nfmt files:\n    rustfmt {{files}}\n\nbuild:\n    cargo build\n",
+        )
+        .unwrap();
+
+        watcher
+            .parse_and_update_justfile(&justfile_path)
+            .await
+            .unwrap();
+
+        let reg = registry.lock().await;
+        let fmt = reg.get_tool("fmt").unwrap();
+        assert!(fmt.metadata.no_cd);
+        assert_eq!(
+            serde_json::to_value(fmt).unwrap()["metadata"]["no_cd"],
+            true
+        );
+        assert!(!reg.get_tool("build").unwrap().metadata.no_cd);
+    }
+
+    #[tokio::test]
+    async fn test_groups_and_source_order_in_tool_metadata() {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = JustfileWatcher::new(registry.clone());
+
+        let temp_dir = TempDir::new().unwrap();
+        let justfile_path = temp_dir.path().join("justfile");
+        fs::write(
+            &justfile_path,
+            "zeta:\n    echo z\n\n[group('ops')]\ndeploy:\n    echo d\n\n[group('dev')]\n[group('ops')]\nbuild:\n    echo b\n",
+        )
+        .unwrap();
+
+        watcher
+            .parse_and_update_justfile(&justfile_path)
+            .await
+            .unwrap();
+
+        let reg = registry.lock().await;
+        let metadata = |name: &str| reg.get_tool(name).unwrap().metadata.clone();
+        assert_eq!(metadata("zeta").source_order, Some(0));
+        assert!(metadata("zeta").groups.is_empty());
+        assert_eq!(metadata("deploy").source_order, Some(1));
+        assert_eq!(metadata("deploy").groups, ["ops"]);
+        assert_eq!(metadata("build").source_order, Some(2));
+        assert_eq!(metadata("build").groups, ["dev", "ops"]);
+    }
+
+    /// Start a watcher on `watch_path` and wait until `justfile`'s tools are registered
+    async fn watch_project(
+        watch_path: std::path::PathBuf,
+    ) -> (
+        Arc<Mutex<ToolRegistry>>,
+        crate::notification::NotificationReceiver,
+        tokio::task::JoinHandle<()>,
+    ) {
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let (sender, receiver) = crate::notification::channel();
+        let watcher = JustfileWatcher::new(registry.clone()).with_notification_sender(sender);
+        let handle = tokio::spawn(async move {
+            let _ = watcher.watch_paths(vec![watch_path]).await;
+        });
+        assert!(wait_for_tool_count(&registry, 1).await);
+        (registry, receiver, handle)
+    }
+
+    async fn wait_for_tool_count(registry: &Arc<Mutex<ToolRegistry>>, expected: usize) -> bool {
+        for _ in 0..100 {
+            if registry.lock().await.list_tools().len() == expected {
+                return true;
+            }
+            sleep(Duration::from_millis(50)).await;
+        }
+        false
+    }
+
+    async fn assert_tools_removed(
+        registry: &Arc<Mutex<ToolRegistry>>,
+        receiver: &mut crate::notification::NotificationReceiver,
+    ) {
+        assert!(
+            wait_for_tool_count(registry, 0).await,
+            "tools still registered: {:?}",
+            registry
+                .lock()
+                .await
+                .list_tools()
+                .iter()
+                .map(|t| t.name.clone())
+                .collect::<Vec<_>>()
+        );
+        let notification = tokio::time::timeout(Duration::from_secs(1), receiver.recv())
+            .await
+            .expect("no list_changed notification");
+        assert!(matches!(
+            notification,
+            Some(crate::notification::Notification::ToolsListChanged)
+        ));
+    }
+
+    fn project_dir(parent: &std::path::Path) -> std::path::PathBuf {
+        let dir = parent.join("project");
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("justfile"), "build:\n    cargo build\n").unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_renamed_justfile_removes_tools() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = project_dir(temp_dir.path());
+        let (registry, mut receiver, handle) = watch_project(dir.clone()).await;
+
+        fs::rename(dir.join("justfile"), dir.join("justfile.bak")).unwrap();
+        assert_tools_removed(&registry, &mut receiver).await;
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_deleted_watch_dir_removes_tools() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = project_dir(temp_dir.path());
+        let (registry, mut receiver, handle) = watch_project(dir.clone()).await;
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_tools_removed(&registry, &mut receiver).await;
+
+        // Recreating the directory registers the tools again
+        project_dir(temp_dir.path());
+        assert!(wait_for_tool_count(&registry, 1).await);
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_renamed_parent_dir_removes_tools() {
+        let temp_dir = TempDir::new().unwrap();
+        let parent = temp_dir.path().join("workspace");
+        fs::create_dir(&parent).unwrap();
+        let dir = project_dir(&parent);
+        let (registry, mut receiver, handle) = watch_project(dir).await;
+
+        fs::rename(&parent, temp_dir.path().join("workspace.old")).unwrap();
+        assert_tools_removed(&registry, &mut receiver).await;
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_edited_import_updates_tools() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("project");
+        fs::create_dir_all(dir.join("ci")).unwrap();
+        fs::write(
+            dir.join("justfile"),
+            "import 'ci/common.just'\n\nbuild:\n    cargo build\n",
+        )
+        .unwrap();
+        fs::write(dir.join("ci/common.just"), "lint:\n    cargo clippy\n").unwrap();
+
+        let registry = Arc::new(Mutex::new(ToolRegistry::new()));
+        let watcher = JustfileWatcher::new(registry.clone());
+        let watch_dir = dir.clone();
+        let handle = tokio::spawn(async move {
+            let _ = watcher.watch_paths(vec![watch_dir]).await;
+        });
+        assert!(wait_for_tool_count(&registry, 2).await);
+
+        // Give the watcher a tick to watch the import's directory
+        sleep(Duration::from_millis(700)).await;
+        fs::write(
+            dir.join("ci/common.just"),
+            "lint:\n    cargo clippy\n\ntest:\n    cargo test\n",
+        )
+        .unwrap();
+        assert!(wait_for_tool_count(&registry, 3).await);
+        assert!(registry.lock().await.get_tool("test").is_some());
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_watched_justfile_path_renamed() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = project_dir(temp_dir.path());
+        let (registry, mut receiver, handle) = watch_project(dir.join("justfile")).await;
+
+        fs::rename(dir.join("justfile"), dir.join("old.just")).unwrap();
+        assert_tools_removed(&registry, &mut receiver).await;
+
+        handle.abort();
+    }
 }
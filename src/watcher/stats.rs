@@ -0,0 +1,215 @@
+//! Watcher health metrics
+//!
+//! Tracks how far the tool registry may lag behind the justfiles on disk:
+//! events waiting in the queue, events lost before they reached the watcher,
+//! how long debounced updates take to apply, and the last event seen for
+//! each justfile. A snapshot is available through
+//! [`JustfileWatcher::stats`](super::JustfileWatcher::stats) and the
+//! `_admin_status` tool, and every applied batch is emitted as a structured
+//! event under the `just_mcp::metrics` tracing target.
+
+use super::supervisor::SupervisionStatus;
+use chrono::{DateTime, Utc};
+use notify::EventKind;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Last event received for a justfile
+#[derive(Debug, Clone, Serialize)]
+pub struct PathEvent {
+    /// `create`, `modify` or `remove`
+    pub kind: &'static str,
+    pub received_at: DateTime<Utc>,
+    /// When the resulting update was applied; `None` while still pending
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+/// Snapshot of watcher health
+#[derive(Debug, Clone, Serialize)]
+pub struct WatcherStats {
+    /// Events received from the filesystem watcher
+    pub events_received: u64,
+    /// Events lost because the queue was full or the watcher reported an
+    /// error; each triggers a full rescan
+    pub events_dropped: u64,
+    /// Events waiting in the queue
+    pub queue_depth: usize,
+    pub queue_capacity: usize,
+    /// Justfiles with changes waiting for the debounce window to close
+    pub pending_updates: usize,
+    /// Batches of debounced updates applied to the registry
+    pub updates_applied: u64,
+    /// Time from the first event of the last batch until it was applied
+    pub last_update_latency_ms: Option<u64>,
+    pub max_update_latency_ms: u64,
+    /// Last event per justfile path
+    pub last_events: BTreeMap<String, PathEvent>,
+    /// Whether the watcher loop is running or being restarted
+    pub supervision: SupervisionStatus,
+}
+
+/// Shared counters updated by the watcher loop and the notify callback
+#[derive(Debug)]
+pub(crate) struct WatcherMetrics {
+    pub queue_capacity: usize,
+    events_received: AtomicU64,
+    events_dropped: AtomicU64,
+    queue_depth: AtomicUsize,
+    pending_updates: AtomicUsize,
+    rescan_needed: AtomicBool,
+    updates_applied: AtomicU64,
+    last_latency_ms: Mutex<Option<u64>>,
+    max_latency_ms: AtomicU64,
+    last_events: Mutex<HashMap<PathBuf, PathEvent>>,
+}
+
+impl WatcherMetrics {
+    pub fn new(queue_capacity: usize) -> Self {
+        Self {
+            queue_capacity,
+            events_received: AtomicU64::new(0),
+            events_dropped: AtomicU64::new(0),
+            queue_depth: AtomicUsize::new(0),
+            pending_updates: AtomicUsize::new(0),
+            rescan_needed: AtomicBool::new(false),
+            updates_applied: AtomicU64::new(0),
+            last_latency_ms: Mutex::new(None),
+            max_latency_ms: AtomicU64::new(0),
+            last_events: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// An event was queued for the watcher loop
+    pub fn enqueued(&self) {
+        self.queue_depth.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// An event was lost; the next flush rescans every watched justfile
+    pub fn dropped(&self) {
+        self.events_dropped.fetch_add(1, Ordering::Relaxed);
+        self.rescan_needed.store(true, Ordering::Relaxed);
+    }
+
+    /// Rescan every watched justfile at the next flush
+    pub fn request_rescan(&self) {
+        self.rescan_needed.store(true, Ordering::Relaxed);
+    }
+
+    /// The watcher loop took an event off the queue
+    pub fn received(&self, kind: &EventKind, justfiles: &[PathBuf]) {
+        self.events_received.fetch_add(1, Ordering::Relaxed);
+        let _ = self
+            .queue_depth
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |d| d.checked_sub(1));
+
+        let kind = match kind {
+            EventKind::Create(_) => "create",
+            EventKind::Remove(_) => "remove",
+            _ => "modify",
+        };
+        let mut last_events = self.last_events.lock().unwrap();
+        for path in justfiles {
+            last_events.insert(
+                path.clone(),
+                PathEvent {
+                    kind,
+                    received_at: Utc::now(),
+                    applied_at: None,
+                },
+            );
+        }
+    }
+
+    pub fn set_pending(&self, pending: usize) {
+        self.pending_updates.store(pending, Ordering::Relaxed);
+    }
+
+    /// Whether a rescan was requested since the last call
+    pub fn take_rescan(&self) -> bool {
+        self.rescan_needed.swap(false, Ordering::Relaxed)
+    }
+
+    /// A batch of updates was applied; returns the batch latency
+    pub fn applied(&self, paths: &[PathBuf], batch_started: Instant) -> Duration {
+        let latency = batch_started.elapsed();
+        let latency_ms = latency.as_millis() as u64;
+        self.updates_applied.fetch_add(1, Ordering::Relaxed);
+        *self.last_latency_ms.lock().unwrap() = Some(latency_ms);
+        self.max_latency_ms.fetch_max(latency_ms, Ordering::Relaxed);
+
+        let mut last_events = self.last_events.lock().unwrap();
+        for path in paths {
+            if let Some(event) = last_events.get_mut(path) {
+                event.applied_at = Some(Utc::now());
+            }
+        }
+        latency
+    }
+
+    pub fn snapshot(&self) -> WatcherStats {
+        WatcherStats {
+            events_received: self.events_received.load(Ordering::Relaxed),
+            events_dropped: self.events_dropped.load(Ordering::Relaxed),
+            queue_depth: self.queue_depth.load(Ordering::Relaxed),
+            queue_capacity: self.queue_capacity,
+            pending_updates: self.pending_updates.load(Ordering::Relaxed),
+            updates_applied: self.updates_applied.load(Ordering::Relaxed),
+            last_update_latency_ms: *self.last_latency_ms.lock().unwrap(),
+            max_update_latency_ms: self.max_latency_ms.load(Ordering::Relaxed),
+            last_events: self
+                .last_events
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(path, event)| (path.display().to_string(), event.clone()))
+                .collect(),
+            supervision: SupervisionStatus::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, ModifyKind};
+
+    #[test]
+    fn test_metrics_track_queue_and_latency() {
+        let metrics = WatcherMetrics::new(100);
+        let path = PathBuf::from("/project/justfile");
+
+        metrics.enqueued();
+        metrics.enqueued();
+        assert_eq!(metrics.snapshot().queue_depth, 2);
+
+        metrics.received(&EventKind::Create(CreateKind::File), &[path.clone()]);
+        metrics.received(&EventKind::Modify(ModifyKind::Any), &[path.clone()]);
+        let stats = metrics.snapshot();
+        assert_eq!(stats.queue_depth, 0);
+        assert_eq!(stats.events_received, 2);
+        let event = &stats.last_events["/project/justfile"];
+        assert_eq!(event.kind, "modify");
+        assert!(event.applied_at.is_none());
+
+        let started = Instant::now() - Duration::from_millis(20);
+        metrics.applied(&[path], started);
+        let stats = metrics.snapshot();
+        assert_eq!(stats.updates_applied, 1);
+        assert!(stats.last_update_latency_ms.unwrap() >= 20);
+        assert!(stats.last_events["/project/justfile"].applied_at.is_some());
+    }
+
+    #[test]
+    fn test_dropped_events_request_rescan() {
+        let metrics = WatcherMetrics::new(1);
+        assert!(!metrics.take_rescan());
+        metrics.dropped();
+        assert_eq!(metrics.snapshot().events_dropped, 1);
+        assert!(metrics.take_rescan());
+        assert!(!metrics.take_rescan());
+    }
+}
@@ -0,0 +1,274 @@
+//! Supervision of the watcher loop
+//!
+//! [`JustfileWatcher::watch_paths`] runs until the process exits, unless the
+//! notify backend fails or the loop panics. Without supervision the server
+//! then keeps serving a frozen tool list. [`JustfileWatcher::watch_supervised`]
+//! runs the loop as a task, notices when it ends, logs a warning and starts
+//! it again after a backoff. Each restart rescans the watch directories, and
+//! tools of justfiles removed in the meantime are collected before it.
+//!
+//! The state is part of the watcher stats in `_admin_status`. While the loop
+//! is down, recipe tool results carry a warning that the tool list may be
+//! stale.
+
+use super::JustfileWatcher;
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Backoff between restarts of the watcher loop
+#[derive(Debug, Clone, Copy)]
+pub struct RestartPolicy {
+    /// Delay before the first restart
+    pub initial_backoff: Duration,
+    /// Upper bound the delay doubles up to
+    pub max_backoff: Duration,
+    /// A loop that ran this long before failing restarts after the initial
+    /// delay again
+    pub stable_after: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            stable_after: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Whether the watcher loop is running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatcherState {
+    /// Not started under supervision
+    #[default]
+    Unsupervised,
+    Running,
+    /// Ended and waiting for its restart
+    Restarting,
+}
+
+/// Supervision state, as reported in the watcher stats
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SupervisionStatus {
+    pub state: WatcherState,
+    /// Times the loop was restarted
+    pub restarts: u64,
+    /// Why the loop last ended
+    pub last_failure: Option<String>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    /// When the loop is started again, while restarting
+    pub next_restart_at: Option<DateTime<Utc>>,
+}
+
+/// Supervision state shared by the supervisor and the tool handlers
+#[derive(Debug, Default)]
+pub struct Supervision {
+    status: Mutex<SupervisionStatus>,
+}
+
+impl Supervision {
+    pub fn status(&self) -> SupervisionStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Warning for tool results while the tool list may be stale
+    pub fn warning(&self) -> Option<String> {
+        let status = self.status.lock().unwrap();
+        (status.state == WatcherState::Restarting).then(|| {
+            format!(
+                "The justfile watcher stopped ({}) and is restarting; the tool list may not reflect recent justfile changes",
+                status.last_failure.as_deref().unwrap_or("unknown cause")
+            )
+        })
+    }
+
+    fn running(&self) {
+        let mut status = self.status.lock().unwrap();
+        status.state = WatcherState::Running;
+        status.next_restart_at = None;
+    }
+
+    fn failed(&self, reason: String, backoff: Duration) {
+        let now = Utc::now();
+        let mut status = self.status.lock().unwrap();
+        status.state = WatcherState::Restarting;
+        status.last_failure = Some(reason);
+        status.last_failure_at = Some(now);
+        status.next_restart_at = chrono::Duration::from_std(backoff)
+            .ok()
+            .map(|backoff| now + backoff);
+    }
+
+    fn restarted(&self) {
+        self.status.lock().unwrap().restarts += 1;
+    }
+}
+
+/// Aborts the watcher loop when the supervisor is aborted
+struct AbortOnDrop(tokio::task::AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl JustfileWatcher {
+    /// Supervision state of the watcher loop
+    pub fn supervision(&self) -> Arc<Supervision> {
+        self.supervision.clone()
+    }
+
+    /// Watch `paths`, restarting the watcher loop whenever it ends
+    ///
+    /// Returns only when the task running it is aborted, which also stops
+    /// the loop.
+    pub async fn watch_supervised(self: Arc<Self>, paths: Vec<PathBuf>, policy: RestartPolicy) {
+        let supervision = self.supervision.clone();
+        supervise(&supervision, policy, |restart| {
+            let watcher = self.clone();
+            let paths = paths.clone();
+            async move {
+                if restart {
+                    // The loop rescans the watch directories; tools of
+                    // justfiles removed while it was down are dropped first
+                    let report = watcher.collect_garbage(false).await?;
+                    if !report.is_clean() {
+                        info!(
+                            "Cleaned up {} registry entries before restarting the watcher",
+                            report.total()
+                        );
+                        watcher.send_tools_changed_notification();
+                    }
+                }
+                watcher.watch_paths(paths).await
+            }
+        })
+        .await
+    }
+}
+
+/// Run the task `start` creates, starting it again after a backoff whenever
+/// it ends; `start` is told whether this is a restart
+async fn supervise<F, Fut>(supervision: &Supervision, policy: RestartPolicy, mut start: F)
+where
+    F: FnMut(bool) -> Fut,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    let mut backoff = policy.initial_backoff;
+    let mut restart = false;
+    loop {
+        if restart {
+            supervision.restarted();
+            info!("Restarting justfile watcher");
+        }
+        supervision.running();
+        let started = Instant::now();
+        let task = tokio::spawn(start(restart));
+        let _guard = AbortOnDrop(task.abort_handle());
+
+        let reason = match task.await {
+            Ok(Ok(())) => "watcher loop exited".to_string(),
+            Ok(Err(e)) => format!("watcher error: {e}"),
+            Err(e) if e.is_panic() => format!("watcher panicked: {}", panic_message(e)),
+            // Only cancelled when the supervisor itself is aborted
+            Err(_) => return,
+        };
+
+        if started.elapsed() >= policy.stable_after {
+            backoff = policy.initial_backoff;
+        }
+        warn!(
+            "Justfile watcher stopped ({}), restarting in {:?}",
+            reason, backoff
+        );
+        supervision.failed(reason, backoff);
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(policy.max_backoff);
+        restart = true;
+    }
+}
+
+fn panic_message(error: tokio::task::JoinError) -> String {
+    let payload = error.into_panic();
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "no message".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_watcher_loop_is_restarted() {
+        let supervision = Arc::new(Supervision::default());
+        let policy = RestartPolicy {
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(20),
+            stable_after: Duration::from_secs(60),
+        };
+        let starts = Arc::new(AtomicUsize::new(0));
+        let (restarted_tx, mut restarted_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let task = {
+            let supervision = supervision.clone();
+            let starts = starts.clone();
+            tokio::spawn(async move {
+                supervise(&supervision, policy, |restart| {
+                    let attempt = starts.fetch_add(1, Ordering::SeqCst);
+                    let restarted_tx = restarted_tx.clone();
+                    async move {
+                        match attempt {
+                            0 => Err(Error::Io(std::io::Error::other("inotify limit"))),
+                            1 => panic!("backend gone"),
+                            _ => {
+                                restarted_tx.send(restart).unwrap();
+                                std::future::pending().await
+                            }
+                        }
+                    }
+                })
+                .await
+            })
+        };
+
+        // Third start, after an error and a panic, is a restart that stays up
+        assert!(restarted_rx.recv().await.unwrap());
+        let status = supervision.status();
+        assert_eq!(status.state, WatcherState::Running);
+        assert_eq!(status.restarts, 2);
+        assert!(status.last_failure.unwrap().contains("backend gone"));
+        assert!(supervision.warning().is_none());
+
+        task.abort();
+        assert!(task.await.unwrap_err().is_cancelled());
+        assert_eq!(starts.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_warning_while_restarting() {
+        let supervision = Supervision::default();
+        assert!(supervision.warning().is_none());
+        supervision.failed(
+            "watcher error: inotify limit".to_string(),
+            Duration::from_secs(1),
+        );
+        let status = supervision.status();
+        assert_eq!(status.state, WatcherState::Restarting);
+        assert!(status.next_restart_at.is_some());
+        assert!(supervision.warning().unwrap().contains("inotify limit"));
+    }
+}
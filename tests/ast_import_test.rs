@@ -1,3 +1,5 @@
+#![cfg(feature = "ast-parser")]
+
 use just_mcp::parser::ast::ASTJustParser;
 use std::fs;
 use tempfile::TempDir;
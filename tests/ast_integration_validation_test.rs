@@ -4,12 +4,16 @@
 //! all existing project recipes and ensures consistency with the regex parser.
 
 use anyhow::Result;
-use just_mcp::parser::{EnhancedJustfileParser, JustfileParser, ParserPreference};
+#[cfg(feature = "ast-parser")]
+use just_mcp::parser::ParserPreference;
+use just_mcp::parser::{EnhancedJustfileParser, JustfileParser};
 #[cfg(feature = "ast-parser")]
 use just_mcp::types::JustTask;
 #[cfg(feature = "ast-parser")]
 use std::collections::HashMap;
 use std::fs;
+#[cfg(feature = "ast-parser")]
+use std::path::Path;
 use std::path::PathBuf;
 #[cfg(feature = "ast-parser")]
 use std::time::Instant;
@@ -66,12 +70,10 @@ fn get_all_project_justfiles() -> Result<Vec<PathBuf>> {
 
     // Modular justfiles in just/ directory
     if let Ok(entries) = fs::read_dir("just") {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                if path.is_file() && path.extension().is_some_and(|ext| ext == "just") {
-                    justfiles.push(path);
-                }
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "just") {
+                justfiles.push(path);
             }
         }
     }
@@ -81,7 +83,7 @@ fn get_all_project_justfiles() -> Result<Vec<PathBuf>> {
 
 /// Test individual justfile with both AST and regex parsers
 #[cfg(feature = "ast-parser")]
-fn test_justfile_parsing(path: &PathBuf) -> Result<ParsingTestResult> {
+fn test_justfile_parsing(path: &Path) -> Result<ParsingTestResult> {
     let file_path = path.to_string_lossy().to_string();
     let mut result = ParsingTestResult {
         file_path: file_path.clone(),
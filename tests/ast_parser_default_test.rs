@@ -1,6 +1,8 @@
 //! Test to verify AST parser is used by default when available
 
-use just_mcp::parser::{EnhancedJustfileParser, ParserPreference};
+use just_mcp::parser::EnhancedJustfileParser;
+#[cfg(feature = "ast-parser")]
+use just_mcp::parser::ParserPreference;
 #[cfg(feature = "ast-parser")]
 use tempfile::TempDir;
 
@@ -35,7 +35,7 @@ mod ast_parser_foundation_tests {
     fn test_basic_content_parsing() {
         let mut parser = ASTJustParser::new().unwrap();
 
-        let test_cases = vec![
+        let test_cases = [
             // Simple recipe
             "hello:\n    echo \"world\"",
             // Recipe with comments
@@ -397,6 +397,7 @@ test:
 mod no_ast_parser_tests {
     /// Test that ensures AST parser functionality is properly gated behind feature flag
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_ast_parser_feature_gated() {
         // This test verifies that when the ast-parser feature is not enabled,
         // the code compiles correctly without the tree-sitter dependencies.
@@ -312,6 +312,7 @@ global-command:
 #[cfg(not(feature = "ast-parser"))]
 mod no_ast_parser_tests {
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_attribute_parsing_feature_gated() {
         // This test verifies that when the ast-parser feature is not enabled,
         // the code compiles correctly without the tree-sitter dependencies.
@@ -231,3 +231,96 @@ fn test_integration_with_existing_arguments() {
     let preference = args.parser.parse::<ParserPreference>().unwrap();
     assert_eq!(preference, ParserPreference::Ast);
 }
+
+#[test]
+fn test_project_parser_overrides() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let web = temp_dir.path().join("web");
+    let legacy = temp_dir.path().join("legacy");
+    std::fs::create_dir_all(&web).unwrap();
+    std::fs::create_dir_all(&legacy).unwrap();
+
+    let args = Args::try_parse_from([
+        "just-mcp".to_string(),
+        "--project-parser".to_string(),
+        "web=cli".to_string(),
+        "--project-parser".to_string(),
+        format!("{}=ast", legacy.display()),
+    ])
+    .unwrap();
+    assert_eq!(
+        args.project_parsers,
+        vec![
+            ("web".to_string(), ParserPreference::Cli),
+            (legacy.display().to_string(), ParserPreference::Ast)
+        ]
+    );
+
+    let watch_configs = vec![
+        (web.clone(), Some("web".to_string())),
+        (legacy.clone(), None),
+    ];
+    let overrides = args.parser_overrides(&watch_configs).unwrap();
+    assert_eq!(
+        overrides,
+        vec![
+            (web, ParserPreference::Cli),
+            (legacy, ParserPreference::Ast)
+        ]
+    );
+
+    // Unknown names are rejected instead of silently ignored
+    let args = Args::try_parse_from(["just-mcp", "--project-parser", "nowhere=cli"]).unwrap();
+    assert!(args.parser_overrides(&watch_configs).is_err());
+
+    assert!(Args::try_parse_from(["just-mcp", "--project-parser", "web=fast"]).is_err());
+    assert!(Args::try_parse_from(["just-mcp", "--project-parser", "web"]).is_err());
+}
+
+#[test]
+fn test_init_command_arguments() {
+    use just_mcp::cli::Commands;
+
+    let args = Args::try_parse_from(["just-mcp", "init", "--template", "rust"]).unwrap();
+    match args.command {
+        Some(Commands::Init {
+            template,
+            dir,
+            force,
+        }) => {
+            assert_eq!(template.as_deref(), Some("rust"));
+            assert_eq!(dir, std::path::PathBuf::from("."));
+            assert!(!force);
+        }
+        other => panic!("expected init, got {other:?}"),
+    }
+
+    let args = Args::try_parse_from(["just-mcp", "init"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Commands::Init { template: None, .. })
+    ));
+}
+
+#[test]
+#[cfg(feature = "ultrafast-framework")]
+fn test_transport_argument() {
+    use just_mcp::server::transports::TransportSpec;
+
+    let args = Args::try_parse_from(["just-mcp"]).unwrap();
+    assert_eq!(args.transport, vec![TransportSpec::Stdio]);
+
+    let args = Args::try_parse_from(["just-mcp", "--transport", "stdio,http:8900"]).unwrap();
+    assert_eq!(
+        args.transport,
+        vec![
+            TransportSpec::Stdio,
+            TransportSpec::Http {
+                host: "127.0.0.1".to_string(),
+                port: 8900
+            }
+        ]
+    );
+
+    assert!(Args::try_parse_from(["just-mcp", "--transport", "sse:8900"]).is_err());
+}
@@ -511,6 +511,7 @@ mod conditional_function_tests {
 #[cfg(not(feature = "ast-parser"))]
 mod no_ast_parser_tests {
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_conditional_function_feature_gated() {
         // This test verifies that when the ast-parser feature is not enabled,
         // the code compiles correctly without the tree-sitter dependencies.
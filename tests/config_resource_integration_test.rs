@@ -46,6 +46,7 @@ fn create_test_args() -> Args {
         command: None,
         watch_dir: vec!["./test-dir".to_string()],
         admin: true,
+        allow_inline_justfiles: false,
         json_logs: false,
         log_level: "debug".to_string(),
         parser: "auto".to_string(),
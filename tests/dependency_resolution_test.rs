@@ -438,6 +438,7 @@ setup: deploy
 #[cfg(not(feature = "ast-parser"))]
 mod no_ast_parser_tests {
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_dependency_resolution_feature_gated() {
         // Verify dependency resolution functionality is properly feature-gated
         assert!(
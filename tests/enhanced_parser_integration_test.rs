@@ -215,6 +215,8 @@ deploy: build test
     assert_eq!(deploy_task.dependencies, vec!["build", "test"]);
 }
 
+// Inline `if` expressions in this justfile are only handled by the AST parser
+#[cfg(feature = "ast-parser")]
 #[test]
 fn test_enhanced_parser_complex_justfile() {
     let parser = EnhancedJustfileParser::new().unwrap();
@@ -446,6 +448,7 @@ fn test_parsing_metrics_and_diagnostics() {
 }
 
 /// Test parser configuration and method selection
+#[cfg(feature = "ast-parser")]
 #[test]
 fn test_parser_configuration_and_method_selection() {
     let mut parser = EnhancedJustfileParser::new().expect("Failed to create parser");
@@ -121,7 +121,7 @@ mod tests {
 
         // Verify message structure
         let messages = &prompt_detail.messages;
-        assert!(messages.len() >= 1, "Expected at least one message");
+        assert!(!messages.is_empty(), "Expected at least one message");
 
         // Check first message (combined system and user due to MCP protocol limitations)
         let first_msg = &messages[0];
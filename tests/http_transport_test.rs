@@ -0,0 +1,76 @@
+//! Streamable HTTP transport on a loopback address other than 127.0.0.1
+
+#![cfg(feature = "http")]
+
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const HOST: &str = "127.0.0.2";
+
+/// POST a JSON-RPC message to `/mcp` and return the raw HTTP response
+async fn post(port: u16, origin: &str, body: &str) -> std::io::Result<String> {
+    let mut stream = TcpStream::connect((HOST, port)).await?;
+    let request = format!(
+        "POST /mcp HTTP/1.1\r\nHost: {HOST}:{port}\r\nOrigin: {origin}\r\n\
+         Content-Type: application/json\r\nAccept: application/json, text/event-stream\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(request.as_bytes()).await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    Ok(response)
+}
+
+#[tokio::test]
+async fn test_initialize_on_non_default_loopback_address() {
+    let Ok(listener) = TcpListener::bind((HOST, 0)).await else {
+        eprintln!("skipping: cannot bind {HOST}");
+        return;
+    };
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let watch_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        watch_dir.path().join("justfile"),
+        "build:\n    echo build\n",
+    )
+    .unwrap();
+    let mut server = tokio::process::Command::new(env!("CARGO_BIN_EXE_just-mcp"))
+        .arg("--watch-dir")
+        .arg(watch_dir.path())
+        .arg("--transport")
+        .arg(format!("http:{HOST}:{port}"))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+
+    let initialize = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-06-18","capabilities":{},"clientInfo":{"name":"test","version":"1.0"}}}"#;
+    let origin = format!("http://{HOST}:{port}");
+    let mut response = None;
+    for _ in 0..100 {
+        if let Ok(text) = post(port, &origin, initialize).await {
+            response = Some(text);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let response = response.expect("server never accepted connections");
+    assert!(
+        response.contains("\"serverInfo\""),
+        "initialize failed: {response}"
+    );
+    assert!(!response.contains("Origin not allowed"));
+
+    // Other origins are still refused
+    let response = post(port, "http://evil.example", initialize).await.unwrap();
+    assert!(!response.contains("\"serverInfo\""), "{response}");
+
+    server.kill().await.unwrap();
+}
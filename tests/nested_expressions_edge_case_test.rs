@@ -321,8 +321,8 @@ mod nested_expressions_edge_cases {
             assert!(conditional.is_valid());
         } else {
             // If it doesn't parse as a conditional, that's also acceptable
+            // Test passes either way
             println!("Complex circular expression didn't parse as conditional - this is expected");
-            assert!(true); // Test passes either way
         }
     }
 
@@ -355,6 +355,7 @@ mod nested_expressions_edge_cases {
 #[cfg(not(feature = "ast-parser"))]
 mod no_ast_parser_tests {
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_nested_expressions_feature_gated() {
         // This test verifies that when the ast-parser feature is not enabled,
         // the code compiles correctly without the tree-sitter dependencies.
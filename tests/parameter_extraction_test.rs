@@ -584,6 +584,7 @@ run-tests +args:
 #[cfg(not(feature = "ast-parser"))]
 mod no_ast_parser_tests {
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_parameter_extraction_feature_gated() {
         // Verify parameter extraction is properly feature-gated
         assert!(
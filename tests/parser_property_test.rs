@@ -0,0 +1,90 @@
+//! Property-based tests: parsers must not panic on malformed justfiles
+//!
+//! Inputs are assembled from justfile fragments — recipe headers,
+//! attributes, interpolations, unbalanced quotes and braces — so they get
+//! much further into the parsers than random bytes would.
+
+use just_mcp::parser::JustfileParser;
+use proptest::prelude::*;
+
+const FRAGMENTS: &[&str] = &[
+    "build:",
+    "test target=\"debug\" *args:",
+    "deploy env='prod' +flags:",
+    "a b c: d e",
+    "    echo {{target}}",
+    "    echo \"{{",
+    "    @echo }}",
+    "\t#!/usr/bin/env bash",
+    "[private]",
+    "[group('dev')]",
+    "[group(",
+    "[confirm(\"Really?\")]",
+    "# [slot: target]",
+    "# {{target}}: build target",
+    "# description",
+    "set shell := [\"bash\", \"-c\"]",
+    "x := `date`",
+    "y := if x == \"a\" { \"b\" } else { \"c\" }",
+    "import 'other.just'",
+    "mod sub",
+    "alias b := build",
+    "\"",
+    "'",
+    "{{",
+    "}}",
+    "(",
+    ":",
+    "=",
+    "",
+    "é✓",
+];
+
+fn justfile() -> impl Strategy<Value = String> {
+    prop_oneof![
+        proptest::collection::vec(proptest::sample::select(FRAGMENTS), 0..20)
+            .prop_map(|lines| lines.join("\n")),
+        any::<String>(),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn regex_parser_never_panics(content in justfile()) {
+        let parser = JustfileParser::new().unwrap();
+        let _ = parser.parse_content(&content);
+    }
+}
+
+#[cfg(feature = "ast-parser")]
+mod ast {
+    use super::justfile;
+    use just_mcp::parser::{ASTJustParser, EnhancedJustfileParser, ParserPreference};
+    use proptest::prelude::*;
+    use std::cell::RefCell;
+
+    thread_local! {
+        // Creating parsers compiles tree-sitter queries; reuse them across cases
+        static AST_PARSER: RefCell<ASTJustParser> = RefCell::new(ASTJustParser::new().unwrap());
+        static ENHANCED_PARSER: EnhancedJustfileParser =
+            EnhancedJustfileParser::new_with_preference(ParserPreference::Ast).unwrap();
+    }
+
+    proptest! {
+        #[test]
+        fn ast_parser_never_panics(content in justfile()) {
+            AST_PARSER.with_borrow_mut(|parser| {
+                if let Ok(tree) = parser.parse_content(&content) {
+                    let _ = parser.extract_recipes(&tree);
+                }
+            });
+        }
+
+        #[test]
+        fn enhanced_parser_never_panics(content in justfile()) {
+            ENHANCED_PARSER.with(|parser| {
+                let _ = parser.parse_content(&content);
+            });
+        }
+    }
+}
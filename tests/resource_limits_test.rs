@@ -1,3 +1,5 @@
+use clap::Parser;
+use just_mcp::cli::Args;
 use just_mcp::executor::TaskExecutor;
 use just_mcp::resource_limits::{ResourceLimits, ResourceManager};
 use just_mcp::security::SecurityConfig;
@@ -77,6 +79,7 @@ timeout_test:
             working_directory: Some(temp_dir.path().to_string_lossy().to_string()),
             environment: HashMap::new(),
             timeout: None, // Use default from resource limits
+            profile: None,
         },
     };
 
@@ -124,6 +127,7 @@ large_output:
             working_directory: Some(temp_dir.path().to_string_lossy().to_string()),
             environment: HashMap::new(),
             timeout: Some(5),
+            profile: None,
         },
     };
 
@@ -167,3 +171,26 @@ fn test_resource_manager_guards() {
     // Can execute again
     assert!(manager.can_execute().is_ok());
 }
+
+#[test]
+fn test_job_limits_from_cli() {
+    let args = Args::try_parse_from([
+        "just-mcp",
+        "--jobs",
+        "2",
+        "--project-jobs",
+        "api=4",
+        "--group-jobs",
+        "build=8",
+        "--max-jobs",
+        "6",
+    ])
+    .unwrap();
+    let limits = args.resource_limits();
+    assert_eq!(limits.max_jobs, 6);
+    assert_eq!(limits.requested_jobs("web", None, None), Some(2));
+    assert_eq!(limits.requested_jobs("api", Some("build"), None), Some(8));
+
+    assert!(Args::try_parse_from(["just-mcp", "--group-jobs", "build=0"]).is_err());
+    assert!(Args::try_parse_from(["just-mcp", "--project-jobs", "api"]).is_err());
+}
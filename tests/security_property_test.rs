@@ -0,0 +1,124 @@
+//! Property-based tests for parameter sanitization and path validation
+//!
+//! Complements `security_test.rs` with generated inputs: shell
+//! metacharacters spliced into otherwise harmless values, arbitrary
+//! Unicode, and paths mixing `..`, `~` and symlinks that escape the
+//! allowed directory.
+
+use just_mcp::security::{SecurityConfig, SecurityValidator};
+use proptest::prelude::*;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Fragments an attacker would use to break out of a parameter
+const INJECTIONS: &[&str] = &[
+    ";", "&", "|", "&&", "||", "$(", "`", "${HOME}", "../", "..\\",
+];
+
+/// Run `printf %s <escaped>` through `sh` and return what it printed
+fn shell_round_trip(escaped: &str) -> String {
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("printf %s {escaped}"))
+        .output()
+        .expect("sh is available");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Canonical location `validate_path` checks: the path, or its parent if
+/// the path doesn't exist
+fn resolved(path: &Path) -> Option<PathBuf> {
+    path.canonicalize().ok().or_else(|| {
+        let parent = path.parent()?.canonicalize().ok()?;
+        Some(parent.join(path.file_name()?))
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn sanitized_parameters_reach_the_shell_unchanged(value in "[^\0]{0,40}") {
+        let validator = SecurityValidator::with_default();
+        let escaped = validator.sanitize_parameter(&value);
+        prop_assert_eq!(shell_round_trip(&escaped), value);
+    }
+}
+
+proptest! {
+    #[test]
+    fn sanitized_parameters_are_quoted_or_inert(value in any::<String>()) {
+        let validator = SecurityValidator::with_default();
+        let escaped = validator.sanitize_parameter(&value);
+        let inert = !escaped.is_empty()
+            && escaped
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_=/,.+".contains(c));
+        prop_assert!(
+            inert || (escaped.starts_with('\'') && escaped.ends_with('\'')),
+            "unquoted metacharacters in {:?}",
+            escaped
+        );
+    }
+
+    #[test]
+    fn injected_metacharacters_are_rejected(
+        prefix in "[a-zA-Z0-9 ._-]{0,20}",
+        injection in proptest::sample::select(INJECTIONS),
+        suffix in "[a-zA-Z0-9 ._-]{0,20}",
+    ) {
+        let validator = SecurityValidator::with_default();
+        let value = format!("{prefix}{injection}{suffix}");
+        prop_assert!(validator.validate_parameter("value", &value).is_err(), "accepted {:?}", value);
+    }
+
+    #[test]
+    fn parameter_validation_never_panics(name in any::<String>(), value in any::<String>()) {
+        let validator = SecurityValidator::with_default();
+        let _ = validator.validate_parameter(&name, &value);
+        let _ = validator.validate_task_name(&value);
+    }
+
+    #[test]
+    fn sanitized_parameter_names_match_mcp_pattern(name in any::<String>()) {
+        let validator = SecurityValidator::with_default();
+        let sanitized = validator.sanitize_parameter_name(&name);
+        let pattern = regex::Regex::new(r"^[a-zA-Z0-9_][a-zA-Z0-9_.-]{0,63}$").unwrap();
+        prop_assert!(pattern.is_match(&sanitized), "{:?} -> {:?}", name, sanitized);
+    }
+
+    #[test]
+    fn validated_paths_stay_inside_allowed_directory(
+        segments in proptest::collection::vec(
+            proptest::sample::select(&["src", "..", ".", "~", "escape", "justfile"][..]),
+            1..6,
+        )
+    ) {
+        let root = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        std::fs::create_dir(root.path().join("src")).unwrap();
+        std::fs::write(root.path().join("justfile"), "").unwrap();
+        std::fs::write(outside.path().join("justfile"), "").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(outside.path(), root.path().join("escape")).unwrap();
+
+        let validator = SecurityValidator::new(SecurityConfig {
+            allowed_paths: vec![root.path().to_path_buf()],
+            ..Default::default()
+        });
+        let path = segments
+            .iter()
+            .fold(root.path().to_path_buf(), |path, segment| path.join(segment));
+
+        if validator.validate_path(&path).is_ok() {
+            let resolved = resolved(&path).expect("validated paths resolve");
+            prop_assert!(
+                resolved.starts_with(root.path().canonicalize().unwrap()),
+                "{} escapes to {}",
+                path.display(),
+                resolved.display()
+            );
+            prop_assert!(!segments.contains(&".."), "accepted traversal {}", path.display());
+        }
+    }
+}
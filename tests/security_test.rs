@@ -37,6 +37,7 @@ test name:
             working_directory: Some(temp_dir.path().to_string_lossy().to_string()),
             environment: HashMap::new(),
             timeout: Some(5),
+            profile: None,
         },
     };
 
@@ -64,6 +65,7 @@ async fn test_security_rejects_path_traversal() {
             working_directory: None,
             environment: HashMap::new(),
             timeout: Some(5),
+            profile: None,
         },
     };
 
@@ -103,6 +105,7 @@ test:
             working_directory: Some(temp_dir.path().to_string_lossy().to_string()),
             environment: HashMap::new(),
             timeout: Some(5),
+            profile: None,
         },
     };
 
@@ -144,6 +147,7 @@ test name:
             working_directory: Some(temp_dir.path().to_string_lossy().to_string()),
             environment: HashMap::new(),
             timeout: Some(5),
+            profile: None,
         },
     };
 
@@ -167,10 +171,12 @@ echo_param value:
     fs::write(&justfile_path, content).unwrap();
 
     // Create custom security config that allows the command patterns we're testing
-    let mut security_config = SecurityConfig::default();
-    security_config.allowed_paths = vec![temp_dir.path().to_path_buf()];
-    security_config.strict_mode = false; // Disable strict mode to allow the patterns through validation
-    security_config.forbidden_patterns = vec![]; // Clear forbidden patterns for this test
+    let security_config = SecurityConfig {
+        allowed_paths: vec![temp_dir.path().to_path_buf()],
+        strict_mode: false, // Disable strict mode to allow the patterns through validation
+        forbidden_patterns: vec![], // Clear forbidden patterns for this test
+        ..Default::default()
+    };
 
     let mut executor = TaskExecutor::new().with_security_config(security_config);
 
@@ -185,6 +191,7 @@ echo_param value:
             working_directory: Some(temp_dir.path().to_string_lossy().to_string()),
             environment: HashMap::new(),
             timeout: Some(5),
+            profile: None,
         },
     };
 
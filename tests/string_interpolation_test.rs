@@ -313,8 +313,8 @@ mod string_interpolation_tests {
         variables.insert("y".to_string(), "3".to_string());
         variables.insert("name".to_string(), "test".to_string());
 
-        let mut functions: HashMap<String, fn(&[String]) -> Result<String, String>> =
-            HashMap::new();
+        type ExprFn = fn(&[String]) -> Result<String, String>;
+        let mut functions: HashMap<String, ExprFn> = HashMap::new();
         functions.insert("add".to_string(), |args: &[String]| {
             if args.len() != 2 {
                 return Err("add requires 2 arguments".to_string());
@@ -506,6 +506,7 @@ Environment: {{env}}
 #[cfg(not(feature = "ast-parser"))]
 mod no_ast_parser_tests {
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_string_interpolation_feature_gated() {
         // This test verifies that when the ast-parser feature is not enabled,
         // the code compiles correctly without the tree-sitter dependencies.
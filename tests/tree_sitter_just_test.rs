@@ -123,6 +123,7 @@ test: build
 mod no_ast_parser_tests {
     /// Test that ensures AST parser functionality is properly gated behind feature flag
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_ast_parser_feature_gated() {
         // This test verifies that when the ast-parser feature is not enabled,
         // the code compiles correctly without the tree-sitter dependencies.
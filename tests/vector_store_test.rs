@@ -7,7 +7,7 @@
 #[cfg(feature = "vector-search")]
 mod vector_store_tests {
     use anyhow::Result;
-    use just_mcp::vector_search::{Document, LibSqlVectorStore, VectorStore};
+    use just_mcp::vector_search::{Document, LibSqlVectorStore, SearchFilters, VectorStore};
     use std::collections::HashMap;
     use tempfile::TempDir;
 
@@ -429,4 +429,81 @@ mod vector_store_tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_search_with_structured_filters() -> Result<()> {
+        let (mut store, _temp_dir) = create_test_store().await?;
+
+        let recipes = [
+            ("deploy_backend", "backend", "ops", "true", "false"),
+            ("deploy_frontend", "frontend", "ops", "true", "false"),
+            ("build_backend", "backend", "dev", "false", "false"),
+            ("helper_backend", "backend", "ops", "false", "true"),
+        ];
+
+        for (i, (id, project, group, has_params, private)) in recipes.iter().enumerate() {
+            let document = create_test_document(id, &format!("{id} recipe"))
+                .with_metadata("project".to_string(), project.to_string())
+                .with_metadata("group".to_string(), group.to_string())
+                .with_metadata("has_params".to_string(), has_params.to_string())
+                .with_metadata("private".to_string(), private.to_string());
+            store
+                .add_document(document, create_test_embedding(384, 0.1 + i as f32 * 0.01))
+                .await?;
+        }
+
+        let query = create_test_embedding(384, 0.1);
+
+        // Threshold of -1.0 accepts every similarity so only filters narrow results
+
+        // No filters behaves like a plain search
+        let all = store
+            .search_with_filters(query.clone(), &SearchFilters::new(), 10, -1.0)
+            .await?;
+        assert_eq!(all.len(), 4);
+
+        // Project filter
+        let backend = store
+            .search_with_filters(
+                query.clone(),
+                &SearchFilters::new().with_project("backend"),
+                10,
+                -1.0,
+            )
+            .await?;
+        assert_eq!(backend.len(), 3);
+        assert!(backend
+            .iter()
+            .all(|r| r.document.metadata.get("project") == Some(&"backend".to_string())));
+
+        // Combined filters narrow to a single recipe
+        let filters = SearchFilters::new()
+            .with_project("backend")
+            .with_group("ops")
+            .with_has_params(true)
+            .with_private(false);
+        let results = store
+            .search_with_filters(query.clone(), &filters, 10, -1.0)
+            .await?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document.id, "deploy_backend");
+        assert!(filters.matches(&results[0].document));
+
+        // Private filter
+        let private = store
+            .search_with_filters(query, &SearchFilters::new().with_private(true), 10, -1.0)
+            .await?;
+        assert_eq!(private.len(), 1);
+        assert_eq!(private[0].document.id, "helper_backend");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_conditions_escape_values() {
+        let filters = SearchFilters::new().with_project("o'brien");
+        let sql = LibSqlVectorStore::build_filter_conditions(&filters);
+        assert!(sql.contains("'o''brien'"));
+        assert!(LibSqlVectorStore::build_filter_conditions(&SearchFilters::new()).is_empty());
+    }
 }
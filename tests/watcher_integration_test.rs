@@ -1,3 +1,5 @@
+#![cfg(feature = "watcher")]
+
 use just_mcp::registry::ToolRegistry;
 use just_mcp::watcher::JustfileWatcher;
 use std::fs;